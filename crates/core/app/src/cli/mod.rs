@@ -1,14 +1,35 @@
 use std::env;
 
+use crate::{ColorChoice, PlanOutputFormat, ProgressMode};
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AppArgs {
     pub command: AppCommand,
     pub build: String,
     pub preset: Option<String>,
+    // Config profile for `--profile`; `gaia clean` reads that flag as its clean profile instead.
+    pub profile: Option<String>,
     pub env_files: Vec<String>,
     pub env_overrides: Vec<(String, String)>,
     pub explicit_overrides: Vec<(String, String)>,
+    pub resolve: ResolveArgs,
     pub clean: CleanArgs,
+    pub plan: PlanArgs,
+    pub run: RunArgs,
+    pub init: InitArgs,
+    pub providers: ProvidersArgs,
+    pub exec: ExecArgs,
+    pub tui: TuiArgs,
+    pub lint: LintArgs,
+    pub color: ColorChoice,
+    // Flag values the parser could not accept; dispatch refuses to run any command while set.
+    pub usage_errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResolveArgs {
+    pub trace: Option<String>,
+    pub show_inputs: bool,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
@@ -19,6 +40,65 @@ pub struct CleanArgs {
     pub dry_run: bool,
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlanArgs {
+    pub lint: bool,
+    pub explain_skip: bool,
+    pub explain_checkpoints: bool,
+    pub dump_plan: Option<String>,
+    pub output_format: PlanOutputFormat,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunArgs {
+    pub resume: bool,
+    pub from: Option<String>,
+    pub phases: Vec<String>,
+    pub skip_phases: Vec<String>,
+    pub dry_run: bool,
+    pub json_summary: bool,
+    pub quiet: bool,
+    pub dump_plan: Option<String>,
+    // Builds named after the first one; each runs in turn once the previous one finishes.
+    pub extra_builds: Vec<String>,
+    pub continue_on_error: bool,
+    // Treat validation warnings as errors for this run.
+    pub strict: bool,
+    pub progress: ProgressMode,
+    // Log every command operations start, redacted, before starting it.
+    pub trace_exec: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InitArgs {
+    pub template: Option<String>,
+    pub list_templates: bool,
+    pub force: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProvidersArgs {
+    pub json: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecArgs {
+    // Everything after `--`, program first.
+    pub command: Vec<String>,
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TuiArgs {
+    pub build: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LintArgs {
+    // Treat lint warnings as errors, failing the command on any finding.
+    pub strict: bool,
+}
+
 impl AppArgs {
     pub fn from_env() -> Self {
         Self::parse_from(env::args().skip(1))
@@ -29,7 +109,7 @@ impl AppArgs {
         I: IntoIterator<Item = S>,
         S: Into<String>,
     {
-        let mut args = args.into_iter().map(Into::into);
+        let mut args = args.into_iter().map(Into::into).peekable();
         let Some(first) = args.next() else {
             return Self::default();
         };
@@ -38,95 +118,105 @@ impl AppArgs {
             "-h" | "--help" | "help" => Self {
                 command: AppCommand::Help,
                 build: String::new(),
-                preset: None,
-                env_files: Vec::new(),
-                env_overrides: Vec::new(),
-                explicit_overrides: Vec::new(),
-                clean: CleanArgs::default(),
+                ..Self::default()
             },
             "-V" | "--version" | "version" => Self {
                 command: AppCommand::Version,
                 build: String::new(),
-                preset: None,
-                env_files: Vec::new(),
-                env_overrides: Vec::new(),
-                explicit_overrides: Vec::new(),
-                clean: CleanArgs::default(),
+                ..Self::default()
             },
             "resolve" => Self {
                 command: AppCommand::Resolve,
                 build: args
                     .next()
                     .unwrap_or_else(|| "examples/default-workspace/configs/default.toml".into()),
-                preset: None,
-                env_files: Vec::new(),
-                env_overrides: Vec::new(),
-                explicit_overrides: Vec::new(),
-                clean: CleanArgs::default(),
+                ..Self::default()
             },
             "tui" => Self {
                 command: AppCommand::Tui,
                 build: args
-                    .next()
+                    .next_if(|arg: &String| !arg.starts_with("--"))
                     .unwrap_or_else(|| "examples/default-workspace/configs/default.toml".into()),
-                preset: None,
-                env_files: Vec::new(),
-                env_overrides: Vec::new(),
-                explicit_overrides: Vec::new(),
-                clean: CleanArgs::default(),
+                ..Self::default()
             },
             "validate" => Self {
                 command: AppCommand::Validate,
                 build: args
                     .next()
                     .unwrap_or_else(|| "examples/default-workspace/configs/default.toml".into()),
-                preset: None,
-                env_files: Vec::new(),
-                env_overrides: Vec::new(),
-                explicit_overrides: Vec::new(),
-                clean: CleanArgs::default(),
+                ..Self::default()
+            },
+            "lint" => Self {
+                command: AppCommand::Lint,
+                build: args
+                    .next_if(|arg: &String| !arg.starts_with("--"))
+                    .unwrap_or_else(|| "examples/default-workspace/configs/default.toml".into()),
+                ..Self::default()
+            },
+            "inputs" => Self {
+                command: AppCommand::Inputs,
+                build: args
+                    .next()
+                    .unwrap_or_else(|| "examples/default-workspace/configs/default.toml".into()),
+                ..Self::default()
+            },
+            "providers" => Self {
+                command: AppCommand::Providers,
+                build: args
+                    .next()
+                    .unwrap_or_else(|| "examples/default-workspace/configs/default.toml".into()),
+                ..Self::default()
+            },
+            "doctor" => Self {
+                command: AppCommand::Doctor,
+                build: args
+                    .next()
+                    .unwrap_or_else(|| "examples/default-workspace/configs/default.toml".into()),
+                ..Self::default()
+            },
+            "exec" => Self {
+                command: AppCommand::Exec,
+                build: args
+                    .next_if(|arg: &String| !arg.starts_with("--"))
+                    .unwrap_or_else(|| "examples/default-workspace/configs/default.toml".into()),
+                ..Self::default()
             },
             "plan" => Self {
                 command: AppCommand::Plan,
                 build: args
                     .next()
                     .unwrap_or_else(|| "examples/default-workspace/configs/default.toml".into()),
-                preset: None,
-                env_files: Vec::new(),
-                env_overrides: Vec::new(),
-                explicit_overrides: Vec::new(),
-                clean: CleanArgs::default(),
+                ..Self::default()
             },
             "clean" => Self {
                 command: AppCommand::Clean,
                 build: args
                     .next()
                     .unwrap_or_else(|| "examples/default-workspace/configs/default.toml".into()),
-                preset: None,
-                env_files: Vec::new(),
-                env_overrides: Vec::new(),
-                explicit_overrides: Vec::new(),
-                clean: CleanArgs::default(),
+                ..Self::default()
+            },
+            "init" => Self {
+                command: AppCommand::Init,
+                build: args
+                    .next_if(|arg: &String| !arg.starts_with("--"))
+                    .unwrap_or_else(|| ".".into()),
+                ..Self::default()
+            },
+            "schema" => Self {
+                command: AppCommand::Schema,
+                build: String::new(),
+                ..Self::default()
             },
             "run" => Self {
                 command: AppCommand::Run,
                 build: args
                     .next()
                     .unwrap_or_else(|| "examples/default-workspace/configs/default.toml".into()),
-                preset: None,
-                env_files: Vec::new(),
-                env_overrides: Vec::new(),
-                explicit_overrides: Vec::new(),
-                clean: CleanArgs::default(),
+                ..Self::default()
             },
             build => Self {
-                command: AppCommand::Run,
                 build: build.into(),
-                preset: None,
-                env_files: Vec::new(),
-                env_overrides: Vec::new(),
-                explicit_overrides: Vec::new(),
-                clean: CleanArgs::default(),
+                ..Self::default()
             },
         };
 
@@ -158,6 +248,9 @@ impl AppArgs {
                             .push((key.to_string(), raw_value.to_string()));
                     }
                 }
+                "--profile" if parsed.command != AppCommand::Clean => {
+                    parsed.profile = args.next();
+                }
                 "--profile" | "--clean-profile" => {
                     parsed.clean.profile = args.next();
                 }
@@ -173,6 +266,130 @@ impl AppArgs {
                 }
                 "--dry-run" => {
                     parsed.clean.dry_run = true;
+                    parsed.run.dry_run = true;
+                    parsed.exec.dry_run = true;
+                }
+                "--trace" => {
+                    parsed.resolve.trace = args.next();
+                }
+                "--show-inputs" => {
+                    parsed.resolve.show_inputs = true;
+                }
+                "--lint" => {
+                    parsed.plan.lint = true;
+                }
+                "--explain-skip" => {
+                    parsed.plan.explain_skip = true;
+                }
+                "--explain-checkpoints" => {
+                    parsed.plan.explain_checkpoints = true;
+                }
+                "--dump-plan" => {
+                    let path = args.next();
+                    parsed.plan.dump_plan.clone_from(&path);
+                    parsed.run.dump_plan = path;
+                }
+                "--output-format" => {
+                    let value = args.next();
+                    if let Some(format) = parsed.choice("--output-format", value.as_deref()) {
+                        parsed.plan.output_format = format;
+                    }
+                }
+                other if other.starts_with("--output-format=") => {
+                    let value = &other["--output-format=".len()..];
+                    if let Some(format) = parsed.choice("--output-format", Some(value)) {
+                        parsed.plan.output_format = format;
+                    }
+                }
+                "--progress" => {
+                    let value = args.next();
+                    if let Some(mode) = parsed.choice("--progress", value.as_deref()) {
+                        parsed.run.progress = mode;
+                    }
+                }
+                other if other.starts_with("--progress=") => {
+                    let value = &other["--progress=".len()..];
+                    if let Some(mode) = parsed.choice("--progress", Some(value)) {
+                        parsed.run.progress = mode;
+                    }
+                }
+                "--template" => {
+                    parsed.init.template = args.next();
+                }
+                "--list-templates" => {
+                    parsed.init.list_templates = true;
+                }
+                "--force" => {
+                    parsed.init.force = true;
+                }
+                "--build" => {
+                    parsed.tui.build = args.next();
+                }
+                "--resume" => {
+                    parsed.run.resume = true;
+                }
+                "--from" => {
+                    parsed.run.from = args.next();
+                }
+                "--phases" => {
+                    if let Some(value) = args.next() {
+                        parsed.run.phases.push(value);
+                    }
+                }
+                "--skip-phases" => {
+                    if let Some(value) = args.next() {
+                        parsed.run.skip_phases.push(value);
+                    }
+                }
+                "--" => {
+                    parsed.exec.command = args.by_ref().collect();
+                }
+                "--json" => {
+                    parsed.providers.json = true;
+                }
+                "--json-summary" => {
+                    parsed.run.json_summary = true;
+                }
+                "--continue-on-error" => {
+                    parsed.run.continue_on_error = true;
+                }
+                "--strict" => {
+                    parsed.run.strict = true;
+                    parsed.lint.strict = true;
+                }
+                "--trace-exec" => {
+                    parsed.run.trace_exec = true;
+                }
+                "--quiet" | "-q" => {
+                    parsed.run.quiet = true;
+                }
+                "--no-fail-fast" | "--fail-fast=false" => {
+                    parsed
+                        .explicit_overrides
+                        .push(("policy.failure.fail_fast".into(), "false".into()));
+                }
+                "--fail-fast" | "--fail-fast=true" => {
+                    parsed
+                        .explicit_overrides
+                        .push(("policy.failure.fail_fast".into(), "true".into()));
+                }
+                "--no-color" => {
+                    parsed.color = ColorChoice::Never;
+                }
+                "--color" => {
+                    let value = args.next();
+                    if let Some(choice) = parsed.choice("--color", value.as_deref()) {
+                        parsed.color = choice;
+                    }
+                }
+                other if other.starts_with("--color=") => {
+                    let value = &other["--color=".len()..];
+                    if let Some(choice) = parsed.choice("--color", Some(value)) {
+                        parsed.color = choice;
+                    }
+                }
+                other if parsed.command == AppCommand::Run && !other.starts_with('-') => {
+                    parsed.run.extra_builds.push(other.into());
                 }
                 _ => {}
             }
@@ -180,6 +397,47 @@ impl AppArgs {
 
         parsed
     }
+
+    // Scripts depend on these flags, so a missing or mistyped value is an error rather than a
+    // silent fallback to the default.
+    fn choice<T: FlagChoice>(&mut self, flag: &str, value: Option<&str>) -> Option<T> {
+        let parsed = value.and_then(T::parse_choice);
+        if parsed.is_none() {
+            self.usage_errors.push(format!(
+                "invalid value {} for {flag}; expected one of: {}",
+                value.map_or_else(|| "(none)".to_string(), |value| format!("'{value}'")),
+                T::CHOICES.join(", ")
+            ));
+        }
+        parsed
+    }
+}
+
+// A flag that takes one of a fixed set of values.
+pub(crate) trait FlagChoice: Sized {
+    const CHOICES: &'static [&'static str];
+    fn parse_choice(value: &str) -> Option<Self>;
+}
+
+impl FlagChoice for PlanOutputFormat {
+    const CHOICES: &'static [&'static str] = &["table", "json", "csv"];
+    fn parse_choice(value: &str) -> Option<Self> {
+        Self::parse(value)
+    }
+}
+
+impl FlagChoice for ProgressMode {
+    const CHOICES: &'static [&'static str] = &["full", "compact"];
+    fn parse_choice(value: &str) -> Option<Self> {
+        Self::parse(value)
+    }
+}
+
+impl FlagChoice for ColorChoice {
+    const CHOICES: &'static [&'static str] = &["auto", "always", "never"];
+    fn parse_choice(value: &str) -> Option<Self> {
+        Self::parse(value)
+    }
 }
 
 impl Default for AppArgs {
@@ -188,10 +446,21 @@ impl Default for AppArgs {
             command: AppCommand::Run,
             build: "examples/default-workspace/configs/default.toml".into(),
             preset: None,
+            profile: None,
             env_files: Vec::new(),
             env_overrides: Vec::new(),
             explicit_overrides: Vec::new(),
+            resolve: ResolveArgs::default(),
             clean: CleanArgs::default(),
+            plan: PlanArgs::default(),
+            run: RunArgs::default(),
+            init: InitArgs::default(),
+            providers: ProvidersArgs::default(),
+            exec: ExecArgs::default(),
+            tui: TuiArgs::default(),
+            lint: LintArgs::default(),
+            color: ColorChoice::default(),
+            usage_errors: Vec::new(),
         }
     }
 }
@@ -201,9 +470,16 @@ pub enum AppCommand {
     Help,
     Version,
     Resolve,
+    Inputs,
     Tui,
     Validate,
+    Lint,
     Plan,
     Clean,
+    Init,
+    Schema,
+    Providers,
+    Doctor,
+    Exec,
     Run,
 }
@@ -1,5 +1,5 @@
 use gaia_config::{ResolveOptions, try_resolve_config_with_options};
-use gaia_spec::{CleanProfileSpec, ResolvedBuildSpec};
+use gaia_spec::{CleanProfileSpec, ImageDefinition, ResolvedBuildSpec};
 use std::collections::BTreeSet;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -122,6 +122,9 @@ fn append_profile(
     if profile.out {
         paths.push(PathBuf::from(&spec.workspace.out_dir));
     }
+    if profile.cache {
+        paths.extend(cache_paths(spec));
+    }
     for path in &profile.paths {
         paths.push(spec.workspace.resolve_path(path).map_err(|error| {
             format!(
@@ -145,6 +148,7 @@ fn append_target_paths(
             paths.push(PathBuf::from(&spec.workspace.build_dir));
             paths.push(PathBuf::from(&spec.workspace.out_dir));
         }
+        "cache" => paths.extend(cache_paths(spec)),
         "configured" => {
             let Some(profile_name) = spec.clean.default_profile.as_deref() else {
                 return Err(format!(
@@ -164,6 +168,17 @@ fn append_target_paths(
     Ok(())
 }
 
+// Caches Gaia fills for the build: the home and cache dirs mounted into docker-run commands, and the
+// buildroot ccache dir when ccache is on. Build and out dirs stay unless the ccache lives in one.
+fn cache_paths(spec: &ResolvedBuildSpec) -> Vec<PathBuf> {
+    let gaia_dir = Path::new(&spec.workspace.root_dir).join(".gaia");
+    let mut paths = vec![gaia_dir.join("docker-home"), gaia_dir.join("docker-cache")];
+    if let ImageDefinition::Buildroot(buildroot) = &spec.image.definition {
+        paths.extend(buildroot.ccache_dir(&spec.workspace));
+    }
+    paths
+}
+
 fn dedupe_paths(paths: Vec<PathBuf>) -> Vec<PathBuf> {
     let mut seen = BTreeSet::new();
     let mut deduped = Vec::new();
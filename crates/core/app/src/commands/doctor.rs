@@ -0,0 +1,226 @@
+use gaia_config::{ResolveOptions, try_resolve_config_with_options};
+use gaia_spec::{ImageProviderKind, ResolvedBuildSpec};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::AppContext;
+
+use super::CommandOutcome;
+
+const GIB: u64 = 1024 * 1024 * 1024;
+// Buildroot downloads and builds a full toolchain; other builds mostly copy outputs around.
+const BUILDROOT_FREE_SPACE_BYTES: u64 = 20 * GIB;
+const DEFAULT_FREE_SPACE_BYTES: u64 = 2 * GIB;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DoctorStatus {
+    Pass,
+    // Worth fixing, but not enough on its own to fail the command.
+    Warn,
+    Fail,
+}
+
+impl DoctorStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pass => "ok",
+            Self::Warn => "warn",
+            Self::Fail => "FAIL",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DoctorCheck {
+    pub name: String,
+    pub status: DoctorStatus,
+    pub detail: String,
+}
+
+pub fn doctor_command(
+    context: &AppContext,
+    build: &str,
+    options: &ResolveOptions,
+) -> CommandOutcome {
+    let spec = match try_resolve_config_with_options(build, options) {
+        Ok(spec) => spec,
+        Err(error) => {
+            return CommandOutcome::Failed {
+                message: error.to_string(),
+            };
+        }
+    };
+    let mut checks = host_tool_checks(context, &spec);
+    for (label, dir) in [
+        ("root_dir", &spec.workspace.root_dir),
+        ("build_dir", &spec.workspace.build_dir),
+        ("out_dir", &spec.workspace.out_dir),
+    ] {
+        checks.push(writable_dir_check(label, Path::new(dir)));
+    }
+    checks.push(free_space_check(&spec));
+    CommandOutcome::Doctor { spec, checks }
+}
+
+// Only providers the build actually uses contribute tools, so a Go toolchain is not demanded of
+// a build without Go artifacts. Required tools are collected first, so a tool one provider needs
+// and another only recommends is checked once, as required.
+fn host_tool_checks(context: &AppContext, spec: &ResolvedBuildSpec) -> Vec<DoctorCheck> {
+    let mut required = Vec::new();
+    let mut optional = Vec::new();
+    for provider in context.source_catalog.providers() {
+        if spec
+            .sources
+            .iter()
+            .any(|source| source.provider_kind() == provider.kind())
+        {
+            required.push((provider.id(), provider.host_tools(spec)));
+            optional.push((provider.id(), provider.optional_host_tools(spec)));
+        }
+    }
+    for provider in context.artifact_catalog.providers() {
+        if spec
+            .artifacts
+            .iter()
+            .any(|artifact| artifact.provider_kind() == provider.kind())
+        {
+            required.push((provider.id(), provider.host_tools(spec)));
+            optional.push((provider.id(), provider.optional_host_tools(spec)));
+        }
+    }
+    for provider in context.image_catalog.providers() {
+        if spec.image.provider_kind() == provider.kind() {
+            required.push((provider.id(), provider.host_tools(spec)));
+            optional.push((provider.id(), provider.optional_host_tools(spec)));
+        }
+    }
+
+    let mut tools: Vec<(&'static str, &'static str, bool)> = Vec::new();
+    let tagged = |entries: Vec<(&'static str, Vec<&'static str>)>, is_required: bool| {
+        entries
+            .into_iter()
+            .flat_map(move |(provider, provider_tools)| {
+                provider_tools
+                    .into_iter()
+                    .map(move |tool| (tool, provider, is_required))
+            })
+    };
+    for (tool, provider, is_required) in tagged(required, true).chain(tagged(optional, false)) {
+        if !tools.iter().any(|(known, _, _)| *known == tool) {
+            tools.push((tool, provider, is_required));
+        }
+    }
+
+    tools
+        .into_iter()
+        .map(|(tool, provider, is_required)| {
+            let name = if is_required {
+                format!("tool {tool} ({provider})")
+            } else {
+                format!("tool {tool} ({provider}, optional)")
+            };
+            match find_on_path(tool) {
+                Some(path) => DoctorCheck {
+                    name,
+                    status: DoctorStatus::Pass,
+                    detail: path.display().to_string(),
+                },
+                None => DoctorCheck {
+                    name,
+                    status: if is_required {
+                        DoctorStatus::Fail
+                    } else {
+                        DoctorStatus::Warn
+                    },
+                    detail: "not found on PATH or in /sbin, /usr/sbin".into(),
+                },
+            }
+        })
+        .collect()
+}
+
+fn writable_dir_check(label: &str, dir: &Path) -> DoctorCheck {
+    let name = format!("workspace {label} {}", dir.display());
+    let Some(existing) = nearest_existing_dir(dir) else {
+        return DoctorCheck {
+            name,
+            status: DoctorStatus::Fail,
+            detail: "no existing parent directory".into(),
+        };
+    };
+    // Creating a probe file is the only check that also covers read-only mounts and ACLs.
+    let probe = existing.join(format!(".gaia-doctor-{}", std::process::id()));
+    match fs::write(&probe, b"") {
+        Ok(()) => {
+            let _ = fs::remove_file(&probe);
+            DoctorCheck {
+                name,
+                status: DoctorStatus::Pass,
+                detail: if existing == dir {
+                    "writable".into()
+                } else {
+                    format!("will be created under writable '{}'", existing.display())
+                },
+            }
+        }
+        Err(error) => DoctorCheck {
+            name,
+            status: DoctorStatus::Fail,
+            detail: format!("'{}' is not writable: {error}", existing.display()),
+        },
+    }
+}
+
+fn free_space_check(spec: &ResolvedBuildSpec) -> DoctorCheck {
+    let build_dir = Path::new(&spec.workspace.build_dir);
+    let name = format!("disk space {}", build_dir.display());
+    let wanted = if spec.image.provider_kind() == ImageProviderKind::Buildroot {
+        BUILDROOT_FREE_SPACE_BYTES
+    } else {
+        DEFAULT_FREE_SPACE_BYTES
+    };
+    let Some(available) = gaia_exec::available_disk_bytes(build_dir) else {
+        return DoctorCheck {
+            name,
+            status: DoctorStatus::Warn,
+            detail: "could not read free space".into(),
+        };
+    };
+    DoctorCheck {
+        name,
+        status: if available >= wanted {
+            DoctorStatus::Pass
+        } else {
+            DoctorStatus::Warn
+        },
+        detail: format!(
+            "{:.1} GiB free, {} GiB recommended",
+            available as f64 / GIB as f64,
+            wanted / GIB
+        ),
+    }
+}
+
+fn nearest_existing_dir(path: &Path) -> Option<PathBuf> {
+    let path = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        env::current_dir().ok()?.join(path)
+    };
+    path.ancestors()
+        .find(|ancestor| ancestor.is_dir())
+        .map(Path::to_path_buf)
+}
+
+// Tools such as `mkfs.ext4` or `losetup` live in the sbin directories, which a non-root `PATH`
+// often leaves out even though the build can still run them.
+const SBIN_DIRS: [&str; 2] = ["/sbin", "/usr/sbin"];
+
+fn find_on_path(program: &str) -> Option<PathBuf> {
+    let path = env::var_os("PATH").unwrap_or_default();
+    env::split_paths(&path)
+        .chain(SBIN_DIRS.into_iter().map(PathBuf::from))
+        .map(|dir| dir.join(program))
+        .find(|path| path.is_file())
+}
@@ -0,0 +1,124 @@
+use gaia_config::{ResolveOptions, try_resolve_config_with_options};
+use gaia_exec::{ExecutionCancellation, ExecutionEvent};
+use gaia_plan::OperationId;
+use gaia_process::{
+    PlannedCommand, ProcessEnvironment, ProcessLogLine, ProcessLogSink, ProcessOutputRetention,
+    ProcessRunErrorKind,
+};
+use std::fs;
+use std::io::{self, Write};
+use std::process::Command;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use crate::{ExecArgs, StdoutSink};
+
+use super::CommandOutcome;
+use super::interrupt::{InterruptGuard, watch_interrupts};
+
+// `gaia exec`: runs one command the way operations run theirs, from the workspace root, under
+// `execution.clean_env`, in its own process group, with output streamed through the sink as
+// `exec:` lines. The build and out dirs are created but never cleaned, and nothing is planned,
+// reported or recorded for resume. There is no timeout; an interrupt cancels the command.
+pub fn exec_command(
+    build: &str,
+    options: &ResolveOptions,
+    exec_args: &ExecArgs,
+    sink: &StdoutSink,
+) -> CommandOutcome {
+    let spec = match try_resolve_config_with_options(build, options) {
+        Ok(spec) => spec,
+        Err(error) => {
+            return CommandOutcome::Failed {
+                message: error.to_string(),
+            };
+        }
+    };
+    let Some((program, program_args)) = exec_args.command.split_first() else {
+        return CommandOutcome::Failed {
+            message: "gaia exec needs a command after '--', e.g. gaia exec <build> -- env".into(),
+        };
+    };
+    let mut command = Command::new(program);
+    command
+        .args(program_args)
+        .current_dir(&spec.workspace.root_dir)
+        .env("GAIA_ROOT_DIR", &spec.workspace.root_dir)
+        .env("GAIA_BUILD_DIR", &spec.workspace.build_dir)
+        .env("GAIA_OUT_DIR", &spec.workspace.out_dir);
+    ProcessEnvironment {
+        clean: spec.policy.execution.clean_env,
+        allowlist: spec.policy.execution.env_allowlist.clone(),
+        ..ProcessEnvironment::default()
+    }
+    .apply(&mut command);
+    let label = format!("command '{}'", exec_args.command.join(" "));
+    let planned = PlannedCommand::from_command(label.clone(), &command);
+    if exec_args.dry_run {
+        return CommandOutcome::Executed {
+            spec,
+            command: planned,
+            dry_run: true,
+            exit_code: 0,
+        };
+    }
+    for dir in [&spec.workspace.build_dir, &spec.workspace.out_dir] {
+        if let Err(error) = fs::create_dir_all(dir) {
+            return CommandOutcome::Failed {
+                message: format!("failed to create workspace dir '{dir}': {error}"),
+            };
+        }
+    }
+
+    let sink = sink.with_preserve_ansi(spec.policy.execution.preserve_ansi);
+    let log_sink: ProcessLogSink = Arc::new(move |line: ProcessLogLine| {
+        let event = ExecutionEvent::Log {
+            operation_id: OperationId::new("exec"),
+            message: line.line,
+        };
+        let _ = writeln!(io::stdout().lock(), "{}", sink.render_event(&event));
+    });
+    let cancellation = ExecutionCancellation::new();
+    let cancel_check = {
+        let cancellation = cancellation.clone();
+        Arc::new(move || cancellation.is_cancelled())
+    };
+    let finished = AtomicBool::new(false);
+    let interrupt_guard = InterruptGuard::install();
+    let run = thread::scope(|scope| {
+        scope.spawn(|| watch_interrupts(&cancellation, &finished, sink));
+        let run = gaia_process::run_command_with_timeout_and_retention(
+            &mut command,
+            Duration::MAX,
+            &label,
+            ProcessOutputRetention {
+                stdout_bytes: 0,
+                stderr_bytes: 0,
+                stdout_lines: 0,
+                stderr_lines: 0,
+            },
+            Some(log_sink),
+            Some(cancel_check),
+        );
+        finished.store(true, Ordering::SeqCst);
+        run
+    });
+    drop(interrupt_guard);
+
+    match run {
+        Ok(run) => CommandOutcome::Executed {
+            spec,
+            command: planned,
+            dry_run: false,
+            exit_code: run.output.status.code().unwrap_or(1),
+        },
+        Err(error) if error.kind == ProcessRunErrorKind::ToolStart => CommandOutcome::Failed {
+            message: error.message,
+        },
+        Err(error) => CommandOutcome::Failed {
+            message: format!("gaia exec: {}", error.message),
+        },
+    }
+}
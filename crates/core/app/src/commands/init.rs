@@ -0,0 +1,307 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::InitArgs;
+
+use super::CommandOutcome;
+
+pub const DEFAULT_INIT_TEMPLATE: &str = "rpi-cm5io";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InitTemplate {
+    pub name: &'static str,
+    pub description: &'static str,
+    files: &'static [(&'static str, &'static str)],
+}
+
+impl InitTemplate {
+    pub fn file_paths(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.files.iter().map(|(path, _)| *path)
+    }
+}
+
+const MOTD: &str = "Welcome to a Gaia-built image.\n";
+
+const GITIGNORE: &str = "/build/\n/out/\n/.gaia/\n";
+
+pub const INIT_TEMPLATES: &[InitTemplate] = &[
+    InitTemplate {
+        name: "rpi-cm5io",
+        description: "Buildroot image for the Raspberry Pi CM5 IO board",
+        files: &[
+            (
+                "build.toml",
+                r#"build_name = "rpi-cm5io-image"
+version = "0.1.0"
+target = "cm5"
+profile = "release"
+
+[workspace]
+root_dir = "${config.root_dir}"
+build_dir = "build"
+out_dir = "out"
+
+[[workspace.named_paths]]
+alias = "assets"
+path = "assets"
+kind = "host"
+
+[[stage.files]]
+id = "motd"
+src = "@assets/etc/motd"
+dest = "/etc/motd"
+origin = "static-asset"
+
+[image]
+kind = "buildroot"
+defconfig = "raspberrypicm5io_defconfig"
+external_tree_mode = "auto"
+
+[[image.expected_images]]
+name = "sdcard.img"
+format = "raw"
+required = true
+
+[image.output]
+collect_dir = "${workspace.out_dir}/images"
+archive_name = "${build.name}-${build.version}.img.xz"
+emit_report = true
+
+[reporting]
+summary = true
+provenance = true
+manifest = true
+"#,
+            ),
+            ("assets/etc/motd", MOTD),
+            (".gitignore", GITIGNORE),
+        ],
+    },
+    InitTemplate {
+        name: "rpi5",
+        description: "Buildroot image for the Raspberry Pi 5",
+        files: &[
+            (
+                "build.toml",
+                r#"build_name = "rpi5-image"
+version = "0.1.0"
+target = "rpi5"
+profile = "release"
+
+[workspace]
+root_dir = "${config.root_dir}"
+build_dir = "build"
+out_dir = "out"
+
+[[workspace.named_paths]]
+alias = "assets"
+path = "assets"
+kind = "host"
+
+[[stage.files]]
+id = "motd"
+src = "@assets/etc/motd"
+dest = "/etc/motd"
+origin = "static-asset"
+
+[image]
+kind = "buildroot"
+defconfig = "raspberrypi5_defconfig"
+external_tree_mode = "auto"
+
+[[image.expected_images]]
+name = "sdcard.img"
+format = "raw"
+required = true
+
+[image.output]
+collect_dir = "${workspace.out_dir}/images"
+archive_name = "${build.name}-${build.version}.img.xz"
+emit_report = true
+
+[reporting]
+summary = true
+provenance = true
+manifest = true
+"#,
+            ),
+            ("assets/etc/motd", MOTD),
+            (".gitignore", GITIGNORE),
+        ],
+    },
+    InitTemplate {
+        name: "generic-x86",
+        description: "Buildroot image for generic x86_64 EFI machines",
+        files: &[
+            (
+                "build.toml",
+                r#"build_name = "generic-x86-image"
+version = "0.1.0"
+target = "x86_64"
+profile = "release"
+
+[workspace]
+root_dir = "${config.root_dir}"
+build_dir = "build"
+out_dir = "out"
+
+[[workspace.named_paths]]
+alias = "assets"
+path = "assets"
+kind = "host"
+
+[[stage.files]]
+id = "motd"
+src = "@assets/etc/motd"
+dest = "/etc/motd"
+origin = "static-asset"
+
+[image]
+kind = "buildroot"
+defconfig = "pc_x86_64_efi_defconfig"
+external_tree_mode = "auto"
+
+[[image.expected_images]]
+name = "disk.img"
+format = "raw"
+required = true
+
+[image.output]
+collect_dir = "${workspace.out_dir}/images"
+archive_name = "${build.name}-${build.version}.img.xz"
+emit_report = true
+
+[reporting]
+summary = true
+provenance = true
+manifest = true
+"#,
+            ),
+            ("assets/etc/motd", MOTD),
+            (".gitignore", GITIGNORE),
+        ],
+    },
+    InitTemplate {
+        name: "starting-point",
+        description: "Mutate an existing root filesystem directory",
+        files: &[
+            (
+                "build.toml",
+                r#"build_name = "starting-point-image"
+version = "0.1.0"
+profile = "release"
+
+[workspace]
+root_dir = "${config.root_dir}"
+build_dir = "build"
+out_dir = "out"
+
+[[workspace.named_paths]]
+alias = "assets"
+path = "assets"
+kind = "host"
+
+[[stage.files]]
+id = "motd"
+src = "@assets/etc/motd"
+dest = "/etc/motd"
+origin = "static-asset"
+
+[image]
+kind = "starting-point"
+rootfs_path = "rootfs"
+rootfs_validation_mode = "require-directory"
+output_mode = "copy-and-archive"
+
+[image.output]
+collect_dir = "${workspace.out_dir}/images"
+archive_name = "${build.name}-${build.version}.tar"
+emit_report = true
+
+[reporting]
+summary = true
+provenance = true
+manifest = true
+"#,
+            ),
+            ("assets/etc/motd", MOTD),
+            ("rootfs/.keep", ""),
+            (".gitignore", GITIGNORE),
+        ],
+    },
+];
+
+pub fn find_init_template(name: &str) -> Option<&'static InitTemplate> {
+    INIT_TEMPLATES.iter().find(|template| template.name == name)
+}
+
+pub fn init_command(root: &str, init_args: &InitArgs) -> CommandOutcome {
+    if init_args.list_templates {
+        return CommandOutcome::InitTemplates {
+            templates: INIT_TEMPLATES.to_vec(),
+        };
+    }
+    let template_name = init_args
+        .template
+        .as_deref()
+        .unwrap_or(DEFAULT_INIT_TEMPLATE);
+    let Some(template) = find_init_template(template_name) else {
+        return CommandOutcome::Failed {
+            message: format!(
+                "unknown init template '{template_name}', available: {}",
+                INIT_TEMPLATES
+                    .iter()
+                    .map(|template| template.name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+    };
+
+    let root = PathBuf::from(root);
+    match init_scaffold_files(&root, template, init_args.force) {
+        Ok(written) => CommandOutcome::Initialized {
+            root,
+            template: template.name,
+            written,
+        },
+        Err(message) => CommandOutcome::Failed { message },
+    }
+}
+
+pub fn init_scaffold_files(
+    root: &Path,
+    template: &InitTemplate,
+    force: bool,
+) -> Result<Vec<PathBuf>, String> {
+    let targets = template
+        .files
+        .iter()
+        .map(|(relative, contents)| (root.join(relative), *contents))
+        .collect::<Vec<_>>();
+    if !force {
+        let collisions = targets
+            .iter()
+            .filter(|(path, _)| path.exists())
+            .map(|(path, _)| path.display().to_string())
+            .collect::<Vec<_>>();
+        if !collisions.is_empty() {
+            return Err(format!(
+                "refusing to overwrite existing file(s) without --force: {}",
+                collisions.join(", ")
+            ));
+        }
+    }
+
+    let mut written = Vec::new();
+    for (path, contents) in targets {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|error| format!("failed to create '{}': {error}", parent.display()))?;
+        }
+        fs::write(&path, contents)
+            .map_err(|error| format!("failed to write '{}': {error}", path.display()))?;
+        written.push(path);
+    }
+    Ok(written)
+}
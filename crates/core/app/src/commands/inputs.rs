@@ -0,0 +1,14 @@
+use gaia_config::{ResolveOptions, try_resolve_config_with_options};
+
+use super::CommandOutcome;
+
+// `gaia inputs`: the declared inputs as a reference for `--set input.<name>=<value>`. Unlike
+// `resolve --show-inputs` this describes what each input accepts rather than what it resolved to.
+pub fn inputs_command(build: &str, options: &ResolveOptions) -> CommandOutcome {
+    match try_resolve_config_with_options(build, options) {
+        Ok(spec) => CommandOutcome::Inputs { spec },
+        Err(error) => CommandOutcome::Failed {
+            message: error.to_string(),
+        },
+    }
+}
@@ -0,0 +1,126 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use gaia_exec::ExecutionCancellation;
+
+use crate::StdoutSink;
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+// Backend tools run in their own process groups, so a terminal Ctrl+C or a CI job's SIGTERM only
+// reaches Gaia. While the guard lives, the first SIGINT or SIGTERM is recorded instead of killing
+// Gaia, and `watch_interrupts` turns it into a cancellation that kills the running tools and rolls
+// back like a TUI cancel. A second signal kills the running tools' process groups from the handler
+// and exits at once, so they are not left orphaned. Signals the parent already ignores stay
+// ignored.
+pub(crate) struct InterruptGuard {
+    #[cfg(unix)]
+    previous: Vec<(libc::c_int, libc::sigaction)>,
+}
+
+impl InterruptGuard {
+    pub(crate) fn install() -> Self {
+        INTERRUPTED.store(false, Ordering::SeqCst);
+        Self {
+            #[cfg(unix)]
+            previous: [libc::SIGINT, libc::SIGTERM]
+                .into_iter()
+                .filter_map(|signal| install_handler(signal).map(|previous| (signal, previous)))
+                .collect(),
+        }
+    }
+}
+
+impl Drop for InterruptGuard {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        for (signal, previous) in &self.previous {
+            // SAFETY: restores the disposition `sigaction` reported when the guard was installed.
+            unsafe {
+                libc::sigaction(*signal, previous, std::ptr::null_mut());
+            }
+        }
+    }
+}
+
+// Whether a signal arrived since the last guard was installed.
+pub(crate) fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+// Polls for a recorded signal until `finished` is set, cancelling the run when one arrives.
+pub(crate) fn watch_interrupts(
+    cancellation: &ExecutionCancellation,
+    finished: &AtomicBool,
+    sink: StdoutSink,
+) {
+    while !finished.load(Ordering::SeqCst) {
+        if INTERRUPTED.load(Ordering::SeqCst) {
+            sink.write_warning(
+                "interrupted; cancelling running operations (interrupt again to exit at once)",
+            );
+            cancellation.cancel();
+            return;
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+}
+
+#[cfg(unix)]
+extern "C" fn record_interrupt(signal: libc::c_int) {
+    if INTERRUPTED.swap(true, Ordering::SeqCst) {
+        gaia_process::kill_running_process_groups();
+        // SAFETY: `_exit` is async-signal-safe and skips the atexit handlers that are not.
+        unsafe {
+            libc::_exit(128 + signal);
+        }
+    }
+}
+
+// Returns the replaced disposition, or `None` when the signal is ignored and left alone.
+#[cfg(unix)]
+fn install_handler(signal: libc::c_int) -> Option<libc::sigaction> {
+    // SAFETY: both structs are zero-initialized plain C data, `sigaction` only reads `action` and
+    // writes `previous`, and the handler only touches lock-free atomics and calls `kill` and
+    // `_exit`, which are async-signal-safe.
+    unsafe {
+        let mut previous = std::mem::zeroed::<libc::sigaction>();
+        if libc::sigaction(signal, std::ptr::null(), &mut previous) != 0
+            || previous.sa_sigaction == libc::SIG_IGN
+        {
+            return None;
+        }
+        let mut action = std::mem::zeroed::<libc::sigaction>();
+        action.sa_sigaction = record_interrupt as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        action.sa_flags = 0;
+        libc::sigemptyset(&mut action.sa_mask);
+        (libc::sigaction(signal, &action, std::ptr::null_mut()) == 0).then_some(previous)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::ColorChoice;
+
+    #[test]
+    fn first_sigterm_cancels_the_run_instead_of_killing_gaia() {
+        let cancellation = ExecutionCancellation::new();
+        let finished = AtomicBool::new(false);
+        let guard = InterruptGuard::install();
+
+        // SAFETY: the guard above handles SIGTERM by storing to an atomic.
+        unsafe {
+            libc::raise(libc::SIGTERM);
+        }
+        watch_interrupts(
+            &cancellation,
+            &finished,
+            StdoutSink::new(ColorChoice::Never),
+        );
+        drop(guard);
+
+        assert!(cancellation.is_cancelled());
+    }
+}
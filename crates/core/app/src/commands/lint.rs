@@ -0,0 +1,28 @@
+use gaia_config::{ResolveOptions, try_resolve_config_with_options};
+use gaia_validate::lint_spec;
+
+use crate::LintArgs;
+
+use super::CommandOutcome;
+
+// `gaia lint`: runs the convention rules over the resolved spec. It does not validate or plan, so
+// a build that lints clean can still fail `gaia validate`; findings are warnings unless `--strict`.
+pub fn lint_build_command(
+    build: &str,
+    options: &ResolveOptions,
+    lint_args: &LintArgs,
+) -> CommandOutcome {
+    let spec = match try_resolve_config_with_options(build, options) {
+        Ok(spec) => spec,
+        Err(error) => {
+            return CommandOutcome::Failed {
+                message: error.to_string(),
+            };
+        }
+    };
+    let mut lint = lint_spec(&spec);
+    if lint_args.strict {
+        lint.promote_warnings();
+    }
+    CommandOutcome::Linted { spec, lint }
+}
@@ -1,5 +1,12 @@
 mod clean;
+mod doctor;
+mod exec;
+mod init;
+mod inputs;
+mod interrupt;
+mod lint;
 mod plan;
+mod providers;
 mod resolve;
 mod run;
 mod state;
@@ -7,20 +14,33 @@ mod validate;
 
 use gaia_exec::ExecutionError;
 use gaia_exec::ExecutionOutcome;
-use gaia_plan::{ExecutionPlan, PlanDiagnostic};
+use gaia_exec::PhaseTiming;
+use gaia_exec::PlannedOperationCommands;
+use gaia_plan::{CheckpointFingerprintSources, ExecutionPlan, PlanDiagnostic};
+use gaia_process::PlannedCommand;
 use gaia_report::{ReportBundle, ReportOutputBundle};
-use gaia_spec::ResolvedBuildSpec;
+use gaia_spec::{CheckpointId, ReportingSpec, ResolvedBuildSpec};
 use gaia_validate::ValidationReport;
+use std::path::PathBuf;
 use std::time::Duration;
 
-use crate::{AppArgs, AppCommand, AppContext};
-use gaia_config::ResolveOptions;
+use crate::{AppArgs, AppCommand, AppContext, PlanOutputFormat, StdoutSink};
+use gaia_config::{ConfigTrace, ResolveOptions};
 
 pub use clean::{CleanReport, clean_build_command};
-pub use plan::plan_build_command;
+pub use doctor::{DoctorCheck, DoctorStatus, doctor_command};
+pub use exec::exec_command;
+pub use init::{DEFAULT_INIT_TEMPLATE, INIT_TEMPLATES, InitTemplate, init_command};
+pub use inputs::inputs_command;
+pub use lint::lint_build_command;
+pub use plan::{ProviderSkip, plan_build_command};
+pub use providers::{ProviderListing, providers_command};
 pub use resolve::resolve_build_command;
-pub use run::run_build_command;
-pub use state::{load_reuse_state, save_reuse_state};
+pub use run::{BatchBuild, run_build_command, run_builds_command};
+pub use state::{
+    clear_resume_state, load_resume_state, load_reuse_state, record_resume_operation,
+    reset_resume_state, save_reuse_state,
+};
 pub use validate::validate_build_command;
 
 // Keep command outcomes value-typed so tests and callers can match complete
@@ -40,20 +60,67 @@ pub enum CommandOutcome {
     },
     Resolved {
         spec: ResolvedBuildSpec,
+        show_inputs: bool,
+    },
+    Inputs {
+        spec: ResolvedBuildSpec,
+    },
+    Traced {
+        trace: ConfigTrace,
+        cli_override: Option<String>,
+        // The preset the build selects, whose overrides the trace cannot attribute to a file.
+        preset: Option<String>,
+        reporting: ReportingSpec,
     },
     Validated {
         spec: ResolvedBuildSpec,
         validation: ValidationReport,
     },
+    // Convention findings from `gaia lint`, in the validation report shape.
+    Linted {
+        spec: ResolvedBuildSpec,
+        lint: ValidationReport,
+    },
     Planned {
         spec: ResolvedBuildSpec,
         plan: ExecutionPlan,
         diagnostics: Vec<PlanDiagnostic>,
+        lints: Vec<PlanDiagnostic>,
+        skipped: Vec<ProviderSkip>,
+        checkpoint_inputs: Vec<(CheckpointId, CheckpointFingerprintSources)>,
+        output_format: PlanOutputFormat,
     },
     Cleaned {
         spec: ResolvedBuildSpec,
         report: CleanReport,
     },
+    Initialized {
+        root: PathBuf,
+        template: &'static str,
+        written: Vec<PathBuf>,
+    },
+    InitTemplates {
+        templates: Vec<InitTemplate>,
+    },
+    Schema {
+        schema: serde_json::Value,
+    },
+    Providers {
+        spec: ResolvedBuildSpec,
+        providers: Vec<ProviderListing>,
+        json: bool,
+    },
+    Doctor {
+        spec: ResolvedBuildSpec,
+        checks: Vec<DoctorCheck>,
+    },
+    // `exit_code` is the command's own status; a dry run never starts it.
+    Executed {
+        spec: ResolvedBuildSpec,
+        command: PlannedCommand,
+        dry_run: bool,
+        exit_code: i32,
+    },
     Ran {
         report: ReportBundle,
         report_outputs: ReportOutputBundle,
@@ -62,10 +129,26 @@ pub enum CommandOutcome {
         validation: ValidationReport,
         plan_diagnostics: Vec<PlanDiagnostic>,
         execution_errors: Vec<ExecutionError>,
+        phase_timings: Vec<PhaseTiming>,
+        json_summary: Option<serde_json::Value>,
+        quiet: bool,
+    },
+    // Each build's own outcome is printed as it finishes; this only carries the batch summary.
+    RanBatch {
+        builds: Vec<BatchBuild>,
+    },
+    DryRun {
+        spec: ResolvedBuildSpec,
+        operations: Vec<PlannedOperationCommands>,
     },
     Failed {
         message: String,
     },
+    // A `gaia run --json-summary` that stopped before executing; the summary still reports it.
+    RunFailed {
+        message: String,
+        json_summary: serde_json::Value,
+    },
 }
 
 pub type CommandResult = CommandOutcome;
@@ -84,31 +167,83 @@ pub struct RunArtifacts {
 }
 
 pub fn dispatch(context: &AppContext, args: AppArgs) -> CommandOutcome {
+    if !args.usage_errors.is_empty() {
+        return CommandOutcome::Failed {
+            message: args.usage_errors.join("\n"),
+        };
+    }
     match args.command {
         AppCommand::Help => CommandOutcome::Help { text: help_text() },
         AppCommand::Version => CommandOutcome::Version {
             text: version_text(),
         },
-        AppCommand::Tui => run_tui_command(context, &args.build, &resolve_options(&args)),
-        AppCommand::Resolve => resolve_build_command(&args.build, &resolve_options(&args)),
+        AppCommand::Tui => run_tui_command(
+            context,
+            &args.build,
+            args.tui.build.as_deref(),
+            &resolve_options(&args),
+        ),
+        AppCommand::Resolve => {
+            resolve_build_command(&args.build, &resolve_options(&args), &args.resolve)
+        }
+        AppCommand::Inputs => inputs_command(&args.build, &resolve_options(&args)),
         AppCommand::Validate => {
             validate_build_command(context, &args.build, &resolve_options(&args))
         }
-        AppCommand::Plan => plan_build_command(context, &args.build, &resolve_options(&args)),
+        AppCommand::Lint => lint_build_command(&args.build, &resolve_options(&args), &args.lint),
+        AppCommand::Plan => {
+            plan_build_command(context, &args.build, &resolve_options(&args), &args.plan)
+        }
         AppCommand::Clean => clean_build_command(&args.build, &resolve_options(&args), &args.clean),
-        AppCommand::Run => run_build_command(context, &args.build, &resolve_options(&args)),
+        AppCommand::Init => init_command(&args.build, &args.init),
+        AppCommand::Schema => CommandOutcome::Schema {
+            schema: gaia_config::config_schema(),
+        },
+        AppCommand::Providers => providers_command(
+            context,
+            &args.build,
+            &resolve_options(&args),
+            &args.providers,
+        ),
+        AppCommand::Doctor => doctor_command(context, &args.build, &resolve_options(&args)),
+        AppCommand::Exec => exec_command(
+            &args.build,
+            &resolve_options(&args),
+            &args.exec,
+            &StdoutSink::new(args.color),
+        ),
+        AppCommand::Run if !args.run.extra_builds.is_empty() => run_builds_command(
+            context,
+            &[vec![args.build.clone()], args.run.extra_builds.clone()].concat(),
+            &resolve_options(&args),
+            &args.run,
+            &StdoutSink::new(args.color),
+        ),
+        AppCommand::Run => run_build_command(
+            context,
+            &args.build,
+            &resolve_options(&args),
+            &args.run,
+            &StdoutSink::new(args.color),
+        ),
     }
 }
 
 #[cfg(feature = "tui")]
-fn run_tui_command(context: &AppContext, build: &str, options: &ResolveOptions) -> CommandOutcome {
-    crate::tui::run_tui_command(context, build, options)
+fn run_tui_command(
+    context: &AppContext,
+    build: &str,
+    open_build: Option<&str>,
+    options: &ResolveOptions,
+) -> CommandOutcome {
+    crate::tui::run_tui_command(context, build, open_build, options)
 }
 
 #[cfg(not(feature = "tui"))]
 fn run_tui_command(
     _context: &AppContext,
     _build: &str,
+    _open_build: Option<&str>,
     _options: &ResolveOptions,
 ) -> CommandOutcome {
     CommandOutcome::Failed {
@@ -123,19 +258,56 @@ fn help_text() -> String {
         "Usage:",
         "  gaia [run] [build-config]",
         "  gaia resolve [build-config]",
+        "  gaia resolve [build-config] --trace <path>",
+        "  gaia resolve [build-config] --show-inputs",
+        "  gaia inputs [build-config]",
         "  gaia tui [build-config]",
+        "  gaia tui --build <build-config>",
         "  gaia validate [build-config]",
+        "  gaia lint [build-config]",
+        "  gaia lint [build-config] --strict",
         "  gaia plan [build-config]",
+        "  gaia plan [build-config] --lint",
+        "  gaia plan [build-config] --explain-skip",
+        "  gaia plan [build-config] --explain-checkpoints",
+        "  gaia plan [build-config] --dump-plan <file>",
+        "  gaia plan [build-config] --output-format table|json|csv",
         "  gaia clean [build-config]",
-        "  gaia clean [build-config] --target build|out|all|configured",
+        "  gaia clean [build-config] --target build|out|all|cache|configured",
         "  gaia clean [build-config] --profile <name>",
         "  gaia clean [build-config] --path <path>",
         "  gaia clean [build-config] --dry-run",
+        "  gaia init [dir]",
+        "  gaia init [dir] --template <name>",
+        "  gaia init [dir] --force",
+        "  gaia init --list-templates",
+        "  gaia schema",
+        "  gaia providers [build-config]",
+        "  gaia providers [build-config] --json",
+        "  gaia doctor [build-config]",
+        "  gaia exec [build-config] -- <command> [args...]",
+        "  gaia exec [build-config] --dry-run -- <command> [args...]",
         "  gaia run [build-config]",
+        "  gaia run <build-config> <build-config>... [--continue-on-error]",
         "  gaia run [build-config] --preset <name>",
+        "  gaia run [build-config] --profile <name>",
         "  gaia run [build-config] --env-file <path>",
         "  gaia run [build-config] --env KEY=VALUE",
         "  gaia run [build-config] --set key=value",
+        "  gaia run [build-config] --strict",
+        "  gaia run [build-config] --progress full|compact",
+        "  gaia run [build-config] --trace-exec",
+        "  gaia run [build-config] --resume",
+        "  gaia run [build-config] --from <operation>",
+        "  gaia run [build-config] --phases <phase,...>",
+        "  gaia run [build-config] --skip-phases <phase,...>",
+        "  gaia run [build-config] --dry-run",
+        "  gaia run [build-config] --dump-plan <file>",
+        "  gaia run [build-config] --json-summary",
+        "  gaia run [build-config] --quiet",
+        "  gaia run [build-config] --no-fail-fast",
+        "  gaia run [build-config] --no-color",
+        "  gaia run [build-config] --color always|auto|never",
         "  gaia --help",
         "  gaia --version",
         "",
@@ -151,6 +323,7 @@ fn version_text() -> String {
 fn resolve_options(args: &AppArgs) -> ResolveOptions {
     ResolveOptions {
         preset: args.preset.clone(),
+        profile: args.profile.clone(),
         env_files: args.env_files.clone(),
         env_overrides: args.env_overrides.clone(),
         explicit_overrides: args.explicit_overrides.clone(),
@@ -1,15 +1,29 @@
 use gaia_config::{ResolveOptions, try_resolve_config_with_options};
-use gaia_plan::plan_build_with_reuse_state;
+use gaia_plan::{
+    ExecutionPlan, PlanDiagnostic, checkpoint_fingerprint_sources, plan_build_with_reuse_state,
+};
+use gaia_spec::ResolvedBuildSpec;
 use gaia_validate::validate_spec_with_providers;
+use std::path::Path;
 
-use crate::AppContext;
+use crate::output::write_plan_dump;
+use crate::{AppContext, PlanArgs};
 
 use super::{CommandOutcome, load_reuse_state};
 
+// A provider that contributes no operations to the plan, and why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderSkip {
+    pub domain: &'static str,
+    pub id: &'static str,
+    pub reason: String,
+}
+
 pub fn plan_build_command(
     context: &AppContext,
     build: &str,
     options: &ResolveOptions,
+    plan_args: &PlanArgs,
 ) -> CommandOutcome {
     let spec = match try_resolve_config_with_options(build, options) {
         Ok(spec) => spec,
@@ -44,9 +58,151 @@ pub fn plan_build_command(
         reuse_state.as_ref(),
     );
     let diagnostics = plan.validate();
+    if let Some(path) = &plan_args.dump_plan
+        && let Err(message) = write_plan_dump(Path::new(path), &plan, &spec.identity.build_name)
+    {
+        return CommandOutcome::Failed { message };
+    }
+    let lints = if plan_args.lint {
+        let mut lints = plan.lint();
+        lints.extend(unmatched_hook_lints(&spec, &plan));
+        lints
+    } else {
+        Vec::new()
+    };
+    let skipped = if plan_args.explain_skip {
+        provider_skips(context, &spec)
+    } else {
+        Vec::new()
+    };
+    let checkpoint_inputs = if plan_args.explain_checkpoints {
+        spec.checkpoints
+            .points
+            .iter()
+            .map(|checkpoint| {
+                (
+                    checkpoint.id.clone(),
+                    checkpoint_fingerprint_sources(&spec, checkpoint),
+                )
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
     CommandOutcome::Planned {
         spec,
         plan,
         diagnostics,
+        lints,
+        skipped,
+        checkpoint_inputs,
+        output_format: plan_args.output_format,
     }
 }
+
+fn provider_skips(context: &AppContext, spec: &ResolvedBuildSpec) -> Vec<ProviderSkip> {
+    let mut skipped = Vec::new();
+    for provider in context.source_catalog.providers() {
+        let sources = spec
+            .sources
+            .iter()
+            .filter(|source| source.provider_kind() == provider.kind())
+            .collect::<Vec<_>>();
+        let reason = if sources.is_empty() {
+            Some("no sources declared".to_string())
+        } else if sources
+            .iter()
+            .all(|source| provider.plan_source(source).is_empty())
+        {
+            Some(format!(
+                "planned no operations for {} source(s)",
+                sources.len()
+            ))
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            skipped.push(ProviderSkip {
+                domain: "source",
+                id: provider.id(),
+                reason,
+            });
+        }
+    }
+    for provider in context.artifact_catalog.providers() {
+        let artifacts = spec
+            .artifacts
+            .iter()
+            .filter(|artifact| artifact.provider_kind() == provider.kind())
+            .collect::<Vec<_>>();
+        let excluded = spec
+            .selection
+            .when_excluded_artifacts
+            .iter()
+            .filter(|(_, kind)| *kind == provider.kind())
+            .map(|(id, _)| id.as_str())
+            .collect::<Vec<_>>();
+        let reason = if artifacts.is_empty() && !excluded.is_empty() {
+            Some(format!("disabled by `when`: {}", excluded.join(", ")))
+        } else if artifacts.is_empty() {
+            Some("no artifacts declared".to_string())
+        } else if artifacts
+            .iter()
+            .all(|artifact| provider.plan_artifact(artifact).operations.is_empty())
+        {
+            Some(format!(
+                "planned no operations for {} artifact(s)",
+                artifacts.len()
+            ))
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            skipped.push(ProviderSkip {
+                domain: "artifact",
+                id: provider.id(),
+                reason,
+            });
+        }
+    }
+    for provider in context.image_catalog.providers() {
+        let reason = if spec.image.provider_kind() != provider.kind() {
+            Some(format!(
+                "image kind is {}",
+                spec.image.provider_kind().as_str()
+            ))
+        } else if provider.plan_image(&spec.image).operations.is_empty() {
+            Some("planned no operations for the image".to_string())
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            skipped.push(ProviderSkip {
+                domain: "image",
+                id: provider.id(),
+                reason,
+            });
+        }
+    }
+    skipped
+}
+
+// Hooks are keyed by operation id, so a typo or an operation this selection leaves out of the
+// plan would otherwise just never fire.
+fn unmatched_hook_lints(spec: &ResolvedBuildSpec, plan: &ExecutionPlan) -> Vec<PlanDiagnostic> {
+    spec.policy
+        .execution
+        .hooks
+        .keys()
+        .filter(|key| {
+            !plan
+                .operations
+                .iter()
+                .any(|operation| operation.id.as_str() == key.as_str())
+        })
+        .map(|key| PlanDiagnostic {
+            code: "unmatched_operation_hook",
+            message: format!("execution.hooks.\"{key}\" matches no operation in the plan"),
+        })
+        .collect()
+}
@@ -0,0 +1,113 @@
+use gaia_config::{ResolveOptions, provider_config_keys, try_resolve_config_with_options};
+use gaia_plan::{OperationKind, PLAN_PHASES};
+use gaia_spec::{ArtifactId, ResolvedBuildSpec, SourceId};
+use serde::Serialize;
+
+use crate::{AppContext, ProvidersArgs};
+
+use super::CommandOutcome;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProviderListing {
+    pub domain: &'static str,
+    pub id: &'static str,
+    pub kind: &'static str,
+    pub description: &'static str,
+    // The config keys this provider reads, led by the `kind` key that selects it.
+    pub config_keys: Vec<String>,
+    // The `run --phases` phase and plan operation kinds this provider's work runs as.
+    pub phase: &'static str,
+    pub operations: Vec<&'static str>,
+    // Whether the resolved build declares anything this provider handles.
+    pub used: bool,
+    pub supported: bool,
+}
+
+pub fn providers_command(
+    context: &AppContext,
+    build: &str,
+    options: &ResolveOptions,
+    providers_args: &ProvidersArgs,
+) -> CommandOutcome {
+    let spec = match try_resolve_config_with_options(build, options) {
+        Ok(spec) => spec,
+        Err(error) => {
+            return CommandOutcome::Failed {
+                message: error.to_string(),
+            };
+        }
+    };
+    CommandOutcome::Providers {
+        providers: provider_listings(context, &spec),
+        spec,
+        json: providers_args.json,
+    }
+}
+
+fn provider_listings(context: &AppContext, spec: &ResolvedBuildSpec) -> Vec<ProviderListing> {
+    let mut listings = Vec::new();
+    let (source_phase, source_operations) = provider_work(&[OperationKind::MaterializeSource {
+        source_id: SourceId::new(""),
+    }]);
+    for provider in context.source_catalog.providers() {
+        listings.push(ProviderListing {
+            domain: "source",
+            id: provider.id(),
+            kind: provider.kind().as_str(),
+            description: provider.describe(),
+            config_keys: provider_config_keys("sources", provider.kind().as_str()),
+            phase: source_phase,
+            operations: source_operations.clone(),
+            used: spec
+                .sources
+                .iter()
+                .any(|source| source.provider_kind() == provider.kind()),
+            supported: provider.supports(spec),
+        });
+    }
+    let (artifact_phase, artifact_operations) = provider_work(&[OperationKind::BuildArtifact {
+        artifact_id: ArtifactId::new(""),
+    }]);
+    for provider in context.artifact_catalog.providers() {
+        listings.push(ProviderListing {
+            domain: "artifact",
+            id: provider.id(),
+            kind: provider.kind().as_str(),
+            description: provider.describe(),
+            config_keys: provider_config_keys("artifacts", provider.kind().as_str()),
+            phase: artifact_phase,
+            operations: artifact_operations.clone(),
+            used: spec
+                .artifacts
+                .iter()
+                .any(|artifact| artifact.provider_kind() == provider.kind()),
+            supported: provider.supports(spec),
+        });
+    }
+    let (image_phase, image_operations) =
+        provider_work(&[OperationKind::PrepareImage, OperationKind::BuildImage]);
+    for provider in context.image_catalog.providers() {
+        listings.push(ProviderListing {
+            domain: "image",
+            id: provider.id(),
+            kind: provider.kind().as_str(),
+            description: provider.describe(),
+            config_keys: provider_config_keys("image", provider.kind().as_str()),
+            phase: image_phase,
+            operations: image_operations.clone(),
+            used: spec.image.provider_kind() == provider.kind(),
+            supported: provider.supports(spec),
+        });
+    }
+    listings
+}
+
+// The phase and operation names of the plan operations a provider domain runs as.
+fn provider_work(kinds: &[OperationKind]) -> (&'static str, Vec<&'static str>) {
+    let phase = kinds
+        .iter()
+        .filter_map(OperationKind::phase)
+        .find(|phase| PLAN_PHASES.contains(phase))
+        .unwrap_or_default();
+    (phase, kinds.iter().map(OperationKind::as_str).collect())
+}
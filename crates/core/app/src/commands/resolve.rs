@@ -1,8 +1,16 @@
-use gaia_config::{ResolveOptions, try_resolve_config_with_options};
+use gaia_config::{ResolveOptions, trace_config_path, try_resolve_config_with_options};
 
 use super::CommandOutcome;
+use crate::ResolveArgs;
 
-pub fn resolve_build_command(build: &str, options: &ResolveOptions) -> CommandOutcome {
+pub fn resolve_build_command(
+    build: &str,
+    options: &ResolveOptions,
+    resolve_args: &ResolveArgs,
+) -> CommandOutcome {
+    if let Some(path) = &resolve_args.trace {
+        return trace_build_command(build, options, path);
+    }
     let spec = match try_resolve_config_with_options(build, options) {
         Ok(spec) => spec,
         Err(error) => {
@@ -11,5 +19,33 @@ pub fn resolve_build_command(build: &str, options: &ResolveOptions) -> CommandOu
             };
         }
     };
-    CommandOutcome::Resolved { spec }
+    CommandOutcome::Resolved {
+        spec,
+        show_inputs: resolve_args.show_inputs,
+    }
+}
+
+// Masking and the selected preset come from the resolved build when it resolves; a trace of a
+// build that does not still masks with the default patterns.
+fn trace_build_command(build: &str, options: &ResolveOptions, path: &str) -> CommandOutcome {
+    let spec = try_resolve_config_with_options(build, options).ok();
+    match trace_config_path(build, path, options.profile.as_deref()) {
+        Ok(trace) => CommandOutcome::Traced {
+            trace,
+            cli_override: options
+                .explicit_overrides
+                .iter()
+                .rev()
+                .find(|(key, _)| key == path)
+                .map(|(_, value)| value.clone()),
+            preset: spec
+                .as_ref()
+                .and_then(|spec| spec.policy.preset.selected.clone())
+                .or_else(|| options.preset.clone()),
+            reporting: spec.map(|spec| spec.reporting).unwrap_or_default(),
+        },
+        Err(error) => CommandOutcome::Failed {
+            message: error.to_string(),
+        },
+    }
 }
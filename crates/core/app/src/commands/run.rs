@@ -1,6 +1,10 @@
 use gaia_config::{ResolveOptions, try_resolve_config_with_options};
-use gaia_exec::{ExecutionProviders, execute_plan};
-use gaia_plan::plan_build_with_reuse_state;
+use gaia_exec::{
+    ExecutionCancellation, ExecutionEvent, ExecutionProviders,
+    execute_plan_with_cancellation_and_observer, phase_timings, plan_operation_commands,
+    write_env_export,
+};
+use gaia_plan::{plan_build_with_reuse_state, select_plan_phases, start_plan_from};
 use gaia_process::ProcessRunErrorKind;
 use gaia_report::{generate_report, write_report_bundle};
 use gaia_validate::validate_spec_with_providers;
@@ -10,12 +14,22 @@ use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::thread;
 use std::time::Duration;
 use std::time::Instant;
 
-use crate::AppContext;
+use crate::output::{
+    LogTails, RunSummaryRecorder, json_failed_summary, json_run_summary, write_plan_dump,
+};
+use crate::{AppContext, RunArgs, StdoutSink};
 
-use super::{CommandOutcome, RunArtifacts, load_reuse_state, save_reuse_state};
+use super::interrupt::{InterruptGuard, interrupted, watch_interrupts};
+use super::{
+    CommandOutcome, RunArtifacts, clear_resume_state, load_resume_state, load_reuse_state,
+    record_resume_operation, reset_resume_state, save_reuse_state,
+};
 
 const DEFAULT_POST_BUILD_HOOK_TIMEOUT_SECONDS: u64 = 300;
 
@@ -23,32 +37,61 @@ pub fn run_build_command(
     context: &AppContext,
     build: &str,
     options: &ResolveOptions,
+    run_args: &RunArgs,
+    sink: &StdoutSink,
 ) -> CommandOutcome {
-    let run = match collect_run_artifacts(context, build, options) {
+    if run_args.dry_run {
+        return dry_run_build_command(context, build, options, run_args);
+    }
+    let started_at = Instant::now();
+    let mut recorder = RunSummaryRecorder::default();
+    let run = match collect_run_artifacts(context, build, options, run_args, sink, &mut recorder) {
         Ok(run) => run,
-        Err(message) => return CommandOutcome::Failed { message },
+        Err(message) => {
+            return refused_run(run_args, message, build, started_at.elapsed());
+        }
     };
 
     if !run.validation.errors.is_empty() {
-        return CommandOutcome::Failed {
-            message: format!(
-                "refusing to run build '{}': {} validation error(s)",
-                run.spec.identity.display_name,
-                run.validation.errors.len()
-            ),
-        };
+        let message = format!(
+            "refusing to run build '{}': {} validation error(s){}",
+            run.spec.identity.display_name,
+            run.validation.errors.len(),
+            promoted_suffix(&run.validation)
+        );
+        return refused_run(
+            run_args,
+            message,
+            &run.report.summary.build_name,
+            run.run_duration,
+        );
     }
 
     if !run.plan_diagnostics.is_empty() {
-        return CommandOutcome::Failed {
-            message: format!(
-                "refusing to run build '{}': {} plan diagnostic(s)",
-                run.spec.identity.display_name,
-                run.plan_diagnostics.len()
-            ),
-        };
+        let message = format!(
+            "refusing to run build '{}': {} plan diagnostic(s)",
+            run.spec.identity.display_name,
+            run.plan_diagnostics.len()
+        );
+        return refused_run(
+            run_args,
+            message,
+            &run.report.summary.build_name,
+            run.run_duration,
+        );
     }
 
+    let phase_timings = phase_timings(&run.plan, &run.outcome.spans);
+    let json_summary = run_args.json_summary.then(|| {
+        json_run_summary(
+            &run.report,
+            &run.report_outputs,
+            run.run_duration,
+            run.outcome.cancelled,
+            &recorder.into_results(),
+            &phase_timings,
+        )
+    });
     CommandOutcome::Ran {
         report: run.report,
         report_outputs: run.report_outputs,
@@ -57,13 +100,173 @@ pub fn run_build_command(
         validation: run.validation,
         plan_diagnostics: run.plan_diagnostics,
         execution_errors: run.outcome.errors,
+        phase_timings,
+        json_summary,
+        quiet: run_args.quiet,
+    }
+}
+
+// A run that stopped before executing still prints its JSON summary when one was requested.
+fn refused_run(
+    run_args: &RunArgs,
+    message: String,
+    build_name: &str,
+    run_duration: Duration,
+) -> CommandOutcome {
+    if run_args.json_summary {
+        CommandOutcome::RunFailed {
+            message,
+            json_summary: json_failed_summary(build_name, run_duration),
+        }
+    } else {
+        CommandOutcome::Failed { message }
+    }
+}
+
+// One build of a `gaia run a.toml b.toml ...` batch; `exit_code` is `None` for a build the batch
+// stopped before.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchBuild {
+    pub build: String,
+    pub exit_code: Option<i32>,
+}
+
+// Runs each build as its own `gaia run`, printing its outcome before the next one starts. Builds
+// share nothing but the CLI flags. A failed build stops the batch unless `--continue-on-error`
+// is set; an interrupt always does.
+pub fn run_builds_command(
+    context: &AppContext,
+    builds: &[String],
+    options: &ResolveOptions,
+    run_args: &RunArgs,
+    sink: &StdoutSink,
+) -> CommandOutcome {
+    // One dump file per invocation; later builds would overwrite the earlier plans.
+    if run_args.dump_plan.is_some() {
+        return CommandOutcome::Failed {
+            message: format!(
+                "--dump-plan writes a single plan file; run the {} builds one at a time to dump each plan",
+                builds.len()
+            ),
+        };
+    }
+    let mut results = Vec::with_capacity(builds.len());
+    let mut stopped = false;
+    for (position, build) in builds.iter().enumerate() {
+        if stopped {
+            results.push(BatchBuild {
+                build: build.clone(),
+                exit_code: None,
+            });
+            continue;
+        }
+        println!("==> build {}/{}: {build}", position + 1, builds.len());
+        let outcome = run_build_command(context, build, options, run_args, sink);
+        crate::print_outcome(&outcome);
+        let exit_code = outcome.exit_code();
+        stopped = interrupted() || (exit_code != 0 && !run_args.continue_on_error);
+        results.push(BatchBuild {
+            build: build.clone(),
+            exit_code: Some(exit_code),
+        });
     }
+    CommandOutcome::RanBatch { builds: results }
+}
+
+// Resolves, validates, and plans like a real run, then reports the commands each operation would
+// start. Nothing is executed and no resume or reuse state is written.
+fn dry_run_build_command(
+    context: &AppContext,
+    build: &str,
+    options: &ResolveOptions,
+    run_args: &RunArgs,
+) -> CommandOutcome {
+    let spec = match try_resolve_config_with_options(build, options) {
+        Ok(spec) => spec,
+        Err(error) => {
+            return CommandOutcome::Failed {
+                message: error.to_string(),
+            };
+        }
+    };
+    let mut validation = validate_spec_with_providers(
+        &spec,
+        &context.source_catalog,
+        &context.artifact_catalog,
+        &context.image_catalog,
+    );
+    if run_args.strict {
+        validation.promote_warnings();
+    }
+    if !validation.errors.is_empty() {
+        return CommandOutcome::Failed {
+            message: format!(
+                "refusing to dry-run build '{}': {} validation error(s){}",
+                spec.identity.display_name,
+                validation.errors.len(),
+                promoted_suffix(&validation)
+            ),
+        };
+    }
+    let reuse_state = run_args
+        .resume
+        .then(|| load_resume_state(&spec))
+        .flatten()
+        .or_else(|| load_reuse_state(&spec));
+    let mut plan = plan_build_with_reuse_state(
+        &spec,
+        &context.source_catalog,
+        &context.artifact_catalog,
+        &context.image_catalog,
+        reuse_state.as_ref(),
+    );
+    if let Err(diagnostic) = select_plan_phases(&mut plan, &run_args.phases, &run_args.skip_phases)
+    {
+        return CommandOutcome::Failed {
+            message: diagnostic.message,
+        };
+    }
+    if let Some(from) = &run_args.from
+        && let Err(diagnostic) = start_plan_from(&spec, &mut plan, from)
+    {
+        return CommandOutcome::Failed {
+            message: diagnostic.message,
+        };
+    }
+    let plan_diagnostics = plan.validate();
+    if !plan_diagnostics.is_empty() {
+        return CommandOutcome::Failed {
+            message: format!(
+                "refusing to dry-run build '{}': {} plan diagnostic(s)",
+                spec.identity.display_name,
+                plan_diagnostics.len()
+            ),
+        };
+    }
+    if let Some(path) = &run_args.dump_plan
+        && let Err(message) = write_plan_dump(Path::new(path), &plan, &spec.identity.build_name)
+    {
+        return CommandOutcome::Failed { message };
+    }
+    let operations = plan_operation_commands(
+        &spec,
+        &plan,
+        &ExecutionProviders {
+            source_catalog: &context.source_catalog,
+            artifact_catalog: &context.artifact_catalog,
+            image_catalog: &context.image_catalog,
+        },
+    );
+    CommandOutcome::DryRun { spec, operations }
 }
 
 fn collect_run_artifacts(
     context: &AppContext,
     build: &str,
     options: &ResolveOptions,
+    run_args: &RunArgs,
+    sink: &StdoutSink,
+    recorder: &mut RunSummaryRecorder,
 ) -> Result<RunArtifacts, String> {
     let span = tracing::info_span!("run_build", build);
     let _guard = span.enter();
@@ -75,26 +278,42 @@ fn collect_run_artifacts(
         build_name = spec.identity.build_name.as_str(),
         "resolved run build spec"
     );
-    let validation = validate_spec_with_providers(
+    let mut validation = validate_spec_with_providers(
         &spec,
         &context.source_catalog,
         &context.artifact_catalog,
         &context.image_catalog,
     );
+    if run_args.strict {
+        validation.promote_warnings();
+    }
     tracing::debug!(
         errors = validation.errors.len(),
         warnings = validation.warnings.len(),
         diagnostics = validation.diagnostics.len(),
         "validated run build spec"
     );
-    let reuse_state = load_reuse_state(&spec);
-    let plan = plan_build_with_reuse_state(
+    let reuse_state = run_args
+        .resume
+        .then(|| load_resume_state(&spec))
+        .flatten()
+        .or_else(|| load_reuse_state(&spec));
+    let mut plan = plan_build_with_reuse_state(
         &spec,
         &context.source_catalog,
         &context.artifact_catalog,
         &context.image_catalog,
         reuse_state.as_ref(),
     );
+    select_plan_phases(&mut plan, &run_args.phases, &run_args.skip_phases)
+        .map_err(|diagnostic| diagnostic.message)?;
+    if let Some(from) = &run_args.from {
+        for warning in
+            start_plan_from(&spec, &mut plan, from).map_err(|diagnostic| diagnostic.message)?
+        {
+            sink.write_warning(&warning.message);
+        }
+    }
     let plan_diagnostics = plan.validate();
     tracing::debug!(
         operations = plan.operations.len(),
@@ -120,15 +339,52 @@ fn collect_run_artifacts(
             plan_diagnostics,
         );
     }
-    let outcome = execute_plan(
-        &spec,
-        &plan,
-        ExecutionProviders {
-            source_catalog: &context.source_catalog,
-            artifact_catalog: &context.artifact_catalog,
-            image_catalog: &context.image_catalog,
-        },
-    );
+    if let Some(path) = &run_args.dump_plan {
+        write_plan_dump(Path::new(path), &plan, &spec.identity.build_name)?;
+    }
+    reset_resume_state(&spec);
+    let (event_tx, event_rx) = mpsc::channel::<ExecutionEvent>();
+    let sink = sink
+        .with_preserve_ansi(spec.policy.execution.preserve_ansi)
+        .with_fold_repeats(spec.policy.execution.fold_repeats)
+        .with_quiet(run_args.quiet)
+        .with_compact_progress(run_args.progress.compact());
+    let mut tails = LogTails::new(spec.policy.execution.output_retention.failure_tail_lines);
+    let cancellation = ExecutionCancellation::new();
+    let finished = AtomicBool::new(false);
+    let interrupt_guard = InterruptGuard::install();
+    let trace_guard = gaia_process::trace_commands(run_args.trace_exec);
+    let outcome = thread::scope(|scope| {
+        scope.spawn(|| watch_interrupts(&cancellation, &finished, sink));
+        let (resume_spec, resume_plan) = (&spec, &plan);
+        scope.spawn(move || {
+            while let Ok(event) = event_rx.recv() {
+                sink.write_event(&event, &mut tails);
+                recorder.record(&event);
+                if let ExecutionEvent::Succeeded { operation_id }
+                | ExecutionEvent::Reused { operation_id } = &event
+                {
+                    record_resume_operation(resume_spec, resume_plan, operation_id);
+                }
+            }
+            sink.finish(&mut tails);
+        });
+        let outcome = execute_plan_with_cancellation_and_observer(
+            &spec,
+            &plan,
+            ExecutionProviders {
+                source_catalog: &context.source_catalog,
+                artifact_catalog: &context.artifact_catalog,
+                image_catalog: &context.image_catalog,
+            },
+            &cancellation,
+            Some(event_tx),
+        );
+        finished.store(true, Ordering::SeqCst);
+        outcome
+    });
+    drop(trace_guard);
+    drop(interrupt_guard);
     tracing::debug!(
         completed = outcome.completed_operations,
         reused = outcome.reused_ids.len(),
@@ -138,6 +394,15 @@ fn collect_run_artifacts(
     );
     let report = generate_report(&spec, &validation, &plan, &outcome);
     let report_outputs = write_report_outputs(&spec, &report)?;
+    // The build result is settled here; nothing after this point may discard its reuse state.
+    if outcome.errors.is_empty() {
+        save_reuse_state(&spec, &plan, &outcome);
+        clear_resume_state(&spec);
+    }
+    // The env file is a convenience for scripts, so failing to write it only warns.
+    if let Err(message) = write_env_export(&spec, &outcome) {
+        sink.write_warning(&message);
+    }
     let run_duration = started_at.elapsed();
     let post_build_output = run_post_build_hook(&spec, &report, &report_outputs, run_duration)
         .map_err(|error| {
@@ -146,9 +411,6 @@ fn collect_run_artifacts(
                 spec.identity.display_name
             )
         })?;
-    if outcome.errors.is_empty() {
-        save_reuse_state(&spec, &plan, &outcome);
-    }
 
     Ok(RunArtifacts {
         spec,
@@ -163,6 +425,15 @@ fn collect_run_artifacts(
     })
 }
 
+// Names the warnings `--strict` counted among the validation errors, so a refusal that would not
+// happen without it says so.
+fn promoted_suffix(validation: &gaia_validate::ValidationReport) -> String {
+    match validation.promoted_warnings {
+        0 => String::new(),
+        promoted => format!(" ({promoted} promoted from warnings by --strict)"),
+    }
+}
+
 fn run_artifacts_without_execution(
     started_at: Instant,
     spec: gaia_spec::ResolvedBuildSpec,
@@ -207,6 +478,7 @@ fn write_report_outputs(
 #[derive(Debug, Serialize)]
 struct PostBuildPayload {
     build_name: String,
+    run_id: Option<String>,
     build_version: Option<String>,
     build_target: Option<String>,
     build_profile: Option<String>,
@@ -294,7 +566,11 @@ fn run_post_build_hook(
         .arg(&payload_path)
         .current_dir(&spec.workspace.root_dir)
         .env("GAIA_POST_BUILD_PAYLOAD", &payload_path)
-        .env("GAIA_POST_BUILD_REPORT_DIR", &report_dir);
+        .env("GAIA_POST_BUILD_REPORT_DIR", &report_dir)
+        .env(
+            "GAIA_RUN_ID",
+            report.summary.run_id.as_deref().unwrap_or_default(),
+        );
     let timeout_seconds = if hook.timeout_seconds == 0 {
         DEFAULT_POST_BUILD_HOOK_TIMEOUT_SECONDS
     } else {
@@ -380,6 +656,7 @@ fn build_post_build_payload(
         .map(|path| path.display().to_string());
     Ok(PostBuildPayload {
         build_name: report.summary.build_name.clone(),
+        run_id: report.summary.run_id.clone(),
         build_version: report.summary.build_version.clone(),
         build_target: report.summary.build_target.clone(),
         build_profile: report.summary.build_profile.clone(),
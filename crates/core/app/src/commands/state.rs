@@ -1,13 +1,66 @@
 use gaia_exec::ExecutionOutcome;
-use gaia_plan::{ExecutionPlan, ReuseState, spec_fingerprint};
+use gaia_plan::{ExecutionPlan, OperationId, ReuseState, spec_fingerprint};
 use gaia_spec::ResolvedBuildSpec;
 use std::collections::{BTreeMap, BTreeSet};
-use std::fs;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
 use std::path::PathBuf;
 
 pub fn load_reuse_state(spec: &ResolvedBuildSpec) -> Option<ReuseState> {
-    let path = reuse_state_path(spec);
-    let contents = fs::read_to_string(path).ok()?;
+    let contents = fs::read_to_string(reuse_state_path(spec)).ok()?;
+    parse_reuse_state(&contents)
+}
+
+// Resume state shares the reuse-state format but is appended to as each
+// operation finishes, so an interrupted or failed run still leaves a record.
+pub fn load_resume_state(spec: &ResolvedBuildSpec) -> Option<ReuseState> {
+    let contents = fs::read_to_string(resume_state_path(spec)).ok()?;
+    parse_reuse_state(&contents)
+}
+
+pub fn reset_resume_state(spec: &ResolvedBuildSpec) {
+    let path = resume_state_path(spec);
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let _ = fs::write(path, format!("fingerprint={}\n", spec_fingerprint(spec)));
+}
+
+pub fn record_resume_operation(
+    spec: &ResolvedBuildSpec,
+    plan: &ExecutionPlan,
+    operation_id: &OperationId,
+) {
+    let Some(operation) = plan
+        .operations
+        .iter()
+        .find(|operation| operation.id == *operation_id)
+    else {
+        return;
+    };
+    let mut body = format!(
+        "{}\nop={};{}\n",
+        operation.id.as_str(),
+        operation.id.as_str(),
+        operation.fingerprint
+    );
+    if let Some(signature) = gaia_plan::operation_output_signature(spec, &operation.kind) {
+        body.push_str(&format!("out={};{}\n", operation.id.as_str(), signature));
+    }
+    let Ok(mut file) = OpenOptions::new()
+        .append(true)
+        .open(resume_state_path(spec))
+    else {
+        return;
+    };
+    let _ = file.write_all(body.as_bytes());
+}
+
+pub fn clear_resume_state(spec: &ResolvedBuildSpec) {
+    let _ = fs::remove_file(resume_state_path(spec));
+}
+
+fn parse_reuse_state(contents: &str) -> Option<ReuseState> {
     let mut lines = contents.lines();
     let fingerprint_line = lines.next()?;
     let fingerprint = fingerprint_line
@@ -45,11 +98,28 @@ pub fn load_reuse_state(spec: &ResolvedBuildSpec) -> Option<ReuseState> {
     })
 }
 
+// Operations `--phases` or `--skip-phases` left out of the plan keep their entries from the
+// previous state while the spec is unchanged, so a partial run does not make the next full run
+// rebuild them.
 pub fn save_reuse_state(
     spec: &ResolvedBuildSpec,
     plan: &ExecutionPlan,
     outcome: &ExecutionOutcome,
 ) {
+    let carried = load_reuse_state(spec)
+        .filter(|previous| previous.spec_fingerprint == spec_fingerprint(spec))
+        .map(|mut previous| {
+            let planned = plan
+                .operations
+                .iter()
+                .map(|operation| operation.id.as_str())
+                .collect::<BTreeSet<_>>();
+            previous
+                .completed_operation_ids
+                .retain(|operation_id| !planned.contains(operation_id.as_str()));
+            previous
+        })
+        .unwrap_or_default();
     let path = reuse_state_path(spec);
     if let Some(parent) = path.parent() {
         let _ = fs::create_dir_all(parent);
@@ -60,6 +130,7 @@ pub fn save_reuse_state(
         .map(|id| id.as_str().to_string())
         .collect::<BTreeSet<_>>();
     completed_operation_ids.extend(outcome.reused_ids.iter().map(|id| id.as_str().to_string()));
+    completed_operation_ids.extend(carried.completed_operation_ids.iter().cloned());
     let mut body = format!("fingerprint={}\n", spec_fingerprint(spec));
     for operation_id in &completed_operation_ids {
         body.push_str(operation_id);
@@ -77,6 +148,14 @@ pub fn save_reuse_state(
             }
         }
     }
+    for operation_id in &carried.completed_operation_ids {
+        if let Some(fingerprint) = carried.operation_fingerprints.get(operation_id) {
+            body.push_str(&format!("op={operation_id};{fingerprint}\n"));
+        }
+        if let Some(signature) = carried.operation_output_signatures.get(operation_id) {
+            body.push_str(&format!("out={operation_id};{signature}\n"));
+        }
+    }
     let _ = fs::write(path, body);
 }
 
@@ -86,6 +165,12 @@ fn reuse_state_path(spec: &ResolvedBuildSpec) -> PathBuf {
         .join(format!("{}.reuse-state", spec.build_name()))
 }
 
+fn resume_state_path(spec: &ResolvedBuildSpec) -> PathBuf {
+    PathBuf::from(&spec.workspace.out_dir)
+        .join(".gaia")
+        .join(format!("{}.resume-state", spec.build_name()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -179,6 +264,82 @@ mod tests {
         );
     }
 
+    #[test]
+    fn resume_state_accumulates_recorded_operations() {
+        let spec = test_spec();
+        let (source_catalog, artifact_catalog, image_catalog) =
+            gaia_default_providers::provider_catalogs();
+        let plan = gaia_plan::plan_build(&spec, &source_catalog, &artifact_catalog, &image_catalog);
+        let source = plan
+            .operations
+            .iter()
+            .find(|operation| operation.id.as_str().starts_with("source:"))
+            .expect("default plan has a source operation");
+
+        reset_resume_state(&spec);
+        record_resume_operation(&spec, &plan, &source.id);
+        let state = load_resume_state(&spec).expect("resume state");
+
+        assert_eq!(state.spec_fingerprint, spec_fingerprint(&spec));
+        assert!(state.completed_operation_ids.contains(source.id.as_str()));
+        assert_eq!(
+            state.operation_fingerprints.get(source.id.as_str()),
+            Some(&source.fingerprint)
+        );
+
+        clear_resume_state(&spec);
+        assert!(load_resume_state(&spec).is_none());
+    }
+
+    #[test]
+    fn reuse_state_keeps_operations_left_out_by_phase_selection() {
+        let spec = test_spec();
+        let (source_catalog, artifact_catalog, image_catalog) =
+            gaia_default_providers::provider_catalogs();
+        let full = gaia_plan::plan_build(&spec, &source_catalog, &artifact_catalog, &image_catalog);
+        let all_completed = ExecutionOutcome {
+            completed_ids: full
+                .operations
+                .iter()
+                .map(|operation| operation.id.clone())
+                .collect(),
+            ..ExecutionOutcome::default()
+        };
+        save_reuse_state(&spec, &full, &all_completed);
+
+        let mut partial = full.clone();
+        gaia_plan::select_plan_phases(&mut partial, &[], &["images,checkpoints".into()])
+            .expect("selection");
+        let partial_completed = ExecutionOutcome {
+            completed_ids: partial
+                .operations
+                .iter()
+                .map(|operation| operation.id.clone())
+                .collect(),
+            ..ExecutionOutcome::default()
+        };
+        save_reuse_state(&spec, &partial, &partial_completed);
+
+        let state = load_reuse_state(&spec).expect("reuse state");
+        let image = full
+            .operations
+            .iter()
+            .find(|operation| operation.kind.phase() == Some("images"))
+            .expect("default plan has an image operation");
+        assert!(
+            !partial
+                .operations
+                .iter()
+                .any(|operation| operation.id == image.id)
+        );
+        assert!(state.completed_operation_ids.contains(image.id.as_str()));
+        assert_eq!(
+            state.operation_fingerprints.get(image.id.as_str()),
+            Some(&image.fingerprint)
+        );
+        assert_eq!(state.completed_operation_ids.len(), full.operations.len());
+    }
+
     #[test]
     fn load_reuse_state_returns_none_for_invalid_fingerprint() {
         let spec = test_spec();
@@ -1,5 +1,6 @@
 use gaia_config::{ResolveOptions, try_resolve_config_with_options};
-use gaia_validate::validate_spec_with_providers;
+use gaia_plan::plan_build;
+use gaia_validate::{DiagnosticSeverity, ValidationDiagnostic, validate_spec_with_providers};
 
 use crate::AppContext;
 
@@ -18,11 +19,30 @@ pub fn validate_build_command(
             };
         }
     };
-    let validation = validate_spec_with_providers(
+    let mut validation = validate_spec_with_providers(
         &spec,
         &context.source_catalog,
         &context.artifact_catalog,
         &context.image_catalog,
     );
+    // A valid spec is also planned so graph problems (duplicate ids, missing dependencies,
+    // cycles) show up here, all at once, instead of on the first `gaia plan` or `gaia run`.
+    if validation.errors.is_empty() {
+        let plan = plan_build(
+            &spec,
+            &context.source_catalog,
+            &context.artifact_catalog,
+            &context.image_catalog,
+        );
+        for diagnostic in plan.validate() {
+            validation.errors.push(diagnostic.message.clone());
+            validation.diagnostics.push(ValidationDiagnostic {
+                severity: DiagnosticSeverity::Error,
+                code: diagnostic.code,
+                message: diagnostic.message,
+                location: Some("plan".into()),
+            });
+        }
+    }
     CommandOutcome::Validated { spec, validation }
 }
@@ -1,14 +1,17 @@
 mod cli;
 mod commands;
+mod output;
 #[cfg(feature = "tui")]
 pub mod tui;
 
 use gaia_artifact_providers::ArtifactProviderCatalog;
+use gaia_config::{ConfigContribution, ConfigTrace};
 use gaia_default_providers::ProviderCatalogs;
 use gaia_image_providers::ImageProviderCatalog;
 use gaia_report::{mask_pairs, mask_value};
 use gaia_source_providers::SourceProviderCatalog;
 use gaia_spec::ResolvedBuildSpec;
+use gaia_validate::DiagnosticSeverity;
 use sha2::{Digest, Sha256};
 use std::any::Any;
 use std::fs;
@@ -17,8 +20,17 @@ use std::panic::{self, AssertUnwindSafe};
 use std::path::Path;
 use std::time::Duration;
 
-pub use cli::{AppArgs, AppCommand, CleanArgs};
-pub use commands::{CommandOutcome, CommandResult};
+use output::JSON_SUMMARY_MARKER;
+
+pub use cli::{
+    AppArgs, AppCommand, CleanArgs, ExecArgs, InitArgs, LintArgs, PlanArgs, ProvidersArgs,
+    ResolveArgs, RunArgs, TuiArgs,
+};
+pub use commands::{
+    BatchBuild, CommandOutcome, CommandResult, DoctorCheck, DoctorStatus, INIT_TEMPLATES,
+    InitTemplate, ProviderListing, ProviderSkip,
+};
+pub use output::{ColorChoice, PlanOutputFormat, ProgressMode, StdoutSink};
 
 #[derive(Default)]
 pub struct AppContext {
@@ -72,9 +84,27 @@ impl CommandOutcome {
         match self {
             Self::Help { .. } | Self::Version { .. } => 0,
             Self::TuiExited { exit_code, .. } => *exit_code,
-            Self::Failed { .. } => 1,
+            Self::Failed { .. } | Self::RunFailed { .. } => 1,
+            Self::Executed { exit_code, .. } => *exit_code,
+            Self::RanBatch { builds } => builds
+                .iter()
+                .filter_map(|build| build.exit_code)
+                .find(|exit_code| *exit_code != 0)
+                .unwrap_or(0),
+            Self::Resolved {
+                spec,
+                show_inputs: true,
+            } if !missing_required_inputs(spec).is_empty() => 2,
             Self::Validated { validation, .. } if !validation.errors.is_empty() => 2,
+            Self::Linted { lint, .. } if !lint.errors.is_empty() => 2,
             Self::Planned { diagnostics, .. } if !diagnostics.is_empty() => 3,
+            Self::Doctor { checks, .. }
+                if checks
+                    .iter()
+                    .any(|check| check.status == DoctorStatus::Fail) =>
+            {
+                1
+            }
             Self::Ran {
                 report,
                 validation,
@@ -100,7 +130,12 @@ fn print_outcome(outcome: &CommandOutcome) {
             println!("{summary}");
         }
         CommandOutcome::TuiExited { .. } => {}
-        CommandOutcome::Resolved { spec } => {
+        CommandOutcome::Resolved { spec, show_inputs } => {
+            if *show_inputs {
+                for line in input_report_lines(spec) {
+                    println!("{line}");
+                }
+            }
             println!(
                 "resolved build '{}' with {} source(s), {} artifact(s), {} install(s)",
                 spec.identity.display_name,
@@ -113,6 +148,15 @@ fn print_outcome(outcome: &CommandOutcome) {
                 println!("{line}");
             }
         }
+        CommandOutcome::Inputs { spec } => {
+            println!(
+                "inputs for '{}'; set one with --set input.<name>=<value>",
+                spec.identity.display_name
+            );
+            for line in input_schema_lines(spec) {
+                println!("{line}");
+            }
+        }
         CommandOutcome::Validated { spec, validation } => {
             println!(
                 "validation: {} error(s), {} warning(s)",
@@ -132,10 +176,62 @@ fn print_outcome(outcome: &CommandOutcome) {
                 println!("{}{}: {}", diagnostic.code, location, diagnostic.message);
             }
         }
+        CommandOutcome::Linted { spec, lint } => {
+            for diagnostic in &lint.diagnostics {
+                let severity = match diagnostic.severity {
+                    DiagnosticSeverity::Warning => "warning",
+                    DiagnosticSeverity::Error => "error",
+                };
+                let location = diagnostic
+                    .location
+                    .as_deref()
+                    .map(|value| format!(" [{value}]"))
+                    .unwrap_or_default();
+                println!(
+                    "lint {severity} {}{}: {}",
+                    diagnostic.code, location, diagnostic.message
+                );
+            }
+            println!(
+                "lint for '{}': {} error(s), {} warning(s)",
+                spec.identity.display_name,
+                lint.errors.len(),
+                lint.warnings.len()
+            );
+        }
         CommandOutcome::Planned {
             spec,
             plan,
             diagnostics,
+            lints,
+            skipped,
+            checkpoint_inputs,
+            output_format: format @ (PlanOutputFormat::Json | PlanOutputFormat::Csv),
+        } => {
+            let rendered = match format {
+                PlanOutputFormat::Csv => Ok(output::render_plan_csv(plan)),
+                _ => output::render_plan_json(plan, &spec.identity.build_name),
+            };
+            match rendered {
+                Ok(rendered) => println!("{}", rendered.trim_end()),
+                Err(error) => eprintln!("{error}"),
+            }
+            // Stdout carries only the rendered plan, so the text-mode extras go to stderr.
+            for diagnostic in diagnostics {
+                eprintln!("plan {}: {}", diagnostic.code, diagnostic.message);
+            }
+            for line in plan_explain_lines(lints, skipped, checkpoint_inputs) {
+                eprintln!("{line}");
+            }
+        }
+        CommandOutcome::Planned {
+            spec,
+            plan,
+            diagnostics,
+            lints,
+            skipped,
+            checkpoint_inputs,
+            ..
         } => {
             println!(
                 "plan for '{}' has {} operation(s)",
@@ -174,6 +270,9 @@ fn print_outcome(outcome: &CommandOutcome) {
             for diagnostic in diagnostics {
                 println!("plan {}: {}", diagnostic.code, diagnostic.message);
             }
+            for line in plan_explain_lines(lints, skipped, checkpoint_inputs) {
+                println!("{line}");
+            }
         }
         CommandOutcome::Cleaned { spec, report } => {
             let action = if report.dry_run {
@@ -202,11 +301,15 @@ fn print_outcome(outcome: &CommandOutcome) {
             validation,
             plan_diagnostics,
             execution_errors,
+            phase_timings,
+            json_summary,
+            quiet,
         } => {
             if let Some(output) = post_build_output
                 && !output.trim().is_empty()
             {
                 println!("{output}");
+                print_json_summary(json_summary.as_ref());
                 return;
             }
             println!(
@@ -219,12 +322,26 @@ fn print_outcome(outcome: &CommandOutcome) {
                 report.summary.error_count,
                 report.summary.warning_count
             );
+            // Quiet runs already streamed each failure with its log tail, so only the outcome,
+            // timing, and failure details follow.
+            if *quiet {
+                println!("run time: {}", format_elapsed(*run_duration));
+                print_run_failures(report, execution_errors);
+                print_json_summary(json_summary.as_ref());
+                return;
+            }
             if !report.summary.image_reuse_details.is_empty() {
                 println!(
                     "image reuse: {}",
                     report.summary.image_reuse_details.join(", ")
                 );
             }
+            if !report.summary.skipped_operations.is_empty() {
+                println!(
+                    "skipped after upstream failure: {}",
+                    report.summary.skipped_operations.join(", ")
+                );
+            }
             if report.summary.rolled_back_operations > 0 {
                 println!(
                     "rollback: operations={}",
@@ -287,6 +404,9 @@ fn print_outcome(outcome: &CommandOutcome) {
                 }
             }
             println!("run time: {}", format_elapsed(*run_duration));
+            for line in phase_time_lines(phase_timings) {
+                println!("{line}");
+            }
             if !report.provenance.selected_inputs.is_empty() {
                 println!(
                     "selection inputs: {}",
@@ -389,37 +509,256 @@ fn print_outcome(outcome: &CommandOutcome) {
             for diagnostic in plan_diagnostics {
                 println!("plan {}: {}", diagnostic.code, diagnostic.message);
             }
-            for error in execution_errors {
+            print_run_failures(report, execution_errors);
+            print_json_summary(json_summary.as_ref());
+        }
+        CommandOutcome::Traced {
+            trace,
+            cli_override,
+            preset,
+            reporting,
+        } => {
+            for line in trace_lines(trace, cli_override.as_deref(), preset.as_deref(), reporting) {
+                println!("{line}");
+            }
+        }
+        CommandOutcome::Initialized {
+            root,
+            template,
+            written,
+        } => {
+            println!(
+                "initialized '{}' from template '{template}' ({} file(s))",
+                root.display(),
+                written.len()
+            );
+            for path in written {
+                println!("wrote: {}", path.display());
+            }
+        }
+        CommandOutcome::InitTemplates { templates } => {
+            for template in templates {
+                let marker = if template.name == commands::DEFAULT_INIT_TEMPLATE {
+                    " (default)"
+                } else {
+                    ""
+                };
+                println!("{}{marker}: {}", template.name, template.description);
+            }
+        }
+        CommandOutcome::Schema { schema } => match serde_json::to_string_pretty(schema) {
+            Ok(schema) => println!("{schema}"),
+            Err(error) => eprintln!("failed to render config schema: {error}"),
+        },
+        CommandOutcome::Providers {
+            spec,
+            providers,
+            json: true,
+        } => {
+            let document = serde_json::json!({
+                "build": spec.identity.build_name,
+                "providers": providers,
+            });
+            match serde_json::to_string_pretty(&document) {
+                Ok(document) => println!("{document}"),
+                Err(error) => eprintln!("failed to render providers: {error}"),
+            }
+        }
+        CommandOutcome::Providers {
+            spec, providers, ..
+        } => {
+            println!("providers for '{}':", spec.identity.display_name);
+            for provider in providers {
+                let status = match (provider.used, provider.supported) {
+                    (true, true) => "used",
+                    (true, false) => "used-unsupported",
+                    (false, _) => "unused",
+                };
                 println!(
-                    "execution-error {} [{}]: {}",
-                    error.code,
-                    error.operation_id.as_str(),
-                    error.message
+                    "{} {} kind={} {status}: {}",
+                    provider.domain, provider.id, provider.kind, provider.description
                 );
-                for line in error.output_tail.iter().take(5) {
-                    println!(
-                        "execution-output [{}]: {}",
-                        error.operation_id.as_str(),
-                        line
-                    );
-                }
             }
-            for failure in report.execution_failures.iter().take(5) {
+        }
+        CommandOutcome::Doctor { spec, checks } => {
+            println!("doctor for '{}':", spec.identity.display_name);
+            for check in checks {
                 println!(
-                    "execution-failure {} {:?} [{}]: {}",
-                    failure.code, failure.class, failure.operation_id, failure.message
+                    "{:<4} {}: {}",
+                    check.status.as_str(),
+                    check.name,
+                    check.detail
                 );
-                for line in failure.output_tail.iter().take(5) {
-                    println!(
-                        "execution-failure-output [{}]: {}",
-                        failure.operation_id, line
-                    );
+            }
+            let count = |status| checks.iter().filter(|check| check.status == status).count();
+            println!(
+                "doctor: {} check(s), {} failed, {} warning(s)",
+                checks.len(),
+                count(DoctorStatus::Fail),
+                count(DoctorStatus::Warn)
+            );
+        }
+        CommandOutcome::RanBatch { builds } => {
+            let failed = builds
+                .iter()
+                .filter(|build| build.exit_code.is_some_and(|exit_code| exit_code != 0))
+                .count();
+            let not_run = builds
+                .iter()
+                .filter(|build| build.exit_code.is_none())
+                .count();
+            println!(
+                "batch: {} build(s), {failed} failed, {not_run} not run",
+                builds.len()
+            );
+            for build in builds {
+                match build.exit_code {
+                    Some(0) => println!("batch ok {}", build.build),
+                    Some(exit_code) => {
+                        println!("batch FAIL {} (exit {exit_code})", build.build)
+                    }
+                    None => println!("batch skipped {}", build.build),
+                }
+            }
+        }
+        CommandOutcome::Executed {
+            spec,
+            command,
+            dry_run: true,
+            ..
+        } => {
+            println!(
+                "dry-run exec for '{}': nothing was executed",
+                spec.identity.display_name
+            );
+            println!("dry-run exec: {}", command.label);
+            println!("  $ {}", command.render());
+        }
+        CommandOutcome::Executed {
+            command, exit_code, ..
+        } => {
+            if *exit_code != 0 {
+                eprintln!("{} exited with status {exit_code}", command.label);
+            }
+        }
+        CommandOutcome::DryRun { spec, operations } => {
+            let commands = operations
+                .iter()
+                .map(|operation| operation.commands.len())
+                .sum::<usize>();
+            println!(
+                "dry-run for '{}': {} operation(s), {} command(s); nothing was executed",
+                spec.identity.display_name,
+                operations.len(),
+                commands
+            );
+            for operation in operations {
+                let id = operation.operation_id.as_str();
+                if operation.reused {
+                    println!("dry-run {id}: reused");
+                } else if let Some(error) = &operation.error {
+                    println!("dry-run {id}: unresolved: {error}");
+                }
+                for command in &operation.commands {
+                    println!("dry-run {id}: {}", command.label);
+                    println!("  $ {}", command.render());
                 }
             }
         }
         CommandOutcome::Failed { message } => {
             eprintln!("{message}");
         }
+        CommandOutcome::RunFailed {
+            message,
+            json_summary,
+        } => {
+            eprintln!("{message}");
+            print_json_summary(Some(json_summary));
+        }
+    }
+}
+
+// The `--lint`, `--explain-skip`, and `--explain-checkpoints` lines of `gaia plan`.
+fn plan_explain_lines(
+    lints: &[gaia_plan::PlanDiagnostic],
+    skipped: &[commands::ProviderSkip],
+    checkpoint_inputs: &[(
+        gaia_spec::CheckpointId,
+        gaia_plan::CheckpointFingerprintSources,
+    )],
+) -> Vec<String> {
+    let mut lines = Vec::new();
+    for lint in lints {
+        lines.push(format!("lint {}: {}", lint.code, lint.message));
+    }
+    for skip in skipped {
+        lines.push(format!("skip {} {}: {}", skip.domain, skip.id, skip.reason));
+    }
+    for (checkpoint_id, sources) in checkpoint_inputs {
+        lines.push(format!(
+            "checkpoint {} reruns with {}",
+            checkpoint_id.as_str(),
+            sources.anchor.as_str()
+        ));
+        for source in &sources.inputs {
+            lines.push(match &source.path {
+                Some(path) => format!(
+                    "checkpoint {} path {} -> {} ({})",
+                    checkpoint_id.as_str(),
+                    source.input.as_str(),
+                    path.display(),
+                    if path.exists() { "present" } else { "missing" }
+                ),
+                None => format!(
+                    "checkpoint {} {}",
+                    checkpoint_id.as_str(),
+                    source.input.as_str()
+                ),
+            });
+        }
+    }
+    lines
+}
+
+fn print_run_failures(
+    report: &gaia_report::ReportBundle,
+    execution_errors: &[gaia_exec::ExecutionError],
+) {
+    for error in execution_errors {
+        println!(
+            "execution-error {} [{}]: {}",
+            error.code,
+            error.operation_id.as_str(),
+            error.message
+        );
+        for line in error.output_tail.iter().take(5) {
+            println!(
+                "execution-output [{}]: {}",
+                error.operation_id.as_str(),
+                line
+            );
+        }
+    }
+    for failure in report.execution_failures.iter().take(5) {
+        println!(
+            "execution-failure {} {:?} [{}]: {}",
+            failure.code, failure.class, failure.operation_id, failure.message
+        );
+        for line in failure.output_tail.iter().take(5) {
+            println!(
+                "execution-failure-output [{}]: {}",
+                failure.operation_id, line
+            );
+        }
+    }
+}
+
+// The JSON summary is printed last, on one line after a marker line, so scripts can split it
+// from the human-readable output that precedes it.
+fn print_json_summary(summary: Option<&serde_json::Value>) {
+    if let Some(summary) = summary {
+        println!("{JSON_SUMMARY_MARKER}");
+        println!("{summary}");
     }
 }
 
@@ -515,13 +854,22 @@ pub fn backend_overview_lines(spec: &ResolvedBuildSpec) -> Vec<String> {
         ));
     }
     if let Some(checkpoint) = spec.checkpoints.points.first() {
-        let backend = checkpoint
-            .backend
-            .as_ref()
+        let backends = checkpoint
+            .restore_backends()
             .map(|backend| backend.backend.as_str())
-            .unwrap_or("default");
+            .collect::<Vec<_>>();
+        let backend = if backends.is_empty() {
+            "default".to_string()
+        } else {
+            backends.join(" -> ")
+        };
+        let restore_to = checkpoint
+            .restore_to
+            .as_deref()
+            .map(|restore_to| format!(" restoring to {restore_to}"))
+            .unwrap_or_default();
         lines.push(format!(
-            "runtime checkpoint target: {} via {}",
+            "runtime checkpoint target: {} via {}{restore_to}",
             checkpoint.id.as_str(),
             backend
         ));
@@ -537,6 +885,23 @@ fn rollback_domains_display(domains: &[String]) -> String {
     }
 }
 
+// One line per phase that ran, in plan order. Task time above wall time means the phase's
+// operations overlapped.
+pub fn phase_time_lines(phases: &[gaia_exec::PhaseTiming]) -> Vec<String> {
+    phases
+        .iter()
+        .map(|phase| {
+            format!(
+                "phase time: {} task={} wall={} operations={}",
+                phase.phase,
+                format_elapsed(Duration::from_millis(phase.task_ms)),
+                format_elapsed(Duration::from_millis(phase.wall_ms)),
+                phase.operations
+            )
+        })
+        .collect()
+}
+
 pub fn runtime_overview_lines(report: &gaia_report::ReportBundle) -> Vec<String> {
     let mut lines = Vec::new();
     lines.push(format!(
@@ -575,6 +940,229 @@ pub fn runtime_overview_lines(report: &gaia_report::ReportBundle) -> Vec<String>
     lines
 }
 
+// Values are masked like the inputs table, keyed by the traced path.
+pub fn trace_lines(
+    trace: &ConfigTrace,
+    cli_override: Option<&str>,
+    preset: Option<&str>,
+    reporting: &gaia_spec::ReportingSpec,
+) -> Vec<String> {
+    let base = trace.root.parent().unwrap_or_else(|| Path::new(""));
+    let display = |path: &Path| {
+        path.strip_prefix(base)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    };
+    let describe = |contribution: &ConfigContribution| {
+        let mut origin = format!("from {}", display(&contribution.file));
+        if let Some(table) = &contribution.table {
+            origin.push_str(&format!(" [{table}]"));
+        }
+        if !contribution.via.is_empty() {
+            let via = contribution
+                .via
+                .iter()
+                .map(|link| format!("{} {}", display(&link.file), link.kind.as_str()))
+                .collect::<Vec<_>>();
+            origin.push_str(&format!(" via {}", via.join(", ")));
+        }
+        origin
+    };
+    let masked = |value: &str| mask_value(&trace.path, value, reporting);
+
+    let mut layers = trace.contributions.iter().rev();
+    let mut lines = Vec::new();
+    if let Some(value) = cli_override {
+        lines.push(format!("{} = {:?} (from --set)", trace.path, masked(value)));
+    } else if let Some(winner) = layers.next() {
+        lines.push(format!(
+            "{} = {} ({})",
+            trace.path,
+            masked(&winner.value),
+            describe(winner)
+        ));
+    } else {
+        lines.push(format!("{} is not set by any config file", trace.path));
+    }
+    // Lists and keyed tables merge across layers, so earlier layers may still contribute.
+    for contribution in layers {
+        lines.push(format!(
+            "  earlier: {} ({})",
+            masked(&contribution.value),
+            describe(contribution)
+        ));
+    }
+    if let Some(preset) = preset
+        && cli_override.is_none()
+    {
+        lines.push(format!(
+            "  not traced: overrides from preset '{preset}' apply after these layers"
+        ));
+    }
+    lines
+}
+
+// One row per declared input. Values are masked like the selection line, and a required input
+// with no value is marked MISSING so `gaia resolve --show-inputs` can fail on it.
+pub fn input_report_lines(spec: &gaia_spec::ResolvedBuildSpec) -> Vec<String> {
+    let declared = &spec.inputs.declared;
+    if declared.is_empty() {
+        return vec!["inputs: none declared".into()];
+    }
+    let rows = declared
+        .iter()
+        .map(|input| {
+            let value = spec
+                .inputs
+                .selected
+                .iter()
+                .find(|(name, _)| *name == input.name)
+                .map(|(_, value)| value);
+            let (value, source) = match value {
+                Some(value) => (
+                    mask_value(&input.name, value, &spec.reporting),
+                    spec.inputs
+                        .origin(&input.name)
+                        .map_or("default", |origin| origin.as_str())
+                        .to_string(),
+                ),
+                None if input.required => ("-".into(), "MISSING (required)".into()),
+                None => ("-".into(), "unset".into()),
+            };
+            [
+                input.name.clone(),
+                input.kind.as_str().into(),
+                value,
+                source,
+            ]
+        })
+        .collect::<Vec<_>>();
+    let header = ["input", "kind", "value", "source"].map(String::from);
+    let widths = (0..3)
+        .map(|column| {
+            rows.iter()
+                .chain([&header])
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+    let mut lines = std::iter::once(&header)
+        .chain(&rows)
+        .map(|row| {
+            format!(
+                "{:<name$}  {:<kind$}  {:<value$}  {}",
+                row[0],
+                row[1],
+                row[2],
+                row[3],
+                name = widths[0],
+                kind = widths[1],
+                value = widths[2],
+            )
+        })
+        .collect::<Vec<_>>();
+    let missing = missing_required_inputs(spec);
+    if !missing.is_empty() {
+        lines.push(format!("missing required inputs: {}", missing.join(", ")));
+    }
+    lines
+}
+
+// One row per declared input with what `--set input.<name>=...` accepts, and its description
+// indented underneath. Defaults of secret-looking inputs are masked like resolved values.
+pub fn input_schema_lines(spec: &gaia_spec::ResolvedBuildSpec) -> Vec<String> {
+    let declared = &spec.inputs.declared;
+    if declared.is_empty() {
+        return vec!["inputs: none declared".into()];
+    }
+    let header = ["input", "kind", "required", "default", "accepts"].map(String::from);
+    let rows = declared
+        .iter()
+        .map(|input| {
+            let mut accepts = Vec::new();
+            if !input.choices.is_empty() {
+                accepts.push(input.choices.join(" | "));
+            }
+            match (&input.min, &input.max) {
+                (Some(min), Some(max)) => accepts.push(format!("{min}..={max}")),
+                (Some(min), None) => accepts.push(format!(">= {min}")),
+                (None, Some(max)) => accepts.push(format!("<= {max}")),
+                (None, None) => {}
+            }
+            if let Some(pattern) = &input.pattern {
+                accepts.push(format!("matches {pattern}"));
+            }
+            [
+                input.name.clone(),
+                input.kind.as_str().into(),
+                if input.required { "yes" } else { "no" }.into(),
+                input.default.as_ref().map_or_else(
+                    || "-".into(),
+                    |value| mask_value(&input.name, value, &spec.reporting),
+                ),
+                if accepts.is_empty() {
+                    "-".into()
+                } else {
+                    accepts.join(", ")
+                },
+            ]
+        })
+        .collect::<Vec<_>>();
+    let widths = (0..4)
+        .map(|column| {
+            rows.iter()
+                .chain([&header])
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+    let render = |row: &[String; 5]| {
+        format!(
+            "{:<name$}  {:<kind$}  {:<required$}  {:<default$}  {}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            row[4],
+            name = widths[0],
+            kind = widths[1],
+            required = widths[2],
+            default = widths[3],
+        )
+        .trim_end()
+        .to_string()
+    };
+    let mut lines = vec![render(&header)];
+    for (input, row) in declared.iter().zip(&rows) {
+        lines.push(render(row));
+        if let Some(description) = input.description.as_deref().map(str::trim)
+            && !description.is_empty()
+        {
+            lines.push(format!("    {description}"));
+        }
+    }
+    lines
+}
+
+fn missing_required_inputs(spec: &gaia_spec::ResolvedBuildSpec) -> Vec<&str> {
+    spec.inputs
+        .declared
+        .iter()
+        .filter(|input| {
+            input.required
+                && !spec
+                    .inputs
+                    .selected
+                    .iter()
+                    .any(|(name, _)| *name == input.name)
+        })
+        .map(|input| input.name.as_str())
+        .collect()
+}
+
 fn print_selection(spec: &gaia_spec::ResolvedBuildSpec) {
     if let Some(selected_build_file) = &spec.selection.selected_build_file {
         println!("selection build-file: {selected_build_file}");
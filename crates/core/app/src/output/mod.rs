@@ -0,0 +1,982 @@
+mod plan;
+mod summary;
+
+use gaia_exec::ExecutionEvent;
+use gaia_plan::OperationId;
+use std::collections::{HashMap, VecDeque};
+use std::env;
+use std::io::{self, IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+pub use plan::{PlanOutputFormat, render_plan_csv, render_plan_json, write_plan_dump};
+pub use summary::{JSON_SUMMARY_MARKER, RunSummaryRecorder, json_failed_summary, json_run_summary};
+
+const ANSI_RED: &str = "\x1b[31m";
+const ANSI_GREEN: &str = "\x1b[32m";
+const ANSI_YELLOW: &str = "\x1b[33m";
+const ANSI_RESET: &str = "\x1b[0m";
+
+// A compact sink prints a progress line at most this often while operations keep finishing, and
+// at least this often, given any event, while they do not.
+const COMPACT_PROGRESS_INTERVAL: Duration = Duration::from_secs(10);
+const COMPACT_PROGRESS_IDLE_INTERVAL: Duration = Duration::from_secs(60);
+// Running operations named on one progress line, longest-running first.
+const COMPACT_PROGRESS_RUNNING_LIMIT: usize = 3;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "auto" => Some(Self::Auto),
+            "always" => Some(Self::Always),
+            "never" => Some(Self::Never),
+            _ => None,
+        }
+    }
+
+    // `auto` follows the common CLI convention: color only when stdout is a
+    // terminal and `NO_COLOR` is unset or empty.
+    pub fn enabled(self) -> bool {
+        match self {
+            Self::Auto => {
+                env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+                    && io::stdout().is_terminal()
+            }
+            Self::Always => true,
+            Self::Never => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProgressMode {
+    #[default]
+    Full,
+    Compact,
+}
+
+impl ProgressMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "full" => Some(Self::Full),
+            "compact" => Some(Self::Compact),
+            _ => None,
+        }
+    }
+
+    // Compact progress is for CI logs; an interactive terminal keeps the full stream.
+    pub fn compact(self) -> bool {
+        self == Self::Compact && !io::stdout().is_terminal()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineStyle {
+    Plain,
+    Warning,
+    Error,
+    Success,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StdoutSink {
+    color: bool,
+    preserve_ansi: bool,
+    fold_repeats: bool,
+    quiet: bool,
+    compact: bool,
+}
+
+impl StdoutSink {
+    pub fn new(choice: ColorChoice) -> Self {
+        Self {
+            color: choice.enabled(),
+            preserve_ansi: false,
+            fold_repeats: false,
+            quiet: false,
+            compact: false,
+        }
+    }
+
+    pub fn plain() -> Self {
+        Self {
+            color: false,
+            preserve_ansi: false,
+            fold_repeats: false,
+            quiet: false,
+            compact: false,
+        }
+    }
+
+    pub fn colored() -> Self {
+        Self {
+            color: true,
+            preserve_ansi: false,
+            fold_repeats: false,
+            quiet: false,
+            compact: false,
+        }
+    }
+
+    // Subprocess color only passes through when the sink itself prints color, so `NO_COLOR` and
+    // piped output still get plain text.
+    pub fn with_preserve_ansi(self, preserve_ansi: bool) -> Self {
+        Self {
+            preserve_ansi,
+            ..self
+        }
+    }
+
+    pub fn with_quiet(self, quiet: bool) -> Self {
+        Self { quiet, ..self }
+    }
+
+    pub fn with_compact_progress(self, compact: bool) -> Self {
+        Self { compact, ..self }
+    }
+
+    pub fn with_fold_repeats(self, fold_repeats: bool) -> Self {
+        Self {
+            fold_repeats,
+            ..self
+        }
+    }
+
+    pub fn write_event(&self, event: &ExecutionEvent, tails: &mut LogTails) {
+        let mut stdout = io::stdout().lock();
+        for line in self.render_lines(event, tails) {
+            let _ = writeln!(stdout, "{line}");
+        }
+    }
+
+    // Prints the log line a folding sink is still holding. Call it once the event stream ends so
+    // a run that stops mid-repeat keeps its final count.
+    pub fn finish(&self, tails: &mut LogTails) {
+        let mut stdout = io::stdout().lock();
+        for line in self.release_held(tails) {
+            let _ = writeln!(stdout, "{line}");
+        }
+    }
+
+    // Warnings about the run itself rather than one operation; printed even when quiet.
+    pub fn write_warning(&self, message: &str) {
+        let line = self.paint(format!("WARN: {message}"), LineStyle::Warning);
+        let _ = writeln!(io::stdout().lock(), "{line}");
+    }
+
+    // Quiet sinks hold each operation's log lines back and only print them, ahead of the `FAIL`
+    // line, when that operation fails. Everything else except `SKIP` and `END` is dropped.
+    // Folding sinks hold the last log line back until a different event arrives, so a run of
+    // identical lines prints once as `<line> (xN)`. Scheduler ticks are never printed.
+    // Compact sinks print failures like quiet ones, plus `BEGIN`, `CANCEL` and throttled
+    // `[done/total] running: ...` progress lines; quiet wins when both are set.
+    pub fn render_lines(&self, event: &ExecutionEvent, tails: &mut LogTails) -> Vec<String> {
+        if matches!(event, ExecutionEvent::SchedulerTick { .. }) {
+            return Vec::new();
+        }
+        if self.compact && !self.quiet {
+            return self.render_compact(event, tails, Instant::now());
+        }
+        if !self.quiet {
+            if !self.fold_repeats {
+                return vec![self.render_event(event)];
+            }
+            if let ExecutionEvent::Log {
+                operation_id,
+                message,
+            } = event
+            {
+                return tails
+                    .hold(operation_id, message)
+                    .map(|(operation_id, message, count)| {
+                        self.render_log(operation_id, &message, count)
+                    })
+                    .into_iter()
+                    .collect();
+            }
+            let mut lines = self.release_held(tails);
+            lines.push(self.render_event(event));
+            return lines;
+        }
+        match event {
+            ExecutionEvent::Log {
+                operation_id,
+                message,
+            } => {
+                tails.push(operation_id.as_str(), message, self.fold_repeats);
+                Vec::new()
+            }
+            ExecutionEvent::Failed { operation_id, .. } => {
+                let mut lines = tails
+                    .take(operation_id.as_str())
+                    .into_iter()
+                    .map(|(message, count)| self.render_log(operation_id.clone(), &message, count))
+                    .collect::<Vec<_>>();
+                lines.push(self.render_event(event));
+                lines
+            }
+            ExecutionEvent::Succeeded { operation_id }
+            | ExecutionEvent::Reused { operation_id }
+            | ExecutionEvent::Cancelled { operation_id } => {
+                tails.take(operation_id.as_str());
+                Vec::new()
+            }
+            ExecutionEvent::Skipped { .. } | ExecutionEvent::RunFinished { .. } => {
+                vec![self.render_event(event)]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn render_compact(
+        &self,
+        event: &ExecutionEvent,
+        tails: &mut LogTails,
+        now: Instant,
+    ) -> Vec<String> {
+        let progress = &mut tails.progress;
+        let mut lines = Vec::new();
+        match event {
+            ExecutionEvent::RunStarted { operations, .. } => {
+                *progress = CompactProgress {
+                    total: *operations,
+                    last_line: Some((now, 0)),
+                    ..CompactProgress::default()
+                };
+                return vec![self.render_event(event)];
+            }
+            ExecutionEvent::RunFinished { .. } => return vec![self.render_event(event)],
+            ExecutionEvent::Started { operation_id } => {
+                progress.running.push((operation_id.clone(), now));
+            }
+            ExecutionEvent::Log {
+                operation_id,
+                message,
+            } => tails.push(operation_id.as_str(), message, self.fold_repeats),
+            ExecutionEvent::Failed { operation_id, .. } => {
+                progress.finish(operation_id);
+                lines.extend(tails.take(operation_id.as_str()).into_iter().map(
+                    |(message, count)| self.render_log(operation_id.clone(), &message, count),
+                ));
+                lines.push(self.render_event(event));
+            }
+            ExecutionEvent::Cancelled { operation_id } => {
+                progress.finish(operation_id);
+                tails.take(operation_id.as_str());
+                lines.push(self.render_event(event));
+            }
+            ExecutionEvent::Succeeded { operation_id }
+            | ExecutionEvent::Reused { operation_id } => {
+                progress.finish(operation_id);
+                tails.take(operation_id.as_str());
+            }
+            ExecutionEvent::Skipped { .. } => {
+                progress.finished += 1;
+                lines.push(self.render_event(event));
+            }
+            _ => {}
+        }
+        lines.extend(tails.progress.line(now));
+        lines
+    }
+
+    pub fn render_event(&self, event: &ExecutionEvent) -> String {
+        let (line, style) = match event {
+            ExecutionEvent::RunStarted {
+                run_id,
+                operations,
+                max_parallel_jobs,
+            } => (
+                format!("BEGIN run={run_id} operations={operations} jobs={max_parallel_jobs}"),
+                LineStyle::Plain,
+            ),
+            ExecutionEvent::RunFinished {
+                completed,
+                failed,
+                skipped,
+                cancelled,
+            } => {
+                let style = if *failed > 0 {
+                    LineStyle::Error
+                } else if *cancelled {
+                    LineStyle::Warning
+                } else {
+                    LineStyle::Success
+                };
+                let skipped = if *skipped > 0 {
+                    format!(" skipped={skipped}")
+                } else {
+                    String::new()
+                };
+                let cancelled = if *cancelled { " cancelled" } else { "" };
+                (
+                    format!("END completed={completed} failed={failed}{skipped}{cancelled}"),
+                    style,
+                )
+            }
+            ExecutionEvent::Started { operation_id } => {
+                (format!("START {}", operation_id.as_str()), LineStyle::Plain)
+            }
+            ExecutionEvent::Log {
+                operation_id,
+                message,
+            } => {
+                let message = sanitize_line(message, self.color && self.preserve_ansi);
+                let style = log_line_style(&message);
+                (format!("{}: {message}", operation_id.as_str()), style)
+            }
+            ExecutionEvent::Progress {
+                operation_id,
+                percent,
+            } => (
+                format!("PROGRESS {} {percent}%", operation_id.as_str()),
+                LineStyle::Plain,
+            ),
+            ExecutionEvent::Heartbeat {
+                operation_id,
+                elapsed_ms,
+            } => (
+                format!(
+                    "ALIVE {} still running ({}s, no output)",
+                    operation_id.as_str(),
+                    elapsed_ms / 1000
+                ),
+                LineStyle::Plain,
+            ),
+            ExecutionEvent::SchedulerTick {
+                running,
+                ready,
+                completed,
+                total,
+            } => (
+                format!("QUEUE running={running} ready={ready} completed={completed}/{total}"),
+                LineStyle::Plain,
+            ),
+            ExecutionEvent::Succeeded { operation_id } => (
+                format!("DONE {}", operation_id.as_str()),
+                LineStyle::Success,
+            ),
+            ExecutionEvent::Reused { operation_id } => (
+                format!("REUSE {}", operation_id.as_str()),
+                LineStyle::Success,
+            ),
+            ExecutionEvent::Cancelled { operation_id } => (
+                format!("CANCEL {}", operation_id.as_str()),
+                LineStyle::Warning,
+            ),
+            ExecutionEvent::Skipped {
+                operation_id,
+                failed_dependency,
+            } => (
+                format!(
+                    "SKIP {} (upstream {} failed)",
+                    operation_id.as_str(),
+                    failed_dependency.as_str()
+                ),
+                LineStyle::Warning,
+            ),
+            ExecutionEvent::Failed {
+                operation_id,
+                message,
+            } => (
+                format!(
+                    "FAIL {}: {}",
+                    operation_id.as_str(),
+                    sanitize_line(message, false)
+                ),
+                LineStyle::Error,
+            ),
+        };
+        self.paint(line, style)
+    }
+
+    fn render_log(&self, operation_id: OperationId, message: &str, count: usize) -> String {
+        let message = if count > 1 {
+            format!("{message} (x{count})")
+        } else {
+            message.to_string()
+        };
+        self.render_event(&ExecutionEvent::Log {
+            operation_id,
+            message,
+        })
+    }
+
+    fn release_held(&self, tails: &mut LogTails) -> Vec<String> {
+        tails
+            .held
+            .take()
+            .map(|(operation_id, message, count)| self.render_log(operation_id, &message, count))
+            .into_iter()
+            .collect()
+    }
+
+    // Color codes are applied after sanitization so process output can never
+    // smuggle its own escape sequences into the terminal. A reset kept from the
+    // process output re-applies the line's color, so it only ends the inner one.
+    fn paint(&self, line: String, style: LineStyle) -> String {
+        if !self.color {
+            return line;
+        }
+        let code = match style {
+            LineStyle::Plain => return line,
+            LineStyle::Warning => ANSI_YELLOW,
+            LineStyle::Error => ANSI_RED,
+            LineStyle::Success => ANSI_GREEN,
+        };
+        let line = reapply_after_resets(&line, code);
+        if line.ends_with(ANSI_RESET) {
+            format!("{code}{line}")
+        } else {
+            format!("{code}{line}{ANSI_RESET}")
+        }
+    }
+}
+
+// Keeps the most recent log lines per operation for quiet and compact sinks, each with the number
+// of times it repeated in a row, the line a folding sink is holding back, and the counts a compact
+// sink reports.
+#[derive(Debug, Default)]
+pub struct LogTails {
+    limit: usize,
+    lines: HashMap<String, VecDeque<(String, usize)>>,
+    held: Option<(OperationId, String, usize)>,
+    progress: CompactProgress,
+}
+
+impl LogTails {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            lines: HashMap::new(),
+            held: None,
+            progress: CompactProgress::default(),
+        }
+    }
+
+    fn push(&mut self, operation_id: &str, message: &str, fold_repeats: bool) {
+        if self.limit == 0 {
+            return;
+        }
+        let lines = self.lines.entry(operation_id.to_string()).or_default();
+        if fold_repeats
+            && let Some((last, count)) = lines.back_mut()
+            && last == message
+        {
+            *count += 1;
+            return;
+        }
+        if lines.len() == self.limit {
+            lines.pop_front();
+        }
+        lines.push_back((message.to_string(), 1));
+    }
+
+    // Counts a repeat of the held line, or holds this one and returns the line it replaces.
+    fn hold(
+        &mut self,
+        operation_id: &OperationId,
+        message: &str,
+    ) -> Option<(OperationId, String, usize)> {
+        if let Some((held_id, held_message, count)) = &mut self.held
+            && held_id == operation_id
+            && held_message == message
+        {
+            *count += 1;
+            return None;
+        }
+        self.held
+            .replace((operation_id.clone(), message.to_string(), 1))
+    }
+
+    fn take(&mut self, operation_id: &str) -> Vec<(String, usize)> {
+        self.lines
+            .remove(operation_id)
+            .map(Vec::from)
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Default)]
+struct CompactProgress {
+    total: usize,
+    finished: usize,
+    // Running operations with the time each started, oldest first.
+    running: Vec<(OperationId, Instant)>,
+    // When the last progress line was printed and how many operations had finished by then.
+    last_line: Option<(Instant, usize)>,
+}
+
+impl CompactProgress {
+    fn finish(&mut self, operation_id: &OperationId) {
+        self.running.retain(|(running, _)| running != operation_id);
+        self.finished += 1;
+    }
+
+    // A progress line is due once operations have finished since the last one and the interval
+    // has passed, or once the idle interval has passed regardless.
+    fn line(&mut self, now: Instant) -> Option<String> {
+        let (last_at, last_finished) = self.last_line?;
+        let since = now.saturating_duration_since(last_at);
+        let due = if self.finished != last_finished {
+            since >= COMPACT_PROGRESS_INTERVAL
+        } else {
+            since >= COMPACT_PROGRESS_IDLE_INTERVAL
+        };
+        if !due {
+            return None;
+        }
+        self.last_line = Some((now, self.finished));
+        let mut running = self
+            .running
+            .iter()
+            .take(COMPACT_PROGRESS_RUNNING_LIMIT)
+            .map(|(operation_id, started_at)| {
+                format!(
+                    "{} ({})",
+                    operation_id.as_str(),
+                    short_elapsed(now.saturating_duration_since(*started_at))
+                )
+            })
+            .collect::<Vec<_>>();
+        if self.running.len() > COMPACT_PROGRESS_RUNNING_LIMIT {
+            running.push(format!(
+                "+{} more",
+                self.running.len() - COMPACT_PROGRESS_RUNNING_LIMIT
+            ));
+        }
+        let running = if running.is_empty() {
+            "none".to_string()
+        } else {
+            running.join(", ")
+        };
+        Some(format!(
+            "[{}/{}] running: {running}",
+            self.finished, self.total
+        ))
+    }
+}
+
+fn short_elapsed(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    match seconds {
+        0..60 => format!("{seconds}s"),
+        60..3600 => format!("{}m", seconds / 60),
+        _ => format!("{}h{:02}m", seconds / 3600, (seconds % 3600) / 60),
+    }
+}
+
+// Matches the level prefix on the visible text, so a line that starts with a kept SGR sequence
+// is still recognized.
+fn log_line_style(message: &str) -> LineStyle {
+    let visible = strip_sgr(message);
+    let trimmed = visible.trim_start();
+    if trimmed.starts_with("ERROR:") {
+        LineStyle::Error
+    } else if trimmed.starts_with("WARN:") {
+        LineStyle::Warning
+    } else {
+        LineStyle::Plain
+    }
+}
+
+// Drops control characters, which leaves any escape sequence inert. With `keep_sgr`, SGR color
+// sequences (`ESC [ <digits;...> m`) are kept whole; cursor movement, OSC, and every other
+// sequence still lose their ESC. A line that kept any SGR sequence ends with a reset, so a color
+// the subprocess left open does not bleed into the lines after it.
+fn sanitize_line(message: &str, keep_sgr: bool) -> String {
+    let mut sanitized = String::with_capacity(message.len());
+    let mut kept_sgr = false;
+    let mut rest = message;
+    while let Some(character) = rest.chars().next() {
+        if keep_sgr
+            && character == '\x1b'
+            && let Some(length) = sgr_sequence_length(rest)
+        {
+            sanitized.push_str(&rest[..length]);
+            rest = &rest[length..];
+            kept_sgr = true;
+            continue;
+        }
+        if character == '\t' || !character.is_control() {
+            sanitized.push(character);
+        }
+        rest = &rest[character.len_utf8()..];
+    }
+    if kept_sgr {
+        sanitized.push_str("\x1b[0m");
+    }
+    sanitized
+}
+
+fn strip_sgr(message: &str) -> String {
+    let mut stripped = String::with_capacity(message.len());
+    let mut rest = message;
+    while let Some(character) = rest.chars().next() {
+        let length = sgr_sequence_length(rest).unwrap_or(character.len_utf8());
+        if length == character.len_utf8() {
+            stripped.push(character);
+        }
+        rest = &rest[length..];
+    }
+    stripped
+}
+
+fn reapply_after_resets(line: &str, code: &str) -> String {
+    let mut painted = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(character) = rest.chars().next() {
+        if let Some(length) = sgr_sequence_length(rest) {
+            let sequence = &rest[..length];
+            painted.push_str(sequence);
+            if (sequence == ANSI_RESET || sequence == "\x1b[m") && length < rest.len() {
+                painted.push_str(code);
+            }
+            rest = &rest[length..];
+            continue;
+        }
+        painted.push(character);
+        rest = &rest[character.len_utf8()..];
+    }
+    painted
+}
+
+fn sgr_sequence_length(text: &str) -> Option<usize> {
+    let params = text.strip_prefix("\x1b[")?;
+    let end = params.find(|character: char| !(character.is_ascii_digit() || character == ';'))?;
+    params[end..]
+        .starts_with('m')
+        .then_some("\x1b[".len() + end + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colors_level_prefixes_after_sanitizing_log_lines() {
+        let warning = ExecutionEvent::Log {
+            operation_id: OperationId::image(),
+            message: "WARN: \x1b[31mlow disk".into(),
+        };
+        let failed = ExecutionEvent::Failed {
+            operation_id: OperationId::image(),
+            message: "make exited".into(),
+        };
+
+        assert_eq!(
+            StdoutSink::plain().render_event(&warning),
+            "image:build: WARN: [31mlow disk"
+        );
+        assert_eq!(
+            StdoutSink::colored().render_event(&warning),
+            "\x1b[33mimage:build: WARN: [31mlow disk\x1b[0m"
+        );
+        assert_eq!(
+            StdoutSink::colored().render_event(&failed),
+            "\x1b[31mFAIL image:build: make exited\x1b[0m"
+        );
+        assert_eq!(
+            StdoutSink::colored().render_event(&ExecutionEvent::Started {
+                operation_id: OperationId::image(),
+            }),
+            "START image:build"
+        );
+        assert_eq!(
+            StdoutSink::colored().render_event(&ExecutionEvent::Heartbeat {
+                operation_id: OperationId::image(),
+                elapsed_ms: 185_400,
+            }),
+            "ALIVE image:build still running (185s, no output)"
+        );
+        assert_eq!(
+            StdoutSink::plain().render_event(&ExecutionEvent::RunStarted {
+                run_id: "1792023992298-3fa1b2c4".into(),
+                operations: 11,
+                max_parallel_jobs: 4,
+            }),
+            "BEGIN run=1792023992298-3fa1b2c4 operations=11 jobs=4"
+        );
+        assert_eq!(
+            StdoutSink::colored().render_event(&ExecutionEvent::RunFinished {
+                completed: 3,
+                failed: 0,
+                skipped: 0,
+                cancelled: true,
+            }),
+            "\x1b[33mEND completed=3 failed=0 cancelled\x1b[0m"
+        );
+        assert_eq!(
+            StdoutSink::plain().render_event(&ExecutionEvent::RunFinished {
+                completed: 2,
+                failed: 1,
+                skipped: 3,
+                cancelled: false,
+            }),
+            "END completed=2 failed=1 skipped=3"
+        );
+        assert_eq!(
+            StdoutSink::plain().render_event(&ExecutionEvent::Skipped {
+                operation_id: OperationId::image(),
+                failed_dependency: OperationId::resolve(),
+            }),
+            "SKIP image:build (upstream resolve-build failed)"
+        );
+    }
+
+    #[test]
+    fn quiet_sink_prints_only_failures_with_their_log_tail() {
+        let sink = StdoutSink::plain().with_quiet(true);
+        let mut tails = LogTails::new(2);
+        let log = |operation_id: OperationId, message: &str| ExecutionEvent::Log {
+            operation_id,
+            message: message.into(),
+        };
+        let mut printed = Vec::new();
+        for event in [
+            ExecutionEvent::RunStarted {
+                run_id: "1792023992298-3fa1b2c4".into(),
+                operations: 2,
+                max_parallel_jobs: 1,
+            },
+            ExecutionEvent::Started {
+                operation_id: OperationId::resolve(),
+            },
+            log(OperationId::resolve(), "resolved"),
+            ExecutionEvent::Succeeded {
+                operation_id: OperationId::resolve(),
+            },
+            log(OperationId::image(), "one"),
+            log(OperationId::image(), "two"),
+            log(OperationId::image(), "three"),
+            ExecutionEvent::Failed {
+                operation_id: OperationId::image(),
+                message: "make exited".into(),
+            },
+            ExecutionEvent::RunFinished {
+                completed: 1,
+                failed: 1,
+                skipped: 0,
+                cancelled: false,
+            },
+        ] {
+            printed.extend(sink.render_lines(&event, &mut tails));
+        }
+
+        assert_eq!(
+            printed,
+            vec![
+                "image:build: two",
+                "image:build: three",
+                "FAIL image:build: make exited",
+                "END completed=1 failed=1",
+            ]
+        );
+    }
+
+    #[test]
+    fn folding_sink_collapses_consecutive_repeats_and_keeps_the_final_count() {
+        let sink = StdoutSink::plain().with_fold_repeats(true);
+        let mut tails = LogTails::new(0);
+        let log = |message: &str| ExecutionEvent::Log {
+            operation_id: OperationId::image(),
+            message: message.into(),
+        };
+        let mut printed = Vec::new();
+        for event in [
+            log("downloading"),
+            log("downloading"),
+            log("downloading"),
+            log("extracting"),
+            ExecutionEvent::Succeeded {
+                operation_id: OperationId::image(),
+            },
+            log("done"),
+            log("done"),
+        ] {
+            printed.extend(sink.render_lines(&event, &mut tails));
+        }
+        printed.extend(sink.release_held(&mut tails));
+
+        assert_eq!(
+            printed,
+            vec![
+                "image:build: downloading (x3)",
+                "image:build: extracting",
+                "DONE image:build",
+                "image:build: done (x2)",
+            ]
+        );
+
+        let quiet = StdoutSink::plain().with_quiet(true).with_fold_repeats(true);
+        let mut tails = LogTails::new(2);
+        let mut printed = Vec::new();
+        for event in [
+            log("one"),
+            log("tick"),
+            log("tick"),
+            log("tick"),
+            ExecutionEvent::Failed {
+                operation_id: OperationId::image(),
+                message: "make exited".into(),
+            },
+        ] {
+            printed.extend(quiet.render_lines(&event, &mut tails));
+        }
+        assert_eq!(
+            printed,
+            vec![
+                "image:build: one",
+                "image:build: tick (x3)",
+                "FAIL image:build: make exited",
+            ]
+        );
+    }
+
+    #[test]
+    fn compact_sink_throttles_progress_lines_and_prints_failures_in_full() {
+        let sink = StdoutSink::plain().with_compact_progress(true);
+        let mut tails = LogTails::new(1);
+        let start = Instant::now();
+        let at = |seconds: u64| start + Duration::from_secs(seconds);
+        let build = OperationId::new("buildroot.build");
+        let mut printed = Vec::new();
+        for (seconds, event) in [
+            (
+                0,
+                ExecutionEvent::RunStarted {
+                    run_id: "1792023992298-3fa1b2c4".into(),
+                    operations: 3,
+                    max_parallel_jobs: 2,
+                },
+            ),
+            (
+                0,
+                ExecutionEvent::Started {
+                    operation_id: OperationId::resolve(),
+                },
+            ),
+            (
+                1,
+                ExecutionEvent::Started {
+                    operation_id: build.clone(),
+                },
+            ),
+            (
+                2,
+                ExecutionEvent::Succeeded {
+                    operation_id: OperationId::resolve(),
+                },
+            ),
+            (
+                5,
+                ExecutionEvent::Log {
+                    operation_id: build.clone(),
+                    message: "make[1]: building".into(),
+                },
+            ),
+            (
+                12,
+                ExecutionEvent::Log {
+                    operation_id: build.clone(),
+                    message: "make[1]: linking".into(),
+                },
+            ),
+            (
+                40,
+                ExecutionEvent::Log {
+                    operation_id: build.clone(),
+                    message: "make[1]: still linking".into(),
+                },
+            ),
+            (
+                200,
+                ExecutionEvent::Log {
+                    operation_id: build.clone(),
+                    message: "make[1]: Error 2".into(),
+                },
+            ),
+            (
+                200,
+                ExecutionEvent::Failed {
+                    operation_id: build.clone(),
+                    message: "make exited".into(),
+                },
+            ),
+            (
+                200,
+                ExecutionEvent::Skipped {
+                    operation_id: OperationId::image(),
+                    failed_dependency: build.clone(),
+                },
+            ),
+            (
+                200,
+                ExecutionEvent::RunFinished {
+                    completed: 1,
+                    failed: 1,
+                    skipped: 1,
+                    cancelled: false,
+                },
+            ),
+        ] {
+            printed.extend(sink.render_compact(&event, &mut tails, at(seconds)));
+        }
+
+        assert_eq!(
+            printed,
+            vec![
+                "BEGIN run=1792023992298-3fa1b2c4 operations=3 jobs=2",
+                "[1/3] running: buildroot.build (11s)",
+                "[1/3] running: buildroot.build (3m)",
+                "buildroot.build: make[1]: Error 2",
+                "FAIL buildroot.build: make exited",
+                "SKIP image:build (upstream buildroot.build failed)",
+                "END completed=1 failed=1 skipped=1",
+            ]
+        );
+        assert_eq!(short_elapsed(Duration::from_secs(3_900)), "1h05m");
+    }
+
+    #[test]
+    fn preserve_ansi_keeps_only_sgr_sequences_on_colored_sinks() {
+        let log = ExecutionEvent::Log {
+            operation_id: OperationId::image(),
+            message: "\x1b[1;32mCompiling\x1b[0m gaia\x1b[2K\x1b]0;title\x07".into(),
+        };
+
+        assert_eq!(
+            StdoutSink::colored()
+                .with_preserve_ansi(true)
+                .render_event(&log),
+            "image:build: \x1b[1;32mCompiling\x1b[0m gaia[2K]0;title\x1b[0m"
+        );
+        assert_eq!(
+            StdoutSink::plain()
+                .with_preserve_ansi(true)
+                .render_event(&log),
+            "image:build: [1;32mCompiling[0m gaia[2K]0;title"
+        );
+        assert_eq!(
+            StdoutSink::colored()
+                .with_preserve_ansi(true)
+                .render_event(&ExecutionEvent::Log {
+                    operation_id: OperationId::image(),
+                    message: "\x1b[1mWARN:\x1b[0m disk \x1b[mlow".into(),
+                }),
+            "\x1b[33mimage:build: \x1b[1mWARN:\x1b[0m\x1b[33m disk \x1b[m\x1b[33mlow\x1b[0m"
+        );
+        assert_eq!(sanitize_line("\x1b[31", true), "[31");
+        assert_eq!(
+            sanitize_line("\x1b[31merror: unterminated", true),
+            "\x1b[31merror: unterminated\x1b[0m"
+        );
+    }
+}
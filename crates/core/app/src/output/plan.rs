@@ -0,0 +1,268 @@
+use gaia_plan::{ExecutionPlan, OperationKind, OperationReuse, PlannedOperation};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+// `gaia plan --output-format`. `table` is the human summary; `json` and `csv` list every operation
+// in plan order on stdout instead.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PlanOutputFormat {
+    #[default]
+    Table,
+    Json,
+    Csv,
+}
+
+impl PlanOutputFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "table" => Some(Self::Table),
+            "json" => Some(Self::Json),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+}
+
+const CSV_COLUMNS: [&str; 7] = [
+    "id",
+    "kind",
+    "phase",
+    "target",
+    "depends_on",
+    "optionality",
+    "reuse",
+];
+
+// The plan as executed: after reuse decisions and `--from`, with every dependency edge resolved
+// to an operation id, so the file can be read without the config that produced it.
+#[derive(Debug, Serialize)]
+struct JsonPlan<'a> {
+    build_id: &'a str,
+    build_name: &'a str,
+    operations: Vec<JsonPlannedOperation<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonPlannedOperation<'a> {
+    id: &'a str,
+    kind: &'static str,
+    target: Option<&'a str>,
+    depends_on: Vec<&'a str>,
+    optionality: &'static str,
+    parallelism: JsonParallelism,
+    fingerprint: String,
+    reuse: JsonReuse<'a>,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonParallelism {
+    domain: &'static str,
+    mode: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum JsonReuse<'a> {
+    Execute {
+        code: &'static str,
+        message: &'a str,
+    },
+    Reuse {
+        source: &'a str,
+    },
+}
+
+fn plan_document<'a>(plan: &'a ExecutionPlan, build_name: &'a str) -> JsonPlan<'a> {
+    JsonPlan {
+        build_id: plan.build_id.as_str(),
+        build_name,
+        operations: plan.operations.iter().map(json_operation).collect(),
+    }
+}
+
+pub fn render_plan_json(plan: &ExecutionPlan, build_name: &str) -> Result<String, String> {
+    serde_json::to_string_pretty(&plan_document(plan, build_name))
+        .map_err(|error| format!("failed to render plan: {error}"))
+}
+
+// One row per operation after a header row. `depends_on` holds space-separated operation ids and
+// `reuse` is `execute` or `reuse`; fields are quoted as RFC 4180 asks.
+pub fn render_plan_csv(plan: &ExecutionPlan) -> String {
+    let mut rows = vec![CSV_COLUMNS.join(",")];
+    for operation in &plan.operations {
+        let depends_on = operation
+            .depends_on
+            .iter()
+            .map(|dependency| dependency.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let reuse = match operation.reuse {
+            OperationReuse::Execute(_) => "execute",
+            OperationReuse::Reuse { .. } => "reuse",
+        };
+        let fields = [
+            operation.id.as_str(),
+            operation.kind.as_str(),
+            operation.kind.phase().unwrap_or_default(),
+            operation_target(&operation.kind).unwrap_or_default(),
+            &depends_on,
+            operation.optionality.as_str(),
+            reuse,
+        ];
+        rows.push(fields.map(csv_field).join(","));
+    }
+    rows.join("\n") + "\n"
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn write_plan_dump(path: &Path, plan: &ExecutionPlan, build_name: &str) -> Result<(), String> {
+    let rendered = render_plan_json(plan, build_name)?;
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent).map_err(|error| {
+            format!(
+                "failed to create plan dump directory '{}': {error}",
+                parent.display()
+            )
+        })?;
+    }
+    fs::write(path, rendered + "\n")
+        .map_err(|error| format!("failed to write plan dump '{}': {error}", path.display()))
+}
+
+fn json_operation(operation: &PlannedOperation) -> JsonPlannedOperation<'_> {
+    JsonPlannedOperation {
+        id: operation.id.as_str(),
+        kind: operation.kind.as_str(),
+        target: operation_target(&operation.kind),
+        depends_on: operation
+            .depends_on
+            .iter()
+            .map(|dependency| dependency.as_str())
+            .collect(),
+        optionality: operation.optionality.as_str(),
+        parallelism: JsonParallelism {
+            domain: operation.parallelism.domain.as_str(),
+            mode: operation.parallelism.mode.as_str(),
+        },
+        fingerprint: format!("{:016x}", operation.fingerprint),
+        reuse: match &operation.reuse {
+            OperationReuse::Execute(reason) => JsonReuse::Execute {
+                code: reason.code,
+                message: &reason.message,
+            },
+            OperationReuse::Reuse { source } => JsonReuse::Reuse { source },
+        },
+    }
+}
+
+fn operation_target(kind: &OperationKind) -> Option<&str> {
+    match kind {
+        OperationKind::MaterializeSource { source_id } => Some(source_id.as_str()),
+        OperationKind::BuildArtifact { artifact_id } => Some(artifact_id.as_str()),
+        OperationKind::InstallArtifact { install_id, .. } => Some(install_id.as_str()),
+        OperationKind::RenderStageFile { item_id }
+        | OperationKind::RenderStageEnvSet { item_id }
+        | OperationKind::RenderStageService { item_id }
+        | OperationKind::RenderStageOverlay { item_id }
+        | OperationKind::RenderStageSymlink { item_id } => Some(item_id.as_str()),
+        OperationKind::CaptureCheckpoint { checkpoint_id } => Some(checkpoint_id.as_str()),
+        OperationKind::ResolveBuild
+        | OperationKind::PrepareImage
+        | OperationKind::BuildImage
+        | OperationKind::EmitReport => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gaia_plan::{OperationId, OperationParallelism, OperationParallelismDomain};
+    use gaia_spec::{BuildId, SourceId, StageItemId};
+
+    #[test]
+    fn plan_document_lists_operations_with_resolved_dependencies_and_reuse() {
+        let source_id = SourceId::from("app");
+        let plan = ExecutionPlan {
+            build_id: BuildId::from("demo"),
+            operations: vec![
+                PlannedOperation::new(OperationId::resolve(), OperationKind::ResolveBuild)
+                    .with_reuse(OperationReuse::Reuse {
+                        source: "reuse_state".into(),
+                    }),
+                PlannedOperation::new(
+                    OperationId::source(&source_id),
+                    OperationKind::MaterializeSource {
+                        source_id: source_id.clone(),
+                    },
+                )
+                .with_dependency(OperationId::resolve())
+                .with_parallelism(OperationParallelism::parallelizable(
+                    OperationParallelismDomain::Sources,
+                ))
+                .with_fingerprint(0xabc),
+            ],
+        };
+
+        let value =
+            serde_json::to_value(plan_document(&plan, "demo-build")).expect("plan serializes");
+
+        assert_eq!(value["build_id"], "demo");
+        assert_eq!(value["build_name"], "demo-build");
+        assert_eq!(
+            value["operations"][0]["reuse"],
+            serde_json::json!({"action": "reuse", "source": "reuse_state"})
+        );
+        assert_eq!(
+            value["operations"][1],
+            serde_json::json!({
+                "id": "source:app",
+                "kind": "materialize-source",
+                "target": "app",
+                "depends_on": ["resolve-build"],
+                "optionality": "required",
+                "parallelism": {"domain": "sources", "mode": "parallelizable"},
+                "fingerprint": "0000000000000abc",
+                "reuse": {
+                    "action": "execute",
+                    "code": "initial_plan",
+                    "message": "operation will execute in this plan"
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn plan_csv_quotes_fields_with_separators() {
+        let item_id = StageItemId::from("motd,\"banner\"");
+        let plan = ExecutionPlan {
+            build_id: BuildId::from("demo"),
+            operations: vec![
+                PlannedOperation::new(OperationId::resolve(), OperationKind::ResolveBuild),
+                PlannedOperation::new(
+                    OperationId::new("stage:file:motd"),
+                    OperationKind::RenderStageFile { item_id },
+                )
+                .with_dependency(OperationId::resolve())
+                .with_dependency(OperationId::new("install:app")),
+            ],
+        };
+
+        assert_eq!(
+            render_plan_csv(&plan),
+            "id,kind,phase,target,depends_on,optionality,reuse\n\
+             resolve-build,resolve-build,,,,required,execute\n\
+             stage:file:motd,render-stage-file,stage,\"motd,\"\"banner\"\"\",resolve-build install:app,required,execute\n"
+        );
+    }
+}
@@ -0,0 +1,194 @@
+use gaia_exec::{ExecutionEvent, PhaseTiming};
+use gaia_report::{FailureClass, ReportBundle, ReportOutputBundle};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+// Marks the machine-readable summary so scripts can find it after the streamed event lines.
+pub const JSON_SUMMARY_MARKER: &str = "--- gaia run json summary ---";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OperationResult {
+    pub id: String,
+    pub status: &'static str,
+    pub ok: bool,
+    pub elapsed_ms: u128,
+    pub error: Option<String>,
+    // Filled from the report's execution failures for failed operations.
+    pub class: Option<FailureClass>,
+    pub exit_code: Option<i32>,
+    pub signal: Option<i32>,
+}
+
+// Watches the same event stream `StdoutSink` prints and times each operation from its start
+// event to its terminal one. Reused operations never start, so they report zero elapsed.
+#[derive(Debug, Default)]
+pub struct RunSummaryRecorder {
+    started: HashMap<String, Instant>,
+    results: Vec<OperationResult>,
+}
+
+impl RunSummaryRecorder {
+    pub fn record(&mut self, event: &ExecutionEvent) {
+        let (operation_id, status, ok, error) = match event {
+            ExecutionEvent::Started { operation_id } => {
+                self.started
+                    .insert(operation_id.as_str().to_string(), Instant::now());
+                return;
+            }
+            ExecutionEvent::Succeeded { operation_id } => (operation_id, "succeeded", true, None),
+            ExecutionEvent::Reused { operation_id } => (operation_id, "reused", true, None),
+            ExecutionEvent::Cancelled { operation_id } => (operation_id, "cancelled", false, None),
+            ExecutionEvent::Skipped {
+                operation_id,
+                failed_dependency,
+            } => (
+                operation_id,
+                "skipped",
+                false,
+                Some(format!("upstream {} failed", failed_dependency.as_str())),
+            ),
+            ExecutionEvent::Failed {
+                operation_id,
+                message,
+            } => (operation_id, "failed", false, Some(message.clone())),
+            _ => return,
+        };
+        let elapsed = self
+            .started
+            .remove(operation_id.as_str())
+            .map(|started| started.elapsed())
+            .unwrap_or_default();
+        self.results.push(OperationResult {
+            id: operation_id.as_str().to_string(),
+            status,
+            ok,
+            elapsed_ms: elapsed.as_millis(),
+            error,
+            class: None,
+            exit_code: None,
+            signal: None,
+        });
+    }
+
+    pub fn into_results(self) -> Vec<OperationResult> {
+        self.results
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct JsonRunSummary<'a> {
+    build_name: &'a str,
+    run_id: Option<&'a str>,
+    status: &'static str,
+    elapsed_ms: u128,
+    operations: Vec<OperationResult>,
+    phases: &'a [PhaseTiming],
+    report_files: Vec<String>,
+}
+
+pub fn json_run_summary(
+    report: &ReportBundle,
+    report_outputs: &ReportOutputBundle,
+    run_duration: Duration,
+    cancelled: bool,
+    operations: &[OperationResult],
+    phases: &[PhaseTiming],
+) -> serde_json::Value {
+    let status = if cancelled {
+        "cancelled"
+    } else if report.summary.error_count == 0 {
+        "completed"
+    } else {
+        "failed"
+    };
+    serde_json::to_value(JsonRunSummary {
+        build_name: &report.summary.build_name,
+        run_id: report.summary.run_id.as_deref(),
+        status,
+        elapsed_ms: run_duration.as_millis(),
+        operations: operations
+            .iter()
+            .map(|operation| classify_operation(operation, report))
+            .collect(),
+        phases,
+        report_files: report_outputs
+            .files
+            .iter()
+            .map(|file| file.path.display().to_string())
+            .collect(),
+    })
+    .unwrap_or_default()
+}
+
+// The summary of a run that stopped before executing anything, so scripts reading
+// `--json-summary` still get a status object.
+pub fn json_failed_summary(build_name: &str, run_duration: Duration) -> serde_json::Value {
+    serde_json::to_value(JsonRunSummary {
+        build_name,
+        run_id: None,
+        status: "failed",
+        elapsed_ms: run_duration.as_millis(),
+        operations: Vec::new(),
+        phases: &[],
+        report_files: Vec::new(),
+    })
+    .unwrap_or_default()
+}
+
+fn classify_operation(operation: &OperationResult, report: &ReportBundle) -> OperationResult {
+    let mut operation = operation.clone();
+    if let Some(failure) = report
+        .execution_failures
+        .iter()
+        .find(|failure| failure.operation_id == operation.id)
+    {
+        operation.class = Some(failure.class);
+        operation.exit_code = failure.exit_code;
+        operation.signal = failure.signal;
+    }
+    operation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gaia_plan::OperationId;
+
+    #[test]
+    fn recorder_keeps_terminal_results_in_completion_order() {
+        let mut recorder = RunSummaryRecorder::default();
+        for event in [
+            ExecutionEvent::RunStarted {
+                run_id: "1792023992298-3fa1b2c4".into(),
+                operations: 3,
+                max_parallel_jobs: 1,
+            },
+            ExecutionEvent::Reused {
+                operation_id: OperationId::resolve(),
+            },
+            ExecutionEvent::Started {
+                operation_id: OperationId::image(),
+            },
+            ExecutionEvent::Failed {
+                operation_id: OperationId::image(),
+                message: "make exited".into(),
+            },
+        ] {
+            recorder.record(&event);
+        }
+
+        let results = recorder.into_results();
+        assert_eq!(
+            results
+                .iter()
+                .map(|result| (result.id.as_str(), result.status, result.ok))
+                .collect::<Vec<_>>(),
+            vec![
+                ("resolve-build", "reused", true),
+                ("image:build", "failed", false)
+            ]
+        );
+        assert_eq!(results[1].error.as_deref(), Some("make exited"));
+    }
+}
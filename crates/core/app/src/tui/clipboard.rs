@@ -0,0 +1,113 @@
+use super::*;
+use std::io::Write;
+use std::process::Stdio;
+
+impl<'a> TuiState<'a> {
+    // Copies the open detail panel as plain text. The clipboard is reached through the
+    // platform's copy command, the same way error logs go through $PAGER, so headless hosts
+    // without one get the text written to a temp file instead.
+    pub(crate) fn copy_detail_view(&mut self) {
+        let view = self.selected_detail_view();
+        let text = self
+            .detail_lines()
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        if clipboard_commands()
+            .into_iter()
+            .any(|command| copy_with(command, &text))
+        {
+            self.set_status(format!("copied {} to the clipboard", view.title()));
+            return;
+        }
+
+        let build_name = self.spec.as_ref().map_or(self.build.as_str(), |spec| {
+            spec.identity.build_name.as_str()
+        });
+        let stem = format!(
+            "gaia-{}-{}",
+            build_name.replace(['/', '\\', ' '], "-"),
+            view.title().to_ascii_lowercase()
+        );
+        match write_new_temp_file(&stem, &format!("{text}\n")) {
+            Ok(path) => self.set_status(format!(
+                "no clipboard available; wrote {} to {}",
+                view.title(),
+                path.display()
+            )),
+            Err(error) => self.set_status(format!(
+                "no clipboard available and writing a temp file failed: {error}"
+            )),
+        }
+    }
+}
+
+// The temp dir is shared, so the file gets a name nobody can have prepared and is created fresh,
+// readable only by the user, rather than written through whatever already sits at a fixed path.
+fn write_new_temp_file(stem: &str, contents: &str) -> std::io::Result<PathBuf> {
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos())
+        .unwrap_or_default();
+    let mut attempt = 0;
+    loop {
+        let path = std::env::temp_dir().join(format!(
+            "{stem}-{}-{nonce:x}-{attempt}.txt",
+            std::process::id()
+        ));
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        match options.open(&path) {
+            Ok(mut file) => {
+                file.write_all(contents.as_bytes())?;
+                return Ok(path);
+            }
+            Err(error) if error.kind() == std::io::ErrorKind::AlreadyExists && attempt < 16 => {
+                attempt += 1;
+            }
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+// Wayland and X11 tools are only tried when their display is set, so an SSH session without a
+// forwarded display falls through to the temp file quickly.
+fn clipboard_commands() -> Vec<(&'static str, &'static [&'static str])> {
+    let mut commands: Vec<(&'static str, &'static [&'static str])> = Vec::new();
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        commands.push(("wl-copy", &[]));
+    }
+    if std::env::var_os("DISPLAY").is_some() {
+        commands.push(("xclip", &["-selection", "clipboard"]));
+        commands.push(("xsel", &["--clipboard", "--input"]));
+    }
+    if cfg!(target_os = "macos") {
+        commands.push(("pbcopy", &[]));
+    }
+    commands
+}
+
+fn copy_with((program, args): (&str, &[&str]), text: &str) -> bool {
+    let Ok(mut child) = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    else {
+        return false;
+    };
+    let written = child
+        .stdin
+        .take()
+        .is_some_and(|mut stdin| stdin.write_all(text.as_bytes()).is_ok());
+    child.wait().is_ok_and(|status| status.success()) && written
+}
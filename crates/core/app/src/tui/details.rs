@@ -87,8 +87,14 @@ impl<'a> TuiState<'a> {
             .iter()
             .map(|operation| {
                 let (status, color) = self.operation_status(operation.id.as_str());
+                let mut label = format!("{} {:?}", operation.id.as_str(), operation.kind);
+                if status == "RUN"
+                    && let Some(average_ms) = self.timings.average_ms(operation.id.as_str())
+                {
+                    label.push_str(&format!("  avg {}", format_average_elapsed(average_ms)));
+                }
                 OperationItem {
-                    label: format!("{} {:?}", operation.id.as_str(), operation.kind),
+                    label,
                     status,
                     color,
                 }
@@ -238,12 +244,20 @@ impl<'a> TuiState<'a> {
         if !spec.selection.selected_inputs.is_empty() {
             lines.push(Line::from(""));
             lines.push(Line::from("inputs:").bold());
-            lines.extend(
-                spec.selection
-                    .selected_inputs
+            lines.extend(spec.selection.selected_inputs.iter().map(|(key, value)| {
+                // Computed values keep their referencing default alongside, so the template is visible.
+                let computed_from = spec
+                    .inputs
+                    .declared
                     .iter()
-                    .map(|(key, value)| Line::from(format!("{key}={value}"))),
-            );
+                    .find(|input| input.name == *key)
+                    .and_then(|input| input.default.as_deref())
+                    .filter(|default| default.contains("${input"));
+                match computed_from {
+                    Some(default) => Line::from(format!("{key}={value} (default: {default})")),
+                    None => Line::from(format!("{key}={value}")),
+                }
+            }));
         }
         if !spec.selection.env_files.is_empty() {
             lines.push(Line::from(""));
@@ -5,11 +5,11 @@ pub(crate) fn discover_build_entries(current_build: &str) -> Vec<BuildEntry> {
 
     let build_configs_dir = PathBuf::from("configs").join("builds");
     if build_configs_dir.exists() {
-        collect_toml_files(&build_configs_dir, &mut paths);
+        collect_config_files(&build_configs_dir, &mut paths);
     } else {
         let configs_dir = PathBuf::from("configs");
         if configs_dir.exists() {
-            collect_toml_files(&configs_dir, &mut paths);
+            collect_config_files(&configs_dir, &mut paths);
         }
     }
 
@@ -29,15 +29,18 @@ pub(crate) fn discover_build_entries(current_build: &str) -> Vec<BuildEntry> {
         .collect()
 }
 
-pub(crate) fn collect_toml_files(dir: &Path, out: &mut Vec<PathBuf>) {
+pub(crate) fn collect_config_files(dir: &Path, out: &mut Vec<PathBuf>) {
     let Ok(entries) = fs::read_dir(dir) else {
         return;
     };
     for entry in entries.flatten() {
         let path = entry.path();
         if path.is_dir() {
-            collect_toml_files(&path, out);
-        } else if path.extension().and_then(|value| value.to_str()) == Some("toml") {
+            collect_config_files(&path, out);
+        } else if matches!(
+            path.extension().and_then(|value| value.to_str()),
+            Some("toml" | "yaml" | "yml")
+        ) {
             out.push(path);
         }
     }
@@ -69,6 +72,11 @@ pub(crate) fn live_operation_status(
             ExecutionEvent::Cancelled { operation_id: id } if id.as_str() == operation_id => {
                 status = Some(("CANCEL", Color::LightYellow));
             }
+            ExecutionEvent::Skipped {
+                operation_id: id, ..
+            } if id.as_str() == operation_id => {
+                status = Some(("SKIP", Color::Yellow));
+            }
             ExecutionEvent::Failed {
                 operation_id: id, ..
             } if id.as_str() == operation_id => {
@@ -80,6 +88,59 @@ pub(crate) fn live_operation_status(
     status
 }
 
+// Progress only applies while the operation is still running; a later
+// terminal event for the same id clears it.
+pub(crate) fn live_operation_progress(events: &[ExecutionEvent], operation_id: &str) -> Option<u8> {
+    for event in events.iter().rev() {
+        match event {
+            ExecutionEvent::Progress {
+                operation_id: id,
+                percent,
+            } if id.as_str() == operation_id => return Some(*percent),
+            ExecutionEvent::Started { operation_id: id }
+            | ExecutionEvent::Succeeded { operation_id: id }
+            | ExecutionEvent::Reused { operation_id: id }
+            | ExecutionEvent::Cancelled { operation_id: id }
+            | ExecutionEvent::Failed {
+                operation_id: id, ..
+            } if id.as_str() == operation_id => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+// A heartbeat only stands while the operation stays silent; any later output or terminal
+// event for the same id clears it.
+pub(crate) fn live_operation_heartbeat(
+    events: &[ExecutionEvent],
+    operation_id: &str,
+) -> Option<u64> {
+    for event in events.iter().rev() {
+        match event {
+            ExecutionEvent::Heartbeat {
+                operation_id: id,
+                elapsed_ms,
+            } if id.as_str() == operation_id => return Some(*elapsed_ms),
+            ExecutionEvent::Log {
+                operation_id: id, ..
+            }
+            | ExecutionEvent::Progress {
+                operation_id: id, ..
+            }
+            | ExecutionEvent::Started { operation_id: id }
+            | ExecutionEvent::Succeeded { operation_id: id }
+            | ExecutionEvent::Reused { operation_id: id }
+            | ExecutionEvent::Cancelled { operation_id: id }
+            | ExecutionEvent::Failed {
+                operation_id: id, ..
+            } if id.as_str() == operation_id => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
 pub(crate) fn current_operation_label(events: &[ExecutionEvent]) -> Option<&str> {
     for event in events.iter().rev() {
         if let ExecutionEvent::Started { operation_id } = event {
@@ -101,8 +162,38 @@ pub(crate) fn live_completed_count(events: &[ExecutionEvent]) -> usize {
         .count()
 }
 
+// The executor announces its operation count before anything starts, so this is known even
+// when the monitor has no plan of its own yet.
+pub(crate) fn live_operation_total(events: &[ExecutionEvent]) -> Option<usize> {
+    events.iter().find_map(|event| match event {
+        ExecutionEvent::RunStarted { operations, .. } => Some(*operations),
+        _ => None,
+    })
+}
+
 pub(crate) fn render_event_line(event: &ExecutionEvent) -> Line<'static> {
     match event {
+        ExecutionEvent::RunStarted {
+            run_id,
+            operations,
+            max_parallel_jobs,
+        } => Line::from(format!(
+            "run {run_id} started: {operations} operation(s), {max_parallel_jobs} job(s)"
+        )),
+        ExecutionEvent::RunFinished {
+            completed,
+            failed,
+            skipped,
+            cancelled,
+        } => Line::from(format!(
+            "run finished: {completed} completed, {failed} failed{}{}",
+            if *skipped > 0 {
+                format!(", {skipped} skipped")
+            } else {
+                String::new()
+            },
+            if *cancelled { ", cancelled" } else { "" }
+        )),
         ExecutionEvent::Started { operation_id } => {
             Line::from(format!("started: {}", operation_id.as_str()))
         }
@@ -115,6 +206,14 @@ pub(crate) fn render_event_line(event: &ExecutionEvent) -> Line<'static> {
         ExecutionEvent::Cancelled { operation_id } => {
             Line::from(format!("cancelled: {}", operation_id.as_str()))
         }
+        ExecutionEvent::Skipped {
+            operation_id,
+            failed_dependency,
+        } => Line::from(format!(
+            "skipped: {}  upstream {} failed",
+            operation_id.as_str(),
+            failed_dependency.as_str()
+        )),
         ExecutionEvent::Failed {
             operation_id,
             message,
@@ -123,6 +222,45 @@ pub(crate) fn render_event_line(event: &ExecutionEvent) -> Line<'static> {
             operation_id,
             message,
         } => Line::from(format!("log: {}  {}", operation_id.as_str(), message)),
+        ExecutionEvent::Progress {
+            operation_id,
+            percent,
+        } => Line::from(format!("progress: {}  {}%", operation_id.as_str(), percent)),
+        ExecutionEvent::Heartbeat {
+            operation_id,
+            elapsed_ms,
+        } => Line::from(format!(
+            "heartbeat: {}  {}",
+            operation_id.as_str(),
+            format_silent_elapsed(*elapsed_ms)
+        )),
+        ExecutionEvent::SchedulerTick {
+            running,
+            ready,
+            completed,
+            total,
+        } => Line::from(format!(
+            "queue: {running} running, {ready} ready, {completed}/{total} completed"
+        )),
+    }
+}
+
+// Coarse on purpose: "3m" reads better than a ticking clock in a status line.
+pub(crate) fn format_silent_elapsed(elapsed_ms: u64) -> String {
+    let seconds = elapsed_ms / 1000;
+    match seconds {
+        0..60 => format!("{seconds}s"),
+        60..3600 => format!("{}m", seconds / 60),
+        _ => format!("{}h{:02}m", seconds / 3600, (seconds % 3600) / 60),
+    }
+}
+
+pub(crate) fn format_average_elapsed(elapsed_ms: u64) -> String {
+    let seconds = elapsed_ms / 1000;
+    match seconds {
+        0..60 => format!("{seconds}s"),
+        60..3600 => format!("{}m{:02}s", seconds / 60, seconds % 60),
+        _ => format!("{}h{:02}m", seconds / 3600, (seconds % 3600) / 60),
     }
 }
 
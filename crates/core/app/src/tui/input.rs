@@ -32,7 +32,9 @@ impl<'a> TuiState<'a> {
         match code {
             KeyCode::Char('b') => self.screen = Screen::Picker,
             KeyCode::Char('p') => self.refresh(),
+            KeyCode::Char('R') => self.reload_config(),
             KeyCode::Char('r') | KeyCode::Char('s') => self.start_run(),
+            KeyCode::Char('y') => self.copy_detail_view(),
             KeyCode::Enter => self.activate_setup_item(),
             KeyCode::Down => self.move_setup_down(),
             KeyCode::Up => self.move_setup_up(),
@@ -72,6 +74,10 @@ impl<'a> TuiState<'a> {
                 }
             }
             KeyCode::Char('c') => self.cancel_run(),
+            KeyCode::Char('r') => self.rerun(),
+            KeyCode::Char('R') => self.reload_config(),
+            KeyCode::Char('o') => self.open_error_log(),
+            KeyCode::Char('y') => self.copy_detail_view(),
             KeyCode::Down => self.move_operation_down(),
             KeyCode::Up => self.move_operation_up(),
             KeyCode::Left if modifiers.is_empty() => self.prev_monitor_view(),
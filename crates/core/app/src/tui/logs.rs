@@ -0,0 +1,101 @@
+use super::*;
+
+impl<'a> TuiState<'a> {
+    // Gaia keeps failure output in memory and in the report bundle only, so the log is written
+    // out on demand and handed to the main loop, which owns the terminal and runs the pager.
+    pub(crate) fn open_error_log(&mut self) {
+        if matches!(self.run_state, RunState::Running { .. }) {
+            self.set_status("error logs open once the run finishes");
+            return;
+        }
+        let Some(operation_id) = self
+            .selected_operation()
+            .map(|operation| operation.id.as_str().to_string())
+        else {
+            self.set_status("no operation selected");
+            return;
+        };
+        let Some(run) = self.last_run.as_ref() else {
+            self.set_status("no error log yet: start a run with 's' first");
+            return;
+        };
+        let Some(error) = run
+            .outcome
+            .errors
+            .iter()
+            .find(|error| error.operation_id.as_str() == operation_id)
+        else {
+            self.set_status(format!(
+                "no error log for {operation_id}: it did not fail in the last run"
+            ));
+            return;
+        };
+
+        let path = error_log_path(&run.spec.workspace.out_dir, &operation_id);
+        let contents = render_error_log(run, error);
+        let written = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(&path, contents));
+        match written {
+            Ok(()) => self.pending_log_view = Some(path),
+            Err(error) => self.set_status(format!(
+                "failed to write error log {}: {error}",
+                path.display()
+            )),
+        }
+    }
+}
+
+fn error_log_path(out_dir: &str, operation_id: &str) -> PathBuf {
+    let file_name = operation_id
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.') {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    PathBuf::from(out_dir)
+        .join(".gaia")
+        .join("logs")
+        .join(format!("{file_name}.log"))
+}
+
+fn render_error_log(run: &RunArtifacts, error: &gaia_exec::ExecutionError) -> String {
+    let mut lines = vec![
+        format!("build: {}", run.spec.identity.build_name),
+        format!(
+            "run: {}",
+            run.outcome.run_id.as_deref().unwrap_or("not started")
+        ),
+        format!("operation: {}", error.operation_id.as_str()),
+        format!("error: {} {}", error.code, error.message),
+    ];
+    if !error.output_tail.is_empty() {
+        lines.push(String::new());
+        lines.push("output tail:".into());
+        lines.extend(error.output_tail.iter().cloned());
+    }
+    let logs = run
+        .outcome
+        .events
+        .iter()
+        .filter_map(|event| match event {
+            ExecutionEvent::Log {
+                operation_id,
+                message,
+            } if *operation_id == error.operation_id => Some(message.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if !logs.is_empty() {
+        lines.push(String::new());
+        lines.push("task logs:".into());
+        lines.extend(logs);
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
@@ -1,21 +1,22 @@
 use std::fs;
 use std::io::{self, Stdout};
 use std::path::{Path, PathBuf};
+use std::process::Command;
 use std::sync::mpsc::{self, Receiver, TryRecvError};
 use std::thread;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
 
 use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
 use crossterm::execute;
 use crossterm::terminal::{
     EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
 };
-use gaia_config::{ResolveOptions, try_resolve_config_with_options};
+use gaia_config::{ResolveOptions, config_file_set, try_resolve_config_with_options};
 use gaia_exec::{
-    ExecutionCancellation, ExecutionEvent, ExecutionProviders,
-    execute_plan_with_cancellation_and_observer,
+    ExecutionCancellation, ExecutionEvent, ExecutionProviders, OperationTimings,
+    execute_plan_with_cancellation_and_observer, phase_timings, write_env_export,
 };
-use gaia_plan::{ExecutionPlan, PlannedOperation, plan_build_with_reuse_state};
+use gaia_plan::{ExecutionPlan, OperationId, PlannedOperation, plan_build_with_reuse_state};
 use gaia_report::{ReportFileKind, generate_report, write_report_bundle};
 use gaia_spec::ResolvedBuildSpec;
 use gaia_validate::{ValidationReport, validate_spec_with_providers};
@@ -27,14 +28,15 @@ use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::{Block, Borders, Gauge, List, ListItem, ListState, Paragraph, Wrap};
 
 use crate::commands::{CommandOutcome, RunArtifacts, load_reuse_state, save_reuse_state};
-use crate::{AppContext, backend_overview_lines, runtime_overview_lines};
+use crate::{AppContext, backend_overview_lines, phase_time_lines, runtime_overview_lines};
 
 pub fn run_tui_command(
     context: &AppContext,
     build: &str,
+    open_build: Option<&str>,
     options: &ResolveOptions,
 ) -> CommandOutcome {
-    match launch_tui(context, build, options) {
+    match launch_tui(context, build, open_build, options) {
         Ok((exit_code, summary)) => CommandOutcome::TuiExited { summary, exit_code },
         Err(error) => CommandOutcome::Failed {
             message: format!("failed to launch tui: {error}"),
@@ -45,10 +47,14 @@ pub fn run_tui_command(
 fn launch_tui(
     context: &AppContext,
     build: &str,
+    open_build: Option<&str>,
     options: &ResolveOptions,
 ) -> io::Result<(i32, String)> {
     let mut state = TuiState::new(context, build, options);
-    state.refresh();
+    match open_build {
+        Some(path) => state.open_build(path),
+        None => state.refresh(),
+    }
 
     let mut terminal = setup_terminal()?;
     let exit_code = run_loop(&mut terminal, &mut state)?;
@@ -69,6 +75,45 @@ fn restore_terminal(terminal: &mut ratatui::Terminal<CrosstermBackend<Stdout>>)
     terminal.show_cursor()
 }
 
+// Hands the terminal to the pager and takes it back afterwards. The TUI is resumed even when the
+// pager cannot be started, so a bad $PAGER only costs a status message.
+fn view_log_file(
+    terminal: &mut ratatui::Terminal<CrosstermBackend<Stdout>>,
+    state: &mut TuiState<'_>,
+    path: &Path,
+) -> io::Result<()> {
+    restore_terminal(terminal)?;
+    let status = pager_command(path).status();
+    enable_raw_mode()?;
+    execute!(terminal.backend_mut(), EnterAlternateScreen)?;
+    terminal.clear()?;
+    match status {
+        Ok(status) if status.success() => state.set_status(format!("viewed {}", path.display())),
+        Ok(status) => state.set_status(format!(
+            "pager exited with {status}; log is at {}",
+            path.display()
+        )),
+        Err(error) => state.set_status(format!(
+            "could not start pager ({error}); log is at {}",
+            path.display()
+        )),
+    }
+    Ok(())
+}
+
+// $PAGER wins over $EDITOR, and either may carry arguments such as `less -R`.
+fn pager_command(path: &Path) -> Command {
+    let program = ["PAGER", "EDITOR"]
+        .into_iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "less".into());
+    let mut parts = program.split_whitespace();
+    let mut command = Command::new(parts.next().unwrap_or("less"));
+    command.args(parts).arg(path);
+    command
+}
+
 fn run_loop(
     terminal: &mut ratatui::Terminal<CrosstermBackend<Stdout>>,
     state: &mut TuiState<'_>,
@@ -100,13 +145,18 @@ fn run_loop(
             }
             _ => state.handle_key(key.code, key.modifiers),
         }
+        if let Some(path) = state.pending_log_view.take() {
+            view_log_file(terminal, state, &path)?;
+        }
         state.tick();
     }
 }
 
+mod clipboard;
 mod details;
 mod discovery;
 mod input;
+mod logs;
 mod model;
 mod render;
 mod run;
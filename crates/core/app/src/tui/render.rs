@@ -21,10 +21,10 @@ pub(crate) fn render_footer(frame: &mut Frame<'_>, area: Rect, state: &TuiState<
         match state.screen {
             Screen::Picker => "[Up/Down] move  [Enter] open build  [r] reload build list  [q] quit",
             Screen::Setup => {
-                "[Up/Down] move  [Left/Right] detail/value  [Enter] select/edit  [s/r] start build  [b] builds  [p] refresh  [q] quit"
+                "[Up/Down] move  [Left/Right] detail/value  [Enter] select/edit  [y] copy view  [s/r] start build  [b] builds  [p] refresh  [q] quit"
             }
             Screen::Monitor => {
-                "[Up/Down] select op  [Left/Right] view  [PgUp/PgDn] scroll  [End] tail  [c] cancel  [q] quit"
+                "[Up/Down] select op  [Left/Right] view  [PgUp/PgDn] scroll  [End] tail  [c] cancel  [r] rerun  [o] error log  [y] copy view  [q] quit"
             }
         }
     };
@@ -37,8 +37,17 @@ pub(crate) fn render_footer(frame: &mut Frame<'_>, area: Rect, state: &TuiState<
     } else {
         Line::from(hint)
     };
-    let footer =
-        Paragraph::new(Text::from(vec![line])).block(Block::default().borders(Borders::TOP));
+    let mut lines = vec![line];
+    if state.config_changed_on_disk {
+        lines.push(Line::from(Span::styled(
+            "config changed on disk - press R to reload",
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::LightRed)
+                .add_modifier(Modifier::BOLD),
+        )));
+    }
+    let footer = Paragraph::new(Text::from(lines)).block(Block::default().borders(Borders::TOP));
     frame.render_widget(footer, area);
 }
 
@@ -72,7 +81,7 @@ pub(crate) fn render_setup(frame: &mut Frame<'_>, area: Rect, state: &mut TuiSta
 pub(crate) fn render_monitor(frame: &mut Frame<'_>, area: Rect, state: &mut TuiState<'_>) {
     let rows = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(5), Constraint::Min(8)])
+        .constraints([Constraint::Length(6), Constraint::Min(8)])
         .split(area);
     render_monitor_progress(frame, rows[0], state);
 
@@ -167,7 +176,11 @@ pub(crate) fn render_detail_panel(
 pub(crate) fn render_monitor_progress(frame: &mut Frame<'_>, area: Rect, state: &TuiState<'_>) {
     let rows = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(3), Constraint::Length(2)])
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Length(2),
+        ])
         .split(area);
     let gauge = Gauge::default()
         .block(
@@ -189,7 +202,26 @@ pub(crate) fn render_monitor_progress(frame: &mut Frame<'_>, area: Rect, state:
         .label(format!("{}%", state.run_progress_percent()));
     frame.render_widget(gauge, rows[0]);
 
+    let operation_block = Block::default().borders(Borders::LEFT | Borders::RIGHT);
+    if let Some((operation_id, percent)) = state.selected_operation_progress() {
+        let operation_gauge = Gauge::default()
+            .block(operation_block)
+            .gauge_style(Style::default().fg(Color::LightCyan))
+            .percent(u16::from(percent))
+            .label(format!("{operation_id} {percent}%"));
+        frame.render_widget(operation_gauge, rows[1]);
+    } else if let Some(heartbeat) = state.selected_operation_heartbeat() {
+        frame.render_widget(
+            Paragraph::new(heartbeat)
+                .style(Style::default().fg(Color::LightYellow))
+                .block(operation_block),
+            rows[1],
+        );
+    } else {
+        frame.render_widget(Paragraph::new("").block(operation_block), rows[1]);
+    }
+
     let summary = Paragraph::new(Text::from(vec![Line::from(state.monitor_summary_line())]))
         .block(Block::default().borders(Borders::LEFT | Borders::RIGHT | Borders::BOTTOM));
-    frame.render_widget(summary, rows[1]);
+    frame.render_widget(summary, rows[2]);
 }
@@ -12,11 +12,10 @@ impl<'a> TuiState<'a> {
         };
         let run_duration = started_at.elapsed();
         let mut finished = None;
+        let mut events = Vec::new();
         loop {
             match receiver.try_recv() {
-                Ok(RunThreadMessage::Event(event)) => {
-                    self.live_events.push(event);
-                }
+                Ok(RunThreadMessage::Event(event)) => events.push(event),
                 Ok(RunThreadMessage::Finished(result)) => {
                     finished = Some(*result);
                     break;
@@ -28,6 +27,9 @@ impl<'a> TuiState<'a> {
                 }
             }
         }
+        for event in events {
+            self.push_live_event(event);
+        }
 
         let Some(message) = finished else {
             return;
@@ -37,7 +39,12 @@ impl<'a> TuiState<'a> {
             Ok(run) => {
                 let cancelled = run.outcome.cancelled;
                 let error_count = run.outcome.errors.len();
-                self.live_events = run.outcome.events.clone();
+                self.live_events.clear();
+                self.live_repeat = None;
+                self.live_queue = None;
+                for event in run.outcome.events.clone() {
+                    self.push_live_event(event);
+                }
                 self.last_run = Some(run);
                 self.last_run_duration = Some(run_duration);
                 if cancelled {
@@ -65,6 +72,47 @@ impl<'a> TuiState<'a> {
         self.detail_scroll = 0;
     }
 
+    // With `fold_repeats`, a log line equal to the previous event's line bumps a count on that
+    // event instead of growing the list; the count is rewritten into the message on every repeat,
+    // so there is nothing left to flush when the run ends mid-repeat. Scheduler ticks only update
+    // the queue depths shown in the run status.
+    pub(crate) fn push_live_event(&mut self, event: ExecutionEvent) {
+        if let ExecutionEvent::SchedulerTick { running, ready, .. } = event {
+            self.live_queue = Some((running, ready));
+            return;
+        }
+        let fold_repeats = self
+            .spec
+            .as_ref()
+            .is_some_and(|spec| spec.policy.execution.fold_repeats);
+        if let ExecutionEvent::Log {
+            operation_id,
+            message,
+        } = &event
+            && fold_repeats
+        {
+            if let Some(ExecutionEvent::Log {
+                operation_id: last_id,
+                message: last_message,
+            }) = self.live_events.last_mut()
+                && last_id == operation_id
+            {
+                let (original, count) = self
+                    .live_repeat
+                    .get_or_insert_with(|| (last_message.clone(), 1));
+                if original == message {
+                    *count += 1;
+                    *last_message = format!("{original} (x{count})");
+                    return;
+                }
+            }
+            self.live_repeat = Some((message.clone(), 1));
+        } else {
+            self.live_repeat = None;
+        }
+        self.live_events.push(event);
+    }
+
     pub(crate) fn start_run(&mut self) {
         if matches!(self.run_state, RunState::Running { .. }) {
             self.set_status("run already in progress");
@@ -105,6 +153,14 @@ impl<'a> TuiState<'a> {
         let cancellation_for_thread = cancellation.clone();
         let (tx, rx) = mpsc::channel();
         self.live_events.clear();
+        self.live_repeat = None;
+        self.live_queue = None;
+        self.timings = self
+            .spec
+            .as_ref()
+            .map(OperationTimings::load)
+            .unwrap_or_default();
+        self.last_run = None;
         self.last_run_duration = None;
         self.pending_exit_code = None;
         thread::spawn(move || {
@@ -126,6 +182,17 @@ impl<'a> TuiState<'a> {
         self.set_status("run started");
     }
 
+    // Re-plans first so reuse decisions reflect what the finished run left behind. The previous
+    // run's receiver was dropped when it went idle, so its worker cannot feed the new run.
+    pub(crate) fn rerun(&mut self) {
+        if matches!(self.run_state, RunState::Running { .. }) {
+            self.set_status("run already in progress");
+            return;
+        }
+        self.refresh();
+        self.start_run();
+    }
+
     pub(crate) fn cancel_run(&mut self) {
         match &self.run_state {
             RunState::Running { cancellation, .. } => {
@@ -189,6 +256,13 @@ pub(crate) fn collect_run_artifacts(
     if outcome.errors.is_empty() && !outcome.cancelled {
         save_reuse_state(&spec, &plan, &outcome);
     }
+    // Failing to write the env file only warns, so it never turns a finished build into a failure.
+    if let Err(message) = write_env_export(&spec, &outcome) {
+        let _ = event_sender.send(RunThreadMessage::Event(ExecutionEvent::Log {
+            operation_id: OperationId::resolve(),
+            message: format!("WARN: {message}"),
+        }));
+    }
 
     Ok(RunArtifacts {
         spec,
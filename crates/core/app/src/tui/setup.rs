@@ -1,4 +1,5 @@
 use super::*;
+use gaia_spec::{InputKindSpec, InputOptionSpec};
 
 impl<'a> TuiState<'a> {
     pub(crate) fn activate_setup_item(&mut self) {
@@ -8,7 +9,10 @@ impl<'a> TuiState<'a> {
                 self.begin_edit(SetupEditField::Branch, self.current_branch_value())
             }
             SetupItem::Target => {
-                self.begin_edit(SetupEditField::Target, self.current_target_value())
+                self.begin_edit(SetupEditField::Target, self.current_target_value());
+                if let Some(hint) = self.declared_input_hint("target") {
+                    self.set_status(format!("editing value ({hint})"));
+                }
             }
             SetupItem::Profile => self.cycle_profile(1),
             SetupItem::Jobs => self.begin_edit(SetupEditField::Jobs, self.current_jobs_value()),
@@ -132,13 +136,17 @@ impl<'a> TuiState<'a> {
                 self.set_status(format!("branch set to {}", self.current_branch_value()));
             }
             SetupEditField::Target => {
+                if let Err(message) = self.check_declared_input("target", &value) {
+                    self.set_status(message);
+                    return;
+                }
                 self.set_or_clear_override("input.target", &value);
                 self.refresh();
                 self.set_status(format!("target set to {}", self.current_target_value()));
             }
             SetupEditField::Jobs => {
-                if value.parse::<u32>().is_ok() {
-                    self.set_or_clear_override("execution.jobs", &value);
+                if let Ok(jobs) = value.parse::<u32>() {
+                    self.set_jobs_override(jobs);
                     self.refresh();
                     self.set_status(format!("jobs set to {}", self.current_jobs_value()));
                 } else {
@@ -148,6 +156,45 @@ impl<'a> TuiState<'a> {
         }
     }
 
+    // Empty values clear the override, so only non-empty edits are checked against the declaration.
+    pub(crate) fn check_declared_input(&self, name: &str, value: &str) -> Result<(), String> {
+        let Some(input) = self.declared_input(name) else {
+            return Ok(());
+        };
+        if value.is_empty() {
+            return Ok(());
+        }
+        input.check_value(value).map_err(|invalid| invalid.message)
+    }
+
+    pub(crate) fn declared_input_hint(&self, name: &str) -> Option<String> {
+        let input = self.declared_input(name)?;
+        let range = input.range_label();
+        match input.kind {
+            InputKindSpec::Integer | InputKindSpec::Float => Some(match range {
+                Some(range) => format!("{} {range}", input.kind.as_str()),
+                None => input.kind.as_str().to_string(),
+            }),
+            InputKindSpec::String => input
+                .pattern
+                .as_ref()
+                .map(|pattern| format!("string matching {pattern}")),
+            InputKindSpec::Datetime => {
+                Some("datetime: now, now+7d or an ISO-8601 timestamp".into())
+            }
+            _ => None,
+        }
+    }
+
+    fn declared_input(&self, name: &str) -> Option<&InputOptionSpec> {
+        self.spec
+            .as_ref()?
+            .inputs
+            .declared
+            .iter()
+            .find(|input| input.name == name)
+    }
+
     pub(crate) fn set_or_clear_override(&mut self, key: &str, value: &str) {
         if value.is_empty() {
             self.options
@@ -167,6 +214,16 @@ impl<'a> TuiState<'a> {
         }
     }
 
+    // Auto (0) drops the override so the config's `jobs` applies before the CPU count.
+    fn set_jobs_override(&mut self, jobs: u32) {
+        let value = if jobs == 0 {
+            String::new()
+        } else {
+            jobs.to_string()
+        };
+        self.set_or_clear_override("execution.jobs", &value);
+    }
+
     pub(crate) fn clear_override(&mut self, key: &str) {
         self.options
             .explicit_overrides
@@ -317,7 +374,7 @@ impl<'a> TuiState<'a> {
             .position(|value| *value == current)
             .unwrap_or(0) as i32;
         let next = (current_index + direction).clamp(0, (steps.len() - 1) as i32) as usize;
-        self.set_or_clear_override("execution.jobs", &steps[next].to_string());
+        self.set_jobs_override(steps[next]);
         self.refresh();
         self.set_status(format!("jobs set to {}", self.current_jobs_value()));
     }
@@ -380,6 +437,36 @@ impl<'a> TuiState<'a> {
         self.set_status(format!("loaded build {label}"));
     }
 
+    // `gaia tui --build <path>` opens a build file directly, listing it in the picker when
+    // discovery did not find it. A missing file leaves the picker open instead.
+    pub(crate) fn open_build(&mut self, path: &str) {
+        if !Path::new(path).is_file() {
+            tracing::warn!(
+                build = path,
+                "tui build file not found; opening the build picker"
+            );
+            self.screen = Screen::Picker;
+            self.set_status(format!("build file '{path}' not found; pick a build"));
+            return;
+        }
+        let index = match self
+            .build_entries
+            .iter()
+            .position(|entry| Path::new(&entry.path) == Path::new(path))
+        {
+            Some(index) => index,
+            None => {
+                self.build_entries.push(BuildEntry {
+                    label: path.to_string(),
+                    path: path.to_string(),
+                });
+                self.build_entries.len() - 1
+            }
+        };
+        self.build_list.select(Some(index));
+        self.open_selected_build();
+    }
+
     pub(crate) fn ensure_build_selection(&mut self) {
         let total = self.build_entries.len();
         match total {
@@ -18,13 +18,24 @@ pub(crate) struct TuiState<'a> {
     pub(crate) last_run: Option<RunArtifacts>,
     pub(crate) last_run_duration: Option<Duration>,
     pub(crate) live_events: Vec<ExecutionEvent>,
+    // Unfolded message and repeat count of the last live event when `fold_repeats` folded it.
+    pub(crate) live_repeat: Option<(String, usize)>,
+    // Running and ready operation counts from the latest scheduler tick of the live run.
+    pub(crate) live_queue: Option<(usize, usize)>,
+    // Run-time history read when a run starts, for the averages shown next to running operations.
+    pub(crate) timings: OperationTimings,
     pub(crate) run_state: RunState,
     pub(crate) status: String,
     pub(crate) status_since: Instant,
     pub(crate) edit_field: Option<SetupEditField>,
     pub(crate) edit_buffer: String,
     pub(crate) pending_exit_code: Option<(i32, Instant)>,
+    // Error log written for the selected failed operation, waiting for the main loop to page it.
+    pub(crate) pending_log_view: Option<PathBuf>,
     pub(crate) detail_follow_tail: bool,
+    // Config files behind the loaded state, with the modification time seen at the last refresh.
+    pub(crate) watched_config: Vec<(PathBuf, Option<SystemTime>)>,
+    pub(crate) config_changed_on_disk: bool,
 }
 
 impl<'a> TuiState<'a> {
@@ -66,13 +77,19 @@ impl<'a> TuiState<'a> {
             last_run: None,
             last_run_duration: None,
             live_events: Vec::new(),
+            live_repeat: None,
+            live_queue: None,
+            timings: OperationTimings::default(),
             run_state: RunState::Idle,
             status: "loading build state".into(),
             status_since: Instant::now(),
             edit_field: None,
             edit_buffer: String::new(),
             pending_exit_code: None,
+            pending_log_view: None,
             detail_follow_tail: true,
+            watched_config: Vec::new(),
+            config_changed_on_disk: false,
         }
     }
 
@@ -90,6 +107,7 @@ impl<'a> TuiState<'a> {
     }
 
     pub(crate) fn refresh(&mut self) {
+        self.snapshot_config_files();
         let spec = match try_resolve_config_with_options(&self.build, &self.options) {
             Ok(spec) => spec,
             Err(error) => {
@@ -130,6 +148,46 @@ impl<'a> TuiState<'a> {
         if let RunState::Running { spinner_tick, .. } = &mut self.run_state {
             *spinner_tick = spinner_tick.wrapping_add(1);
         }
+        self.check_config_on_disk();
+    }
+
+    // A config that no longer parses keeps the previous file list, so fixing it still shows up.
+    pub(crate) fn snapshot_config_files(&mut self) {
+        let files = config_file_set(&self.build).unwrap_or_else(|_| {
+            self.watched_config
+                .iter()
+                .map(|(path, _)| path.clone())
+                .collect()
+        });
+        self.watched_config = files
+            .into_iter()
+            .map(|path| {
+                let modified = file_modified(&path);
+                (path, modified)
+            })
+            .collect();
+        self.config_changed_on_disk = false;
+    }
+
+    // Only flags the change; the loaded state is replaced when the user reloads, never mid-run.
+    pub(crate) fn check_config_on_disk(&mut self) {
+        if !self.config_changed_on_disk {
+            self.config_changed_on_disk = self
+                .watched_config
+                .iter()
+                .any(|(path, seen)| file_modified(path) != *seen);
+        }
+    }
+
+    pub(crate) fn reload_config(&mut self) {
+        if matches!(self.run_state, RunState::Running { .. }) {
+            self.set_status("config reload waits until the run finishes");
+            return;
+        }
+        self.refresh();
+        if self.spec.is_some() {
+            self.set_status("reloaded config from disk");
+        }
     }
 
     pub(crate) fn should_exit(&self) -> Option<i32> {
@@ -138,3 +196,9 @@ impl<'a> TuiState<'a> {
             .and_then(|(code, deadline)| (Instant::now() >= *deadline).then_some(*code))
     }
 }
+
+fn file_modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
@@ -18,16 +18,31 @@ impl<'a> TuiState<'a> {
             }
             RunState::Running { started_at, .. } => {
                 let current = current_operation_label(&self.live_events).unwrap_or("starting");
+                let queue = self
+                    .live_queue
+                    .map(|(running, ready)| format!(" ({running} running, {ready} ready)"))
+                    .unwrap_or_default();
                 format!(
-                    "running: {current} {}",
+                    "running: {current} {}{queue}",
                     format_elapsed(started_at.elapsed())
                 )
             }
         }
     }
 
+    // A running build sizes progress from the executor's own count, which covers runs launched
+    // before the monitor planned anything.
+    pub(crate) fn run_progress_total(&self) -> usize {
+        match &self.run_state {
+            RunState::Running { .. } => {
+                live_operation_total(&self.live_events).unwrap_or_else(|| self.operation_total())
+            }
+            RunState::Idle => self.operation_total(),
+        }
+    }
+
     pub(crate) fn run_progress_percent(&self) -> u16 {
-        let total = self.operation_total();
+        let total = self.run_progress_total();
         if total == 0 {
             return 0;
         }
@@ -40,6 +55,34 @@ impl<'a> TuiState<'a> {
         ((completed as f64 / total as f64) * 100.0) as u16
     }
 
+    pub(crate) fn selected_operation_progress(&self) -> Option<(String, u8)> {
+        if !matches!(self.run_state, RunState::Running { .. }) {
+            return None;
+        }
+        let operation_id = self
+            .selected_operation()
+            .map(|operation| operation.id.as_str())
+            .or_else(|| current_operation_label(&self.live_events))?;
+        live_operation_progress(&self.live_events, operation_id)
+            .map(|percent| (operation_id.to_string(), percent))
+    }
+
+    pub(crate) fn selected_operation_heartbeat(&self) -> Option<String> {
+        if !matches!(self.run_state, RunState::Running { .. }) {
+            return None;
+        }
+        let operation_id = self
+            .selected_operation()
+            .map(|operation| operation.id.as_str())
+            .or_else(|| current_operation_label(&self.live_events))?;
+        live_operation_heartbeat(&self.live_events, operation_id).map(|elapsed_ms| {
+            format!(
+                "{operation_id} still running ({}, no output)",
+                format_silent_elapsed(elapsed_ms)
+            )
+        })
+    }
+
     pub(crate) fn run_elapsed_label(&self) -> String {
         match &self.run_state {
             RunState::Running { started_at, .. } => format_elapsed(started_at.elapsed()),
@@ -52,7 +95,7 @@ impl<'a> TuiState<'a> {
             RunState::Running { .. } => {
                 let current = current_operation_label(&self.live_events).unwrap_or("starting");
                 let completed = live_completed_count(&self.live_events);
-                let total = self.operation_total();
+                let total = self.run_progress_total();
                 format!(
                     "live: op={}  completed={}/{}  events={}  use Left/Right for Events|Logs|Reports",
                     current,
@@ -124,6 +167,11 @@ impl<'a> TuiState<'a> {
         if let Some(duration) = self.last_run_duration {
             lines.push(format!("tui build time: {}", format_elapsed(duration)));
         }
+        lines.extend(
+            phase_time_lines(&phase_timings(&run.plan, &run.outcome.spans))
+                .into_iter()
+                .map(|line| format!("tui {line}")),
+        );
         lines.push(format!("tui output dir: {}", run.spec.workspace.out_dir));
         lines.push(format!(
             "tui reports dir: {}",
@@ -1,6 +1,8 @@
 pub mod support;
 
 use gaia_app::{AppArgs, CommandOutcome, run_with_args};
+use std::fs;
+use std::path::Path;
 use support::{unique_dir, write_temp_build};
 
 #[test]
@@ -134,3 +136,43 @@ required = true
         outcome => panic!("expected successful ran outcome, got {outcome:?}"),
     }
 }
+
+#[test]
+fn run_keeps_a_successful_build_when_its_env_export_cannot_be_written() {
+    let run_out_dir = unique_dir("gaia-cli-env-export-out");
+    let blocker = unique_dir("gaia-cli-env-export-blocker");
+    fs::create_dir_all(Path::new(&blocker).parent().expect("scratch root")).expect("scratch root");
+    fs::write(&blocker, "not a directory").expect("blocking file");
+    let build = write_temp_build(&format!(
+        r#"
+build_name = "env-export-unwritable"
+
+[workspace]
+root_dir = "."
+build_dir = "build"
+out_dir = "{run_out_dir}"
+
+[execution]
+env_export = "{blocker}/build.env"
+
+[image]
+kind = "buildroot"
+defconfig = "dummy_defconfig"
+allow_fallback = true
+
+[[image.expected_images]]
+name = "rootfs.tar"
+format = "tar"
+required = true
+"#
+    ));
+
+    let run = run_with_args(AppArgs::parse_from(vec!["run".to_string(), build]));
+
+    assert_eq!(run.exit_code(), 0, "{run:?}");
+    assert!(
+        Path::new(&run_out_dir)
+            .join(".gaia/env-export-unwritable.reuse-state")
+            .is_file()
+    );
+}
@@ -91,3 +91,52 @@ paths = [".cache/gaia"]
     assert!(PathBuf::from(&build_dir).exists());
     assert!(PathBuf::from(&out_dir).exists());
 }
+
+#[test]
+fn clean_cache_target_keeps_build_and_out() {
+    let root_dir = unique_dir("gaia-cli-clean-cache-root");
+    let ccache_dir = PathBuf::from(&root_dir).join("build/buildroot-ccache");
+    let docker_cache = PathBuf::from(&root_dir).join(".gaia/docker-cache");
+    let build_file = PathBuf::from(&root_dir).join("build/kept.txt");
+    let out_dir = PathBuf::from(&root_dir).join("out");
+    fs::create_dir_all(&ccache_dir).expect("ccache dir");
+    fs::create_dir_all(&docker_cache).expect("docker cache dir");
+    fs::create_dir_all(&out_dir).expect("out dir");
+    fs::write(&build_file, "build").expect("build file");
+
+    let build = write_temp_build(&format!(
+        r#"
+build_name = "clean-cache"
+
+[workspace]
+root_dir = "{root_dir}"
+build_dir = "{root_dir}/build"
+out_dir = "{root_dir}/out"
+
+[image]
+kind = "buildroot"
+defconfig = "raspberrypi_defconfig"
+ccache = true
+"#
+    ));
+
+    let outcome = run_with_args(AppArgs::parse_from(["clean", &build, "--target", "cache"]));
+
+    match outcome {
+        CommandOutcome::Cleaned { report, .. } => {
+            assert_eq!(
+                report.removed,
+                vec![docker_cache.clone(), ccache_dir.clone()]
+            );
+            assert_eq!(
+                report.missing,
+                vec![PathBuf::from(&root_dir).join(".gaia/docker-home")]
+            );
+        }
+        other => panic!("expected cleaned outcome, got {other:?}"),
+    }
+    assert!(!ccache_dir.exists());
+    assert!(!docker_cache.exists());
+    assert!(build_file.exists());
+    assert!(out_dir.exists());
+}
@@ -1,6 +1,8 @@
 pub mod support;
 
-use gaia_app::{AppArgs, CommandOutcome, run_with_args};
+use gaia_app::{
+    AppArgs, CommandOutcome, input_report_lines, input_schema_lines, run_with_args, trace_lines,
+};
 use std::fs;
 use std::path::PathBuf;
 use support::{unique_dir, write_temp_build};
@@ -176,3 +178,223 @@ stage_files = ["missing-stage-file"]
     let _ = fs::remove_file(path);
     let _ = fs::remove_file(out_file);
 }
+
+#[test]
+fn resolve_show_inputs_reports_sources_and_fails_on_missing_required_inputs() {
+    let out_dir = unique_dir("gaia-cli-show-inputs-out");
+    let build_dir = unique_dir("gaia-cli-show-inputs-build");
+    let path = write_temp_build(&format!(
+        r#"
+build_name = "show-inputs"
+preset = "ci"
+
+[workspace]
+root_dir = "."
+build_dir = "{build_dir}"
+out_dir = "{out_dir}"
+
+[image]
+kind = "starting-point"
+rootfs_path = "/tmp/rootfs"
+
+[inputs.profile]
+kind = "enum"
+default = "release"
+choices = ["dev", "ci", "release"]
+
+[inputs.variant]
+kind = "string"
+default = "base"
+
+[inputs.jobs]
+kind = "integer"
+default = "4"
+
+[inputs.build_number]
+kind = "integer"
+required = true
+
+[inputs.notes]
+kind = "string"
+
+[presets.ci]
+overrides = [["input.profile", "ci"]]
+"#,
+    ));
+
+    let outcome = run_with_args(AppArgs::parse_from([
+        "resolve",
+        path.as_str(),
+        "--show-inputs",
+        "--set",
+        "input.jobs=8",
+    ]));
+
+    assert_eq!(outcome.exit_code(), 2);
+    let CommandOutcome::Resolved { spec, show_inputs } = &outcome else {
+        panic!("expected resolved outcome, got {outcome:?}");
+    };
+    assert!(show_inputs);
+    let lines = input_report_lines(spec);
+    let row = |name: &str| {
+        lines
+            .iter()
+            .find(|line| line.starts_with(&format!("{name} ")))
+            .map(|line| line.split_whitespace().collect::<Vec<_>>())
+            .unwrap_or_else(|| panic!("missing row for {name}: {lines:?}"))
+    };
+    assert_eq!(row("profile"), ["profile", "enum", "ci", "preset"]);
+    assert_eq!(row("variant"), ["variant", "string", "base", "default"]);
+    assert_eq!(row("jobs"), ["jobs", "integer", "8", "cli-override"]);
+    assert_eq!(
+        row("build_number"),
+        ["build_number", "integer", "-", "MISSING", "(required)"]
+    );
+    assert_eq!(row("notes"), ["notes", "string", "-", "unset"]);
+    assert_eq!(
+        lines.last().map(String::as_str),
+        Some("missing required inputs: build_number")
+    );
+
+    let supplied = run_with_args(AppArgs::parse_from([
+        "resolve",
+        path.as_str(),
+        "--show-inputs",
+        "--set",
+        "input.build_number=42",
+    ]));
+    assert_eq!(supplied.exit_code(), 0);
+
+    let _ = fs::remove_file(path);
+}
+
+#[test]
+fn inputs_command_describes_what_each_input_accepts() {
+    let out_dir = unique_dir("gaia-cli-inputs-out");
+    let build_dir = unique_dir("gaia-cli-inputs-build");
+    let path = write_temp_build(&format!(
+        r#"
+build_name = "inputs"
+
+[workspace]
+root_dir = "."
+build_dir = "{build_dir}"
+out_dir = "{out_dir}"
+
+[image]
+kind = "starting-point"
+rootfs_path = "/tmp/rootfs"
+
+[inputs.profile]
+kind = "enum"
+description = "Release profile"
+default = "release"
+choices = ["dev", "release"]
+
+[inputs.jobs]
+kind = "integer"
+min = 1
+max = 64
+
+[inputs.build_number]
+kind = "integer"
+required = true
+"#,
+    ));
+
+    let outcome = run_with_args(AppArgs::parse_from(["inputs", path.as_str()]));
+
+    assert_eq!(outcome.exit_code(), 0);
+    let CommandOutcome::Inputs { spec } = &outcome else {
+        panic!("expected inputs outcome, got {outcome:?}");
+    };
+    let lines = input_schema_lines(spec);
+    let row = |name: &str| {
+        let index = lines
+            .iter()
+            .position(|line| line.starts_with(&format!("{name} ")))
+            .unwrap_or_else(|| panic!("missing row for {name}: {lines:?}"));
+        (
+            lines[index].split_whitespace().collect::<Vec<_>>(),
+            lines.get(index + 1).cloned().unwrap_or_default(),
+        )
+    };
+    let (profile, description) = row("profile");
+    assert_eq!(
+        profile,
+        ["profile", "enum", "no", "release", "dev", "|", "release"]
+    );
+    assert_eq!(description, "    Release profile");
+    assert_eq!(row("jobs").0, ["jobs", "integer", "no", "-", "1..=64"]);
+    assert_eq!(
+        row("build_number").0,
+        ["build_number", "integer", "yes", "-", "-"]
+    );
+
+    let _ = fs::remove_file(path);
+}
+
+#[test]
+fn resolve_trace_masks_secret_values_and_names_the_untraced_preset() {
+    let path = write_temp_build(
+        r#"
+build_name = "trace-masking"
+preset = "ci"
+
+[workspace]
+root_dir = "."
+build_dir = "build"
+out_dir = "out"
+
+[image]
+kind = "starting-point"
+rootfs_path = "/tmp/rootfs"
+
+[inputs.api_token]
+kind = "string"
+default = "from-file"
+
+[presets.ci]
+overrides = [["input.api_token", "from-preset"]]
+"#,
+    );
+
+    let traced = |extra: &[&str]| {
+        let mut args = vec![
+            "resolve",
+            path.as_str(),
+            "--trace",
+            "inputs.api_token.default",
+        ];
+        args.extend_from_slice(extra);
+        match run_with_args(AppArgs::parse_from(args)) {
+            CommandOutcome::Traced {
+                trace,
+                cli_override,
+                preset,
+                reporting,
+            } => trace_lines(
+                &trace,
+                cli_override.as_deref(),
+                preset.as_deref(),
+                &reporting,
+            ),
+            outcome => panic!("expected traced outcome, got {outcome:?}"),
+        }
+    };
+
+    let from_file = traced(&[]);
+    assert!(from_file[0].starts_with("inputs.api_token.default = *** (from "));
+    assert_eq!(
+        from_file.last().map(String::as_str),
+        Some("  not traced: overrides from preset 'ci' apply after these layers")
+    );
+    assert!(!from_file.concat().contains("from-file"));
+
+    let from_cli = traced(&["--set", "inputs.api_token.default=s3cret"]);
+    assert_eq!(
+        from_cli[0],
+        "inputs.api_token.default = \"***\" (from --set)"
+    );
+    assert!(!from_cli.concat().contains("s3cret"));
+}
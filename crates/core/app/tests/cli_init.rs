@@ -0,0 +1,94 @@
+pub mod support;
+
+use gaia_app::{AppArgs, CommandOutcome, INIT_TEMPLATES, run_with_args};
+use std::fs;
+use std::path::PathBuf;
+use support::unique_dir;
+
+#[test]
+fn every_init_template_writes_a_resolvable_build() {
+    for template in INIT_TEMPLATES {
+        // No Cargo.toml above the scaffold, so its paths must not depend on the nearest project
+        // root or on the directory the test runs from.
+        let root = unique_dir(&format!("gaia-cli-init-{}", template.name));
+
+        let init = run_with_args(AppArgs::parse_from([
+            "init",
+            root.as_str(),
+            "--template",
+            template.name,
+        ]));
+
+        match init {
+            CommandOutcome::Initialized {
+                template: selected,
+                written,
+                ..
+            } => {
+                assert_eq!(selected, template.name);
+                assert_eq!(written.len(), template.file_paths().count());
+            }
+            other => panic!(
+                "expected initialized outcome for {}, got {other:?}",
+                template.name
+            ),
+        }
+        for relative in template.file_paths() {
+            assert!(
+                PathBuf::from(&root).join(relative).is_file(),
+                "{} should write {relative}",
+                template.name
+            );
+        }
+        let validate = run_with_args(AppArgs::parse_from([
+            "validate".to_string(),
+            format!("{root}/build.toml"),
+        ]));
+        assert!(
+            matches!(validate, CommandOutcome::Validated { ref validation, .. } if validation.errors.is_empty()),
+            "{} scaffold should validate, got {validate:?}",
+            template.name
+        );
+    }
+}
+
+#[test]
+fn init_refuses_to_overwrite_without_force() {
+    let root = unique_dir("gaia-cli-init-collision");
+    fs::create_dir_all(&root).expect("init root");
+    fs::write(
+        PathBuf::from(&root).join("build.toml"),
+        "build_name = \"keep\"\n",
+    )
+    .expect("existing build");
+
+    let refused = run_with_args(AppArgs::parse_from(["init", root.as_str()]));
+    assert!(
+        matches!(refused, CommandOutcome::Failed { ref message } if message.contains("--force"))
+    );
+    assert_eq!(
+        fs::read_to_string(PathBuf::from(&root).join("build.toml")).expect("build"),
+        "build_name = \"keep\"\n"
+    );
+
+    let forced = run_with_args(AppArgs::parse_from(["init", root.as_str(), "--force"]));
+    assert!(matches!(
+        forced,
+        CommandOutcome::Initialized {
+            template: "rpi-cm5io",
+            ..
+        }
+    ));
+}
+
+#[test]
+fn init_lists_templates_without_a_directory() {
+    let listed = run_with_args(AppArgs::parse_from(["init", "--list-templates"]));
+
+    match listed {
+        CommandOutcome::InitTemplates { templates } => {
+            assert_eq!(templates.len(), INIT_TEMPLATES.len());
+        }
+        other => panic!("expected template list, got {other:?}"),
+    }
+}
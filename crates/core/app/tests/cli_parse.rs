@@ -1,6 +1,9 @@
 pub mod support;
 
-use gaia_app::{AppArgs, AppCommand};
+use gaia_app::{
+    AppArgs, AppCommand, ColorChoice, CommandOutcome, DoctorStatus, PlanOutputFormat, ProgressMode,
+    run_with_args,
+};
 
 #[test]
 fn parses_help_and_version_commands() {
@@ -89,3 +92,678 @@ fn parses_clean_command_flags() {
     assert_eq!(args.clean.paths, vec![".cache/gaia".to_string()]);
     assert!(args.clean.dry_run);
 }
+
+#[test]
+fn profile_flag_selects_a_config_profile_except_for_clean() {
+    let run = AppArgs::parse_from([
+        "run",
+        "examples/default-workspace/configs/default.toml",
+        "--profile",
+        "ci",
+    ]);
+    assert_eq!(run.profile.as_deref(), Some("ci"));
+    assert_eq!(run.clean.profile, None);
+
+    let clean = AppArgs::parse_from([
+        "clean",
+        "examples/default-workspace/configs/default.toml",
+        "--profile",
+        "dist",
+    ]);
+    assert_eq!(clean.profile, None);
+    assert_eq!(clean.clean.profile.as_deref(), Some("dist"));
+}
+
+#[test]
+fn parses_plan_lint_flag() {
+    let args = AppArgs::parse_from([
+        "plan",
+        "examples/default-workspace/configs/default.toml",
+        "--lint",
+    ]);
+
+    assert_eq!(args.command, AppCommand::Plan);
+    assert!(args.plan.lint);
+    assert!(!AppArgs::parse_from(["plan"]).plan.lint);
+}
+
+#[test]
+fn parses_plan_output_format_values() {
+    let format = |flag: &[&str]| {
+        let mut args = vec!["plan", "examples/default-workspace/configs/default.toml"];
+        args.extend_from_slice(flag);
+        AppArgs::parse_from(args).plan.output_format
+    };
+
+    assert_eq!(format(&[]), PlanOutputFormat::Table);
+    assert_eq!(format(&["--output-format", "csv"]), PlanOutputFormat::Csv);
+    assert_eq!(format(&["--output-format=json"]), PlanOutputFormat::Json);
+    let mistyped = AppArgs::parse_from(["plan", "build.toml", "--output-format", "jsn"]);
+    assert_eq!(mistyped.plan.output_format, PlanOutputFormat::Table);
+    assert_eq!(
+        mistyped.usage_errors,
+        ["invalid value 'jsn' for --output-format; expected one of: table, json, csv"]
+    );
+    match run_with_args(mistyped) {
+        outcome @ CommandOutcome::Failed { .. } => assert_eq!(outcome.exit_code(), 1),
+        outcome => panic!("expected failed outcome, got {outcome:?}"),
+    }
+}
+
+#[test]
+fn parses_no_color_flag() {
+    let args = AppArgs::parse_from([
+        "run",
+        "examples/default-workspace/configs/default.toml",
+        "--no-color",
+    ]);
+
+    assert_eq!(args.color, ColorChoice::Never);
+    assert_eq!(AppArgs::parse_from(["run"]).color, ColorChoice::Auto);
+}
+
+#[test]
+fn parses_color_flag_values() {
+    let color = |flag: &[&str]| {
+        let mut args = vec!["run", "examples/default-workspace/configs/default.toml"];
+        args.extend_from_slice(flag);
+        AppArgs::parse_from(args).color
+    };
+
+    assert_eq!(color(&["--color=always"]), ColorChoice::Always);
+    assert_eq!(color(&["--color", "never"]), ColorChoice::Never);
+    assert_eq!(color(&["--color=auto"]), ColorChoice::Auto);
+    assert_eq!(
+        AppArgs::parse_from(["run", "build.toml", "--color=sometimes"]).usage_errors,
+        ["invalid value 'sometimes' for --color; expected one of: auto, always, never"]
+    );
+    assert_eq!(
+        AppArgs::parse_from(["run", "build.toml", "--color"]).usage_errors,
+        ["invalid value (none) for --color; expected one of: auto, always, never"]
+    );
+}
+
+#[test]
+fn parses_run_resume_flag() {
+    let args = AppArgs::parse_from([
+        "run",
+        "examples/default-workspace/configs/default.toml",
+        "--resume",
+    ]);
+
+    assert_eq!(args.command, AppCommand::Run);
+    assert!(args.run.resume);
+}
+
+#[test]
+fn parses_run_from_operation() {
+    let build = "examples/default-workspace/configs/default.toml";
+    assert_eq!(
+        AppArgs::parse_from(["run", build, "--from", "image:build"])
+            .run
+            .from
+            .as_deref(),
+        Some("image:build")
+    );
+    assert_eq!(AppArgs::parse_from(["run", build]).run.from, None);
+}
+
+#[test]
+fn parses_run_phase_selection() {
+    let build = "examples/default-workspace/configs/default.toml";
+    let args = AppArgs::parse_from([
+        "run",
+        build,
+        "--phases",
+        "sources,artifacts",
+        "--phases",
+        "installs",
+        "--skip-phases",
+        "checkpoints",
+    ]);
+
+    assert_eq!(args.run.phases, ["sources,artifacts", "installs"]);
+    assert_eq!(args.run.skip_phases, ["checkpoints"]);
+    assert!(AppArgs::parse_from(["run", build]).run.phases.is_empty());
+}
+
+#[test]
+fn parses_fail_fast_flags_as_policy_overrides() {
+    let build = "examples/default-workspace/configs/default.toml";
+    for flag in ["--no-fail-fast", "--fail-fast=false"] {
+        assert_eq!(
+            AppArgs::parse_from(["run", build, flag]).explicit_overrides,
+            vec![("policy.failure.fail_fast".to_string(), "false".to_string())]
+        );
+    }
+    assert_eq!(
+        AppArgs::parse_from(["run", build, "--fail-fast"]).explicit_overrides,
+        vec![("policy.failure.fail_fast".to_string(), "true".to_string())]
+    );
+}
+
+#[test]
+fn parses_tui_build_flag() {
+    let tui = AppArgs::parse_from(["tui", "--build", "configs/adhoc.toml"]);
+    assert_eq!(tui.command, AppCommand::Tui);
+    assert_eq!(tui.tui.build.as_deref(), Some("configs/adhoc.toml"));
+    assert_eq!(tui.build, "examples/default-workspace/configs/default.toml");
+    assert_eq!(AppArgs::parse_from(["tui"]).tui.build, None);
+}
+
+#[test]
+fn parses_run_quiet_flag() {
+    let build = "examples/default-workspace/configs/default.toml";
+    assert!(AppArgs::parse_from(["run", build, "--quiet"]).run.quiet);
+    assert!(AppArgs::parse_from(["run", build, "-q"]).run.quiet);
+    assert!(!AppArgs::parse_from(["run", build]).run.quiet);
+}
+
+#[test]
+fn run_dry_run_prints_commands_without_executing() {
+    let out_dir = support::unique_dir("gaia-cli-dry-run-out");
+    let build_dir = support::unique_dir("gaia-cli-dry-run-build");
+    let args = AppArgs::parse_from(vec![
+        "run".to_string(),
+        support::smoke_example_build_path(),
+        "--dry-run".to_string(),
+        "--set".to_string(),
+        format!("workspace.out_dir={out_dir}"),
+        "--set".to_string(),
+        format!("workspace.build_dir={build_dir}"),
+    ]);
+    assert!(args.run.dry_run);
+
+    match run_with_args(args) {
+        CommandOutcome::DryRun { operations, .. } => {
+            let prepare = operations
+                .iter()
+                .find(|operation| operation.operation_id.as_str() == "image:prepare")
+                .expect("image prepare operation");
+            let rendered = prepare
+                .commands
+                .iter()
+                .map(|command| command.render())
+                .collect::<Vec<_>>();
+            assert_eq!(rendered.len(), 2, "{rendered:?}");
+            assert!(rendered[0].contains("make O="), "{rendered:?}");
+            assert!(rendered[0].contains("defconfig BR2_DEFCONFIG="));
+        }
+        other => panic!("expected dry-run outcome, got {other:?}"),
+    }
+    assert!(!std::path::Path::new(&out_dir).exists());
+}
+
+#[test]
+fn run_with_several_builds_runs_them_in_order_and_stops_at_a_failure() {
+    let run = |extra: &[&str]| {
+        let mut args = vec![
+            "run".to_string(),
+            support::smoke_example_build_path(),
+            "missing-build.toml".to_string(),
+            support::smoke_example_build_path(),
+            "--dry-run".to_string(),
+        ];
+        args.extend(extra.iter().map(|arg| arg.to_string()));
+        AppArgs::parse_from(args)
+    };
+    let args = run(&[]);
+    assert_eq!(
+        args.run.extra_builds,
+        [
+            "missing-build.toml".to_string(),
+            support::smoke_example_build_path()
+        ]
+    );
+    assert!(!args.run.continue_on_error);
+
+    let outcome = run_with_args(args);
+    let CommandOutcome::RanBatch { builds } = &outcome else {
+        panic!("expected batch outcome, got {outcome:?}");
+    };
+    let exit_codes = builds
+        .iter()
+        .map(|build| build.exit_code)
+        .collect::<Vec<_>>();
+    assert_eq!(exit_codes, [Some(0), Some(1), None]);
+    assert_eq!(builds[1].build, "missing-build.toml");
+    assert_eq!(outcome.exit_code(), 1);
+
+    let CommandOutcome::RanBatch { builds } = run_with_args(run(&["--continue-on-error"])) else {
+        panic!("expected batch outcome");
+    };
+    assert_eq!(builds[2].exit_code, Some(0));
+}
+
+#[test]
+fn run_with_several_builds_rejects_dump_plan_before_running_any_build() {
+    let dump_path =
+        std::path::PathBuf::from(support::unique_dir("gaia-batch-dump-plan")).join("plan.json");
+    let args = AppArgs::parse_from(vec![
+        "run".to_string(),
+        support::smoke_example_build_path(),
+        support::smoke_example_build_path(),
+        "--dry-run".to_string(),
+        "--dump-plan".to_string(),
+        dump_path.display().to_string(),
+    ]);
+
+    let CommandOutcome::Failed { message } = run_with_args(args) else {
+        panic!("expected failed outcome");
+    };
+    assert!(message.contains("--dump-plan"), "{message}");
+    assert!(!dump_path.exists());
+}
+
+#[test]
+fn parses_run_progress_modes() {
+    let default = AppArgs::parse_from(["run", "build.toml"]);
+    assert_eq!(default.run.progress, ProgressMode::Full);
+
+    let compact = AppArgs::parse_from(["run", "build.toml", "--progress", "compact"]);
+    assert_eq!(compact.run.progress, ProgressMode::Compact);
+    assert!(compact.run.extra_builds.is_empty());
+
+    let full = AppArgs::parse_from(["run", "build.toml", "--progress=compact", "--progress=full"]);
+    assert_eq!(full.run.progress, ProgressMode::Full);
+    assert!(full.usage_errors.is_empty());
+
+    assert_eq!(
+        AppArgs::parse_from(["run", "build.toml", "--progress", "quiet"]).usage_errors,
+        ["invalid value 'quiet' for --progress; expected one of: full, compact"]
+    );
+}
+
+#[test]
+fn parses_run_trace_exec() {
+    assert!(!AppArgs::parse_from(["run", "build.toml"]).run.trace_exec);
+
+    let traced = AppArgs::parse_from(["run", "build.toml", "--trace-exec"]);
+    assert!(traced.run.trace_exec);
+    assert!(traced.run.extra_builds.is_empty());
+}
+
+#[test]
+fn strict_run_refuses_a_build_with_validation_warnings() {
+    let root_dir = support::unique_dir("gaia-strict-run");
+    std::fs::create_dir_all(&root_dir).expect("root dir");
+    let build = support::write_temp_build(&format!(
+        r#"
+build_name = "strict-warnings"
+
+[workspace]
+root_dir = "{root_dir}"
+build_dir = "build"
+out_dir = "out"
+
+[reporting]
+summary = false
+provenance = false
+manifest = false
+sbom = false
+
+[image]
+kind = "starting-point"
+rootfs_path = "{root_dir}"
+"#
+    ));
+    let run = |extra: &[&str]| {
+        let mut args = vec!["run".to_string(), build.clone(), "--dry-run".to_string()];
+        args.extend(extra.iter().map(|arg| arg.to_string()));
+        AppArgs::parse_from(args)
+    };
+
+    assert!(matches!(
+        run_with_args(run(&[])),
+        CommandOutcome::DryRun { .. }
+    ));
+    let args = run(&["--strict"]);
+    assert!(args.run.strict);
+    match run_with_args(args) {
+        CommandOutcome::Failed { message } => assert_eq!(
+            message,
+            "refusing to dry-run build 'strict-warnings': 1 validation error(s) (1 promoted from warnings by --strict)"
+        ),
+        other => panic!("expected strict refusal, got {other:?}"),
+    }
+
+    let _ = std::fs::remove_dir_all(root_dir);
+    let _ = std::fs::remove_file(build);
+}
+
+#[test]
+fn lint_fails_on_convention_findings_only_when_strict() {
+    let build = support::write_temp_build(
+        r#"
+build_name = "lint-findings"
+"#,
+    );
+    let lint = |extra: &[&str]| {
+        let mut args = vec!["lint".to_string(), build.clone()];
+        args.extend(extra.iter().map(|arg| arg.to_string()));
+        AppArgs::parse_from(args)
+    };
+
+    let outcome = run_with_args(lint(&[]));
+    match &outcome {
+        CommandOutcome::Linted { lint, .. } => {
+            assert_eq!(lint.diagnostics[0].code, "build_version_missing");
+            assert!(lint.errors.is_empty());
+        }
+        other => panic!("expected lint findings, got {other:?}"),
+    }
+    assert_eq!(outcome.exit_code(), 0);
+
+    let args = lint(&["--strict"]);
+    assert_eq!(args.command, AppCommand::Lint);
+    assert!(args.lint.strict);
+    assert_eq!(run_with_args(args).exit_code(), 2);
+
+    let _ = std::fs::remove_file(build);
+}
+
+#[test]
+fn providers_command_lists_builtin_providers_with_usage() {
+    let args = AppArgs::parse_from(vec!["providers".to_string(), support::config_path()]);
+    assert_eq!(args.command, AppCommand::Providers);
+
+    match run_with_args(args) {
+        CommandOutcome::Providers { providers, .. } => {
+            let rust = providers
+                .iter()
+                .find(|provider| provider.id == "artifact.rust")
+                .expect("rust provider listed");
+            assert!(rust.used && rust.supported);
+            assert_eq!(rust.description, "Builds a Rust package with cargo.");
+            let go = providers
+                .iter()
+                .find(|provider| provider.id == "artifact.go")
+                .expect("go provider listed");
+            assert!(!go.used);
+            assert_eq!(
+                providers
+                    .iter()
+                    .filter(|provider| provider.domain == "image")
+                    .count(),
+                2
+            );
+        }
+        other => panic!("expected providers outcome, got {other:?}"),
+    }
+}
+
+#[test]
+fn providers_json_describes_config_keys_and_operations() {
+    let args = AppArgs::parse_from(vec![
+        "providers".to_string(),
+        support::config_path(),
+        "--json".to_string(),
+    ]);
+    assert!(args.providers.json);
+
+    match run_with_args(args) {
+        CommandOutcome::Providers {
+            providers, json, ..
+        } => {
+            assert!(json);
+            let buildroot = providers
+                .iter()
+                .find(|provider| provider.id == "image.buildroot")
+                .expect("buildroot provider listed");
+            assert_eq!(
+                serde_json::to_value(buildroot).expect("listing json"),
+                serde_json::json!({
+                    "domain": "image",
+                    "id": "image.buildroot",
+                    "kind": "buildroot",
+                    "description": buildroot.description,
+                    "config_keys": buildroot.config_keys,
+                    "phase": "images",
+                    "operations": ["prepare-image", "build-image"],
+                    "used": true,
+                    "supported": true,
+                })
+            );
+            assert_eq!(buildroot.config_keys[0], "image.kind");
+            assert!(
+                buildroot
+                    .config_keys
+                    .iter()
+                    .any(|key| key == "image.defconfig")
+            );
+            assert!(
+                !buildroot
+                    .config_keys
+                    .iter()
+                    .any(|key| key == "image.rootfs_path" || key == "image.feed")
+            );
+        }
+        other => panic!("expected providers outcome, got {other:?}"),
+    }
+}
+
+#[test]
+fn plan_explain_skip_lists_providers_without_operations() {
+    let args = AppArgs::parse_from(vec![
+        "plan".to_string(),
+        support::config_path(),
+        "--explain-skip".to_string(),
+    ]);
+    assert!(args.plan.explain_skip);
+
+    match run_with_args(args) {
+        CommandOutcome::Planned { spec, skipped, .. } => {
+            let reason = |id: &str| {
+                skipped
+                    .iter()
+                    .find(|skip| skip.id == id)
+                    .map(|skip| skip.reason.as_str())
+            };
+            assert_eq!(reason("artifact.rust"), None);
+            assert_eq!(reason("artifact.go"), Some("no artifacts declared"));
+            let unselected = skipped
+                .iter()
+                .filter(|skip| skip.domain == "image")
+                .collect::<Vec<_>>();
+            assert_eq!(unselected.len(), 1);
+            assert_eq!(
+                unselected[0].reason,
+                format!("image kind is {}", spec.image.provider_kind().as_str())
+            );
+        }
+        other => panic!("expected planned outcome, got {other:?}"),
+    }
+}
+
+#[test]
+fn plan_explain_checkpoints_lists_each_checkpoint_anchor() {
+    let args = AppArgs::parse_from(vec![
+        "plan".to_string(),
+        support::config_path(),
+        "--explain-checkpoints".to_string(),
+    ]);
+    assert!(args.plan.explain_checkpoints);
+
+    let CommandOutcome::Planned {
+        checkpoint_inputs, ..
+    } = run_with_args(args)
+    else {
+        panic!("expected planned outcome");
+    };
+    assert_eq!(checkpoint_inputs.len(), 1);
+    assert_eq!(checkpoint_inputs[0].0.as_str(), "base-image");
+    assert_eq!(checkpoint_inputs[0].1.anchor.as_str(), "image:build");
+    assert!(checkpoint_inputs[0].1.inputs.is_empty());
+}
+
+#[test]
+fn plan_dump_plan_writes_the_plan_as_json() {
+    let dump_path =
+        std::path::PathBuf::from(support::unique_dir("gaia-dump-plan")).join("plan.json");
+    let args = AppArgs::parse_from(vec![
+        "plan".to_string(),
+        support::config_path(),
+        "--dump-plan".to_string(),
+        dump_path.display().to_string(),
+    ]);
+    assert_eq!(args.plan.dump_plan, args.run.dump_plan);
+
+    let CommandOutcome::Planned { plan, .. } = run_with_args(args) else {
+        panic!("expected planned outcome");
+    };
+    let dumped: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&dump_path).expect("plan dump written"))
+            .expect("plan dump is json");
+    let operations = dumped["operations"].as_array().expect("operations");
+    assert_eq!(operations.len(), plan.operations.len());
+    for (dumped, operation) in operations.iter().zip(&plan.operations) {
+        assert_eq!(dumped["id"], operation.id.as_str());
+        assert_eq!(
+            dumped["depends_on"],
+            serde_json::json!(
+                operation
+                    .depends_on
+                    .iter()
+                    .map(|dependency| dependency.as_str())
+                    .collect::<Vec<_>>()
+            )
+        );
+    }
+
+    let _ = std::fs::remove_dir_all(dump_path.parent().expect("dump dir"));
+}
+
+#[test]
+fn doctor_checks_tools_of_used_providers_and_workspace_dirs() {
+    let args = AppArgs::parse_from(vec!["doctor".to_string(), support::config_path()]);
+    assert_eq!(args.command, AppCommand::Doctor);
+
+    let outcome = run_with_args(args);
+    match &outcome {
+        CommandOutcome::Doctor { checks, .. } => {
+            let cargo = checks
+                .iter()
+                .find(|check| check.name == "tool cargo (artifact.rust)")
+                .expect("rust artifacts need cargo");
+            assert_eq!(cargo.status, DoctorStatus::Pass);
+            assert!(
+                !checks
+                    .iter()
+                    .any(|check| check.name.starts_with("tool go "))
+            );
+            assert_eq!(
+                checks
+                    .iter()
+                    .filter(|check| check.name.starts_with("workspace "))
+                    .count(),
+                3
+            );
+            assert!(
+                checks
+                    .iter()
+                    .any(|check| check.name.starts_with("disk space "))
+            );
+            let failed = checks
+                .iter()
+                .any(|check| check.status == DoctorStatus::Fail);
+            assert_eq!(outcome.exit_code() != 0, failed);
+        }
+        other => panic!("expected doctor outcome, got {other:?}"),
+    }
+}
+
+#[test]
+fn exec_runs_the_command_after_the_separator_from_the_workspace_root() {
+    let args = AppArgs::parse_from(vec![
+        "exec".to_string(),
+        support::config_path(),
+        "--dry-run".to_string(),
+        "--".to_string(),
+        "sh".to_string(),
+        "-c".to_string(),
+        "exit 7".to_string(),
+        "--dry-run".to_string(),
+    ]);
+    assert_eq!(args.command, AppCommand::Exec);
+    assert!(args.exec.dry_run);
+    assert_eq!(args.exec.command, ["sh", "-c", "exit 7", "--dry-run"]);
+
+    match run_with_args(args.clone()) {
+        CommandOutcome::Executed {
+            spec,
+            command,
+            dry_run: true,
+            exit_code: 0,
+        } => {
+            assert_eq!(command.program, "sh");
+            assert_eq!(
+                command.cwd.as_deref(),
+                Some(spec.workspace.root_dir.as_str())
+            );
+            assert!(
+                command
+                    .env
+                    .contains(&("GAIA_BUILD_DIR".into(), spec.workspace.build_dir.clone()))
+            );
+        }
+        other => panic!("expected exec dry run, got {other:?}"),
+    }
+
+    let outcome = run_with_args(AppArgs {
+        exec: gaia_app::ExecArgs {
+            dry_run: false,
+            ..args.exec
+        },
+        ..args
+    });
+    assert!(
+        matches!(outcome, CommandOutcome::Executed { exit_code: 7, .. }),
+        "{outcome:?}"
+    );
+    assert_eq!(outcome.exit_code(), 7);
+}
+
+#[test]
+fn schema_command_emits_config_schema() {
+    let args = AppArgs::parse_from(["schema"]);
+    assert_eq!(args.command, AppCommand::Schema);
+
+    match run_with_args(args) {
+        CommandOutcome::Schema { schema } => {
+            let properties = &schema["properties"];
+            for section in [
+                "workspace",
+                "sources",
+                "artifacts",
+                "stage",
+                "image",
+                "checkpoints",
+                "inputs",
+            ] {
+                assert!(properties.get(section).is_some(), "missing {section}");
+            }
+        }
+        other => panic!("expected schema outcome, got {other:?}"),
+    }
+}
+
+#[test]
+fn parses_resolve_trace_flag() {
+    let args = AppArgs::parse_from([
+        "resolve",
+        "examples/default-workspace/configs/default.toml",
+        "--trace",
+        "image.defconfig",
+    ]);
+
+    assert_eq!(args.command, AppCommand::Resolve);
+    assert_eq!(args.resolve.trace.as_deref(), Some("image.defconfig"));
+    assert!(!args.resolve.show_inputs);
+
+    let args = AppArgs::parse_from([
+        "resolve",
+        "examples/default-workspace/configs/default.toml",
+        "--show-inputs",
+    ]);
+    assert!(args.resolve.show_inputs);
+}
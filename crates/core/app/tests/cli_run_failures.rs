@@ -24,6 +24,7 @@ fn run_command_surfaces_execution_failures_from_backend_errors() {
         format!("workspace.out_dir={run_out_dir}"),
         "--set".to_string(),
         format!("workspace.build_dir={run_build_dir}"),
+        "--json-summary".to_string(),
     ]));
 
     assert_eq!(run.exit_code(), 4);
@@ -34,8 +35,30 @@ fn run_command_surfaces_execution_failures_from_backend_errors() {
             validation,
             plan_diagnostics,
             execution_errors,
+            phase_timings,
+            json_summary,
             ..
         } => {
+            let json_summary = json_summary.expect("json summary requested");
+            assert_eq!(json_summary["status"], "failed");
+            let operations = json_summary["operations"].as_array().expect("operations");
+            assert!(operations.iter().any(|operation| {
+                operation["ok"] == false
+                    && operation["error"].as_str().is_some()
+                    && operation["class"].as_str().is_some()
+            }));
+            // The failed operation's time still counts towards its phase.
+            assert!(!phase_timings.is_empty());
+            let phases = json_summary["phases"].as_array().expect("phases");
+            assert_eq!(phases.len(), phase_timings.len());
+            assert_eq!(phases[0]["phase"], phase_timings[0].phase);
+            assert!(phases[0]["task_ms"].as_u64().is_some());
+            assert!(
+                !json_summary["report_files"]
+                    .as_array()
+                    .expect("report files")
+                    .is_empty()
+            );
             assert!(validation.errors.is_empty());
             assert!(plan_diagnostics.is_empty());
             assert!(!execution_errors.is_empty(), "expected execution errors");
@@ -130,3 +153,69 @@ fn run_command_reports_when_rollback_is_disabled_by_policy() {
         outcome => panic!("expected ran outcome, got {outcome:?}"),
     }
 }
+
+#[test]
+fn run_from_unknown_operation_fails_before_execution() {
+    let root_dir = unique_dir("gaia-cli-from-root");
+    let run_out_dir = unique_dir("gaia-cli-from-out");
+    fs::create_dir_all(&root_dir).expect("workspace root");
+    seed_default_assets(&root_dir);
+
+    let run = run_with_args(AppArgs::parse_from(vec![
+        "run".to_string(),
+        config_path(),
+        "--set".to_string(),
+        format!("workspace.root_dir={root_dir}"),
+        "--set".to_string(),
+        format!("workspace.out_dir={run_out_dir}"),
+        "--from".to_string(),
+        "artifact:missing".to_string(),
+    ]));
+
+    match run {
+        CommandOutcome::Failed { message } => {
+            assert!(message.contains("cannot start from unknown operation 'artifact:missing'"));
+        }
+        outcome => panic!("expected failed outcome, got {outcome:?}"),
+    }
+    assert!(!PathBuf::from(&run_out_dir).join(".gaia/reports").exists());
+}
+
+#[test]
+fn run_refused_before_execution_still_prints_a_json_summary() {
+    let root_dir = unique_dir("gaia-cli-refused-root");
+    let run_out_dir = unique_dir("gaia-cli-refused-out");
+    fs::create_dir_all(&root_dir).expect("workspace root");
+    seed_default_assets(&root_dir);
+
+    let run = run_with_args(AppArgs::parse_from(vec![
+        "run".to_string(),
+        config_path(),
+        "--set".to_string(),
+        format!("workspace.root_dir={root_dir}"),
+        "--set".to_string(),
+        format!("workspace.out_dir={run_out_dir}"),
+        "--from".to_string(),
+        "artifact:missing".to_string(),
+        "--json-summary".to_string(),
+    ]));
+
+    assert_eq!(run.exit_code(), 1);
+    match run {
+        CommandOutcome::RunFailed {
+            message,
+            json_summary,
+        } => {
+            assert!(message.contains("cannot start from unknown operation 'artifact:missing'"));
+            assert_eq!(json_summary["status"], "failed");
+            assert!(json_summary["run_id"].is_null());
+            assert!(
+                json_summary["operations"]
+                    .as_array()
+                    .expect("operations")
+                    .is_empty()
+            );
+        }
+        outcome => panic!("expected failed run outcome, got {outcome:?}"),
+    }
+}
@@ -155,6 +155,7 @@ pub fn seed_reuse_state(root_dir: &str, build_dir: &str, out_dir: &str) {
         &config_path(),
         &ResolveOptions {
             preset: Some("ci".into()),
+            profile: None,
             env_files: vec!["examples/default-workspace/configs/runtime.env".into()],
             env_overrides: vec![
                 ("API_TOKEN".into(), "super-secret-token".into()),
@@ -14,13 +14,14 @@ use image::{
 };
 use policy::{
     compile_backoff_strategy, compile_command_policy, compile_docker_execution, compile_input_kind,
-    compile_output_retention, compile_provider_retry_attempts, compile_provider_retry_backoff_ms,
-    compile_provider_timeout_seconds, compile_rollback_domains,
+    compile_operation_hooks, compile_output_retention, compile_provider_retry_attempts,
+    compile_provider_retry_backoff_ms, compile_provider_timeout_seconds, compile_rollback_domains,
 };
 use precedence::{precedence_layers, selection_precedence_order};
 use source::{compile_source_pin_policy, compile_source_refresh_policy};
 use when::apply_when_selection;
 
+use crate::overrides::selected_input_origins;
 use crate::raw::{
     RawArtifactConfig, RawArtifactDefinition, RawArtifactInstallClass, RawBuildConfig,
     RawBuildrootExpectedImageFormat, RawBuildrootExternalTreeMode, RawCheckpointConfig,
@@ -31,37 +32,40 @@ use crate::raw::{
 
 use gaia_spec::{
     ArtifactDefinition, ArtifactExecutionSpec, ArtifactInstallClassSpec,
-    ArtifactInstallIdentitySpec, ArtifactOutputSpec, ArtifactRef, ArtifactSpec,
-    ArtifactVariantSpec, BuildMetadataSpec, BuildModeSpec, BuildPolicySpec,
+    ArtifactInstallIdentitySpec, ArtifactOutputSpec, ArtifactProviderKind, ArtifactRef,
+    ArtifactSpec, ArtifactVariantSpec, BuildMetadataSpec, BuildModeSpec, BuildPolicySpec,
     BuildrootExpectedImageFormatSpec, BuildrootExpectedImageSpec, BuildrootExternalTreeModeSpec,
-    BuildrootImageSpec, CheckpointAnchorRef, CheckpointBackendRef, CheckpointId,
-    CheckpointPointSpec, CheckpointPolicy, CleanProfileSpec, CleanSpec, CommandProviderPolicySpec,
-    DEFAULT_ARCHIVE_PROVIDER_TIMEOUT_SECONDS, DEFAULT_BUILDROOT_PROVIDER_TIMEOUT_SECONDS,
-    DEFAULT_DOWNLOAD_PROVIDER_TIMEOUT_SECONDS, DEFAULT_GIT_PROVIDER_TIMEOUT_SECONDS,
-    DEFAULT_GO_PROVIDER_TIMEOUT_SECONDS, DEFAULT_JAVA_PROVIDER_TIMEOUT_SECONDS,
+    BuildrootImageSpec, CheckpointAnchorRef, CheckpointBackendRef, CheckpointFingerprintInput,
+    CheckpointId, CheckpointPointSpec, CheckpointPolicy, CleanProfileSpec, CleanSpec,
+    CommandProviderPolicySpec, DEFAULT_ARCHIVE_PROVIDER_TIMEOUT_SECONDS,
+    DEFAULT_BUILDROOT_PROVIDER_TIMEOUT_SECONDS, DEFAULT_DOWNLOAD_PROVIDER_TIMEOUT_SECONDS,
+    DEFAULT_GIT_PROVIDER_TIMEOUT_SECONDS, DEFAULT_GO_PROVIDER_TIMEOUT_SECONDS,
+    DEFAULT_HEARTBEAT_SECONDS, DEFAULT_JAVA_PROVIDER_TIMEOUT_SECONDS,
     DEFAULT_NODE_PROVIDER_TIMEOUT_SECONDS, DEFAULT_PYTHON_PROVIDER_TIMEOUT_SECONDS,
     DEFAULT_RUST_PROVIDER_TIMEOUT_SECONDS, DEFAULT_STARTING_POINT_PROVIDER_TIMEOUT_SECONDS,
     DockerArtifactExecutionSpec, DockerExecutionSpec, ExecutionPolicySpec,
     FailureHandlingPolicySpec, GitProviderPolicySpec, GitSourceSpec, GoArtifactSpec,
     ImageDefinition, ImageFeedSpec, ImageOutputSpec, ImageSpec, InputKindSpec, InputOptionSpec,
-    InputSpec, InstallEntrySpec, InstallId, InterpolationSpec, JavaArtifactSpec, NodeArtifactSpec,
-    OutputRetentionPolicySpec, PathSourceSpec, PostBuildHookSpec, PrecedenceLayerSpec,
-    PrecedencePolicySpec, PrecedenceSource, PrecedenceTarget, PresetSelectionSpec,
-    ProductIdentitySpec, ProvenanceIdentitySpec, ProvenanceSpec, ProviderExecutionPolicySpec,
-    PythonArtifactSpec, ReportingOutputsSpec, ReportingSpec, ResolvedBuildSpec,
-    RetryBackoffStrategySpec, RollbackDomain, RustArtifactSpec, RustProviderPolicySpec,
-    SecretMaskingSpec, SelectionSpec, SourceDefinition, SourcePinPolicySpec, SourceRef,
-    SourceRefreshPolicySpec, SourceSpec, StageContentOriginSpec, StageEnvSetSpec, StageFileSpec,
-    StageItemId, StageServiceSpec, StartingPointImageSpec, StartingPointOutputModeSpec,
+    InputSpec, InstallEntrySpec, InstallId, InterpolationSpec, JavaArtifactSpec, JavaBuildToolSpec,
+    NodeArtifactSpec, OutputRetentionPolicySpec, PathSourceSpec, PostBuildHookSpec,
+    PrecedenceLayerSpec, PrecedencePolicySpec, PrecedenceSource, PrecedenceTarget,
+    PresetSelectionSpec, ProductIdentitySpec, ProvenanceIdentitySpec, ProvenanceSpec,
+    ProviderExecutionPolicySpec, PythonArtifactSpec, ReportingOutputsSpec, ReportingSpec,
+    ResolvedBuildSpec, RetryBackoffStrategySpec, RollbackDomain, RustArtifactSpec,
+    RustProviderPolicySpec, SecretMaskingSpec, SelectionSpec, SourceDefinition,
+    SourcePinPolicySpec, SourceRef, SourceRefreshPolicySpec, SourceSpec, StageContentOriginSpec,
+    StageEnvSetSpec, StageFileSpec, StageItemId, StageOverlaySpec, StageServiceSpec,
+    StageSymlinkSpec, StartingPointImageSpec, StartingPointOutputModeSpec,
     StartingPointRootfsValidationModeSpec, UnresolvedInterpolationSpec, WorkspaceNamedPathSpec,
     WorkspacePathKindSpec, WorkspaceSpec,
 };
 
 pub fn compile_config(mut raw: RawBuildConfig) -> ResolvedBuildSpec {
-    apply_when_selection(&mut raw);
+    let when_excluded_artifacts = apply_when_selection(&mut raw);
     let precedence_order = selection_precedence_order(&raw);
     let precedence_layers = precedence_layers(&raw);
     let applied_presets = raw.preset.clone().into_iter().collect();
+    let input_origins = selected_input_origins(&raw);
     let compiled_image_feed = compile_image_feed(&raw);
     let mut spec = ResolvedBuildSpec::new(raw.build_name);
     spec.identity.display_name = raw
@@ -81,6 +85,7 @@ pub fn compile_config(mut raw: RawBuildConfig) -> ResolvedBuildSpec {
         env_overrides: raw.env_overrides,
         explicit_overrides: raw.explicit_overrides,
         precedence_order,
+        when_excluded_artifacts,
     };
     spec.metadata = BuildMetadataSpec {
         version: spec.identity.version.clone(),
@@ -106,9 +111,13 @@ pub fn compile_config(mut raw: RawBuildConfig) -> ResolvedBuildSpec {
                 required: input.required,
                 default: input.default.clone(),
                 choices: input.choices.clone(),
+                min: input.min.clone(),
+                max: input.max.clone(),
+                pattern: input.pattern.clone(),
             })
             .collect(),
         selected: raw.selected_inputs.clone(),
+        origins: input_origins,
     };
     spec.policy = BuildPolicySpec {
         preset: PresetSelectionSpec {
@@ -134,11 +143,24 @@ pub fn compile_config(mut raw: RawBuildConfig) -> ResolvedBuildSpec {
             jobs: raw.execution.jobs,
             docker: compile_docker_execution(&raw.execution),
             output_retention: compile_output_retention(&raw.execution.output_retention),
+            clean_env: raw.execution.clean_env.unwrap_or(false),
+            env_allowlist: raw.execution.env_allowlist.clone(),
+            preserve_ansi: raw.execution.preserve_ansi.unwrap_or(false),
+            heartbeat_seconds: raw
+                .execution
+                .heartbeat_seconds
+                .unwrap_or(DEFAULT_HEARTBEAT_SECONDS),
+            fold_repeats: raw.execution.fold_repeats.unwrap_or(false),
+            hooks: compile_operation_hooks(&raw.execution),
+            env_export: raw.execution.env_export.clone(),
+            min_free_space_mb: raw.execution.min_free_space_mb.unwrap_or(0),
+            stall_warning_seconds: raw.execution.stall_warning_seconds.unwrap_or(0),
         },
         failure: FailureHandlingPolicySpec {
             rollback_on_error: raw.failure.rollback_on_error.unwrap_or(true),
             preserve_failed_outputs: raw.failure.preserve_failed_outputs.unwrap_or(false),
             rollback_domains: compile_rollback_domains(raw.failure.rollback_domains),
+            fail_fast: raw.failure.fail_fast.unwrap_or(true),
         },
         providers: ProviderExecutionPolicySpec {
             rust: RustProviderPolicySpec {
@@ -154,6 +176,7 @@ pub fn compile_config(mut raw: RawBuildConfig) -> ResolvedBuildSpec {
                     raw.providers.rust.timeout_seconds,
                     DEFAULT_RUST_PROVIDER_TIMEOUT_SECONDS,
                 ),
+                clean_env: raw.providers.rust.clean_env,
             },
             git: GitProviderPolicySpec {
                 allow_remote_resolution: raw.providers.git.allow_remote_resolution,
@@ -168,6 +191,7 @@ pub fn compile_config(mut raw: RawBuildConfig) -> ResolvedBuildSpec {
                     raw.providers.git.timeout_seconds,
                     DEFAULT_GIT_PROVIDER_TIMEOUT_SECONDS,
                 ),
+                clean_env: raw.providers.git.clean_env,
             },
             archive: compile_command_policy(
                 &raw.providers.archive,
@@ -221,6 +245,7 @@ pub fn compile_config(mut raw: RawBuildConfig) -> ResolvedBuildSpec {
                         description: profile.description,
                         build: profile.build,
                         out: profile.out,
+                        cache: profile.cache,
                         paths: profile.paths,
                     },
                 )
@@ -362,6 +387,9 @@ pub fn compile_config(mut raw: RawBuildConfig) -> ResolvedBuildSpec {
             src: file.src,
             dest: file.dest,
             origin: compile_stage_content_origin(file.origin),
+            uid: file.uid,
+            gid: file.gid,
+            caps: file.caps,
         })
         .collect();
     spec.stage.env_sets = raw
@@ -384,6 +412,27 @@ pub fn compile_config(mut raw: RawBuildConfig) -> ResolvedBuildSpec {
             unit_path: service.unit_path,
         })
         .collect();
+    spec.stage.overlays = raw
+        .stage
+        .overlays
+        .into_iter()
+        .map(|overlay| StageOverlaySpec {
+            id: StageItemId::new(overlay.id),
+            src: overlay.src,
+            dest: overlay.dest,
+        })
+        .collect();
+    spec.stage.symlinks = raw
+        .stage
+        .symlinks
+        .into_iter()
+        .map(|symlink| StageSymlinkSpec {
+            id: StageItemId::new(symlink.id),
+            target: symlink.target,
+            link: symlink.link,
+        })
+        .collect();
+    spec.stage.fast_copy = raw.stage.fast_copy.unwrap_or(false);
     spec.image = ImageSpec {
         definition: match raw.image.definition {
             RawImageDefinition::Buildroot {
@@ -396,6 +445,9 @@ pub fn compile_config(mut raw: RawBuildConfig) -> ResolvedBuildSpec {
                 external_tree,
                 external_tree_mode,
                 expected_images,
+                ccache,
+                ccache_dir,
+                incremental,
             } => ImageDefinition::Buildroot(BuildrootImageSpec {
                 source: source.map(gaia_spec::SourceId::new),
                 defconfig,
@@ -413,6 +465,9 @@ pub fn compile_config(mut raw: RawBuildConfig) -> ResolvedBuildSpec {
                         required: image.required,
                     })
                     .collect(),
+                ccache: ccache.unwrap_or(false),
+                ccache_dir,
+                incremental: incremental.unwrap_or(false),
             }),
             RawImageDefinition::StartingPoint {
                 source,
@@ -463,6 +518,7 @@ pub fn compile_config(mut raw: RawBuildConfig) -> ResolvedBuildSpec {
             summary: raw.reporting.summary,
             provenance: raw.reporting.provenance,
             manifest: raw.reporting.manifest,
+            sbom: raw.reporting.sbom,
         },
         masking: SecretMaskingSpec {
             enabled: raw.reporting.masking.enabled,
@@ -6,6 +6,8 @@ pub(crate) fn compile_artifact(raw: RawArtifactConfig) -> ArtifactSpec {
             package,
             target_name,
             emit_directory,
+            linker,
+            sysroot,
         } => ArtifactDefinition::Rust(RustArtifactSpec {
             package,
             target_name,
@@ -14,10 +16,21 @@ pub(crate) fn compile_artifact(raw: RawArtifactConfig) -> ArtifactSpec {
             } else {
                 ArtifactVariantSpec::File
             },
+            linker,
+            sysroot,
+        }),
+        RawArtifactDefinition::Java {
+            build_target,
+            build_tool,
+            tasks,
+        } => ArtifactDefinition::Java(JavaArtifactSpec {
+            build_target,
+            build_tool: build_tool.map(|tool| match tool {
+                crate::raw::RawJavaBuildTool::Maven => JavaBuildToolSpec::Maven,
+                crate::raw::RawJavaBuildTool::Gradle => JavaBuildToolSpec::Gradle,
+            }),
+            tasks,
         }),
-        RawArtifactDefinition::Java { build_target } => {
-            ArtifactDefinition::Java(JavaArtifactSpec { build_target })
-        }
         RawArtifactDefinition::Node { package_dir } => {
             ArtifactDefinition::Node(NodeArtifactSpec { package_dir })
         }
@@ -44,6 +57,7 @@ pub(crate) fn compile_artifact(raw: RawArtifactConfig) -> ArtifactSpec {
         output: ArtifactOutputSpec {
             path: raw.output_path,
         },
+        mutex_group: raw.mutex_group,
     }
 }
 
@@ -1,12 +1,28 @@
 use super::*;
 
 pub(crate) fn compile_checkpoint(raw: RawCheckpointConfig) -> CheckpointPointSpec {
+    let mut restore_backends = raw
+        .backend
+        .into_iter()
+        .map(|backend| CheckpointBackendRef { backend });
     CheckpointPointSpec {
         id: CheckpointId::new(raw.id),
-        backend: raw.backend.map(|backend| CheckpointBackendRef { backend }),
+        backend: restore_backends.next(),
+        fallback_backends: restore_backends.collect(),
+        upload_backends: raw
+            .upload_backends
+            .into_iter()
+            .map(|backend| CheckpointBackendRef { backend })
+            .collect(),
         use_policy: compile_checkpoint_policy(raw.use_policy),
         upload_policy: compile_checkpoint_policy(raw.upload_policy),
         anchor: compile_checkpoint_anchor(raw.anchor),
+        fingerprint_from: raw
+            .fingerprint_from
+            .iter()
+            .map(|input| CheckpointFingerprintInput::parse(input))
+            .collect(),
+        restore_to: raw.restore_to,
     }
 }
 
@@ -17,6 +33,12 @@ pub(crate) fn compile_checkpoint_anchor(raw: Option<String>) -> CheckpointAnchor
     if raw == "image" {
         return CheckpointAnchorRef::Image;
     }
+    if let Some(id) = raw.strip_prefix("source:") {
+        return CheckpointAnchorRef::Source(gaia_spec::SourceId::new(id));
+    }
+    if let Some(id) = raw.strip_prefix("artifact:") {
+        return CheckpointAnchorRef::Artifact(gaia_spec::ArtifactId::new(id));
+    }
     if let Some(id) = raw.strip_prefix("install:") {
         return CheckpointAnchorRef::Install(InstallId::new(id));
     }
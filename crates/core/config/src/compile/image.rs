@@ -61,6 +61,36 @@ pub(crate) fn compile_image_feed(raw: &RawBuildConfig) -> ImageFeedSpec {
                 .map(StageItemId::new)
                 .collect()
         },
+        stage_overlays: if raw.image.feed.stage_overlays.is_empty() {
+            raw.stage
+                .overlays
+                .iter()
+                .map(|overlay| StageItemId::new(overlay.id.clone()))
+                .collect()
+        } else {
+            raw.image
+                .feed
+                .stage_overlays
+                .iter()
+                .cloned()
+                .map(StageItemId::new)
+                .collect()
+        },
+        stage_symlinks: if raw.image.feed.stage_symlinks.is_empty() {
+            raw.stage
+                .symlinks
+                .iter()
+                .map(|symlink| StageItemId::new(symlink.id.clone()))
+                .collect()
+        } else {
+            raw.image
+                .feed
+                .stage_symlinks
+                .iter()
+                .cloned()
+                .map(StageItemId::new)
+                .collect()
+        },
     }
 }
 
@@ -1,5 +1,9 @@
 use super::*;
-use gaia_spec::{DEFAULT_COMMAND_RETRY_ATTEMPTS, DEFAULT_COMMAND_RETRY_BACKOFF_MS};
+use gaia_spec::{
+    DEFAULT_COMMAND_RETRY_ATTEMPTS, DEFAULT_COMMAND_RETRY_BACKOFF_MS,
+    DEFAULT_OPERATION_HOOK_TIMEOUT_SECONDS, HookFailureMode, OperationHooksSpec,
+};
+use std::collections::BTreeMap;
 
 pub(crate) fn compile_docker_execution(
     execution: &crate::raw::RawExecutionPolicyConfig,
@@ -13,6 +17,33 @@ pub(crate) fn compile_docker_execution(
     })
 }
 
+pub(crate) fn compile_operation_hooks(
+    execution: &crate::raw::RawExecutionPolicyConfig,
+) -> BTreeMap<String, OperationHooksSpec> {
+    execution
+        .hooks
+        .iter()
+        .map(|(operation_id, hooks)| {
+            (
+                operation_id.clone(),
+                OperationHooksSpec {
+                    pre: hooks.pre.clone(),
+                    post: hooks.post.clone(),
+                    post_failure: match hooks.post_failure {
+                        Some(crate::raw::RawHookFailureMode::Fail) => HookFailureMode::Fail,
+                        Some(crate::raw::RawHookFailureMode::Warn) | None => HookFailureMode::Warn,
+                    },
+                    timeout_seconds: if hooks.timeout_seconds == 0 {
+                        DEFAULT_OPERATION_HOOK_TIMEOUT_SECONDS
+                    } else {
+                        hooks.timeout_seconds
+                    },
+                },
+            )
+        })
+        .collect()
+}
+
 pub(crate) fn compile_output_retention(
     raw: &crate::raw::RawOutputRetentionPolicyConfig,
 ) -> OutputRetentionPolicySpec {
@@ -48,6 +79,7 @@ pub(crate) fn compile_command_policy(
         retry_backoff_strategy: compile_backoff_strategy(raw.retry_backoff_strategy),
         timeout_seconds: nonzero_u64_or(raw.timeout_seconds, default_timeout_seconds),
         local_jobs: raw.local_jobs,
+        clean_env: raw.clean_env,
     }
 }
 
@@ -76,8 +108,10 @@ pub(crate) fn compile_input_kind(raw: crate::raw::RawInputKind) -> InputKindSpec
     match raw {
         crate::raw::RawInputKind::String => InputKindSpec::String,
         crate::raw::RawInputKind::Integer => InputKindSpec::Integer,
+        crate::raw::RawInputKind::Float => InputKindSpec::Float,
         crate::raw::RawInputKind::Boolean => InputKindSpec::Boolean,
         crate::raw::RawInputKind::Enum => InputKindSpec::Enum,
+        crate::raw::RawInputKind::Datetime => InputKindSpec::Datetime,
     }
 }
 
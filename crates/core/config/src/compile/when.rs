@@ -1,6 +1,8 @@
 use super::*;
 
-pub(crate) fn apply_when_selection(raw: &mut RawBuildConfig) {
+pub(crate) fn apply_when_selection(
+    raw: &mut RawBuildConfig,
+) -> Vec<(String, ArtifactProviderKind)> {
     let context = WhenContext {
         target: raw.target.clone(),
         profile: raw.profile.clone(),
@@ -33,7 +35,30 @@ pub(crate) fn apply_when_selection(raw: &mut RawBuildConfig) {
         .iter()
         .map(|entry| entry.id.clone())
         .collect::<std::collections::HashSet<_>>();
+    let stage_overlay_ids_before = raw
+        .stage
+        .overlays
+        .iter()
+        .map(|entry| entry.id.clone())
+        .collect::<std::collections::HashSet<_>>();
+    let stage_symlink_ids_before = raw
+        .stage
+        .symlinks
+        .iter()
+        .map(|entry| entry.id.clone())
+        .collect::<std::collections::HashSet<_>>();
 
+    let excluded_artifacts = raw
+        .artifacts
+        .iter()
+        .filter(|artifact| !when_matches(artifact.when.as_ref(), &context))
+        .map(|artifact| {
+            (
+                artifact.id.clone(),
+                raw_artifact_provider_kind(&artifact.definition),
+            )
+        })
+        .collect();
     raw.artifacts
         .retain(|artifact| when_matches(artifact.when.as_ref(), &context));
     raw.install
@@ -47,6 +72,12 @@ pub(crate) fn apply_when_selection(raw: &mut RawBuildConfig) {
     raw.stage
         .services
         .retain(|service| when_matches(service.when.as_ref(), &context));
+    raw.stage
+        .overlays
+        .retain(|overlay| when_matches(overlay.when.as_ref(), &context));
+    raw.stage
+        .symlinks
+        .retain(|symlink| when_matches(symlink.when.as_ref(), &context));
 
     if !raw.image.feed.install_entries.is_empty() {
         let selected = raw
@@ -95,6 +126,41 @@ pub(crate) fn apply_when_selection(raw: &mut RawBuildConfig) {
             .stage_services
             .retain(|id| selected.contains(id.as_str()) || !stage_service_ids_before.contains(id));
     }
+    if !raw.image.feed.stage_overlays.is_empty() {
+        let selected = raw
+            .stage
+            .overlays
+            .iter()
+            .map(|entry| entry.id.as_str())
+            .collect::<std::collections::HashSet<_>>();
+        raw.image
+            .feed
+            .stage_overlays
+            .retain(|id| selected.contains(id.as_str()) || !stage_overlay_ids_before.contains(id));
+    }
+    if !raw.image.feed.stage_symlinks.is_empty() {
+        let selected = raw
+            .stage
+            .symlinks
+            .iter()
+            .map(|entry| entry.id.as_str())
+            .collect::<std::collections::HashSet<_>>();
+        raw.image
+            .feed
+            .stage_symlinks
+            .retain(|id| selected.contains(id.as_str()) || !stage_symlink_ids_before.contains(id));
+    }
+    excluded_artifacts
+}
+
+fn raw_artifact_provider_kind(definition: &RawArtifactDefinition) -> ArtifactProviderKind {
+    match definition {
+        RawArtifactDefinition::Rust { .. } => ArtifactProviderKind::Rust,
+        RawArtifactDefinition::Java { .. } => ArtifactProviderKind::Java,
+        RawArtifactDefinition::Node { .. } => ArtifactProviderKind::Node,
+        RawArtifactDefinition::Python { .. } => ArtifactProviderKind::Python,
+        RawArtifactDefinition::Go { .. } => ArtifactProviderKind::Go,
+    }
 }
 
 struct WhenContext {
@@ -0,0 +1,138 @@
+use std::fs;
+use std::path::Path;
+
+use toml::{Table, Value};
+
+use crate::ConfigError;
+use crate::load::parse_config_file;
+
+// An editable TOML view of a single config file. Paths are dotted table keys
+// such as `image.output.archive_name`; array indexing is intentionally not
+// supported because array entries are identified by their `id` field.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigDoc {
+    table: Table,
+}
+
+impl ConfigDoc {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn parse(contents: &str) -> Result<Self, ConfigError> {
+        Self::parse_with_origin(Path::new("<memory>"), contents)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let contents =
+            fs::read_to_string(path).map_err(|error| ConfigError::config_read(path, error))?;
+        match parse_config_file(path, &contents)? {
+            Value::Table(table) => Ok(Self { table }),
+            other => Err(ConfigError::config_shape(
+                path,
+                format!("expected a table, found {}", other.type_str()),
+            )),
+        }
+    }
+
+    fn parse_with_origin(origin: &Path, contents: &str) -> Result<Self, ConfigError> {
+        let table = contents
+            .parse::<Table>()
+            .map_err(|error| ConfigError::config_parse(origin, error))?;
+        Ok(Self { table })
+    }
+
+    pub fn table(&self) -> &Table {
+        &self.table
+    }
+
+    pub fn into_table(self) -> Table {
+        self.table
+    }
+
+    pub fn to_toml_string(&self) -> String {
+        self.table.to_string()
+    }
+
+    pub fn get_path(&self, path: &str) -> Option<&Value> {
+        let segments = split_path(path).ok()?;
+        let (last, parents) = segments.split_last()?;
+        let mut table = &self.table;
+        for segment in parents {
+            table = table.get(*segment)?.as_table()?;
+        }
+        table.get(*last)
+    }
+
+    pub fn set_path(&mut self, path: &str, value: Value) -> Result<(), ConfigError> {
+        let segments = split_path(path)?;
+        let (last, parents) = segments
+            .split_last()
+            .expect("split_path never returns an empty path");
+        let mut table = &mut self.table;
+        for (index, segment) in parents.iter().enumerate() {
+            let entry = table
+                .entry(segment.to_string())
+                .or_insert_with(|| Value::Table(Table::new()));
+            let Value::Table(child) = entry else {
+                return Err(ConfigError::config_doc_path(
+                    path,
+                    format!(
+                        "'{}' is a {}, not a table",
+                        segments[..=index].join("."),
+                        entry.type_str()
+                    ),
+                ));
+            };
+            table = child;
+        }
+        if let Some(existing) = table.get(*last)
+            && existing.is_table() != value.is_table()
+        {
+            return Err(ConfigError::config_doc_path(
+                path,
+                format!(
+                    "cannot replace {} with {}",
+                    existing.type_str(),
+                    value.type_str()
+                ),
+            ));
+        }
+        table.insert(last.to_string(), value);
+        Ok(())
+    }
+
+    // Removes the value at `path` and prunes parent tables left empty by the
+    // removal. Returns the removed value, or `None` when the path was unset.
+    pub fn remove_path(&mut self, path: &str) -> Result<Option<Value>, ConfigError> {
+        let segments = split_path(path)?;
+        Ok(remove_from_table(&mut self.table, &segments))
+    }
+}
+
+pub(crate) fn split_path(path: &str) -> Result<Vec<&str>, ConfigError> {
+    let segments = path.split('.').map(str::trim).collect::<Vec<_>>();
+    if segments.iter().any(|segment| segment.is_empty()) {
+        return Err(ConfigError::config_doc_path(
+            path,
+            "path segments cannot be empty",
+        ));
+    }
+    Ok(segments)
+}
+
+fn remove_from_table(table: &mut Table, segments: &[&str]) -> Option<Value> {
+    let (first, rest) = segments.split_first()?;
+    if rest.is_empty() {
+        return table.remove(*first);
+    }
+    let child = table.get_mut(*first)?.as_table_mut()?;
+    let removed = remove_from_table(child, rest);
+    if removed.is_some() && child.is_empty() {
+        table.remove(*first);
+    }
+    removed
+}
+
+#[cfg(test)]
+mod tests;
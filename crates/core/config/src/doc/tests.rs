@@ -0,0 +1,60 @@
+use super::*;
+
+#[test]
+fn set_path_creates_nested_tables() {
+    let mut doc = ConfigDoc::parse("build_name = \"demo\"\n").expect("doc should parse");
+
+    doc.set_path("policy.providers.rust.timeout_seconds", Value::Integer(120))
+        .expect("deep path should be created");
+
+    assert_eq!(
+        doc.get_path("policy.providers.rust.timeout_seconds"),
+        Some(&Value::Integer(120))
+    );
+    let reparsed =
+        ConfigDoc::parse(&doc.to_toml_string()).expect("rendered doc should parse again");
+    assert_eq!(reparsed, doc);
+}
+
+#[test]
+fn set_path_rejects_table_scalar_collisions() {
+    let mut doc = ConfigDoc::parse("[image]\nkind = \"buildroot\"\n").expect("doc should parse");
+
+    let nested = doc
+        .set_path("image.kind.name", Value::String("x".into()))
+        .expect_err("scalar parent should not become a table");
+    assert!(nested.to_string().contains("'image.kind' is a string"));
+
+    let error = doc
+        .set_path("image", Value::Boolean(true))
+        .expect_err("table should not be replaced by a scalar");
+    assert!(
+        error
+            .to_string()
+            .contains("cannot replace table with boolean")
+    );
+    assert_eq!(
+        doc.get_path("image.kind"),
+        Some(&Value::String("buildroot".into()))
+    );
+}
+
+#[test]
+fn remove_path_prunes_empty_parent_tables() {
+    let mut doc =
+        ConfigDoc::parse("build_name = \"demo\"\n\n[image.output]\narchive_name = \"demo.tar\"\n")
+            .expect("doc should parse");
+
+    let removed = doc
+        .remove_path("image.output.archive_name")
+        .expect("path should be valid");
+
+    assert_eq!(removed, Some(Value::String("demo.tar".into())));
+    assert!(doc.get_path("image").is_none());
+    assert_eq!(
+        doc.remove_path("image.output.archive_name")
+            .expect("path should be valid"),
+        None
+    );
+    assert!(doc.remove_path("image..archive_name").is_err());
+}
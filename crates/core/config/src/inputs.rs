@@ -0,0 +1,94 @@
+use crate::ConfigError;
+use crate::raw::{RawBuildConfig, RawInputKind};
+use gaia_spec::InputKindSpec;
+use std::collections::BTreeMap;
+
+// Input values may reference other inputs, e.g. a default of `gaia-${input.variant}`. The
+// references are substituted here, in dependency order, so later interpolation only ever sees
+// computed values; every other token is left for the interpolation pass. Datetime inputs are fixed
+// to an RFC3339 timestamp here too, so inputs referencing them get the timestamp.
+pub(crate) fn resolve_input_references(
+    mut raw: RawBuildConfig,
+) -> Result<RawBuildConfig, ConfigError> {
+    let values = raw
+        .selected_inputs
+        .iter()
+        .cloned()
+        .collect::<BTreeMap<_, _>>();
+    let mut resolved = BTreeMap::new();
+    for name in values.keys() {
+        resolve_input(name, &raw, &values, &mut resolved, &mut Vec::new())?;
+    }
+    for (name, value) in &mut raw.selected_inputs {
+        if let Some(computed) = resolved.get(name) {
+            value.clone_from(computed);
+        }
+    }
+    Ok(raw)
+}
+
+// `${inputs.resolved.<name>}` is accepted alongside the shorter `${input.<name>}` and
+// `${inputs.<name>}` forms.
+pub(crate) fn input_reference(token: &str) -> Option<&str> {
+    token
+        .strip_prefix("inputs.resolved.")
+        .or_else(|| token.strip_prefix("input."))
+        .or_else(|| token.strip_prefix("inputs."))
+}
+
+fn resolve_input(
+    name: &str,
+    raw: &RawBuildConfig,
+    values: &BTreeMap<String, String>,
+    resolved: &mut BTreeMap<String, String>,
+    stack: &mut Vec<String>,
+) -> Result<String, ConfigError> {
+    if let Some(value) = resolved.get(name) {
+        return Ok(value.clone());
+    }
+    if let Some(start) = stack.iter().position(|entry| entry == name) {
+        let mut cycle = stack[start..].to_vec();
+        cycle.push(name.to_string());
+        return Err(ConfigError::InputReferenceCycle { cycle });
+    }
+    stack.push(name.to_string());
+
+    let value = &values[name];
+    let mut output = String::new();
+    let mut rest = value.as_str();
+    while let Some(start) = rest.find("${") {
+        output.push_str(&rest[..start]);
+        let remainder = &rest[start + 2..];
+        let Some(end) = remainder.find('}') else {
+            rest = &rest[start..];
+            break;
+        };
+        let token = &remainder[..end];
+        match input_reference(token) {
+            Some(reference) if values.contains_key(reference) => {
+                output.push_str(&resolve_input(reference, raw, values, resolved, stack)?);
+            }
+            Some(reference) => {
+                return Err(ConfigError::MissingInputReference {
+                    input: name.to_string(),
+                    reference: reference.to_string(),
+                    declared: raw.inputs.contains_key(reference),
+                });
+            }
+            None => output.push_str(&rest[start..start + end + 3]),
+        }
+        rest = &remainder[end + 1..];
+    }
+    output.push_str(rest);
+    if raw
+        .inputs
+        .get(name)
+        .is_some_and(|input| input.kind == RawInputKind::Datetime)
+    {
+        output = InputKindSpec::Datetime.resolve_value(&output);
+    }
+
+    stack.pop();
+    resolved.insert(name.to_string(), output.clone());
+    Ok(output)
+}
@@ -0,0 +1,119 @@
+use crate::ConfigError;
+use crate::env::ResolvedEnvironment;
+use crate::interpolate::interpolate_value;
+use crate::raw::{RawArtifactConfig, RawBuildConfig};
+use std::collections::BTreeMap;
+
+const INSTANCE_TOKEN: &str = "${instance}";
+
+// An artifact with `instances` stands for one artifact per listed name, with id `<id>:<name>`
+// and `${instance}` replaced in the fields that usually differ between them. Each listed entry
+// is interpolated first and split on commas and whitespace, so one entry such as
+// `${interpolation.values.boards}` fans out over a configured list. Expansion runs before the
+// rest of the config is interpolated, so a `${instance}` left anywhere else is reported as
+// unresolved.
+//
+// An expanded artifact's dependencies on another expanded artifact resolve to the instance of
+// the same name, and fail when there is none rather than guessing; `<id>:<instance>` names one
+// explicitly. An artifact without instances depends on every instance. Installs and checkpoint
+// anchors have to name an instance, since they point at a single output.
+pub(crate) fn expand_artifact_instances(
+    mut raw: RawBuildConfig,
+    env: &ResolvedEnvironment,
+) -> Result<RawBuildConfig, ConfigError> {
+    let mut instances = BTreeMap::new();
+    for artifact in &raw.artifacts {
+        if artifact.instances.is_empty() {
+            continue;
+        }
+        let mut names = Vec::new();
+        for entry in &artifact.instances {
+            let value = interpolate_value(entry, &raw, env);
+            let split = value
+                .split(|character: char| character == ',' || character.is_whitespace())
+                .filter(|name| !name.is_empty())
+                .collect::<Vec<_>>();
+            if split.is_empty() {
+                return Err(ConfigError::InvalidArtifactInstance {
+                    artifact: artifact.id.clone(),
+                    instance: entry.clone(),
+                });
+            }
+            for name in split {
+                if name.contains([':', '/']) || name.contains("${") {
+                    return Err(ConfigError::InvalidArtifactInstance {
+                        artifact: artifact.id.clone(),
+                        instance: name.to_string(),
+                    });
+                }
+                if !names.iter().any(|existing| existing == name) {
+                    names.push(name.to_string());
+                }
+            }
+        }
+        instances.insert(artifact.id.clone(), names);
+    }
+    if instances.is_empty() {
+        return Ok(raw);
+    }
+
+    let mut expanded = Vec::new();
+    for artifact in std::mem::take(&mut raw.artifacts) {
+        let Some(names) = instances.get(&artifact.id) else {
+            expanded.push(with_instance_dependencies(artifact, None, &instances)?);
+            continue;
+        };
+        for instance in names {
+            let mut copy = artifact.clone();
+            copy.id = format!("{}:{instance}", artifact.id);
+            copy.instances = Vec::new();
+            for field in [
+                &mut copy.target,
+                &mut copy.profile,
+                &mut copy.install_name,
+                &mut copy.install_dest_hint,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                *field = field.replace(INSTANCE_TOKEN, instance);
+            }
+            copy.output_path = copy.output_path.replace(INSTANCE_TOKEN, instance);
+            expanded.push(with_instance_dependencies(
+                copy,
+                Some(instance),
+                &instances,
+            )?);
+        }
+    }
+    raw.artifacts = expanded;
+    Ok(raw)
+}
+
+fn with_instance_dependencies(
+    mut artifact: RawArtifactConfig,
+    instance: Option<&str>,
+    instances: &BTreeMap<String, Vec<String>>,
+) -> Result<RawArtifactConfig, ConfigError> {
+    let mut dependencies = Vec::new();
+    for dependency in artifact.dependencies {
+        let Some(names) = instances.get(&dependency) else {
+            dependencies.push(dependency);
+            continue;
+        };
+        match instance {
+            Some(instance) if names.iter().any(|name| name == instance) => {
+                dependencies.push(format!("{dependency}:{instance}"));
+            }
+            Some(_) => {
+                return Err(ConfigError::UnmatchedArtifactInstance {
+                    artifact: artifact.id,
+                    dependency,
+                });
+            }
+            None => dependencies.extend(names.iter().map(|name| format!("{dependency}:{name}"))),
+        }
+    }
+    artifact.dependencies = dependencies;
+    Ok(artifact)
+}
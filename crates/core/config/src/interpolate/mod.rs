@@ -173,6 +173,9 @@ pub fn interpolate_config(raw: RawBuildConfig, env: &ResolvedEnvironment) -> Raw
             file.when = file.when.map(|when| interpolate_when(when, &snapshot, env));
             file.src = resolver::interpolate_string(file.src, &snapshot, env);
             file.dest = resolver::interpolate_string(file.dest, &snapshot, env);
+            file.caps = file
+                .caps
+                .map(|value| resolver::interpolate_string(value, &snapshot, env));
             file
         })
         .collect();
@@ -215,6 +218,36 @@ pub fn interpolate_config(raw: RawBuildConfig, env: &ResolvedEnvironment) -> Raw
             service
         })
         .collect();
+    interpolated.stage.overlays = snapshot
+        .stage
+        .overlays
+        .iter()
+        .cloned()
+        .map(|mut overlay| {
+            overlay.id = resolver::interpolate_string(overlay.id, &snapshot, env);
+            overlay.when = overlay
+                .when
+                .map(|when| interpolate_when(when, &snapshot, env));
+            overlay.src = resolver::interpolate_string(overlay.src, &snapshot, env);
+            overlay.dest = resolver::interpolate_string(overlay.dest, &snapshot, env);
+            overlay
+        })
+        .collect();
+    interpolated.stage.symlinks = snapshot
+        .stage
+        .symlinks
+        .iter()
+        .cloned()
+        .map(|mut symlink| {
+            symlink.id = resolver::interpolate_string(symlink.id, &snapshot, env);
+            symlink.when = symlink
+                .when
+                .map(|when| interpolate_when(when, &snapshot, env));
+            symlink.target = resolver::interpolate_string(symlink.target, &snapshot, env);
+            symlink.link = resolver::interpolate_string(symlink.link, &snapshot, env);
+            symlink
+        })
+        .collect();
     interpolated.image.definition =
         interpolate_image_definition(snapshot.image.definition.clone(), &snapshot, env);
     interpolated.image.feed.install_entries = snapshot
@@ -249,6 +282,22 @@ pub fn interpolate_config(raw: RawBuildConfig, env: &ResolvedEnvironment) -> Raw
         .cloned()
         .map(|value| resolver::interpolate_string(value, &snapshot, env))
         .collect();
+    interpolated.image.feed.stage_overlays = snapshot
+        .image
+        .feed
+        .stage_overlays
+        .iter()
+        .cloned()
+        .map(|value| resolver::interpolate_string(value, &snapshot, env))
+        .collect();
+    interpolated.image.feed.stage_symlinks = snapshot
+        .image
+        .feed
+        .stage_symlinks
+        .iter()
+        .cloned()
+        .map(|value| resolver::interpolate_string(value, &snapshot, env))
+        .collect();
     interpolated.image.output.collect_dir = snapshot
         .image
         .output
@@ -313,11 +362,31 @@ pub fn interpolate_config(raw: RawBuildConfig, env: &ResolvedEnvironment) -> Raw
         hook.script = resolver::interpolate_string(hook.script, &snapshot, env);
         hook
     });
+    interpolated.execution.env_export = snapshot
+        .execution
+        .env_export
+        .clone()
+        .map(|value| resolver::interpolate_string(value, &snapshot, env));
+    for hooks in interpolated.execution.hooks.values_mut() {
+        for command in hooks.pre.iter_mut().chain(hooks.post.iter_mut()) {
+            *command = resolver::interpolate_string(std::mem::take(command), &snapshot, env);
+        }
+    }
     interpolated.unresolved_tokens = scanner::collect_unresolved_tokens(&interpolated);
 
     interpolated
 }
 
+// Resolves one value against the config before the rest of it is interpolated, for fields that
+// shape the config itself.
+pub(crate) fn interpolate_value(
+    value: &str,
+    raw: &RawBuildConfig,
+    env: &ResolvedEnvironment,
+) -> String {
+    resolver::interpolate_string(value.to_string(), raw, env)
+}
+
 fn interpolate_source(
     mut source: RawSourceConfig,
     raw: &RawBuildConfig,
@@ -413,6 +482,9 @@ fn interpolate_artifact(
     artifact.install_dest_hint = artifact
         .install_dest_hint
         .map(|value| resolver::interpolate_string(value, raw, env));
+    artifact.mutex_group = artifact
+        .mutex_group
+        .map(|value| resolver::interpolate_string(value, raw, env));
     artifact.dependencies = artifact
         .dependencies
         .into_iter()
@@ -424,13 +496,26 @@ fn interpolate_artifact(
             package,
             target_name,
             emit_directory,
+            linker,
+            sysroot,
         } => RawArtifactDefinition::Rust {
             package: resolver::interpolate_string(package, raw, env),
             target_name: target_name.map(|value| resolver::interpolate_string(value, raw, env)),
             emit_directory,
+            linker: linker.map(|value| resolver::interpolate_string(value, raw, env)),
+            sysroot: sysroot.map(|value| resolver::interpolate_string(value, raw, env)),
         },
-        RawArtifactDefinition::Java { build_target } => RawArtifactDefinition::Java {
+        RawArtifactDefinition::Java {
+            build_target,
+            build_tool,
+            tasks,
+        } => RawArtifactDefinition::Java {
             build_target: resolver::interpolate_string(build_target, raw, env),
+            build_tool,
+            tasks: tasks
+                .into_iter()
+                .map(|value| resolver::interpolate_string(value, raw, env))
+                .collect(),
         },
         RawArtifactDefinition::Node { package_dir } => RawArtifactDefinition::Node {
             package_dir: resolver::interpolate_string(package_dir, raw, env),
@@ -491,6 +576,9 @@ fn interpolate_image_definition(
             external_tree,
             external_tree_mode,
             expected_images,
+            ccache,
+            ccache_dir,
+            incremental,
         } => RawImageDefinition::Buildroot {
             source: source.map(|value| resolver::interpolate_string(value, raw, env)),
             defconfig: defconfig.map(|value| resolver::interpolate_string(value, raw, env)),
@@ -520,6 +608,9 @@ fn interpolate_image_definition(
                     required: image.required,
                 })
                 .collect(),
+            ccache,
+            ccache_dir: ccache_dir.map(|value| resolver::interpolate_string(value, raw, env)),
+            incremental,
         },
         RawImageDefinition::StartingPoint {
             source,
@@ -582,9 +673,19 @@ fn interpolate_checkpoint(
     checkpoint.id = resolver::interpolate_string(checkpoint.id, raw, env);
     checkpoint.backend = checkpoint
         .backend
-        .map(|value| resolver::interpolate_string(value, raw, env));
+        .into_iter()
+        .map(|value| resolver::interpolate_string(value, raw, env))
+        .collect();
+    checkpoint.upload_backends = checkpoint
+        .upload_backends
+        .into_iter()
+        .map(|value| resolver::interpolate_string(value, raw, env))
+        .collect();
     checkpoint.anchor = checkpoint
         .anchor
         .map(|value| resolver::interpolate_string(value, raw, env));
+    checkpoint.restore_to = checkpoint
+        .restore_to
+        .map(|value| resolver::interpolate_string(value, raw, env));
     checkpoint
 }
@@ -1,4 +1,5 @@
 use crate::env::ResolvedEnvironment;
+use crate::inputs::input_reference;
 use crate::raw::RawBuildConfig;
 use std::env;
 use std::path::{Path, PathBuf};
@@ -60,11 +61,8 @@ fn resolve_token(token: &str, raw: &RawBuildConfig, env: &ResolvedEnvironment) -
             .map(|value| interpolate_string(value, raw, env))
             .unwrap_or_default(),
         "preset.name" => raw.preset.clone().unwrap_or_default(),
-        _ if token.starts_with("input.") || token.starts_with("inputs.") => {
-            let name = token
-                .strip_prefix("input.")
-                .or_else(|| token.strip_prefix("inputs."))
-                .unwrap_or_default();
+        _ if input_reference(token).is_some() => {
+            let name = input_reference(token).unwrap_or_default();
             raw.selected_inputs
                 .iter()
                 .find_map(|(input_name, value)| {
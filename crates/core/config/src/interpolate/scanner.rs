@@ -211,6 +211,11 @@ pub(crate) fn collect_unresolved_tokens(raw: &RawBuildConfig) -> Vec<RawUnresolv
             artifact.install_dest_hint.as_deref(),
             &mut unresolved,
         );
+        scan_optional(
+            &format!("artifacts.{}.mutex_group", artifact.id),
+            artifact.mutex_group.as_deref(),
+            &mut unresolved,
+        );
         scan_string(
             &format!("artifacts.{}.output_path", artifact.id),
             &artifact.output_path,
@@ -227,6 +232,8 @@ pub(crate) fn collect_unresolved_tokens(raw: &RawBuildConfig) -> Vec<RawUnresolv
             RawArtifactDefinition::Rust {
                 package,
                 target_name,
+                linker,
+                sysroot,
                 ..
             } => {
                 scan_string(
@@ -239,13 +246,34 @@ pub(crate) fn collect_unresolved_tokens(raw: &RawBuildConfig) -> Vec<RawUnresolv
                     target_name.as_deref(),
                     &mut unresolved,
                 );
+                scan_optional(
+                    &format!("artifacts.{}.rust.linker", artifact.id),
+                    linker.as_deref(),
+                    &mut unresolved,
+                );
+                scan_optional(
+                    &format!("artifacts.{}.rust.sysroot", artifact.id),
+                    sysroot.as_deref(),
+                    &mut unresolved,
+                );
             }
-            RawArtifactDefinition::Java { build_target } => {
+            RawArtifactDefinition::Java {
+                build_target,
+                tasks,
+                ..
+            } => {
                 scan_string(
                     &format!("artifacts.{}.java.build_target", artifact.id),
                     build_target,
                     &mut unresolved,
                 );
+                for (index, task) in tasks.iter().enumerate() {
+                    scan_string(
+                        &format!("artifacts.{}.java.tasks[{index}]", artifact.id),
+                        task,
+                        &mut unresolved,
+                    );
+                }
             }
             RawArtifactDefinition::Node { package_dir } => {
                 scan_string(
@@ -315,6 +343,11 @@ pub(crate) fn collect_unresolved_tokens(raw: &RawBuildConfig) -> Vec<RawUnresolv
             &file.dest,
             &mut unresolved,
         );
+        scan_optional(
+            &format!("stage.files.{}.caps", file.id),
+            file.caps.as_deref(),
+            &mut unresolved,
+        );
     }
     for env_set in &raw.stage.env_sets {
         scan_string(
@@ -357,6 +390,40 @@ pub(crate) fn collect_unresolved_tokens(raw: &RawBuildConfig) -> Vec<RawUnresolv
             &mut unresolved,
         );
     }
+    for overlay in &raw.stage.overlays {
+        scan_string(
+            &format!("stage.overlays.{}.id", overlay.id),
+            &overlay.id,
+            &mut unresolved,
+        );
+        scan_string(
+            &format!("stage.overlays.{}.src", overlay.id),
+            &overlay.src,
+            &mut unresolved,
+        );
+        scan_string(
+            &format!("stage.overlays.{}.dest", overlay.id),
+            &overlay.dest,
+            &mut unresolved,
+        );
+    }
+    for symlink in &raw.stage.symlinks {
+        scan_string(
+            &format!("stage.symlinks.{}.id", symlink.id),
+            &symlink.id,
+            &mut unresolved,
+        );
+        scan_string(
+            &format!("stage.symlinks.{}.target", symlink.id),
+            &symlink.target,
+            &mut unresolved,
+        );
+        scan_string(
+            &format!("stage.symlinks.{}.link", symlink.id),
+            &symlink.link,
+            &mut unresolved,
+        );
+    }
 
     match &raw.image.definition {
         RawImageDefinition::Buildroot {
@@ -366,6 +433,7 @@ pub(crate) fn collect_unresolved_tokens(raw: &RawBuildConfig) -> Vec<RawUnresolv
             config_overrides,
             external_tree,
             expected_images,
+            ccache_dir,
             ..
         } => {
             scan_optional(
@@ -402,6 +470,11 @@ pub(crate) fn collect_unresolved_tokens(raw: &RawBuildConfig) -> Vec<RawUnresolv
                 external_tree.as_deref(),
                 &mut unresolved,
             );
+            scan_optional(
+                "image.buildroot.ccache_dir",
+                ccache_dir.as_deref(),
+                &mut unresolved,
+            );
             for (index, expected_image) in expected_images.iter().enumerate() {
                 scan_string(
                     &format!("image.buildroot.expected_images.{index}.name"),
@@ -504,6 +577,20 @@ pub(crate) fn collect_unresolved_tokens(raw: &RawBuildConfig) -> Vec<RawUnresolv
             &mut unresolved,
         );
     }
+    for (index, stage_overlay) in raw.image.feed.stage_overlays.iter().enumerate() {
+        scan_string(
+            &format!("image.feed.stage_overlays.{index}"),
+            stage_overlay,
+            &mut unresolved,
+        );
+    }
+    for (index, stage_symlink) in raw.image.feed.stage_symlinks.iter().enumerate() {
+        scan_string(
+            &format!("image.feed.stage_symlinks.{index}"),
+            stage_symlink,
+            &mut unresolved,
+        );
+    }
     scan_optional(
         "image.output.collect_dir",
         raw.image.output.collect_dir.as_deref(),
@@ -521,14 +608,28 @@ pub(crate) fn collect_unresolved_tokens(raw: &RawBuildConfig) -> Vec<RawUnresolv
             &checkpoint.id,
             &mut unresolved,
         );
+        for (index, backend) in checkpoint.backend.iter().enumerate() {
+            scan_string(
+                &format!("checkpoints.{}.backend[{index}]", checkpoint.id),
+                backend,
+                &mut unresolved,
+            );
+        }
+        for (index, backend) in checkpoint.upload_backends.iter().enumerate() {
+            scan_string(
+                &format!("checkpoints.{}.upload_backends[{index}]", checkpoint.id),
+                backend,
+                &mut unresolved,
+            );
+        }
         scan_optional(
-            &format!("checkpoints.{}.backend", checkpoint.id),
-            checkpoint.backend.as_deref(),
+            &format!("checkpoints.{}.anchor", checkpoint.id),
+            checkpoint.anchor.as_deref(),
             &mut unresolved,
         );
         scan_optional(
-            &format!("checkpoints.{}.anchor", checkpoint.id),
-            checkpoint.anchor.as_deref(),
+            &format!("checkpoints.{}.restore_to", checkpoint.id),
+            checkpoint.restore_to.as_deref(),
             &mut unresolved,
         );
     }
@@ -576,6 +677,22 @@ pub(crate) fn collect_unresolved_tokens(raw: &RawBuildConfig) -> Vec<RawUnresolv
     if let Some(hook) = &raw.reporting.post_build {
         scan_string("reporting.post_build.script", &hook.script, &mut unresolved);
     }
+    scan_optional(
+        "execution.env_export",
+        raw.execution.env_export.as_deref(),
+        &mut unresolved,
+    );
+    for (operation_id, hooks) in &raw.execution.hooks {
+        for (phase, commands) in [("pre", &hooks.pre), ("post", &hooks.post)] {
+            for (index, command) in commands.iter().enumerate() {
+                scan_string(
+                    &format!("execution.hooks.{operation_id}.{phase}[{index}]"),
+                    command,
+                    &mut unresolved,
+                );
+            }
+        }
+    }
 
     unresolved.sort_by(|a, b| {
         a.location
@@ -1,17 +1,30 @@
 mod compile;
+mod doc;
 mod env;
+mod inputs;
+mod instances;
 mod interpolate;
 mod load;
 mod merge;
 mod overrides;
 mod raw;
+mod schema;
+mod trace;
 
 pub use compile::compile_config;
+pub use doc::ConfigDoc;
+pub use schema::{config_schema, provider_config_keys};
+pub use trace::{
+    ConfigContribution, ConfigLayerKind, ConfigLayerLink, ConfigTrace, config_file_set,
+    trace_config_path,
+};
 
 use env::resolve_environment;
+use inputs::resolve_input_references;
+use instances::expand_artifact_instances;
 use interpolate::interpolate_config;
 use load::{discover_build_root, load_build_config};
-use merge::merge_config;
+use merge::{apply_selected_profile, merge_config, merge_config_strict, strict_merge_requested};
 use overrides::{apply_cli_overrides, apply_selected_preset, collect_selected_inputs};
 
 use gaia_spec::ResolvedBuildSpec;
@@ -30,6 +43,32 @@ pub fn try_resolve_config(build: &str) -> Result<ResolvedBuildSpec, ConfigError>
     try_resolve_config_with_options(build, &ResolveOptions::default())
 }
 
+// Resolves `build` with overrides written the way `--set` takes them, such as
+// `image.output.archive_name=demo.tar` or `input.target=rpi5`, for callers embedding Gaia
+// without its CLI. The result is the same spec `gaia resolve --set ...` produces.
+pub fn try_resolve_config_with_overrides(
+    build: &str,
+    overrides: &[impl AsRef<str>],
+) -> Result<ResolvedBuildSpec, ConfigError> {
+    let explicit_overrides = overrides
+        .iter()
+        .map(|entry| {
+            let entry = entry.as_ref();
+            entry
+                .split_once('=')
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .ok_or_else(|| ConfigError::invalid_override_value(entry, "", "key=value"))
+        })
+        .collect::<Result<_, _>>()?;
+    try_resolve_config_with_options(
+        build,
+        &ResolveOptions {
+            explicit_overrides,
+            ..ResolveOptions::default()
+        },
+    )
+}
+
 pub fn try_resolve_config_with_options(
     build: &str,
     options: &ResolveOptions,
@@ -38,6 +77,7 @@ pub fn try_resolve_config_with_options(
         "resolve_config",
         build,
         preset = ?options.preset,
+        profile = ?options.profile,
         env_files = options.env_files.len(),
         env_overrides = options.env_overrides.len(),
         explicit_overrides = options.explicit_overrides.len(),
@@ -46,11 +86,17 @@ pub fn try_resolve_config_with_options(
     tracing::debug!(build, preset = ?options.preset, "resolving build config");
     let raw = load_build_config(build)?;
     tracing::debug!(build, "loaded build config");
-    let merged = merge_config(raw);
+    let merged = if strict_merge_requested(&raw) {
+        merge_config_strict(raw)?
+    } else {
+        merge_config(raw)
+    };
+    let merged = apply_selected_profile(merged, options.profile.as_deref())?;
     let selected = apply_preset_selection(merged, build, options);
     let preset_applied = apply_selected_preset(selected)?;
-    let overridden = apply_cli_overrides(preset_applied, options)?;
+    let overridden = resolve_input_references(apply_cli_overrides(preset_applied, options)?)?;
     let env = resolve_environment(&overridden)?;
+    let overridden = expand_artifact_instances(overridden, &env)?;
     tracing::debug!(
         build,
         env_files = options.env_files.len(),
@@ -70,6 +116,7 @@ pub fn try_resolve_config_with_options(
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct ResolveOptions {
     pub preset: Option<String>,
+    pub profile: Option<String>,
     pub env_files: Vec<String>,
     pub env_overrides: Vec<(String, String)>,
     pub explicit_overrides: Vec<(String, String)>,
@@ -110,11 +157,39 @@ pub enum ConfigError {
     MissingPreset {
         preset: String,
     },
+    MissingProfile {
+        profile: String,
+        available: Vec<String>,
+    },
     InvalidOverrideValue {
         key: String,
         value: String,
         expected: &'static str,
     },
+    ConfigDocPath {
+        path: String,
+        message: String,
+    },
+    InputReferenceCycle {
+        cycle: Vec<String>,
+    },
+    MissingInputReference {
+        input: String,
+        reference: String,
+        // Whether the referenced input is declared but has neither a default nor a selection.
+        declared: bool,
+    },
+    MergeTypeConflict {
+        conflicts: Vec<String>,
+    },
+    InvalidArtifactInstance {
+        artifact: String,
+        instance: String,
+    },
+    UnmatchedArtifactInstance {
+        artifact: String,
+        dependency: String,
+    },
 }
 
 impl ConfigError {
@@ -159,6 +234,13 @@ impl ConfigError {
         }
     }
 
+    pub(crate) fn config_doc_path(path: &str, message: impl Into<String>) -> Self {
+        Self::ConfigDocPath {
+            path: path.to_string(),
+            message: message.into(),
+        }
+    }
+
     fn invalid_override_value(
         key: impl Into<String>,
         value: impl Into<String>,
@@ -218,6 +300,15 @@ impl fmt::Display for ConfigError {
                 formatter,
                 "selected preset '{preset}' was not defined in the resolved config"
             ),
+            Self::MissingProfile { profile, available } if available.is_empty() => write!(
+                formatter,
+                "selected profile '{profile}' was not defined; the config declares no profiles"
+            ),
+            Self::MissingProfile { profile, available } => write!(
+                formatter,
+                "selected profile '{profile}' was not defined; available profiles: {}",
+                available.join(", ")
+            ),
             Self::InvalidOverrideValue {
                 key,
                 value,
@@ -226,6 +317,48 @@ impl fmt::Display for ConfigError {
                 formatter,
                 "invalid override value for '{key}': '{value}' (expected {expected})"
             ),
+            Self::ConfigDocPath { path, message } => {
+                write!(formatter, "invalid config path '{path}': {message}")
+            }
+            Self::InputReferenceCycle { cycle } => {
+                write!(
+                    formatter,
+                    "input reference cycle detected: {}",
+                    cycle.join(" -> ")
+                )
+            }
+            Self::MissingInputReference {
+                input,
+                reference,
+                declared: true,
+            } => write!(
+                formatter,
+                "input '{input}' references input '{reference}', which has no default or selected value"
+            ),
+            Self::MissingInputReference {
+                input,
+                reference,
+                declared: false,
+            } => write!(
+                formatter,
+                "input '{input}' references undeclared input '{reference}'"
+            ),
+            Self::MergeTypeConflict { conflicts } => write!(
+                formatter,
+                "strict_merge: config layers change the type of existing values: {}",
+                conflicts.join("; ")
+            ),
+            Self::InvalidArtifactInstance { artifact, instance } => write!(
+                formatter,
+                "artifact '{artifact}' lists invalid instance '{instance}': instance names must be non-empty, fully interpolated, and contain no ':' or '/'"
+            ),
+            Self::UnmatchedArtifactInstance {
+                artifact,
+                dependency,
+            } => write!(
+                formatter,
+                "artifact '{artifact}' depends on '{dependency}', which has no instance of the same name; depend on '{dependency}:<instance>' instead"
+            ),
         }
     }
 }
@@ -288,12 +421,13 @@ fn normalize_paths(mut raw: raw::RawBuildConfig) -> Result<raw::RawBuildConfig,
     }
     match &mut raw.image.definition {
         raw::RawImageDefinition::Buildroot {
-            external_tree: Some(external_tree),
+            external_tree,
+            ccache_dir,
             ..
         } => {
-            *external_tree = absolutize(&workspace_root, external_tree)
-                .display()
-                .to_string();
+            for path in [external_tree, ccache_dir].into_iter().flatten() {
+                *path = absolutize(&workspace_root, path).display().to_string();
+            }
         }
         raw::RawImageDefinition::StartingPoint {
             source,
@@ -312,7 +446,6 @@ fn normalize_paths(mut raw: raw::RawBuildConfig) -> Result<raw::RawBuildConfig,
                 *value = absolutize(&workspace_root, value).display().to_string();
             }
         }
-        _ => {}
     }
     Ok(raw)
 }
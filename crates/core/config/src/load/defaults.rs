@@ -0,0 +1,109 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::ConfigError;
+use crate::schema::config_schema;
+
+const DEFAULTED_SECTIONS: [&str; 4] = ["sources", "artifacts", "install", "checkpoints"];
+
+// `[defaults.<section>]` fills keys that `[[<section>]]` entries of the same file leave unset;
+// explicit entry values always win. Only fields every entry kind shares can be defaulted, so a
+// default never lands on an entry kind that would reject or silently ignore it.
+pub(crate) fn apply_section_defaults(
+    path: &Path,
+    value: &mut toml::Value,
+) -> Result<(), ConfigError> {
+    let Some(root) = value.as_table_mut() else {
+        return Ok(());
+    };
+    let Some(defaults) = root.remove("defaults") else {
+        return Ok(());
+    };
+    let toml::Value::Table(defaults) = defaults else {
+        return Err(ConfigError::config_shape(path, "defaults must be a table"));
+    };
+    let schema = config_schema();
+    for (section, section_defaults) in defaults {
+        if !DEFAULTED_SECTIONS.contains(&section.as_str()) {
+            return Err(ConfigError::config_shape(
+                path,
+                format!(
+                    "defaults.{section} is not supported; defaults apply to {}",
+                    DEFAULTED_SECTIONS.join(", ")
+                ),
+            ));
+        }
+        let toml::Value::Table(section_defaults) = section_defaults else {
+            return Err(ConfigError::config_shape(
+                path,
+                format!("defaults.{section} must be a table"),
+            ));
+        };
+        let shared = shared_fields(&schema["properties"][section.as_str()]["items"]);
+        for key in section_defaults.keys() {
+            if key == "id" || key == "kind" {
+                return Err(ConfigError::config_shape(
+                    path,
+                    format!(
+                        "defaults.{section}.{key} cannot be defaulted; every entry sets its own"
+                    ),
+                ));
+            }
+            if !shared.contains(key.as_str()) {
+                return Err(ConfigError::config_shape(
+                    path,
+                    format!(
+                        "defaults.{section}.{key} is not a field shared by every {section} entry"
+                    ),
+                ));
+            }
+        }
+        let Some(entries) = root.get_mut(&section).and_then(toml::Value::as_array_mut) else {
+            continue;
+        };
+        for entry in entries {
+            if let Some(entry) = entry.as_table_mut() {
+                fill_absent(entry, &section_defaults);
+            }
+        }
+    }
+    Ok(())
+}
+
+// Tagged sections list their shared fields on every variant, so the intersection is exactly the
+// set that is valid for all kinds.
+fn shared_fields(schema: &Value) -> BTreeSet<&str> {
+    match schema.get("oneOf").and_then(Value::as_array) {
+        Some(variants) => variants
+            .iter()
+            .map(property_names)
+            .reduce(|shared, variant| shared.intersection(&variant).copied().collect())
+            .unwrap_or_default(),
+        None => property_names(schema),
+    }
+}
+
+fn property_names(schema: &Value) -> BTreeSet<&str> {
+    schema["properties"]
+        .as_object()
+        .map(|properties| properties.keys().map(String::as_str).collect())
+        .unwrap_or_default()
+}
+
+// Nested tables such as `execution` merge key by key, so an entry that only sets part of one
+// still inherits the rest.
+fn fill_absent(entry: &mut toml::Table, defaults: &toml::Table) {
+    for (key, default) in defaults {
+        match (entry.get_mut(key), default) {
+            (None, _) => {
+                entry.insert(key.clone(), default.clone());
+            }
+            (Some(toml::Value::Table(existing)), toml::Value::Table(default)) => {
+                fill_absent(existing, default);
+            }
+            (Some(_), _) => {}
+        }
+    }
+}
@@ -1,3 +1,7 @@
+mod defaults;
+mod types;
+mod yaml;
+
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -55,12 +59,17 @@ fn load_build_config_from_path(
     );
     let contents = fs::read_to_string(&canonical_path)
         .map_err(|error| ConfigError::config_read(&canonical_path, error))?;
-    let value: toml::Value = toml::from_str(&contents)
-        .map_err(|error| ConfigError::config_parse(&canonical_path, error))?;
+    let mut value = parse_config_file(&canonical_path, &contents)?;
     validate_raw_toml_shape(&canonical_path, &value)?;
-    let mut raw: RawBuildConfig = value
-        .try_into()
-        .map_err(|error| ConfigError::config_parse(&canonical_path, error))?;
+    defaults::apply_section_defaults(&canonical_path, &mut value)?;
+    let mut raw: RawBuildConfig = value.clone().try_into().map_err(|error| {
+        let mismatches = types::describe_type_mismatches(&value);
+        if mismatches.is_empty() {
+            ConfigError::config_parse(&canonical_path, error)
+        } else {
+            ConfigError::config_shape(&canonical_path, mismatches.join("; "))
+        }
+    })?;
     raw.source_path = Some(canonical_path.clone());
     if raw.build_name.trim().is_empty() {
         raw.build_name = infer_build_name(&canonical_path);
@@ -100,6 +109,21 @@ fn load_build_config_from_path(
     Ok(raw)
 }
 
+// `.yaml`/`.yml` files are converted into the TOML value model; every other file parses as TOML.
+pub(crate) fn parse_config_file(path: &Path, contents: &str) -> Result<toml::Value, ConfigError> {
+    if is_yaml_path(path) {
+        yaml::parse_yaml_config(contents).map_err(|error| ConfigError::config_parse(path, error))
+    } else {
+        toml::from_str(contents).map_err(|error| ConfigError::config_parse(path, error))
+    }
+}
+
+fn is_yaml_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| matches!(extension, "yaml" | "yml"))
+}
+
 fn validate_raw_toml_shape(path: &Path, value: &toml::Value) -> Result<(), ConfigError> {
     let Some(workspace) = value.get("workspace").and_then(toml::Value::as_table) else {
         return Ok(());
@@ -138,6 +162,14 @@ fn resolve_build_path(build: &str) -> Result<PathBuf, ConfigError> {
             .join("builds")
             .join(format!("{build}.toml")),
         root.join("examples").join(build).join("build.toml"),
+        root.join("configs").join(format!("{build}.yaml")),
+        root.join("configs").join(format!("{build}.yml")),
+        root.join("configs")
+            .join("builds")
+            .join(format!("{build}.yaml")),
+        root.join("configs")
+            .join("builds")
+            .join(format!("{build}.yml")),
     ];
 
     candidates
@@ -0,0 +1,201 @@
+use serde_json::Value;
+
+use crate::schema::config_schema;
+
+// Walks a parsed config file against the config schema and describes every value whose TOML type
+// cannot deserialize into the field, e.g. `enabled = "true"` for a boolean. Only consulted once
+// serde has already rejected the file, so a schema gap can never reject a valid config.
+pub(crate) fn describe_type_mismatches(value: &toml::Value) -> Vec<String> {
+    let root = config_schema();
+    let mut mismatches = Vec::new();
+    check_value(&root, &root, value, "", &mut mismatches);
+    mismatches
+}
+
+fn check_value(
+    root: &Value,
+    schema: &Value,
+    value: &toml::Value,
+    location: &str,
+    mismatches: &mut Vec<String>,
+) {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let name = reference.trim_start_matches("#/$defs/");
+        return check_value(root, &root["$defs"][name], value, location, mismatches);
+    }
+    if let Some(variants) = schema.get("oneOf").and_then(Value::as_array) {
+        // Tagged tables pick their variant by `kind`; other unions accept any matching variant.
+        if let toml::Value::Table(table) = value
+            && schema.get("type").and_then(Value::as_str) == Some("object")
+        {
+            let kind = table.get("kind").and_then(toml::Value::as_str);
+            if let Some(variant) = variants
+                .iter()
+                .find(|variant| variant["properties"]["kind"]["const"].as_str() == kind)
+            {
+                check_value(root, variant, value, location, mismatches);
+            }
+            return;
+        }
+        if let Some(variant) = variants.iter().find(|variant| accepts_type(variant, value)) {
+            check_value(root, variant, value, location, mismatches);
+        } else {
+            let expected = variants
+                .iter()
+                .filter_map(expected_type)
+                .collect::<Vec<_>>()
+                .join(" or ");
+            mismatches.push(mismatch(location, &expected, value, None));
+        }
+        return;
+    }
+    if !accepts_type(schema, value) {
+        let expected = expected_type(schema).unwrap_or_else(|| "another type".into());
+        mismatches.push(mismatch(location, &expected, value, hint(schema, value)));
+        return;
+    }
+    if schema.get("minimum").is_some()
+        && let toml::Value::Integer(number) = value
+        && *number < 0
+    {
+        mismatches.push(format!(
+            "`{location}` expects a non-negative integer, found {number}"
+        ));
+        return;
+    }
+    match value {
+        toml::Value::Table(table) => {
+            for (key, entry) in table {
+                let entry_schema = schema
+                    .get("properties")
+                    .and_then(|properties| properties.get(key))
+                    .or_else(|| {
+                        schema
+                            .get("additionalProperties")
+                            .filter(|value| value.is_object())
+                    });
+                if let Some(entry_schema) = entry_schema {
+                    check_value(
+                        root,
+                        entry_schema,
+                        entry,
+                        &join_key(location, key),
+                        mismatches,
+                    );
+                }
+            }
+        }
+        toml::Value::Array(items) => {
+            let prefix = schema.get("prefixItems").and_then(Value::as_array);
+            for (index, item) in items.iter().enumerate() {
+                let item_schema = prefix
+                    .and_then(|prefix| prefix.get(index))
+                    .or_else(|| schema.get("items").filter(|items| items.is_object()));
+                if let Some(item_schema) = item_schema {
+                    check_value(
+                        root,
+                        item_schema,
+                        item,
+                        &format!("{location}[{index}]"),
+                        mismatches,
+                    );
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn join_key(location: &str, key: &str) -> String {
+    if location.is_empty() {
+        key.to_string()
+    } else {
+        format!("{location}.{key}")
+    }
+}
+
+fn schema_types(schema: &Value) -> Vec<&str> {
+    match schema.get("type") {
+        Some(Value::String(kind)) => vec![kind.as_str()],
+        Some(Value::Array(kinds)) => kinds.iter().filter_map(Value::as_str).collect(),
+        // `const` and `enum` entries in this schema are always strings.
+        _ if schema.get("const").is_some() => vec!["string"],
+        _ => Vec::new(),
+    }
+}
+
+fn accepts_type(schema: &Value, value: &toml::Value) -> bool {
+    let kinds = schema_types(schema);
+    kinds.is_empty()
+        || kinds.iter().any(|kind| match *kind {
+            "string" => value.is_str(),
+            "integer" => value.is_integer(),
+            // serde reads TOML integers into float fields as well.
+            "number" => value.is_integer() || value.is_float(),
+            "boolean" => value.is_bool(),
+            "array" => value.is_array(),
+            "object" => value.is_table(),
+            _ => true,
+        })
+}
+
+fn expected_type(schema: &Value) -> Option<String> {
+    let kinds = schema_types(schema);
+    (!kinds.is_empty()).then(|| {
+        kinds
+            .iter()
+            .map(|kind| match *kind {
+                "object" => "table",
+                kind => kind,
+            })
+            .collect::<Vec<_>>()
+            .join(" or ")
+    })
+}
+
+fn mismatch(location: &str, expected: &str, value: &toml::Value, hint: Option<String>) -> String {
+    let location = if location.is_empty() {
+        "<root>"
+    } else {
+        location
+    };
+    let found = match value {
+        toml::Value::String(text) => format!("string \"{text}\""),
+        toml::Value::Table(_) | toml::Value::Array(_) => value.type_str().to_string(),
+        other => format!("{} {other}", other.type_str()),
+    };
+    match hint {
+        Some(hint) => format!("`{location}` expects {expected}, found {found} ({hint})"),
+        None => format!("`{location}` expects {expected}, found {found}"),
+    }
+}
+
+fn hint(schema: &Value, value: &toml::Value) -> Option<String> {
+    let kinds = schema_types(schema);
+    match value {
+        toml::Value::String(text) if kinds.contains(&"boolean") => {
+            matches!(text.as_str(), "true" | "false")
+                .then(|| format!("write `{text}` without quotes"))
+        }
+        toml::Value::String(text) if kinds.contains(&"integer") => text
+            .parse::<i64>()
+            .is_ok()
+            .then(|| format!("write `{text}` without quotes")),
+        toml::Value::Float(number) if kinds.contains(&"integer") => (number.fract() == 0.0)
+            .then(|| format!("write `{}` without the fraction", *number as i64)),
+        toml::Value::Integer(_) | toml::Value::Float(_) | toml::Value::Boolean(_)
+            if kinds.contains(&"string") =>
+        {
+            Some(format!("quote it as \"{value}\""))
+        }
+        toml::Value::String(_)
+        | toml::Value::Integer(_)
+        | toml::Value::Float(_)
+        | toml::Value::Boolean(_)
+            if kinds.contains(&"array") =>
+        {
+            Some(format!("wrap it in a list: [{value}]"))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,104 @@
+use serde_norway::Value;
+
+// YAML build files are converted into the same `toml::Value` model TOML files parse into, so
+// everything after loading is format-agnostic. The conversion rules:
+// - the document must be a mapping; an empty document is an empty config
+// - mapping keys must be strings
+// - integers that fit in i64 stay integers, every other number becomes a float
+// - a null mapping value means the key is unset and is dropped; nulls anywhere else are rejected
+// - tagged values (`!tag value`) are rejected since TOML has nothing to map them to
+pub(crate) fn parse_yaml_config(contents: &str) -> Result<toml::Value, String> {
+    let value: Value = serde_norway::from_str(contents).map_err(|error| error.to_string())?;
+    match value {
+        Value::Null => Ok(toml::Value::Table(toml::Table::new())),
+        Value::Mapping(_) => yaml_to_toml(value, ""),
+        other => Err(format!(
+            "a YAML build config must be a mapping, found {}",
+            yaml_type(&other)
+        )),
+    }
+}
+
+fn yaml_to_toml(value: Value, location: &str) -> Result<toml::Value, String> {
+    match value {
+        Value::Bool(value) => Ok(toml::Value::Boolean(value)),
+        Value::Number(number) => {
+            if let Some(integer) = number.as_i64() {
+                Ok(toml::Value::Integer(integer))
+            } else if number.is_u64() {
+                Err(format!(
+                    "`{location}` is {number}, which does not fit in a 64-bit signed integer"
+                ))
+            } else {
+                Ok(toml::Value::Float(
+                    number.as_f64().expect("YAML number is i64, u64 or f64"),
+                ))
+            }
+        }
+        Value::String(value) => Ok(toml::Value::String(value)),
+        Value::Sequence(items) => items
+            .into_iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let item_location = format!("{location}[{index}]");
+                if item.is_null() {
+                    return Err(format!(
+                        "`{item_location}` is null; arrays cannot contain null"
+                    ));
+                }
+                yaml_to_toml(item, &item_location)
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(toml::Value::Array),
+        Value::Mapping(entries) => {
+            let mut table = toml::Table::new();
+            for (key, value) in entries {
+                let Value::String(key) = key else {
+                    return Err(format!(
+                        "`{}` has a {} key; mapping keys must be strings",
+                        display_location(location),
+                        yaml_type(&key)
+                    ));
+                };
+                if value.is_null() {
+                    continue;
+                }
+                let key_location = if location.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{location}.{key}")
+                };
+                table.insert(key, yaml_to_toml(value, &key_location)?);
+            }
+            Ok(toml::Value::Table(table))
+        }
+        Value::Null => Err(format!("`{location}` is null")),
+        Value::Tagged(tagged) => Err(format!(
+            "`{location}` uses the YAML tag {}, which is not supported",
+            tagged.tag
+        )),
+    }
+}
+
+fn display_location(location: &str) -> &str {
+    if location.is_empty() {
+        "<root>"
+    } else {
+        location
+    }
+}
+
+fn yaml_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Sequence(_) => "sequence",
+        Value::Mapping(_) => "mapping",
+        Value::Tagged(_) => "tagged",
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,79 @@
+use super::*;
+
+#[test]
+fn maps_yaml_scalars_onto_toml_types() {
+    let value = parse_yaml_config(
+        "build_name: demo\nenabled: true\njobs: 4\nnegative: -3\nratio: 0.5\nwhole_float: 2.0\nquoted: \"4\"\n",
+    )
+    .expect("yaml should convert");
+
+    assert_eq!(value["build_name"], toml::Value::String("demo".into()));
+    assert_eq!(value["enabled"], toml::Value::Boolean(true));
+    assert_eq!(value["jobs"], toml::Value::Integer(4));
+    assert_eq!(value["negative"], toml::Value::Integer(-3));
+    assert_eq!(value["ratio"], toml::Value::Float(0.5));
+    assert_eq!(value["whole_float"], toml::Value::Float(2.0));
+    assert_eq!(value["quoted"], toml::Value::String("4".into()));
+}
+
+#[test]
+fn maps_nested_mappings_and_sequences() {
+    let value = parse_yaml_config(
+        "workspace:\n  root_dir: .\nsources:\n  - id: app\n    kind: path\n    path: app\n",
+    )
+    .expect("yaml should convert");
+
+    let expected: toml::Value = toml::from_str(
+        "[workspace]\nroot_dir = \".\"\n\n[[sources]]\nid = \"app\"\nkind = \"path\"\npath = \"app\"\n",
+    )
+    .expect("toml should parse");
+    assert_eq!(value, expected);
+}
+
+#[test]
+fn drops_null_mapping_values_and_accepts_empty_documents() {
+    let value = parse_yaml_config("build_name: demo\nversion: null\nmetadata:\n  branch: ~\n")
+        .expect("yaml should convert");
+
+    let table = value.as_table().expect("root table");
+    assert!(!table.contains_key("version"));
+    assert_eq!(
+        value["metadata"],
+        toml::Value::Table(toml::Table::new()),
+        "null nested values are dropped too"
+    );
+    assert_eq!(
+        parse_yaml_config("").expect("empty document"),
+        toml::Value::Table(toml::Table::new())
+    );
+}
+
+#[test]
+fn rejects_values_toml_cannot_represent() {
+    let null_item = parse_yaml_config("imports:\n  - a.yaml\n  - null\n").expect_err("null item");
+    assert_eq!(
+        null_item,
+        "`imports[1]` is null; arrays cannot contain null"
+    );
+
+    let key = parse_yaml_config("labels:\n  1: one\n").expect_err("integer key");
+    assert_eq!(
+        key,
+        "`labels` has a number key; mapping keys must be strings"
+    );
+
+    let too_large = parse_yaml_config("jobs: 18446744073709551615\n").expect_err("u64 overflow");
+    assert!(too_large.starts_with("`jobs` is 18446744073709551615"));
+
+    let tagged = parse_yaml_config("jobs: !custom 4\n").expect_err("tagged value");
+    assert_eq!(
+        tagged,
+        "`jobs` uses the YAML tag !custom, which is not supported"
+    );
+
+    let root = parse_yaml_config("- a\n- b\n").expect_err("sequence root");
+    assert_eq!(
+        root,
+        "a YAML build config must be a mapping, found sequence"
+    );
+}
@@ -1,5 +1,8 @@
+mod values;
+
 use std::collections::BTreeMap;
 
+use crate::ConfigError;
 use crate::raw::{
     RawBuildConfig, RawBuildrootExpectedImageConfig, RawExecutionPolicyConfig,
     RawFailurePolicyConfig, RawGitProviderPolicyConfig, RawImageConfig, RawImageDefinition,
@@ -25,6 +28,45 @@ pub fn merge_config(raw: RawBuildConfig) -> RawBuildConfig {
     merge_two(merged, strip_loaded_children(raw))
 }
 
+// `merge_config` for builds that set `strict_merge = true`: a layer that changes the type of a
+// value set by the layers below it is an error instead of silently replacing it.
+pub fn merge_config_strict(raw: RawBuildConfig) -> Result<RawBuildConfig, ConfigError> {
+    let conflicts = values::layer_type_conflicts(&raw)?;
+    if !conflicts.is_empty() {
+        return Err(ConfigError::MergeTypeConflict { conflicts });
+    }
+    Ok(merge_config(raw))
+}
+
+// Merges the profile named by `--profile` over the merged layers, using the same rules as an
+// import, and drops every profile so none of them reaches later passes.
+pub(crate) fn apply_selected_profile(
+    mut raw: RawBuildConfig,
+    profile: Option<&str>,
+) -> Result<RawBuildConfig, ConfigError> {
+    let mut profiles = std::mem::take(&mut raw.profiles);
+    let Some(name) = profile else {
+        return Ok(raw);
+    };
+    let Some(selected) = profiles.remove(name) else {
+        return Err(ConfigError::MissingProfile {
+            profile: name.to_string(),
+            available: profiles.into_keys().collect(),
+        });
+    };
+    Ok(merge_two(raw, strip_loaded_children(selected)))
+}
+
+// The effective `strict_merge` setting, read with the same last-layer-wins order as the merge.
+pub fn strict_merge_requested(raw: &RawBuildConfig) -> bool {
+    fn setting(raw: &RawBuildConfig) -> Option<bool> {
+        raw.strict_merge
+            .or_else(|| raw.imported_configs.iter().rev().find_map(setting))
+            .or_else(|| raw.extends_config.as_deref().and_then(setting))
+    }
+    setting(raw).unwrap_or(false)
+}
+
 fn merge_two(mut base: RawBuildConfig, overlay: RawBuildConfig) -> RawBuildConfig {
     if !overlay.build_name.trim().is_empty() {
         base.build_name = overlay.build_name;
@@ -54,9 +96,19 @@ fn merge_two(mut base: RawBuildConfig, overlay: RawBuildConfig) -> RawBuildConfi
         base.preset = overlay.preset;
     }
     base.presets = merge_presets(base.presets, overlay.presets);
+    for (name, profile) in overlay.profiles {
+        let merged = match base.profiles.remove(&name) {
+            Some(existing) => merge_two(existing, profile),
+            None => profile,
+        };
+        base.profiles.insert(name, merged);
+    }
     if overlay.source_path.is_some() {
         base.source_path = overlay.source_path;
     }
+    if overlay.strict_merge.is_some() {
+        base.strict_merge = overlay.strict_merge;
+    }
 
     base.env_files = merge_string_lists(base.env_files, overlay.env_files);
     base.env.extend(overlay.env);
@@ -110,6 +162,10 @@ fn merge_execution_policy(
     base: RawExecutionPolicyConfig,
     overlay: RawExecutionPolicyConfig,
 ) -> RawExecutionPolicyConfig {
+    // Hooks are replaced per operation, like clean profiles, so a layer can swap one
+    // operation's commands without restating the others.
+    let mut hooks = base.hooks;
+    hooks.extend(overlay.hooks);
     RawExecutionPolicyConfig {
         jobs: base.jobs.max(overlay.jobs),
         docker: crate::raw::RawDockerExecutionConfig {
@@ -117,6 +173,15 @@ fn merge_execution_policy(
             image: overlay.docker.image.or(base.docker.image),
         },
         output_retention: merge_output_retention(base.output_retention, overlay.output_retention),
+        clean_env: overlay.clean_env.or(base.clean_env),
+        env_allowlist: merge_string_lists(base.env_allowlist, overlay.env_allowlist),
+        preserve_ansi: overlay.preserve_ansi.or(base.preserve_ansi),
+        heartbeat_seconds: overlay.heartbeat_seconds.or(base.heartbeat_seconds),
+        fold_repeats: overlay.fold_repeats.or(base.fold_repeats),
+        hooks,
+        env_export: overlay.env_export.or(base.env_export),
+        min_free_space_mb: overlay.min_free_space_mb.or(base.min_free_space_mb),
+        stall_warning_seconds: overlay.stall_warning_seconds.or(base.stall_warning_seconds),
     }
 }
 
@@ -146,6 +211,9 @@ fn merge_stage(base: RawStageConfig, overlay: RawStageConfig) -> RawStageConfig
         files: merge_by_key(base.files, overlay.files, |item| item.id.clone()),
         env_sets: merge_by_key(base.env_sets, overlay.env_sets, |item| item.id.clone()),
         services: merge_by_key(base.services, overlay.services, |item| item.id.clone()),
+        overlays: merge_by_key(base.overlays, overlay.overlays, |item| item.id.clone()),
+        symlinks: merge_by_key(base.symlinks, overlay.symlinks, |item| item.id.clone()),
+        fast_copy: overlay.fast_copy.or(base.fast_copy),
     }
 }
 
@@ -173,6 +241,16 @@ fn merge_image(base: RawImageConfig, overlay: RawImageConfig) -> RawImageConfig
             } else {
                 overlay.feed.stage_services
             },
+            stage_overlays: if overlay.feed.stage_overlays.is_empty() {
+                base.feed.stage_overlays
+            } else {
+                overlay.feed.stage_overlays
+            },
+            stage_symlinks: if overlay.feed.stage_symlinks.is_empty() {
+                base.feed.stage_symlinks
+            } else {
+                overlay.feed.stage_symlinks
+            },
         },
         output: RawImageOutputConfig {
             collect_dir: overlay.output.collect_dir.or(base.output.collect_dir),
@@ -198,6 +276,9 @@ fn merge_image_definition(
                 external_tree: base_external_tree,
                 external_tree_mode: base_external_tree_mode,
                 expected_images: base_expected_images,
+                ccache: base_ccache,
+                ccache_dir: base_ccache_dir,
+                incremental: base_incremental,
             },
             RawImageDefinition::Buildroot {
                 source: overlay_source,
@@ -209,6 +290,9 @@ fn merge_image_definition(
                 external_tree: overlay_external_tree,
                 external_tree_mode: overlay_external_tree_mode,
                 expected_images: overlay_expected_images,
+                ccache: overlay_ccache,
+                ccache_dir: overlay_ccache_dir,
+                incremental: overlay_incremental,
             },
         ) => RawImageDefinition::Buildroot {
             source: overlay_source.or(base_source),
@@ -220,6 +304,9 @@ fn merge_image_definition(
             external_tree: overlay_external_tree.or(base_external_tree),
             external_tree_mode: overlay_external_tree_mode.or(base_external_tree_mode),
             expected_images: merge_expected_images(base_expected_images, overlay_expected_images),
+            ccache: overlay_ccache.or(base_ccache),
+            ccache_dir: overlay_ccache_dir.or(base_ccache_dir),
+            incremental: overlay_incremental.or(base_incremental),
         },
         (
             RawImageDefinition::StartingPoint {
@@ -297,6 +384,9 @@ fn merge_image_definition(
                 external_tree: None,
                 external_tree_mode: None,
                 expected_images,
+                ccache: None,
+                ccache_dir: None,
+                incremental: None,
             },
         ) if expected_images.is_empty()
             && config_fragments.is_empty()
@@ -345,6 +435,7 @@ fn merge_reporting(base: RawReportingConfig, overlay: RawReportingConfig) -> Raw
         summary: base.summary || overlay.summary,
         provenance: base.provenance || overlay.provenance,
         manifest: base.manifest || overlay.manifest,
+        sbom: base.sbom || overlay.sbom,
         masking: merge_reporting_masking(base.masking, overlay.masking),
         post_build: merge_reporting_post_build(base.post_build, overlay.post_build),
     }
@@ -405,6 +496,7 @@ fn merge_failure_policy(
             .preserve_failed_outputs
             .or(base.preserve_failed_outputs),
         rollback_domains: overlay.rollback_domains.or(base.rollback_domains),
+        fail_fast: overlay.fail_fast.or(base.fail_fast),
     }
 }
 
@@ -422,6 +514,7 @@ fn merge_provider_policies(
                 .max(overlay.rust.retry_backoff_ms),
             retry_backoff_strategy: overlay.rust.retry_backoff_strategy,
             timeout_seconds: base.rust.timeout_seconds.max(overlay.rust.timeout_seconds),
+            clean_env: overlay.rust.clean_env.or(base.rust.clean_env),
         },
         git: RawGitProviderPolicyConfig {
             allow_remote_resolution: base.git.allow_remote_resolution
@@ -430,6 +523,7 @@ fn merge_provider_policies(
             retry_backoff_ms: base.git.retry_backoff_ms.max(overlay.git.retry_backoff_ms),
             retry_backoff_strategy: overlay.git.retry_backoff_strategy,
             timeout_seconds: base.git.timeout_seconds.max(overlay.git.timeout_seconds),
+            clean_env: overlay.git.clean_env.or(base.git.clean_env),
         },
         archive: merge_command_policy(base.archive, overlay.archive),
         download: merge_command_policy(base.download, overlay.download),
@@ -452,6 +546,7 @@ fn merge_command_policy(
         retry_backoff_strategy: overlay.retry_backoff_strategy,
         timeout_seconds: base.timeout_seconds.max(overlay.timeout_seconds),
         local_jobs: base.local_jobs.max(overlay.local_jobs),
+        clean_env: overlay.clean_env.or(base.clean_env),
     }
 }
 
@@ -619,6 +714,9 @@ mod tests {
                 ],
                 external_tree: None,
                 external_tree_mode: None,
+                ccache: Some(true),
+                ccache_dir: None,
+                incremental: Some(true),
                 expected_images: vec![RawBuildrootExpectedImageConfig {
                     name: "rootfs.squashfs".into(),
                     format: RawBuildrootExpectedImageFormat::Squashfs,
@@ -635,6 +733,9 @@ mod tests {
                 external_tree: None,
                 external_tree_mode: None,
                 expected_images: vec![],
+                ccache: None,
+                ccache_dir: Some("/var/cache/gaia-ccache".into()),
+                incremental: None,
             },
         );
 
@@ -644,9 +745,15 @@ mod tests {
                 config_fragments,
                 config_overrides,
                 expected_images,
+                ccache,
+                ccache_dir,
+                incremental,
                 ..
             } => {
                 assert_eq!(defconfig.as_deref(), Some("raspberrypicm5io_defconfig"));
+                assert_eq!(ccache, Some(true));
+                assert_eq!(incremental, Some(true));
+                assert_eq!(ccache_dir.as_deref(), Some("/var/cache/gaia-ccache"));
                 assert_eq!(
                     config_fragments,
                     vec![
@@ -0,0 +1,130 @@
+use std::path::Path;
+
+use toml::{Table, Value};
+
+use crate::ConfigDoc;
+use crate::ConfigError;
+use crate::raw::RawBuildConfig;
+
+// Array-of-table entries are matched across layers by the first of these keys that every entry
+// carries, so `[[sources]]` pair up by `id` and `expected_images` by `name`.
+const ENTRY_KEYS: [&str; 3] = ["id", "name", "alias"];
+
+// Layers every file of the extends/imports chain as plain TOML values, in the same order
+// `merge_config` merges them, and describes each value a later file gives a different type than
+// the layers below it. A changed `kind` on a table counts too, since `kind` picks the variant the
+// rest of the table is read as. Paths address array entries by id, as in `sources.<id>.kind`.
+pub(crate) fn layer_type_conflicts(raw: &RawBuildConfig) -> Result<Vec<String>, ConfigError> {
+    let mut layered = Value::Table(Table::new());
+    let mut conflicts = Vec::new();
+    layer_file_values(raw, &mut layered, &mut conflicts)?;
+    Ok(conflicts)
+}
+
+fn layer_file_values(
+    raw: &RawBuildConfig,
+    layered: &mut Value,
+    conflicts: &mut Vec<String>,
+) -> Result<(), ConfigError> {
+    for child in raw
+        .extends_config
+        .as_deref()
+        .into_iter()
+        .chain(&raw.imported_configs)
+    {
+        layer_file_values(child, layered, conflicts)?;
+    }
+    let Some(file) = &raw.source_path else {
+        return Ok(());
+    };
+    let doc = ConfigDoc::load(file)?;
+    merge_values(layered, Value::Table(doc.into_table()), "", file, conflicts);
+    Ok(())
+}
+
+fn merge_values(
+    base: &mut Value,
+    overlay: Value,
+    location: &str,
+    file: &Path,
+    conflicts: &mut Vec<String>,
+) {
+    match (base, overlay) {
+        (Value::Table(base), Value::Table(overlay)) => {
+            if let (Some(Value::String(from)), Some(Value::String(to))) =
+                (base.get("kind"), overlay.get("kind"))
+                && from != to
+            {
+                conflicts.push(format!(
+                    "`{}` changes from \"{from}\" to \"{to}\" in '{}'",
+                    join_key(location, "kind"),
+                    file.display()
+                ));
+            }
+            for (key, value) in overlay {
+                let key_location = join_key(location, &key);
+                match base.get_mut(&key) {
+                    Some(existing) => merge_values(existing, value, &key_location, file, conflicts),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (Value::Array(base), Value::Array(overlay)) => match entry_key(base, &overlay) {
+            Some(key) => {
+                for entry in overlay {
+                    let id = entry_id(&entry, key).to_string();
+                    match base
+                        .iter_mut()
+                        .find(|existing| entry_id(existing, key) == id)
+                    {
+                        Some(existing) => {
+                            merge_values(existing, entry, &join_key(location, &id), file, conflicts)
+                        }
+                        None => base.push(entry),
+                    }
+                }
+            }
+            None => *base = overlay,
+        },
+        (base, overlay) => {
+            if base.type_str() != overlay.type_str() {
+                conflicts.push(format!(
+                    "`{location}` is {} in a lower layer but {} in '{}'",
+                    with_article(base.type_str()),
+                    with_article(overlay.type_str()),
+                    file.display()
+                ));
+            }
+            *base = overlay;
+        }
+    }
+}
+
+fn entry_key(base: &[Value], overlay: &[Value]) -> Option<&'static str> {
+    ENTRY_KEYS.into_iter().find(|key| {
+        base.iter()
+            .chain(overlay)
+            .all(|entry| entry.get(key).is_some_and(Value::is_str))
+    })
+}
+
+fn entry_id<'a>(entry: &'a Value, key: &str) -> &'a str {
+    entry.get(key).and_then(Value::as_str).unwrap_or_default()
+}
+
+fn join_key(location: &str, key: &str) -> String {
+    if location.is_empty() {
+        key.to_string()
+    } else {
+        format!("{location}.{key}")
+    }
+}
+
+fn with_article(type_name: &str) -> String {
+    match type_name {
+        "array" | "integer" => format!("an {type_name}"),
+        _ => format!("a {type_name}"),
+    }
+}
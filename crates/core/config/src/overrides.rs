@@ -1,4 +1,5 @@
 use crate::{ConfigError, ResolveOptions, raw};
+use gaia_spec::InputValueOrigin;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum KnownOverrideKey {
@@ -20,11 +21,16 @@ enum KnownOverrideKey {
     ImageFeedStageFiles,
     ImageFeedStageEnvSets,
     ImageFeedStageServices,
+    ImageFeedStageOverlays,
+    ImageFeedStageSymlinks,
     ImageBuildrootDefconfig,
     ImageBuildrootAllowFallback,
     ImageBuildrootExternalTree,
     ImageBuildrootSource,
     ImageBuildrootExternalTreeMode,
+    ImageBuildrootCcache,
+    ImageBuildrootCcacheDir,
+    ImageBuildrootIncremental,
     ImageStartingPointRootfsPath,
     ImageStartingPointSource,
     ImageStartingPointSourcePath,
@@ -37,9 +43,18 @@ enum KnownOverrideKey {
     ProvenanceIdentityVendor,
     ProvenanceIdentityChannel,
     PolicyFailureRollbackOnError,
+    PolicyFailureFailFast,
     ExecutionJobs,
     ExecutionDockerEnabled,
     ExecutionDockerImage,
+    ExecutionCleanEnv,
+    ExecutionPreserveAnsi,
+    ExecutionHeartbeatSeconds,
+    ExecutionFoldRepeats,
+    ExecutionEnvExport,
+    ExecutionMinFreeSpaceMb,
+    ExecutionStallWarningSeconds,
+    StageFastCopy,
     ExecutionOutputRetentionStdoutBytes,
     ExecutionOutputRetentionStderrBytes,
     ExecutionOutputRetentionStdoutLines,
@@ -105,6 +120,8 @@ impl<'a> OverrideKey<'a> {
             "image.feed.stage_files" => Self::Known(KnownOverrideKey::ImageFeedStageFiles),
             "image.feed.stage_env_sets" => Self::Known(KnownOverrideKey::ImageFeedStageEnvSets),
             "image.feed.stage_services" => Self::Known(KnownOverrideKey::ImageFeedStageServices),
+            "image.feed.stage_overlays" => Self::Known(KnownOverrideKey::ImageFeedStageOverlays),
+            "image.feed.stage_symlinks" => Self::Known(KnownOverrideKey::ImageFeedStageSymlinks),
             "image.buildroot.defconfig" => Self::Known(KnownOverrideKey::ImageBuildrootDefconfig),
             "image.allow_fallback" | "image.buildroot.allow_fallback" => {
                 Self::Known(KnownOverrideKey::ImageBuildrootAllowFallback)
@@ -116,6 +133,11 @@ impl<'a> OverrideKey<'a> {
             "image.buildroot.external_tree_mode" => {
                 Self::Known(KnownOverrideKey::ImageBuildrootExternalTreeMode)
             }
+            "image.buildroot.ccache" => Self::Known(KnownOverrideKey::ImageBuildrootCcache),
+            "image.buildroot.ccache_dir" => Self::Known(KnownOverrideKey::ImageBuildrootCcacheDir),
+            "image.buildroot.incremental" => {
+                Self::Known(KnownOverrideKey::ImageBuildrootIncremental)
+            }
             "image.starting-point.rootfs_path" => {
                 Self::Known(KnownOverrideKey::ImageStartingPointRootfsPath)
             }
@@ -146,6 +168,7 @@ impl<'a> OverrideKey<'a> {
             "policy.failure.rollback_on_error" => {
                 Self::Known(KnownOverrideKey::PolicyFailureRollbackOnError)
             }
+            "policy.failure.fail_fast" => Self::Known(KnownOverrideKey::PolicyFailureFailFast),
             "execution.jobs" | "policy.execution.jobs" => {
                 Self::Known(KnownOverrideKey::ExecutionJobs)
             }
@@ -155,6 +178,28 @@ impl<'a> OverrideKey<'a> {
             "execution.docker.image" | "policy.execution.docker.image" => {
                 Self::Known(KnownOverrideKey::ExecutionDockerImage)
             }
+            "execution.clean_env" | "policy.execution.clean_env" => {
+                Self::Known(KnownOverrideKey::ExecutionCleanEnv)
+            }
+            "execution.preserve_ansi" | "policy.execution.preserve_ansi" => {
+                Self::Known(KnownOverrideKey::ExecutionPreserveAnsi)
+            }
+            "execution.heartbeat_seconds" | "policy.execution.heartbeat_seconds" => {
+                Self::Known(KnownOverrideKey::ExecutionHeartbeatSeconds)
+            }
+            "execution.fold_repeats" | "policy.execution.fold_repeats" => {
+                Self::Known(KnownOverrideKey::ExecutionFoldRepeats)
+            }
+            "execution.env_export" | "policy.execution.env_export" => {
+                Self::Known(KnownOverrideKey::ExecutionEnvExport)
+            }
+            "execution.min_free_space_mb" | "policy.execution.min_free_space_mb" => {
+                Self::Known(KnownOverrideKey::ExecutionMinFreeSpaceMb)
+            }
+            "execution.stall_warning_seconds" | "policy.execution.stall_warning_seconds" => {
+                Self::Known(KnownOverrideKey::ExecutionStallWarningSeconds)
+            }
+            "stage.fast_copy" => Self::Known(KnownOverrideKey::StageFastCopy),
             "execution.output_retention.stdout_bytes"
             | "policy.execution.output_retention.stdout_bytes" => {
                 Self::Known(KnownOverrideKey::ExecutionOutputRetentionStdoutBytes)
@@ -399,6 +444,12 @@ fn apply_known_override(
         KnownOverrideKey::ImageFeedStageServices => {
             raw.image.feed.stage_services = split_csv(value)
         }
+        KnownOverrideKey::ImageFeedStageOverlays => {
+            raw.image.feed.stage_overlays = split_csv(value)
+        }
+        KnownOverrideKey::ImageFeedStageSymlinks => {
+            raw.image.feed.stage_symlinks = split_csv(value)
+        }
         KnownOverrideKey::ImageBuildrootDefconfig => {
             if let raw::RawImageDefinition::Buildroot { defconfig, .. } = &mut raw.image.definition
             {
@@ -424,6 +475,24 @@ fn apply_known_override(
                 *source = Some(value.to_string());
             }
         }
+        KnownOverrideKey::ImageBuildrootCcache => {
+            if let raw::RawImageDefinition::Buildroot { ccache, .. } = &mut raw.image.definition {
+                *ccache = Some(parse_bool_override(key, value)?);
+            }
+        }
+        KnownOverrideKey::ImageBuildrootCcacheDir => {
+            if let raw::RawImageDefinition::Buildroot { ccache_dir, .. } = &mut raw.image.definition
+            {
+                *ccache_dir = Some(value.to_string());
+            }
+        }
+        KnownOverrideKey::ImageBuildrootIncremental => {
+            if let raw::RawImageDefinition::Buildroot { incremental, .. } =
+                &mut raw.image.definition
+            {
+                *incremental = Some(parse_bool_override(key, value)?);
+            }
+        }
         KnownOverrideKey::ImageBuildrootExternalTreeMode => {
             if let raw::RawImageDefinition::Buildroot {
                 external_tree_mode, ..
@@ -531,13 +600,42 @@ fn apply_known_override(
         KnownOverrideKey::PolicyFailureRollbackOnError => {
             raw.failure.rollback_on_error = Some(parse_bool_override(key, value)?)
         }
-        KnownOverrideKey::ExecutionJobs => raw.execution.jobs = parse_u32_override(key, value)?,
+        KnownOverrideKey::PolicyFailureFailFast => {
+            raw.failure.fail_fast = Some(parse_bool_override(key, value)?)
+        }
+        // `0` means auto, which defers to the config's own `jobs` before the CPU count.
+        KnownOverrideKey::ExecutionJobs => match parse_u32_override(key, value)? {
+            0 => {}
+            jobs => raw.execution.jobs = jobs,
+        },
         KnownOverrideKey::ExecutionDockerEnabled => {
             raw.execution.docker.enabled = parse_bool_override(key, value)?
         }
         KnownOverrideKey::ExecutionDockerImage => {
             raw.execution.docker.image = Some(value.to_string())
         }
+        KnownOverrideKey::ExecutionCleanEnv => {
+            raw.execution.clean_env = Some(parse_bool_override(key, value)?)
+        }
+        KnownOverrideKey::ExecutionPreserveAnsi => {
+            raw.execution.preserve_ansi = Some(parse_bool_override(key, value)?)
+        }
+        KnownOverrideKey::ExecutionHeartbeatSeconds => {
+            raw.execution.heartbeat_seconds = Some(parse_u64_override(key, value)?)
+        }
+        KnownOverrideKey::ExecutionFoldRepeats => {
+            raw.execution.fold_repeats = Some(parse_bool_override(key, value)?)
+        }
+        KnownOverrideKey::ExecutionEnvExport => raw.execution.env_export = Some(value.to_string()),
+        KnownOverrideKey::ExecutionMinFreeSpaceMb => {
+            raw.execution.min_free_space_mb = Some(parse_u64_override(key, value)?)
+        }
+        KnownOverrideKey::ExecutionStallWarningSeconds => {
+            raw.execution.stall_warning_seconds = Some(parse_u64_override(key, value)?)
+        }
+        KnownOverrideKey::StageFastCopy => {
+            raw.stage.fast_copy = Some(parse_bool_override(key, value)?)
+        }
         KnownOverrideKey::ExecutionOutputRetentionStdoutBytes => {
             raw.execution.output_retention.stdout_bytes = parse_usize_override(key, value)?
         }
@@ -662,6 +760,45 @@ pub(crate) fn collect_selected_inputs(raw: &raw::RawBuildConfig) -> Vec<(String,
     selected
 }
 
+// Mirrors the layering in `collect_selected_inputs`: defaults, then the selected preset's
+// overrides, then `--set`.
+pub(crate) fn selected_input_origins(raw: &raw::RawBuildConfig) -> Vec<(String, InputValueOrigin)> {
+    let input_name = |key: &str| match OverrideKey::parse(key) {
+        OverrideKey::Input(name) if raw.inputs.contains_key(name) => Some(name.to_string()),
+        _ => None,
+    };
+    let preset_inputs = raw
+        .preset
+        .as_ref()
+        .and_then(|preset| raw.presets.get(preset))
+        .map(|preset| {
+            preset
+                .overrides
+                .iter()
+                .filter_map(|(key, _)| input_name(key))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let cli_inputs = raw
+        .explicit_overrides
+        .iter()
+        .filter_map(|(key, _)| input_name(key))
+        .collect::<Vec<_>>();
+    raw.selected_inputs
+        .iter()
+        .map(|(name, _)| {
+            let origin = if cli_inputs.contains(name) {
+                InputValueOrigin::CliOverride
+            } else if preset_inputs.contains(name) {
+                InputValueOrigin::Preset
+            } else {
+                InputValueOrigin::Default
+            };
+            (name.clone(), origin)
+        })
+        .collect()
+}
+
 fn parse_bool_override(key: &str, value: &str) -> Result<bool, ConfigError> {
     match value {
         "1" | "true" | "yes" | "on" => Ok(true),
@@ -22,12 +22,17 @@ fn documented_override_keys_parse_as_typed_keys() {
         "image.feed.stage_files",
         "image.feed.stage_env_sets",
         "image.feed.stage_services",
+        "image.feed.stage_overlays",
+        "image.feed.stage_symlinks",
         "image.buildroot.defconfig",
         "image.allow_fallback",
         "image.buildroot.allow_fallback",
         "image.buildroot.external_tree",
         "image.buildroot.source",
         "image.buildroot.external_tree_mode",
+        "image.buildroot.ccache",
+        "image.buildroot.ccache_dir",
+        "image.buildroot.incremental",
         "image.starting-point.rootfs_path",
         "image.starting-point.source",
         "image.starting-point.source_path",
@@ -40,12 +45,28 @@ fn documented_override_keys_parse_as_typed_keys() {
         "provenance.identity.vendor",
         "provenance.identity.channel",
         "policy.failure.rollback_on_error",
+        "policy.failure.fail_fast",
         "execution.jobs",
         "policy.execution.jobs",
         "execution.docker.enabled",
         "policy.execution.docker.enabled",
         "execution.docker.image",
         "policy.execution.docker.image",
+        "execution.clean_env",
+        "policy.execution.clean_env",
+        "execution.preserve_ansi",
+        "policy.execution.preserve_ansi",
+        "execution.heartbeat_seconds",
+        "policy.execution.heartbeat_seconds",
+        "execution.fold_repeats",
+        "policy.execution.fold_repeats",
+        "execution.env_export",
+        "policy.execution.env_export",
+        "execution.min_free_space_mb",
+        "policy.execution.min_free_space_mb",
+        "execution.stall_warning_seconds",
+        "policy.execution.stall_warning_seconds",
+        "stage.fast_copy",
         "execution.output_retention.stdout_bytes",
         "policy.execution.output_retention.stdout_bytes",
         "execution.output_retention.stderr_bytes",
@@ -20,8 +20,11 @@ pub struct RawBuildConfig {
     pub inputs: BTreeMap<String, RawInputOptionConfig>,
     pub preset: Option<String>,
     pub presets: BTreeMap<String, RawPresetConfig>,
+    // Partial build configs; `--profile <name>` merges one over the base and the rest are dropped.
+    pub profiles: BTreeMap<String, RawBuildConfig>,
     pub extends: Option<String>,
     pub imports: Vec<String>,
+    pub strict_merge: Option<bool>,
     pub env_files: Vec<String>,
     pub env: BTreeMap<String, String>,
     pub workspace: RawWorkspaceConfig,
@@ -141,6 +144,50 @@ pub struct RawInputOptionConfig {
     pub required: bool,
     pub default: Option<String>,
     pub choices: Vec<String>,
+    #[serde(deserialize_with = "deserialize_input_bound")]
+    pub min: Option<String>,
+    #[serde(deserialize_with = "deserialize_input_bound")]
+    pub max: Option<String>,
+    pub pattern: Option<String>,
+}
+
+// Buildroot symbols may be written as `[["KEY", "value"]]` pairs, which keep their order, or as an
+// inline `{ KEY = "value" }` table, which is applied in key order.
+fn deserialize_buildroot_symbols<'de, D>(deserializer: D) -> Result<Vec<(String, String)>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum RawBuildrootSymbols {
+        Pairs(Vec<(String, String)>),
+        Table(BTreeMap<String, String>),
+    }
+
+    Ok(match RawBuildrootSymbols::deserialize(deserializer)? {
+        RawBuildrootSymbols::Pairs(pairs) => pairs,
+        RawBuildrootSymbols::Table(table) => table.into_iter().collect(),
+    })
+}
+
+// Bounds are written as TOML numbers but kept as strings, like input defaults, so raw configs stay `Eq`.
+fn deserialize_input_bound<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum RawInputBound {
+        Integer(i64),
+        Float(f64),
+        Text(String),
+    }
+
+    Ok(Some(match RawInputBound::deserialize(deserializer)? {
+        RawInputBound::Integer(value) => value.to_string(),
+        RawInputBound::Float(value) => value.to_string(),
+        RawInputBound::Text(value) => value,
+    }))
 }
 
 #[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
@@ -149,8 +196,10 @@ pub enum RawInputKind {
     #[default]
     String,
     Integer,
+    Float,
     Boolean,
     Enum,
+    Datetime,
 }
 
 #[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
@@ -175,6 +224,7 @@ pub struct RawCleanProfileConfig {
     pub description: Option<String>,
     pub build: bool,
     pub out: bool,
+    pub cache: bool,
     pub paths: Vec<String>,
 }
 
@@ -184,6 +234,31 @@ pub struct RawExecutionPolicyConfig {
     pub jobs: u32,
     pub docker: RawDockerExecutionConfig,
     pub output_retention: RawOutputRetentionPolicyConfig,
+    pub clean_env: Option<bool>,
+    pub env_allowlist: Vec<String>,
+    pub preserve_ansi: Option<bool>,
+    pub heartbeat_seconds: Option<u64>,
+    pub fold_repeats: Option<bool>,
+    pub hooks: BTreeMap<String, RawOperationHooksConfig>,
+    pub env_export: Option<String>,
+    pub min_free_space_mb: Option<u64>,
+    pub stall_warning_seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct RawOperationHooksConfig {
+    pub pre: Vec<String>,
+    pub post: Vec<String>,
+    pub post_failure: Option<RawHookFailureMode>,
+    pub timeout_seconds: u64,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum RawHookFailureMode {
+    Warn,
+    Fail,
 }
 
 #[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
@@ -209,6 +284,7 @@ pub struct RawFailurePolicyConfig {
     pub rollback_on_error: Option<bool>,
     pub preserve_failed_outputs: Option<bool>,
     pub rollback_domains: Option<Vec<RawRollbackDomain>>,
+    pub fail_fast: Option<bool>,
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
@@ -245,6 +321,7 @@ pub struct RawRustProviderPolicyConfig {
     pub retry_backoff_ms: u64,
     pub retry_backoff_strategy: RawRetryBackoffStrategy,
     pub timeout_seconds: u64,
+    pub clean_env: Option<bool>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
@@ -255,6 +332,7 @@ pub struct RawGitProviderPolicyConfig {
     pub retry_backoff_ms: u64,
     pub retry_backoff_strategy: RawRetryBackoffStrategy,
     pub timeout_seconds: u64,
+    pub clean_env: Option<bool>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
@@ -265,6 +343,7 @@ pub struct RawCommandProviderPolicyConfig {
     pub retry_backoff_strategy: RawRetryBackoffStrategy,
     pub timeout_seconds: u64,
     pub local_jobs: u32,
+    pub clean_env: Option<bool>,
 }
 
 #[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
@@ -383,6 +462,10 @@ pub struct RawArtifactConfig {
     pub install_class: Option<RawArtifactInstallClass>,
     #[serde(default)]
     pub install_dest_hint: Option<String>,
+    #[serde(default)]
+    pub mutex_group: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_artifact_instances")]
+    pub instances: Vec<String>,
     pub output_path: String,
     #[serde(flatten)]
     pub definition: RawArtifactDefinition,
@@ -419,6 +502,13 @@ pub enum RawArtifactInstallClass {
     Data,
 }
 
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum RawJavaBuildTool {
+    Maven,
+    Gradle,
+}
+
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
 #[serde(tag = "kind", rename_all = "kebab-case")]
 pub enum RawArtifactDefinition {
@@ -428,9 +518,17 @@ pub enum RawArtifactDefinition {
         target_name: Option<String>,
         #[serde(default)]
         emit_directory: bool,
+        #[serde(default)]
+        linker: Option<String>,
+        #[serde(default)]
+        sysroot: Option<String>,
     },
     Java {
         build_target: String,
+        #[serde(default)]
+        build_tool: Option<RawJavaBuildTool>,
+        #[serde(default)]
+        tasks: Vec<String>,
     },
     Node {
         package_dir: String,
@@ -466,6 +564,9 @@ pub struct RawStageConfig {
     pub files: Vec<RawStageFileConfig>,
     pub env_sets: Vec<RawStageEnvSetConfig>,
     pub services: Vec<RawStageServiceConfig>,
+    pub overlays: Vec<RawStageOverlayConfig>,
+    pub symlinks: Vec<RawStageSymlinkConfig>,
+    pub fast_copy: Option<bool>,
 }
 
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
@@ -477,6 +578,12 @@ pub struct RawStageFileConfig {
     pub dest: String,
     #[serde(default)]
     pub origin: Option<RawStageContentOrigin>,
+    #[serde(default)]
+    pub uid: Option<u32>,
+    #[serde(default)]
+    pub gid: Option<u32>,
+    #[serde(default)]
+    pub caps: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
@@ -506,6 +613,24 @@ pub struct RawStageServiceConfig {
     pub unit_path: String,
 }
 
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct RawStageOverlayConfig {
+    pub id: String,
+    #[serde(default)]
+    pub when: Option<RawWhenConfig>,
+    pub src: String,
+    pub dest: String,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct RawStageSymlinkConfig {
+    pub id: String,
+    #[serde(default)]
+    pub when: Option<RawWhenConfig>,
+    pub target: String,
+    pub link: String,
+}
+
 #[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
 #[serde(default, deny_unknown_fields)]
 pub struct RawWhenConfig {
@@ -548,7 +673,7 @@ pub enum RawImageDefinition {
         allow_fallback: bool,
         #[serde(default)]
         config_fragments: Vec<String>,
-        #[serde(default)]
+        #[serde(default, deserialize_with = "deserialize_buildroot_symbols")]
         config_overrides: Vec<(String, String)>,
         #[serde(default)]
         external_tree: Option<String>,
@@ -556,6 +681,12 @@ pub enum RawImageDefinition {
         external_tree_mode: Option<RawBuildrootExternalTreeMode>,
         #[serde(default)]
         expected_images: Vec<RawBuildrootExpectedImageConfig>,
+        #[serde(default)]
+        ccache: Option<bool>,
+        #[serde(default)]
+        ccache_dir: Option<String>,
+        #[serde(default)]
+        incremental: Option<bool>,
     },
     StartingPoint {
         #[serde(default)]
@@ -589,6 +720,9 @@ impl Default for RawImageDefinition {
             external_tree: None,
             external_tree_mode: None,
             expected_images: Vec::new(),
+            ccache: None,
+            ccache_dir: None,
+            incremental: None,
         }
     }
 }
@@ -601,6 +735,8 @@ pub struct RawImageFeedConfig {
     pub stage_files: Vec<String>,
     pub stage_env_sets: Vec<String>,
     pub stage_services: Vec<String>,
+    pub stage_overlays: Vec<String>,
+    pub stage_symlinks: Vec<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
@@ -677,11 +813,55 @@ pub struct RawImageOutputConfig {
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
 pub struct RawCheckpointConfig {
     pub id: String,
-    pub backend: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_checkpoint_backends")]
+    pub backend: Vec<String>,
+    #[serde(default)]
+    pub upload_backends: Vec<String>,
     pub use_policy: RawCheckpointPolicy,
     pub upload_policy: RawCheckpointPolicy,
     #[serde(default)]
     pub anchor: Option<String>,
+    #[serde(default)]
+    pub fingerprint_from: Vec<String>,
+    #[serde(default)]
+    pub restore_to: Option<String>,
+}
+
+// `backend` may name one backend or list restore fallbacks in the order they are tried.
+fn deserialize_checkpoint_backends<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum RawCheckpointBackends {
+        One(String),
+        Ordered(Vec<String>),
+    }
+
+    Ok(match RawCheckpointBackends::deserialize(deserializer)? {
+        RawCheckpointBackends::One(backend) => vec![backend],
+        RawCheckpointBackends::Ordered(backends) => backends,
+    })
+}
+
+// `instances` may list names or give one string, typically an interpolated value, that expands
+// into the names.
+fn deserialize_artifact_instances<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum RawArtifactInstances {
+        One(String),
+        Listed(Vec<String>),
+    }
+
+    Ok(match RawArtifactInstances::deserialize(deserializer)? {
+        RawArtifactInstances::One(instances) => vec![instances],
+        RawArtifactInstances::Listed(instances) => instances,
+    })
 }
 
 #[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
@@ -699,6 +879,7 @@ pub struct RawReportingConfig {
     pub summary: bool,
     pub provenance: bool,
     pub manifest: bool,
+    pub sbom: bool,
     pub masking: RawReportingMaskingConfig,
     pub post_build: Option<RawPostBuildHookConfig>,
 }
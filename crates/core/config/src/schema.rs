@@ -0,0 +1,732 @@
+use serde_json::{Map, Value, json};
+
+// The schema mirrors the serde shape of `RawBuildConfig` by hand: raw types stay free of schema
+// derives, so new raw fields need a matching entry here for editors to complete them.
+pub fn config_schema() -> Value {
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "Gaia build config",
+        "type": "object",
+        "additionalProperties": false,
+        "properties": {
+            "build_name": string(),
+            "display_name": string(),
+            "version": string(),
+            "description": string(),
+            "branch": string(),
+            "target": string(),
+            "profile": string(),
+            "labels": pairs(),
+            "product": object(
+                &[("family", string()), ("name", string()), ("sku", string())],
+                &[],
+            ),
+            "inputs": map_of(input_schema()),
+            "preset": string(),
+            "presets": map_of(object(
+                &[
+                    ("env_files", strings()),
+                    ("env", map_of(string())),
+                    ("overrides", pairs()),
+                ],
+                &[],
+            )),
+            // Each profile is a partial build config merged over the base when selected.
+            "profiles": map_of(json!({ "type": "object" })),
+            "extends": string(),
+            "imports": strings(),
+            "strict_merge": boolean(),
+            "env_files": strings(),
+            "env": map_of(string()),
+            "workspace": workspace_schema(),
+            "sources": array_of(source_schema()),
+            "artifacts": array_of(artifact_schema()),
+            "install": array_of(install_schema()),
+            "stage": stage_schema(),
+            "image": image_schema(),
+            "checkpoints": array_of(checkpoint_schema()),
+            // Each table holds fields shared by every entry of that section.
+            "defaults": object(
+                &[
+                    ("sources", json!({ "type": "object" })),
+                    ("artifacts", json!({ "type": "object" })),
+                    ("install", json!({ "type": "object" })),
+                    ("checkpoints", json!({ "type": "object" })),
+                ],
+                &[],
+            ),
+            "interpolation": object(
+                &[("allow_unresolved", boolean()), ("values", pairs())],
+                &[],
+            ),
+            "clean": clean_schema(),
+            "execution": execution_schema(),
+            "failure": object(
+                &[
+                    ("rollback_on_error", boolean()),
+                    ("preserve_failed_outputs", boolean()),
+                    ("fail_fast", boolean()),
+                    (
+                        "rollback_domains",
+                        array_of(enumeration(&[
+                            "sources",
+                            "artifacts",
+                            "installs",
+                            "stage",
+                            "images",
+                            "checkpoints",
+                        ])),
+                    ),
+                ],
+                &[],
+            ),
+            "providers": providers_schema(),
+            "provenance": object(
+                &[(
+                    "identity",
+                    object(
+                        &[
+                            ("project", string()),
+                            ("vendor", string()),
+                            ("channel", string()),
+                            ("labels", pairs()),
+                        ],
+                        &[],
+                    ),
+                )],
+                &[],
+            ),
+            "reporting": reporting_schema(),
+        },
+        "$defs": {
+            "when": when_schema(),
+        },
+    })
+}
+
+// The keys a provider kind adds under `sources[]`, `artifacts[]`, or `image`, as dotted config
+// paths led by the `kind` key that selects it. Fields every kind of the section shares are left
+// out, since they say nothing about the provider.
+pub fn provider_config_keys(section: &str, kind: &str) -> Vec<String> {
+    let schema = config_schema();
+    let section_schema = &schema["properties"][section];
+    let (variants, prefix) = match section_schema.get("items") {
+        Some(items) => (&items["oneOf"], format!("{section}[]")),
+        None => (&section_schema["oneOf"], section.to_string()),
+    };
+    let variants = variants.as_array().map(Vec::as_slice).unwrap_or_default();
+    let keys_of = |variant: &Value| {
+        variant["properties"]
+            .as_object()
+            .map(|properties| properties.keys().cloned().collect::<Vec<_>>())
+            .unwrap_or_default()
+    };
+    let Some(variant) = variants
+        .iter()
+        .find(|variant| variant["properties"]["kind"]["const"] == kind)
+    else {
+        return Vec::new();
+    };
+    let mut keys = vec![format!("{prefix}.kind")];
+    keys.extend(
+        keys_of(variant)
+            .into_iter()
+            .filter(|key| {
+                key != "kind" && !variants.iter().all(|other| keys_of(other).contains(key))
+            })
+            .map(|key| format!("{prefix}.{key}")),
+    );
+    keys
+}
+
+fn input_schema() -> Value {
+    object(
+        &[
+            ("description", string()),
+            (
+                "kind",
+                enumeration(&["string", "integer", "float", "boolean", "enum", "datetime"]),
+            ),
+            ("required", boolean()),
+            ("default", string()),
+            ("choices", strings()),
+            ("min", json!({ "type": ["number", "string"] })),
+            ("max", json!({ "type": ["number", "string"] })),
+            ("pattern", string()),
+        ],
+        &[],
+    )
+}
+
+fn workspace_schema() -> Value {
+    object(
+        &[
+            ("root_dir", string()),
+            ("build_dir", string()),
+            ("out_dir", string()),
+            (
+                "named_paths",
+                array_of(object(
+                    &[
+                        ("alias", string()),
+                        ("path", string()),
+                        ("kind", enumeration(&["host", "logical"])),
+                    ],
+                    &[],
+                )),
+            ),
+        ],
+        &[],
+    )
+}
+
+fn source_schema() -> Value {
+    let refresh = || enumeration(&["auto", "always", "never"]);
+    let pin = || enumeration(&["floating", "locked"]);
+    tagged(
+        &[("id", string())],
+        &["id"],
+        &[
+            (
+                "git",
+                vec![
+                    ("repo", string()),
+                    ("branch", string()),
+                    ("tag", string()),
+                    ("rev", string()),
+                    ("subdir", string()),
+                    ("update", boolean()),
+                    ("refresh", refresh()),
+                    ("pin", pin()),
+                ],
+                &["repo"],
+            ),
+            (
+                "path",
+                vec![
+                    ("path", string()),
+                    ("identity_ignore", strings()),
+                    ("refresh", refresh()),
+                    ("pin", pin()),
+                ],
+                &["path"],
+            ),
+            (
+                "archive",
+                vec![
+                    ("path", string()),
+                    ("strip_components", unsigned()),
+                    ("refresh", refresh()),
+                    ("pin", pin()),
+                ],
+                &["path", "strip_components"],
+            ),
+            (
+                "download",
+                vec![
+                    ("url", string()),
+                    ("sha256", string()),
+                    ("output_path", string()),
+                    ("refresh", refresh()),
+                    ("pin", pin()),
+                ],
+                &["url", "output_path"],
+            ),
+        ],
+    )
+}
+
+fn artifact_schema() -> Value {
+    tagged(
+        &[
+            ("id", string()),
+            ("when", when_ref()),
+            ("source", string()),
+            (
+                "execution",
+                object(
+                    &[
+                        ("backend", enumeration(&["host", "docker"])),
+                        ("docker", object(&[("image", string())], &[])),
+                    ],
+                    &[],
+                ),
+            ),
+            ("target", string()),
+            ("profile", string()),
+            ("dependencies", strings()),
+            ("install_name", string()),
+            (
+                "install_class",
+                enumeration(&["binary", "library", "archive", "config", "service", "data"]),
+            ),
+            ("install_dest_hint", string()),
+            ("mutex_group", string()),
+            ("instances", json!({ "oneOf": [string(), strings()] })),
+            ("output_path", string()),
+        ],
+        &["id", "output_path"],
+        &[
+            (
+                "rust",
+                vec![
+                    ("package", string()),
+                    ("target_name", string()),
+                    ("emit_directory", boolean()),
+                    ("linker", string()),
+                    ("sysroot", string()),
+                ],
+                &["package"],
+            ),
+            (
+                "java",
+                vec![
+                    ("build_target", string()),
+                    ("build_tool", enumeration(&["maven", "gradle"])),
+                    ("tasks", strings()),
+                ],
+                &["build_target"],
+            ),
+            ("node", vec![("package_dir", string())], &["package_dir"]),
+            ("python", vec![("package_dir", string())], &["package_dir"]),
+            ("go", vec![("package", string())], &["package"]),
+        ],
+    )
+}
+
+fn install_schema() -> Value {
+    object(
+        &[
+            ("id", string()),
+            ("when", when_ref()),
+            ("artifact", string()),
+            ("dest", string()),
+            ("replace", boolean()),
+            ("mode", unsigned()),
+            ("owner", string()),
+            ("group", string()),
+        ],
+        &["id", "artifact", "dest"],
+    )
+}
+
+fn stage_schema() -> Value {
+    object(
+        &[
+            (
+                "files",
+                array_of(object(
+                    &[
+                        ("id", string()),
+                        ("when", when_ref()),
+                        ("src", string()),
+                        ("dest", string()),
+                        (
+                            "origin",
+                            enumeration(&["static-asset", "generated", "provider-emitted"]),
+                        ),
+                        ("uid", unsigned()),
+                        ("gid", unsigned()),
+                        ("caps", string()),
+                    ],
+                    &["id", "src", "dest"],
+                )),
+            ),
+            (
+                "env_sets",
+                array_of(object(
+                    &[
+                        ("id", string()),
+                        ("when", when_ref()),
+                        ("name", string()),
+                        ("entries", pairs()),
+                    ],
+                    &["id", "name", "entries"],
+                )),
+            ),
+            (
+                "services",
+                array_of(object(
+                    &[
+                        ("id", string()),
+                        ("when", when_ref()),
+                        ("name", string()),
+                        ("unit_path", string()),
+                    ],
+                    &["id", "name", "unit_path"],
+                )),
+            ),
+            (
+                "overlays",
+                array_of(object(
+                    &[
+                        ("id", string()),
+                        ("when", when_ref()),
+                        ("src", string()),
+                        ("dest", string()),
+                    ],
+                    &["id", "src", "dest"],
+                )),
+            ),
+            (
+                "symlinks",
+                array_of(object(
+                    &[
+                        ("id", string()),
+                        ("when", when_ref()),
+                        ("target", string()),
+                        ("link", string()),
+                    ],
+                    &["id", "target", "link"],
+                )),
+            ),
+            ("fast_copy", boolean()),
+        ],
+        &[],
+    )
+}
+
+fn image_schema() -> Value {
+    let feed = object(
+        &[
+            ("install_entries", strings()),
+            ("stage_files", strings()),
+            ("stage_env_sets", strings()),
+            ("stage_services", strings()),
+            ("stage_overlays", strings()),
+            ("stage_symlinks", strings()),
+        ],
+        &[],
+    );
+    let output = object(
+        &[
+            ("collect_dir", string()),
+            ("archive_name", string()),
+            ("emit_report", boolean()),
+        ],
+        &[],
+    );
+    let packages = object(
+        &[
+            ("enabled", boolean()),
+            ("execute", boolean()),
+            ("manager", string()),
+            ("release_version", string()),
+            ("allow_major_upgrade", boolean()),
+            ("update", boolean()),
+            ("dist_upgrade", boolean()),
+            ("install", strings()),
+            ("remove", strings()),
+            ("extra_args", strings()),
+            ("os_release_path", string()),
+        ],
+        &[],
+    );
+    let expected_image = object(
+        &[
+            ("name", string()),
+            (
+                "format",
+                enumeration(&["tar", "ext4", "squashfs", "raw", "kernel"]),
+            ),
+            ("required", boolean()),
+        ],
+        &["name", "format"],
+    );
+    tagged(
+        &[("feed", feed), ("output", output)],
+        &[],
+        &[
+            (
+                "buildroot",
+                vec![
+                    ("source", string()),
+                    ("defconfig", string()),
+                    ("defconfig_path", string()),
+                    ("allow_fallback", boolean()),
+                    ("config_fragments", strings()),
+                    (
+                        "config_overrides",
+                        json!({ "oneOf": [map_of(string()), pairs()] }),
+                    ),
+                    ("external_tree", string()),
+                    (
+                        "external_tree_mode",
+                        enumeration(&["auto", "required", "disabled"]),
+                    ),
+                    ("expected_images", array_of(expected_image)),
+                    ("ccache", boolean()),
+                    ("ccache_dir", string()),
+                    ("incremental", boolean()),
+                ],
+                &[],
+            ),
+            (
+                "starting-point",
+                vec![
+                    ("source", string()),
+                    ("source_path", string()),
+                    ("rootfs_path", string()),
+                    ("image_partition", string()),
+                    ("image_read_only", boolean()),
+                    ("packages", packages),
+                    (
+                        "rootfs_validation_mode",
+                        enumeration(&[
+                            "require-exists",
+                            "require-directory",
+                            "require-file",
+                            "allow-missing",
+                        ]),
+                    ),
+                    (
+                        "output_mode",
+                        enumeration(&["copy-rootfs", "archive-only", "copy-and-archive"]),
+                    ),
+                ],
+                &[],
+            ),
+        ],
+    )
+}
+
+fn checkpoint_schema() -> Value {
+    let policy = || enumeration(&["off", "auto", "always"]);
+    object(
+        &[
+            ("id", string()),
+            ("backend", json!({ "oneOf": [string(), strings()] })),
+            ("upload_backends", strings()),
+            ("use_policy", policy()),
+            ("upload_policy", policy()),
+            ("anchor", string()),
+            ("fingerprint_from", array_of(string())),
+            ("restore_to", string()),
+        ],
+        &["id", "use_policy", "upload_policy"],
+    )
+}
+
+fn clean_schema() -> Value {
+    object(
+        &[
+            ("default", string()),
+            (
+                "profiles",
+                map_of(object(
+                    &[
+                        ("description", string()),
+                        ("build", boolean()),
+                        ("out", boolean()),
+                        ("cache", boolean()),
+                        ("paths", strings()),
+                    ],
+                    &[],
+                )),
+            ),
+        ],
+        &[],
+    )
+}
+
+fn execution_schema() -> Value {
+    object(
+        &[
+            ("jobs", unsigned()),
+            (
+                "docker",
+                object(&[("enabled", boolean()), ("image", string())], &[]),
+            ),
+            (
+                "output_retention",
+                object(
+                    &[
+                        ("stdout_bytes", unsigned()),
+                        ("stderr_bytes", unsigned()),
+                        ("stdout_lines", unsigned()),
+                        ("stderr_lines", unsigned()),
+                        ("failure_tail_lines", unsigned()),
+                    ],
+                    &[],
+                ),
+            ),
+            ("clean_env", boolean()),
+            ("env_allowlist", strings()),
+            ("preserve_ansi", boolean()),
+            ("heartbeat_seconds", unsigned()),
+            ("min_free_space_mb", unsigned()),
+            ("stall_warning_seconds", unsigned()),
+            ("fold_repeats", boolean()),
+            ("env_export", string()),
+            (
+                "hooks",
+                map_of(object(
+                    &[
+                        ("pre", strings()),
+                        ("post", strings()),
+                        ("post_failure", enumeration(&["warn", "fail"])),
+                        ("timeout_seconds", unsigned()),
+                    ],
+                    &[],
+                )),
+            ),
+        ],
+        &[],
+    )
+}
+
+fn providers_schema() -> Value {
+    let retry = || {
+        vec![
+            ("retry_attempts", unsigned()),
+            ("retry_backoff_ms", unsigned()),
+            (
+                "retry_backoff_strategy",
+                enumeration(&["fixed", "exponential"]),
+            ),
+            ("timeout_seconds", unsigned()),
+            ("clean_env", boolean()),
+        ]
+    };
+    let with = |extra: (&'static str, Value)| {
+        let mut fields = retry();
+        fields.insert(0, extra);
+        object(&fields, &[])
+    };
+    let command = || with(("local_jobs", unsigned()));
+    object(
+        &[
+            ("rust", with(("allow_nested_build", boolean()))),
+            ("git", with(("allow_remote_resolution", boolean()))),
+            ("archive", command()),
+            ("download", command()),
+            ("go", command()),
+            ("java", command()),
+            ("node", command()),
+            ("python", command()),
+            ("buildroot", command()),
+            ("starting_point", command()),
+        ],
+        &[],
+    )
+}
+
+fn reporting_schema() -> Value {
+    object(
+        &[
+            ("summary", boolean()),
+            ("provenance", boolean()),
+            ("manifest", boolean()),
+            ("sbom", boolean()),
+            (
+                "masking",
+                object(
+                    &[
+                        ("enabled", boolean()),
+                        ("replacement", string()),
+                        ("patterns", strings()),
+                    ],
+                    &[],
+                ),
+            ),
+            (
+                "post_build",
+                object(
+                    &[("script", string()), ("timeout_seconds", unsigned())],
+                    &[],
+                ),
+            ),
+        ],
+        &[],
+    )
+}
+
+fn when_schema() -> Value {
+    object(
+        &[
+            ("target", string()),
+            ("profile", string()),
+            ("branch", string()),
+            ("image_kind", enumeration(&["buildroot", "starting-point"])),
+            ("all", array_of(when_ref())),
+            ("any", array_of(when_ref())),
+            ("not", when_ref()),
+        ],
+        &[],
+    )
+}
+
+fn when_ref() -> Value {
+    json!({ "$ref": "#/$defs/when" })
+}
+
+fn string() -> Value {
+    json!({ "type": "string" })
+}
+
+fn boolean() -> Value {
+    json!({ "type": "boolean" })
+}
+
+fn unsigned() -> Value {
+    json!({ "type": "integer", "minimum": 0 })
+}
+
+fn strings() -> Value {
+    array_of(string())
+}
+
+// Open-ended key/value pairs deserialize from `[["key", "value"], ...]`.
+fn pairs() -> Value {
+    array_of(json!({
+        "type": "array",
+        "prefixItems": [string(), string()],
+        "items": false,
+        "minItems": 2,
+    }))
+}
+
+fn enumeration(values: &[&str]) -> Value {
+    json!({ "type": "string", "enum": values })
+}
+
+fn array_of(items: Value) -> Value {
+    json!({ "type": "array", "items": items })
+}
+
+fn map_of(values: Value) -> Value {
+    json!({ "type": "object", "additionalProperties": values })
+}
+
+fn object(properties: &[(&str, Value)], required: &[&str]) -> Value {
+    let properties = properties
+        .iter()
+        .map(|(key, value)| ((*key).to_string(), value.clone()))
+        .collect::<Map<_, _>>();
+    json!({
+        "type": "object",
+        "additionalProperties": false,
+        "properties": properties,
+        "required": required,
+    })
+}
+
+type Variant<'a> = (&'a str, Vec<(&'a str, Value)>, &'a [&'a str]);
+
+// Internally tagged raw enums flatten into their parent table, so every variant repeats the
+// shared fields alongside its own and pins `kind` to the variant name.
+fn tagged(shared: &[(&str, Value)], shared_required: &[&str], variants: &[Variant<'_>]) -> Value {
+    let variants = variants
+        .iter()
+        .map(|(kind, fields, required)| {
+            let mut properties = shared.to_vec();
+            properties.push(("kind", json!({ "const": kind })));
+            properties.extend(fields.iter().cloned());
+            let mut required = required.to_vec();
+            required.extend(shared_required);
+            required.push("kind");
+            object(&properties, &required)
+        })
+        .collect::<Vec<_>>();
+    json!({ "type": "object", "oneOf": variants })
+}
@@ -0,0 +1,198 @@
+use std::path::PathBuf;
+
+use toml::{Table, Value};
+
+use crate::doc::split_path;
+use crate::load::load_build_config;
+use crate::raw::RawBuildConfig;
+use crate::{ConfigDoc, ConfigError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigTrace {
+    pub path: String,
+    pub root: PathBuf,
+    // Contributions are in merge order, so the last entry is the file whose value won.
+    pub contributions: Vec<ConfigContribution>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigContribution {
+    pub file: PathBuf,
+    pub via: Vec<ConfigLayerLink>,
+    // The table inside `file` that holds the value when it is not the key itself, such as
+    // `defaults.sources` or `profiles.ci`.
+    pub table: Option<String>,
+    // Rendered as inline TOML so traces stay comparable and printable.
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigLayerLink {
+    pub file: PathBuf,
+    pub kind: ConfigLayerKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigLayerKind {
+    Extends,
+    Imports,
+}
+
+impl ConfigLayerKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Extends => "extends",
+            Self::Imports => "imports",
+        }
+    }
+}
+
+impl ConfigTrace {
+    pub fn winner(&self) -> Option<&ConfigContribution> {
+        self.contributions.last()
+    }
+}
+
+// Reports which files in the extends/imports chain set `path`, visiting them in the same order
+// `merge_config` layers them. Array-of-table entries are addressed by id, as in `sources.<id>.repo`.
+// A `[defaults.<section>]` value counts for the file it fills an entry in, and the selected
+// profile's tables come after every file, as `apply_selected_profile` merges them last.
+pub fn trace_config_path(
+    build: &str,
+    path: &str,
+    profile: Option<&str>,
+) -> Result<ConfigTrace, ConfigError> {
+    let segments = split_path(path)?;
+    let raw = load_build_config(build)?;
+    let mut contributions = Vec::new();
+    let mut profile_contributions = Vec::new();
+    collect_contributions(
+        &raw,
+        &mut Vec::new(),
+        &TracedKey { segments, profile },
+        &mut contributions,
+        &mut profile_contributions,
+    )?;
+    contributions.extend(profile_contributions);
+    Ok(ConfigTrace {
+        path: path.to_string(),
+        root: raw.source_path.unwrap_or_default(),
+        contributions,
+    })
+}
+
+// Every file the build reads through extends/imports, in merge order with the build file last.
+pub fn config_file_set(build: &str) -> Result<Vec<PathBuf>, ConfigError> {
+    let raw = load_build_config(build)?;
+    let mut files = Vec::new();
+    collect_files(&raw, &mut files);
+    Ok(files)
+}
+
+fn collect_files(raw: &RawBuildConfig, files: &mut Vec<PathBuf>) {
+    for child in raw
+        .extends_config
+        .as_deref()
+        .into_iter()
+        .chain(&raw.imported_configs)
+    {
+        collect_files(child, files);
+    }
+    if let Some(file) = &raw.source_path
+        && !files.contains(file)
+    {
+        files.push(file.clone());
+    }
+}
+
+struct TracedKey<'a> {
+    segments: Vec<&'a str>,
+    profile: Option<&'a str>,
+}
+
+fn collect_contributions(
+    raw: &RawBuildConfig,
+    via: &mut Vec<ConfigLayerLink>,
+    key: &TracedKey<'_>,
+    contributions: &mut Vec<ConfigContribution>,
+    profile_contributions: &mut Vec<ConfigContribution>,
+) -> Result<(), ConfigError> {
+    let Some(file) = &raw.source_path else {
+        return Ok(());
+    };
+    let children = raw
+        .extends_config
+        .as_deref()
+        .map(|extended| (extended, ConfigLayerKind::Extends))
+        .into_iter()
+        .chain(
+            raw.imported_configs
+                .iter()
+                .map(|imported| (imported, ConfigLayerKind::Imports)),
+        );
+    for (child, kind) in children {
+        via.push(ConfigLayerLink {
+            file: file.clone(),
+            kind,
+        });
+        collect_contributions(child, via, key, contributions, profile_contributions)?;
+        via.pop();
+    }
+
+    let doc = ConfigDoc::load(file)?;
+    let contribution = |table: Option<String>, value: &Value| ConfigContribution {
+        file: file.clone(),
+        via: via.iter().rev().cloned().collect(),
+        table,
+        value: value.to_string(),
+    };
+    if let Some(value) = lookup(doc.table(), &key.segments) {
+        contributions.push(contribution(None, value));
+    } else if let Some(value) = section_default(doc.table(), &key.segments) {
+        contributions.push(contribution(
+            Some(format!("defaults.{}", key.segments[0])),
+            value,
+        ));
+    }
+    if let Some(profile) = key.profile
+        && let Some(value) = doc
+            .table()
+            .get("profiles")
+            .and_then(|profiles| profiles.get(profile))
+            .and_then(Value::as_table)
+            .and_then(|table| lookup(table, &key.segments))
+    {
+        profile_contributions.push(contribution(Some(format!("profiles.{profile}")), value));
+    }
+    Ok(())
+}
+
+// The `[defaults.<section>]` value for `<section>.<id>.<key>` when the same file declares the entry
+// but leaves the key unset.
+fn section_default<'a>(table: &'a Table, segments: &[&str]) -> Option<&'a Value> {
+    let [section, id, rest @ ..] = segments else {
+        return None;
+    };
+    if rest.is_empty() {
+        return None;
+    }
+    lookup(table, &[section, id])?;
+    lookup(table.get("defaults")?.get(*section)?.as_table()?, rest)
+}
+
+fn lookup<'a>(table: &'a Table, segments: &[&str]) -> Option<&'a Value> {
+    let (first, rest) = segments.split_first()?;
+    let mut value = table.get(*first)?;
+    let mut rest = rest;
+    while let Some((segment, remaining)) = rest.split_first() {
+        value = match value {
+            Value::Table(child) => child.get(*segment)?,
+            Value::Array(entries) => entries
+                .iter()
+                .find(|entry| entry.get("id").and_then(Value::as_str) == Some(*segment))?,
+            _ => return None,
+        };
+        rest = remaining;
+    }
+    Some(value)
+}
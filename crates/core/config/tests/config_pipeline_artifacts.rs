@@ -214,3 +214,251 @@ rootfs_path = "/tmp/rootfs"
 
     let _ = std::fs::remove_file(path);
 }
+
+#[test]
+fn section_defaults_fill_unset_entry_fields() {
+    let path = write_temp_config(
+        r#"
+build_name = "section-defaults"
+
+[workspace]
+root_dir = "."
+build_dir = "build"
+out_dir = "out"
+
+[image]
+kind = "starting-point"
+rootfs_path = "/tmp/rootfs"
+
+[defaults.artifacts]
+profile = "release"
+mutex_group = "toolchain"
+
+[defaults.install]
+replace = true
+
+[[artifacts]]
+id = "api"
+kind = "rust"
+package = "api"
+output_path = "out/api"
+
+[[artifacts]]
+id = "node"
+kind = "java"
+build_target = "node.jar"
+profile = "debug"
+output_path = "out/node.jar"
+
+[[install]]
+id = "api"
+artifact = "api"
+dest = "/usr/bin/api"
+"#,
+    );
+
+    let spec = resolve_config(path.to_str().expect("temp path utf-8"));
+
+    assert_eq!(spec.artifacts[0].build_mode, Some(BuildModeSpec::Release));
+    assert_eq!(spec.artifacts[1].build_mode, Some(BuildModeSpec::Debug));
+    assert!(
+        spec.artifacts
+            .iter()
+            .all(|artifact| artifact.mutex_group.as_deref() == Some("toolchain"))
+    );
+    assert!(spec.install.entries[0].replace);
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn section_defaults_reject_kind_specific_fields() {
+    let path = write_temp_config(
+        r#"
+build_name = "section-defaults-kind-specific"
+
+[workspace]
+root_dir = "."
+build_dir = "build"
+out_dir = "out"
+
+[image]
+kind = "starting-point"
+rootfs_path = "/tmp/rootfs"
+
+[defaults.artifacts]
+package = "api"
+"#,
+    );
+
+    let error = gaia_config::try_resolve_config(path.to_str().expect("temp path utf-8"))
+        .expect_err("kind-specific defaults should fail");
+
+    assert!(
+        error
+            .to_string()
+            .contains("defaults.artifacts.package is not a field shared by every artifacts entry")
+    );
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn artifact_instances_expand_into_one_artifact_per_instance() {
+    let path = write_temp_config(
+        r#"
+build_name = "artifact-instances"
+
+[interpolation]
+values = [["arches", "aarch64, x86_64"]]
+
+[workspace]
+root_dir = "."
+build_dir = "build"
+out_dir = "out"
+
+[image]
+kind = "starting-point"
+rootfs_path = "/tmp/rootfs"
+
+[[artifacts]]
+id = "runtime"
+kind = "rust"
+package = "runtime"
+instances = ["aarch64", "x86_64"]
+target = "${instance}-unknown-linux-gnu"
+output_path = "out/${instance}/runtime"
+
+[[artifacts]]
+id = "app"
+kind = "rust"
+package = "app"
+instances = "${interpolation.values.arches}"
+target = "${instance}-unknown-linux-gnu"
+dependencies = ["runtime"]
+output_path = "out/${instance}/app"
+
+[[artifacts]]
+id = "bundle"
+kind = "rust"
+package = "bundle"
+dependencies = ["app"]
+output_path = "out/bundle"
+"#,
+    );
+
+    let spec = resolve_config(path.to_str().expect("temp path utf-8"));
+
+    let artifact = |id: &str| {
+        spec.artifacts
+            .iter()
+            .find(|artifact| artifact.id.as_str() == id)
+            .unwrap_or_else(|| panic!("artifact {id}"))
+    };
+    let dependencies = |id: &str| {
+        artifact(id)
+            .dependencies
+            .iter()
+            .map(|dependency| dependency.id.as_str().to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(spec.artifacts.len(), 5);
+    assert_eq!(
+        artifact("runtime:x86_64").target.as_deref(),
+        Some("x86_64-unknown-linux-gnu")
+    );
+    assert!(
+        artifact("runtime:aarch64")
+            .output
+            .path
+            .ends_with("out/aarch64/runtime")
+    );
+    assert_eq!(
+        artifact("app:x86_64").target.as_deref(),
+        Some("x86_64-unknown-linux-gnu")
+    );
+    assert_eq!(dependencies("app:aarch64"), vec!["runtime:aarch64"]);
+    assert_eq!(dependencies("app:x86_64"), vec!["runtime:x86_64"]);
+    assert_eq!(dependencies("bundle"), vec!["app:aarch64", "app:x86_64"]);
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn artifact_instances_reject_names_that_break_ids() {
+    let path = write_temp_config(
+        r#"
+build_name = "artifact-instances-invalid"
+
+[workspace]
+root_dir = "."
+build_dir = "build"
+out_dir = "out"
+
+[image]
+kind = "starting-point"
+rootfs_path = "/tmp/rootfs"
+
+[[artifacts]]
+id = "runtime"
+kind = "rust"
+package = "runtime"
+instances = ["arm:v7"]
+output_path = "out/runtime"
+"#,
+    );
+
+    let error = gaia_config::try_resolve_config(path.to_str().expect("temp path utf-8"))
+        .expect_err("instance names with ':' should fail");
+
+    assert!(matches!(
+        error,
+        gaia_config::ConfigError::InvalidArtifactInstance { ref instance, .. } if instance == "arm:v7"
+    ));
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn artifact_instances_reject_dependencies_without_a_matching_instance() {
+    let path = write_temp_config(
+        r#"
+build_name = "artifact-instances-unmatched"
+
+[workspace]
+root_dir = "."
+build_dir = "build"
+out_dir = "out"
+
+[image]
+kind = "starting-point"
+rootfs_path = "/tmp/rootfs"
+
+[[artifacts]]
+id = "runtime"
+kind = "rust"
+package = "runtime"
+instances = ["aarch64", "x86_64"]
+output_path = "out/${instance}/runtime"
+
+[[artifacts]]
+id = "app"
+kind = "rust"
+package = "app"
+instances = ["aarch64", "riscv64"]
+dependencies = ["runtime"]
+output_path = "out/${instance}/app"
+"#,
+    );
+
+    let error = gaia_config::try_resolve_config(path.to_str().expect("temp path utf-8"))
+        .expect_err("app:riscv64 has no runtime instance to depend on");
+
+    assert!(matches!(
+        error,
+        gaia_config::ConfigError::UnmatchedArtifactInstance { ref artifact, ref dependency }
+            if artifact == "app:riscv64" && dependency == "runtime"
+    ));
+
+    let _ = std::fs::remove_file(path);
+}
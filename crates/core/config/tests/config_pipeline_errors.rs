@@ -130,3 +130,48 @@ fn rejects_invalid_numeric_override_with_typed_error() {
     );
     assert!(error.to_string().contains("slow"));
 }
+
+#[test]
+fn type_mismatches_name_the_config_path_and_both_types() {
+    let nonce = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system time before unix epoch")
+        .as_nanos();
+    let path = std::env::temp_dir().join(format!("gaia-type-mismatch-{nonce}.toml"));
+    std::fs::write(
+        &path,
+        r#"
+build_name = "type-mismatch"
+
+[workspace]
+root_dir = "."
+build_dir = "build"
+out_dir = "out"
+
+[execution]
+jobs = 4.0
+clean_env = "true"
+
+[image]
+kind = "starting-point"
+rootfs_path = "/tmp/rootfs"
+"#,
+    )
+    .expect("temp config should be written");
+
+    let error = gaia_config::try_resolve_config(path.to_str().expect("temp path should be utf-8"))
+        .expect_err("mistyped values should fail");
+
+    assert!(matches!(
+        error,
+        gaia_config::ConfigError::ConfigShape { .. }
+    ));
+    let message = error.to_string();
+    assert!(message.contains(
+        "`execution.jobs` expects integer, found float 4.0 (write `4` without the fraction)"
+    ));
+    assert!(message.contains(
+        "`execution.clean_env` expects boolean, found string \"true\" (write `true` without quotes)"
+    ));
+    let _ = std::fs::remove_file(path);
+}
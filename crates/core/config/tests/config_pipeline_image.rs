@@ -243,3 +243,40 @@ config_overrides = [["BR2_TARGET_GENERIC_HOSTNAME", "\"gaia-smoke\""]]
 
     let _ = std::fs::remove_dir_all(root);
 }
+
+#[test]
+fn resolves_buildroot_config_overrides_from_inline_table() {
+    let path = write_temp_config(
+        r#"
+build_name = "buildroot-symbol-table"
+
+[workspace]
+root_dir = "."
+build_dir = "build"
+out_dir = "out"
+
+[image]
+kind = "buildroot"
+defconfig = "qemu_x86_64_defconfig"
+config_overrides = { BR2_TARGET_GENERIC_HOSTNAME = "\"gaia\"", BR2_CCACHE = "y" }
+"#,
+    );
+
+    let spec = resolve_config(path.to_str().expect("temp path utf-8"));
+
+    match &spec.image.definition {
+        ImageDefinition::Buildroot(buildroot) => assert_eq!(
+            buildroot.config_overrides,
+            vec![
+                ("BR2_CCACHE".to_string(), "y".to_string()),
+                (
+                    "BR2_TARGET_GENERIC_HOSTNAME".to_string(),
+                    "\"gaia\"".to_string()
+                ),
+            ]
+        ),
+        other => panic!("expected buildroot image, got {other:?}"),
+    }
+
+    let _ = std::fs::remove_file(path);
+}
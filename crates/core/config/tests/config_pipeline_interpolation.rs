@@ -107,3 +107,158 @@ rootfs_path = "/tmp/rootfs"
     let hook = spec.reporting.post_build.expect("post-build hook");
     assert_eq!(hook.script, "scripts/cm5/post-build.sh");
 }
+
+fn computed_inputs_config(extra_inputs: &str) -> String {
+    format!(
+        r#"
+build_name = "computed-inputs"
+target = "${{input.image_name}}"
+
+[workspace]
+root_dir = "."
+build_dir = "build"
+out_dir = "out"
+
+[inputs.variant]
+kind = "string"
+default = "cm5"
+
+[inputs.version]
+kind = "string"
+default = "1.4"
+{extra_inputs}
+[image]
+kind = "starting-point"
+rootfs_path = "/tmp/rootfs"
+"#
+    )
+}
+
+#[test]
+fn input_defaults_resolve_references_to_other_inputs_in_dependency_order() {
+    let path = write_temp_config(&computed_inputs_config(
+        r#"
+[inputs.image_name]
+kind = "string"
+default = "${inputs.resolved.label}-${input.version}"
+
+[inputs.label]
+kind = "string"
+default = "gaia-${inputs.variant}"
+"#,
+    ));
+    let options = gaia_config::ResolveOptions {
+        explicit_overrides: vec![("input.variant".into(), "rpi4".into())],
+        ..gaia_config::ResolveOptions::default()
+    };
+
+    let spec = gaia_config::try_resolve_config_with_options(&path.display().to_string(), &options)
+        .expect("computed inputs should resolve");
+    let selected = |name: &str| {
+        spec.inputs
+            .selected
+            .iter()
+            .find(|(input, _)| input == name)
+            .map(|(_, value)| value.clone())
+    };
+    assert_eq!(selected("image_name").as_deref(), Some("gaia-rpi4-1.4"));
+    assert_eq!(selected("label").as_deref(), Some("gaia-rpi4"));
+    assert_eq!(spec.metadata.target.as_deref(), Some("gaia-rpi4-1.4"));
+}
+
+#[test]
+fn input_reference_cycles_and_missing_references_are_rejected() {
+    let cycle = write_temp_config(&computed_inputs_config(
+        r#"
+[inputs.image_name]
+kind = "string"
+default = "${input.alias}"
+
+[inputs.alias]
+kind = "string"
+default = "${input.image_name}"
+"#,
+    ));
+    let error = gaia_config::try_resolve_config(&cycle.display().to_string())
+        .expect_err("cyclic input references should fail");
+    assert!(matches!(
+        error,
+        gaia_config::ConfigError::InputReferenceCycle { .. }
+    ));
+    assert_eq!(
+        error.to_string(),
+        "input reference cycle detected: alias -> image_name -> alias"
+    );
+
+    let missing = write_temp_config(&computed_inputs_config(
+        r#"
+[inputs.image_name]
+kind = "string"
+default = "gaia-${input.board}"
+"#,
+    ));
+    let error = gaia_config::try_resolve_config(&missing.display().to_string())
+        .expect_err("references to undeclared inputs should fail");
+    assert_eq!(
+        error.to_string(),
+        "input 'image_name' references undeclared input 'board'"
+    );
+
+    let unset = write_temp_config(&computed_inputs_config(
+        r#"
+[inputs.image_name]
+kind = "string"
+default = "gaia-${input.board}"
+
+[inputs.board]
+kind = "string"
+required = true
+"#,
+    ));
+    let error = gaia_config::try_resolve_config(&unset.display().to_string())
+        .expect_err("references to inputs without a value should fail");
+    assert!(matches!(
+        error,
+        gaia_config::ConfigError::MissingInputReference { declared: true, .. }
+    ));
+}
+
+#[test]
+fn datetime_inputs_resolve_to_rfc3339_before_references_see_them() {
+    let path = write_temp_config(&computed_inputs_config(
+        r#"
+[inputs.image_name]
+kind = "string"
+default = "gaia-${input.released}"
+
+[inputs.released]
+kind = "datetime"
+default = "2026-03-01T09:30:00+01:00"
+
+[inputs.expiry]
+kind = "datetime"
+default = "now+7d"
+
+[inputs.typo]
+kind = "datetime"
+default = "now+7x"
+"#,
+    ));
+
+    let spec = resolve_config(&path.display().to_string());
+    let selected = |name: &str| {
+        spec.inputs
+            .selected
+            .iter()
+            .find(|(input, _)| input == name)
+            .map(|(_, value)| value.clone())
+            .expect("selected input")
+    };
+    assert_eq!(selected("released"), "2026-03-01T08:30:00Z");
+    assert_eq!(selected("image_name"), "gaia-2026-03-01T08:30:00Z");
+    let expiry = selected("expiry");
+    assert_eq!(expiry.len(), "2026-03-01T08:30:00Z".len());
+    assert!(expiry.ends_with('Z'));
+    // Unparseable values are kept for validation to report.
+    assert_eq!(selected("typo"), "now+7x");
+}
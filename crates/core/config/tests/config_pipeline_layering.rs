@@ -8,13 +8,14 @@ use gaia_spec::{
     DEFAULT_COMMAND_RETRY_BACKOFF_MS, DEFAULT_DOWNLOAD_PROVIDER_TIMEOUT_SECONDS,
     DEFAULT_GIT_PROVIDER_TIMEOUT_SECONDS, DEFAULT_GO_PROVIDER_TIMEOUT_SECONDS,
     DEFAULT_JAVA_PROVIDER_TIMEOUT_SECONDS, DEFAULT_NODE_PROVIDER_TIMEOUT_SECONDS,
-    DEFAULT_PYTHON_PROVIDER_TIMEOUT_SECONDS, DEFAULT_RUST_PROVIDER_TIMEOUT_SECONDS,
-    DEFAULT_STARTING_POINT_PROVIDER_TIMEOUT_SECONDS, ImageDefinition, InputKindSpec,
-    RetryBackoffStrategySpec, RollbackDomain, SourceDefinition, SourcePinPolicySpec,
-    SourceRefreshPolicySpec, StageContentOriginSpec, WorkspacePathKindSpec,
+    DEFAULT_OPERATION_HOOK_TIMEOUT_SECONDS, DEFAULT_PYTHON_PROVIDER_TIMEOUT_SECONDS,
+    DEFAULT_RUST_PROVIDER_TIMEOUT_SECONDS, DEFAULT_STARTING_POINT_PROVIDER_TIMEOUT_SECONDS,
+    HookFailureMode, ImageDefinition, ImageProviderKind, InputKindSpec, RetryBackoffStrategySpec,
+    RollbackDomain, SourceDefinition, SourcePinPolicySpec, SourceRefreshPolicySpec,
+    StageContentOriginSpec, WorkspacePathKindSpec,
 };
 use std::path::PathBuf;
-use support::{default_config_path, write_temp_config};
+use support::{default_config_path, write_temp_config, write_temp_config_at};
 
 #[test]
 fn resolves_layered_default_config() {
@@ -446,6 +447,7 @@ fn resolves_config_with_cli_selection_overrides() {
         &default_config_path(),
         &gaia_config::ResolveOptions {
             preset: Some("ci".into()),
+            profile: None,
             env_files: Vec::new(),
             env_overrides: vec![("GAIA_MODE".into(), "ci-env".into())],
             explicit_overrides: vec![
@@ -636,6 +638,58 @@ fn resolves_global_docker_execution_backend() {
     assert_eq!(docker.image, "ghcr.io/example/gaia-cross:latest");
 }
 
+#[test]
+fn clean_env_is_opt_in_and_overridable() {
+    let spec = resolve_config(&default_config_path());
+    assert!(!spec.policy.execution.clean_env);
+
+    let spec = gaia_config::resolve_config_with_options(
+        &default_config_path(),
+        &gaia_config::ResolveOptions {
+            explicit_overrides: vec![("execution.clean_env".into(), "true".into())],
+            ..gaia_config::ResolveOptions::default()
+        },
+    );
+
+    assert!(spec.policy.execution.clean_env);
+    assert!(
+        spec.policy.execution.clean_env_for(
+            spec.policy
+                .providers
+                .image_command_policy(ImageProviderKind::Buildroot)
+        )
+    );
+}
+
+#[test]
+fn resolves_with_set_style_overrides_for_embedders() {
+    let spec = gaia_config::try_resolve_config_with_overrides(
+        &default_config_path(),
+        &["execution.clean_env=true", "build.version=9.9.9"],
+    )
+    .expect("resolve with overrides");
+
+    assert!(spec.policy.execution.clean_env);
+    assert_eq!(spec.identity.version.as_deref(), Some("9.9.9"));
+    assert_eq!(
+        spec.selection.explicit_overrides,
+        vec![
+            ("execution.clean_env".to_string(), "true".to_string()),
+            ("build.version".to_string(), "9.9.9".to_string()),
+        ]
+    );
+
+    let error = gaia_config::try_resolve_config_with_overrides(
+        &default_config_path(),
+        &["execution.clean_env"],
+    )
+    .expect_err("override without a value");
+    assert_eq!(
+        error.to_string(),
+        "invalid override value for 'execution.clean_env': '' (expected key=value)"
+    );
+}
+
 #[test]
 fn resolves_execution_output_retention_overrides() {
     let spec = gaia_config::resolve_config_with_options(
@@ -692,6 +746,37 @@ fn resolves_buildroot_local_jobs_without_changing_scheduler_jobs() {
     assert_eq!(spec.policy.providers.buildroot.local_jobs, 2);
 }
 
+#[test]
+fn zero_jobs_override_keeps_the_config_jobs() {
+    let dir = std::env::temp_dir().join(format!(
+        "gaia-config-jobs-{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("system time before unix epoch")
+            .as_nanos()
+    ));
+    let build = dir.join("build.toml");
+    write_temp_config_at(
+        &build,
+        "build_name = \"jobs\"\n\n[image]\nkind = \"buildroot\"\ndefconfig = \"board_defconfig\"\n\n[execution]\njobs = 4\n",
+    );
+    let jobs_with = |value: &str| {
+        gaia_config::resolve_config_with_options(
+            &build.display().to_string(),
+            &gaia_config::ResolveOptions {
+                explicit_overrides: vec![("execution.jobs".into(), value.into())],
+                ..gaia_config::ResolveOptions::default()
+            },
+        )
+        .policy
+        .execution
+        .jobs
+    };
+
+    assert_eq!(jobs_with("0"), 4);
+    assert_eq!(jobs_with("2"), 2);
+}
+
 #[test]
 fn resolves_buildroot_local_jobs_separately_from_scheduler_jobs() {
     let spec = gaia_config::resolve_config_with_options(
@@ -752,3 +837,301 @@ paths = [".cache/gaia", "@generated"]
         vec![".cache/gaia".to_string(), "@generated".to_string()]
     );
 }
+
+#[test]
+fn traces_config_path_through_extends_and_imports() {
+    let dir = std::env::temp_dir().join(format!(
+        "gaia-config-trace-{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("system time before unix epoch")
+            .as_nanos()
+    ));
+    write_temp_config_at(
+        &dir.join("base.toml"),
+        "[image]\nkind = \"buildroot\"\ndefconfig = \"base_defconfig\"\n\n[[sources]]\nid = \"app\"\nkind = \"path\"\npath = \"base\"\n",
+    );
+    write_temp_config_at(
+        &dir.join("configs/buildroot.toml"),
+        "[image]\nkind = \"buildroot\"\ndefconfig = \"board_defconfig\"\n",
+    );
+    let build = dir.join("build.toml");
+    write_temp_config_at(
+        &build,
+        "build_name = \"trace\"\nextends = \"base.toml\"\nimports = [\"configs/buildroot.toml\"]\n",
+    );
+
+    let trace =
+        gaia_config::trace_config_path(&build.display().to_string(), "image.defconfig", None)
+            .expect("trace should load");
+    let files = trace
+        .contributions
+        .iter()
+        .map(|contribution| (contribution.file.clone(), contribution.value.clone()))
+        .collect::<Vec<_>>();
+    let canonical = |path: &str| std::fs::canonicalize(dir.join(path)).expect("canonical path");
+    assert_eq!(
+        files,
+        vec![
+            (canonical("base.toml"), "\"base_defconfig\"".to_string()),
+            (
+                canonical("configs/buildroot.toml"),
+                "\"board_defconfig\"".to_string()
+            ),
+        ]
+    );
+    assert_eq!(
+        gaia_config::config_file_set(&build.display().to_string()).expect("file set"),
+        vec![
+            canonical("base.toml"),
+            canonical("configs/buildroot.toml"),
+            canonical("build.toml"),
+        ]
+    );
+    let winner = trace.winner().expect("winning layer");
+    assert_eq!(winner.via.len(), 1);
+    assert_eq!(winner.via[0].file, canonical("build.toml"));
+    assert_eq!(winner.via[0].kind, gaia_config::ConfigLayerKind::Imports);
+
+    let source =
+        gaia_config::trace_config_path(&build.display().to_string(), "sources.app.path", None)
+            .expect("trace should load");
+    assert_eq!(
+        source.winner().map(|winner| winner.value.as_str()),
+        Some("\"base\"")
+    );
+}
+
+#[test]
+fn trace_reports_section_defaults_and_the_selected_profile() {
+    let dir = std::env::temp_dir().join(format!(
+        "gaia-config-trace-layers-{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("system time before unix epoch")
+            .as_nanos()
+    ));
+    let build = dir.join("build.toml");
+    write_temp_config_at(
+        &build,
+        "build_name = \"trace-layers\"\n\n[defaults.sources]\nrefresh = \"always\"\n\n[[sources]]\nid = \"app\"\nkind = \"path\"\npath = \"app\"\n\n[execution]\njobs = 2\n\n[profiles.ci.execution]\njobs = 8\n",
+    );
+    let build = build.display().to_string();
+
+    let defaulted = gaia_config::trace_config_path(&build, "sources.app.refresh", None)
+        .expect("trace should load");
+    let winner = defaulted.winner().expect("defaults layer");
+    assert_eq!(winner.table.as_deref(), Some("defaults.sources"));
+    assert_eq!(winner.value, "\"always\"");
+
+    let unselected =
+        gaia_config::trace_config_path(&build, "execution.jobs", None).expect("trace should load");
+    assert_eq!(unselected.contributions.len(), 1);
+    let profiled = gaia_config::trace_config_path(&build, "execution.jobs", Some("ci"))
+        .expect("trace should load");
+    let layers = profiled
+        .contributions
+        .iter()
+        .map(|contribution| (contribution.table.as_deref(), contribution.value.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(layers, vec![(None, "2"), (Some("profiles.ci"), "8")]);
+}
+
+#[test]
+fn resolves_yaml_build_layered_over_toml_files() {
+    let dir = std::env::temp_dir().join(format!(
+        "gaia-config-yaml-{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("system time before unix epoch")
+            .as_nanos()
+    ));
+    write_temp_config_at(
+        &dir.join("base.toml"),
+        "[image]\nkind = \"buildroot\"\ndefconfig = \"base_defconfig\"\n\n[[sources]]\nid = \"app\"\nkind = \"path\"\npath = \"base\"\n",
+    );
+    write_temp_config_at(
+        &dir.join("configs/jobs.yml"),
+        "execution:\n  jobs: 6\n  clean_env: true\n",
+    );
+    let build = dir.join("build.yaml");
+    write_temp_config_at(
+        &build,
+        "build_name: yaml-build\nextends: base.toml\nimports:\n  - configs/jobs.yml\nimage:\n  kind: buildroot\n  defconfig: board_defconfig\n",
+    );
+
+    let spec = resolve_config(&build.display().to_string());
+
+    assert_eq!(spec.identity.build_name, "yaml-build");
+    assert_eq!(spec.policy.execution.jobs, 6);
+    assert!(spec.policy.execution.clean_env);
+    assert!(matches!(
+        &spec.image.definition,
+        ImageDefinition::Buildroot(image) if image.defconfig.as_deref() == Some("board_defconfig")
+    ));
+    assert!(
+        spec.sources
+            .iter()
+            .any(|source| source.id.as_str() == "app")
+    );
+    let trace =
+        gaia_config::trace_config_path(&build.display().to_string(), "execution.jobs", None)
+            .expect("trace should load yaml layers");
+    assert_eq!(
+        trace.winner().map(|winner| winner.value.as_str()),
+        Some("6")
+    );
+
+    write_temp_config_at(&dir.join("broken.yaml"), "build_name: [unclosed\n");
+    let error = gaia_config::try_resolve_config(&dir.join("broken.yaml").display().to_string())
+        .expect_err("malformed yaml should fail");
+    assert!(matches!(
+        error,
+        gaia_config::ConfigError::ConfigParse { .. }
+    ));
+}
+
+#[test]
+fn strict_merge_rejects_layers_that_change_value_types() {
+    let dir = std::env::temp_dir().join(format!(
+        "gaia-config-strict-merge-{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("system time before unix epoch")
+            .as_nanos()
+    ));
+    write_temp_config_at(
+        &dir.join("base.toml"),
+        "[image]\nkind = \"buildroot\"\ndefconfig = \"base_defconfig\"\n\n[inputs.jobs]\nkind = \"float\"\nmin = 1\n\n[[sources]]\nid = \"app\"\nkind = \"path\"\npath = \"base\"\n\n[[sources]]\nid = \"docs\"\nkind = \"path\"\npath = \"docs\"\n",
+    );
+    let overlay = "[inputs.jobs]\nmin = 0.5\n\n[[sources]]\nid = \"app\"\nkind = \"git\"\nrepo = \"https://example.com/app.git\"\n\n[[sources]]\nid = \"docs\"\nkind = \"path\"\npath = \"docs-v2\"\n";
+    let permissive = dir.join("permissive.toml");
+    write_temp_config_at(
+        &permissive,
+        &format!("build_name = \"permissive\"\nextends = \"base.toml\"\n\n{overlay}"),
+    );
+    let strict = dir.join("strict.toml");
+    write_temp_config_at(
+        &strict,
+        &format!(
+            "build_name = \"strict\"\nextends = \"base.toml\"\nstrict_merge = true\n\n{overlay}"
+        ),
+    );
+
+    let spec = resolve_config(&permissive.display().to_string());
+    assert!(spec.sources.iter().any(|source| source.id.as_str() == "app"
+        && matches!(source.definition, SourceDefinition::Git { .. })));
+
+    let error = gaia_config::try_resolve_config(&strict.display().to_string())
+        .expect_err("strict merge should reject type changes");
+    let gaia_config::ConfigError::MergeTypeConflict { conflicts } = &error else {
+        panic!("expected a merge type conflict, got {error}");
+    };
+    let strict_file = std::fs::canonicalize(&strict).expect("canonical path");
+    assert_eq!(
+        conflicts,
+        &vec![
+            format!(
+                "`inputs.jobs.min` is an integer in a lower layer but a float in '{}'",
+                strict_file.display()
+            ),
+            format!(
+                "`sources.app.kind` changes from \"path\" to \"git\" in '{}'",
+                strict_file.display()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn operation_hooks_are_replaced_per_operation_across_layers() {
+    let dir = std::env::temp_dir().join(format!(
+        "gaia-config-operation-hooks-{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("system time before unix epoch")
+            .as_nanos()
+    ));
+    write_temp_config_at(
+        &dir.join("base.toml"),
+        "[execution.hooks.\"resolve-build\"]\npre = [\"notify start\"]\npost = [\"notify end\"]\n\n[execution.hooks.\"image:build\"]\npost = [\"notify image\"]\n",
+    );
+    let build = dir.join("build.toml");
+    write_temp_config_at(
+        &build,
+        "build_name = \"hooks\"\nextends = \"base.toml\"\n\n[execution.hooks.\"image:build\"]\npost = [\"notify board\"]\npost_failure = \"fail\"\ntimeout_seconds = 10\n",
+    );
+
+    let hooks = resolve_config(&build.display().to_string())
+        .policy
+        .execution
+        .hooks;
+    assert_eq!(hooks["resolve-build"].pre, vec!["notify start"]);
+    assert_eq!(
+        hooks["resolve-build"].timeout_seconds,
+        DEFAULT_OPERATION_HOOK_TIMEOUT_SECONDS
+    );
+    assert_eq!(hooks["resolve-build"].post_failure, HookFailureMode::Warn);
+    assert_eq!(hooks["image:build"].post, vec!["notify board"]);
+    assert_eq!(hooks["image:build"].post_failure, HookFailureMode::Fail);
+    assert_eq!(hooks["image:build"].timeout_seconds, 10);
+}
+
+#[test]
+fn selected_profile_merges_over_imports_below_cli_overrides() {
+    let dir = std::env::temp_dir().join(format!(
+        "gaia-config-profiles-{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("system time before unix epoch")
+            .as_nanos()
+    ));
+    write_temp_config_at(
+        &dir.join("base.toml"),
+        "[[sources]]\nid = \"app\"\nkind = \"path\"\npath = \"app\"\n\n[profiles.ci]\nversion = \"ci\"\n",
+    );
+    let build = dir.join("build.toml");
+    write_temp_config_at(
+        &build,
+        "build_name = \"profiles\"\nimports = [\"base.toml\"]\n\n[execution]\njobs = 2\n\n[profiles.ci.execution]\njobs = 8\n\n[[profiles.ci.sources]]\nid = \"app\"\nkind = \"path\"\npath = \"app-ci\"\n\n[profiles.release]\nversion = \"1.0.0\"\n",
+    );
+    let resolve = |profile: &str, explicit_overrides: Vec<(String, String)>| {
+        gaia_config::try_resolve_config_with_options(
+            &build.display().to_string(),
+            &gaia_config::ResolveOptions {
+                profile: Some(profile.into()),
+                explicit_overrides,
+                ..gaia_config::ResolveOptions::default()
+            },
+        )
+    };
+
+    let base = resolve_config(&build.display().to_string());
+    assert_eq!(base.policy.execution.jobs, 2);
+    assert_eq!(base.identity.version, None);
+
+    let ci = resolve("ci", Vec::new()).expect("ci profile");
+    assert_eq!(ci.identity.version.as_deref(), Some("ci"));
+    assert_eq!(ci.policy.execution.jobs, 8);
+    assert!(matches!(
+        &ci.sources[0].definition,
+        SourceDefinition::Path(path) if path.path.ends_with("app-ci")
+    ));
+
+    let overridden =
+        resolve("ci", vec![("execution.jobs".into(), "3".into())]).expect("ci profile");
+    assert_eq!(overridden.policy.execution.jobs, 3);
+
+    let error = resolve("nightly", Vec::new()).expect_err("undeclared profile");
+    assert_eq!(
+        error,
+        gaia_config::ConfigError::MissingProfile {
+            profile: "nightly".into(),
+            available: vec!["ci".into(), "release".into()],
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "selected profile 'nightly' was not defined; available profiles: ci, release"
+    );
+}
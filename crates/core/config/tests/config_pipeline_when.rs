@@ -100,6 +100,13 @@ when = { branch = "main" }
             .collect::<Vec<_>>(),
         vec!["kept-artifact"]
     );
+    assert_eq!(
+        spec.selection.when_excluded_artifacts,
+        vec![(
+            "dropped-artifact".to_string(),
+            gaia_spec::ArtifactProviderKind::Rust
+        )]
+    );
     assert_eq!(
         spec.install
             .entries
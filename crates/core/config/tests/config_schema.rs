@@ -0,0 +1,131 @@
+use gaia_config::{config_schema, provider_config_keys};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[test]
+fn config_schema_describes_every_key_used_by_example_configs() {
+    let schema = config_schema();
+    let examples = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../../examples");
+    let mut configs = Vec::new();
+    collect_build_configs(&examples, &mut configs);
+    assert!(!configs.is_empty(), "expected example build configs");
+
+    for config in configs {
+        let contents = fs::read_to_string(&config).expect("example config should be readable");
+        let value: toml::Value = toml::from_str(&contents).expect("example config should parse");
+        let mut unknown = Vec::new();
+        check_value(&schema, &schema, &value, "", &mut unknown);
+        assert!(
+            unknown.is_empty(),
+            "{} uses keys missing from the schema: {unknown:?}",
+            config.display()
+        );
+    }
+}
+
+#[test]
+fn config_schema_pins_tagged_variants_by_kind() {
+    let schema = config_schema();
+    let sources = &schema["properties"]["sources"]["items"]["oneOf"];
+    let kinds = sources
+        .as_array()
+        .expect("source variants")
+        .iter()
+        .map(|variant| {
+            variant["properties"]["kind"]["const"]
+                .as_str()
+                .unwrap_or("")
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(kinds, ["git", "path", "archive", "download"]);
+    assert_eq!(
+        schema["properties"]["checkpoints"]["items"]["required"],
+        serde_json::json!(["id", "use_policy", "upload_policy"])
+    );
+}
+
+#[test]
+fn provider_config_keys_list_the_keys_of_one_kind() {
+    assert_eq!(
+        provider_config_keys("sources", "path"),
+        vec![
+            "sources[].kind",
+            "sources[].identity_ignore",
+            "sources[].path"
+        ]
+    );
+    assert_eq!(
+        provider_config_keys("artifacts", "go"),
+        vec!["artifacts[].kind", "artifacts[].package"]
+    );
+    assert!(provider_config_keys("image", "starting-point").contains(&"image.rootfs_path".into()));
+    assert!(provider_config_keys("artifacts", "cobol").is_empty());
+}
+
+fn collect_build_configs(dir: &Path, configs: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).expect("examples dir should be readable") {
+        let path = entry.expect("examples entry").path();
+        if path.is_dir() {
+            collect_build_configs(&path, configs);
+        } else if path
+            .extension()
+            .is_some_and(|extension| extension == "toml")
+            && !matches!(
+                path.file_name().and_then(|name| name.to_str()),
+                Some("Cargo.toml" | "pyproject.toml" | "config.toml")
+            )
+        {
+            configs.push(path);
+        }
+    }
+}
+
+fn check_value(
+    root: &Value,
+    schema: &Value,
+    value: &toml::Value,
+    location: &str,
+    unknown: &mut Vec<String>,
+) {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let name = reference.trim_start_matches("#/$defs/");
+        return check_value(root, &root["$defs"][name], value, location, unknown);
+    }
+    match value {
+        toml::Value::Table(table) => {
+            let variants = schema
+                .get("oneOf")
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_else(|| vec![schema.clone()]);
+            let kind = table.get("kind").and_then(toml::Value::as_str);
+            let variant = variants
+                .iter()
+                .find(|variant| variant["properties"]["kind"]["const"].as_str() == kind)
+                .unwrap_or(&variants[0]);
+            for (key, entry) in table {
+                let path = format!("{location}{key}");
+                let entry_schema = variant["properties"].get(key).or_else(|| {
+                    variant
+                        .get("additionalProperties")
+                        .filter(|v| v.is_object())
+                });
+                match entry_schema {
+                    Some(entry_schema) => {
+                        check_value(root, entry_schema, entry, &format!("{path}."), unknown)
+                    }
+                    None => unknown.push(path),
+                }
+            }
+        }
+        toml::Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items").filter(|items| items.is_object()) {
+                for item in items {
+                    check_value(root, item_schema, item, location, unknown);
+                }
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,115 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use gaia_spec::ResolvedBuildSpec;
+
+use crate::ExecutionOutcome;
+use crate::operations::run_output_env_name;
+
+pub fn env_export_path(spec: &ResolvedBuildSpec) -> Option<PathBuf> {
+    Some(workspace_path(
+        spec,
+        spec.policy.execution.env_export.as_deref()?,
+    ))
+}
+
+fn workspace_path(spec: &ResolvedBuildSpec, path: &str) -> PathBuf {
+    let path = Path::new(path);
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        Path::new(&spec.workspace.root_dir).join(path)
+    }
+}
+
+// Writes `[execution] env_export` for a run that succeeded, so deployment scripts can `source`
+// what it produced. A failed or cancelled run removes the previous file instead, so nothing
+// downstream picks up values from an older build. Returns the path that was written.
+pub fn write_env_export(
+    spec: &ResolvedBuildSpec,
+    outcome: &ExecutionOutcome,
+) -> Result<Option<PathBuf>, String> {
+    let Some(path) = env_export_path(spec) else {
+        return Ok(None);
+    };
+    if !outcome.errors.is_empty() || outcome.cancelled {
+        return match fs::remove_file(&path) {
+            Ok(()) => Ok(None),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(format!(
+                "failed to remove stale env export '{}': {error}",
+                path.display()
+            )),
+        };
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|error| {
+            format!(
+                "failed to create env export dir '{}': {error}",
+                parent.display()
+            )
+        })?;
+    }
+    fs::write(&path, env_export_contents(spec, outcome))
+        .map_err(|error| format!("failed to write env export '{}': {error}", path.display()))?;
+    Ok(Some(path))
+}
+
+fn env_export_contents(spec: &ResolvedBuildSpec, outcome: &ExecutionOutcome) -> String {
+    // From the image output contract rather than the image results, so a run that reuses the
+    // image still exports where it is.
+    let collect_dir = spec
+        .image
+        .output
+        .collect_dir
+        .as_deref()
+        .map(|dir| workspace_path(spec, dir));
+    let archive = collect_dir
+        .as_ref()
+        .zip(spec.image.output.archive_name.as_deref())
+        .map(|(dir, name)| dir.join(name));
+    let mut entries = vec![
+        (
+            "GAIA_BUILD_ID".to_string(),
+            Some(spec.identity.id.as_str().to_string()),
+        ),
+        (
+            "GAIA_BUILD_NAME".to_string(),
+            Some(spec.identity.build_name.clone()),
+        ),
+        (
+            "GAIA_BUILD_VERSION".to_string(),
+            spec.identity.version.clone(),
+        ),
+        ("GAIA_RUN_ID".to_string(), outcome.run_id.clone()),
+        (
+            "GAIA_OUT_DIR".to_string(),
+            Some(spec.workspace.out_dir.clone()),
+        ),
+        (
+            "GAIA_IMAGE_ARCHIVE".to_string(),
+            archive.map(|path| path.display().to_string()),
+        ),
+        (
+            "GAIA_IMAGE_COLLECT_DIR".to_string(),
+            collect_dir.map(|path| path.display().to_string()),
+        ),
+    ];
+    entries.extend(
+        outcome
+            .outputs
+            .iter()
+            .map(|(key, value)| (run_output_env_name(key), Some(value.clone()))),
+    );
+    entries
+        .into_iter()
+        .filter_map(|(key, value)| Some(format!("{key}={}\n", shell_quote(&value?))))
+        .collect()
+}
+
+// Single quotes keep every byte literal in POSIX shells; an embedded quote closes the string,
+// adds an escaped quote, and reopens it.
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use gaia_plan::OperationId;
+
+use crate::ExecutionEvent;
+
+struct RunningOperation {
+    started: Instant,
+    last_activity: Instant,
+}
+
+// Sits between the executor and its observer: every event is forwarded unchanged, and running
+// operations that stay silent for `interval` get a `Heartbeat`. Heartbeats only reach the
+// observer, never the recorded outcome.
+pub(crate) fn relay_with_heartbeats(
+    observer: Sender<ExecutionEvent>,
+    interval: Duration,
+) -> (Sender<ExecutionEvent>, JoinHandle<()>) {
+    let (sender, receiver) = mpsc::channel();
+    let handle = thread::spawn(move || relay(receiver, observer, interval));
+    (sender, handle)
+}
+
+fn relay(receiver: Receiver<ExecutionEvent>, observer: Sender<ExecutionEvent>, interval: Duration) {
+    let mut running = HashMap::<OperationId, RunningOperation>::new();
+    // Polling at a fraction of the interval keeps heartbeats close to it without busy waiting.
+    let tick = (interval / 4).max(Duration::from_millis(10));
+    loop {
+        match receiver.recv_timeout(tick) {
+            Ok(event) => {
+                let finished = matches!(event, ExecutionEvent::RunFinished { .. });
+                track(&mut running, &event, Instant::now());
+                if observer.send(event).is_err() || finished {
+                    return;
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+        let now = Instant::now();
+        let mut silent = running
+            .iter_mut()
+            .filter(|(_, operation)| now.duration_since(operation.last_activity) >= interval)
+            .collect::<Vec<_>>();
+        silent.sort_by(|left, right| left.0.as_str().cmp(right.0.as_str()));
+        for (operation_id, operation) in silent {
+            operation.last_activity = now;
+            let elapsed_ms = u64::try_from(now.duration_since(operation.started).as_millis())
+                .unwrap_or(u64::MAX);
+            let _ = observer.send(ExecutionEvent::Heartbeat {
+                operation_id: operation_id.clone(),
+                elapsed_ms,
+            });
+        }
+    }
+}
+
+fn track(
+    running: &mut HashMap<OperationId, RunningOperation>,
+    event: &ExecutionEvent,
+    now: Instant,
+) {
+    match event {
+        ExecutionEvent::Started { operation_id } => {
+            running.insert(
+                operation_id.clone(),
+                RunningOperation {
+                    started: now,
+                    last_activity: now,
+                },
+            );
+        }
+        ExecutionEvent::Log { operation_id, .. }
+        | ExecutionEvent::Progress { operation_id, .. } => {
+            if let Some(operation) = running.get_mut(operation_id) {
+                operation.last_activity = now;
+            }
+        }
+        ExecutionEvent::Succeeded { operation_id }
+        | ExecutionEvent::Reused { operation_id }
+        | ExecutionEvent::Cancelled { operation_id }
+        | ExecutionEvent::Skipped { operation_id, .. }
+        | ExecutionEvent::Failed { operation_id, .. } => {
+            running.remove(operation_id);
+        }
+        ExecutionEvent::RunStarted { .. }
+        | ExecutionEvent::RunFinished { .. }
+        | ExecutionEvent::Heartbeat { .. }
+        | ExecutionEvent::SchedulerTick { .. } => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relay_emits_heartbeats_only_for_silent_running_operations() {
+        let (observer, events) = mpsc::channel();
+        let (sender, handle) = relay_with_heartbeats(observer, Duration::from_millis(40));
+        let silent = OperationId::new("artifact:silent");
+        let chatty = OperationId::new("artifact:chatty");
+        sender
+            .send(ExecutionEvent::Started {
+                operation_id: silent.clone(),
+            })
+            .expect("send");
+        sender
+            .send(ExecutionEvent::Started {
+                operation_id: chatty.clone(),
+            })
+            .expect("send");
+        for _ in 0..10 {
+            thread::sleep(Duration::from_millis(15));
+            sender
+                .send(ExecutionEvent::Log {
+                    operation_id: chatty.clone(),
+                    message: "still compiling".into(),
+                })
+                .expect("send");
+        }
+        for operation_id in [&silent, &chatty] {
+            sender
+                .send(ExecutionEvent::Succeeded {
+                    operation_id: operation_id.clone(),
+                })
+                .expect("send");
+        }
+        thread::sleep(Duration::from_millis(100));
+        sender
+            .send(ExecutionEvent::RunFinished {
+                completed: 1,
+                failed: 0,
+                skipped: 0,
+                cancelled: false,
+            })
+            .expect("send");
+        handle.join().expect("relay thread");
+
+        let events = events.try_iter().collect::<Vec<_>>();
+        let heartbeats = events
+            .iter()
+            .filter_map(|event| match event {
+                ExecutionEvent::Heartbeat {
+                    operation_id,
+                    elapsed_ms,
+                } => Some((operation_id.clone(), *elapsed_ms)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert!(!heartbeats.is_empty());
+        assert!(heartbeats.iter().all(|(id, _)| *id == silent));
+        assert!(heartbeats.iter().all(|(_, elapsed_ms)| *elapsed_ms >= 40));
+        assert_eq!(
+            events
+                .iter()
+                .filter(|event| !matches!(event, ExecutionEvent::Heartbeat { .. }))
+                .count(),
+            15
+        );
+        assert!(matches!(
+            events.last(),
+            Some(ExecutionEvent::RunFinished { .. })
+        ));
+    }
+}
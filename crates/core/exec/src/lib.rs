@@ -1,12 +1,16 @@
+mod env_export;
 mod fs;
+mod heartbeat;
 mod operations;
 mod process;
 mod runtime;
 mod scheduler;
+mod timings;
 
 use std::collections::HashMap;
-use std::sync::mpsc::Sender;
+use std::sync::mpsc::{RecvTimeoutError, Sender};
 use std::thread;
+use std::time::{Duration, Instant};
 
 use gaia_artifact_providers::ArtifactProviderCatalog;
 use gaia_image_providers::ImageProviderCatalog;
@@ -16,15 +20,23 @@ use gaia_source_providers::SourceProviderCatalog;
 use gaia_spec::ResolvedBuildSpec;
 use runtime::ExecutionRuntime;
 use scheduler::{
-    ScheduleReadyContext, ScheduleReadyState, next_pending_operation_id, resolve_parallel_jobs,
-    schedule_ready_operations,
+    OperationCompletion, ScheduleReadyContext, ScheduleReadyState, next_pending_operation_id,
+    resolve_parallel_jobs, schedule_ready_operations, scheduler_tick, skipped_by_failures,
+    stall_report,
 };
 
+pub use env_export::{env_export_path, write_env_export};
 pub use operations::{
     ExecutionCleanupStatus, ExecutionError, ExecutionErrorKind, ExecutionEvent,
-    OperationExecutionResult,
+    OperationExecutionResult, PlannedOperationCommands, available_disk_bytes,
+    plan_operation_commands,
+};
+pub use runtime::{
+    CleanupFailure, ExecutionCancellation, ExecutionContext, ExecutionOutcome, OperationSpan,
+};
+pub use timings::{
+    OperationTimings, PhaseTiming, TIMING_SAMPLES_PER_OPERATION, phase_timings, timings_path,
 };
-pub use runtime::{CleanupFailure, ExecutionCancellation, ExecutionContext, ExecutionOutcome};
 
 pub struct ExecutionProviders<'a> {
     pub source_catalog: &'a SourceProviderCatalog,
@@ -67,14 +79,31 @@ pub fn execute_plan_with_cancellation_and_observer(
         "execute_plan",
         build_id = %spec.identity.id.as_str(),
         build_name = %spec.identity.build_name,
+        run_id = tracing::field::Empty,
         operations = plan.operations.len(),
         max_parallel_jobs,
         rollback_on_error = spec.policy.failure.rollback_on_error,
     );
     let _guard = span.enter();
+    let (event_sender, heartbeat_relay) = match event_sender {
+        Some(observer) if spec.policy.execution.heartbeat_seconds > 0 => {
+            let (sender, relay) = heartbeat::relay_with_heartbeats(
+                observer,
+                Duration::from_secs(spec.policy.execution.heartbeat_seconds),
+            );
+            (Some(sender), Some(relay))
+        }
+        event_sender => (event_sender, None),
+    };
     let context = ExecutionContext::new(spec);
+    span.record("run_id", context.run_id.as_str());
     let mut runtime = ExecutionRuntime::new(context, event_sender);
-    let build_name = runtime.context().build_name.clone();
+    let execution = runtime.context().clone();
+    runtime.emit_event(ExecutionEvent::RunStarted {
+        run_id: execution.run_id.clone(),
+        operations: plan.operations.len(),
+        max_parallel_jobs,
+    });
     let observer = runtime.event_sender();
     let operation_count = plan.operations.len();
     let operation_index: HashMap<&str, usize> = plan
@@ -94,6 +123,8 @@ pub fn execute_plan_with_cancellation_and_observer(
         }
     }
     let mut completed = vec![false; operation_count];
+    let mut failed = vec![false; operation_count];
+    let fail_fast = spec.policy.failure.fail_fast;
     let mut running = vec![false; operation_count];
     let mut running_count = 0usize;
     let mut first_failure: Option<(
@@ -107,6 +138,7 @@ pub fn execute_plan_with_cancellation_and_observer(
         Option<gaia_spec::RollbackDomain>,
         Vec<std::path::PathBuf>,
     )> = None;
+    let stall_warning = Duration::from_secs(spec.policy.execution.stall_warning_seconds);
     let cancel_check: ProcessCancelCheck = {
         let cancellation = cancellation.clone();
         std::sync::Arc::new(move || cancellation.is_cancelled())
@@ -116,21 +148,23 @@ pub fn execute_plan_with_cancellation_and_observer(
         spec,
         plan,
         providers: &providers,
-        build_name: build_name.as_str(),
+        execution: &execution,
         event_sender: observer.clone(),
         cancel_check: cancel_check.clone(),
         max_parallel_jobs,
     };
 
     thread::scope(|scope| {
-        let (result_tx, result_rx) =
-            std::sync::mpsc::channel::<(usize, OperationExecutionResult)>();
+        let (result_tx, result_rx) = std::sync::mpsc::channel::<OperationCompletion>();
+        let mut last_tick = None;
         loop {
             if cancellation.is_cancelled() {
                 cancellation_pending = true;
             }
 
-            if !cancellation_pending && first_failure.is_none() {
+            // Without fail-fast, dependents of a failed operation never become ready, so
+            // everything still scheduled is independent of the failure.
+            if !cancellation_pending && (first_failure.is_none() || !fail_fast) {
                 let scheduled_any = schedule_ready_operations(
                     scope,
                     &result_tx,
@@ -139,6 +173,7 @@ pub fn execute_plan_with_cancellation_and_observer(
                     ScheduleReadyState {
                         remaining_dependencies: &remaining_dependencies,
                         completed: &completed,
+                        failed: &failed,
                         running: &mut running,
                         running_count: &mut running_count,
                     },
@@ -149,6 +184,16 @@ pub fn execute_plan_with_cancellation_and_observer(
             }
 
             if running_count == 0 {
+                if !fail_fast && !cancellation_pending {
+                    for (index, failed_dependency) in
+                        skipped_by_failures(&failed, &completed, &dependents)
+                    {
+                        runtime.skip(
+                            plan.operations[index].id.clone(),
+                            plan.operations[failed_dependency].id.clone(),
+                        );
+                    }
+                }
                 if let Some((failed_operation_id, failed_cleanup_domain, failed_cleanup_paths)) =
                     first_failure.take()
                 {
@@ -187,7 +232,41 @@ pub fn execute_plan_with_cancellation_and_observer(
                 break;
             }
 
-            let Ok((index, result)) = result_rx.recv() else {
+            // Counting the queue is a walk over every operation, so it only happens for an observer,
+            // and an unchanged queue is not sent again.
+            if let Some(observer) = &observer {
+                let tick = scheduler_tick(&remaining_dependencies, &completed, &running, &failed);
+                if last_tick.as_ref() != Some(&tick) {
+                    let _ = observer.send(tick.clone());
+                    last_tick = Some(tick);
+                }
+            }
+
+            // Operation timeouts bound each operation; this only reports, repeatedly, that the loop
+            // itself has stopped seeing completions.
+            let completion = if stall_warning.is_zero() {
+                result_rx.recv().ok()
+            } else {
+                let waiting_since = Instant::now();
+                loop {
+                    match result_rx.recv_timeout(stall_warning) {
+                        Ok(completion) => break Some(completion),
+                        Err(RecvTimeoutError::Timeout) => tracing::warn!(
+                            idle_seconds = waiting_since.elapsed().as_secs(),
+                            waiting_on = %stall_report(
+                                plan,
+                                &remaining_dependencies,
+                                &completed,
+                                &running,
+                                &failed
+                            ),
+                            "no operation finished within execution.stall_warning_seconds"
+                        ),
+                        Err(RecvTimeoutError::Disconnected) => break None,
+                    }
+                }
+            };
+            let Some(OperationCompletion { index, result }) = completion else {
                 break;
             };
             running[index] = false;
@@ -229,12 +308,16 @@ pub fn execute_plan_with_cancellation_and_observer(
                     remaining_dependencies[dependent] =
                         remaining_dependencies[dependent].saturating_sub(1);
                 }
-            } else if first_failure.is_none() {
+            } else {
+                failed[index] = true;
+            }
+            if !succeeded && first_failure.is_none() {
                 if let Some(error) = &result.error {
                     tracing::warn!(
                         operation_id = %result.operation_id.as_str(),
                         error_code = error.code,
                         error_kind = ?error.kind,
+                        exit = error.exit.map(tracing::field::display),
                         output_tail_lines = error.output_tail.len(),
                         cleanup_domain = ?result.cleanup_domain,
                         cleanup_paths = result.cleanup_paths.len(),
@@ -259,5 +342,12 @@ pub fn execute_plan_with_cancellation_and_observer(
         }
     });
 
-    runtime.finish()
+    runtime.emit_run_finished();
+    timings::record_timings(spec, runtime.elapsed_ms());
+    let outcome = runtime.finish();
+    // Joining keeps every forwarded event ahead of the return, as without the relay.
+    if let Some(relay) = heartbeat_relay {
+        let _ = relay.join();
+    }
+    outcome
 }
@@ -0,0 +1,177 @@
+use std::env;
+use std::path::Path;
+
+use gaia_plan::{OperationKind, PlannedOperation};
+use gaia_spec::ResolvedBuildSpec;
+
+use super::helpers::failure_with_kind;
+use super::{ExecutionErrorKind, ExecutionEvent, OperationExecutionResult};
+
+const MIB: u64 = 1024 * 1024;
+// How ENOSPC and EDQUOT read once a tool or `std::io::Error` has turned them into text.
+const OUT_OF_SPACE_MARKERS: [&str; 4] = [
+    "no space left on device",
+    "os error 28",
+    "disk quota exceeded",
+    "os error 122",
+];
+
+// Free bytes on the filesystem holding `path`, or on its nearest existing ancestor when `path`
+// has not been created yet. `None` when the platform cannot report it.
+pub fn available_disk_bytes(path: &Path) -> Option<u64> {
+    let path = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        env::current_dir().ok()?.join(path)
+    };
+    let existing = path.ancestors().find(|ancestor| ancestor.is_dir())?;
+    filesystem_available_bytes(existing)
+}
+
+#[cfg(unix)]
+fn filesystem_available_bytes(path: &Path) -> Option<u64> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stats = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+    // SAFETY: `path` is NUL-terminated and `stats` is only read after statvfs reports success.
+    if unsafe { libc::statvfs(path.as_ptr(), stats.as_mut_ptr()) } != 0 {
+        return None;
+    }
+    let stats = unsafe { stats.assume_init() };
+    // The statvfs field widths differ between platforms.
+    #[allow(clippy::unnecessary_cast)]
+    Some((stats.f_bavail as u64).saturating_mul(stats.f_frsize as u64))
+}
+
+#[cfg(not(unix))]
+fn filesystem_available_bytes(_path: &Path) -> Option<u64> {
+    None
+}
+
+// Refuses to start an operation that fills the build dir when `[execution] min_free_space_mb`
+// is set and the filesystem already has less than that free.
+pub(super) fn check_free_space(
+    operation: &PlannedOperation,
+    spec: &ResolvedBuildSpec,
+) -> Option<OperationExecutionResult> {
+    let required_mb = spec.policy.execution.min_free_space_mb;
+    let writes_build_dir = matches!(
+        operation.kind,
+        OperationKind::MaterializeSource { .. }
+            | OperationKind::BuildArtifact { .. }
+            | OperationKind::PrepareImage
+            | OperationKind::BuildImage
+    );
+    if required_mb == 0 || !writes_build_dir {
+        return None;
+    }
+    let build_dir = Path::new(&spec.workspace.build_dir);
+    let available = available_disk_bytes(build_dir)?;
+    if available >= required_mb.saturating_mul(MIB) {
+        return None;
+    }
+    Some(failure_with_kind(
+        operation.id.clone(),
+        "disk_space_low",
+        ExecutionErrorKind::DiskFull,
+        format!(
+            "out of disk space in '{}': {} MiB free, {required_mb} MiB required by execution.min_free_space_mb",
+            build_dir.display(),
+            available / MIB
+        ),
+    ))
+}
+
+// A full disk surfaces as whatever the failing tool printed; naming it keeps the report from
+// blaming the backend command for what is really a host problem.
+pub(super) fn classify_out_of_space(
+    mut result: OperationExecutionResult,
+    build_dir: &str,
+) -> OperationExecutionResult {
+    let Some(error) = result.error.as_mut() else {
+        return result;
+    };
+    if matches!(
+        error.kind,
+        ExecutionErrorKind::DiskFull | ExecutionErrorKind::Cancelled
+    ) || !std::iter::once(&error.message)
+        .chain(&error.output_tail)
+        .any(|line| reports_out_of_space(line))
+    {
+        return result;
+    }
+    error.kind = ExecutionErrorKind::DiskFull;
+    error.message = format!("out of disk space in '{build_dir}': {}", error.message);
+    for event in &mut result.events {
+        if let ExecutionEvent::Failed {
+            operation_id,
+            message,
+        } = event
+            && *operation_id == result.operation_id
+        {
+            message.clone_from(&error.message);
+        }
+    }
+    result
+}
+
+fn reports_out_of_space(line: &str) -> bool {
+    let line = line.to_ascii_lowercase();
+    OUT_OF_SPACE_MARKERS
+        .iter()
+        .any(|marker| line.contains(marker))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::helpers::failure_with_kind_and_tail;
+    use gaia_plan::OperationId;
+
+    #[test]
+    fn out_of_space_failures_are_reclassified_with_the_build_dir() {
+        let failed = failure_with_kind_and_tail(
+            OperationId::new("artifact:app"),
+            "artifact_failed",
+            ExecutionErrorKind::BackendCommand,
+            "cargo build failed".into(),
+            vec![
+                "error: failed to write target/debug/app: No space left on device (os error 28)"
+                    .into(),
+            ],
+        );
+
+        let result = classify_out_of_space(failed, "/work/.gaia/build");
+
+        let error = result.error.as_ref().expect("error");
+        assert_eq!(error.kind, ExecutionErrorKind::DiskFull);
+        assert_eq!(error.code, "artifact_failed");
+        assert_eq!(
+            error.message,
+            "out of disk space in '/work/.gaia/build': cargo build failed"
+        );
+        assert_eq!(
+            result.events,
+            vec![ExecutionEvent::Failed {
+                operation_id: OperationId::new("artifact:app"),
+                message: error.message.clone(),
+            }]
+        );
+    }
+
+    #[test]
+    fn other_failures_keep_their_kind() {
+        let failed = failure_with_kind(
+            OperationId::new("artifact:app"),
+            "artifact_failed",
+            ExecutionErrorKind::BackendCommand,
+            "cargo build failed: exit status 101".into(),
+        );
+
+        let result = classify_out_of_space(failed.clone(), "/work/.gaia/build");
+
+        assert_eq!(result, failed);
+    }
+}
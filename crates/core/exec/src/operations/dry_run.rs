@@ -0,0 +1,90 @@
+use super::*;
+use gaia_image_providers::ImageProviderOperation;
+use gaia_plan::ExecutionPlan;
+use gaia_process::PlannedCommand;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedOperationCommands {
+    pub operation_id: OperationId,
+    pub reused: bool,
+    pub commands: Vec<PlannedCommand>,
+    pub error: Option<String>,
+}
+
+// Resolves the commands each planned operation would start, in plan order, without running
+// anything. Reused operations and operations that only write runtime state report no commands.
+pub fn plan_operation_commands(
+    spec: &ResolvedBuildSpec,
+    plan: &ExecutionPlan,
+    providers: &ExecutionProviders<'_>,
+) -> Vec<PlannedOperationCommands> {
+    plan.operations
+        .iter()
+        .map(|operation| {
+            let reused = matches!(operation.reuse, OperationReuse::Reuse { .. });
+            let commands = if reused {
+                Ok(Vec::new())
+            } else {
+                operation_commands(spec, operation, providers)
+            };
+            let (commands, error) = match commands {
+                Ok(commands) => (commands, None),
+                Err(message) => (Vec::new(), Some(message)),
+            };
+            PlannedOperationCommands {
+                operation_id: operation.id.clone(),
+                reused,
+                commands,
+                error,
+            }
+        })
+        .collect()
+}
+
+fn operation_commands(
+    spec: &ResolvedBuildSpec,
+    operation: &PlannedOperation,
+    providers: &ExecutionProviders<'_>,
+) -> Result<Vec<PlannedCommand>, String> {
+    match &operation.kind {
+        OperationKind::BuildArtifact { artifact_id } => {
+            let artifact = spec
+                .artifacts
+                .iter()
+                .find(|artifact| artifact.id == *artifact_id)
+                .ok_or_else(|| format!("missing artifact spec '{}'", artifact_id.as_str()))?;
+            let provider = providers
+                .artifact_catalog
+                .find_for_kind(artifact.provider_kind())
+                .ok_or_else(|| {
+                    format!("missing artifact provider for '{}'", artifact_id.as_str())
+                })?;
+            let contract =
+                artifact_execution_contract(spec, artifact).map_err(|error| error.message)?;
+            provider
+                .planned_artifact_commands(artifact, &contract)
+                .map_err(|error| error.message)
+        }
+        OperationKind::PrepareImage | OperationKind::BuildImage => {
+            let provider_kind = spec.image.provider_kind();
+            let provider = providers
+                .image_catalog
+                .find_for_kind(provider_kind)
+                .ok_or_else(|| format!("missing image provider for '{provider_kind:?}'"))?;
+            let image_operation = if operation.kind == OperationKind::PrepareImage {
+                ImageProviderOperation::Prepare
+            } else {
+                ImageProviderOperation::Build
+            };
+            provider
+                .planned_image_commands(
+                    spec,
+                    &spec.image,
+                    image_operation,
+                    &image_execution_policy(spec),
+                )
+                .map_err(|error| error.message)
+        }
+        _ => Ok(Vec::new()),
+    }
+}
@@ -1,7 +1,11 @@
 use super::*;
+use gaia_artifact_providers::{ArtifactExecutionContract, ArtifactProviderError};
 use gaia_image_providers::ImageExecutionPolicy;
-use gaia_spec::{KeyValueState, SourceDefinition, StageItemId};
+use gaia_spec::{ArtifactDefinition, ImageDefinition, KeyValueState, StageItemId};
 use std::fs as std_fs;
+use std::path::Path;
+
+use crate::runtime::ExecutionContext;
 
 pub(crate) fn merge_streamed_logs(
     receiver: mpsc::Receiver<String>,
@@ -46,6 +50,23 @@ pub(crate) fn process_output_retention(
     }
 }
 
+pub(crate) fn artifact_execution_contract(
+    spec: &ResolvedBuildSpec,
+    artifact: &gaia_spec::ArtifactSpec,
+) -> Result<ArtifactExecutionContract, ArtifactProviderError> {
+    ArtifactExecutionContract::from_spec(
+        artifact,
+        resolve_artifact_source_dir(spec, artifact),
+        matches!(artifact.definition, ArtifactDefinition::Rust(_))
+            && spec.policy.providers.rust.allow_nested_build,
+        spec.policy
+            .providers
+            .artifact_command_policy(artifact.provider_kind()),
+        spec.policy.execution.output_retention,
+    )
+    .try_with_build_context(spec)
+}
+
 pub(crate) fn resolve_artifact_source_dir(
     spec: &ResolvedBuildSpec,
     artifact: &gaia_spec::ArtifactSpec,
@@ -55,37 +76,13 @@ pub(crate) fn resolve_artifact_source_dir(
         .sources
         .iter()
         .find(|source| source.id == source_ref.id)?;
-    match &source.definition {
-        SourceDefinition::Path(path) => {
-            let candidate = PathBuf::from(&path.path);
-            let resolved = if candidate.is_absolute() {
-                candidate
-            } else {
-                PathBuf::from(&spec.workspace.root_dir).join(candidate)
-            };
-            Some(
-                std_fs::canonicalize(&resolved)
-                    .unwrap_or(resolved)
-                    .display()
-                    .to_string(),
-            )
-        }
-        SourceDefinition::Git(_) | SourceDefinition::Archive(_) | SourceDefinition::Download(_) => {
-            let build_dir = PathBuf::from(&spec.workspace.build_dir);
-            let resolved_build_dir = if build_dir.is_absolute() {
-                build_dir
-            } else {
-                PathBuf::from(&spec.workspace.root_dir).join(build_dir)
-            };
-            let resolved = resolved_build_dir.join("sources").join(source.id.as_str());
-            Some(
-                std_fs::canonicalize(&resolved)
-                    .unwrap_or(resolved)
-                    .display()
-                    .to_string(),
-            )
-        }
-    }
+    let resolved = source.materialized_path(&spec.workspace);
+    Some(
+        std_fs::canonicalize(&resolved)
+            .unwrap_or(resolved)
+            .display()
+            .to_string(),
+    )
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -93,6 +90,8 @@ pub(crate) enum StageRuntimeKind {
     File,
     Env,
     Service,
+    Overlay,
+    Symlink,
 }
 
 impl StageRuntimeKind {
@@ -101,6 +100,8 @@ impl StageRuntimeKind {
             Self::File => "file",
             Self::Env => "env",
             Self::Service => "service",
+            Self::Overlay => "overlay",
+            Self::Symlink => "symlink",
         }
     }
 }
@@ -175,6 +176,46 @@ pub(crate) fn stage_result(
                         .unwrap_or_default(),
                 );
         }
+        StageRuntimeKind::Overlay => {
+            let overlay = spec
+                .stage
+                .overlays
+                .iter()
+                .find(|overlay| overlay.id == *item_id);
+            state = state
+                .with(
+                    "src",
+                    overlay
+                        .map(|overlay| overlay.src.as_str())
+                        .unwrap_or_default(),
+                )
+                .with(
+                    "dest",
+                    overlay
+                        .map(|overlay| overlay.dest.as_str())
+                        .unwrap_or_default(),
+                );
+        }
+        StageRuntimeKind::Symlink => {
+            let symlink = spec
+                .stage
+                .symlinks
+                .iter()
+                .find(|symlink| symlink.id == *item_id);
+            state = state
+                .with(
+                    "target",
+                    symlink
+                        .map(|symlink| symlink.target.as_str())
+                        .unwrap_or_default(),
+                )
+                .with(
+                    "link",
+                    symlink
+                        .map(|symlink| symlink.link.as_str())
+                        .unwrap_or_default(),
+                );
+        }
     };
     let state_path = stage_state_path(spec, kind, item_id);
     if let Err(message) = write_runtime_state(state_path.clone(), &state) {
@@ -209,6 +250,23 @@ fn stage_state_path(spec: &ResolvedBuildSpec, kind: &str, item_id: &StageItemId)
     runtime_state_dir(spec).join(format!("stage-{kind}-{}.state", item_id.as_str()))
 }
 
+// Anchor outputs relative to the workspace root, which is what a restore into `restore_root`
+// rebuilds; outputs outside the workspace keep their absolute path.
+pub(crate) fn checkpoint_anchor_layout(spec: &ResolvedBuildSpec, outputs: &[PathBuf]) -> String {
+    let root = gaia_spec::resolve_workspace_path(&spec.workspace, ".").unwrap_or_default();
+    outputs
+        .iter()
+        .map(|output| {
+            output
+                .strip_prefix(&root)
+                .unwrap_or(output)
+                .display()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
 pub(crate) fn checkpoint_state_path(
     spec: &ResolvedBuildSpec,
     checkpoint_id: &gaia_spec::CheckpointId,
@@ -216,6 +274,105 @@ pub(crate) fn checkpoint_state_path(
     runtime_state_dir(spec).join(format!("checkpoint-{}.state", checkpoint_id.as_str()))
 }
 
+// Artifact builds publish run outputs by appending `key=value` lines to the file named by
+// `GAIA_OUTPUTS`, and read what earlier operations published as `GAIA_OUTPUT_<KEY>`. The file
+// lives in `.gaia/` under the workspace root, the dir Docker builds mount, rather than under
+// `out_dir`, which may sit outside it; it is removed once read.
+pub(crate) fn artifact_outputs_path(
+    spec: &ResolvedBuildSpec,
+    context: &ExecutionContext,
+    artifact_id: &gaia_spec::ArtifactId,
+) -> PathBuf {
+    spec.workspace
+        .root_path()
+        .join(".gaia/outputs")
+        .join(format!("{}-{}.env", context.run_id, artifact_id.as_str()))
+}
+
+pub(crate) fn export_run_outputs(
+    environment: &mut gaia_process::ProcessEnvironment,
+    context: &ExecutionContext,
+    outputs_path: &Path,
+) -> Result<(), String> {
+    if let Some(parent) = outputs_path.parent() {
+        std_fs::create_dir_all(parent).map_err(|error| {
+            format!(
+                "failed to create run outputs dir '{}': {error}",
+                parent.display()
+            )
+        })?;
+    }
+    std_fs::write(outputs_path, "").map_err(|error| {
+        format!(
+            "failed to create run outputs file '{}': {error}",
+            outputs_path.display()
+        )
+    })?;
+    for (key, value) in context.outputs() {
+        environment.vars.insert(run_output_env_name(&key), value);
+    }
+    environment
+        .vars
+        .insert("GAIA_OUTPUTS".into(), outputs_path.display().to_string());
+    Ok(())
+}
+
+pub(crate) fn publish_run_outputs(
+    context: &ExecutionContext,
+    outputs_path: &Path,
+) -> Result<Vec<String>, String> {
+    let contents = match std_fs::read_to_string(outputs_path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => {
+            return Err(format!(
+                "failed to read run outputs '{}': {error}",
+                outputs_path.display()
+            ));
+        }
+    };
+    let _ = std_fs::remove_file(outputs_path);
+    let mut published = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Some((key, value)) = line
+            .split_once('=')
+            .filter(|(key, _)| is_run_output_key(key))
+        else {
+            return Err(format!(
+                "run outputs line {} must be `key=value` with a key of letters, digits, '_', '-' or '.': {line}",
+                index + 1
+            ));
+        };
+        context.set_output(key, value);
+        published.push(format!("published run output '{key}'"));
+    }
+    Ok(published)
+}
+
+fn is_run_output_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|character| character.is_ascii_alphanumeric() || "_-.".contains(character))
+}
+
+pub(crate) fn run_output_env_name(key: &str) -> String {
+    let name = key
+        .chars()
+        .map(|character| {
+            if character.is_ascii_alphanumeric() {
+                character.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    format!("GAIA_OUTPUT_{name}")
+}
+
 pub(crate) fn write_runtime_state(path: PathBuf, state: &KeyValueState) -> Result<(), String> {
     if let Some(parent) = path.parent() {
         std_fs::create_dir_all(parent).map_err(|error| {
@@ -322,6 +479,7 @@ pub(crate) fn failure_with_kind_and_tail(
             cleanup_paths: Vec::new(),
             cleanup_status: crate::ExecutionCleanupStatus::NotRequired,
             cleanup_failures: Vec::new(),
+            exit: None,
         }),
         cancelled: false,
         reused_source: None,
@@ -443,6 +601,13 @@ pub(crate) fn execution_error_kind_from_image(
 }
 
 impl OperationExecutionResult {
+    pub(crate) fn with_exit(mut self, exit: Option<gaia_process::ProcessExit>) -> Self {
+        if let Some(error) = &mut self.error {
+            error.exit = exit;
+        }
+        self
+    }
+
     pub(crate) fn with_cleanup_paths(mut self, cleanup_paths: Vec<PathBuf>) -> Self {
         self.cleanup_paths = cleanup_paths;
         self
@@ -482,11 +647,12 @@ pub(crate) fn artifact_cleanup_paths(contract: &ArtifactExecutionContract) -> Ve
 }
 
 pub(crate) fn image_cleanup_paths(
+    spec: &ResolvedBuildSpec,
     result: &gaia_image_providers::ImageExecutionResult,
 ) -> Vec<PathBuf> {
     let mut paths = Vec::new();
     if let Some(collect_dir) = &result.collect_dir {
-        paths.push(collect_dir.clone());
+        paths.extend(collect_dir_cleanup_paths(spec, collect_dir));
     }
     if let Some(archive_path) = &result.archive_path
         && !paths.iter().any(|existing| existing == archive_path)
@@ -499,7 +665,7 @@ pub(crate) fn image_cleanup_paths(
 pub(crate) fn image_definition_cleanup_paths(spec: &ResolvedBuildSpec) -> Vec<PathBuf> {
     let mut paths = Vec::new();
     if let Some(collect_dir) = &spec.image.output.collect_dir {
-        paths.push(PathBuf::from(collect_dir));
+        paths.extend(collect_dir_cleanup_paths(spec, Path::new(collect_dir)));
     }
     if let (Some(collect_dir), Some(archive_name)) = (
         &spec.image.output.collect_dir,
@@ -513,10 +679,86 @@ pub(crate) fn image_definition_cleanup_paths(spec: &ResolvedBuildSpec) -> Vec<Pa
     paths
 }
 
+// An incremental Buildroot image keeps its output dir, so rollback removes the rest of the
+// collect dir entry by entry instead of the whole dir.
+fn collect_dir_cleanup_paths(spec: &ResolvedBuildSpec, collect_dir: &Path) -> Vec<PathBuf> {
+    let preserved = match &spec.image.definition {
+        ImageDefinition::Buildroot(buildroot) => buildroot.preserved_output_dir(collect_dir),
+        _ => None,
+    };
+    let Some(preserved) = preserved else {
+        return vec![collect_dir.to_path_buf()];
+    };
+    std_fs::read_dir(collect_dir)
+        .map(|entries| {
+            let mut paths = entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| *path != preserved)
+                .collect::<Vec<_>>();
+            paths.sort();
+            paths
+        })
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn run_outputs_round_trip_through_the_outputs_file() {
+        let mut spec = ResolvedBuildSpec::new("run-outputs");
+        let root = std::env::temp_dir().join(format!("gaia-run-outputs-{}", std::process::id()));
+        spec.workspace.root_dir = root.join("workspace").display().to_string();
+        spec.workspace.out_dir = root.join("out").display().to_string();
+        let context = ExecutionContext::new(&spec);
+        context.set_output("app.version", "1.2.3");
+        let outputs_path =
+            artifact_outputs_path(&spec, &context, &gaia_spec::ArtifactId::new("api"));
+        // Docker builds mount only the workspace root, so the file must not follow `out_dir`.
+        assert!(outputs_path.starts_with(&spec.workspace.root_dir));
+        let mut environment = gaia_process::ProcessEnvironment::default();
+
+        export_run_outputs(&mut environment, &context, &outputs_path).expect("export outputs");
+        assert_eq!(
+            environment
+                .vars
+                .get("GAIA_OUTPUT_APP_VERSION")
+                .map(String::as_str),
+            Some("1.2.3")
+        );
+        assert_eq!(
+            environment.vars.get("GAIA_OUTPUTS"),
+            Some(&outputs_path.display().to_string())
+        );
+
+        std_fs::write(
+            &outputs_path,
+            "api.url=http://localhost:8080/a=b\n\napp.version=1.2.4\n",
+        )
+        .expect("outputs file");
+        let published = publish_run_outputs(&context, &outputs_path).expect("publish outputs");
+        assert_eq!(published.len(), 2);
+        assert!(!outputs_path.exists());
+        assert_eq!(
+            context.outputs().into_iter().collect::<Vec<_>>(),
+            vec![
+                (
+                    "api.url".to_string(),
+                    "http://localhost:8080/a=b".to_string()
+                ),
+                ("app.version".to_string(), "1.2.4".to_string()),
+            ]
+        );
+
+        std_fs::write(&outputs_path, "just text\n").expect("outputs file");
+        let error = publish_run_outputs(&context, &outputs_path).expect_err("malformed line");
+        assert!(error.starts_with("run outputs line 1 must be `key=value`"));
+
+        let _ = std_fs::remove_dir_all(root);
+    }
+
     #[test]
     fn output_tail_uses_execution_retention_policy() {
         let mut spec = ResolvedBuildSpec::new("tail-policy");
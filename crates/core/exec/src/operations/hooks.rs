@@ -0,0 +1,179 @@
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Duration;
+
+use gaia_process::{ProcessCancelCheck, ProcessExit, ProcessRunErrorKind};
+use gaia_spec::{HookFailureMode, OperationHooksSpec};
+
+use super::*;
+
+struct HookFailure {
+    kind: ExecutionErrorKind,
+    message: String,
+    output_tail: Vec<String>,
+    exit: Option<ProcessExit>,
+}
+
+// Pre hooks run in order before the body and the first failure fails the operation without
+// running it. Post hooks all run after a body that succeeded or failed, never after a cancelled
+// one; their failures are logged as warnings unless `post_failure = "fail"` turns a success into a
+// failure. Cleanup paths of the body are kept either way, so rollback still sees them.
+pub(crate) fn run_with_hooks(
+    operation: &PlannedOperation,
+    spec: &ResolvedBuildSpec,
+    context: &ExecutionContext,
+    hooks: &OperationHooksSpec,
+    cancel_check: Option<ProcessCancelCheck>,
+    body: impl FnOnce() -> OperationExecutionResult,
+) -> OperationExecutionResult {
+    let mut pre_logs = Vec::new();
+    for command in &hooks.pre {
+        match run_hook(
+            operation,
+            spec,
+            context,
+            hooks,
+            command,
+            "starting",
+            cancel_check.clone(),
+        ) {
+            Ok(()) => pre_logs.push(ExecutionEvent::Log {
+                operation_id: operation.id.clone(),
+                message: format!("ran pre hook '{command}'"),
+            }),
+            Err(failure) => {
+                let mut result = failure_with_kind_and_tail(
+                    operation.id.clone(),
+                    "operation_pre_hook_failed",
+                    failure.kind,
+                    format!("pre hook '{command}' failed: {}", failure.message),
+                    failure.output_tail,
+                )
+                .with_exit(failure.exit);
+                result.events.splice(0..0, pre_logs);
+                return result;
+            }
+        }
+    }
+
+    let mut result = body();
+    result.events.splice(0..0, pre_logs);
+    if result.cancelled {
+        return result;
+    }
+    let status = if result.error.is_some() {
+        "failed"
+    } else {
+        "succeeded"
+    };
+    for command in &hooks.post {
+        let message = match run_hook(
+            operation,
+            spec,
+            context,
+            hooks,
+            command,
+            status,
+            cancel_check.clone(),
+        ) {
+            Ok(()) => format!("ran post hook '{command}'"),
+            Err(failure)
+                if hooks.post_failure == HookFailureMode::Fail && result.error.is_none() =>
+            {
+                result = fail_after_post_hook(result, command, failure);
+                continue;
+            }
+            Err(failure) => format!("WARN: post hook '{command}' failed: {}", failure.message),
+        };
+        let terminal = result.events.len().saturating_sub(1);
+        result.events.insert(
+            terminal,
+            ExecutionEvent::Log {
+                operation_id: operation.id.clone(),
+                message,
+            },
+        );
+    }
+    result
+}
+
+fn fail_after_post_hook(
+    succeeded: OperationExecutionResult,
+    command: &str,
+    failure: HookFailure,
+) -> OperationExecutionResult {
+    let mut result = failure_with_kind_and_tail(
+        succeeded.operation_id.clone(),
+        "operation_post_hook_failed",
+        failure.kind,
+        format!("post hook '{command}' failed: {}", failure.message),
+        failure.output_tail,
+    )
+    .with_exit(failure.exit);
+    let mut events = succeeded.events;
+    events.retain(|event| !matches!(event, ExecutionEvent::Succeeded { .. }));
+    events.append(&mut result.events);
+    result.events = events;
+    result.image_results = succeeded.image_results;
+    result.cleanup_domain = succeeded.cleanup_domain;
+    result.cleanup_paths = succeeded.cleanup_paths;
+    if let Some(error) = &mut result.error {
+        error.cleanup_domain = result.cleanup_domain;
+        error.cleanup_paths = result.cleanup_paths.clone();
+    }
+    result
+}
+
+fn run_hook(
+    operation: &PlannedOperation,
+    spec: &ResolvedBuildSpec,
+    context: &ExecutionContext,
+    hooks: &OperationHooksSpec,
+    command_line: &str,
+    status: &str,
+    cancel_check: Option<ProcessCancelCheck>,
+) -> Result<(), HookFailure> {
+    let mut command = Command::new("sh");
+    command
+        .arg("-c")
+        .arg(command_line)
+        .current_dir(PathBuf::from(&spec.workspace.root_dir))
+        .env("GAIA_OPERATION_ID", operation.id.as_str())
+        .env("GAIA_OPERATION_STATUS", status)
+        .env("GAIA_RUN_ID", &context.run_id);
+    let run = gaia_process::run_command_with_timeout(
+        &mut command,
+        Duration::from_secs(hooks.timeout_seconds.max(1)),
+        &format!("hook '{command_line}'"),
+        None,
+        cancel_check,
+    )
+    .map_err(|error| HookFailure {
+        kind: match error.kind {
+            ProcessRunErrorKind::ToolStart => ExecutionErrorKind::ToolStart,
+            ProcessRunErrorKind::Timeout => ExecutionErrorKind::Timeout,
+            ProcessRunErrorKind::Cancelled => ExecutionErrorKind::Cancelled,
+            ProcessRunErrorKind::RuntimeState => ExecutionErrorKind::RuntimeState,
+        },
+        message: error.message,
+        output_tail: Vec::new(),
+        exit: None,
+    })?;
+    let Some(exit) = ProcessExit::from_status(&run.output.status) else {
+        return Ok(());
+    };
+    let lines = run
+        .stdout_lines
+        .into_iter()
+        .chain(run.stderr_lines)
+        .collect::<Vec<_>>();
+    Err(HookFailure {
+        kind: ExecutionErrorKind::BackendCommand,
+        message: match exit {
+            ProcessExit::Code(code) => format!("exited with status {code}"),
+            signal => format!("terminated by {signal}"),
+        },
+        output_tail: output_tail(&lines, spec),
+        exit: Some(exit),
+    })
+}
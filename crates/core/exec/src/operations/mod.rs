@@ -1,19 +1,39 @@
+mod disk_space;
+mod dry_run;
 mod helpers;
+mod hooks;
 
-use gaia_artifact_providers::ArtifactExecutionContract;
-use gaia_plan::{OperationId, OperationKind, OperationReuse, PlannedOperation};
-use gaia_spec::{ArtifactDefinition, ResolvedBuildSpec, RollbackDomain};
+pub use disk_space::available_disk_bytes;
+pub use dry_run::{PlannedOperationCommands, plan_operation_commands};
+use gaia_plan::{
+    OperationId, OperationKind, OperationReuse, PlannedOperation, checkpoint_anchor_outputs,
+};
+use gaia_spec::{ResolvedBuildSpec, RollbackDomain};
+pub(crate) use helpers::run_output_env_name;
 use helpers::*;
 use std::path::PathBuf;
 
 use crate::ExecutionProviders;
 use crate::fs::FsMutation;
 use crate::process;
-use crate::runtime::process_log_sink;
+use crate::runtime::{ExecutionContext, process_log_sink, process_progress_sink};
 use std::sync::mpsc;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ExecutionEvent {
+    // Emitted once before any operation starts so consumers can size progress up front.
+    RunStarted {
+        run_id: String,
+        operations: usize,
+        max_parallel_jobs: usize,
+    },
+    // Emitted once after rollback or cancellation cleanup, as the last event of a run.
+    RunFinished {
+        completed: usize,
+        failed: usize,
+        skipped: usize,
+        cancelled: bool,
+    },
     Started {
         operation_id: OperationId,
     },
@@ -21,6 +41,24 @@ pub enum ExecutionEvent {
         operation_id: OperationId,
         message: String,
     },
+    Progress {
+        operation_id: OperationId,
+        percent: u8,
+    },
+    // Sent only to observers, for a running operation that has been silent for the configured
+    // heartbeat interval; `elapsed_ms` counts from when the operation started.
+    Heartbeat {
+        operation_id: OperationId,
+        elapsed_ms: u64,
+    },
+    // Sent only to observers whenever the scheduler's queue changes: operations running, ready
+    // but waiting for a free job slot, and finished, out of `total`.
+    SchedulerTick {
+        running: usize,
+        ready: usize,
+        completed: usize,
+        total: usize,
+    },
     Succeeded {
         operation_id: OperationId,
     },
@@ -30,6 +68,12 @@ pub enum ExecutionEvent {
     Cancelled {
         operation_id: OperationId,
     },
+    // Only emitted with `fail_fast = false`, for operations left unrun because something they
+    // transitively depend on failed.
+    Skipped {
+        operation_id: OperationId,
+        failed_dependency: OperationId,
+    },
     Failed {
         operation_id: OperationId,
         message: String,
@@ -47,6 +91,8 @@ pub struct ExecutionError {
     pub cleanup_paths: Vec<PathBuf>,
     pub cleanup_status: ExecutionCleanupStatus,
     pub cleanup_failures: Vec<String>,
+    // The exit code or signal of the failed command, when the failure came from one.
+    pub exit: Option<gaia_process::ProcessExit>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -60,6 +106,7 @@ pub enum ExecutionErrorKind {
     BackendCommand,
     PolicyBlocked,
     RuntimeState,
+    DiskFull,
     Unknown,
 }
 
@@ -116,13 +163,14 @@ pub fn dispatch_operation(
     operation: &PlannedOperation,
     spec: &ResolvedBuildSpec,
     providers: &ExecutionProviders<'_>,
-    build_name: &str,
+    context: &ExecutionContext,
     event_sender: Option<std::sync::mpsc::Sender<ExecutionEvent>>,
     cancel_check: Option<gaia_process::ProcessCancelCheck>,
 ) -> OperationExecutionResult {
     let span = tracing::info_span!(
         "execute_operation",
         build_id = %spec.identity.id.as_str(),
+        run_id = %context.run_id,
         operation_id = %operation.id.as_str(),
         operation_kind = ?operation.kind,
         parallelism_mode = ?operation.parallelism.mode,
@@ -131,7 +179,7 @@ pub fn dispatch_operation(
     );
     let _guard = span.enter();
     if let OperationReuse::Reuse { source } = &operation.reuse {
-        OperationExecutionResult {
+        return OperationExecutionResult {
             operation_id: operation.id.clone(),
             events: vec![
                 ExecutionEvent::Log {
@@ -148,405 +196,534 @@ pub fn dispatch_operation(
             image_results: Vec::new(),
             cleanup_domain: None,
             cleanup_paths: Vec::new(),
-        }
+        };
+    }
+    if let Some(failure) = disk_space::check_free_space(operation, spec) {
+        return failure;
+    }
+    let result = if let Some(hooks) = spec.policy.execution.hooks.get(operation.id.as_str()) {
+        hooks::run_with_hooks(
+            operation,
+            spec,
+            context,
+            hooks,
+            cancel_check.clone(),
+            || {
+                run_operation(
+                    operation,
+                    spec,
+                    providers,
+                    context,
+                    event_sender,
+                    cancel_check,
+                )
+            },
+        )
     } else {
-        match &operation.kind {
-            OperationKind::ResolveBuild => OperationExecutionResult::success(
-                operation.id.clone(),
-                format!("resolved build '{build_name}'"),
-            ),
-            OperationKind::MaterializeSource { source_id } => {
-                let Some(source) = spec.sources.iter().find(|source| source.id == *source_id)
-                else {
-                    return failure_with_kind(
-                        operation.id.clone(),
-                        "missing_source_spec",
-                        ExecutionErrorKind::MissingSpec,
-                        format!("missing source spec '{}'", source_id.as_str()),
-                    );
-                };
-                let Some(provider) = providers
-                    .source_catalog
-                    .find_for_kind(source.provider_kind())
-                else {
-                    return failure_with_kind(
-                        operation.id.clone(),
-                        "missing_source_provider",
-                        ExecutionErrorKind::MissingProvider,
-                        format!("missing source provider for '{}'", source_id.as_str()),
-                    );
-                };
-                let (log_tx, log_rx) = mpsc::channel::<String>();
-                let direct_sink = process_log_sink(operation.id.clone(), event_sender.clone());
-                let log_sink = direct_sink.map(|direct_sink| {
-                    std::sync::Arc::new(move |line: gaia_source_providers::ProcessLogLine| {
-                        let _ = log_tx.send(line.line.clone());
-                        direct_sink(line);
-                    }) as gaia_source_providers::ProcessLogSink
-                });
-                success_from_messages(
+        run_operation(
+            operation,
+            spec,
+            providers,
+            context,
+            event_sender,
+            cancel_check,
+        )
+    };
+    disk_space::classify_out_of_space(result, &spec.workspace.build_dir)
+}
+
+fn run_operation(
+    operation: &PlannedOperation,
+    spec: &ResolvedBuildSpec,
+    providers: &ExecutionProviders<'_>,
+    context: &ExecutionContext,
+    event_sender: Option<std::sync::mpsc::Sender<ExecutionEvent>>,
+    cancel_check: Option<gaia_process::ProcessCancelCheck>,
+) -> OperationExecutionResult {
+    match &operation.kind {
+        OperationKind::ResolveBuild => OperationExecutionResult::success(
+            operation.id.clone(),
+            format!("resolved build '{}'", context.build_name),
+        ),
+        OperationKind::MaterializeSource { source_id } => {
+            let Some(source) = spec.sources.iter().find(|source| source.id == *source_id) else {
+                return failure_with_kind(
                     operation.id.clone(),
-                    match provider.execute_source(spec, source, log_sink, cancel_check.clone()) {
-                        Ok(messages) => merge_streamed_logs(log_rx, messages),
-                        Err(message) => {
-                            let logs = merge_streamed_logs(log_rx, vec![message.message]);
-                            if matches!(
-                                message.kind,
-                                gaia_source_providers::SourceProviderErrorKind::Cancelled
-                            ) {
-                                return cancelled_with_cleanup(
-                                    operation.id.clone(),
-                                    logs.join("\n"),
-                                    RollbackDomain::Sources,
-                                    source_cleanup_paths(spec, source),
-                                );
-                            }
-                            return failure_with_cleanup_and_tail(
+                    "missing_source_spec",
+                    ExecutionErrorKind::MissingSpec,
+                    format!("missing source spec '{}'", source_id.as_str()),
+                );
+            };
+            let Some(provider) = providers
+                .source_catalog
+                .find_for_kind(source.provider_kind())
+            else {
+                return failure_with_kind(
+                    operation.id.clone(),
+                    "missing_source_provider",
+                    ExecutionErrorKind::MissingProvider,
+                    format!("missing source provider for '{}'", source_id.as_str()),
+                );
+            };
+            let (log_tx, log_rx) = mpsc::channel::<String>();
+            let direct_sink = process_log_sink(operation.id.clone(), event_sender.clone());
+            let log_sink = direct_sink.map(|direct_sink| {
+                std::sync::Arc::new(move |line: gaia_source_providers::ProcessLogLine| {
+                    let _ = log_tx.send(line.line.clone());
+                    direct_sink(line);
+                }) as gaia_source_providers::ProcessLogSink
+            });
+            success_from_messages(
+                operation.id.clone(),
+                match provider.execute_source(spec, source, log_sink, cancel_check.clone()) {
+                    Ok(messages) => merge_streamed_logs(log_rx, messages),
+                    Err(message) => {
+                        let exit = message.exit;
+                        let logs = merge_streamed_logs(log_rx, vec![message.message]);
+                        if matches!(
+                            message.kind,
+                            gaia_source_providers::SourceProviderErrorKind::Cancelled
+                        ) {
+                            return cancelled_with_cleanup(
                                 operation.id.clone(),
-                                "source_execution_failed",
-                                execution_error_kind_from_source(&message.kind),
                                 logs.join("\n"),
-                                output_tail(&logs, spec),
                                 RollbackDomain::Sources,
                                 source_cleanup_paths(spec, source),
                             );
                         }
-                    },
-                    format!("materialized source '{}'", source_id.as_str()),
-                    RollbackDomain::Sources,
-                    source_cleanup_paths(spec, source),
-                )
-            }
-            OperationKind::BuildArtifact { artifact_id } => {
-                let Some(artifact) = spec
-                    .artifacts
-                    .iter()
-                    .find(|artifact| artifact.id == *artifact_id)
-                else {
-                    return failure_with_kind(
-                        operation.id.clone(),
-                        "missing_artifact_spec",
-                        ExecutionErrorKind::MissingSpec,
-                        format!("missing artifact spec '{}'", artifact_id.as_str()),
-                    );
-                };
-                let Some(provider) = providers
-                    .artifact_catalog
-                    .find_for_kind(artifact.provider_kind())
-                else {
-                    return failure_with_kind(
-                        operation.id.clone(),
-                        "missing_artifact_provider",
-                        ExecutionErrorKind::MissingProvider,
-                        format!("missing artifact provider for '{}'", artifact_id.as_str()),
-                    );
-                };
-                let artifact_execution_policy = spec
-                    .policy
-                    .providers
-                    .artifact_command_policy(artifact.provider_kind());
-                let contract = match ArtifactExecutionContract::from_spec(
-                    artifact,
-                    resolve_artifact_source_dir(spec, artifact),
-                    matches!(artifact.definition, ArtifactDefinition::Rust(_))
-                        && spec.policy.providers.rust.allow_nested_build,
-                    artifact_execution_policy,
-                    spec.policy.execution.output_retention,
-                )
-                .try_with_build_context(spec)
-                {
-                    Ok(contract) => contract,
-                    Err(message) => {
                         return failure_with_cleanup_and_tail(
                             operation.id.clone(),
-                            "artifact_contract_invalid",
-                            execution_error_kind_from_artifact(&message.kind),
-                            message.message.clone(),
-                            output_tail(&[message.message], spec),
-                            RollbackDomain::Artifacts,
-                            Vec::new(),
-                        );
+                            "source_execution_failed",
+                            execution_error_kind_from_source(&message.kind),
+                            logs.join("\n"),
+                            output_tail(&logs, spec),
+                            RollbackDomain::Sources,
+                            source_cleanup_paths(spec, source),
+                        )
+                        .with_exit(exit);
                     }
-                };
-                let _ = process::ProcessSpec::new(format!("build:{}", artifact_id.as_str()));
-                let (log_tx, log_rx) = mpsc::channel::<String>();
-                let direct_sink = process_log_sink(operation.id.clone(), event_sender.clone());
-                let log_sink = direct_sink.map(|direct_sink| {
-                    std::sync::Arc::new(move |line: gaia_artifact_providers::ProcessLogLine| {
-                        let _ = log_tx.send(line.line.clone());
-                        direct_sink(line);
-                    }) as gaia_artifact_providers::ProcessLogSink
-                });
-                success_from_messages(
+                },
+                format!("materialized source '{}'", source_id.as_str()),
+                RollbackDomain::Sources,
+                source_cleanup_paths(spec, source),
+            )
+        }
+        OperationKind::BuildArtifact { artifact_id } => {
+            let Some(artifact) = spec
+                .artifacts
+                .iter()
+                .find(|artifact| artifact.id == *artifact_id)
+            else {
+                return failure_with_kind(
                     operation.id.clone(),
-                    match provider.execute_artifact(
-                        artifact,
-                        &contract,
-                        log_sink,
-                        cancel_check.clone(),
-                    ) {
-                        Ok(messages) => merge_streamed_logs(log_rx, messages),
+                    "missing_artifact_spec",
+                    ExecutionErrorKind::MissingSpec,
+                    format!("missing artifact spec '{}'", artifact_id.as_str()),
+                );
+            };
+            let Some(provider) = providers
+                .artifact_catalog
+                .find_for_kind(artifact.provider_kind())
+            else {
+                return failure_with_kind(
+                    operation.id.clone(),
+                    "missing_artifact_provider",
+                    ExecutionErrorKind::MissingProvider,
+                    format!("missing artifact provider for '{}'", artifact_id.as_str()),
+                );
+            };
+            let mut contract = match artifact_execution_contract(spec, artifact) {
+                Ok(contract) => contract,
+                Err(message) => {
+                    return failure_with_cleanup_and_tail(
+                        operation.id.clone(),
+                        "artifact_contract_invalid",
+                        execution_error_kind_from_artifact(&message.kind),
+                        message.message.clone(),
+                        output_tail(&[message.message], spec),
+                        RollbackDomain::Artifacts,
+                        Vec::new(),
+                    );
+                }
+            };
+            let outputs_path = artifact_outputs_path(spec, context, artifact_id);
+            if let Err(message) =
+                export_run_outputs(&mut contract.environment, context, &outputs_path)
+            {
+                return failure_with_kind(
+                    operation.id.clone(),
+                    "run_outputs_failed",
+                    ExecutionErrorKind::RuntimeState,
+                    message,
+                );
+            }
+            let _ = process::ProcessSpec::new(format!("build:{}", artifact_id.as_str()));
+            let (log_tx, log_rx) = mpsc::channel::<String>();
+            let direct_sink = process_log_sink(operation.id.clone(), event_sender.clone());
+            let log_sink = direct_sink.map(|direct_sink| {
+                std::sync::Arc::new(move |line: gaia_artifact_providers::ProcessLogLine| {
+                    let _ = log_tx.send(line.line.clone());
+                    direct_sink(line);
+                }) as gaia_artifact_providers::ProcessLogSink
+            });
+            success_from_messages(
+                operation.id.clone(),
+                match provider.execute_artifact(artifact, &contract, log_sink, cancel_check.clone())
+                {
+                    Ok(mut messages) => match publish_run_outputs(context, &outputs_path) {
+                        Ok(published) => {
+                            messages.extend(published);
+                            merge_streamed_logs(log_rx, messages)
+                        }
                         Err(message) => {
-                            let logs = merge_streamed_logs(log_rx, vec![message.message]);
-                            if matches!(
-                                message.kind,
-                                gaia_artifact_providers::ArtifactProviderErrorKind::Cancelled
-                            ) {
-                                return cancelled_with_cleanup(
-                                    operation.id.clone(),
-                                    logs.join("\n"),
-                                    RollbackDomain::Artifacts,
-                                    artifact_cleanup_paths(&contract),
-                                );
-                            }
                             return failure_with_cleanup_and_tail(
                                 operation.id.clone(),
-                                "artifact_execution_failed",
-                                execution_error_kind_from_artifact(&message.kind),
-                                logs.join("\n"),
-                                output_tail(&logs, spec),
+                                "run_outputs_invalid",
+                                ExecutionErrorKind::RuntimeState,
+                                message.clone(),
+                                output_tail(&[message], spec),
                                 RollbackDomain::Artifacts,
                                 artifact_cleanup_paths(&contract),
                             );
                         }
                     },
-                    format!("built artifact '{}'", artifact_id.as_str()),
-                    RollbackDomain::Artifacts,
-                    artifact_cleanup_paths(&contract),
-                )
-            }
-            OperationKind::InstallArtifact {
-                install_id,
-                artifact,
-            } => {
-                let _ = FsMutation::install(format!(
-                    "artifact:{} -> install:{}",
-                    artifact.id.as_str(),
-                    install_id.as_str()
-                ));
-                let install = spec
-                    .install
-                    .entries
-                    .iter()
-                    .find(|entry| entry.id == *install_id);
-                let state = gaia_spec::KeyValueState::new()
-                    .with("kind", "install")
-                    .with("install_id", install_id.as_str())
-                    .with("artifact_id", artifact.id.as_str())
-                    .with(
-                        "dest",
-                        install.map(|entry| entry.dest.as_str()).unwrap_or_default(),
-                    )
-                    .with(
-                        "replace",
-                        install.map(|entry| entry.replace).unwrap_or(false),
-                    )
-                    .with(
-                        "mode",
-                        install
-                            .and_then(|entry| entry.mode)
-                            .map(|mode| format!("{mode:o}"))
-                            .unwrap_or_default(),
-                    )
-                    .with(
-                        "owner",
-                        install
-                            .and_then(|entry| entry.owner.as_deref())
-                            .unwrap_or_default(),
-                    )
-                    .with(
-                        "group",
-                        install
-                            .and_then(|entry| entry.group.as_deref())
-                            .unwrap_or_default(),
-                    );
-                let state_path = install_state_path(spec, install_id);
-                if let Err(message) = write_runtime_state(state_path.clone(), &state) {
-                    return failure_with_cleanup(
-                        operation.id.clone(),
-                        "install_runtime_state_failed",
-                        ExecutionErrorKind::RuntimeState,
-                        message,
-                        RollbackDomain::Installs,
-                        vec![state_path],
-                    );
-                }
-                OperationExecutionResult::success(
-                    operation.id.clone(),
-                    format!(
-                        "installed artifact '{}' via '{}'",
-                        artifact.id.as_str(),
-                        install_id.as_str()
-                    ),
-                )
-                .with_cleanup_domain(RollbackDomain::Installs)
-                .with_cleanup_paths(vec![install_state_path(spec, install_id)])
-            }
-            OperationKind::RenderStageFile { item_id } => stage_result(
-                spec,
-                operation.id.clone(),
-                StageRuntimeKind::File,
-                "rendered stage file",
-                item_id,
-            ),
-            OperationKind::RenderStageEnvSet { item_id } => stage_result(
-                spec,
-                operation.id.clone(),
-                StageRuntimeKind::Env,
-                "rendered stage env set",
-                item_id,
-            ),
-            OperationKind::RenderStageService { item_id } => stage_result(
-                spec,
-                operation.id.clone(),
-                StageRuntimeKind::Service,
-                "rendered stage service",
-                item_id,
-            ),
-            OperationKind::PrepareImage | OperationKind::BuildImage => {
-                let provider_kind = spec.image.provider_kind();
-                let Some(provider) = providers.image_catalog.find_for_kind(provider_kind) else {
-                    return failure_with_kind(
-                        operation.id.clone(),
-                        "missing_image_provider",
-                        ExecutionErrorKind::MissingProvider,
-                        format!("missing image provider for '{provider_kind:?}'"),
-                    );
-                };
-                let image_plan = provider.plan_image(&spec.image);
-                let image_operation = match &operation.kind {
-                    OperationKind::PrepareImage => {
-                        gaia_image_providers::ImageProviderOperation::Prepare
-                    }
-                    OperationKind::BuildImage => {
-                        gaia_image_providers::ImageProviderOperation::Build
-                    }
-                    _ => unreachable!(),
-                };
-                let _ = process::ProcessSpec::new("build-image");
-                let (log_tx, log_rx) = mpsc::channel::<String>();
-                let direct_sink = process_log_sink(operation.id.clone(), event_sender.clone());
-                let log_sink = direct_sink.map(|direct_sink| {
-                    std::sync::Arc::new(move |line: gaia_image_providers::ProcessLogLine| {
-                        let _ = log_tx.send(line.line.clone());
-                        direct_sink(line);
-                    }) as gaia_image_providers::ProcessLogSink
-                });
-                let image_policy = image_execution_policy(spec);
-                let image_result = match provider.execute_image_operation(
-                    gaia_image_providers::ImageOperationExecution {
-                        spec,
-                        image: &spec.image,
-                        operation: image_operation,
-                        output: &image_plan.output,
-                        policy: &image_policy,
-                        log_sink,
-                        cancel_check: cancel_check.clone(),
-                    },
-                ) {
-                    Ok(mut result) => {
-                        result.messages = merge_streamed_logs(log_rx, result.messages);
-                        result
-                    }
                     Err(message) => {
+                        let _ = std::fs::remove_file(&outputs_path);
+                        let exit = message.exit;
                         let logs = merge_streamed_logs(log_rx, vec![message.message]);
                         if matches!(
                             message.kind,
-                            gaia_image_providers::ImageProviderErrorKind::Cancelled
+                            gaia_artifact_providers::ArtifactProviderErrorKind::Cancelled
                         ) {
                             return cancelled_with_cleanup(
                                 operation.id.clone(),
                                 logs.join("\n"),
-                                RollbackDomain::Images,
-                                image_definition_cleanup_paths(spec),
+                                RollbackDomain::Artifacts,
+                                artifact_cleanup_paths(&contract),
                             );
                         }
                         return failure_with_cleanup_and_tail(
                             operation.id.clone(),
-                            "image_execution_failed",
-                            execution_error_kind_from_image(&message.kind),
+                            "artifact_execution_failed",
+                            execution_error_kind_from_artifact(&message.kind),
                             logs.join("\n"),
                             output_tail(&logs, spec),
+                            RollbackDomain::Artifacts,
+                            artifact_cleanup_paths(&contract),
+                        )
+                        .with_exit(exit);
+                    }
+                },
+                format!("built artifact '{}'", artifact_id.as_str()),
+                RollbackDomain::Artifacts,
+                artifact_cleanup_paths(&contract),
+            )
+        }
+        OperationKind::InstallArtifact {
+            install_id,
+            artifact,
+        } => {
+            let _ = FsMutation::install(format!(
+                "artifact:{} -> install:{}",
+                artifact.id.as_str(),
+                install_id.as_str()
+            ));
+            let install = spec
+                .install
+                .entries
+                .iter()
+                .find(|entry| entry.id == *install_id);
+            let state = gaia_spec::KeyValueState::new()
+                .with("kind", "install")
+                .with("install_id", install_id.as_str())
+                .with("artifact_id", artifact.id.as_str())
+                .with(
+                    "dest",
+                    install.map(|entry| entry.dest.as_str()).unwrap_or_default(),
+                )
+                .with(
+                    "replace",
+                    install.map(|entry| entry.replace).unwrap_or(false),
+                )
+                .with(
+                    "mode",
+                    install
+                        .and_then(|entry| entry.mode)
+                        .map(|mode| format!("{mode:o}"))
+                        .unwrap_or_default(),
+                )
+                .with(
+                    "owner",
+                    install
+                        .and_then(|entry| entry.owner.as_deref())
+                        .unwrap_or_default(),
+                )
+                .with(
+                    "group",
+                    install
+                        .and_then(|entry| entry.group.as_deref())
+                        .unwrap_or_default(),
+                );
+            let state_path = install_state_path(spec, install_id);
+            if let Err(message) = write_runtime_state(state_path.clone(), &state) {
+                return failure_with_cleanup(
+                    operation.id.clone(),
+                    "install_runtime_state_failed",
+                    ExecutionErrorKind::RuntimeState,
+                    message,
+                    RollbackDomain::Installs,
+                    vec![state_path],
+                );
+            }
+            OperationExecutionResult::success(
+                operation.id.clone(),
+                format!(
+                    "installed artifact '{}' via '{}'",
+                    artifact.id.as_str(),
+                    install_id.as_str()
+                ),
+            )
+            .with_cleanup_domain(RollbackDomain::Installs)
+            .with_cleanup_paths(vec![install_state_path(spec, install_id)])
+        }
+        OperationKind::RenderStageFile { item_id } => stage_result(
+            spec,
+            operation.id.clone(),
+            StageRuntimeKind::File,
+            "rendered stage file",
+            item_id,
+        ),
+        OperationKind::RenderStageEnvSet { item_id } => stage_result(
+            spec,
+            operation.id.clone(),
+            StageRuntimeKind::Env,
+            "rendered stage env set",
+            item_id,
+        ),
+        OperationKind::RenderStageService { item_id } => stage_result(
+            spec,
+            operation.id.clone(),
+            StageRuntimeKind::Service,
+            "rendered stage service",
+            item_id,
+        ),
+        OperationKind::RenderStageOverlay { item_id } => stage_result(
+            spec,
+            operation.id.clone(),
+            StageRuntimeKind::Overlay,
+            "rendered stage overlay",
+            item_id,
+        ),
+        OperationKind::RenderStageSymlink { item_id } => stage_result(
+            spec,
+            operation.id.clone(),
+            StageRuntimeKind::Symlink,
+            "rendered stage symlink",
+            item_id,
+        ),
+        OperationKind::PrepareImage | OperationKind::BuildImage => {
+            let provider_kind = spec.image.provider_kind();
+            let Some(provider) = providers.image_catalog.find_for_kind(provider_kind) else {
+                return failure_with_kind(
+                    operation.id.clone(),
+                    "missing_image_provider",
+                    ExecutionErrorKind::MissingProvider,
+                    format!("missing image provider for '{provider_kind:?}'"),
+                );
+            };
+            let image_plan = provider.plan_image(&spec.image);
+            let image_operation = match &operation.kind {
+                OperationKind::PrepareImage => {
+                    gaia_image_providers::ImageProviderOperation::Prepare
+                }
+                OperationKind::BuildImage => gaia_image_providers::ImageProviderOperation::Build,
+                _ => unreachable!(),
+            };
+            let _ = process::ProcessSpec::new("build-image");
+            let (log_tx, log_rx) = mpsc::channel::<String>();
+            let direct_sink = process_log_sink(operation.id.clone(), event_sender.clone());
+            let log_sink = direct_sink.map(|direct_sink| {
+                std::sync::Arc::new(move |line: gaia_image_providers::ProcessLogLine| {
+                    let _ = log_tx.send(line.line.clone());
+                    direct_sink(line);
+                }) as gaia_image_providers::ProcessLogSink
+            });
+            let image_policy = image_execution_policy(spec);
+            let image_result = match provider.execute_image_operation(
+                gaia_image_providers::ImageOperationExecution {
+                    spec,
+                    image: &spec.image,
+                    operation: image_operation,
+                    output: &image_plan.output,
+                    policy: &image_policy,
+                    log_sink,
+                    progress_sink: process_progress_sink(
+                        operation.id.clone(),
+                        event_sender.clone(),
+                    ),
+                    cancel_check: cancel_check.clone(),
+                },
+            ) {
+                Ok(mut result) => {
+                    result.messages = merge_streamed_logs(log_rx, result.messages);
+                    result
+                }
+                Err(message) => {
+                    let exit = message.exit;
+                    let logs = merge_streamed_logs(log_rx, vec![message.message]);
+                    if matches!(
+                        message.kind,
+                        gaia_image_providers::ImageProviderErrorKind::Cancelled
+                    ) {
+                        return cancelled_with_cleanup(
+                            operation.id.clone(),
+                            logs.join("\n"),
                             RollbackDomain::Images,
                             image_definition_cleanup_paths(spec),
                         );
                     }
-                };
-                let image_cleanup = image_cleanup_paths(&image_result);
-                success_from_messages(
-                    operation.id.clone(),
-                    image_result.messages.clone(),
-                    match &operation.kind {
-                        OperationKind::PrepareImage => "prepared image base".into(),
-                        OperationKind::BuildImage => "built image".into(),
-                        _ => unreachable!(),
-                    },
-                    RollbackDomain::Images,
-                    image_cleanup,
-                )
-                .with_image_result(image_result)
-            }
-            OperationKind::CaptureCheckpoint { checkpoint_id } => {
-                let checkpoint = spec
-                    .checkpoints
-                    .points
-                    .iter()
-                    .find(|checkpoint| checkpoint.id == *checkpoint_id);
-                let state = gaia_spec::KeyValueState::new()
-                    .with("kind", "checkpoint")
-                    .with("checkpoint_id", checkpoint_id.as_str())
-                    .with(
-                        "backend",
-                        checkpoint
-                            .and_then(|checkpoint| checkpoint.backend.as_ref())
-                            .map(|backend| backend.backend.as_str())
-                            .unwrap_or_default(),
-                    )
-                    .with(
-                        "anchor",
-                        checkpoint
-                            .map(|checkpoint| checkpoint.anchor.as_str())
-                            .unwrap_or_else(|| "image".to_string()),
+                    return failure_with_cleanup_and_tail(
+                        operation.id.clone(),
+                        "image_execution_failed",
+                        execution_error_kind_from_image(&message.kind),
+                        logs.join("\n"),
+                        output_tail(&logs, spec),
+                        RollbackDomain::Images,
+                        image_definition_cleanup_paths(spec),
                     )
-                    .with(
-                        "use_policy",
-                        format!(
-                            "{:?}",
+                    .with_exit(exit);
+                }
+            };
+            let image_cleanup = image_cleanup_paths(spec, &image_result);
+            success_from_messages(
+                operation.id.clone(),
+                image_result.messages.clone(),
+                match &operation.kind {
+                    OperationKind::PrepareImage => "prepared image base".into(),
+                    OperationKind::BuildImage => "built image".into(),
+                    _ => unreachable!(),
+                },
+                RollbackDomain::Images,
+                image_cleanup,
+            )
+            .with_image_result(image_result)
+        }
+        OperationKind::CaptureCheckpoint { checkpoint_id } => {
+            let checkpoint = spec
+                .checkpoints
+                .points
+                .iter()
+                .find(|checkpoint| checkpoint.id == *checkpoint_id);
+            let state = gaia_spec::KeyValueState::new()
+                .with("kind", "checkpoint")
+                .with("checkpoint_id", checkpoint_id.as_str())
+                .with(
+                    "backend",
+                    checkpoint
+                        .and_then(|checkpoint| checkpoint.backend.as_ref())
+                        .map(|backend| backend.backend.as_str())
+                        .unwrap_or_default(),
+                )
+                .with(
+                    "restore_backends",
+                    checkpoint
+                        .map(|checkpoint| {
                             checkpoint
-                                .map(|checkpoint| checkpoint.use_policy)
-                                .unwrap_or_default()
-                        ),
-                    )
-                    .with(
-                        "upload_policy",
-                        format!(
-                            "{:?}",
+                                .restore_backends()
+                                .map(|backend| backend.backend.as_str())
+                                .collect::<Vec<_>>()
+                                .join(",")
+                        })
+                        .unwrap_or_default(),
+                )
+                .with(
+                    "upload_backends",
+                    checkpoint
+                        .map(|checkpoint| {
                             checkpoint
-                                .map(|checkpoint| checkpoint.upload_policy)
-                                .unwrap_or_default()
-                        ),
-                    );
-                let state_path = checkpoint_state_path(spec, checkpoint_id);
-                if let Err(message) = write_runtime_state(state_path.clone(), &state) {
-                    return failure_with_cleanup(
-                        operation.id.clone(),
-                        "checkpoint_runtime_state_failed",
-                        ExecutionErrorKind::RuntimeState,
-                        message,
-                        RollbackDomain::Checkpoints,
-                        vec![state_path],
-                    );
-                }
-                OperationExecutionResult::success(
-                    operation.id.clone(),
-                    format!("captured checkpoint '{}'", checkpoint_id.as_str()),
+                                .upload_targets()
+                                .iter()
+                                .map(|backend| backend.backend.as_str())
+                                .collect::<Vec<_>>()
+                                .join(",")
+                        })
+                        .unwrap_or_default(),
                 )
-                .with_cleanup_domain(RollbackDomain::Checkpoints)
-                .with_cleanup_paths(vec![checkpoint_state_path(spec, checkpoint_id)])
-            }
-            OperationKind::EmitReport => {
-                OperationExecutionResult::success(operation.id.clone(), "emitted report".into())
+                .with("run_id", context.run_id.as_str())
+                .with(
+                    "anchor",
+                    checkpoint
+                        .map(|checkpoint| checkpoint.anchor.as_str())
+                        .unwrap_or_else(|| "image".to_string()),
+                )
+                .with(
+                    "anchor_outputs",
+                    checkpoint
+                        .map(|checkpoint| {
+                            checkpoint_anchor_outputs(spec, &checkpoint.anchor)
+                                .iter()
+                                .map(|path| path.display().to_string())
+                                .collect::<Vec<_>>()
+                                .join(",")
+                        })
+                        .unwrap_or_default(),
+                )
+                .with(
+                    "anchor_layout",
+                    checkpoint
+                        .map(|checkpoint| {
+                            checkpoint_anchor_layout(
+                                spec,
+                                &checkpoint_anchor_outputs(spec, &checkpoint.anchor),
+                            )
+                        })
+                        .unwrap_or_default(),
+                )
+                .with(
+                    "restore_root",
+                    checkpoint
+                        .and_then(|checkpoint| checkpoint.restore_root(&spec.workspace).ok())
+                        .map(|root| root.display().to_string())
+                        .unwrap_or_default(),
+                )
+                .with(
+                    "use_policy",
+                    format!(
+                        "{:?}",
+                        checkpoint
+                            .map(|checkpoint| checkpoint.use_policy)
+                            .unwrap_or_default()
+                    ),
+                )
+                .with(
+                    "upload_policy",
+                    format!(
+                        "{:?}",
+                        checkpoint
+                            .map(|checkpoint| checkpoint.upload_policy)
+                            .unwrap_or_default()
+                    ),
+                );
+            let state_path = checkpoint_state_path(spec, checkpoint_id);
+            if let Err(message) = write_runtime_state(state_path.clone(), &state) {
+                return failure_with_cleanup(
+                    operation.id.clone(),
+                    "checkpoint_runtime_state_failed",
+                    ExecutionErrorKind::RuntimeState,
+                    message,
+                    RollbackDomain::Checkpoints,
+                    vec![state_path],
+                );
             }
+            OperationExecutionResult::success(
+                operation.id.clone(),
+                format!("captured checkpoint '{}'", checkpoint_id.as_str()),
+            )
+            .with_cleanup_domain(RollbackDomain::Checkpoints)
+            .with_cleanup_paths(vec![checkpoint_state_path(spec, checkpoint_id)])
+        }
+        OperationKind::EmitReport => {
+            OperationExecutionResult::success(operation.id.clone(), "emitted report".into())
         }
     }
 }
@@ -1,42 +1,100 @@
 use gaia_image_providers::ImageExecutionResult;
 use gaia_plan::OperationId;
-use gaia_process::{ProcessLogLine, ProcessLogSink};
+use gaia_process::{ProcessLogLine, ProcessLogSink, ProcessProgressSink};
 use gaia_spec::{ResolvedBuildSpec, RollbackDomain};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
+use std::hash::{BuildHasher, RandomState};
 use std::path::PathBuf;
 use std::sync::mpsc::Sender;
 use std::sync::{
-    Arc,
+    Arc, Mutex,
     atomic::{AtomicBool, Ordering},
 };
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
 use crate::{ExecutionCleanupStatus, ExecutionError, ExecutionEvent, OperationExecutionResult};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub struct ExecutionContext {
     pub build_name: String,
+    // Unique per invocation, unlike `spec.identity.id`, which is the same for every run of a
+    // build; ties log lines, checkpoint state, and reports of one run together.
+    pub run_id: String,
+    // Key/value results operations publish for later operations of the same run. Clones share
+    // the map, so parallel workers see each other's writes; it is never persisted, and a reused
+    // operation publishes nothing.
+    outputs: Arc<Mutex<BTreeMap<String, String>>>,
 }
 
 impl ExecutionContext {
     pub fn new(spec: &ResolvedBuildSpec) -> Self {
         Self {
             build_name: spec.identity.display_name.clone(),
+            run_id: new_run_id(),
+            outputs: Arc::default(),
         }
     }
+
+    pub fn set_output(&self, key: impl Into<String>, value: impl Into<String>) {
+        self.lock_outputs().insert(key.into(), value.into());
+    }
+
+    pub fn get_output(&self, key: &str) -> Option<String> {
+        self.lock_outputs().get(key).cloned()
+    }
+
+    pub fn outputs(&self) -> BTreeMap<String, String> {
+        self.lock_outputs().clone()
+    }
+
+    // A worker that panicked mid-insert cannot leave the map half-written, so a poisoned lock
+    // is still safe to read.
+    fn lock_outputs(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, String>> {
+        self.outputs
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+// `<unix millis>-<8 random hex digits>`: sorts by start time and stays unique across runs that
+// start in the same millisecond.
+fn new_run_id() -> String {
+    let started_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis())
+        .unwrap_or_default();
+    let random = RandomState::new().hash_one(started_ms) as u32;
+    format!("{started_ms}-{random:08x}")
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct ExecutionOutcome {
+    // `None` when nothing was executed, e.g. for a run refused by validation.
+    pub run_id: Option<String>,
     pub completed_operations: usize,
     pub completed_ids: Vec<OperationId>,
     pub reused_ids: Vec<OperationId>,
     pub rolled_back_ids: Vec<OperationId>,
+    pub skipped_ids: Vec<OperationId>,
     pub cancelled: bool,
     pub cancelled_operation_id: Option<OperationId>,
     pub image_results: Vec<ImageExecutionResult>,
+    // Run outputs as published when the run ended.
+    pub outputs: BTreeMap<String, String>,
     pub events: Vec<ExecutionEvent>,
     pub errors: Vec<ExecutionError>,
     pub cleanup_failures: Vec<CleanupFailure>,
+    // Start and finish of every operation that started, in finish order.
+    pub spans: Vec<OperationSpan>,
+}
+
+// Offsets from the start of the run. Reused and skipped operations never start, so have none.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperationSpan {
+    pub operation_id: OperationId,
+    pub started_ms: u64,
+    pub finished_ms: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -70,15 +128,24 @@ pub struct ExecutionRuntime {
     outcome: ExecutionOutcome,
     cleanup_stack: Vec<(OperationId, Option<RollbackDomain>, Vec<PathBuf>)>,
     event_sender: Option<Sender<ExecutionEvent>>,
+    started_at: HashMap<OperationId, Instant>,
+    elapsed_ms: Vec<(OperationId, u64)>,
+    run_started_at: Instant,
 }
 
 impl ExecutionRuntime {
     pub fn new(context: ExecutionContext, event_sender: Option<Sender<ExecutionEvent>>) -> Self {
         Self {
+            outcome: ExecutionOutcome {
+                run_id: Some(context.run_id.clone()),
+                ..ExecutionOutcome::default()
+            },
             context,
-            outcome: ExecutionOutcome::default(),
             cleanup_stack: Vec::new(),
             event_sender,
+            started_at: HashMap::new(),
+            elapsed_ms: Vec::new(),
+            run_started_at: Instant::now(),
         }
     }
 
@@ -116,12 +183,60 @@ impl ExecutionRuntime {
     }
 
     pub fn emit_event(&mut self, event: ExecutionEvent) {
+        match &event {
+            ExecutionEvent::Started { operation_id } => {
+                self.started_at.insert(operation_id.clone(), Instant::now());
+            }
+            ExecutionEvent::Succeeded { operation_id }
+            | ExecutionEvent::Failed { operation_id, .. }
+            | ExecutionEvent::Cancelled { operation_id } => {
+                if let Some(started_at) = self.started_at.remove(operation_id) {
+                    let since_run_start = |instant: Instant| {
+                        u64::try_from(instant.duration_since(self.run_started_at).as_millis())
+                            .unwrap_or(u64::MAX)
+                    };
+                    let span = OperationSpan {
+                        operation_id: operation_id.clone(),
+                        started_ms: since_run_start(started_at),
+                        finished_ms: since_run_start(Instant::now()),
+                    };
+                    if matches!(event, ExecutionEvent::Succeeded { .. }) {
+                        self.elapsed_ms
+                            .push((operation_id.clone(), span.finished_ms - span.started_ms));
+                    }
+                    self.outcome.spans.push(span);
+                }
+            }
+            _ => {}
+        }
         if let Some(sender) = &self.event_sender {
             let _ = sender.send(event.clone());
         }
         self.outcome.events.push(event);
     }
 
+    pub fn emit_run_finished(&mut self) {
+        self.emit_event(ExecutionEvent::RunFinished {
+            completed: self.outcome.completed_operations,
+            failed: self.outcome.errors.len(),
+            skipped: self.outcome.skipped_ids.len(),
+            cancelled: self.outcome.cancelled,
+        });
+    }
+
+    pub fn skip(&mut self, operation_id: OperationId, failed_dependency: OperationId) {
+        self.outcome.skipped_ids.push(operation_id.clone());
+        self.emit_event(ExecutionEvent::Skipped {
+            operation_id,
+            failed_dependency,
+        });
+    }
+
+    // Run times of the operations that started and succeeded in this run, in completion order.
+    pub fn elapsed_ms(&self) -> &[(OperationId, u64)] {
+        &self.elapsed_ms
+    }
+
     pub fn event_sender(&self) -> Option<Sender<ExecutionEvent>> {
         self.event_sender.clone()
     }
@@ -262,7 +377,8 @@ impl ExecutionRuntime {
         }
     }
 
-    pub fn finish(self) -> ExecutionOutcome {
+    pub fn finish(mut self) -> ExecutionOutcome {
+        self.outcome.outputs = self.context.outputs();
         self.outcome
     }
 
@@ -301,6 +417,20 @@ pub fn process_log_sink(
     })
 }
 
+pub fn process_progress_sink(
+    operation_id: OperationId,
+    sender: Option<Sender<ExecutionEvent>>,
+) -> Option<ProcessProgressSink> {
+    sender.map(|sender| {
+        std::sync::Arc::new(move |percent: u8| {
+            let _ = sender.send(ExecutionEvent::Progress {
+                operation_id: operation_id.clone(),
+                percent: percent.min(100),
+            });
+        }) as ProcessProgressSink
+    })
+}
+
 fn cleanup_paths(operation_id: &OperationId, paths: &[PathBuf]) -> Vec<CleanupFailure> {
     let mut failures = Vec::new();
     for path in paths {
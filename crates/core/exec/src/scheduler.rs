@@ -12,13 +12,21 @@ use gaia_spec::{ArtifactDefinition, ResolvedBuildSpec};
 
 use crate::ExecutionProviders;
 use crate::operations::{ExecutionEvent, OperationExecutionResult, dispatch_operation};
-use crate::runtime::ExecutionRuntime;
+use crate::runtime::{ExecutionContext, ExecutionRuntime};
+
+const STALL_REPORT_LIMIT: usize = 10;
+
+// What a worker thread hands back to the executor loop when its operation ends.
+pub(crate) struct OperationCompletion {
+    pub(crate) index: usize,
+    pub(crate) result: OperationExecutionResult,
+}
 
 pub(crate) struct ScheduleReadyContext<'env> {
     pub(crate) spec: &'env ResolvedBuildSpec,
     pub(crate) plan: &'env ExecutionPlan,
     pub(crate) providers: &'env ExecutionProviders<'env>,
-    pub(crate) build_name: &'env str,
+    pub(crate) execution: &'env ExecutionContext,
     pub(crate) event_sender: Option<Sender<ExecutionEvent>>,
     pub(crate) cancel_check: ProcessCancelCheck,
     pub(crate) max_parallel_jobs: usize,
@@ -27,44 +35,30 @@ pub(crate) struct ScheduleReadyContext<'env> {
 pub(crate) struct ScheduleReadyState<'a> {
     pub(crate) remaining_dependencies: &'a [usize],
     pub(crate) completed: &'a [bool],
+    pub(crate) failed: &'a [bool],
     pub(crate) running: &'a mut [bool],
     pub(crate) running_count: &'a mut usize,
 }
 
 pub(crate) fn schedule_ready_operations<'scope, 'env>(
     scope: &'scope thread::Scope<'scope, 'env>,
-    result_tx: &std::sync::mpsc::Sender<(usize, OperationExecutionResult)>,
+    result_tx: &std::sync::mpsc::Sender<OperationCompletion>,
     runtime: &mut ExecutionRuntime,
     context: &ScheduleReadyContext<'env>,
     state: ScheduleReadyState<'_>,
 ) -> bool {
-    let mut scheduled_any = false;
     let spec = context.spec;
     let plan = context.plan;
     let providers = context.providers;
-    let build_name = context.build_name;
-    let event_sender = &context.event_sender;
-    let cancel_check = &context.cancel_check;
+    let execution = context.execution;
     let max_parallel_jobs = context.max_parallel_jobs;
-    let ScheduleReadyState {
-        remaining_dependencies,
-        completed,
-        running,
-        running_count,
-    } = state;
-    loop {
-        if *running_count >= max_parallel_jobs {
-            break;
-        }
-        let Some(index) =
-            next_schedulable_operation(spec, plan, remaining_dependencies, completed, running)
-        else {
-            break;
-        };
+    let running_before = *state.running_count;
+    let ready = take_ready_operations(spec, plan, state, max_parallel_jobs);
+    for (position, &index) in ready.iter().enumerate() {
         let operation = &plan.operations[index];
         let tx = result_tx.clone();
-        let operation_event_sender = event_sender.clone();
-        let operation_cancel_check = cancel_check.clone();
+        let operation_event_sender = context.event_sender.clone();
+        let operation_cancel_check = context.cancel_check.clone();
         runtime.emit_event(ExecutionEvent::Started {
             operation_id: operation.id.clone(),
         });
@@ -73,24 +67,56 @@ pub(crate) fn schedule_ready_operations<'scope, 'env>(
             operation_kind = ?operation.kind,
             parallelism_mode = ?operation.parallelism.mode,
             parallelism_domain = ?operation.parallelism.domain,
-            running_operations = *running_count,
+            running_operations = running_before + position,
             max_parallel_jobs,
             "operation started"
         );
-        running[index] = true;
-        *running_count += 1;
         scope.spawn(move || {
             let result = dispatch_operation(
                 operation,
                 spec,
                 providers,
-                build_name,
+                execution,
                 operation_event_sender,
                 Some(operation_cancel_check),
             );
-            let _ = tx.send((index, result));
+            let _ = tx.send(OperationCompletion { index, result });
         });
-        scheduled_any = true;
+    }
+    !ready.is_empty()
+}
+
+// Picks the operations to start now, in start order, and marks them running. Filling stops at
+// `max_parallel_jobs` or after an operation that has to run alone.
+pub(crate) fn take_ready_operations(
+    spec: &ResolvedBuildSpec,
+    plan: &ExecutionPlan,
+    state: ScheduleReadyState<'_>,
+    max_parallel_jobs: usize,
+) -> Vec<usize> {
+    let ScheduleReadyState {
+        remaining_dependencies,
+        completed,
+        failed,
+        running,
+        running_count,
+    } = state;
+    let mut ready = Vec::new();
+    while *running_count < max_parallel_jobs {
+        let Some(index) = next_schedulable_operation(
+            spec,
+            plan,
+            remaining_dependencies,
+            completed,
+            failed,
+            running,
+        ) else {
+            break;
+        };
+        running[index] = true;
+        *running_count += 1;
+        ready.push(index);
+        let operation = &plan.operations[index];
         if !supports_parallel_runtime(
             operation.parallelism.mode.clone(),
             &operation.parallelism.domain,
@@ -98,7 +124,81 @@ pub(crate) fn schedule_ready_operations<'scope, 'env>(
             break;
         }
     }
-    scheduled_any
+    ready
+}
+
+// Ready counts operations whose dependencies all finished but that have not started, because
+// every job slot is taken or an exclusive operation is running.
+pub(crate) fn scheduler_tick(
+    remaining_dependencies: &[usize],
+    completed: &[bool],
+    running: &[bool],
+    failed: &[bool],
+) -> ExecutionEvent {
+    let ready = (0..completed.len())
+        .filter(|&index| {
+            remaining_dependencies[index] == 0
+                && !completed[index]
+                && !running[index]
+                && !failed[index]
+        })
+        .count();
+    ExecutionEvent::SchedulerTick {
+        running: running.iter().filter(|running| **running).count(),
+        ready,
+        completed: completed.iter().filter(|completed| **completed).count(),
+        total: completed.len(),
+    }
+}
+
+// What the executor loop is waiting on when no operation has finished for a while: the running
+// operations, the ready ones that have not started, and how many dependencies each blocked one
+// still waits for. A stall with nothing running but work left over points at a scheduling bug.
+pub(crate) fn stall_report(
+    plan: &ExecutionPlan,
+    remaining_dependencies: &[usize],
+    completed: &[bool],
+    running: &[bool],
+    failed: &[bool],
+) -> String {
+    let pending = |index: usize| !completed[index] && !running[index] && !failed[index];
+    let list = |items: Vec<String>| {
+        if items.is_empty() {
+            "none".to_string()
+        } else if items.len() > STALL_REPORT_LIMIT {
+            let more = items.len() - STALL_REPORT_LIMIT;
+            format!(
+                "{}, and {more} more",
+                items[..STALL_REPORT_LIMIT].join(", ")
+            )
+        } else {
+            items.join(", ")
+        }
+    };
+    let ids = |keep: &dyn Fn(usize) -> bool| {
+        (0..plan.operations.len())
+            .filter(|&index| keep(index))
+            .map(|index| plan.operations[index].id.as_str().to_string())
+            .collect::<Vec<_>>()
+    };
+    let blocked = (0..plan.operations.len())
+        .filter(|&index| pending(index) && remaining_dependencies[index] > 0)
+        .map(|index| {
+            format!(
+                "{} ({})",
+                plan.operations[index].id.as_str(),
+                remaining_dependencies[index]
+            )
+        })
+        .collect::<Vec<_>>();
+    format!(
+        "running: {}; ready: {}; blocked (dependencies left): {}",
+        list(ids(&|index| running[index])),
+        list(ids(
+            &|index| pending(index) && remaining_dependencies[index] == 0
+        )),
+        list(blocked)
+    )
 }
 
 pub(crate) fn resolve_parallel_jobs(spec: &ResolvedBuildSpec) -> usize {
@@ -114,11 +214,40 @@ pub(crate) fn resolve_parallel_jobs(spec: &ResolvedBuildSpec) -> usize {
     }
 }
 
+// Each skipped operation names the first failed operation, in plan order, that it transitively
+// depends on.
+pub(crate) fn skipped_by_failures(
+    failed: &[bool],
+    completed: &[bool],
+    dependents: &[Vec<usize>],
+) -> Vec<(usize, usize)> {
+    let mut failed_dependency = vec![None; failed.len()];
+    for (origin, _) in failed.iter().enumerate().filter(|(_, failed)| **failed) {
+        let mut pending = dependents[origin].clone();
+        while let Some(index) = pending.pop() {
+            if failed_dependency[index].is_some() || failed[index] || completed[index] {
+                continue;
+            }
+            failed_dependency[index] = Some(origin);
+            pending.extend(dependents[index].iter().copied());
+        }
+    }
+    failed_dependency
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, origin)| origin.map(|origin| (index, origin)))
+        .collect()
+}
+
+// Always picks the lowest plan index among ready operations, so the spawn sequence depends only on
+// the order completions are received, never on map iteration or thread timing. A failed operation
+// is never picked again; without fail-fast its dependents are skipped instead.
 fn next_schedulable_operation(
     spec: &ResolvedBuildSpec,
     plan: &ExecutionPlan,
     remaining_dependencies: &[usize],
     completed: &[bool],
+    failed: &[bool],
     running: &[bool],
 ) -> Option<usize> {
     let mut ready_parallel = Vec::new();
@@ -140,7 +269,7 @@ fn next_schedulable_operation(
             }
             continue;
         }
-        if completed[index] || remaining_dependencies[index] != 0 {
+        if completed[index] || failed[index] || remaining_dependencies[index] != 0 {
             continue;
         }
         match operation.parallelism.mode {
@@ -228,6 +357,9 @@ enum ParallelResourceKey {
     Checkpoint {
         checkpoint_id: String,
     },
+    MutexGroup {
+        name: String,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -266,6 +398,8 @@ enum RuntimeDestinationKind {
     Install,
     StageFile,
     StageService,
+    StageOverlay,
+    StageSymlink,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -294,6 +428,11 @@ fn operation_parallel_resource_keys(
             let mut keys = vec![ParallelResourceKey::ArtifactOutput {
                 path: ResourcePath::host(&spec.workspace.root_dir, &artifact.output.path),
             }];
+            if let Some(group) = &artifact.mutex_group {
+                keys.push(ParallelResourceKey::MutexGroup {
+                    name: group.clone(),
+                });
+            }
             let Some(source_dir) = resolve_artifact_parallel_source_dir(spec, artifact) else {
                 return keys;
             };
@@ -378,6 +517,34 @@ fn operation_parallel_resource_keys(
                 path: ResourcePath::virtual_path(&service.unit_path),
             }]
         }
+        gaia_plan::OperationKind::RenderStageOverlay { item_id } => {
+            let Some(overlay) = spec
+                .stage
+                .overlays
+                .iter()
+                .find(|overlay| overlay.id == *item_id)
+            else {
+                return Vec::new();
+            };
+            vec![ParallelResourceKey::RuntimeDestination {
+                kind: RuntimeDestinationKind::StageOverlay,
+                path: ResourcePath::virtual_path(&overlay.dest),
+            }]
+        }
+        gaia_plan::OperationKind::RenderStageSymlink { item_id } => {
+            let Some(symlink) = spec
+                .stage
+                .symlinks
+                .iter()
+                .find(|symlink| symlink.id == *item_id)
+            else {
+                return Vec::new();
+            };
+            vec![ParallelResourceKey::RuntimeDestination {
+                kind: RuntimeDestinationKind::StageSymlink,
+                path: ResourcePath::virtual_path(&symlink.link),
+            }]
+        }
         gaia_plan::OperationKind::PrepareImage => vec![ParallelResourceKey::ImageWorkspace],
         gaia_plan::OperationKind::BuildImage => {
             let mut keys = vec![ParallelResourceKey::ImageWorkspace];
@@ -414,25 +581,10 @@ fn resolve_artifact_parallel_source_dir(
         .sources
         .iter()
         .find(|source| source.id == source_ref.id)?;
-    match &source.definition {
-        gaia_spec::SourceDefinition::Path(path) => {
-            let candidate = std::path::PathBuf::from(&path.path);
-            let resolved = if candidate.is_absolute() {
-                candidate
-            } else {
-                std::path::PathBuf::from(&spec.workspace.root_dir).join(candidate)
-            };
-            Some(ResourcePath::host(&spec.workspace.root_dir, resolved))
-        }
-        gaia_spec::SourceDefinition::Git(_)
-        | gaia_spec::SourceDefinition::Archive(_)
-        | gaia_spec::SourceDefinition::Download(_) => Some(ResourcePath::host(
-            &spec.workspace.root_dir,
-            PathBuf::from(&spec.workspace.build_dir)
-                .join("sources")
-                .join(source.id.as_str()),
-        )),
-    }
+    Some(ResourcePath::host(
+        &spec.workspace.root_dir,
+        source.materialized_path(&spec.workspace),
+    ))
 }
 
 pub(crate) fn next_pending_operation_id(
@@ -5,7 +5,7 @@ use gaia_spec::{
     ArtifactVariantSpec, BuildrootImageSpec, CheckpointId, ImageDefinition, ImageSpec,
     InstallEntrySpec, InstallId, NodeArtifactSpec, PathSourceSpec, RustArtifactSpec,
     SourceDefinition, SourcePinPolicySpec, SourceRef, SourceRefreshPolicySpec, SourceSpec,
-    StageContentOriginSpec, StageFileSpec, StageItemId, StageServiceSpec,
+    StageFileSpec, StageItemId, StageServiceSpec,
 };
 
 fn parallel_operation(
@@ -45,6 +45,8 @@ fn rust_artifact(id: &str, source: &str, output: &str) -> ArtifactSpec {
             package: id.into(),
             target_name: None,
             variant: ArtifactVariantSpec::File,
+            linker: None,
+            sysroot: None,
         }),
         Some(SourceRef::new(source)),
         ArtifactOutputSpec {
@@ -68,7 +70,14 @@ fn node_artifact(id: &str, source: &str, package_dir: &str, output: &str) -> Art
 
 fn assert_second_operation_blocked(spec: &ResolvedBuildSpec, plan: &ExecutionPlan) {
     assert_eq!(
-        next_schedulable_operation(spec, plan, &[0, 0], &[false, false], &[true, false]),
+        next_schedulable_operation(
+            spec,
+            plan,
+            &[0, 0],
+            &[false, false],
+            &[false, false],
+            &[true, false]
+        ),
         None
     );
 }
@@ -77,18 +86,8 @@ fn assert_second_operation_blocked(spec: &ResolvedBuildSpec, plan: &ExecutionPla
 fn scheduler_blocks_parallel_stage_files_with_same_destination() {
     let mut spec = ResolvedBuildSpec::new("parallel-resource-test");
     spec.stage.files = vec![
-        StageFileSpec {
-            id: StageItemId::new("motd-a"),
-            src: "a".into(),
-            dest: "/etc/motd".into(),
-            origin: StageContentOriginSpec::StaticAsset,
-        },
-        StageFileSpec {
-            id: StageItemId::new("motd-b"),
-            src: "b".into(),
-            dest: "/etc/motd".into(),
-            origin: StageContentOriginSpec::StaticAsset,
-        },
+        StageFileSpec::static_asset(StageItemId::new("motd-a"), "a", "/etc/motd"),
+        StageFileSpec::static_asset(StageItemId::new("motd-b"), "b", "/etc/motd"),
     ];
     let plan = ExecutionPlan {
         build_id: spec.identity.id.clone(),
@@ -115,18 +114,8 @@ fn scheduler_blocks_parallel_stage_files_with_same_destination() {
 fn scheduler_allows_parallel_stage_files_with_different_destinations() {
     let mut spec = ResolvedBuildSpec::new("parallel-resource-test");
     spec.stage.files = vec![
-        StageFileSpec {
-            id: StageItemId::new("motd"),
-            src: "a".into(),
-            dest: "/etc/motd".into(),
-            origin: StageContentOriginSpec::StaticAsset,
-        },
-        StageFileSpec {
-            id: StageItemId::new("issue"),
-            src: "b".into(),
-            dest: "/etc/issue".into(),
-            origin: StageContentOriginSpec::StaticAsset,
-        },
+        StageFileSpec::static_asset(StageItemId::new("motd"), "a", "/etc/motd"),
+        StageFileSpec::static_asset(StageItemId::new("issue"), "b", "/etc/issue"),
     ];
     let plan = ExecutionPlan {
         build_id: spec.identity.id.clone(),
@@ -147,7 +136,14 @@ fn scheduler_allows_parallel_stage_files_with_different_destinations() {
     };
 
     assert_eq!(
-        next_schedulable_operation(&spec, &plan, &[0, 0], &[false, false], &[true, false]),
+        next_schedulable_operation(
+            &spec,
+            &plan,
+            &[0, 0],
+            &[false, false],
+            &[false, false],
+            &[true, false]
+        ),
         Some(1)
     );
 }
@@ -240,7 +236,14 @@ fn scheduler_allows_parallel_artifacts_with_different_build_inputs() {
     };
 
     assert_eq!(
-        next_schedulable_operation(&spec, &plan, &[0, 0], &[false, false], &[true, false]),
+        next_schedulable_operation(
+            &spec,
+            &plan,
+            &[0, 0],
+            &[false, false],
+            &[false, false],
+            &[true, false]
+        ),
         Some(1)
     );
 }
@@ -375,3 +378,231 @@ fn scheduler_blocks_parallel_checkpoints_with_same_id() {
 
     assert_second_operation_blocked(&spec, &plan);
 }
+
+// Mirrors the executor loop: fill free slots from `next_schedulable_operation`, then receive one
+// completion at a time. Completions are ordered by fake finish time, ties by plan index.
+#[test]
+fn scheduler_serializes_artifacts_in_the_same_mutex_group() {
+    let mut spec = ResolvedBuildSpec::new("mutex-group-test");
+    spec.sources = vec![path_source("workspace", "workspace")];
+    spec.artifacts = ["server-a", "server-b", "client"]
+        .into_iter()
+        .map(|id| {
+            node_artifact(
+                id,
+                "workspace",
+                &format!("packages/{id}"),
+                &format!("out/{id}.tgz"),
+            )
+        })
+        .collect();
+    spec.artifacts[0].mutex_group = Some("port-8080".into());
+    spec.artifacts[1].mutex_group = Some("port-8080".into());
+    spec.artifacts[2].mutex_group = Some("port-9090".into());
+    let plan = ExecutionPlan {
+        build_id: spec.identity.id.clone(),
+        operations: spec
+            .artifacts
+            .iter()
+            .map(|artifact| {
+                parallel_artifact_operation(
+                    OperationId::artifact(&artifact.id),
+                    OperationKind::BuildArtifact {
+                        artifact_id: artifact.id.clone(),
+                    },
+                )
+            })
+            .collect(),
+    };
+
+    let schedule = simulated_schedule(&spec, &plan, &[10, 10, 10], 3);
+    let window = |id: &str| {
+        schedule
+            .iter()
+            .find(|(operation_id, _)| *operation_id == OperationId::artifact(&ArtifactId::new(id)))
+            .map(|(_, window)| *window)
+            .expect("scheduled artifact")
+    };
+    let (a_start, a_end) = window("server-a");
+    let (b_start, b_end) = window("server-b");
+    assert!(
+        a_end <= b_start || b_end <= a_start,
+        "server-a {a_start}..{a_end} overlaps server-b {b_start}..{b_end}"
+    );
+    assert_eq!(window("client").0, 0);
+    assert_eq!(schedule.len(), 3);
+}
+
+fn simulated_spawn_order(
+    spec: &ResolvedBuildSpec,
+    plan: &ExecutionPlan,
+    durations: &[u64],
+    max_parallel_jobs: usize,
+) -> Vec<OperationId> {
+    simulated_schedule(spec, plan, durations, max_parallel_jobs)
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect()
+}
+
+// Returns each operation in spawn order with its simulated (start, finish) window.
+fn simulated_schedule(
+    spec: &ResolvedBuildSpec,
+    plan: &ExecutionPlan,
+    durations: &[u64],
+    max_parallel_jobs: usize,
+) -> Vec<(OperationId, (u64, u64))> {
+    let count = plan.operations.len();
+    let mut remaining_dependencies = plan
+        .operations
+        .iter()
+        .map(|operation| operation.depends_on.len())
+        .collect::<Vec<_>>();
+    let mut completed = vec![false; count];
+    let failed = vec![false; count];
+    let mut running = vec![false; count];
+    let mut running_count = 0;
+    let mut finishes_at = vec![0u64; count];
+    let mut now = 0u64;
+    let mut spawned = Vec::new();
+    loop {
+        let ready = take_ready_operations(
+            spec,
+            plan,
+            ScheduleReadyState {
+                remaining_dependencies: &remaining_dependencies,
+                completed: &completed,
+                failed: &failed,
+                running: &mut running,
+                running_count: &mut running_count,
+            },
+            max_parallel_jobs,
+        );
+        for index in ready {
+            finishes_at[index] = now + durations[index];
+            spawned.push((plan.operations[index].id.clone(), (now, finishes_at[index])));
+        }
+        let Some(finished) = (0..count)
+            .filter(|index| running[*index])
+            .min_by_key(|index| (finishes_at[*index], *index))
+        else {
+            break;
+        };
+        now = finishes_at[finished];
+        running[finished] = false;
+        running_count -= 1;
+        completed[finished] = true;
+        for (index, operation) in plan.operations.iter().enumerate() {
+            if operation.depends_on.contains(&plan.operations[finished].id) {
+                remaining_dependencies[index] -= 1;
+            }
+        }
+    }
+    spawned
+}
+
+#[test]
+fn scheduler_spawn_order_is_stable_for_a_fixed_completion_order() {
+    let mut spec = ResolvedBuildSpec::new("deterministic-schedule-test");
+    spec.sources = ["a", "b", "c", "d"]
+        .into_iter()
+        .map(|id| path_source(id, id))
+        .collect();
+    let source_ids = spec
+        .sources
+        .iter()
+        .map(|source| OperationId::source(&source.id))
+        .collect::<Vec<_>>();
+    let mut operations = spec
+        .sources
+        .iter()
+        .map(|source| {
+            parallel_operation(
+                OperationId::source(&source.id),
+                OperationKind::MaterializeSource {
+                    source_id: source.id.clone(),
+                },
+                OperationParallelismDomain::Sources,
+            )
+        })
+        .collect::<Vec<_>>();
+    operations.push(
+        source_ids.iter().fold(
+            PlannedOperation::new(
+                OperationId::new("image:prepare"),
+                OperationKind::PrepareImage,
+            )
+            .with_parallelism(OperationParallelism::exclusive(
+                OperationParallelismDomain::Images,
+            ))
+            .with_reuse(OperationReuse::execute("test", "test operation")),
+            |operation, source_id| operation.with_dependency(source_id.clone()),
+        ),
+    );
+    let plan = ExecutionPlan {
+        build_id: spec.identity.id.clone(),
+        operations,
+    };
+    let durations = [3, 1, 2, 1, 1];
+
+    let first = simulated_spawn_order(&spec, &plan, &durations, 2);
+    for _ in 0..8 {
+        assert_eq!(simulated_spawn_order(&spec, &plan, &durations, 2), first);
+    }
+    let ids = first.iter().map(OperationId::as_str).collect::<Vec<_>>();
+    assert_eq!(
+        ids,
+        [
+            "source:a",
+            "source:b",
+            "source:c",
+            "source:d",
+            "image:prepare"
+        ]
+    );
+}
+
+#[test]
+fn skipped_by_failures_blames_the_first_failed_upstream_in_plan_order() {
+    // 0 and 1 fail; 2 depends on both, 3 depends on 2, 4 depends on nothing and completed.
+    let failed = [true, true, false, false, false];
+    let completed = [false, false, false, false, true];
+    let dependents = vec![vec![2], vec![2], vec![3], Vec::new(), Vec::new()];
+
+    let mut skipped = skipped_by_failures(&failed, &completed, &dependents);
+    skipped.sort();
+
+    assert_eq!(skipped, vec![(2, 0), (3, 0)]);
+}
+
+#[test]
+fn stall_report_lists_running_ready_and_blocked_operations() {
+    let operation =
+        |id: &str| PlannedOperation::new(OperationId::new(id), OperationKind::PrepareImage);
+    let plan = ExecutionPlan {
+        build_id: "demo".into(),
+        operations: vec![
+            operation("resolve-build"),
+            operation("artifact:app"),
+            operation("artifact:cli"),
+            operation("install:app"),
+            operation("image:build"),
+        ],
+    };
+    // resolve-build completed, artifact:app runs, artifact:cli is ready, the rest wait.
+    let remaining_dependencies = [0, 0, 0, 1, 2];
+    let completed = [true, false, false, false, false];
+    let running = [false, true, false, false, false];
+    let failed = [false; 5];
+
+    assert_eq!(
+        stall_report(
+            &plan,
+            &remaining_dependencies,
+            &completed,
+            &running,
+            &failed
+        ),
+        "running: artifact:app; ready: artifact:cli; blocked (dependencies left): install:app (1), image:build (2)"
+    );
+}
@@ -0,0 +1,222 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use gaia_plan::{ExecutionPlan, OperationId, PLAN_PHASES};
+use gaia_spec::ResolvedBuildSpec;
+use serde::{Deserialize, Serialize};
+
+use crate::OperationSpan;
+
+pub const TIMING_SAMPLES_PER_OPERATION: usize = 5;
+
+// Recent successful run times per operation id, oldest first. Only operations that actually ran
+// are sampled; reused operations would drag the average towards zero.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OperationTimings {
+    #[serde(default)]
+    operations: BTreeMap<String, Vec<u64>>,
+}
+
+impl OperationTimings {
+    // A missing or unreadable history is treated as empty; it only feeds estimates.
+    pub fn load(spec: &ResolvedBuildSpec) -> Self {
+        Self::read(&timings_path(spec))
+    }
+
+    pub fn samples(&self, operation_id: &str) -> &[u64] {
+        self.operations
+            .get(operation_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn average_ms(&self, operation_id: &str) -> Option<u64> {
+        let samples = self.samples(operation_id);
+        (!samples.is_empty()).then(|| samples.iter().sum::<u64>() / samples.len() as u64)
+    }
+
+    fn record(&mut self, operation_id: &str, elapsed_ms: u64) {
+        let samples = self.operations.entry(operation_id.to_string()).or_default();
+        samples.push(elapsed_ms);
+        if samples.len() > TIMING_SAMPLES_PER_OPERATION {
+            samples.drain(..samples.len() - TIMING_SAMPLES_PER_OPERATION);
+        }
+    }
+
+    fn read(path: &Path) -> Self {
+        let Ok(bytes) = fs::read(path) else {
+            return Self::default();
+        };
+        serde_json::from_slice(&bytes).unwrap_or_else(|error| {
+            tracing::warn!(
+                path = %path.display(),
+                %error,
+                "ignoring unreadable operation timings"
+            );
+            Self::default()
+        })
+    }
+}
+
+pub fn timings_path(spec: &ResolvedBuildSpec) -> PathBuf {
+    spec.workspace.out_path().join(".gaia").join("timings.json")
+}
+
+// The history is re-read right before writing and replaced by rename, so concurrent runs never
+// leave a torn file behind; at worst the samples of one of two overlapping writers are lost.
+pub(crate) fn record_timings(spec: &ResolvedBuildSpec, samples: &[(OperationId, u64)]) {
+    if samples.is_empty() {
+        return;
+    }
+    let path = timings_path(spec);
+    let mut timings = OperationTimings::read(&path);
+    for (operation_id, elapsed_ms) in samples {
+        timings.record(operation_id.as_str(), *elapsed_ms);
+    }
+    if let Err(error) = write_atomically(&path, &timings) {
+        tracing::warn!(
+            path = %path.display(),
+            %error,
+            "failed to record operation timings"
+        );
+    }
+}
+
+// Time one run spent in a plan phase. `task_ms` sums its operations' own run times and `wall_ms`
+// spans its first start to its last finish, so parallel work shows `task_ms` above `wall_ms`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PhaseTiming {
+    pub phase: &'static str,
+    pub operations: usize,
+    pub task_ms: u64,
+    pub wall_ms: u64,
+}
+
+// Phases in plan order, leaving out those with no started operation. Bookkeeping operations
+// belong to no phase and are not counted.
+pub fn phase_timings(plan: &ExecutionPlan, spans: &[OperationSpan]) -> Vec<PhaseTiming> {
+    PLAN_PHASES
+        .into_iter()
+        .filter_map(|phase| {
+            let spans = spans
+                .iter()
+                .filter(|span| {
+                    plan.operations.iter().any(|operation| {
+                        operation.id == span.operation_id && operation.kind.phase() == Some(phase)
+                    })
+                })
+                .collect::<Vec<_>>();
+            let started_ms = spans.iter().map(|span| span.started_ms).min()?;
+            let finished_ms = spans.iter().map(|span| span.finished_ms).max()?;
+            Some(PhaseTiming {
+                phase,
+                operations: spans.len(),
+                task_ms: spans
+                    .iter()
+                    .map(|span| span.finished_ms - span.started_ms)
+                    .sum(),
+                wall_ms: finished_ms - started_ms,
+            })
+        })
+        .collect()
+}
+
+fn write_atomically(path: &Path, timings: &OperationTimings) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let bytes = serde_json::to_vec_pretty(timings).map_err(io::Error::other)?;
+    let temporary = path.with_extension(format!("json.{}.tmp", std::process::id()));
+    fs::write(&temporary, bytes)?;
+    fs::rename(&temporary, path).inspect_err(|_| {
+        let _ = fs::remove_file(&temporary);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timings_keep_recent_samples_and_tolerate_corrupt_files() {
+        let dir = std::env::temp_dir().join(format!("gaia-timings-{}", std::process::id()));
+        let path = dir.join("timings.json");
+        fs::create_dir_all(&dir).expect("create dir");
+        fs::write(&path, "{ not json").expect("write corrupt file");
+        let mut timings = OperationTimings::read(&path);
+        assert_eq!(timings, OperationTimings::default());
+
+        for elapsed_ms in 1..=7 {
+            timings.record("artifact:app", elapsed_ms * 1000);
+        }
+        write_atomically(&path, &timings).expect("write timings");
+        let reloaded = OperationTimings::read(&path);
+        assert_eq!(
+            reloaded.samples("artifact:app"),
+            &[3000, 4000, 5000, 6000, 7000]
+        );
+        assert_eq!(reloaded.average_ms("artifact:app"), Some(5000));
+        assert_eq!(reloaded.average_ms("artifact:other"), None);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn phase_timings_sum_task_time_and_span_wall_time() {
+        let operation =
+            |id: &str, kind| gaia_plan::PlannedOperation::new(OperationId::new(id), kind);
+        let plan = ExecutionPlan {
+            build_id: gaia_spec::BuildId::new("phase-timings"),
+            operations: vec![
+                operation("resolve-build", gaia_plan::OperationKind::ResolveBuild),
+                operation(
+                    "source:a",
+                    gaia_plan::OperationKind::MaterializeSource {
+                        source_id: gaia_spec::SourceId::new("a"),
+                    },
+                ),
+                operation(
+                    "source:b",
+                    gaia_plan::OperationKind::MaterializeSource {
+                        source_id: gaia_spec::SourceId::new("b"),
+                    },
+                ),
+                operation("image:build", gaia_plan::OperationKind::BuildImage),
+            ],
+        };
+        let span = |id: &str, started_ms, finished_ms| OperationSpan {
+            operation_id: OperationId::new(id),
+            started_ms,
+            finished_ms,
+        };
+
+        let timings = phase_timings(
+            &plan,
+            &[
+                span("resolve-build", 0, 10),
+                span("source:a", 10, 2_010),
+                span("source:b", 20, 3_020),
+                span("image:build", 3_020, 63_020),
+            ],
+        );
+
+        assert_eq!(
+            timings,
+            [
+                PhaseTiming {
+                    phase: "sources",
+                    operations: 2,
+                    task_ms: 5_000,
+                    wall_ms: 3_010,
+                },
+                PhaseTiming {
+                    phase: "images",
+                    operations: 1,
+                    task_ms: 60_000,
+                    wall_ms: 60_000,
+                },
+            ]
+        );
+    }
+}
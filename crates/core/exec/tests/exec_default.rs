@@ -1,6 +1,9 @@
 pub mod support;
 
-use gaia_exec::{ExecutionEvent, ExecutionProviders, execute_plan};
+use gaia_exec::{
+    ExecutionCancellation, ExecutionEvent, ExecutionProviders, execute_plan,
+    execute_plan_with_cancellation_and_observer,
+};
 use gaia_plan::plan_build;
 use std::fs;
 use std::path::Path;
@@ -25,6 +28,24 @@ fn executes_default_plan_with_runtime_events() {
     assert_eq!(outcome.completed_operations, 11);
     assert!(outcome.errors.is_empty());
     assert!(outcome.reused_ids.is_empty());
+    let run_id = outcome
+        .run_id
+        .clone()
+        .expect("executed runs carry a run id");
+    assert!(matches!(
+        outcome.events.first(),
+        Some(ExecutionEvent::RunStarted { run_id: started, operations, .. })
+            if *started == run_id && *operations == plan.operations.len()
+    ));
+    assert_eq!(
+        outcome.events.last(),
+        Some(&ExecutionEvent::RunFinished {
+            completed: 11,
+            failed: 0,
+            skipped: 0,
+            cancelled: false,
+        })
+    );
     assert!(
         outcome
             .events
@@ -108,4 +129,67 @@ fn executes_default_plan_with_runtime_events() {
             .expect("checkpoint runtime state")
             .contains("anchor=image")
     );
+    assert!(
+        fs::read_to_string(runtime_dir.join("checkpoint-base-image.state"))
+            .expect("checkpoint runtime state")
+            .contains(&format!("run_id={run_id}"))
+    );
+
+    let rerun = execute_plan(
+        &spec,
+        &plan,
+        ExecutionProviders {
+            source_catalog: &source_catalog,
+            artifact_catalog: &artifact_catalog,
+            image_catalog: &image_catalog,
+        },
+    );
+    assert_ne!(rerun.run_id, Some(run_id), "each run gets a fresh id");
+}
+
+#[test]
+fn observers_receive_scheduler_ticks_that_are_not_recorded() {
+    let spec = test_spec();
+    let (source_catalog, artifact_catalog, image_catalog) = provider_catalogs();
+    let plan = plan_build(&spec, &source_catalog, &artifact_catalog, &image_catalog);
+    let (observer, events) = std::sync::mpsc::channel();
+
+    let outcome = execute_plan_with_cancellation_and_observer(
+        &spec,
+        &plan,
+        ExecutionProviders {
+            source_catalog: &source_catalog,
+            artifact_catalog: &artifact_catalog,
+            image_catalog: &image_catalog,
+        },
+        &ExecutionCancellation::new(),
+        Some(observer),
+    );
+
+    assert!(outcome.errors.is_empty());
+    let ticks = events
+        .try_iter()
+        .filter_map(|event| match event {
+            ExecutionEvent::SchedulerTick {
+                running,
+                ready,
+                completed,
+                total,
+            } => Some((running, ready, completed, total)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert!(matches!(ticks.first(), Some((running, _, 0, _)) if *running > 0));
+    assert!(ticks.windows(2).all(|pair| pair[0] != pair[1]));
+    assert!(
+        ticks
+            .iter()
+            .all(|(_, _, completed, total)| *total == plan.operations.len() && completed < total)
+    );
+    assert!(
+        !outcome
+            .events
+            .iter()
+            .any(|event| matches!(event, ExecutionEvent::SchedulerTick { .. }))
+    );
 }
@@ -0,0 +1,95 @@
+pub mod support;
+
+use gaia_exec::{ExecutionErrorKind, ExecutionEvent, ExecutionProviders, execute_plan};
+use gaia_plan::{OperationKind, plan_build};
+use gaia_spec::{OperationHooksSpec, ResolvedBuildSpec};
+use std::fs;
+use support::{provider_catalogs, test_spec};
+
+fn run_only(
+    spec: &ResolvedBuildSpec,
+    keep: impl Fn(&OperationKind) -> bool,
+) -> gaia_exec::ExecutionOutcome {
+    let (source_catalog, artifact_catalog, image_catalog) = provider_catalogs();
+    let mut plan = plan_build(spec, &source_catalog, &artifact_catalog, &image_catalog);
+    plan.operations.retain(|operation| keep(&operation.kind));
+    plan.operations.truncate(1);
+    for operation in &mut plan.operations {
+        operation.depends_on.clear();
+    }
+    execute_plan(
+        spec,
+        &plan,
+        ExecutionProviders {
+            source_catalog: &source_catalog,
+            artifact_catalog: &artifact_catalog,
+            image_catalog: &image_catalog,
+        },
+    )
+}
+
+#[test]
+fn min_free_space_fails_source_operations_before_they_start() {
+    let mut spec = test_spec();
+    spec.policy.execution.min_free_space_mb = u64::MAX / (1024 * 1024);
+
+    let outcome = run_only(&spec, |kind| {
+        matches!(kind, OperationKind::MaterializeSource { .. })
+    });
+
+    assert_eq!(outcome.errors.len(), 1);
+    let error = &outcome.errors[0];
+    assert_eq!(error.code, "disk_space_low");
+    assert_eq!(error.kind, ExecutionErrorKind::DiskFull);
+    assert!(error.message.starts_with(&format!(
+        "out of disk space in '{}': ",
+        spec.workspace.build_dir
+    )));
+    assert!(
+        error
+            .message
+            .ends_with("MiB required by execution.min_free_space_mb")
+    );
+    assert!(outcome.events.iter().any(|event| matches!(
+        event,
+        ExecutionEvent::Failed { operation_id, message }
+            if *operation_id == error.operation_id && *message == error.message
+    )));
+}
+
+#[test]
+fn min_free_space_does_not_gate_other_operations() {
+    let mut spec = test_spec();
+    spec.policy.execution.min_free_space_mb = u64::MAX / (1024 * 1024);
+
+    let outcome = run_only(&spec, |kind| matches!(kind, OperationKind::ResolveBuild));
+
+    assert!(outcome.errors.is_empty(), "{:?}", outcome.errors);
+}
+
+#[test]
+fn tool_output_reporting_a_full_disk_is_classified_as_disk_full() {
+    let mut spec = test_spec();
+    fs::create_dir_all(&spec.workspace.out_dir).expect("out dir");
+    spec.policy.execution.hooks.insert(
+        "resolve-build".into(),
+        OperationHooksSpec {
+            pre: vec![
+                "echo 'write /build/rootfs.ext4: No space left on device' >&2; exit 1".into(),
+            ],
+            timeout_seconds: 30,
+            ..OperationHooksSpec::default()
+        },
+    );
+
+    let outcome = run_only(&spec, |kind| matches!(kind, OperationKind::ResolveBuild));
+
+    assert_eq!(outcome.errors.len(), 1);
+    let error = &outcome.errors[0];
+    assert_eq!(error.code, "operation_pre_hook_failed");
+    assert_eq!(error.kind, ExecutionErrorKind::DiskFull);
+    assert!(error.message.starts_with(&format!(
+        "out of disk space in '{}': pre hook",
+        spec.workspace.build_dir
+    )));
+}
@@ -0,0 +1,87 @@
+pub mod support;
+
+use gaia_exec::{ExecutionError, ExecutionErrorKind, ExecutionOutcome, write_env_export};
+use gaia_plan::OperationId;
+use std::fs;
+use support::test_spec;
+
+fn spec_with_env_export() -> gaia_spec::ResolvedBuildSpec {
+    let mut spec = test_spec();
+    spec.policy.execution.env_export = Some(format!("{}/deploy/build.env", spec.workspace.out_dir));
+    spec
+}
+
+#[test]
+fn successful_run_exports_quoted_build_values_and_run_outputs() {
+    let spec = spec_with_env_export();
+    let outcome = ExecutionOutcome {
+        run_id: Some("1700000000000-0000abcd".into()),
+        outputs: [
+            ("app.version".to_string(), "1.4.0".to_string()),
+            ("motto".to_string(), "it's $HOME".to_string()),
+        ]
+        .into(),
+        ..ExecutionOutcome::default()
+    };
+
+    let path = write_env_export(&spec, &outcome)
+        .expect("env export")
+        .expect("configured path");
+
+    let contents = fs::read_to_string(&path).expect("env file");
+    assert!(contents.contains(&format!("GAIA_BUILD_ID='{}'\n", spec.identity.id.as_str())));
+    assert!(contents.contains("GAIA_RUN_ID='1700000000000-0000abcd'\n"));
+    assert!(contents.contains(&format!("GAIA_OUT_DIR='{}'\n", spec.workspace.out_dir)));
+    assert!(contents.contains("GAIA_OUTPUT_APP_VERSION='1.4.0'\n"));
+    assert!(contents.contains("GAIA_OUTPUT_MOTTO='it'\\''s $HOME'\n"));
+}
+
+#[test]
+fn image_paths_are_exported_when_the_image_was_reused() {
+    let mut spec = spec_with_env_export();
+    spec.image.output.collect_dir = Some(format!("{}/images", spec.workspace.out_dir));
+    spec.image.output.archive_name = Some("demo.tar".into());
+
+    // A no-op run reuses the image operation, so the outcome carries no image results.
+    let path = write_env_export(&spec, &ExecutionOutcome::default())
+        .expect("env export")
+        .expect("configured path");
+
+    let contents = fs::read_to_string(&path).expect("env file");
+    assert!(contents.contains(&format!(
+        "GAIA_IMAGE_ARCHIVE='{}/images/demo.tar'\n",
+        spec.workspace.out_dir
+    )));
+    assert!(contents.contains(&format!(
+        "GAIA_IMAGE_COLLECT_DIR='{}/images'\n",
+        spec.workspace.out_dir
+    )));
+}
+
+#[test]
+fn failed_run_removes_the_previous_env_export() {
+    let spec = spec_with_env_export();
+    let path = write_env_export(&spec, &ExecutionOutcome::default())
+        .expect("env export")
+        .expect("configured path");
+    assert!(path.is_file());
+
+    let failed = ExecutionOutcome {
+        errors: vec![ExecutionError {
+            code: "artifact_failed",
+            kind: ExecutionErrorKind::BackendCommand,
+            operation_id: OperationId::new("artifact:app"),
+            message: "build failed".into(),
+            output_tail: Vec::new(),
+            cleanup_domain: None,
+            cleanup_paths: Vec::new(),
+            cleanup_status: gaia_exec::ExecutionCleanupStatus::NotRequired,
+            cleanup_failures: Vec::new(),
+            exit: None,
+        }],
+        ..ExecutionOutcome::default()
+    };
+
+    assert_eq!(write_env_export(&spec, &failed).expect("env export"), None);
+    assert!(!path.exists());
+}
@@ -0,0 +1,130 @@
+pub mod support;
+
+use gaia_exec::{ExecutionEvent, ExecutionProviders, execute_plan};
+use gaia_plan::{ExecutionPlan, plan_build};
+use gaia_process::ProcessExit;
+use gaia_spec::{HookFailureMode, OperationHooksSpec, ResolvedBuildSpec};
+use std::fs;
+use std::path::Path;
+use support::{provider_catalogs, test_spec};
+
+fn resolve_only(spec: &ResolvedBuildSpec) -> ExecutionPlan {
+    let (source_catalog, artifact_catalog, image_catalog) = provider_catalogs();
+    let mut plan = plan_build(spec, &source_catalog, &artifact_catalog, &image_catalog);
+    plan.operations
+        .retain(|operation| operation.id.as_str() == "resolve-build");
+    plan
+}
+
+fn run(spec: &ResolvedBuildSpec) -> gaia_exec::ExecutionOutcome {
+    let (source_catalog, artifact_catalog, image_catalog) = provider_catalogs();
+    execute_plan(
+        spec,
+        &resolve_only(spec),
+        ExecutionProviders {
+            source_catalog: &source_catalog,
+            artifact_catalog: &artifact_catalog,
+            image_catalog: &image_catalog,
+        },
+    )
+}
+
+fn with_hooks(hooks: OperationHooksSpec) -> ResolvedBuildSpec {
+    let mut spec = test_spec();
+    fs::create_dir_all(&spec.workspace.out_dir).expect("out dir");
+    spec.policy.execution.hooks.insert(
+        "resolve-build".into(),
+        OperationHooksSpec {
+            timeout_seconds: 30,
+            ..hooks
+        },
+    );
+    spec
+}
+
+#[test]
+fn hooks_run_around_the_operation_with_its_id_and_status() {
+    let out_dir = test_spec().workspace.out_dir;
+    fs::create_dir_all(&out_dir).expect("out dir");
+    let log = Path::new(&out_dir).join("hooks.log");
+    let record = format!(
+        "echo \"$GAIA_OPERATION_ID $GAIA_OPERATION_STATUS\" >> '{}'",
+        log.display()
+    );
+    let spec = with_hooks(OperationHooksSpec {
+        pre: vec![record.clone()],
+        post: vec![record],
+        ..OperationHooksSpec::default()
+    });
+
+    let outcome = run(&spec);
+
+    assert!(outcome.errors.is_empty(), "{:?}", outcome.errors);
+    assert_eq!(
+        fs::read_to_string(&log).expect("hook log"),
+        "resolve-build starting\nresolve-build succeeded\n"
+    );
+}
+
+#[test]
+fn failing_pre_hook_fails_the_operation_without_running_it() {
+    let spec = with_hooks(OperationHooksSpec {
+        pre: vec!["exit 3".into()],
+        ..OperationHooksSpec::default()
+    });
+
+    let outcome = run(&spec);
+
+    assert_eq!(outcome.errors.len(), 1);
+    assert_eq!(outcome.errors[0].code, "operation_pre_hook_failed");
+    assert_eq!(outcome.errors[0].exit, Some(ProcessExit::Code(3)));
+    assert!(!outcome.events.iter().any(|event| matches!(
+        event,
+        ExecutionEvent::Log { message, .. } if message.starts_with("resolved build")
+    )));
+}
+
+#[test]
+fn hook_killed_by_a_signal_reports_the_signal() {
+    let outcome = run(&with_hooks(OperationHooksSpec {
+        pre: vec!["kill -9 $$".into()],
+        ..OperationHooksSpec::default()
+    }));
+
+    assert_eq!(outcome.errors.len(), 1);
+    assert_eq!(outcome.errors[0].exit, Some(ProcessExit::Signal(9)));
+    assert!(
+        outcome.errors[0]
+            .message
+            .ends_with("terminated by signal 9 (SIGKILL)"),
+        "{}",
+        outcome.errors[0].message
+    );
+}
+
+#[test]
+fn failing_post_hook_warns_unless_configured_to_fail() {
+    let warned = run(&with_hooks(OperationHooksSpec {
+        post: vec!["exit 1".into()],
+        ..OperationHooksSpec::default()
+    }));
+    assert!(warned.errors.is_empty());
+    assert!(warned.events.iter().any(|event| matches!(
+        event,
+        ExecutionEvent::Log { message, .. } if message.starts_with("WARN: post hook 'exit 1' failed")
+    )));
+
+    let failed = run(&with_hooks(OperationHooksSpec {
+        post: vec!["exit 1".into()],
+        post_failure: HookFailureMode::Fail,
+        ..OperationHooksSpec::default()
+    }));
+    assert_eq!(failed.errors.len(), 1);
+    assert_eq!(failed.errors[0].code, "operation_post_hook_failed");
+    assert!(
+        !failed
+            .events
+            .iter()
+            .any(|event| matches!(event, ExecutionEvent::Succeeded { .. }))
+    );
+}
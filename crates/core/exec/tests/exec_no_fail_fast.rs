@@ -0,0 +1,120 @@
+pub mod support;
+
+use gaia_exec::{ExecutionEvent, ExecutionProviders, execute_plan};
+use gaia_plan::{
+    ExecutionPlan, OperationId, OperationKind, OperationOptionality, OperationParallelism,
+    OperationParallelismDomain, OperationReuse, PlannedOperation,
+};
+use gaia_spec::OperationHooksSpec;
+use std::fs;
+use support::{provider_catalogs, unique_dir};
+
+fn path_source(id: &str, root_dir: &str) -> gaia_spec::SourceSpec {
+    gaia_spec::SourceSpec::new(
+        id,
+        gaia_spec::SourceDefinition::Path(gaia_spec::PathSourceSpec {
+            path: root_dir.to_string(),
+            identity_ignore: Vec::new(),
+            refresh_policy: gaia_spec::SourceRefreshPolicySpec::Never,
+            pin_policy: gaia_spec::SourcePinPolicySpec::Locked,
+        }),
+    )
+}
+
+fn source_operation(source: &gaia_spec::SourceSpec, depends_on: OperationId) -> PlannedOperation {
+    PlannedOperation::new(
+        OperationId::source(&source.id),
+        OperationKind::MaterializeSource {
+            source_id: source.id.clone(),
+        },
+    )
+    .with_dependency(depends_on)
+    .with_parallelism(OperationParallelism::parallelizable(
+        OperationParallelismDomain::Sources,
+    ))
+    .with_optionality(OperationOptionality::Required)
+    .with_reuse(OperationReuse::execute("source", "source"))
+}
+
+#[test]
+fn failed_operation_without_fail_fast_runs_once_and_skips_its_dependents() {
+    let mut spec = gaia_spec::ResolvedBuildSpec::new("no-fail-fast");
+    spec.workspace.root_dir = unique_dir("gaia-exec-no-fail-fast-root");
+    spec.workspace.build_dir = unique_dir("gaia-exec-no-fail-fast-build");
+    spec.workspace.out_dir = unique_dir("gaia-exec-no-fail-fast-out");
+    fs::create_dir_all(&spec.workspace.root_dir).expect("root dir");
+    spec.policy.failure.fail_fast = false;
+    // Rollback would reset the completed count the sibling should show up in.
+    spec.policy.failure.rollback_on_error = false;
+    spec.sources = vec![
+        path_source("broken", &spec.workspace.root_dir),
+        path_source("downstream", &spec.workspace.root_dir),
+        path_source("sibling", &spec.workspace.root_dir),
+    ];
+    spec.policy.execution.hooks.insert(
+        "source:broken".into(),
+        OperationHooksSpec {
+            pre: vec!["false".into()],
+            timeout_seconds: 30,
+            ..OperationHooksSpec::default()
+        },
+    );
+    let broken = OperationId::source(&spec.sources[0].id);
+    let plan = ExecutionPlan {
+        build_id: spec.identity.id.clone(),
+        operations: vec![
+            PlannedOperation::new(OperationId::resolve(), OperationKind::ResolveBuild)
+                .with_parallelism(OperationParallelism::exclusive(
+                    OperationParallelismDomain::Global,
+                ))
+                .with_optionality(OperationOptionality::Required)
+                .with_reuse(OperationReuse::execute("resolve", "resolve")),
+            source_operation(&spec.sources[0], OperationId::resolve()),
+            source_operation(&spec.sources[1], broken.clone()),
+            source_operation(&spec.sources[2], OperationId::resolve()),
+        ],
+    };
+
+    let (source_catalog, artifact_catalog, image_catalog) = provider_catalogs();
+    let outcome = execute_plan(
+        &spec,
+        &plan,
+        ExecutionProviders {
+            source_catalog: &source_catalog,
+            artifact_catalog: &artifact_catalog,
+            image_catalog: &image_catalog,
+        },
+    );
+
+    let started = |id: &OperationId| {
+        outcome
+            .events
+            .iter()
+            .filter(|event| {
+                matches!(event, ExecutionEvent::Started { operation_id } if operation_id == id)
+            })
+            .count()
+    };
+    assert_eq!(started(&broken), 1);
+    assert_eq!(started(&OperationId::source(&spec.sources[1].id)), 0);
+    assert!(outcome.events.iter().any(|event| matches!(
+        event,
+        ExecutionEvent::Skipped { operation_id, failed_dependency }
+            if operation_id.as_str() == "source:downstream" && *failed_dependency == broken
+    )));
+    assert!(outcome.events.iter().any(|event| matches!(
+        event,
+        ExecutionEvent::Succeeded { operation_id } if operation_id.as_str() == "source:sibling"
+    )));
+    assert!(outcome.events.iter().any(|event| matches!(
+        event,
+        ExecutionEvent::RunFinished {
+            completed: 2,
+            failed: 1,
+            skipped: 1,
+            cancelled: false,
+        }
+    )));
+    assert_eq!(outcome.errors.len(), 1);
+    assert_eq!(outcome.errors[0].operation_id, broken);
+}
@@ -110,6 +110,8 @@ pub fn artifact_failure_spec_with_overrides(
         package: "missing-package".into(),
         target_name: Some("gaia-bad".into()),
         variant: gaia_spec::ArtifactVariantSpec::File,
+        linker: None,
+        sysroot: None,
     });
     spec.artifacts.push(bad_artifact);
     spec
@@ -131,6 +133,7 @@ pub fn failing_spec_with_overrides(
         &default_config_path(),
         &ResolveOptions {
             preset: Some("ci".into()),
+            profile: None,
             explicit_overrides,
             ..ResolveOptions::default()
         },
@@ -2,6 +2,7 @@ use gaia_spec::BuildId;
 use std::collections::{BTreeMap, BTreeSet};
 
 use crate::OperationId;
+use crate::OperationKind;
 use crate::PlannedOperation;
 use std::collections::{HashMap, HashSet};
 
@@ -81,68 +82,195 @@ impl ExecutionPlan {
             }
         }
 
-        let graph: HashMap<&str, Vec<&str>> = self
+        for cycle in operation_cycles(&self.operations) {
+            let members = cycle
+                .members
+                .iter()
+                .map(|index| format!("'{}'", self.operations[*index].id.as_str()))
+                .collect::<Vec<_>>();
+            let path = cycle
+                .path
+                .iter()
+                .map(|index| self.operations[*index].id.as_str())
+                .collect::<Vec<_>>();
+            diagnostics.push(PlanDiagnostic {
+                code: "operation_cycle",
+                message: format!(
+                    "operation cycle detected between {} ({})",
+                    members.join(", "),
+                    path.join(" -> ")
+                ),
+            });
+        }
+
+        diagnostics
+    }
+
+    // Lints are advisory: they flag planned declarations nothing consumes and
+    // never make a plan invalid.
+    pub fn lint(&self) -> Vec<PlanDiagnostic> {
+        let consumed: HashSet<&str> = self
             .operations
             .iter()
-            .map(|operation| {
-                (
-                    operation.id.as_str(),
-                    operation
-                        .depends_on
-                        .iter()
-                        .map(OperationId::as_str)
-                        .collect::<Vec<_>>(),
-                )
-            })
+            .flat_map(|operation| operation.depends_on.iter().map(OperationId::as_str))
             .collect();
 
-        let mut permanent = HashSet::new();
-        let mut visiting = Vec::<String>::new();
+        let mut diagnostics = Vec::new();
         for operation in &self.operations {
-            detect_cycle(
-                operation.id.as_str(),
-                &graph,
-                &mut permanent,
-                &mut visiting,
-                &mut diagnostics,
-            );
+            if consumed.contains(operation.id.as_str()) {
+                continue;
+            }
+            match &operation.kind {
+                OperationKind::MaterializeSource { source_id } => {
+                    diagnostics.push(PlanDiagnostic {
+                        code: "unconsumed_source",
+                        message: format!(
+                            "source '{}' is materialized but no artifact or image depends on it",
+                            source_id.as_str()
+                        ),
+                    });
+                }
+                OperationKind::BuildArtifact { artifact_id } => {
+                    diagnostics.push(PlanDiagnostic {
+                        code: "unconsumed_artifact",
+                        message: format!(
+                            "artifact '{}' is built but never installed or used as a dependency",
+                            artifact_id.as_str()
+                        ),
+                    });
+                }
+                _ => {}
+            }
         }
-
         diagnostics
     }
 }
 
-fn detect_cycle(
-    operation_id: &str,
-    graph: &HashMap<&str, Vec<&str>>,
-    permanent: &mut HashSet<String>,
-    visiting: &mut Vec<String>,
-    diagnostics: &mut Vec<PlanDiagnostic>,
-) {
-    if permanent.contains(operation_id) {
-        return;
-    }
+struct OperationCycle {
+    members: Vec<usize>,
+    path: Vec<usize>,
+}
 
-    if let Some(index) = visiting.iter().position(|id| id == operation_id) {
-        let mut cycle = visiting[index..].to_vec();
-        cycle.push(operation_id.to_string());
-        diagnostics.push(PlanDiagnostic {
-            code: "operation_cycle",
-            message: format!("operation cycle detected: {}", cycle.join(" -> ")),
-        });
-        return;
+// Tarjan's strongly connected components over the dependency edges. Each component with more
+// than one operation, or an operation that depends on itself, is one cycle; it is reported with
+// every member in plan order, so one validation shows all operations that have to be untangled,
+// and with one concrete dependency path through it that shows where to cut.
+fn operation_cycles(operations: &[PlannedOperation]) -> Vec<OperationCycle> {
+    let mut index_by_id = HashMap::new();
+    for (index, operation) in operations.iter().enumerate() {
+        index_by_id.entry(operation.id.as_str()).or_insert(index);
     }
+    let edges = operations
+        .iter()
+        .map(|operation| {
+            operation
+                .depends_on
+                .iter()
+                .filter_map(|dependency| index_by_id.get(dependency.as_str()).copied())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
 
-    let Some(dependencies) = graph.get(operation_id) else {
-        return;
+    let mut search = ComponentSearch {
+        edges: &edges,
+        order: vec![None; operations.len()],
+        low_link: vec![0; operations.len()],
+        on_stack: vec![false; operations.len()],
+        stack: Vec::new(),
+        next_order: 0,
+        components: Vec::new(),
     };
+    for index in 0..operations.len() {
+        if search.order[index].is_none() {
+            search.visit(index);
+        }
+    }
+
+    let mut cycles = search
+        .components
+        .into_iter()
+        .filter(|component| component.len() > 1 || edges[component[0]].contains(&component[0]))
+        .map(|mut component| {
+            component.sort_unstable();
+            OperationCycle {
+                path: cycle_path(&edges, &component),
+                members: component,
+            }
+        })
+        .collect::<Vec<_>>();
+    cycles.sort_unstable_by_key(|cycle| cycle.members[0]);
+    cycles
+}
+
+// Depth-first search from the component's first member, following dependencies in declaration
+// order and staying inside the component, until an edge leads back to the start. Every member of
+// a strongly connected component reaches every other, so the search always closes the loop.
+fn cycle_path(edges: &[Vec<usize>], component: &[usize]) -> Vec<usize> {
+    let start = component[0];
+    let mut path = vec![start];
+    let mut visited = HashSet::from([start]);
+    let mut next_edge = vec![0];
+    while let Some(position) = next_edge.last_mut() {
+        let current = path[path.len() - 1];
+        let Some(&dependency) = edges[current].get(*position) else {
+            path.pop();
+            next_edge.pop();
+            continue;
+        };
+        *position += 1;
+        if dependency == start {
+            path.push(start);
+            return path;
+        }
+        if component.binary_search(&dependency).is_ok() && visited.insert(dependency) {
+            path.push(dependency);
+            next_edge.push(0);
+        }
+    }
+    vec![start, start]
+}
 
-    visiting.push(operation_id.to_string());
-    for dependency in dependencies {
-        if graph.contains_key(dependency) {
-            detect_cycle(dependency, graph, permanent, visiting, diagnostics);
+struct ComponentSearch<'a> {
+    edges: &'a [Vec<usize>],
+    order: Vec<Option<usize>>,
+    low_link: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    next_order: usize,
+    components: Vec<Vec<usize>>,
+}
+
+impl ComponentSearch<'_> {
+    fn visit(&mut self, index: usize) {
+        self.order[index] = Some(self.next_order);
+        self.low_link[index] = self.next_order;
+        self.next_order += 1;
+        self.stack.push(index);
+        self.on_stack[index] = true;
+
+        for &dependency in &self.edges[index] {
+            match self.order[dependency] {
+                None => {
+                    self.visit(dependency);
+                    self.low_link[index] = self.low_link[index].min(self.low_link[dependency]);
+                }
+                Some(order) if self.on_stack[dependency] => {
+                    self.low_link[index] = self.low_link[index].min(order);
+                }
+                Some(_) => {}
+            }
+        }
+
+        if Some(self.low_link[index]) == self.order[index] {
+            let mut component = Vec::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack[member] = false;
+                component.push(member);
+                if member == index {
+                    break;
+                }
+            }
+            self.components.push(component);
         }
     }
-    visiting.pop();
-    permanent.insert(operation_id.to_string());
 }
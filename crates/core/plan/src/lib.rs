@@ -1,5 +1,6 @@
 mod graph;
 mod operations;
+mod phases;
 mod reuse;
 
 pub use graph::{ExecutionPlan, PlanDiagnostic, ReuseState};
@@ -8,7 +9,11 @@ pub use operations::{
     OperationParallelismDomain, OperationParallelismMode, OperationReuse, PlannedOperation,
     RebuildReason,
 };
-pub use reuse::{operation_output_signature, spec_fingerprint};
+pub use phases::{PLAN_PHASES, select_plan_phases};
+pub use reuse::{
+    CheckpointFingerprintSource, CheckpointFingerprintSources, checkpoint_anchor_outputs,
+    checkpoint_fingerprint_sources, operation_output_signature, spec_fingerprint, start_plan_from,
+};
 
 use gaia_artifact_providers::{ArtifactProviderCatalog, ArtifactProviderOperation};
 use gaia_image_providers::{ImageProviderCatalog, ImageProviderOperation};
@@ -248,6 +253,58 @@ pub fn plan_build_with_reuse_state(
             ),
         });
     }
+    for overlay in &spec.stage.overlays {
+        let op_id = OperationId::stage_overlay(&overlay.id);
+        stage_operation_ids.push(op_id.clone());
+        operations.push(PlannedOperation {
+            id: op_id,
+            kind: OperationKind::RenderStageOverlay {
+                item_id: overlay.id.clone(),
+            },
+            depends_on: stage_dependencies.clone(),
+            parallelism: OperationParallelism::parallelizable(OperationParallelismDomain::Runtime),
+            optionality: OperationOptionality::Required,
+            fingerprint: operation_fingerprint(
+                spec,
+                &OperationKind::RenderStageOverlay {
+                    item_id: overlay.id.clone(),
+                },
+            ),
+            reuse: OperationReuse::execute(
+                "stage_overlay_required",
+                format!(
+                    "stage overlay '{}' will render from staged inputs",
+                    overlay.id.as_str()
+                ),
+            ),
+        });
+    }
+    for symlink in &spec.stage.symlinks {
+        let op_id = OperationId::stage_symlink(&symlink.id);
+        stage_operation_ids.push(op_id.clone());
+        operations.push(PlannedOperation {
+            id: op_id,
+            kind: OperationKind::RenderStageSymlink {
+                item_id: symlink.id.clone(),
+            },
+            depends_on: stage_dependencies.clone(),
+            parallelism: OperationParallelism::parallelizable(OperationParallelismDomain::Runtime),
+            optionality: OperationOptionality::Required,
+            fingerprint: operation_fingerprint(
+                spec,
+                &OperationKind::RenderStageSymlink {
+                    item_id: symlink.id.clone(),
+                },
+            ),
+            reuse: OperationReuse::execute(
+                "stage_symlink_required",
+                format!(
+                    "stage symlink '{}' will render from staged inputs",
+                    symlink.id.as_str()
+                ),
+            ),
+        });
+    }
 
     let provider = image_catalog
         .find_for_kind(spec.image.provider_kind())
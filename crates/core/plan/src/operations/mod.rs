@@ -40,6 +40,14 @@ impl OperationId {
         Self::new(format!("stage:service:{}", stage_item_id.as_str()))
     }
 
+    pub fn stage_overlay(stage_item_id: &StageItemId) -> Self {
+        Self::new(format!("stage:overlay:{}", stage_item_id.as_str()))
+    }
+
+    pub fn stage_symlink(stage_item_id: &StageItemId) -> Self {
+        Self::new(format!("stage:symlink:{}", stage_item_id.as_str()))
+    }
+
     pub fn image() -> Self {
         Self::new("image:build")
     }
@@ -178,6 +186,15 @@ pub enum OperationParallelismMode {
     Parallelizable,
 }
 
+impl OperationParallelismMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Exclusive => "exclusive",
+            Self::Parallelizable => "parallelizable",
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum OperationParallelismDomain {
     #[default]
@@ -190,6 +207,20 @@ pub enum OperationParallelismDomain {
     Reporting,
 }
 
+impl OperationParallelismDomain {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Global => "global",
+            Self::Sources => "sources",
+            Self::Artifacts => "artifacts",
+            Self::Runtime => "runtime",
+            Self::Images => "images",
+            Self::Checkpoints => "checkpoints",
+            Self::Reporting => "reporting",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum OperationOptionality {
     #[default]
@@ -255,6 +286,12 @@ pub enum OperationKind {
     RenderStageService {
         item_id: StageItemId,
     },
+    RenderStageOverlay {
+        item_id: StageItemId,
+    },
+    RenderStageSymlink {
+        item_id: StageItemId,
+    },
     PrepareImage,
     BuildImage,
     CaptureCheckpoint {
@@ -262,3 +299,61 @@ pub enum OperationKind {
     },
     EmitReport,
 }
+
+impl OperationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ResolveBuild => "resolve-build",
+            Self::MaterializeSource { .. } => "materialize-source",
+            Self::BuildArtifact { .. } => "build-artifact",
+            Self::InstallArtifact { .. } => "install-artifact",
+            Self::RenderStageFile { .. } => "render-stage-file",
+            Self::RenderStageEnvSet { .. } => "render-stage-env-set",
+            Self::RenderStageService { .. } => "render-stage-service",
+            Self::RenderStageOverlay { .. } => "render-stage-overlay",
+            Self::RenderStageSymlink { .. } => "render-stage-symlink",
+            Self::PrepareImage => "prepare-image",
+            Self::BuildImage => "build-image",
+            Self::CaptureCheckpoint { .. } => "capture-checkpoint",
+            Self::EmitReport => "emit-report",
+        }
+    }
+
+    // The `run --phases` label of the operation, named like the rollback domain its outputs belong
+    // to. Resolving the build and emitting the report are bookkeeping and belong to no phase.
+    pub fn phase(&self) -> Option<&'static str> {
+        match self {
+            Self::MaterializeSource { .. } => Some("sources"),
+            Self::BuildArtifact { .. } => Some("artifacts"),
+            Self::InstallArtifact { .. } => Some("installs"),
+            Self::RenderStageFile { .. }
+            | Self::RenderStageEnvSet { .. }
+            | Self::RenderStageService { .. }
+            | Self::RenderStageOverlay { .. }
+            | Self::RenderStageSymlink { .. } => Some("stage"),
+            Self::PrepareImage | Self::BuildImage => Some("images"),
+            Self::CaptureCheckpoint { .. } => Some("checkpoints"),
+            Self::ResolveBuild | Self::EmitReport => None,
+        }
+    }
+
+    // Whether the operation leaves outputs behind that a checkpoint can capture and restore.
+    // Bookkeeping operations and the intermediate image preparation have nothing to restore.
+    pub fn is_checkpointable(&self) -> bool {
+        match self {
+            Self::MaterializeSource { .. }
+            | Self::BuildArtifact { .. }
+            | Self::InstallArtifact { .. }
+            | Self::RenderStageFile { .. }
+            | Self::RenderStageEnvSet { .. }
+            | Self::RenderStageService { .. }
+            | Self::RenderStageOverlay { .. }
+            | Self::RenderStageSymlink { .. }
+            | Self::BuildImage => true,
+            Self::ResolveBuild
+            | Self::PrepareImage
+            | Self::CaptureCheckpoint { .. }
+            | Self::EmitReport => false,
+        }
+    }
+}
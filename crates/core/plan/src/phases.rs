@@ -0,0 +1,95 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{ExecutionPlan, OperationOptionality, PlanDiagnostic};
+
+// Every phase `OperationKind::phase` reports, in the order the plan builds them.
+pub const PLAN_PHASES: [&str; 6] = [
+    "sources",
+    "artifacts",
+    "installs",
+    "stage",
+    "images",
+    "checkpoints",
+];
+
+// `run --phases` / `--skip-phases`: drops every operation outside the selected phases. An empty
+// `phases` selects all of them before `skip_phases` is taken out. Phases are labels, not an order,
+// so selecting `stage` does not pull in the `installs` it needs; resolve-build and the report
+// always stay. A kept operation that needs a required operation from a dropped phase fails the
+// selection, while edges to conditional or best-effort operations, and the report's edges, are
+// dropped along with them.
+pub fn select_plan_phases(
+    plan: &mut ExecutionPlan,
+    phases: &[String],
+    skip_phases: &[String],
+) -> Result<(), PlanDiagnostic> {
+    let phases = parse_phases(phases)?;
+    let skip_phases = parse_phases(skip_phases)?;
+    if phases.is_empty() && skip_phases.is_empty() {
+        return Ok(());
+    }
+    let kept = |phase: Option<&str>| {
+        phase.is_none_or(|phase| {
+            (phases.is_empty() || phases.contains(&phase)) && !skip_phases.contains(&phase)
+        })
+    };
+    let dropped = plan
+        .operations
+        .iter()
+        .filter(|operation| !kept(operation.kind.phase()))
+        .map(|operation| (operation.id.clone(), operation))
+        .collect::<HashMap<_, _>>();
+    for operation in &plan.operations {
+        let Some(phase) = operation.kind.phase() else {
+            continue;
+        };
+        if !kept(Some(phase)) {
+            continue;
+        }
+        for dependency in &operation.depends_on {
+            if let Some(needed) = dropped.get(dependency)
+                && needed.optionality == OperationOptionality::Required
+            {
+                return Err(PlanDiagnostic {
+                    code: "phase_dependency_excluded",
+                    message: format!(
+                        "'{}' needs '{}' from phase '{}', which this run leaves out; select that phase too",
+                        operation.id.as_str(),
+                        dependency.as_str(),
+                        needed.kind.phase().unwrap_or_default()
+                    ),
+                });
+            }
+        }
+    }
+    let dropped = dropped.into_keys().collect::<HashSet<_>>();
+    plan.operations
+        .retain(|operation| !dropped.contains(&operation.id));
+    for operation in &mut plan.operations {
+        operation
+            .depends_on
+            .retain(|dependency| !dropped.contains(dependency));
+    }
+    Ok(())
+}
+
+fn parse_phases(values: &[String]) -> Result<Vec<&'static str>, PlanDiagnostic> {
+    values
+        .iter()
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            PLAN_PHASES
+                .into_iter()
+                .find(|phase| *phase == name || phase.strip_suffix('s') == Some(name))
+                .ok_or_else(|| PlanDiagnostic {
+                    code: "unknown_phase",
+                    message: format!(
+                        "unknown phase '{name}'; expected one of {}",
+                        PLAN_PHASES.join(", ")
+                    ),
+                })
+        })
+        .collect()
+}
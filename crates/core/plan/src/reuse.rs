@@ -1,10 +1,13 @@
 use crate::{
-    ExecutionPlan, OperationId, OperationKind, OperationOptionality, OperationReuse, ReuseState,
+    ExecutionPlan, OperationId, OperationKind, OperationOptionality, OperationReuse,
+    PlanDiagnostic, ReuseState,
 };
+use gaia_process::capture_shell_stdout_cached;
 use gaia_spec::{
-    ArtifactDefinition, CheckpointAnchorRef, ImageDefinition, ResolvedBuildSpec, SourceDefinition,
+    ArtifactDefinition, CheckpointAnchorRef, CheckpointFingerprintInput, ImageDefinition,
+    ResolvedBuildSpec, SourceDefinition,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::env;
 use std::fs;
 use std::hash::{DefaultHasher, Hash, Hasher};
@@ -133,6 +136,77 @@ pub(crate) fn apply_reuse_state(
     plan
 }
 
+// `run --from <operation>` runs that operation and everything depending on it; every other
+// operation is assumed complete and reused as it stands. The returned warnings name assumed
+// operations the run depends on whose outputs are missing.
+pub fn start_plan_from(
+    spec: &ResolvedBuildSpec,
+    plan: &mut ExecutionPlan,
+    operation_id: &str,
+) -> Result<Vec<PlanDiagnostic>, PlanDiagnostic> {
+    if !plan
+        .operations
+        .iter()
+        .any(|operation| operation.id.as_str() == operation_id)
+    {
+        return Err(PlanDiagnostic {
+            code: "unknown_start_operation",
+            message: format!(
+                "cannot start from unknown operation '{operation_id}'; `gaia plan` lists the operation ids"
+            ),
+        });
+    }
+    let mut runs = HashSet::from([operation_id.to_string()]);
+    loop {
+        let reached = plan
+            .operations
+            .iter()
+            .filter(|operation| !runs.contains(operation.id.as_str()))
+            .filter(|operation| {
+                operation
+                    .depends_on
+                    .iter()
+                    .any(|dependency| runs.contains(dependency.as_str()))
+            })
+            .map(|operation| operation.id.as_str().to_string())
+            .collect::<Vec<_>>();
+        if reached.is_empty() {
+            break;
+        }
+        runs.extend(reached);
+    }
+
+    let needed = plan
+        .operations
+        .iter()
+        .filter(|operation| runs.contains(operation.id.as_str()))
+        .flat_map(|operation| operation.depends_on.iter().map(OperationId::as_str))
+        .filter(|dependency| !runs.contains(*dependency))
+        .collect::<HashSet<_>>();
+    let mut warnings = Vec::new();
+    for operation in &plan.operations {
+        if needed.contains(operation.id.as_str())
+            && !operation_outputs_present(spec, &operation.kind)
+        {
+            warnings.push(PlanDiagnostic {
+                code: "start_dependency_outputs_missing",
+                message: format!(
+                    "'{}' is assumed complete by --from {operation_id} but its outputs are missing",
+                    operation.id.as_str()
+                ),
+            });
+        }
+    }
+    for operation in &mut plan.operations {
+        if !runs.contains(operation.id.as_str()) {
+            operation.reuse = OperationReuse::Reuse {
+                source: format!("--from {operation_id}"),
+            };
+        }
+    }
+    Ok(warnings)
+}
+
 pub(crate) fn artifact_rebuild_message(artifact: &gaia_spec::ArtifactSpec) -> String {
     if !artifact.dependencies.is_empty() {
         return format!(
@@ -209,6 +283,22 @@ pub(crate) fn operation_fingerprint(spec: &ResolvedBuildSpec, kind: &OperationKi
                 .map(|item| format!("{item:?}"))
                 .hash(&mut hasher);
         }
+        OperationKind::RenderStageOverlay { item_id } => {
+            spec.stage
+                .overlays
+                .iter()
+                .find(|item| item.id == *item_id)
+                .map(|item| format!("{item:?}"))
+                .hash(&mut hasher);
+        }
+        OperationKind::RenderStageSymlink { item_id } => {
+            spec.stage
+                .symlinks
+                .iter()
+                .find(|item| item.id == *item_id)
+                .map(|item| format!("{item:?}"))
+                .hash(&mut hasher);
+        }
         OperationKind::PrepareImage | OperationKind::BuildImage => {
             format!("{:?}", spec.image).hash(&mut hasher);
             image_backend_signature(spec, &spec.image).hash(&mut hasher);
@@ -218,7 +308,12 @@ pub(crate) fn operation_fingerprint(spec: &ResolvedBuildSpec, kind: &OperationKi
                 .points
                 .iter()
                 .find(|checkpoint| checkpoint.id == *checkpoint_id)
-                .map(|checkpoint| format!("{checkpoint:?}"))
+                .map(|checkpoint| {
+                    format!(
+                        "{checkpoint:?}|{}",
+                        checkpoint_fingerprint_inputs_signature(spec, checkpoint)
+                    )
+                })
                 .hash(&mut hasher);
         }
         OperationKind::EmitReport => {
@@ -275,13 +370,20 @@ fn artifact_backend_signature(artifact: &gaia_spec::ArtifactSpec) -> String {
 
 fn image_backend_signature(spec: &ResolvedBuildSpec, image: &gaia_spec::ImageSpec) -> String {
     match &image.definition {
-        ImageDefinition::Buildroot(_buildroot) => {
+        ImageDefinition::Buildroot(buildroot) => {
             let buildroot_dir = env::var("GAIA_BUILDROOT_DIR")
                 .ok()
                 .or_else(|| env::var("BUILDROOT_DIR").ok())
                 .unwrap_or_default();
+            // Fragment contents feed the merged symbol set, so edits must invalidate the image.
+            let fragment_signatures = buildroot
+                .config_fragments
+                .iter()
+                .map(|fragment| path_state_signature(&resolve_workspace_path(spec, fragment)))
+                .collect::<Vec<_>>()
+                .join(",");
             format!(
-                "{}|{}|{}|{}",
+                "{}|{}|{}|{}|{}",
                 command_signature("make", ["--version"]),
                 command_signature("tar", ["--version"]),
                 buildroot_dir.clone().if_empty_then("no-buildroot-dir"),
@@ -289,7 +391,8 @@ fn image_backend_signature(spec: &ResolvedBuildSpec, image: &gaia_spec::ImageSpe
                     "no-buildroot-state".to_string()
                 } else {
                     path_state_signature(Path::new(&buildroot_dir))
-                }
+                },
+                fragment_signatures
             )
         }
         ImageDefinition::StartingPoint(starting_point) => {
@@ -315,6 +418,68 @@ fn image_backend_signature(spec: &ResolvedBuildSpec, image: &gaia_spec::ImageSpe
     }
 }
 
+// What a checkpoint's fingerprint reads beyond the checkpoint's own settings. The capture also
+// reruns whenever `anchor` reruns, since it depends on that operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckpointFingerprintSources {
+    pub anchor: OperationId,
+    pub inputs: Vec<CheckpointFingerprintSource>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckpointFingerprintSource {
+    pub input: CheckpointFingerprintInput,
+    // The workspace-resolved path the fingerprint hashes; commands have none.
+    pub path: Option<PathBuf>,
+}
+
+pub fn checkpoint_fingerprint_sources(
+    spec: &ResolvedBuildSpec,
+    checkpoint: &gaia_spec::CheckpointPointSpec,
+) -> CheckpointFingerprintSources {
+    CheckpointFingerprintSources {
+        anchor: checkpoint_anchor_dependency(&checkpoint.anchor),
+        inputs: checkpoint
+            .fingerprint_from
+            .iter()
+            .map(|input| CheckpointFingerprintSource {
+                input: input.clone(),
+                path: match input {
+                    CheckpointFingerprintInput::Path(path) => {
+                        Some(resolve_workspace_path(spec, path))
+                    }
+                    CheckpointFingerprintInput::Command(_) => None,
+                },
+            })
+            .collect(),
+    }
+}
+
+// Failed commands hash their error text; validation reports them before a plan is executed.
+fn checkpoint_fingerprint_inputs_signature(
+    spec: &ResolvedBuildSpec,
+    checkpoint: &gaia_spec::CheckpointPointSpec,
+) -> String {
+    checkpoint_fingerprint_sources(spec, checkpoint)
+        .inputs
+        .iter()
+        .map(|source| match &source.input {
+            CheckpointFingerprintInput::Path(_) => source
+                .path
+                .as_deref()
+                .map(path_state_signature)
+                .unwrap_or_default(),
+            CheckpointFingerprintInput::Command(command) => {
+                match capture_shell_stdout_cached(Path::new(&spec.workspace.root_dir), command) {
+                    Ok(stdout) => format!("cmd:{stdout}"),
+                    Err(failure) => format!("cmd-failed:{failure}"),
+                }
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
 fn command_signature<const N: usize>(program: &str, args: [&str; N]) -> String {
     let mut command = Command::new(program);
     command.args(args);
@@ -447,7 +612,17 @@ fn workspace_path_ignores(spec: &ResolvedBuildSpec) -> Vec<String> {
         "build".to_string(),
         "out".to_string(),
     ];
-    for path in [&spec.workspace.build_dir, &spec.workspace.out_dir] {
+    // A ccache dir inside the workspace changes on every compile; it must not churn path sources.
+    let ccache_dir = match &spec.image.definition {
+        ImageDefinition::Buildroot(buildroot) => buildroot
+            .ccache_dir(&spec.workspace)
+            .map(|dir| dir.display().to_string()),
+        _ => None,
+    };
+    for path in [&spec.workspace.build_dir, &spec.workspace.out_dir]
+        .into_iter()
+        .chain(ccache_dir.as_ref())
+    {
         let candidate = Path::new(path);
         if let Some(name) = candidate.file_name().and_then(|name| name.to_str())
             && !ignored.iter().any(|ignored_name| ignored_name == name)
@@ -491,6 +666,12 @@ fn operation_outputs_present(spec: &ResolvedBuildSpec, kind: &OperationKind) ->
         OperationKind::RenderStageService { item_id } => {
             stage_state_path(spec, "service", item_id).is_file()
         }
+        OperationKind::RenderStageOverlay { item_id } => {
+            stage_state_path(spec, "overlay", item_id).is_file()
+        }
+        OperationKind::RenderStageSymlink { item_id } => {
+            stage_state_path(spec, "symlink", item_id).is_file()
+        }
         OperationKind::CaptureCheckpoint { checkpoint_id } => {
             checkpoint_state_path(spec, checkpoint_id).is_file()
         }
@@ -572,6 +753,12 @@ pub fn operation_output_signature(
         OperationKind::RenderStageService { item_id } => Some(provider_state_signature(
             &stage_state_path(spec, "service", item_id),
         )),
+        OperationKind::RenderStageOverlay { item_id } => Some(provider_state_signature(
+            &stage_state_path(spec, "overlay", item_id),
+        )),
+        OperationKind::RenderStageSymlink { item_id } => Some(provider_state_signature(
+            &stage_state_path(spec, "symlink", item_id),
+        )),
         OperationKind::PrepareImage => {
             spec.image.output.collect_dir.as_deref().map(|collect_dir| {
                 let output_dir = Path::new(collect_dir).join("buildroot-output");
@@ -644,9 +831,45 @@ fn checkpoint_state_path(
     runtime_state_dir(spec).join(format!("checkpoint-{}.state", checkpoint_id.as_str()))
 }
 
+// The paths a checkpoint on `anchor` captures and restores: the anchored operation's outputs,
+// or its runtime state file for operations whose output only exists inside the staged image.
+pub fn checkpoint_anchor_outputs(
+    spec: &ResolvedBuildSpec,
+    anchor: &CheckpointAnchorRef,
+) -> Vec<PathBuf> {
+    match anchor {
+        CheckpointAnchorRef::Image => spec
+            .image
+            .output
+            .collect_dir
+            .iter()
+            .map(PathBuf::from)
+            .collect(),
+        CheckpointAnchorRef::Source(id) => spec
+            .sources
+            .iter()
+            .filter(|source| source.id == *id)
+            .map(|source| source.materialized_path(&spec.workspace))
+            .collect(),
+        CheckpointAnchorRef::Artifact(id) => spec
+            .artifacts
+            .iter()
+            .filter(|artifact| artifact.id == *id)
+            .map(|artifact| PathBuf::from(&artifact.output.path))
+            .collect(),
+        CheckpointAnchorRef::Install(id) => vec![install_state_path(spec, id)],
+        CheckpointAnchorRef::StageFile(id) => vec![stage_state_path(spec, "file", id)],
+        CheckpointAnchorRef::StageEnvSet(id) => vec![stage_state_path(spec, "env", id)],
+        CheckpointAnchorRef::StageService(id) => vec![stage_state_path(spec, "service", id)],
+        CheckpointAnchorRef::Unknown(_) => Vec::new(),
+    }
+}
+
 pub(crate) fn checkpoint_anchor_dependency(anchor: &CheckpointAnchorRef) -> OperationId {
     match anchor {
         CheckpointAnchorRef::Image => OperationId::image(),
+        CheckpointAnchorRef::Source(id) => OperationId::source(id),
+        CheckpointAnchorRef::Artifact(id) => OperationId::artifact(id),
         CheckpointAnchorRef::Install(id) => OperationId::install(id),
         CheckpointAnchorRef::StageFile(id) => OperationId::stage_file(id),
         CheckpointAnchorRef::StageEnvSet(id) => OperationId::stage_env_set(id),
@@ -1,7 +1,11 @@
 pub mod support;
 
 use gaia_config::resolve_config;
-use gaia_plan::{OperationOptionality, plan_build};
+use gaia_plan::{
+    OperationId, OperationOptionality, checkpoint_anchor_outputs, checkpoint_fingerprint_sources,
+    plan_build,
+};
+use gaia_spec::CheckpointFingerprintInput;
 use std::fs;
 use std::path::PathBuf;
 use support::{provider_catalogs, unique_dir};
@@ -132,3 +136,218 @@ upload_policy = "off"
             .any(|dependency| dependency.as_str() == "checkpoint:best-effort-checkpoint")
     );
 }
+
+#[test]
+fn checkpoint_fingerprint_mixes_in_command_output() {
+    let fingerprint_with_stamp = |stamp: &str| {
+        let root_dir = unique_dir("gaia-plan-root");
+        fs::create_dir_all(&root_dir).expect("root dir");
+        fs::write(PathBuf::from(&root_dir).join("toolchain.txt"), stamp).expect("stamp");
+        let config_path = PathBuf::from(&root_dir).join("build.toml");
+        fs::write(
+            &config_path,
+            format!(
+                r#"
+build_name = "checkpoint-fingerprint"
+
+[workspace]
+root_dir = "{root_dir}"
+build_dir = "build"
+out_dir = "out"
+
+[image]
+kind = "starting-point"
+rootfs_path = "/tmp/rootfs"
+
+[[checkpoints]]
+id = "base-image"
+backend = "local"
+use_policy = "auto"
+upload_policy = "off"
+fingerprint_from = ["cmd:cat toolchain.txt"]
+"#
+            ),
+        )
+        .expect("config");
+
+        let spec = resolve_config(config_path.to_str().expect("utf-8 config path"));
+        let (source_catalog, artifact_catalog, image_catalog) = provider_catalogs();
+        let plan = plan_build(&spec, &source_catalog, &artifact_catalog, &image_catalog);
+        plan.operations
+            .iter()
+            .find(|operation| operation.id.as_str() == "checkpoint:base-image")
+            .expect("checkpoint operation")
+            .fingerprint
+    };
+
+    assert_eq!(
+        fingerprint_with_stamp("gcc 13"),
+        fingerprint_with_stamp("gcc 13")
+    );
+    assert_ne!(
+        fingerprint_with_stamp("gcc 13"),
+        fingerprint_with_stamp("gcc 14")
+    );
+}
+
+#[test]
+fn checkpoint_fingerprint_sources_resolve_paths_and_keep_commands() {
+    let root_dir = unique_dir("gaia-plan-root");
+    fs::create_dir_all(PathBuf::from(&root_dir).join("board")).expect("board dir");
+    let config_path = PathBuf::from(&root_dir).join("build.toml");
+    fs::write(
+        &config_path,
+        format!(
+            r#"
+build_name = "checkpoint-sources"
+
+[workspace]
+root_dir = "{root_dir}"
+build_dir = "build"
+out_dir = "out"
+
+[image]
+kind = "starting-point"
+rootfs_path = "/tmp/rootfs"
+
+[[checkpoints]]
+id = "base-image"
+backend = "local"
+use_policy = "auto"
+upload_policy = "off"
+fingerprint_from = ["board", "missing.cfg", "cmd:git rev-parse HEAD"]
+"#
+        ),
+    )
+    .expect("config");
+
+    let spec = resolve_config(config_path.to_str().expect("utf-8 config path"));
+    let sources = checkpoint_fingerprint_sources(&spec, &spec.checkpoints.points[0]);
+
+    assert_eq!(sources.anchor.as_str(), "image:build");
+    let paths = sources
+        .inputs
+        .iter()
+        .map(|source| source.path.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        vec![
+            Some(PathBuf::from(&root_dir).join("board")),
+            Some(PathBuf::from(&root_dir).join("missing.cfg")),
+            None,
+        ]
+    );
+    assert_eq!(
+        sources.inputs[2].input,
+        CheckpointFingerprintInput::Command("git rev-parse HEAD".into())
+    );
+}
+
+#[test]
+fn checkpoint_can_anchor_to_artifact_operation_and_resolve_its_outputs() {
+    let root_dir = unique_dir("gaia-plan-artifact-anchor-root");
+    fs::create_dir_all(&root_dir).expect("root dir");
+    let config_path = PathBuf::from(&root_dir).join("build.toml");
+    fs::write(
+        &config_path,
+        r#"
+build_name = "checkpoint-artifact-anchor"
+
+[workspace]
+root_dir = "."
+build_dir = "build"
+out_dir = "out"
+
+[image]
+kind = "starting-point"
+rootfs_path = "/tmp/rootfs"
+
+[[artifacts]]
+id = "gaia-app"
+kind = "rust"
+package = "gaia"
+output_path = "out/gaia"
+
+[[install]]
+id = "install-gaia-app"
+artifact = "gaia-app"
+dest = "/usr/bin/gaia"
+
+[[checkpoints]]
+id = "after-app"
+backend = "local"
+anchor = "artifact:gaia-app"
+use_policy = "auto"
+upload_policy = "off"
+"#,
+    )
+    .expect("config");
+
+    let spec = resolve_config(config_path.to_str().expect("utf-8 config path"));
+    let (source_catalog, artifact_catalog, image_catalog) = provider_catalogs();
+    let plan = plan_build(&spec, &source_catalog, &artifact_catalog, &image_catalog);
+
+    assert!(plan.validate().is_empty());
+    let checkpoint = plan
+        .operations
+        .iter()
+        .find(|operation| operation.id.as_str() == "checkpoint:after-app")
+        .expect("checkpoint operation");
+    assert_eq!(
+        checkpoint.depends_on,
+        vec![OperationId::new("artifact:gaia-app")]
+    );
+    let anchor = &spec.checkpoints.points[0].anchor;
+    assert_eq!(anchor.as_str(), "artifact:gaia-app");
+    assert_eq!(
+        checkpoint_anchor_outputs(&spec, anchor),
+        vec![PathBuf::from(&spec.artifacts[0].output.path)]
+    );
+}
+
+#[test]
+fn checkpoint_anchored_to_a_path_source_resolves_to_the_source_path() {
+    let root_dir = unique_dir("gaia-plan-source-anchor-root");
+    fs::create_dir_all(PathBuf::from(&root_dir).join("vendor/app")).expect("source dir");
+    let config_path = PathBuf::from(&root_dir).join("build.toml");
+    fs::write(
+        &config_path,
+        r#"
+build_name = "checkpoint-source-anchor"
+
+[workspace]
+root_dir = "."
+build_dir = "build"
+out_dir = "out"
+
+[image]
+kind = "starting-point"
+rootfs_path = "/tmp/rootfs"
+
+[[sources]]
+id = "app-src"
+kind = "path"
+path = "vendor/app"
+
+[[checkpoints]]
+id = "after-source"
+backend = "local"
+anchor = "source:app-src"
+use_policy = "auto"
+upload_policy = "off"
+"#,
+    )
+    .expect("config");
+
+    let spec = resolve_config(config_path.to_str().expect("utf-8 config path"));
+
+    assert_eq!(
+        checkpoint_anchor_outputs(&spec, &spec.checkpoints.points[0].anchor),
+        vec![spec.sources[0].materialized_path(&spec.workspace)]
+    );
+    assert!(
+        checkpoint_anchor_outputs(&spec, &spec.checkpoints.points[0].anchor)[0]
+            .ends_with("vendor/app")
+    );
+}
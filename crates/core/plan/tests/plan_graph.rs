@@ -187,6 +187,56 @@ defconfig = "qemu_aarch64_virt_defconfig"
     );
 }
 
+#[test]
+fn stage_overlay_operation_gates_the_image_build() {
+    let root_dir = unique_dir("gaia-plan-stage-overlay-root");
+    fs::create_dir_all(&root_dir).expect("root dir");
+    let config_path = PathBuf::from(&root_dir).join("build.toml");
+    fs::write(
+        &config_path,
+        r#"
+build_name = "stage-overlay"
+
+[workspace]
+root_dir = "."
+build_dir = "build"
+out_dir = "out"
+
+[image]
+kind = "starting-point"
+rootfs_path = "/tmp/rootfs"
+
+[[stage.overlays]]
+id = "base-overlay"
+src = "overlay"
+dest = "/"
+"#,
+    )
+    .expect("config");
+
+    let spec = resolve_config(config_path.to_str().expect("utf-8 config path"));
+    assert_eq!(spec.image.feed.stage_overlays, vec!["base-overlay".into()]);
+    let (source_catalog, artifact_catalog, image_catalog) = provider_catalogs();
+    let plan = plan_build(&spec, &source_catalog, &artifact_catalog, &image_catalog);
+
+    assert!(
+        plan.operations
+            .iter()
+            .any(|operation| operation.id.as_str() == "stage:overlay:base-overlay")
+    );
+    let image_build = plan
+        .operations
+        .iter()
+        .find(|operation| operation.id.as_str() == "image:build")
+        .expect("image build operation");
+    assert!(
+        image_build
+            .depends_on
+            .iter()
+            .any(|dependency| dependency.as_str() == "stage:overlay:base-overlay")
+    );
+}
+
 #[test]
 fn required_operation_depends_on_best_effort_is_a_plan_error() {
     let plan = gaia_plan::ExecutionPlan {
@@ -217,3 +267,60 @@ fn required_operation_depends_on_best_effort_is_a_plan_error() {
             .any(|diagnostic| { diagnostic.code == "required_depends_on_best_effort" })
     );
 }
+
+#[test]
+fn plan_validation_reports_every_structural_error_at_once() {
+    let artifact = |id: &str, depends_on: &[&str]| {
+        let artifact_id = gaia_spec::ArtifactId::new(id);
+        depends_on.iter().fold(
+            gaia_plan::PlannedOperation::new(
+                gaia_plan::OperationId::artifact(&artifact_id),
+                gaia_plan::OperationKind::BuildArtifact { artifact_id },
+            ),
+            |operation, dependency| {
+                operation.with_dependency(gaia_plan::OperationId::artifact(
+                    &gaia_spec::ArtifactId::new(*dependency),
+                ))
+            },
+        )
+    };
+    let plan = gaia_plan::ExecutionPlan {
+        build_id: gaia_spec::BuildId::new("broken-plan"),
+        operations: vec![
+            artifact("app", &["missing-lib"]),
+            artifact("app", &[]),
+            artifact("api", &["db"]),
+            artifact("db", &["cache", "api"]),
+            artifact("cache", &["api"]),
+            artifact("loop", &["loop"]),
+            artifact("leaf", &["api"]),
+        ],
+    };
+
+    let diagnostics = plan.validate();
+    let messages = |code: &str| {
+        diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.code == code)
+            .map(|diagnostic| diagnostic.message.as_str())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        messages("duplicate_operation_id"),
+        vec!["duplicate operation id 'artifact:app'"]
+    );
+    assert_eq!(
+        messages("missing_dependency_node"),
+        vec!["operation 'artifact:app' depends on missing operation 'artifact:missing-lib'"]
+    );
+    assert_eq!(
+        messages("operation_cycle"),
+        vec![
+            "operation cycle detected between 'artifact:api', 'artifact:db', 'artifact:cache' \
+             (artifact:api -> artifact:db -> artifact:cache -> artifact:api)",
+            "operation cycle detected between 'artifact:loop' (artifact:loop -> artifact:loop)",
+        ]
+    );
+    assert_eq!(diagnostics.len(), 4);
+}
@@ -0,0 +1,54 @@
+pub mod support;
+
+use gaia_config::resolve_config;
+use gaia_plan::plan_build;
+use std::fs;
+use std::path::PathBuf;
+use support::{provider_catalogs, unique_dir};
+
+#[test]
+fn lint_reports_unconsumed_sources_and_artifacts_without_invalidating_plan() {
+    let root_dir = unique_dir("gaia-plan-lint-root");
+    fs::create_dir_all(&root_dir).expect("root dir");
+    let config_path = PathBuf::from(&root_dir).join("build.toml");
+    fs::write(
+        &config_path,
+        r#"
+build_name = "lint-unconsumed"
+
+[workspace]
+root_dir = "."
+build_dir = "build"
+out_dir = "out"
+
+[[sources]]
+id = "orphan-source"
+kind = "path"
+path = "."
+
+[[artifacts]]
+id = "orphan-app"
+kind = "rust"
+package = "gaia"
+output_path = "out/gaia"
+
+[image]
+kind = "starting-point"
+rootfs_path = "/tmp/rootfs"
+"#,
+    )
+    .expect("config");
+
+    let spec = resolve_config(config_path.to_str().expect("utf-8 config path"));
+    let (source_catalog, artifact_catalog, image_catalog) = provider_catalogs();
+    let plan = plan_build(&spec, &source_catalog, &artifact_catalog, &image_catalog);
+    let lints = plan.lint();
+
+    assert!(plan.validate().is_empty());
+    assert!(lints.iter().any(|lint| {
+        lint.code == "unconsumed_source" && lint.message.contains("'orphan-source'")
+    }));
+    assert!(lints.iter().any(|lint| {
+        lint.code == "unconsumed_artifact" && lint.message.contains("'orphan-app'")
+    }));
+}
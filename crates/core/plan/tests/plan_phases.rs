@@ -0,0 +1,88 @@
+pub mod support;
+
+use gaia_config::resolve_config;
+use gaia_plan::{ExecutionPlan, plan_build, select_plan_phases};
+use support::{default_config_path, provider_catalogs};
+
+fn default_plan() -> ExecutionPlan {
+    let spec = resolve_config(&default_config_path());
+    let (source_catalog, artifact_catalog, image_catalog) = provider_catalogs();
+    plan_build(&spec, &source_catalog, &artifact_catalog, &image_catalog)
+}
+
+fn phases(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+#[test]
+fn skipped_phases_are_pruned_with_their_edges() {
+    let mut plan = default_plan();
+
+    select_plan_phases(&mut plan, &[], &phases(&["images,checkpoints"])).expect("selection");
+
+    assert!(plan.validate().is_empty());
+    assert!(
+        plan.operations
+            .iter()
+            .all(|operation| !matches!(operation.kind.phase(), Some("images" | "checkpoints")))
+    );
+    let report = plan
+        .operations
+        .iter()
+        .find(|operation| operation.id.as_str() == "report:emit")
+        .expect("report stays");
+    assert!(report.depends_on.is_empty());
+    assert!(
+        plan.operations
+            .iter()
+            .any(|operation| operation.id.as_str() == "resolve-build")
+    );
+    assert!(
+        plan.operations
+            .iter()
+            .any(|operation| operation.kind.phase() == Some("stage"))
+    );
+}
+
+#[test]
+fn selected_phases_accept_singular_names_and_keep_bookkeeping() {
+    let mut plan = default_plan();
+
+    select_plan_phases(&mut plan, &phases(&["source", "artifacts"]), &[]).expect("selection");
+
+    assert!(plan.validate().is_empty());
+    let mut kept = plan
+        .operations
+        .iter()
+        .map(|operation| operation.kind.phase().unwrap_or("bookkeeping"))
+        .collect::<Vec<_>>();
+    kept.dedup();
+    assert_eq!(kept, ["bookkeeping", "sources", "artifacts", "bookkeeping"]);
+}
+
+#[test]
+fn phases_do_not_pull_in_the_phases_they_depend_on() {
+    let mut plan = default_plan();
+    let before = plan.clone();
+
+    let error =
+        select_plan_phases(&mut plan, &phases(&["stage"]), &[]).expect_err("needs installs");
+
+    assert_eq!(error.code, "phase_dependency_excluded");
+    assert!(
+        error
+            .message
+            .contains("needs 'install:install-gaia-app' from phase 'installs'"),
+        "{}",
+        error.message
+    );
+    assert_eq!(plan, before);
+
+    let error =
+        select_plan_phases(&mut plan, &phases(&["stages", "rootfs"]), &[]).expect_err("unknown");
+    assert_eq!(error.code, "unknown_phase");
+    assert_eq!(
+        error.message,
+        "unknown phase 'stages'; expected one of sources, artifacts, installs, stage, images, checkpoints"
+    );
+}
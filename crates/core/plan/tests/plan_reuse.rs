@@ -2,7 +2,7 @@ pub mod support;
 
 use gaia_plan::{
     OperationReuse, ReuseState, operation_output_signature, plan_build,
-    plan_build_with_reuse_state, spec_fingerprint,
+    plan_build_with_reuse_state, spec_fingerprint, start_plan_from,
 };
 use std::collections::BTreeSet;
 use std::fs;
@@ -441,3 +441,53 @@ fn plan_does_not_reuse_when_spec_fingerprint_is_stale_even_if_outputs_match() {
             )
     }));
 }
+
+#[test]
+fn start_from_reuses_everything_the_operation_does_not_reach() {
+    let spec = test_spec();
+    let (source_catalog, artifact_catalog, image_catalog) = provider_catalogs();
+    fs::create_dir_all(PathBuf::from(&spec.workspace.build_dir).join("sources/gaia-upstream"))
+        .expect("gaia-upstream source dir");
+    fs::write(
+        PathBuf::from(&spec.workspace.build_dir).join("sources/gaia-upstream/source.txt"),
+        "ok",
+    )
+    .expect("gaia-upstream source marker");
+    let mut plan = plan_build(&spec, &source_catalog, &artifact_catalog, &image_catalog);
+
+    let warnings = start_plan_from(&spec, &mut plan, "artifact:gaia-app").expect("known operation");
+
+    let reuse_of = |id: &str| {
+        plan.operations
+            .iter()
+            .find(|operation| operation.id.as_str() == id)
+            .map(|operation| operation.reuse.clone())
+            .expect("planned operation")
+    };
+    assert_eq!(
+        reuse_of("source:gaia-upstream"),
+        OperationReuse::Reuse {
+            source: "--from artifact:gaia-app".into()
+        }
+    );
+    assert!(matches!(
+        reuse_of("resolve-build"),
+        OperationReuse::Reuse { .. }
+    ));
+    assert!(reuse_of("artifact:gaia-app").should_execute());
+    assert!(reuse_of("install:install-gaia-app").should_execute());
+    assert!(reuse_of("report:emit").should_execute());
+    let messages = warnings
+        .iter()
+        .map(|warning| warning.message.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        [
+            "'source:workspace-root' is assumed complete by --from artifact:gaia-app but its outputs are missing"
+        ]
+    );
+
+    let error = start_plan_from(&spec, &mut plan, "artifact:missing").expect_err("unknown");
+    assert_eq!(error.code, "unknown_start_operation");
+}
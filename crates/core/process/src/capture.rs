@@ -0,0 +1,43 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Mutex, OnceLock};
+
+use crate::output_text;
+
+type CaptureCache = Mutex<HashMap<(PathBuf, String), Result<String, String>>>;
+
+// Runs `command` through `sh -c` in `cwd` and returns its trimmed stdout. Results, including
+// failures, are cached for the life of the process so validation and planning in one invocation
+// observe the same output and run each command once.
+pub fn capture_shell_stdout_cached(cwd: &Path, command: &str) -> Result<String, String> {
+    static CACHE: OnceLock<CaptureCache> = OnceLock::new();
+    let key = (cwd.to_path_buf(), command.to_string());
+    let cache = CACHE.get_or_init(Default::default);
+    if let Some(cached) = cache.lock().ok().and_then(|cache| cache.get(&key).cloned()) {
+        return cached;
+    }
+    let captured = capture_shell_stdout(cwd, command);
+    if let Ok(mut cache) = cache.lock() {
+        cache.insert(key, captured.clone());
+    }
+    captured
+}
+
+fn capture_shell_stdout(cwd: &Path, command: &str) -> Result<String, String> {
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .current_dir(cwd)
+        .output()
+        .map_err(|error| format!("failed to start in '{}': {error}", cwd.display()))?;
+    if !output.status.success() {
+        let detail = output_text(&output);
+        return Err(if detail.is_empty() {
+            format!("failed with {}", output.status)
+        } else {
+            format!("failed with {}: {detail}", output.status)
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
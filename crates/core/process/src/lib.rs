@@ -1,7 +1,12 @@
+use std::collections::BTreeMap;
 use std::ffi::OsStr;
+use std::fmt;
 use std::io::{BufRead, BufReader, Read};
-use std::process::{Child, Command, Output, Stdio};
+use std::process::{Child, Command, ExitStatus, Output, Stdio};
 use std::sync::Arc;
+#[cfg(unix)]
+use std::sync::atomic::AtomicI32;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
 use std::thread;
 use std::time::{Duration, Instant};
@@ -12,9 +17,13 @@ use std::os::unix::process::CommandExt;
 const MAX_RETAINED_STREAM_BYTES: usize = 1024 * 1024;
 const MAX_RETAINED_STREAM_LINES: usize = 1_000;
 
+pub const DEFAULT_CLEAN_ENV_ALLOWLIST: [&str; 3] = ["PATH", "HOME", "TERM"];
+
+mod capture;
 mod docker;
 mod tar;
 
+pub use capture::capture_shell_stdout_cached;
 pub use docker::{
     DockerRunError, DockerRunSpec, absolute_docker_mount_candidate, discover_docker_mounts,
     docker_run_command, normalize_docker_mount_path,
@@ -35,6 +44,29 @@ pub struct ProcessLogLine {
 
 pub type ProcessLogSink = Arc<dyn Fn(ProcessLogLine) + Send + Sync + 'static>;
 pub type ProcessCancelCheck = Arc<dyn Fn() -> bool + Send + Sync + 'static>;
+pub type ProcessProgressSink = Arc<dyn Fn(u8) + Send + Sync + 'static>;
+
+static TRACE_COMMANDS: AtomicBool = AtomicBool::new(false);
+
+// `gaia run --trace-exec`: while the guard lives, every command started through
+// `run_command_with_timeout_and_retention` is first logged as `TRACE: <command>`, rendered and
+// redacted like a dry-run `PlannedCommand`. The switch is process-wide because commands start deep
+// inside providers that never see the run's arguments.
+pub struct CommandTraceGuard {
+    previous: bool,
+}
+
+pub fn trace_commands(enabled: bool) -> CommandTraceGuard {
+    CommandTraceGuard {
+        previous: TRACE_COMMANDS.swap(enabled, Ordering::SeqCst),
+    }
+}
+
+impl Drop for CommandTraceGuard {
+    fn drop(&mut self) {
+        TRACE_COMMANDS.store(self.previous, Ordering::SeqCst);
+    }
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ProcessRetryBackoffStrategy {
@@ -56,6 +88,86 @@ pub struct ProcessRunError {
     pub message: String,
 }
 
+// How a finished command that did not succeed ended: its own exit code, or the signal that killed
+// it. A `Signal(9)` with no timeout or cancellation behind it usually means the kernel's OOM
+// killer, which a plain exit code would hide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessExit {
+    Code(i32),
+    Signal(i32),
+}
+
+impl ProcessExit {
+    // `None` for a successful status.
+    pub fn from_status(status: &ExitStatus) -> Option<Self> {
+        if status.success() {
+            return None;
+        }
+        if let Some(code) = status.code() {
+            return Some(Self::Code(code));
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt;
+            if let Some(signal) = status.signal() {
+                return Some(Self::Signal(signal));
+            }
+        }
+        Some(Self::Code(1))
+    }
+
+    // `docker run` reports a container process killed by signal N as exit 128+N, 137 for the
+    // OOM killer, so those codes read as the signal. A process that exits with such a code on
+    // its own is indistinguishable and reads as a signal too.
+    pub fn from_container_status(status: &ExitStatus) -> Option<Self> {
+        match Self::from_status(status)? {
+            Self::Code(code) if (129..=192).contains(&code) => Some(Self::Signal(code - 128)),
+            exit => Some(exit),
+        }
+    }
+
+    pub fn code(self) -> Option<i32> {
+        match self {
+            Self::Code(code) => Some(code),
+            Self::Signal(_) => None,
+        }
+    }
+
+    pub fn signal(self) -> Option<i32> {
+        match self {
+            Self::Code(_) => None,
+            Self::Signal(signal) => Some(signal),
+        }
+    }
+}
+
+impl fmt::Display for ProcessExit {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Code(code) => write!(formatter, "exit code {code}"),
+            Self::Signal(signal) => match signal_name(signal) {
+                Some(name) => write!(formatter, "signal {signal} ({name})"),
+                None => write!(formatter, "signal {signal}"),
+            },
+        }
+    }
+}
+
+fn signal_name(signal: i32) -> Option<&'static str> {
+    Some(match signal {
+        1 => "SIGHUP",
+        2 => "SIGINT",
+        3 => "SIGQUIT",
+        6 => "SIGABRT",
+        7 => "SIGBUS",
+        9 => "SIGKILL",
+        11 => "SIGSEGV",
+        13 => "SIGPIPE",
+        15 => "SIGTERM",
+        _ => return None,
+    })
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ProcessRunResult {
     pub output: Output,
@@ -82,6 +194,48 @@ impl Default for ProcessOutputRetention {
     }
 }
 
+// `clean` starts children from an empty environment that only carries the
+// default allowlist, the configured `allowlist` names and any variables the
+// command set explicitly. `vars` are set on every command either way.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProcessEnvironment {
+    pub clean: bool,
+    pub allowlist: Vec<String>,
+    pub vars: BTreeMap<String, String>,
+}
+
+impl ProcessEnvironment {
+    pub fn apply(&self, command: &mut Command) {
+        command.envs(&self.vars);
+        if !self.clean {
+            return;
+        }
+        let explicit = command
+            .get_envs()
+            .map(|(key, value)| (key.to_os_string(), value.map(OsStr::to_os_string)))
+            .collect::<Vec<_>>();
+        command.env_clear();
+        let names = DEFAULT_CLEAN_ENV_ALLOWLIST
+            .into_iter()
+            .chain(self.allowlist.iter().map(String::as_str));
+        for name in names {
+            if let Some(value) = std::env::var_os(name) {
+                command.env(name, value);
+            }
+        }
+        for (key, value) in explicit {
+            match value {
+                Some(value) => {
+                    command.env(key, value);
+                }
+                None => {
+                    command.env_remove(key);
+                }
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 enum StreamMessage {
     Data {
@@ -199,6 +353,19 @@ pub fn run_command_with_timeout_and_retention(
         timeout_seconds = timeout.as_secs(),
         "starting process"
     );
+    if TRACE_COMMANDS.load(Ordering::SeqCst) {
+        let line = format!(
+            "TRACE: {}",
+            PlannedCommand::from_command(label, command).render()
+        );
+        match &sink {
+            Some(sink) => sink(ProcessLogLine {
+                stream: ProcessLogStream::Stderr,
+                line,
+            }),
+            None => tracing::info!(command_label = label, "{line}"),
+        }
+    }
     command.stdout(Stdio::piped()).stderr(Stdio::piped());
     configure_process_group(command);
     let mut child = command.spawn().map_err(|error| {
@@ -217,6 +384,7 @@ pub fn run_command_with_timeout_and_retention(
     })?;
     let child_id = child.id();
     let process_group = ProcessGroup::for_child(&child);
+    let _running = process_group.register();
     tracing::debug!(
         command_label = label,
         command_program = %description.program,
@@ -334,6 +502,67 @@ pub fn run_command_with_timeout_and_retention(
     })
 }
 
+// A command as it would be started, captured for dry runs instead of being spawned. Arguments
+// and explicitly set env values go through the same redaction as process start logs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedCommand {
+    pub label: String,
+    pub program: String,
+    pub args: Vec<String>,
+    pub cwd: Option<String>,
+    pub env: Vec<(String, String)>,
+}
+
+impl PlannedCommand {
+    pub fn from_command(label: impl Into<String>, command: &Command) -> Self {
+        let description = command_description(command);
+        Self {
+            label: label.into(),
+            program: description.program,
+            args: description.args,
+            cwd: description.cwd,
+            env: command
+                .get_envs()
+                .filter_map(|(key, value)| {
+                    let key = os_str_lossy(key);
+                    let value = value.map(os_str_lossy)?;
+                    let value = if sensitive_name(&key) {
+                        "<redacted>".to_string()
+                    } else {
+                        value
+                    };
+                    Some((key, value))
+                })
+                .collect(),
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(cwd) = &self.cwd {
+            parts.push(format!("cd {} &&", shell_quote(cwd)));
+        }
+        for (key, value) in &self.env {
+            parts.push(format!("{key}={}", shell_quote(value)));
+        }
+        parts.push(shell_quote(&self.program));
+        parts.extend(self.args.iter().map(|arg| shell_quote(arg)));
+        parts.join(" ")
+    }
+}
+
+fn shell_quote(value: &str) -> String {
+    let plain = !value.is_empty()
+        && value
+            .chars()
+            .all(|character| character.is_ascii_alphanumeric() || "-_./=:,@%+".contains(character));
+    if plain {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct ProcessCommandDescription {
     program: String,
@@ -529,6 +758,51 @@ impl ProcessGroup {
     }
 }
 
+// Process groups of the commands running right now, kept lock-free so a signal handler can read
+// them. A zero slot is free; once every slot is taken further groups go unlisted.
+#[cfg(unix)]
+static RUNNING_GROUPS: [AtomicI32; 256] = [const { AtomicI32::new(0) }; 256];
+
+// Clears the group's slot when the command's run ends.
+struct RunningGroup {
+    #[cfg(unix)]
+    slot: Option<usize>,
+}
+
+impl ProcessGroup {
+    fn register(self) -> RunningGroup {
+        RunningGroup {
+            #[cfg(unix)]
+            slot: RUNNING_GROUPS.iter().position(|slot| {
+                slot.compare_exchange(0, self.pgid, Ordering::SeqCst, Ordering::SeqCst)
+                    .is_ok()
+            }),
+        }
+    }
+}
+
+impl Drop for RunningGroup {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let Some(slot) = self.slot {
+            RUNNING_GROUPS[slot].store(0, Ordering::SeqCst);
+        }
+    }
+}
+
+// Sends SIGKILL to the process group of every command still running. It only loads atomics and
+// calls `kill`, so it is async-signal-safe: Gaia's interrupt handler calls it before exiting on a
+// second signal, since the groups never see the terminal's signals themselves.
+pub fn kill_running_process_groups() {
+    #[cfg(unix)]
+    for slot in &RUNNING_GROUPS {
+        let pgid = slot.load(Ordering::SeqCst);
+        if pgid > 0 {
+            terminate_leftover_tree(ProcessGroup { pgid });
+        }
+    }
+}
+
 fn terminate_child_tree(child: &mut Child, group: ProcessGroup) {
     terminate_leftover_tree(group);
     let _ = child.kill();
@@ -35,6 +35,95 @@ fn command_description_captures_program_args_and_cwd() {
     assert_eq!(description.cwd.as_deref(), Some("/tmp"));
 }
 
+#[cfg(unix)]
+#[test]
+fn running_process_groups_are_listed_until_their_run_ends() {
+    let mut command = Command::new("sleep");
+    command.arg("30");
+    configure_process_group(&mut command);
+    let mut child = command.spawn().expect("spawn sleep");
+    let group = ProcessGroup::for_child(&child);
+    let listed = || {
+        RUNNING_GROUPS
+            .iter()
+            .any(|slot| slot.load(Ordering::SeqCst) == group.pgid)
+    };
+
+    let running = group.register();
+    assert!(listed());
+    drop(running);
+    assert!(!listed());
+
+    terminate_child_tree(&mut child, group);
+}
+
+#[cfg(unix)]
+#[test]
+fn process_exit_tells_exit_codes_from_signals() {
+    let status = |script: &str| {
+        Command::new("sh")
+            .arg("-c")
+            .arg(script)
+            .status()
+            .expect("sh runs")
+    };
+
+    assert_eq!(ProcessExit::from_status(&status("exit 0")), None);
+    let exited = ProcessExit::from_status(&status("exit 3")).expect("failed");
+    assert_eq!(exited, ProcessExit::Code(3));
+    assert_eq!(exited.to_string(), "exit code 3");
+    let killed = ProcessExit::from_status(&status("kill -9 $$")).expect("killed");
+    assert_eq!(killed.signal(), Some(9));
+    assert_eq!(killed.code(), None);
+    assert_eq!(killed.to_string(), "signal 9 (SIGKILL)");
+}
+
+#[cfg(unix)]
+#[test]
+fn container_exit_codes_above_128_read_as_signals() {
+    let status = |script: &str| {
+        Command::new("sh")
+            .arg("-c")
+            .arg(script)
+            .status()
+            .expect("sh runs")
+    };
+
+    assert_eq!(
+        ProcessExit::from_container_status(&status("exit 137")),
+        Some(ProcessExit::Signal(9))
+    );
+    assert_eq!(
+        ProcessExit::from_container_status(&status("exit 125")),
+        Some(ProcessExit::Code(125))
+    );
+    assert_eq!(
+        ProcessExit::from_status(&status("exit 137")),
+        Some(ProcessExit::Code(137))
+    );
+}
+
+#[test]
+fn planned_command_renders_cwd_env_and_redacts_secrets() {
+    let mut command = Command::new("make");
+    command
+        .arg("O=/tmp/out dir")
+        .arg("--password")
+        .arg("hunter2")
+        .env("BR2_EXTERNAL", "/work/external")
+        .env("DEPLOY_TOKEN", "abc123")
+        .env_remove("MAKEFLAGS")
+        .current_dir("/work/buildroot");
+
+    let planned = PlannedCommand::from_command("buildroot make", &command);
+
+    assert_eq!(planned.label, "buildroot make");
+    assert_eq!(
+        planned.render(),
+        "cd /work/buildroot && BR2_EXTERNAL=/work/external DEPLOY_TOKEN='<redacted>' make 'O=/tmp/out dir' --password '<redacted>'"
+    );
+}
+
 #[test]
 fn run_command_reports_cancellation() {
     let cancelled = Arc::new(AtomicBool::new(false));
@@ -169,6 +258,79 @@ fn clone_command_preserves_program_args_cwd_and_env() {
     );
 }
 
+#[test]
+fn clean_process_environment_keeps_only_allowlisted_and_explicit_vars() {
+    let mut command = Command::new("env");
+    command.env("GAIA_CLEAN_ENV_EXPLICIT", "yes");
+    ProcessEnvironment {
+        clean: true,
+        allowlist: vec!["GAIA_CLEAN_ENV_UNSET".into()],
+        ..ProcessEnvironment::default()
+    }
+    .apply(&mut command);
+
+    let result = run_command_with_timeout(&mut command, Duration::from_secs(5), "env", None, None)
+        .expect("env should run");
+
+    assert!(result.output.status.success());
+    let names = result
+        .stdout_lines
+        .iter()
+        .filter_map(|line| line.split_once('=').map(|(name, _)| name.to_string()))
+        .collect::<Vec<_>>();
+    assert!(names.iter().any(|name| name == "GAIA_CLEAN_ENV_EXPLICIT"));
+    for name in &names {
+        assert!(
+            DEFAULT_CLEAN_ENV_ALLOWLIST.contains(&name.as_str())
+                || name == "GAIA_CLEAN_ENV_EXPLICIT",
+            "unexpected inherited variable {name}"
+        );
+    }
+}
+
+#[test]
+fn traced_commands_log_a_redacted_trace_line_before_starting() {
+    let lines = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let sink: ProcessLogSink = {
+        let lines = lines.clone();
+        Arc::new(move |line: ProcessLogLine| lines.lock().expect("lines").push(line.line))
+    };
+    let mut command = Command::new("echo");
+    command
+        .arg("built")
+        .current_dir("/tmp")
+        .env("GAIA_BUILD_DIR", "/tmp/build")
+        .env("API_TOKEN", "s3cret");
+
+    let guard = trace_commands(true);
+    run_command_with_timeout(
+        &mut command,
+        Duration::from_secs(10),
+        "trace-test",
+        Some(sink.clone()),
+        None,
+    )
+    .expect("traced command runs");
+    drop(guard);
+    run_command_with_timeout(
+        &mut command,
+        Duration::from_secs(10),
+        "trace-test",
+        Some(sink),
+        None,
+    )
+    .expect("untraced command runs");
+
+    let lines = lines.lock().expect("lines");
+    assert_eq!(lines.len(), 3, "{lines:?}");
+    assert!(lines[0].starts_with("TRACE: cd /tmp && "), "{}", lines[0]);
+    assert!(lines[0].contains("GAIA_BUILD_DIR=/tmp/build"));
+    assert!(lines[0].contains("API_TOKEN='<redacted>'"));
+    assert!(!lines[0].contains("s3cret"));
+    assert!(lines[0].ends_with(" echo built"));
+    assert_eq!(lines[1..], ["built", "built"]);
+}
+
 #[test]
 fn run_command_retains_bounded_output_tail() {
     let result = run_command_with_timeout(
@@ -4,6 +4,7 @@ mod model;
 mod output;
 mod provenance;
 mod rebuild;
+mod sbom;
 mod selection;
 mod state;
 mod summary;
@@ -19,6 +20,7 @@ pub use model::*;
 pub use output::write_report_bundle;
 pub use provenance::render_provenance;
 pub use rebuild::render_rebuild_reasons;
+pub use sbom::render_sbom;
 pub use selection::render_selection;
 pub use summary::{render_execution_failures, render_summary};
 
@@ -46,5 +48,7 @@ pub fn generate_report(
         manifest: manifest::render_manifest_with_outcome(spec, plan, outcome),
         rebuild_reasons: rebuild::render_rebuild_reasons(spec, plan, outcome),
         execution_failures: summary::render_execution_failures(outcome),
+        sbom: (spec.reporting.outputs.sbom && !sbom::image_left_in_place(plan, outcome))
+            .then(|| sbom::render_sbom(spec, outcome)),
     }
 }
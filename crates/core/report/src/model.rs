@@ -10,11 +10,15 @@ pub struct ReportBundle {
     pub manifest: ManifestReport,
     pub rebuild_reasons: Vec<RebuildReasonReport>,
     pub execution_failures: Vec<ExecutionFailureReport>,
+    // Only rendered when `reporting.sbom` is enabled.
+    pub sbom: Option<SbomReport>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct RunSummary {
     pub build_name: String,
+    // The executor's per-invocation id; `None` when the run stopped before executing anything.
+    pub run_id: Option<String>,
     pub build_version: Option<String>,
     pub build_description: Option<String>,
     pub build_branch: Option<String>,
@@ -29,6 +33,8 @@ pub struct RunSummary {
     pub image_reused: bool,
     pub image_reuse_details: Vec<String>,
     pub rolled_back_operations: usize,
+    // Operations left unrun under `fail_fast = false` because an upstream operation failed.
+    pub skipped_operations: Vec<String>,
     pub cleanup_failure_count: usize,
     pub source_count: usize,
     pub artifact_count: usize,
@@ -63,6 +69,7 @@ pub enum FailureClass {
     BackendCommand,
     PolicyBlocked,
     RuntimeState,
+    DiskFull,
     Unknown,
 }
 
@@ -77,6 +84,10 @@ pub struct ExecutionFailureReport {
     pub cleanup_paths: Vec<String>,
     pub cleanup_status: CleanupStatus,
     pub cleanup_failures: Vec<String>,
+    // Exactly one is set when the failure came from a command: its exit code, or the signal that
+    // killed it (9 for an OOM kill).
+    pub exit_code: Option<i32>,
+    pub signal: Option<i32>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
@@ -290,6 +301,41 @@ pub struct ManifestCheckpointRecord {
     pub backend_state: BTreeMap<String, String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SbomReport {
+    pub format: &'static str,
+    pub format_version: u32,
+    pub build_name: String,
+    pub build_version: Option<String>,
+    pub run_id: Option<String>,
+    pub images: Vec<SbomImageRecord>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SbomImageRecord {
+    pub provider_id: String,
+    pub rootfs_dir: Option<String>,
+    pub archive: Option<SbomFileRecord>,
+    pub packages: Vec<SbomPackageRecord>,
+    pub files: Vec<SbomFileRecord>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SbomPackageRecord {
+    pub name: String,
+    pub version: Option<String>,
+    pub file_count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SbomFileRecord {
+    pub path: String,
+    pub bytes: u64,
+    pub sha256: String,
+    // The package that installed the file, when the image provider reports packages.
+    pub package: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct RebuildReasonReport {
     pub operation_id: String,
@@ -322,6 +368,7 @@ pub enum ReportFileKind {
     Provenance,
     Manifest,
     RebuildReasons,
+    Sbom,
 }
 
 impl ReportFileKind {
@@ -332,6 +379,7 @@ impl ReportFileKind {
             Self::Provenance => "provenance",
             Self::Manifest => "manifest",
             Self::RebuildReasons => "rebuild-reasons",
+            Self::Sbom => "sbom",
         }
     }
 }
@@ -19,7 +19,7 @@ pub fn write_report_bundle(
     fs::create_dir_all(&report_dir)?;
 
     let build_name = spec.build_name().replace(['/', '\\', ' '], "-");
-    let files = vec![
+    let mut files = vec![
         write_json_file(
             &report_dir,
             &format!("{build_name}.summary.json"),
@@ -51,6 +51,14 @@ pub fn write_report_bundle(
             &report.rebuild_reasons,
         )?,
     ];
+    if let Some(sbom) = &report.sbom {
+        files.push(write_json_file(
+            &report_dir,
+            &format!("{build_name}.sbom.json"),
+            ReportFileKind::Sbom,
+            sbom,
+        )?);
+    }
 
     tracing::debug!(
         build = %spec.build_name(),
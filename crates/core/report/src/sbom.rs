@@ -0,0 +1,146 @@
+use gaia_exec::ExecutionOutcome;
+use gaia_plan::ExecutionPlan;
+use gaia_spec::ResolvedBuildSpec;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+use crate::model::{SbomFileRecord, SbomImageRecord, SbomPackageRecord, SbomReport};
+
+const SBOM_FORMAT: &str = "gaia-sbom";
+const SBOM_FORMAT_VERSION: u32 = 1;
+
+// The SBOM lists, per image result that left something behind, the packages the image provider reported and
+// every regular file in the finished rootfs with its size and sha256. Symlinks and
+// other special files are left out; directories are walked in name order so the
+// output is stable between runs of the same tree.
+pub fn render_sbom(spec: &ResolvedBuildSpec, outcome: &ExecutionOutcome) -> SbomReport {
+    SbomReport {
+        format: SBOM_FORMAT,
+        format_version: SBOM_FORMAT_VERSION,
+        build_name: spec.identity.build_name.clone(),
+        build_version: spec.identity.version.clone(),
+        run_id: outcome.run_id.clone(),
+        images: outcome
+            .image_results
+            .iter()
+            .filter(|result| {
+                result.rootfs_dir.is_some()
+                    || result.archive_path.is_some()
+                    || !result.packages.is_empty()
+            })
+            .map(|result| {
+                let file_owners = result
+                    .packages
+                    .iter()
+                    .flat_map(|package| {
+                        package
+                            .files
+                            .iter()
+                            .map(|file| (file.as_str(), package.name.as_str()))
+                    })
+                    .collect::<BTreeMap<_, _>>();
+                let mut files = Vec::new();
+                if let Some(rootfs_dir) = &result.rootfs_dir {
+                    collect_files(rootfs_dir, rootfs_dir, &mut files);
+                }
+                for file in &mut files {
+                    file.package = file_owners
+                        .get(file.path.as_str())
+                        .map(|package| package.to_string());
+                }
+                SbomImageRecord {
+                    provider_id: result.provider_id.clone(),
+                    rootfs_dir: result
+                        .rootfs_dir
+                        .as_ref()
+                        .map(|path| path.display().to_string()),
+                    archive: result
+                        .archive_path
+                        .as_ref()
+                        .filter(|path| path.is_file())
+                        .map(|path| file_record(path, path.display().to_string())),
+                    packages: result
+                        .packages
+                        .iter()
+                        .map(|package| SbomPackageRecord {
+                            name: package.name.clone(),
+                            version: package.version.clone(),
+                            file_count: package.files.len(),
+                        })
+                        .collect(),
+                    files,
+                }
+            })
+            .collect(),
+    }
+}
+
+// Every image operation was reused or left out of the plan, so the run has no image result to
+// describe. Rendering then would replace the SBOM of the image still in place with an empty one,
+// so the previous file is kept instead.
+pub(crate) fn image_left_in_place(plan: &ExecutionPlan, outcome: &ExecutionOutcome) -> bool {
+    outcome.image_results.is_empty()
+        && plan
+            .operations
+            .iter()
+            .filter(|operation| operation.kind.phase() == Some("images"))
+            .all(|operation| outcome.reused_ids.contains(&operation.id))
+}
+
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<SbomFileRecord>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut entries = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .collect::<Vec<_>>();
+    entries.sort();
+    for path in entries {
+        let Ok(metadata) = fs::symlink_metadata(&path) else {
+            continue;
+        };
+        if metadata.is_dir() {
+            collect_files(root, &path, files);
+        } else if metadata.is_file() {
+            let relative = path
+                .strip_prefix(root)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .into_owned();
+            files.push(file_record(&path, relative));
+        }
+    }
+}
+
+fn file_record(path: &Path, display_path: String) -> SbomFileRecord {
+    SbomFileRecord {
+        path: display_path,
+        bytes: fs::metadata(path)
+            .map(|metadata| metadata.len())
+            .unwrap_or(0),
+        sha256: sha256_file(path).unwrap_or_else(|error| format!("sha256-error:{error}")),
+        package: None,
+    }
+}
+
+fn sha256_file(path: &Path) -> io::Result<String> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 8192];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
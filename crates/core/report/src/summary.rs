@@ -50,6 +50,7 @@ pub fn render_summary(
         .collect::<Vec<_>>();
     RunSummary {
         build_name: spec.identity.display_name.clone(),
+        run_id: outcome.run_id.clone(),
         build_version: spec.identity.version.clone(),
         build_description: spec.metadata.description.clone(),
         build_branch: spec.metadata.branch.clone(),
@@ -64,6 +65,11 @@ pub fn render_summary(
         image_reused: outcome.image_results.iter().any(|result| result.reused),
         image_reuse_details,
         rolled_back_operations: outcome.rolled_back_ids.len(),
+        skipped_operations: outcome
+            .skipped_ids
+            .iter()
+            .map(|id| id.as_str().to_string())
+            .collect(),
         cleanup_failure_count: outcome.cleanup_failures.len(),
         rollback_on_error: spec.policy.failure.rollback_on_error,
         preserve_failed_outputs: spec.policy.failure.preserve_failed_outputs,
@@ -101,6 +107,8 @@ pub fn render_execution_failures(outcome: &ExecutionOutcome) -> Vec<ExecutionFai
                 .collect(),
             cleanup_status: cleanup_status(error.cleanup_status),
             cleanup_failures: error.cleanup_failures.clone(),
+            exit_code: error.exit.and_then(|exit| exit.code()),
+            signal: error.exit.and_then(|exit| exit.signal()),
         })
         .collect()
 }
@@ -137,6 +145,7 @@ fn classify_execution_error(error: &ExecutionError) -> FailureClass {
         gaia_exec::ExecutionErrorKind::BackendCommand => FailureClass::BackendCommand,
         gaia_exec::ExecutionErrorKind::PolicyBlocked => FailureClass::PolicyBlocked,
         gaia_exec::ExecutionErrorKind::RuntimeState => FailureClass::RuntimeState,
+        gaia_exec::ExecutionErrorKind::DiskFull => FailureClass::DiskFull,
         gaia_exec::ExecutionErrorKind::Unknown => match error.code {
             code if code.starts_with("missing_") => {
                 if code.ends_with("_provider") {
@@ -152,7 +161,9 @@ fn classify_execution_error(error: &ExecutionError) -> FailureClass {
 
 fn classify_execution_message(message: &str) -> FailureClass {
     let lowered = message.to_ascii_lowercase();
-    if lowered.contains("failed to start ") {
+    if lowered.contains("no space left on device") || lowered.contains("out of disk space") {
+        FailureClass::DiskFull
+    } else if lowered.contains("failed to start ") {
         FailureClass::ToolStart
     } else if lowered.contains("timed out") {
         FailureClass::Timeout
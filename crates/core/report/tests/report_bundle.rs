@@ -328,3 +328,103 @@ fn writes_report_bundle_files_with_sizes() {
             .any(|file| matches!(file.kind, ReportFileKind::Selection))
     );
 }
+
+#[test]
+fn writes_sbom_of_the_image_rootfs_when_enabled() {
+    let mut spec = test_spec();
+    spec.reporting.outputs.sbom = true;
+    if let gaia_spec::ImageDefinition::Buildroot(buildroot) = &mut spec.image.definition {
+        buildroot.allow_fallback = true;
+    }
+    let (source_catalog, artifact_catalog, image_catalog) = provider_catalogs();
+    let validation =
+        validate_spec_with_providers(&spec, &source_catalog, &artifact_catalog, &image_catalog);
+    let plan = plan_build(&spec, &source_catalog, &artifact_catalog, &image_catalog);
+    let outcome = execute_plan(
+        &spec,
+        &plan,
+        ExecutionProviders {
+            source_catalog: &source_catalog,
+            artifact_catalog: &artifact_catalog,
+            image_catalog: &image_catalog,
+        },
+    );
+    let report = generate_report(&spec, &validation, &plan, &outcome);
+
+    let sbom = report.sbom.as_ref().expect("sbom should be rendered");
+    assert_eq!(sbom.format, "gaia-sbom");
+    assert_eq!(sbom.run_id, outcome.run_id);
+    let image = sbom.images.first().expect("image record");
+    assert!(
+        image
+            .rootfs_dir
+            .as_deref()
+            .is_some_and(|dir| dir.ends_with("rootfs"))
+    );
+    assert!(!image.files.is_empty());
+    assert!(
+        image
+            .files
+            .iter()
+            .all(|file| file.sha256.len() == 64 && !file.path.starts_with('/'))
+    );
+
+    let written = write_report_bundle(&spec, &report).expect("report files should be written");
+    assert!(written.files.iter().any(|file| {
+        matches!(file.kind, ReportFileKind::Sbom)
+            && file
+                .path
+                .ends_with(format!("{}.sbom.json", spec.identity.build_name))
+    }));
+}
+
+#[test]
+fn keeps_the_previous_sbom_when_the_image_was_reused() {
+    let mut spec = test_spec();
+    spec.reporting.outputs.sbom = true;
+    let (source_catalog, artifact_catalog, image_catalog) = provider_catalogs();
+    let validation =
+        validate_spec_with_providers(&spec, &source_catalog, &artifact_catalog, &image_catalog);
+    let plan = plan_build(&spec, &source_catalog, &artifact_catalog, &image_catalog);
+    let reused = gaia_exec::ExecutionOutcome {
+        reused_ids: plan
+            .operations
+            .iter()
+            .map(|operation| operation.id.clone())
+            .collect(),
+        ..gaia_exec::ExecutionOutcome::default()
+    };
+
+    let report = generate_report(&spec, &validation, &plan, &reused);
+
+    assert!(report.sbom.is_none());
+    let written = write_report_bundle(&spec, &report).expect("report files should be written");
+    assert!(
+        !written
+            .files
+            .iter()
+            .any(|file| matches!(file.kind, ReportFileKind::Sbom))
+    );
+}
+
+#[test]
+fn omits_sbom_unless_enabled() {
+    let spec = test_spec();
+    let (source_catalog, artifact_catalog, image_catalog) = provider_catalogs();
+    let validation =
+        validate_spec_with_providers(&spec, &source_catalog, &artifact_catalog, &image_catalog);
+    let plan = plan_build(&spec, &source_catalog, &artifact_catalog, &image_catalog);
+    let outcome = execute_plan(
+        &spec,
+        &plan,
+        ExecutionProviders {
+            source_catalog: &source_catalog,
+            artifact_catalog: &artifact_catalog,
+            image_catalog: &image_catalog,
+        },
+    );
+
+    let report = generate_report(&spec, &validation, &plan, &outcome);
+
+    assert!(report.sbom.is_none());
+}
@@ -4,6 +4,7 @@ use gaia_exec::{
     ExecutionCleanupStatus, ExecutionError, ExecutionErrorKind, ExecutionEvent, ExecutionOutcome,
 };
 use gaia_plan::{OperationId, plan_build};
+use gaia_process::ProcessExit;
 use gaia_report::generate_report;
 use gaia_spec::RollbackDomain;
 use gaia_validate::validate_spec_with_providers;
@@ -26,6 +27,7 @@ fn execution_error(
         cleanup_paths: Vec::new(),
         cleanup_status: ExecutionCleanupStatus::NotRequired,
         cleanup_failures: Vec::new(),
+        exit: None,
     }
 }
 
@@ -184,6 +186,44 @@ fn failed_execution_report_preserves_output_tail() {
     assert_eq!(report.execution_failures[0].output_tail, output_tail);
 }
 
+#[test]
+fn failed_execution_report_tells_exit_codes_from_signals() {
+    let spec = test_spec();
+    let (source_catalog, artifact_catalog, image_catalog) = provider_catalogs();
+    let validation =
+        validate_spec_with_providers(&spec, &source_catalog, &artifact_catalog, &image_catalog);
+    let plan = plan_build(&spec, &source_catalog, &artifact_catalog, &image_catalog);
+    let failed = |exit| ExecutionError {
+        exit: Some(exit),
+        ..execution_error(
+            "artifact_execution_failed",
+            ExecutionErrorKind::BackendCommand,
+            OperationId::artifact(&spec.artifacts[0].id),
+            "artifact failed",
+            Vec::new(),
+        )
+    };
+    let outcome = ExecutionOutcome {
+        errors: vec![failed(ProcessExit::Signal(9)), failed(ProcessExit::Code(2))],
+        ..ExecutionOutcome::default()
+    };
+
+    let report = generate_report(&spec, &validation, &plan, &outcome);
+
+    let exits = report
+        .execution_failures
+        .iter()
+        .map(|failure| (failure.class, failure.exit_code, failure.signal))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        exits,
+        [
+            (gaia_report::FailureClass::BackendCommand, None, Some(9)),
+            (gaia_report::FailureClass::BackendCommand, Some(2), None),
+        ]
+    );
+}
+
 #[test]
 fn failed_execution_report_includes_cleanup_status_and_failures() {
     let spec = test_spec();
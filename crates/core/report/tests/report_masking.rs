@@ -13,6 +13,7 @@ fn masks_sensitive_env_values_in_provenance() {
         &default_config_path(),
         &gaia_config::ResolveOptions {
             preset: Some("ci".into()),
+            profile: None,
             env_files: vec!["runtime.env".into()],
             env_overrides: vec![
                 ("API_TOKEN".into(), "super-secret-token".into()),
@@ -12,6 +12,8 @@ pub struct ArtifactSpec {
     pub dependencies: Vec<ArtifactRef>,
     pub output: ArtifactOutputSpec,
     pub install_identity: Option<ArtifactInstallIdentitySpec>,
+    // Artifacts sharing a group never build at the same time; the group implies no ordering.
+    pub mutex_group: Option<String>,
 }
 
 impl ArtifactSpec {
@@ -31,6 +33,7 @@ impl ArtifactSpec {
             dependencies: Vec::new(),
             output,
             install_identity: None,
+            mutex_group: None,
         }
     }
 
@@ -76,11 +79,34 @@ pub struct RustArtifactSpec {
     pub package: String,
     pub target_name: Option<String>,
     pub variant: ArtifactVariantSpec,
+    // Cross-compilation settings applied to the artifact `target`; both require one.
+    pub linker: Option<String>,
+    pub sysroot: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct JavaArtifactSpec {
+    // A path or glob relative to the source dir, `target/` or `build/libs/`.
     pub build_target: String,
+    // `None` picks the tool from the build files present in the source dir.
+    pub build_tool: Option<JavaBuildToolSpec>,
+    // Replaces the default `package` goal or `build` task, e.g. `["shadowJar"]`.
+    pub tasks: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JavaBuildToolSpec {
+    Maven,
+    Gradle,
+}
+
+impl JavaBuildToolSpec {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Maven => "maven",
+            Self::Gradle => "gradle",
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
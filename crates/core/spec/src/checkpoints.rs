@@ -1,4 +1,9 @@
-use crate::{IdError, InstallId, StageItemId};
+use std::path::{Path, PathBuf};
+
+use crate::{
+    ArtifactId, IdError, InstallId, SourceId, StageItemId, WorkspacePathError, WorkspaceSpec,
+    resolve_workspace_path,
+};
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct CheckpointSpec {
@@ -9,9 +14,72 @@ pub struct CheckpointSpec {
 pub struct CheckpointPointSpec {
     pub id: CheckpointId,
     pub backend: Option<CheckpointBackendRef>,
+    pub fallback_backends: Vec<CheckpointBackendRef>,
+    pub upload_backends: Vec<CheckpointBackendRef>,
     pub use_policy: CheckpointPolicy,
     pub upload_policy: CheckpointPolicy,
     pub anchor: CheckpointAnchorRef,
+    pub fingerprint_from: Vec<CheckpointFingerprintInput>,
+    // Workspace path a restore writes the anchor outputs under instead of their own locations.
+    pub restore_to: Option<String>,
+}
+
+impl CheckpointPointSpec {
+    // Restore tries these in order and stops at the first backend holding the checkpoint.
+    pub fn restore_backends(&self) -> impl Iterator<Item = &CheckpointBackendRef> {
+        self.backend.iter().chain(&self.fallback_backends)
+    }
+
+    // Uploads go to every listed backend; without an explicit list they follow the restore order.
+    pub fn upload_targets(&self) -> Vec<&CheckpointBackendRef> {
+        if self.upload_backends.is_empty() {
+            self.restore_backends().collect()
+        } else {
+            self.upload_backends.iter().collect()
+        }
+    }
+
+    // Anchor outputs are captured relative to the workspace root, so restoring under this root
+    // rebuilds the same layout: the workspace root itself by default, or `restore_to`, which has
+    // to stay inside the workspace.
+    pub fn restore_root(&self, workspace: &WorkspaceSpec) -> Result<PathBuf, WorkspacePathError> {
+        let root = resolve_workspace_path(workspace, ".")?;
+        let Some(restore_to) = &self.restore_to else {
+            return Ok(root);
+        };
+        let path = resolve_workspace_path(workspace, restore_to)?;
+        if root == Path::new(".") || path.starts_with(&root) {
+            Ok(path)
+        } else {
+            Err(WorkspacePathError::ParentTraversal {
+                raw: restore_to.clone(),
+            })
+        }
+    }
+}
+
+// Extra inputs mixed into a checkpoint fingerprint; commands run in the workspace root and
+// contribute their stdout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckpointFingerprintInput {
+    Path(String),
+    Command(String),
+}
+
+impl CheckpointFingerprintInput {
+    pub fn parse(raw: &str) -> Self {
+        match raw.strip_prefix("cmd:") {
+            Some(command) => Self::Command(command.trim().to_string()),
+            None => Self::Path(raw.to_string()),
+        }
+    }
+
+    pub fn as_str(&self) -> String {
+        match self {
+            Self::Path(path) => path.clone(),
+            Self::Command(command) => format!("cmd:{command}"),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -95,6 +163,8 @@ pub enum CheckpointPolicy {
 pub enum CheckpointAnchorRef {
     #[default]
     Image,
+    Source(SourceId),
+    Artifact(ArtifactId),
     Install(InstallId),
     StageFile(StageItemId),
     StageEnvSet(StageItemId),
@@ -106,6 +176,8 @@ impl CheckpointAnchorRef {
     pub fn as_str(&self) -> String {
         match self {
             Self::Image => "image".to_string(),
+            Self::Source(id) => format!("source:{}", id.as_str()),
+            Self::Artifact(id) => format!("artifact:{}", id.as_str()),
             Self::Install(id) => format!("install:{}", id.as_str()),
             Self::StageFile(id) => format!("stage-file:{}", id.as_str()),
             Self::StageEnvSet(id) => format!("stage-env:{}", id.as_str()),
@@ -125,6 +197,50 @@ impl std::fmt::Display for CheckpointAnchorRef {
 mod tests {
     use super::*;
 
+    #[test]
+    fn restore_root_defaults_to_the_workspace_and_rejects_paths_outside_it() {
+        let workspace = WorkspaceSpec {
+            root_dir: "/repo".into(),
+            ..WorkspaceSpec::default()
+        };
+        let mut checkpoint = CheckpointPointSpec {
+            id: CheckpointId::new("base"),
+            backend: None,
+            fallback_backends: Vec::new(),
+            upload_backends: Vec::new(),
+            use_policy: CheckpointPolicy::Off,
+            upload_policy: CheckpointPolicy::Off,
+            anchor: CheckpointAnchorRef::Image,
+            fingerprint_from: Vec::new(),
+            restore_to: None,
+        };
+        assert_eq!(
+            checkpoint.restore_root(&workspace),
+            Ok(PathBuf::from("/repo"))
+        );
+
+        checkpoint.restore_to = Some("tmp/restore".into());
+        assert_eq!(
+            checkpoint.restore_root(&workspace),
+            Ok(PathBuf::from("/repo/tmp/restore"))
+        );
+        checkpoint.restore_to = Some("/repo/tmpfs".into());
+        assert_eq!(
+            checkpoint.restore_root(&workspace),
+            Ok(PathBuf::from("/repo/tmpfs"))
+        );
+
+        for outside in ["../elsewhere", "/dev/shm/restore"] {
+            checkpoint.restore_to = Some(outside.into());
+            assert_eq!(
+                checkpoint.restore_root(&workspace),
+                Err(WorkspacePathError::ParentTraversal {
+                    raw: outside.into()
+                })
+            );
+        }
+    }
+
     #[test]
     fn checkpoint_id_rejects_empty_fallible_construction() {
         assert_eq!(CheckpointId::try_new(""), Err(IdError::empty()));
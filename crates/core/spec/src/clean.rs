@@ -11,5 +11,7 @@ pub struct CleanProfileSpec {
     pub description: Option<String>,
     pub build: bool,
     pub out: bool,
+    // The cache dirs `gaia clean --target cache` removes.
+    pub cache: bool,
     pub paths: Vec<String>,
 }
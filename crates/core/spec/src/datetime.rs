@@ -0,0 +1,181 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+// 0000-01-01T00:00:00Z and 9999-12-31T23:59:59Z, the range a four-digit RFC3339 year can hold.
+const MIN_UNIX_SECONDS: i64 = -62_167_219_200;
+const MAX_UNIX_SECONDS: i64 = 253_402_300_799;
+
+pub(crate) fn now_unix_seconds() -> i64 {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(elapsed) => i64::try_from(elapsed.as_secs()).unwrap_or(i64::MAX),
+        Err(before) => i64::try_from(before.duration().as_secs()).map_or(i64::MIN, |secs| -secs),
+    }
+}
+
+// Resolves `now`, `now+<n><unit>`, `now-<n><unit>`, or an ISO-8601 date or timestamp to an RFC3339
+// UTC timestamp such as `2026-01-31T12:00:00Z`. The error describes what was expected.
+pub(crate) fn resolve_datetime(value: &str, now: i64) -> Result<String, String> {
+    let seconds = match value.strip_prefix("now") {
+        Some(offset) => now
+            .checked_add(parse_relative_offset(offset).ok_or_else(|| {
+                "a relative time like now, now+7d or now-12h (units s, m, h, d, w)".to_string()
+            })?)
+            .ok_or_else(|| "a relative time within years 0000..=9999".to_string())?,
+        None => parse_timestamp(value).ok_or_else(|| {
+            "now, now+<n><s|m|h|d|w>, or an ISO-8601 date or timestamp".to_string()
+        })?,
+    };
+    if !(MIN_UNIX_SECONDS..=MAX_UNIX_SECONDS).contains(&seconds) {
+        return Err("a time within years 0000..=9999".into());
+    }
+    Ok(format_rfc3339(seconds))
+}
+
+fn parse_relative_offset(offset: &str) -> Option<i64> {
+    if offset.is_empty() {
+        return Some(0);
+    }
+    let (sign, rest) = match offset.as_bytes()[0] {
+        b'+' => (1, &offset[1..]),
+        b'-' => (-1, &offset[1..]),
+        _ => return None,
+    };
+    let unit = rest.chars().last()?;
+    let amount = &rest[..rest.len() - unit.len_utf8()];
+    if amount.is_empty() || !amount.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    let unit_seconds = match unit {
+        's' => 1,
+        'm' => 60,
+        'h' => 3_600,
+        'd' => SECONDS_PER_DAY,
+        'w' => 7 * SECONDS_PER_DAY,
+        _ => return None,
+    };
+    amount
+        .parse::<i64>()
+        .ok()?
+        .checked_mul(unit_seconds)
+        .map(|seconds| sign * seconds)
+}
+
+// Accepts `YYYY-MM-DD` (midnight UTC) and `YYYY-MM-DDTHH:MM[:SS[.fff]][Z|±HH:MM]`; a timestamp
+// without an offset is read as UTC. Fractional seconds are dropped.
+fn parse_timestamp(value: &str) -> Option<i64> {
+    let (date, time) = match value.find(['T', 't', ' ']) {
+        Some(split) => (&value[..split], Some(&value[split + 1..])),
+        None => (value, None),
+    };
+    let mut parts = date.split('-');
+    let year = fixed_digits(parts.next()?, 4)?;
+    let month = fixed_digits(parts.next()?, 2)?;
+    let day = fixed_digits(parts.next()?, 2)?;
+    if parts.next().is_some()
+        || !(1..=12).contains(&month)
+        || day == 0
+        || day > days_in_month(year, month)
+    {
+        return None;
+    }
+    let days = days_from_civil(year, month, day);
+    let Some(time) = time else {
+        return Some(days * SECONDS_PER_DAY);
+    };
+    let (clock, offset_seconds) = split_offset(time)?;
+    let mut fields = clock.split(':');
+    let hour = fixed_digits(fields.next()?, 2)?;
+    let minute = fixed_digits(fields.next()?, 2)?;
+    let second = match fields.next() {
+        Some(second) => {
+            let (whole, fraction) = second.split_once('.').unwrap_or((second, "0"));
+            if fraction.is_empty() || !fraction.bytes().all(|byte| byte.is_ascii_digit()) {
+                return None;
+            }
+            fixed_digits(whole, 2)?
+        }
+        None => 0,
+    };
+    if fields.next().is_some() || hour > 23 || minute > 59 || second > 59 {
+        return None;
+    }
+    Some(days * SECONDS_PER_DAY + hour * 3_600 + minute * 60 + second - offset_seconds)
+}
+
+fn split_offset(time: &str) -> Option<(&str, i64)> {
+    if let Some(clock) = time.strip_suffix(['Z', 'z']) {
+        return Some((clock, 0));
+    }
+    let Some(split) = time.rfind(['+', '-']) else {
+        return Some((time, 0));
+    };
+    let (clock, offset) = time.split_at(split);
+    let sign = if offset.starts_with('-') { -1 } else { 1 };
+    let (hours, minutes) = offset[1..].split_once(':')?;
+    let (hours, minutes) = (fixed_digits(hours, 2)?, fixed_digits(minutes, 2)?);
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    Some((clock, sign * (hours * 3_600 + minutes * 60)))
+}
+
+fn fixed_digits(text: &str, width: usize) -> Option<i64> {
+    if text.len() != width || !text.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
+
+fn format_rfc3339(seconds: i64) -> String {
+    let days = seconds.div_euclid(SECONDS_PER_DAY);
+    let clock = seconds.rem_euclid(SECONDS_PER_DAY);
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        clock / 3_600,
+        clock % 3_600 / 60,
+        clock % 60
+    )
+}
+
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Days since 1970-01-01 in the proleptic Gregorian calendar, counting eras of 400 years from March
+// so the leap day falls at the end of each year.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
@@ -1,5 +1,5 @@
-use crate::{InstallId, SourceId, StageItemId};
-use std::path::Path;
+use crate::{InstallId, SourceId, StageItemId, WorkspaceSpec};
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ImageSpec {
@@ -43,6 +43,8 @@ pub struct ImageFeedSpec {
     pub stage_files: Vec<StageItemId>,
     pub stage_env_sets: Vec<StageItemId>,
     pub stage_services: Vec<StageItemId>,
+    pub stage_overlays: Vec<StageItemId>,
+    pub stage_symlinks: Vec<StageItemId>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -56,6 +58,9 @@ pub struct BuildrootImageSpec {
     pub external_tree: Option<String>,
     pub external_tree_mode: BuildrootExternalTreeModeSpec,
     pub expected_images: Vec<BuildrootExpectedImageSpec>,
+    pub ccache: bool,
+    pub ccache_dir: Option<String>,
+    pub incremental: bool,
 }
 
 impl BuildrootImageSpec {
@@ -66,6 +71,25 @@ impl BuildrootImageSpec {
     pub fn external_tree_path(&self) -> Option<&Path> {
         self.external_tree.as_deref().map(Path::new)
     }
+
+    // A relative `ccache_dir` resolves against the workspace root; without one the cache lives
+    // under `build_dir` so it is shared by every output dir of the workspace.
+    pub fn ccache_dir(&self, workspace: &WorkspaceSpec) -> Option<PathBuf> {
+        if !self.ccache {
+            return None;
+        }
+        Some(match self.ccache_dir.as_deref() {
+            Some(dir) => Path::new(&workspace.root_dir).join(dir),
+            None => Path::new(&workspace.build_dir).join("buildroot-ccache"),
+        })
+    }
+
+    // Buildroot builds into `buildroot-output` under the image collect dir. Incremental images
+    // keep that dir across runs, so rollback and `gaia clean` leave it in place.
+    pub fn preserved_output_dir(&self, collect_dir: &Path) -> Option<PathBuf> {
+        self.incremental
+            .then(|| collect_dir.join("buildroot-output"))
+    }
 }
 
 impl Default for BuildrootImageSpec {
@@ -80,6 +104,9 @@ impl Default for BuildrootImageSpec {
             external_tree: None,
             external_tree_mode: BuildrootExternalTreeModeSpec::Auto,
             expected_images: Vec::new(),
+            ccache: false,
+            ccache_dir: None,
+            incremental: false,
         }
     }
 }
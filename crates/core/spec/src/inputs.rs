@@ -1,7 +1,21 @@
+use crate::datetime::{now_unix_seconds, resolve_datetime};
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct InputSpec {
     pub declared: Vec<InputOptionSpec>,
     pub selected: Vec<(String, String)>,
+    // Which layer supplied each selected value, in the same order as `selected`.
+    pub origins: Vec<(String, InputValueOrigin)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputValueOrigin {
+    Default,
+    Preset,
+    CliOverride,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -12,6 +26,11 @@ pub struct InputOptionSpec {
     pub required: bool,
     pub default: Option<String>,
     pub choices: Vec<String>,
+    // Bounds stay in their rendered form so the spec keeps `Eq`; they are parsed per `kind`.
+    pub min: Option<String>,
+    pub max: Option<String>,
+    // Must match the whole value; kept as text for `Eq` and compiled once per process.
+    pub pattern: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
@@ -19,6 +38,408 @@ pub enum InputKindSpec {
     #[default]
     String,
     Integer,
+    Float,
     Boolean,
     Enum,
+    Datetime,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputValueError {
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl InputOptionSpec {
+    // The single check for a candidate value, shared by validation and the TUI editors.
+    pub fn check_value(&self, value: &str) -> Result<(), InputValueError> {
+        let invalid = |code, expected: String| InputValueError {
+            code,
+            message: format!("input '{}' expects {expected}, got '{value}'", self.name),
+        };
+        match self.kind {
+            InputKindSpec::Integer => {
+                let parsed = value
+                    .parse::<i64>()
+                    .map_err(|_| invalid("input_integer_invalid", "an integer value".into()))?;
+                self.check_range(value, parsed, |bound| bound.parse::<i64>().ok())
+            }
+            InputKindSpec::Float => {
+                let parsed = value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|parsed| parsed.is_finite())
+                    .ok_or_else(|| invalid("input_float_invalid", "a number".into()))?;
+                self.check_range(value, parsed, parse_float_bound)
+            }
+            InputKindSpec::Boolean
+                if !matches!(
+                    value,
+                    "1" | "0" | "true" | "false" | "yes" | "no" | "on" | "off"
+                ) =>
+            {
+                Err(invalid("input_boolean_invalid", "a boolean value".into()))
+            }
+            InputKindSpec::Enum if !self.choices.iter().any(|choice| choice == value) => {
+                Err(invalid(
+                    "input_enum_invalid",
+                    format!("one of [{}]", self.choices.join(", ")),
+                ))
+            }
+            InputKindSpec::Datetime => resolve_datetime(value, now_unix_seconds())
+                .map(|_| ())
+                .map_err(|expected| invalid("input_datetime_invalid", expected)),
+            InputKindSpec::String => self.check_pattern_match(value),
+            _ => Ok(()),
+        }
+    }
+
+    // Reports a pattern that cannot apply or does not compile, like `check_bounds` for min/max.
+    pub fn check_pattern(&self) -> Result<(), InputValueError> {
+        let Some(pattern) = &self.pattern else {
+            return Ok(());
+        };
+        if self.kind != InputKindSpec::String {
+            return Err(InputValueError {
+                code: "input_pattern_unsupported",
+                message: format!(
+                    "input '{}' sets pattern, which only applies to string inputs",
+                    self.name
+                ),
+            });
+        }
+        compiled_pattern(pattern)
+            .map(|_| ())
+            .map_err(|error| self.invalid_pattern(pattern, &error))
+    }
+
+    fn check_pattern_match(&self, value: &str) -> Result<(), InputValueError> {
+        let Some(pattern) = &self.pattern else {
+            return Ok(());
+        };
+        let regex =
+            compiled_pattern(pattern).map_err(|error| self.invalid_pattern(pattern, &error))?;
+        if regex.is_match(value) {
+            return Ok(());
+        }
+        Err(InputValueError {
+            code: "input_pattern_mismatch",
+            message: format!(
+                "input '{}' must match pattern '{pattern}', got '{value}'",
+                self.name
+            ),
+        })
+    }
+
+    fn invalid_pattern(&self, pattern: &str, error: &str) -> InputValueError {
+        InputValueError {
+            code: "input_pattern_invalid",
+            message: format!(
+                "input '{}' has an invalid pattern '{pattern}': {error}",
+                self.name
+            ),
+        }
+    }
+
+    // Reports bounds that cannot apply to this input, so a typo does not silently disable them.
+    pub fn check_bounds(&self) -> Result<(), InputValueError> {
+        if self.min.is_none() && self.max.is_none() {
+            return Ok(());
+        }
+        let parses = |bound: &str| match self.kind {
+            InputKindSpec::Integer => bound.parse::<i64>().is_ok(),
+            InputKindSpec::Float => parse_float_bound(bound).is_some(),
+            _ => false,
+        };
+        if !matches!(self.kind, InputKindSpec::Integer | InputKindSpec::Float) {
+            return Err(InputValueError {
+                code: "input_bound_unsupported",
+                message: format!(
+                    "input '{}' sets min/max, which only apply to integer and float inputs",
+                    self.name
+                ),
+            });
+        }
+        for bound in [&self.min, &self.max].into_iter().flatten() {
+            if !parses(bound) {
+                return Err(InputValueError {
+                    code: "input_bound_invalid",
+                    message: format!(
+                        "input '{}' has a min/max bound '{bound}' that is not a valid {} value",
+                        self.name,
+                        self.kind.as_str()
+                    ),
+                });
+            }
+        }
+        if let (Some(min), Some(max)) = (&self.min, &self.max)
+            && parse_float_bound(min) > parse_float_bound(max)
+        {
+            return Err(InputValueError {
+                code: "input_bound_invalid",
+                message: format!("input '{}' has min {min} greater than max {max}", self.name),
+            });
+        }
+        Ok(())
+    }
+
+    // Human-readable allowed range, for example `1..=64` or `>= 0.5`.
+    pub fn range_label(&self) -> Option<String> {
+        match (&self.min, &self.max) {
+            (Some(min), Some(max)) => Some(format!("{min}..={max}")),
+            (Some(min), None) => Some(format!(">= {min}")),
+            (None, Some(max)) => Some(format!("<= {max}")),
+            (None, None) => None,
+        }
+    }
+
+    fn check_range<T: PartialOrd>(
+        &self,
+        value: &str,
+        parsed: T,
+        parse_bound: impl Fn(&str) -> Option<T>,
+    ) -> Result<(), InputValueError> {
+        let below = self
+            .min
+            .as_deref()
+            .and_then(&parse_bound)
+            .is_some_and(|min| parsed < min);
+        let above = self
+            .max
+            .as_deref()
+            .and_then(&parse_bound)
+            .is_some_and(|max| parsed > max);
+        if below || above {
+            return Err(InputValueError {
+                code: "input_out_of_range",
+                message: format!(
+                    "input '{}' must be within {}, got '{value}'",
+                    self.name,
+                    self.range_label().unwrap_or_default()
+                ),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl InputKindSpec {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Integer => "integer",
+            Self::Float => "float",
+            Self::Boolean => "boolean",
+            Self::Enum => "enum",
+            Self::Datetime => "datetime",
+        }
+    }
+
+    // Datetime values such as `now+7d` become an RFC3339 UTC timestamp; every other kind, and a
+    // datetime that does not parse, is returned as given so validation can report it.
+    pub fn resolve_value(self, value: &str) -> String {
+        match self {
+            Self::Datetime => {
+                resolve_datetime(value, now_unix_seconds()).unwrap_or_else(|_| value.to_string())
+            }
+            _ => value.to_string(),
+        }
+    }
+}
+
+impl InputValueOrigin {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Preset => "preset",
+            Self::CliOverride => "cli-override",
+        }
+    }
+}
+
+impl InputSpec {
+    pub fn origin(&self, name: &str) -> Option<InputValueOrigin> {
+        self.origins
+            .iter()
+            .find(|(entry, _)| entry == name)
+            .map(|(_, origin)| *origin)
+    }
+}
+
+// Validation and every TUI keystroke check against the same few patterns, so each is compiled
+// once. Patterns are anchored, so `[a-z]+` rejects `abc1` rather than matching its prefix.
+fn compiled_pattern(pattern: &str) -> Result<Regex, String> {
+    static CACHE: OnceLock<Mutex<HashMap<String, Result<Regex, String>>>> = OnceLock::new();
+    let mut cache = CACHE
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    cache
+        .entry(pattern.to_string())
+        .or_insert_with(|| {
+            Regex::new(&format!("^(?:{pattern})$")).map_err(|error| error.to_string())
+        })
+        .clone()
+}
+
+fn parse_float_bound(bound: &str) -> Option<f64> {
+    bound
+        .parse::<f64>()
+        .ok()
+        .filter(|parsed| parsed.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numeric_input(kind: InputKindSpec, min: Option<&str>, max: Option<&str>) -> InputOptionSpec {
+        InputOptionSpec {
+            name: "jobs".into(),
+            description: None,
+            kind,
+            required: false,
+            default: None,
+            choices: Vec::new(),
+            min: min.map(str::to_string),
+            max: max.map(str::to_string),
+            pattern: None,
+        }
+    }
+
+    fn pattern_input(kind: InputKindSpec, pattern: &str) -> InputOptionSpec {
+        InputOptionSpec {
+            name: "version".into(),
+            pattern: Some(pattern.into()),
+            ..numeric_input(kind, None, None)
+        }
+    }
+
+    #[test]
+    fn check_value_enforces_inclusive_numeric_bounds() {
+        let jobs = numeric_input(InputKindSpec::Integer, Some("1"), Some("64"));
+        assert!(jobs.check_value("1").is_ok());
+        assert!(jobs.check_value("64").is_ok());
+        assert_eq!(
+            jobs.check_value("0").map_err(|invalid| invalid.code),
+            Err("input_out_of_range")
+        );
+        assert_eq!(
+            jobs.check_value("1.5").map_err(|invalid| invalid.code),
+            Err("input_integer_invalid")
+        );
+
+        let load = numeric_input(InputKindSpec::Float, None, Some("0.75"));
+        assert_eq!(load.range_label().as_deref(), Some("<= 0.75"));
+        assert!(load.check_value("0.75").is_ok());
+        assert!(load.check_value("0.8").is_err());
+        assert!(load.check_value("NaN").is_err());
+    }
+
+    #[test]
+    fn check_bounds_rejects_unparseable_and_inverted_ranges() {
+        assert!(
+            numeric_input(InputKindSpec::Integer, Some("1.5"), None)
+                .check_bounds()
+                .is_err()
+        );
+        assert!(
+            numeric_input(InputKindSpec::Float, Some("2"), Some("1"))
+                .check_bounds()
+                .is_err()
+        );
+        assert_eq!(
+            numeric_input(InputKindSpec::Enum, Some("1"), None)
+                .check_bounds()
+                .map_err(|invalid| invalid.code),
+            Err("input_bound_unsupported")
+        );
+    }
+
+    #[test]
+    fn check_value_matches_string_inputs_against_the_whole_pattern() {
+        let version = pattern_input(InputKindSpec::String, r"\d+\.\d+\.\d+");
+        assert!(version.check_pattern().is_ok());
+        assert!(version.check_value("1.2.3").is_ok());
+        let mismatch = version
+            .check_value("1.2.3-rc1")
+            .expect_err("suffix is not matched");
+        assert_eq!(mismatch.code, "input_pattern_mismatch");
+        assert_eq!(
+            mismatch.message,
+            r"input 'version' must match pattern '\d+\.\d+\.\d+', got '1.2.3-rc1'"
+        );
+    }
+
+    #[test]
+    fn resolve_datetime_handles_relative_and_literal_times() {
+        let now = 1_767_225_600; // 2026-01-01T00:00:00Z
+        assert_eq!(
+            resolve_datetime("now", now).as_deref(),
+            Ok("2026-01-01T00:00:00Z")
+        );
+        assert_eq!(
+            resolve_datetime("now+7d", now).as_deref(),
+            Ok("2026-01-08T00:00:00Z")
+        );
+        assert_eq!(
+            resolve_datetime("now-90m", now).as_deref(),
+            Ok("2025-12-31T22:30:00Z")
+        );
+        assert_eq!(
+            resolve_datetime("2024-02-29", now).as_deref(),
+            Ok("2024-02-29T00:00:00Z")
+        );
+        assert_eq!(
+            resolve_datetime("2024-03-01T08:15:30.25+02:00", now).as_deref(),
+            Ok("2024-03-01T06:15:30Z")
+        );
+        for invalid in [
+            "now+7x",
+            "now+d",
+            "now 7d",
+            "2023-02-29",
+            "2024-13-01",
+            "tomorrow",
+        ] {
+            assert!(resolve_datetime(invalid, now).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn datetime_inputs_reject_unparseable_offsets_and_resolve_valid_ones() {
+        let expiry = InputOptionSpec {
+            name: "expiry".into(),
+            ..numeric_input(InputKindSpec::Datetime, None, None)
+        };
+        let invalid = expiry.check_value("now+7y").expect_err("unknown unit");
+        assert_eq!(invalid.code, "input_datetime_invalid");
+        assert_eq!(
+            invalid.message,
+            "input 'expiry' expects a relative time like now, now+7d or now-12h (units s, m, h, d, w), got 'now+7y'"
+        );
+        assert_eq!(InputKindSpec::Datetime.resolve_value("now+7y"), "now+7y");
+        let resolved = InputKindSpec::Datetime.resolve_value("now+1w");
+        assert!(resolved.ends_with('Z'));
+        assert!(expiry.check_value(&resolved).is_ok());
+    }
+
+    #[test]
+    fn check_pattern_rejects_invalid_and_non_string_patterns() {
+        let invalid = pattern_input(InputKindSpec::String, "v(1");
+        assert_eq!(
+            invalid.check_pattern().map_err(|invalid| invalid.code),
+            Err("input_pattern_invalid")
+        );
+        assert_eq!(
+            invalid.check_value("v1").map_err(|invalid| invalid.code),
+            Err("input_pattern_invalid")
+        );
+        assert_eq!(
+            pattern_input(InputKindSpec::Integer, "[0-9]+")
+                .check_pattern()
+                .map_err(|invalid| invalid.code),
+            Err("input_pattern_unsupported")
+        );
+    }
 }
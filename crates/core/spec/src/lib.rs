@@ -1,6 +1,7 @@
 mod artifact;
 mod checkpoints;
 mod clean;
+mod datetime;
 mod ids;
 mod image;
 mod inputs;
@@ -19,11 +20,11 @@ pub use artifact::{
     ArtifactDefinition, ArtifactExecutionSpec, ArtifactInstallClassSpec,
     ArtifactInstallIdentitySpec, ArtifactOutputSpec, ArtifactProviderKind, ArtifactRef,
     ArtifactSpec, ArtifactVariantSpec, BuildModeSpec, DockerArtifactExecutionSpec, GoArtifactSpec,
-    JavaArtifactSpec, NodeArtifactSpec, PythonArtifactSpec, RustArtifactSpec,
+    JavaArtifactSpec, JavaBuildToolSpec, NodeArtifactSpec, PythonArtifactSpec, RustArtifactSpec,
 };
 pub use checkpoints::{
-    CheckpointAnchorRef, CheckpointBackendRef, CheckpointId, CheckpointPointSpec, CheckpointPolicy,
-    CheckpointSpec,
+    CheckpointAnchorRef, CheckpointBackendRef, CheckpointFingerprintInput, CheckpointId,
+    CheckpointPointSpec, CheckpointPolicy, CheckpointSpec,
 };
 pub use clean::{CleanProfileSpec, CleanSpec};
 pub use ids::{ArtifactId, BuildId, IdError, InstallId, SourceId, StageItemId};
@@ -33,7 +34,7 @@ pub use image::{
     ImageSpec, StartingPointImageSpec, StartingPointOutputModeSpec, StartingPointPackagesSpec,
     StartingPointRootfsValidationModeSpec,
 };
-pub use inputs::{InputKindSpec, InputOptionSpec, InputSpec};
+pub use inputs::{InputKindSpec, InputOptionSpec, InputSpec, InputValueError, InputValueOrigin};
 pub use install::{InstallEntrySpec, InstallSpec};
 pub use metadata::{BuildMetadataSpec, ProductIdentitySpec};
 pub use policy::{
@@ -41,17 +42,19 @@ pub use policy::{
     DEFAULT_BUILDROOT_PROVIDER_TIMEOUT_SECONDS, DEFAULT_COMMAND_RETRY_ATTEMPTS,
     DEFAULT_COMMAND_RETRY_BACKOFF_MS, DEFAULT_COMMAND_RETRY_BACKOFF_STRATEGY,
     DEFAULT_DOWNLOAD_PROVIDER_TIMEOUT_SECONDS, DEFAULT_GIT_PROVIDER_TIMEOUT_SECONDS,
-    DEFAULT_GO_PROVIDER_TIMEOUT_SECONDS, DEFAULT_JAVA_PROVIDER_TIMEOUT_SECONDS,
-    DEFAULT_NODE_PROVIDER_TIMEOUT_SECONDS, DEFAULT_OUTPUT_RETENTION_FAILURE_TAIL_LINES,
+    DEFAULT_GO_PROVIDER_TIMEOUT_SECONDS, DEFAULT_HEARTBEAT_SECONDS,
+    DEFAULT_JAVA_PROVIDER_TIMEOUT_SECONDS, DEFAULT_NODE_PROVIDER_TIMEOUT_SECONDS,
+    DEFAULT_OPERATION_HOOK_TIMEOUT_SECONDS, DEFAULT_OUTPUT_RETENTION_FAILURE_TAIL_LINES,
     DEFAULT_OUTPUT_RETENTION_POLICY, DEFAULT_OUTPUT_RETENTION_STDERR_BYTES,
     DEFAULT_OUTPUT_RETENTION_STDERR_LINES, DEFAULT_OUTPUT_RETENTION_STDOUT_BYTES,
     DEFAULT_OUTPUT_RETENTION_STDOUT_LINES, DEFAULT_PROVIDER_LOCAL_JOBS,
     DEFAULT_PYTHON_PROVIDER_TIMEOUT_SECONDS, DEFAULT_RUST_PROVIDER_TIMEOUT_SECONDS,
     DEFAULT_STARTING_POINT_PROVIDER_TIMEOUT_SECONDS, DockerExecutionSpec, ExecutionPolicySpec,
-    FailureHandlingPolicySpec, GitProviderPolicySpec, InterpolationSpec, OutputRetentionPolicySpec,
-    PrecedenceLayerSpec, PrecedencePolicySpec, PrecedenceSource, PrecedenceTarget,
-    PresetSelectionSpec, ProviderExecutionPolicySpec, ResolvedCommandPolicySpec,
-    RetryBackoffStrategySpec, RollbackDomain, RustProviderPolicySpec, UnresolvedInterpolationSpec,
+    FailureHandlingPolicySpec, GitProviderPolicySpec, HookFailureMode, InterpolationSpec,
+    OperationHooksSpec, OutputRetentionPolicySpec, PrecedenceLayerSpec, PrecedencePolicySpec,
+    PrecedenceSource, PrecedenceTarget, PresetSelectionSpec, ProviderExecutionPolicySpec,
+    ResolvedCommandPolicySpec, RetryBackoffStrategySpec, RollbackDomain, RustProviderPolicySpec,
+    UnresolvedInterpolationSpec,
 };
 pub use provenance::{ProvenanceIdentitySpec, ProvenanceSpec};
 pub use reporting::{PostBuildHookSpec, ReportingOutputsSpec, ReportingSpec, SecretMaskingSpec};
@@ -61,7 +64,8 @@ pub use source::{
     SourcePinPolicySpec, SourceProviderKind, SourceRef, SourceRefreshPolicySpec, SourceSpec,
 };
 pub use stage::{
-    StageContentOriginSpec, StageEnvSetSpec, StageFileSpec, StageServiceSpec, StageSpec,
+    StageContentOriginSpec, StageEnvSetSpec, StageFileSpec, StageOverlaySpec, StageServiceSpec,
+    StageSpec, StageSymlinkSpec,
 };
 pub use state::KeyValueState;
 pub use workspace::{
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use crate::{ArtifactProviderKind, ImageProviderKind, SourceProviderKind};
 
 pub const DEFAULT_COMMAND_RETRY_ATTEMPTS: u32 = 1;
@@ -16,6 +18,8 @@ pub const DEFAULT_PYTHON_PROVIDER_TIMEOUT_SECONDS: u64 = 300;
 pub const DEFAULT_BUILDROOT_PROVIDER_TIMEOUT_SECONDS: u64 = 900;
 pub const DEFAULT_STARTING_POINT_PROVIDER_TIMEOUT_SECONDS: u64 = 120;
 pub const DEFAULT_PROVIDER_LOCAL_JOBS: u32 = 0;
+pub const DEFAULT_HEARTBEAT_SECONDS: u64 = 30;
+pub const DEFAULT_OPERATION_HOOK_TIMEOUT_SECONDS: u64 = 300;
 
 pub const DEFAULT_OUTPUT_RETENTION_STDOUT_BYTES: usize = 1024 * 1024;
 pub const DEFAULT_OUTPUT_RETENTION_STDERR_BYTES: usize = 1024 * 1024;
@@ -45,6 +49,46 @@ pub struct ExecutionPolicySpec {
     pub jobs: u32,
     pub docker: Option<DockerExecutionSpec>,
     pub output_retention: OutputRetentionPolicySpec,
+    pub clean_env: bool,
+    pub env_allowlist: Vec<String>,
+    pub preserve_ansi: bool,
+    // Silence, in seconds, before a running operation gets a heartbeat event; 0 disables them.
+    pub heartbeat_seconds: u64,
+    // Collapses runs of identical consecutive log lines into `<line> (xN)` in stdout and the TUI.
+    pub fold_repeats: bool,
+    // Keyed by operation id, as printed by `gaia plan`.
+    pub hooks: BTreeMap<String, OperationHooksSpec>,
+    // Workspace-relative path of the `KEY=value` file written after a successful run.
+    pub env_export: Option<String>,
+    // Free space, in MiB, the build dir needs before a source, artifact, or image operation
+    // starts; 0 disables the check.
+    pub min_free_space_mb: u64,
+    // Seconds the scheduler may go without an operation finishing before it logs what it is
+    // waiting on; 0 disables the warning.
+    pub stall_warning_seconds: u64,
+}
+
+impl ExecutionPolicySpec {
+    pub fn clean_env_for(&self, command_policy: ResolvedCommandPolicySpec) -> bool {
+        command_policy.clean_env.unwrap_or(self.clean_env)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OperationHooksSpec {
+    pub pre: Vec<String>,
+    pub post: Vec<String>,
+    pub post_failure: HookFailureMode,
+    pub timeout_seconds: u64,
+}
+
+// What a failing post hook does to an operation that itself succeeded. A failing pre hook
+// always fails the operation, since its body never ran.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HookFailureMode {
+    #[default]
+    Warn,
+    Fail,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -140,6 +184,7 @@ pub struct ResolvedCommandPolicySpec {
     pub retry_backoff_strategy: RetryBackoffStrategySpec,
     pub timeout_seconds: u64,
     pub local_jobs: u32,
+    pub clean_env: Option<bool>,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
@@ -171,6 +216,7 @@ pub struct RustProviderPolicySpec {
     pub retry_backoff_ms: u64,
     pub retry_backoff_strategy: RetryBackoffStrategySpec,
     pub timeout_seconds: u64,
+    pub clean_env: Option<bool>,
 }
 
 impl From<&RustProviderPolicySpec> for ResolvedCommandPolicySpec {
@@ -181,6 +227,7 @@ impl From<&RustProviderPolicySpec> for ResolvedCommandPolicySpec {
             retry_backoff_strategy: policy.retry_backoff_strategy,
             timeout_seconds: policy.timeout_seconds,
             local_jobs: 0,
+            clean_env: policy.clean_env,
         }
     }
 }
@@ -192,6 +239,7 @@ pub struct GitProviderPolicySpec {
     pub retry_backoff_ms: u64,
     pub retry_backoff_strategy: RetryBackoffStrategySpec,
     pub timeout_seconds: u64,
+    pub clean_env: Option<bool>,
 }
 
 impl From<&GitProviderPolicySpec> for ResolvedCommandPolicySpec {
@@ -202,6 +250,7 @@ impl From<&GitProviderPolicySpec> for ResolvedCommandPolicySpec {
             retry_backoff_strategy: policy.retry_backoff_strategy,
             timeout_seconds: policy.timeout_seconds,
             local_jobs: 0,
+            clean_env: policy.clean_env,
         }
     }
 }
@@ -213,6 +262,7 @@ pub struct CommandProviderPolicySpec {
     pub retry_backoff_strategy: RetryBackoffStrategySpec,
     pub timeout_seconds: u64,
     pub local_jobs: u32,
+    pub clean_env: Option<bool>,
 }
 
 impl From<&CommandProviderPolicySpec> for ResolvedCommandPolicySpec {
@@ -223,6 +273,7 @@ impl From<&CommandProviderPolicySpec> for ResolvedCommandPolicySpec {
             retry_backoff_strategy: policy.retry_backoff_strategy,
             timeout_seconds: policy.timeout_seconds,
             local_jobs: policy.local_jobs,
+            clean_env: policy.clean_env,
         }
     }
 }
@@ -232,6 +283,8 @@ pub struct FailureHandlingPolicySpec {
     pub rollback_on_error: bool,
     pub preserve_failed_outputs: bool,
     pub rollback_domains: Vec<RollbackDomain>,
+    // When false, operations that do not depend on a failed one keep running after it fails.
+    pub fail_fast: bool,
 }
 
 impl Default for FailureHandlingPolicySpec {
@@ -240,6 +293,7 @@ impl Default for FailureHandlingPolicySpec {
             rollback_on_error: true,
             preserve_failed_outputs: false,
             rollback_domains: RollbackDomain::all(),
+            fail_fast: true,
         }
     }
 }
@@ -352,6 +406,7 @@ mod tests {
                 retry_backoff_strategy: RetryBackoffStrategySpec::Exponential,
                 timeout_seconds: 120,
                 local_jobs: 0,
+                clean_env: None,
             }
         );
         let buildroot_policy = providers.image_command_policy(ImageProviderKind::Buildroot);
@@ -368,4 +423,21 @@ mod tests {
             ResolvedCommandPolicySpec::default()
         );
     }
+
+    #[test]
+    fn provider_clean_env_overrides_execution_default() {
+        let mut providers = ProviderExecutionPolicySpec::default();
+        providers.buildroot.clean_env = Some(false);
+        let execution = ExecutionPolicySpec {
+            clean_env: true,
+            ..ExecutionPolicySpec::default()
+        };
+
+        assert!(
+            !execution.clean_env_for(providers.image_command_policy(ImageProviderKind::Buildroot))
+        );
+        assert!(
+            execution.clean_env_for(providers.artifact_command_policy(ArtifactProviderKind::Rust))
+        );
+    }
 }
@@ -10,6 +10,8 @@ pub struct ReportingOutputsSpec {
     pub summary: bool,
     pub provenance: bool,
     pub manifest: bool,
+    // Off by default: the SBOM hashes every file in the image rootfs.
+    pub sbom: bool,
 }
 
 impl Default for ReportingOutputsSpec {
@@ -18,6 +20,7 @@ impl Default for ReportingOutputsSpec {
             summary: true,
             provenance: true,
             manifest: true,
+            sbom: false,
         }
     }
 }
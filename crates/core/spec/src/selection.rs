@@ -1,3 +1,5 @@
+use crate::ArtifactProviderKind;
+
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct SelectionSpec {
     pub requested_build: Option<String>,
@@ -8,4 +10,6 @@ pub struct SelectionSpec {
     pub env_overrides: Vec<(String, String)>,
     pub explicit_overrides: Vec<(String, String)>,
     pub precedence_order: Vec<String>,
+    // Artifacts dropped by their `when` condition, kept so planning can explain the gap.
+    pub when_excluded_artifacts: Vec<(String, ArtifactProviderKind)>,
 }
@@ -1,5 +1,5 @@
-use crate::SourceId;
-use std::path::Path;
+use crate::{SourceId, WorkspaceSpec};
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SourceSpec {
@@ -18,6 +18,21 @@ impl SourceSpec {
     pub fn provider_kind(&self) -> SourceProviderKind {
         self.definition.provider_kind()
     }
+
+    // Where the source tree is read from: a path source stays where it points, every other kind
+    // is materialized under `<build_dir>/sources/<id>`.
+    pub fn materialized_path(&self, workspace: &WorkspaceSpec) -> PathBuf {
+        let path = match &self.definition {
+            SourceDefinition::Path(path) => path.as_path().to_path_buf(),
+            SourceDefinition::Git(_)
+            | SourceDefinition::Archive(_)
+            | SourceDefinition::Download(_) => workspace
+                .build_path()
+                .join("sources")
+                .join(self.id.as_str()),
+        };
+        workspace.root_path().join(path)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -6,6 +6,10 @@ pub struct StageSpec {
     pub files: Vec<StageFileSpec>,
     pub env_sets: Vec<StageEnvSetSpec>,
     pub services: Vec<StageServiceSpec>,
+    pub overlays: Vec<StageOverlaySpec>,
+    pub symlinks: Vec<StageSymlinkSpec>,
+    // Clone overlay files copy-on-write where the filesystem supports it.
+    pub fast_copy: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -14,6 +18,10 @@ pub struct StageFileSpec {
     pub src: String,
     pub dest: String,
     pub origin: StageContentOriginSpec,
+    // Ownership and file capabilities applied after the copy; Unix only.
+    pub uid: Option<u32>,
+    pub gid: Option<u32>,
+    pub caps: Option<String>,
 }
 
 impl StageFileSpec {
@@ -28,6 +36,9 @@ impl StageFileSpec {
             src: src.into(),
             dest: dest.into(),
             origin,
+            uid: None,
+            gid: None,
+            caps: None,
         }
     }
 
@@ -64,6 +75,36 @@ impl StageFileSpec {
     }
 }
 
+// A symlink entry creates `link` in the rootfs pointing at `target`, which is kept verbatim.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageSymlinkSpec {
+    pub id: StageItemId,
+    pub target: String,
+    pub link: String,
+}
+
+impl StageSymlinkSpec {
+    pub fn new(
+        id: impl Into<StageItemId>,
+        target: impl Into<String>,
+        link: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            target: target.into(),
+            link: link.into(),
+        }
+    }
+
+    pub fn target_path(&self) -> &Path {
+        Path::new(&self.target)
+    }
+
+    pub fn link_path(&self) -> &Path {
+        Path::new(&self.link)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct StageEnvSetSpec {
     pub id: StageItemId,
@@ -111,6 +152,36 @@ impl StageServiceSpec {
     }
 }
 
+// An overlay copies the whole `src` directory tree under the `dest` prefix in the rootfs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageOverlaySpec {
+    pub id: StageItemId,
+    pub src: String,
+    pub dest: String,
+}
+
+impl StageOverlaySpec {
+    pub fn new(
+        id: impl Into<StageItemId>,
+        src: impl Into<String>,
+        dest: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            src: src.into(),
+            dest: dest.into(),
+        }
+    }
+
+    pub fn src_path(&self) -> &Path {
+        Path::new(&self.src)
+    }
+
+    pub fn dest_path(&self) -> &Path {
+        Path::new(&self.dest)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum StageContentOriginSpec {
     StaticAsset,
@@ -68,6 +68,18 @@ pub(crate) fn validate_artifacts(
                 Some(format!("artifact:{}", artifact.id.as_str())),
             ));
         }
+        if let Some(group) = &artifact.mutex_group
+            && group.trim().is_empty()
+        {
+            diagnostics.push(error(
+                "artifact_mutex_group_empty",
+                format!(
+                    "artifact '{}' has an empty mutex group",
+                    artifact.id.as_str()
+                ),
+                Some(format!("artifact:{}", artifact.id.as_str())),
+            ));
+        }
         if let Some(identity) = &artifact.install_identity {
             if identity.install_name.trim().is_empty() {
                 diagnostics.push(error(
@@ -132,6 +144,16 @@ pub(crate) fn validate_artifacts(
                         Some(format!("artifact:{}", artifact.id.as_str())),
                     ));
                 }
+                if java.tasks.iter().any(|task| task.trim().is_empty()) {
+                    diagnostics.push(error(
+                        "java_task_empty",
+                        format!(
+                            "java artifact '{}' has an empty build task",
+                            artifact.id.as_str()
+                        ),
+                        Some(format!("artifact:{}", artifact.id.as_str())),
+                    ));
+                }
             }
             ArtifactDefinition::Node(node) => {
                 if node.package_dir.trim().is_empty() {
@@ -1,6 +1,11 @@
 use std::collections::HashSet;
+use std::path::Path;
 
-use gaia_spec::{CheckpointAnchorRef, CheckpointPolicy, ResolvedBuildSpec};
+use gaia_process::capture_shell_stdout_cached;
+use gaia_spec::{
+    ArtifactId, CheckpointAnchorRef, CheckpointFingerprintInput, CheckpointPointSpec,
+    CheckpointPolicy, ImageDefinition, ResolvedBuildSpec, SourceId, resolve_workspace_path,
+};
 
 use crate::ValidationDiagnostic;
 use crate::diagnostics::error;
@@ -25,9 +30,10 @@ pub(crate) fn validate_checkpoints(
                 Some(format!("checkpoint:{}", checkpoint.id.as_str())),
             ));
         }
-        if checkpoint.backend.is_none()
-            && (checkpoint.use_policy != CheckpointPolicy::Off
-                || checkpoint.upload_policy != CheckpointPolicy::Off)
+        let restores = checkpoint.use_policy != CheckpointPolicy::Off;
+        let uploads = checkpoint.upload_policy != CheckpointPolicy::Off;
+        if (restores && checkpoint.backend.is_none())
+            || (uploads && checkpoint.upload_targets().is_empty())
         {
             diagnostics.push(error(
                 "checkpoint_backend_missing",
@@ -38,6 +44,7 @@ pub(crate) fn validate_checkpoints(
                 Some(format!("checkpoint:{}", checkpoint.id.as_str())),
             ));
         }
+        validate_checkpoint_backend_names(checkpoint, diagnostics);
         match &checkpoint.anchor {
             CheckpointAnchorRef::Image => {}
             CheckpointAnchorRef::Unknown(raw) => diagnostics.push(error(
@@ -49,6 +56,32 @@ pub(crate) fn validate_checkpoints(
                 ),
                 Some(format!("checkpoint:{}", checkpoint.id.as_str())),
             )),
+            CheckpointAnchorRef::Source(id) => {
+                if !spec.sources.iter().any(|source| source.id == *id) {
+                    diagnostics.push(error(
+                        "unknown_checkpoint_anchor",
+                        format!(
+                            "checkpoint '{}' references unknown source anchor '{}'",
+                            checkpoint.id.as_str(),
+                            id.as_str()
+                        ),
+                        Some(format!("checkpoint:{}", checkpoint.id.as_str())),
+                    ));
+                }
+            }
+            CheckpointAnchorRef::Artifact(id) => {
+                if !spec.artifacts.iter().any(|artifact| artifact.id == *id) {
+                    diagnostics.push(error(
+                        "unknown_checkpoint_anchor",
+                        format!(
+                            "checkpoint '{}' references unknown artifact anchor '{}'",
+                            checkpoint.id.as_str(),
+                            id.as_str()
+                        ),
+                        Some(format!("checkpoint:{}", checkpoint.id.as_str())),
+                    ));
+                }
+            }
             CheckpointAnchorRef::Install(id) => {
                 if !spec.install.entries.iter().any(|install| install.id == *id) {
                     diagnostics.push(error(
@@ -116,6 +149,20 @@ pub(crate) fn validate_checkpoints(
         ));
         }
 
+        validate_fingerprint_inputs(spec, checkpoint, diagnostics);
+
+        if let Err(path_error) = checkpoint.restore_root(&spec.workspace) {
+            diagnostics.push(error(
+                "checkpoint_restore_to_invalid",
+                format!(
+                    "checkpoint '{}' restore_to '{}' must be a path inside the workspace: {path_error}",
+                    checkpoint.id.as_str(),
+                    checkpoint.restore_to.as_deref().unwrap_or_default()
+                ),
+                Some(format!("checkpoint:{}.restore_to", checkpoint.id.as_str())),
+            ));
+        }
+
         if checkpoint_requires_report_ordering(checkpoint)
             && !anchor_is_in_image_dependency_chain(spec, &checkpoint.anchor)
         {
@@ -133,12 +180,119 @@ pub(crate) fn validate_checkpoints(
     }
 }
 
+// Fingerprint commands run here so a failing one stops the build before planning; the cached
+// output is what the planner later hashes.
+fn validate_checkpoint_backend_names(
+    checkpoint: &CheckpointPointSpec,
+    diagnostics: &mut Vec<ValidationDiagnostic>,
+) {
+    let location = format!("checkpoint:{}", checkpoint.id.as_str());
+    let lists = [
+        ("backend", checkpoint.restore_backends().collect::<Vec<_>>()),
+        (
+            "upload_backends",
+            checkpoint.upload_backends.iter().collect::<Vec<_>>(),
+        ),
+    ];
+    for (field, backends) in lists {
+        let mut seen = HashSet::new();
+        for backend in backends {
+            if backend.backend.trim().is_empty() {
+                diagnostics.push(error(
+                    "checkpoint_backend_empty",
+                    format!(
+                        "checkpoint '{}' lists an empty name in `{field}`",
+                        checkpoint.id.as_str()
+                    ),
+                    Some(location.clone()),
+                ));
+            } else if !seen.insert(backend.backend.as_str()) {
+                diagnostics.push(error(
+                    "duplicate_checkpoint_backend",
+                    format!(
+                        "checkpoint '{}' lists backend '{}' more than once in `{field}`",
+                        checkpoint.id.as_str(),
+                        backend.backend
+                    ),
+                    Some(location.clone()),
+                ));
+            }
+        }
+    }
+}
+
+fn validate_fingerprint_inputs(
+    spec: &ResolvedBuildSpec,
+    checkpoint: &CheckpointPointSpec,
+    diagnostics: &mut Vec<ValidationDiagnostic>,
+) {
+    let location = || {
+        Some(format!(
+            "checkpoint:{}.fingerprint_from",
+            checkpoint.id.as_str()
+        ))
+    };
+    for input in &checkpoint.fingerprint_from {
+        match input {
+            CheckpointFingerprintInput::Path(path) => {
+                if let Err(path_error) = resolve_workspace_path(&spec.workspace, path) {
+                    diagnostics.push(error(
+                        "checkpoint_fingerprint_path_invalid",
+                        format!(
+                            "checkpoint '{}' fingerprint path '{path}' is invalid: {path_error}",
+                            checkpoint.id.as_str()
+                        ),
+                        location(),
+                    ));
+                }
+            }
+            CheckpointFingerprintInput::Command(command) if command.is_empty() => {
+                diagnostics.push(error(
+                    "checkpoint_fingerprint_command_empty",
+                    format!(
+                        "checkpoint '{}' has an empty 'cmd:' fingerprint entry",
+                        checkpoint.id.as_str()
+                    ),
+                    location(),
+                ));
+            }
+            CheckpointFingerprintInput::Command(command) => {
+                if let Err(failure) =
+                    capture_shell_stdout_cached(Path::new(&spec.workspace.root_dir), command)
+                {
+                    diagnostics.push(error(
+                        "checkpoint_fingerprint_command_failed",
+                        format!(
+                            "checkpoint '{}' fingerprint command '{command}' {failure}",
+                            checkpoint.id.as_str()
+                        ),
+                        location(),
+                    ));
+                }
+            }
+        }
+    }
+}
+
 fn anchor_is_supported_by_image_flow(
     spec: &ResolvedBuildSpec,
     anchor: &CheckpointAnchorRef,
 ) -> bool {
     match anchor {
         CheckpointAnchorRef::Image => true,
+        CheckpointAnchorRef::Source(id) => {
+            image_source(spec) == Some(id)
+                || image_fed_artifacts(spec).iter().any(|artifact_id| {
+                    spec.artifacts.iter().any(|artifact| {
+                        artifact.id == *artifact_id
+                            && artifact
+                                .source
+                                .as_ref()
+                                .is_some_and(|source| source.id == *id)
+                    })
+                })
+        }
+        CheckpointAnchorRef::Artifact(id) => image_fed_artifacts(spec).contains(id),
         CheckpointAnchorRef::Install(id) => spec
             .image
             .feed
@@ -164,6 +318,41 @@ fn anchor_is_supported_by_image_flow(
     }
 }
 
+fn image_source(spec: &ResolvedBuildSpec) -> Option<&SourceId> {
+    match &spec.image.definition {
+        ImageDefinition::Buildroot(buildroot) => buildroot.source.as_ref(),
+        ImageDefinition::StartingPoint(starting_point) => starting_point.source.as_ref(),
+    }
+}
+
+// Artifacts installed by the image feed, plus every artifact they transitively depend on.
+fn image_fed_artifacts(spec: &ResolvedBuildSpec) -> Vec<ArtifactId> {
+    let mut fed = spec
+        .install
+        .entries
+        .iter()
+        .filter(|install| spec.image.feed.install_entries.contains(&install.id))
+        .map(|install| install.artifact.id.clone())
+        .collect::<Vec<_>>();
+    let mut index = 0;
+    while let Some(artifact_id) = fed.get(index).cloned() {
+        index += 1;
+        let dependencies = spec
+            .artifacts
+            .iter()
+            .filter(|artifact| artifact.id == artifact_id)
+            .flat_map(|artifact| artifact.dependencies.iter())
+            .map(|dependency| dependency.id.clone())
+            .collect::<Vec<_>>();
+        for dependency in dependencies {
+            if !fed.contains(&dependency) {
+                fed.push(dependency);
+            }
+        }
+    }
+    fed
+}
+
 fn anchor_is_in_image_dependency_chain(
     spec: &ResolvedBuildSpec,
     anchor: &CheckpointAnchorRef,
@@ -1,6 +1,6 @@
 use gaia_spec::ResolvedBuildSpec;
 
-use crate::{DiagnosticSeverity, ValidationReport};
+use crate::ValidationReport;
 
 pub fn validate_spec(spec: &ResolvedBuildSpec) -> ValidationReport {
     let mut diagnostics = Vec::new();
@@ -13,20 +13,5 @@ pub fn validate_spec(spec: &ResolvedBuildSpec) -> ValidationReport {
     crate::image::validate_image_contract(spec, &mut diagnostics);
     crate::reporting::validate_reporting(spec, &mut diagnostics);
 
-    let warnings = diagnostics
-        .iter()
-        .filter(|d| d.severity == DiagnosticSeverity::Warning)
-        .map(|d| d.message.clone())
-        .collect();
-    let errors = diagnostics
-        .iter()
-        .filter(|d| d.severity == DiagnosticSeverity::Error)
-        .map(|d| d.message.clone())
-        .collect();
-
-    ValidationReport {
-        warnings,
-        errors,
-        diagnostics,
-    }
+    ValidationReport::from_diagnostics(diagnostics)
 }
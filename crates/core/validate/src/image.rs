@@ -1,5 +1,6 @@
 use std::collections::HashSet;
 use std::fs;
+use std::path::{Path, PathBuf};
 
 use gaia_spec::{
     BuildrootExpectedImageFormatSpec, BuildrootExternalTreeModeSpec, ImageDefinition,
@@ -147,6 +148,32 @@ pub(crate) fn validate_image_contract(
                     Some("image".into()),
                 ));
             }
+            if buildroot.ccache
+                && buildroot.defconfig.is_none()
+                && buildroot.defconfig_path.is_none()
+            {
+                diagnostics.push(error(
+                    "buildroot_ccache_requires_base_config",
+                    "buildroot ccache requires defconfig or defconfig_path to also be set".into(),
+                    Some("image".into()),
+                ));
+            }
+            if !buildroot.ccache && buildroot.ccache_dir.is_some() {
+                diagnostics.push(warning(
+                    "buildroot_ccache_dir_unused",
+                    "buildroot ccache_dir is set but ccache is disabled".into(),
+                    Some("image".into()),
+                ));
+            }
+            if let Some(output_dir) = spec
+                .image
+                .output
+                .collect_dir
+                .as_deref()
+                .and_then(|collect_dir| buildroot.preserved_output_dir(Path::new(collect_dir)))
+            {
+                validate_incremental_clean_profile(spec, &output_dir, diagnostics);
+            }
             for (key, value) in &buildroot.config_overrides {
                 if key.trim().is_empty() {
                     diagnostics.push(error(
@@ -296,7 +323,9 @@ pub(crate) fn validate_image_contract(
                 && (!spec.image.feed.install_entries.is_empty()
                     || !spec.image.feed.stage_files.is_empty()
                     || !spec.image.feed.stage_env_sets.is_empty()
-                    || !spec.image.feed.stage_services.is_empty())
+                    || !spec.image.feed.stage_services.is_empty()
+                    || !spec.image.feed.stage_overlays.is_empty()
+                    || !spec.image.feed.stage_symlinks.is_empty())
             {
                 diagnostics.push(error(
                     "starting_point_raw_image_read_only_overlay",
@@ -382,6 +411,40 @@ pub(crate) fn validate_image_contract(
             ));
         }
     }
+    for stage_id in &spec.image.feed.stage_overlays {
+        if !spec
+            .stage
+            .overlays
+            .iter()
+            .any(|overlay| overlay.id == *stage_id)
+        {
+            diagnostics.push(error(
+                "unknown_image_feed_stage_overlay",
+                format!(
+                    "image feed references unknown stage overlay '{}'",
+                    stage_id.as_str()
+                ),
+                Some("image.feed.stage_overlays".into()),
+            ));
+        }
+    }
+    for stage_id in &spec.image.feed.stage_symlinks {
+        if !spec
+            .stage
+            .symlinks
+            .iter()
+            .any(|symlink| symlink.id == *stage_id)
+        {
+            diagnostics.push(error(
+                "unknown_image_feed_stage_symlink",
+                format!(
+                    "image feed references unknown stage symlink '{}'",
+                    stage_id.as_str()
+                ),
+                Some("image.feed.stage_symlinks".into()),
+            ));
+        }
+    }
 
     if spec.image.output.archive_name.is_some() && spec.image.output.collect_dir.is_none() {
         diagnostics.push(error(
@@ -401,6 +464,48 @@ fn starting_point_looks_like_raw_image(starting_point: &StartingPointImageSpec)
     rawish(&starting_point.rootfs_path) || starting_point.source_path.as_deref().is_some_and(rawish)
 }
 
+// The configured clean profile is what `gaia clean` runs by default, so a profile whose `build`,
+// `out` or `paths` cover the kept output dir would undo every incremental build.
+fn validate_incremental_clean_profile(
+    spec: &ResolvedBuildSpec,
+    output_dir: &Path,
+    diagnostics: &mut Vec<ValidationDiagnostic>,
+) {
+    let Some(profile_name) = spec.clean.default_profile.as_deref() else {
+        return;
+    };
+    let Some(profile) = spec.clean.profiles.get(profile_name) else {
+        return;
+    };
+    let mut cleaned = Vec::new();
+    if profile.build {
+        cleaned.push((
+            "build".to_string(),
+            PathBuf::from(&spec.workspace.build_dir),
+        ));
+    }
+    if profile.out {
+        cleaned.push(("out".to_string(), PathBuf::from(&spec.workspace.out_dir)));
+    }
+    for path in &profile.paths {
+        if let Ok(resolved) = resolve_workspace_path(spec, path) {
+            cleaned.push((format!("paths entry '{path}'"), resolved));
+        }
+    }
+    for (setting, path) in cleaned {
+        if output_dir.starts_with(&path) {
+            diagnostics.push(error(
+                "buildroot_incremental_clean_conflict",
+                format!(
+                    "buildroot incremental keeps '{}' across runs, but clean profile '{profile_name}' ({setting}) removes it; disable incremental or narrow the profile",
+                    output_dir.display()
+                ),
+                Some("image".into()),
+            ));
+        }
+    }
+}
+
 fn validate_buildroot_expected_images_against_defconfig(
     buildroot: &gaia_spec::BuildrootImageSpec,
     defconfig_contents: &str,
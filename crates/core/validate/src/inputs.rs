@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use gaia_spec::{InputKindSpec, ResolvedBuildSpec};
+use gaia_spec::ResolvedBuildSpec;
 
 use crate::diagnostics::error;
 use crate::{DiagnosticSeverity, ValidationDiagnostic};
@@ -31,6 +31,28 @@ pub(crate) fn validate_inputs(
                 Some(format!("input:{}", input.name)),
             ));
         }
+        if let Err(invalid) = input.check_bounds().and_then(|()| input.check_pattern()) {
+            diagnostics.push(error(
+                invalid.code,
+                invalid.message,
+                Some(format!("input:{}", input.name)),
+            ));
+        } else if let Some(default) = &input.default
+            && !spec
+                .inputs
+                .selected
+                .iter()
+                .any(|(name, value)| name == &input.name && value == default)
+            && let Err(invalid) = input.check_value(default)
+        {
+            // A bad default is a config bug even when this run selects another value; a selected
+            // default is reported once by the selected-value check below.
+            diagnostics.push(error(
+                invalid.code,
+                format!("default for {}", invalid.message),
+                Some(format!("input:{}", input.name)),
+            ));
+        }
     }
     for (name, value) in &spec.inputs.selected {
         let Some(input) = spec
@@ -46,37 +68,15 @@ pub(crate) fn validate_inputs(
             ));
             continue;
         };
-        match input.kind {
-            InputKindSpec::Integer if value.parse::<i64>().is_err() => diagnostics.push(error(
-                "input_integer_invalid",
-                format!("input '{}' expects an integer value, got '{}'", name, value),
+        // An uncompilable pattern is already reported once against the declaration.
+        if let Err(invalid) = input.check_value(value)
+            && invalid.code != "input_pattern_invalid"
+        {
+            diagnostics.push(error(
+                invalid.code,
+                invalid.message,
                 Some(format!("input:{name}")),
-            )),
-            InputKindSpec::Boolean
-                if !matches!(
-                    value.as_str(),
-                    "1" | "0" | "true" | "false" | "yes" | "no" | "on" | "off"
-                ) =>
-            {
-                diagnostics.push(error(
-                    "input_boolean_invalid",
-                    format!("input '{}' expects a boolean value, got '{}'", name, value),
-                    Some(format!("input:{name}")),
-                ))
-            }
-            InputKindSpec::Enum if !input.choices.iter().any(|choice| choice == value) => {
-                diagnostics.push(error(
-                    "input_enum_invalid",
-                    format!(
-                        "input '{}' expects one of [{}], got '{}'",
-                        name,
-                        input.choices.join(", "),
-                        value
-                    ),
-                    Some(format!("input:{name}")),
-                ))
-            }
-            _ => {}
+            ));
         }
     }
 
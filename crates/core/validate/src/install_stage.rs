@@ -1,6 +1,7 @@
 use std::collections::{HashMap, HashSet};
 use std::fs;
 
+use gaia_image_providers::stage_overlay_entries;
 use gaia_spec::{ResolvedBuildSpec, StageContentOriginSpec};
 
 use crate::ValidationDiagnostic;
@@ -214,6 +215,95 @@ pub(crate) fn validate_install_and_stage(
         }
     }
 
+    let mut overlay_entries = Vec::new();
+    for overlay in &spec.stage.overlays {
+        let location = format!("stage:{}", overlay.id.as_str());
+        if !overlay.id.is_valid() {
+            diagnostics.push(error(
+                "stage_item_id_empty",
+                "stage overlay id cannot be empty".into(),
+                Some("stage-overlay".into()),
+            ));
+        }
+        if !stage_ids.insert(overlay.id.as_str().to_string()) {
+            diagnostics.push(error(
+                "duplicate_stage_item_id",
+                format!("duplicate stage item id '{}'", overlay.id.as_str()),
+                Some(location.clone()),
+            ));
+        }
+        if overlay.src.trim().is_empty() || overlay.dest.trim().is_empty() {
+            diagnostics.push(error(
+                "stage_overlay_path_empty",
+                format!(
+                    "stage overlay '{}' must have both src and dest",
+                    overlay.id.as_str()
+                ),
+                Some(location),
+            ));
+            continue;
+        }
+        if !overlay.dest.starts_with('/') {
+            diagnostics.push(error(
+                "stage_overlay_dest_not_absolute",
+                format!(
+                    "stage overlay '{}' has non-absolute destination '{}'",
+                    overlay.id.as_str(),
+                    overlay.dest
+                ),
+                Some(location.clone()),
+            ));
+        }
+        match resolve_workspace_path(spec, &overlay.src).and_then(|resolved_path| {
+            stage_overlay_entries(&resolved_path).map_err(|error| error.message)
+        }) {
+            Ok(entries) => overlay_entries.push((overlay, entries)),
+            Err(message) => {
+                diagnostics.push(error("stage_overlay_src_invalid", message, Some(location)))
+            }
+        }
+    }
+
+    for symlink in &spec.stage.symlinks {
+        let location = format!("stage:{}", symlink.id.as_str());
+        if !symlink.id.is_valid() {
+            diagnostics.push(error(
+                "stage_item_id_empty",
+                "stage symlink id cannot be empty".into(),
+                Some("stage-symlink".into()),
+            ));
+        }
+        if !stage_ids.insert(symlink.id.as_str().to_string()) {
+            diagnostics.push(error(
+                "duplicate_stage_item_id",
+                format!("duplicate stage item id '{}'", symlink.id.as_str()),
+                Some(location.clone()),
+            ));
+        }
+        if symlink.target.trim().is_empty() || symlink.link.trim().is_empty() {
+            diagnostics.push(error(
+                "stage_symlink_path_empty",
+                format!(
+                    "stage symlink '{}' must have both target and link",
+                    symlink.id.as_str()
+                ),
+                Some(location),
+            ));
+            continue;
+        }
+        if !symlink.link.starts_with('/') {
+            diagnostics.push(error(
+                "stage_symlink_link_not_absolute",
+                format!(
+                    "stage symlink '{}' has non-absolute link '{}'",
+                    symlink.id.as_str(),
+                    symlink.link
+                ),
+                Some(location),
+            ));
+        }
+    }
+
     let mut image_destinations: HashMap<String, String> = HashMap::new();
     for install in &spec.install.entries {
         register_image_destination(
@@ -251,6 +341,31 @@ pub(crate) fn validate_install_and_stage(
             format!("stage service '{}'", service.id.as_str()),
         );
     }
+    for (overlay, entries) in overlay_entries {
+        for entry in entries.iter().filter(|entry| entry.claims_destination()) {
+            register_image_destination(
+                diagnostics,
+                &mut image_destinations,
+                entry.image_path(&overlay.dest),
+                format!("stage:{}", overlay.id.as_str()),
+                format!("stage overlay '{}'", overlay.id.as_str()),
+            );
+        }
+    }
+    for symlink in spec
+        .stage
+        .symlinks
+        .iter()
+        .filter(|symlink| !symlink.link.trim().is_empty())
+    {
+        register_image_destination(
+            diagnostics,
+            &mut image_destinations,
+            symlink.link.clone(),
+            format!("stage:{}", symlink.id.as_str()),
+            format!("stage symlink '{}'", symlink.id.as_str()),
+        );
+    }
 }
 
 fn register_image_destination(
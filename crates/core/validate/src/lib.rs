@@ -5,6 +5,7 @@ mod diagnostics;
 mod image;
 mod inputs;
 mod install_stage;
+mod lint;
 mod model;
 mod providers;
 mod reporting;
@@ -12,5 +13,6 @@ mod sources;
 mod workspace;
 
 pub use core::validate_spec;
+pub use lint::{LINT_RULES, LintRule, lint_spec};
 pub use model::{DiagnosticSeverity, ValidationDiagnostic, ValidationReport};
 pub use providers::validate_spec_with_providers;
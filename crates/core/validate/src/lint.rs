@@ -0,0 +1,126 @@
+use gaia_spec::ResolvedBuildSpec;
+
+use crate::diagnostics::warning;
+use crate::{ValidationDiagnostic, ValidationReport};
+
+// A convention check over the resolved spec. Unlike validation, a finding never means the build
+// cannot run; it flags config a team would rather not merge. Adding a rule is writing one of these
+// and listing it in `LINT_RULES`.
+pub type LintRule = fn(&ResolvedBuildSpec) -> Vec<ValidationDiagnostic>;
+
+// Run in order by `lint_spec`; findings keep that order.
+pub const LINT_RULES: &[LintRule] = &[
+    build_version_missing,
+    input_description_missing,
+    required_input_has_default,
+    id_not_kebab_case,
+];
+
+// `gaia lint`: every rule in `LINT_RULES`, collected into the same report shape validation uses,
+// so `--strict` can promote the warnings the same way `gaia run --strict` does.
+pub fn lint_spec(spec: &ResolvedBuildSpec) -> ValidationReport {
+    ValidationReport::from_diagnostics(LINT_RULES.iter().flat_map(|rule| rule(spec)).collect())
+}
+
+// Without a version the image archive, report and SBOM all carry an empty build version.
+fn build_version_missing(spec: &ResolvedBuildSpec) -> Vec<ValidationDiagnostic> {
+    if spec
+        .identity
+        .version
+        .as_deref()
+        .is_some_and(|version| !version.trim().is_empty())
+    {
+        return Vec::new();
+    }
+    vec![warning(
+        "build_version_missing",
+        format!("build '{}' sets no version", spec.identity.build_name),
+        Some("build".into()),
+    )]
+}
+
+fn input_description_missing(spec: &ResolvedBuildSpec) -> Vec<ValidationDiagnostic> {
+    spec.inputs
+        .declared
+        .iter()
+        .filter(|input| {
+            input
+                .description
+                .as_deref()
+                .is_none_or(|description| description.trim().is_empty())
+        })
+        .map(|input| {
+            warning(
+                "input_description_missing",
+                format!(
+                    "input '{}' has no description for `gaia inputs` and the TUI",
+                    input.name
+                ),
+                Some(format!("input:{}", input.name)),
+            )
+        })
+        .collect()
+}
+
+// A default always satisfies `required`, so the flag promises a choice the config never asks for.
+fn required_input_has_default(spec: &ResolvedBuildSpec) -> Vec<ValidationDiagnostic> {
+    spec.inputs
+        .declared
+        .iter()
+        .filter(|input| input.required && input.default.is_some())
+        .map(|input| {
+            warning(
+                "required_input_has_default",
+                format!(
+                    "input '{}' is required but has a default, so it can never be missing",
+                    input.name
+                ),
+                Some(format!("input:{}", input.name)),
+            )
+        })
+        .collect()
+}
+
+// Ids end up in operation ids, state file names and report keys; lowercase kebab-case keeps them
+// uniform across all of those.
+fn id_not_kebab_case(spec: &ResolvedBuildSpec) -> Vec<ValidationDiagnostic> {
+    let ids = spec
+        .sources
+        .iter()
+        .map(|source| ("source", source.id.as_str()))
+        .chain(
+            spec.artifacts
+                .iter()
+                .map(|artifact| ("artifact", artifact.id.as_str())),
+        )
+        .chain(
+            spec.install
+                .entries
+                .iter()
+                .map(|entry| ("install", entry.id.as_str())),
+        )
+        .chain(
+            spec.checkpoints
+                .points
+                .iter()
+                .map(|checkpoint| ("checkpoint", checkpoint.id.as_str())),
+        );
+    ids.filter(|(_, id)| !id.is_empty() && !is_kebab_case(id))
+        .map(|(kind, id)| {
+            warning(
+                "id_not_kebab_case",
+                format!("{kind} id '{id}' is not lowercase kebab-case"),
+                Some(format!("{kind}:{id}")),
+            )
+        })
+        .collect()
+}
+
+fn is_kebab_case(id: &str) -> bool {
+    !id.starts_with('-')
+        && !id.ends_with('-')
+        && !id.contains("--")
+        && id
+            .chars()
+            .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-')
+}
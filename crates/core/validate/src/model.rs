@@ -17,4 +17,37 @@ pub struct ValidationReport {
     pub warnings: Vec<String>,
     pub errors: Vec<String>,
     pub diagnostics: Vec<ValidationDiagnostic>,
+    // How many of `errors` were warnings before `promote_warnings`.
+    pub promoted_warnings: usize,
+}
+
+impl ValidationReport {
+    // Splits the diagnostics' messages into `warnings` and `errors` by severity.
+    pub(crate) fn from_diagnostics(diagnostics: Vec<ValidationDiagnostic>) -> Self {
+        let messages = |severity| {
+            diagnostics
+                .iter()
+                .filter(|diagnostic: &&ValidationDiagnostic| diagnostic.severity == severity)
+                .map(|diagnostic| diagnostic.message.clone())
+                .collect()
+        };
+        Self {
+            warnings: messages(DiagnosticSeverity::Warning),
+            errors: messages(DiagnosticSeverity::Error),
+            diagnostics,
+            promoted_warnings: 0,
+        }
+    }
+
+    // `gaia run --strict`: turns every warning into an error, keeping the diagnostics in order.
+    // Returns how many were promoted.
+    pub fn promote_warnings(&mut self) -> usize {
+        let promoted = self.warnings.len();
+        self.errors.append(&mut self.warnings);
+        for diagnostic in &mut self.diagnostics {
+            diagnostic.severity = DiagnosticSeverity::Error;
+        }
+        self.promoted_warnings += promoted;
+        promoted
+    }
 }
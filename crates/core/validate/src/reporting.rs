@@ -10,6 +10,7 @@ pub(crate) fn validate_reporting(
     if !spec.reporting.outputs.summary
         && !spec.reporting.outputs.provenance
         && !spec.reporting.outputs.manifest
+        && !spec.reporting.outputs.sbom
     {
         diagnostics.push(warning(
             "reporting_outputs_disabled",
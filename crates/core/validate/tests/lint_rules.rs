@@ -0,0 +1,80 @@
+use gaia_spec::{
+    InputKindSpec, InputOptionSpec, PathSourceSpec, ResolvedBuildSpec, SourceDefinition, SourceId,
+    SourcePinPolicySpec, SourceRefreshPolicySpec, SourceSpec,
+};
+use gaia_validate::{DiagnosticSeverity, lint_spec};
+
+fn input(
+    name: &str,
+    description: Option<&str>,
+    required: bool,
+    default: Option<&str>,
+) -> InputOptionSpec {
+    InputOptionSpec {
+        name: name.into(),
+        description: description.map(Into::into),
+        kind: InputKindSpec::String,
+        required,
+        default: default.map(Into::into),
+        choices: Vec::new(),
+        min: None,
+        max: None,
+        pattern: None,
+    }
+}
+
+#[test]
+fn lint_reports_convention_findings_as_warnings() {
+    let mut spec = ResolvedBuildSpec::new("lint-rules");
+    spec.inputs.declared = vec![
+        input("target", Some("Hardware target"), false, Some("cm5")),
+        input("profile", None, true, Some("dev")),
+    ];
+    spec.sources.push(SourceSpec::new(
+        SourceId::new("App_Source"),
+        SourceDefinition::Path(PathSourceSpec {
+            path: "src".into(),
+            identity_ignore: Vec::new(),
+            refresh_policy: SourceRefreshPolicySpec::Never,
+            pin_policy: SourcePinPolicySpec::Locked,
+        }),
+    ));
+
+    let lint = lint_spec(&spec);
+
+    let findings = lint
+        .diagnostics
+        .iter()
+        .map(|diagnostic| {
+            (
+                diagnostic.code,
+                diagnostic.location.as_deref().unwrap_or(""),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        findings,
+        [
+            ("build_version_missing", "build"),
+            ("input_description_missing", "input:profile"),
+            ("required_input_has_default", "input:profile"),
+            ("id_not_kebab_case", "source:App_Source"),
+        ]
+    );
+    assert!(
+        lint.diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.severity == DiagnosticSeverity::Warning)
+    );
+    assert!(lint.errors.is_empty());
+    assert_eq!(lint.warnings.len(), 4);
+}
+
+#[test]
+fn a_conventional_spec_lints_clean() {
+    let mut spec = ResolvedBuildSpec::new("lint-clean");
+    spec.identity.version = Some("1.0.0".into());
+    spec.inputs.declared = vec![input("target", Some("Hardware target"), true, None)];
+
+    assert_eq!(lint_spec(&spec), Default::default());
+}
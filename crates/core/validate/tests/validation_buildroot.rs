@@ -136,6 +136,58 @@ config_fragments = ["assets/fragment.cfg"]
     let _ = fs::remove_file(path);
 }
 
+#[test]
+fn buildroot_incremental_conflicts_with_a_default_clean_profile_removing_its_output() {
+    let config = |profile: &str| {
+        format!(
+            r#"
+build_name = "incremental-buildroot-clean"
+
+[workspace]
+root_dir = "."
+build_dir = "build"
+out_dir = "out"
+
+[image]
+kind = "buildroot"
+defconfig = "qemu_x86_64_defconfig"
+incremental = true
+
+[image.output]
+collect_dir = "out/images/buildroot"
+
+[clean]
+default = "reset"
+
+[clean.profiles.reset]
+{profile}
+"#
+        )
+    };
+    let conflicts = |profile: &str| {
+        let path = write_temp_config(&config(profile));
+        let spec = resolve_config(path.to_str().expect("temp path utf-8"));
+        let _ = fs::remove_file(path);
+        validate_spec(&spec)
+            .diagnostics
+            .into_iter()
+            .filter(|diagnostic| diagnostic.code == "buildroot_incremental_clean_conflict")
+            .count()
+    };
+
+    assert_eq!(conflicts("out = true"), 1);
+    assert_eq!(
+        conflicts(
+            "build = true
+out = true"
+        ),
+        1
+    );
+    assert_eq!(conflicts(r#"paths = ["out/images"]"#), 1);
+    assert_eq!(conflicts("build = true"), 0);
+    assert_eq!(conflicts(r#"paths = ["out/images/buildroot/archive"]"#), 0);
+}
+
 #[test]
 fn missing_buildroot_config_fragment_is_an_error() {
     let nonce = SystemTime::now()
@@ -148,3 +148,277 @@ dest = "/usr/bin/gaia-alt"
 
     let _ = fs::remove_file(path);
 }
+
+#[test]
+fn failing_checkpoint_fingerprint_command_is_reported() {
+    let path = write_temp_config(
+        r#"
+build_name = "checkpoint-fingerprint-command"
+
+[workspace]
+root_dir = "."
+build_dir = "build"
+out_dir = "out"
+
+[image]
+kind = "starting-point"
+rootfs_path = "/tmp/rootfs"
+
+[[checkpoints]]
+id = "base-image"
+backend = "local"
+use_policy = "auto"
+upload_policy = "off"
+fingerprint_from = ["cmd:echo toolchain-1", "cmd:echo missing >&2; exit 3", "cmd:"]
+"#,
+    );
+
+    let spec = resolve_config(path.to_str().expect("temp path utf-8"));
+    let report = validate_spec(&spec);
+    let codes = report
+        .diagnostics
+        .iter()
+        .filter(|diagnostic| {
+            diagnostic.location.as_deref() == Some("checkpoint:base-image.fingerprint_from")
+        })
+        .map(|diagnostic| (diagnostic.code, diagnostic.message.as_str()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(codes.len(), 2, "{codes:?}");
+    assert!(codes.iter().any(|(code, message)| {
+        *code == "checkpoint_fingerprint_command_failed"
+            && message.contains("'echo missing >&2; exit 3' failed with exit status: 3: missing")
+    }));
+    assert!(
+        codes
+            .iter()
+            .any(|(code, _)| *code == "checkpoint_fingerprint_command_empty")
+    );
+
+    let _ = fs::remove_file(path);
+}
+
+#[test]
+fn checkpoint_backend_lists_set_restore_order_and_upload_targets() {
+    let path = write_temp_config(
+        r#"
+build_name = "checkpoint-backend-order"
+
+[workspace]
+root_dir = "."
+build_dir = "build"
+out_dir = "out"
+
+[image]
+kind = "starting-point"
+rootfs_path = "/tmp/rootfs"
+
+[[checkpoints]]
+id = "scalar"
+backend = "local"
+use_policy = "auto"
+upload_policy = "auto"
+
+[[checkpoints]]
+id = "ordered"
+backend = ["lan-s3", "origin-s3"]
+upload_backends = ["lan-s3", "origin-s3", "lan-s3"]
+use_policy = "auto"
+upload_policy = "auto"
+
+[[checkpoints]]
+id = "upload-only"
+upload_backends = ["origin-s3"]
+use_policy = "off"
+upload_policy = "always"
+"#,
+    );
+
+    let spec = resolve_config(path.to_str().expect("temp path utf-8"));
+    let names = |backends: Vec<&gaia_spec::CheckpointBackendRef>| {
+        backends
+            .into_iter()
+            .map(|backend| backend.backend.clone())
+            .collect::<Vec<_>>()
+    };
+    let point = |id: &str| {
+        spec.checkpoints
+            .points
+            .iter()
+            .find(|point| point.id.as_str() == id)
+            .expect("checkpoint present")
+    };
+    let scalar = point("scalar");
+    assert_eq!(names(scalar.restore_backends().collect()), vec!["local"]);
+    assert_eq!(names(scalar.upload_targets()), vec!["local"]);
+    let ordered = point("ordered");
+    assert_eq!(
+        names(ordered.restore_backends().collect()),
+        vec!["lan-s3", "origin-s3"]
+    );
+
+    let report = validate_spec(&spec);
+    let codes = report
+        .diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.code.contains("checkpoint_backend"))
+        .map(|diagnostic| (diagnostic.code, diagnostic.location.as_deref()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        codes,
+        vec![("duplicate_checkpoint_backend", Some("checkpoint:ordered"))]
+    );
+
+    let _ = fs::remove_file(path);
+}
+
+#[test]
+fn checkpoint_can_anchor_to_artifacts_and_sources_feeding_the_image() {
+    let path = write_temp_config(
+        r#"
+build_name = "checkpoint-artifact-anchor"
+
+[workspace]
+root_dir = "."
+build_dir = "build"
+out_dir = "out"
+
+[image]
+kind = "starting-point"
+rootfs_path = "/tmp/rootfs"
+
+[image.feed]
+install_entries = ["install-gaia-app"]
+
+[[sources]]
+id = "app-src"
+kind = "path"
+path = "."
+
+[[artifacts]]
+id = "gaia-app"
+kind = "rust"
+package = "gaia"
+source = "app-src"
+output_path = "out/gaia"
+
+[[artifacts]]
+id = "gaia-tool"
+kind = "rust"
+package = "gaia-tool"
+output_path = "out/gaia-tool"
+
+[[install]]
+id = "install-gaia-app"
+artifact = "gaia-app"
+dest = "/usr/bin/gaia"
+
+[[checkpoints]]
+id = "after-app"
+backend = "local"
+anchor = "artifact:gaia-app"
+use_policy = "auto"
+upload_policy = "off"
+
+[[checkpoints]]
+id = "after-src"
+backend = "local"
+anchor = "source:app-src"
+use_policy = "auto"
+upload_policy = "off"
+
+[[checkpoints]]
+id = "after-tool"
+backend = "local"
+anchor = "artifact:gaia-tool"
+use_policy = "off"
+upload_policy = "off"
+
+[[checkpoints]]
+id = "after-missing"
+backend = "local"
+anchor = "artifact:not-real"
+use_policy = "off"
+upload_policy = "off"
+"#,
+    );
+
+    let spec = resolve_config(path.to_str().expect("temp path utf-8"));
+    let report = validate_spec(&spec);
+    let codes_at = |location: &str| {
+        report
+            .diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.location.as_deref() == Some(location))
+            .map(|diagnostic| diagnostic.code)
+            .collect::<Vec<_>>()
+    };
+
+    assert!(codes_at("checkpoint:after-app").is_empty());
+    assert!(codes_at("checkpoint:after-src").is_empty());
+    assert_eq!(
+        codes_at("checkpoint:after-tool"),
+        ["illegal_checkpoint_anchor_domain"]
+    );
+    assert!(codes_at("checkpoint:after-missing").contains(&"unknown_checkpoint_anchor"));
+
+    let _ = fs::remove_file(path);
+}
+
+#[test]
+fn checkpoint_restore_to_must_stay_inside_the_workspace() {
+    let path = write_temp_config(
+        r#"
+build_name = "checkpoint-restore-to"
+
+[workspace]
+root_dir = "."
+build_dir = "build"
+out_dir = "out"
+
+[image]
+kind = "starting-point"
+rootfs_path = "/tmp/rootfs"
+
+[[checkpoints]]
+id = "tmpfs"
+backend = "local"
+use_policy = "auto"
+upload_policy = "off"
+restore_to = "build/tmpfs/restore"
+
+[[checkpoints]]
+id = "escaping"
+backend = "local"
+use_policy = "auto"
+upload_policy = "off"
+restore_to = "../outside"
+
+[[checkpoints]]
+id = "absolute"
+backend = "local"
+use_policy = "auto"
+upload_policy = "off"
+restore_to = "/dev/shm/restore"
+"#,
+    );
+
+    let spec = resolve_config(path.to_str().expect("temp path utf-8"));
+    let report = validate_spec(&spec);
+    let locations = report
+        .diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.code == "checkpoint_restore_to_invalid")
+        .map(|diagnostic| diagnostic.location.as_deref().unwrap_or_default())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        locations,
+        vec![
+            "checkpoint:absolute.restore_to",
+            "checkpoint:escaping.restore_to"
+        ]
+    );
+
+    let _ = fs::remove_file(path);
+}
@@ -24,6 +24,8 @@ fn empty_public_ids_are_validation_errors() {
             package: "demo".into(),
             target_name: None,
             variant: gaia_spec::ArtifactVariantSpec::File,
+            linker: None,
+            sysroot: None,
         }),
         None,
         ArtifactOutputSpec {
@@ -39,21 +41,24 @@ fn empty_public_ids_are_validation_errors() {
         owner: None,
         group: None,
     });
-    spec.stage.files.push(StageFileSpec {
-        id: StageItemId::new(""),
-        src: "assets/demo".into(),
-        dest: "/etc/demo".into(),
-        origin: gaia_spec::StageContentOriginSpec::Generated,
-    });
+    spec.stage.files.push(StageFileSpec::generated(
+        StageItemId::new(""),
+        "assets/demo",
+        "/etc/demo",
+    ));
     spec.checkpoints = CheckpointSpec {
         points: vec![CheckpointPointSpec {
             id: CheckpointId::new(""),
             backend: Some(gaia_spec::CheckpointBackendRef {
                 backend: "local".into(),
             }),
+            fallback_backends: Vec::new(),
+            upload_backends: Vec::new(),
             use_policy: CheckpointPolicy::Auto,
             upload_policy: CheckpointPolicy::Off,
             anchor: CheckpointAnchorRef::Image,
+            fingerprint_from: Vec::new(),
+            restore_to: None,
         }],
     };
 
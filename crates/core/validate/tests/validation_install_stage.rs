@@ -1,7 +1,7 @@
 pub mod support;
 
 use gaia_config::resolve_config;
-use gaia_validate::validate_spec;
+use gaia_validate::{DiagnosticSeverity, validate_spec};
 use std::fs;
 use support::{create_temp_workspace, write_temp_config};
 
@@ -343,6 +343,19 @@ origin = "static-asset"
         "expected busybox portability warnings for wget and awk patterns"
     );
 
+    let mut strict = report.clone();
+    let promoted = strict.promote_warnings();
+    assert_eq!(promoted, report.warnings.len());
+    assert_eq!(strict.promoted_warnings, promoted);
+    assert!(strict.warnings.is_empty());
+    assert_eq!(strict.errors.len(), report.errors.len() + promoted);
+    assert!(
+        strict
+            .diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.severity == DiagnosticSeverity::Error)
+    );
+
     let _ = fs::remove_dir_all(root);
 }
 
@@ -401,3 +414,174 @@ unit_path = "assets/provision.target"
 
     let _ = fs::remove_dir_all(root);
 }
+
+#[test]
+fn stage_overlay_files_participate_in_destination_collisions() {
+    let root = create_temp_workspace("gaia-stage-overlay-collision");
+    fs::create_dir_all(root.join("overlay/etc/gaia")).expect("overlay dir");
+    fs::create_dir_all(root.join("overlay/var/empty")).expect("empty overlay dir");
+    fs::write(root.join("overlay/etc/gaia/config.toml"), "ok").expect("overlay file");
+    fs::create_dir_all(root.join("assets")).expect("assets dir");
+    fs::write(root.join("assets/config.toml"), "ok").expect("stage file");
+    let config_path = root.join("build.toml");
+    fs::write(
+        &config_path,
+        format!(
+            r#"
+build_name = "stage-overlay-collision"
+
+[workspace]
+root_dir = "{}"
+build_dir = "build"
+out_dir = "out"
+
+[image]
+kind = "starting-point"
+rootfs_path = "/tmp/rootfs"
+
+[[stage.files]]
+id = "gaia-config"
+src = "assets/config.toml"
+dest = "/etc/gaia/config.toml"
+origin = "static-asset"
+
+[[stage.overlays]]
+id = "base-overlay"
+src = "overlay"
+dest = "/"
+"#,
+            root.display()
+        ),
+    )
+    .expect("config");
+
+    let spec = resolve_config(config_path.to_str().expect("utf-8 path"));
+    let report = validate_spec(&spec);
+
+    let conflicts = report
+        .diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.code == "image_destination_conflict")
+        .collect::<Vec<_>>();
+    assert_eq!(conflicts.len(), 1, "{:?}", report.diagnostics);
+    assert!(conflicts[0].message.contains("'/etc/gaia/config.toml'"));
+    assert!(
+        conflicts[0]
+            .message
+            .contains("stage overlay 'base-overlay'")
+    );
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[cfg(unix)]
+#[test]
+fn stage_overlay_symlinks_escaping_the_overlay_root_are_rejected() {
+    let root = create_temp_workspace("gaia-stage-overlay-escape");
+    fs::create_dir_all(root.join("overlay/etc")).expect("overlay dir");
+    std::os::unix::fs::symlink("../../secret", root.join("overlay/etc/leak")).expect("symlink");
+    std::os::unix::fs::symlink("/usr/lib/os-release", root.join("overlay/etc/os-release"))
+        .expect("absolute symlink");
+    let config_path = root.join("build.toml");
+    fs::write(
+        &config_path,
+        format!(
+            r#"
+build_name = "stage-overlay-escape"
+
+[workspace]
+root_dir = "{}"
+build_dir = "build"
+out_dir = "out"
+
+[image]
+kind = "starting-point"
+rootfs_path = "/tmp/rootfs"
+
+[[stage.overlays]]
+id = "escape-overlay"
+src = "overlay"
+dest = "/"
+"#,
+            root.display()
+        ),
+    )
+    .expect("config");
+
+    let spec = resolve_config(config_path.to_str().expect("utf-8 path"));
+    let report = validate_spec(&spec);
+
+    let diagnostic = report
+        .diagnostics
+        .iter()
+        .find(|diagnostic| diagnostic.code == "stage_overlay_src_invalid")
+        .expect("escaping symlink should be rejected");
+    assert!(diagnostic.message.contains("escapes the overlay root"));
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn stage_symlink_links_participate_in_destination_collisions() {
+    let root = create_temp_workspace("gaia-stage-symlink-collision");
+    fs::create_dir_all(root.join("assets")).expect("assets dir");
+    fs::write(root.join("assets/sh"), "#!/bin/true").expect("stage file");
+    let config_path = root.join("build.toml");
+    fs::write(
+        &config_path,
+        format!(
+            r#"
+build_name = "stage-symlink-collision"
+
+[workspace]
+root_dir = "{}"
+build_dir = "build"
+out_dir = "out"
+
+[image]
+kind = "starting-point"
+rootfs_path = "/tmp/rootfs"
+
+[[stage.files]]
+id = "shell"
+src = "assets/sh"
+dest = "/usr/bin/sh"
+origin = "static-asset"
+
+[[stage.symlinks]]
+id = "busybox-sh"
+target = "/bin/busybox"
+link = "/usr/bin/sh"
+
+[[stage.symlinks]]
+id = "relative-link"
+target = "busybox"
+link = "bin/ash"
+"#,
+            root.display()
+        ),
+    )
+    .expect("config");
+
+    let spec = resolve_config(config_path.to_str().expect("utf-8 path"));
+    assert_eq!(
+        spec.image.feed.stage_symlinks,
+        vec!["busybox-sh".into(), "relative-link".into()]
+    );
+    let report = validate_spec(&spec);
+
+    let conflict = report
+        .diagnostics
+        .iter()
+        .find(|diagnostic| diagnostic.code == "image_destination_conflict")
+        .expect("symlink collision");
+    assert!(conflict.message.contains("stage symlink 'busybox-sh'"));
+    assert!(
+        report
+            .diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.code == "stage_symlink_link_not_absolute")
+    );
+
+    let _ = fs::remove_dir_all(root);
+}
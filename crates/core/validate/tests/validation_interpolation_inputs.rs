@@ -153,3 +153,134 @@ rootfs_path = "/tmp/rootfs"
 
     let _ = fs::remove_file(path);
 }
+
+#[test]
+fn numeric_inputs_are_checked_against_min_and_max() {
+    let path = write_temp_config(
+        r#"
+build_name = "ranged-inputs"
+
+[inputs.jobs]
+kind = "integer"
+default = "4"
+min = 1
+max = 64
+
+[inputs.top_level_load]
+kind = "float"
+default = "0.5"
+min = 1.5
+
+[inputs.label]
+kind = "string"
+max = 3
+
+[workspace]
+root_dir = "."
+build_dir = "build"
+out_dir = "out"
+
+[image]
+kind = "starting-point"
+rootfs_path = "/tmp/rootfs"
+"#,
+    );
+    let path_str = path.to_str().expect("temp path utf-8");
+
+    let report = validate_spec(&resolve_config(path_str));
+    let codes = |location: &str| {
+        report
+            .diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.location.as_deref() == Some(location))
+            .map(|diagnostic| diagnostic.code)
+            .collect::<Vec<_>>()
+    };
+    assert!(codes("input:jobs").is_empty());
+    assert_eq!(codes("input:top_level_load"), ["input_out_of_range"]);
+    assert_eq!(codes("input:label"), ["input_bound_unsupported"]);
+
+    let overridden = gaia_config::resolve_config_with_options(
+        path_str,
+        &gaia_config::ResolveOptions {
+            explicit_overrides: vec![("input.jobs".into(), "65".into())],
+            ..gaia_config::ResolveOptions::default()
+        },
+    );
+    let report = validate_spec(&overridden);
+    assert!(report.diagnostics.iter().any(|diagnostic| {
+        diagnostic.code == "input_out_of_range"
+            && diagnostic.message == "input 'jobs' must be within 1..=64, got '65'"
+    }));
+
+    let _ = fs::remove_file(path);
+}
+
+#[test]
+fn string_inputs_are_checked_against_their_pattern() {
+    let path = write_temp_config(
+        r#"
+build_name = "pattern-inputs"
+
+[inputs.version]
+kind = "string"
+default = "1.4.0"
+pattern = '\d+\.\d+\.\d+'
+
+[inputs.image_name]
+kind = "string"
+default = "Gaia Image"
+pattern = "[a-z][a-z0-9-]*"
+
+[inputs.channel]
+kind = "string"
+default = "stable"
+pattern = "(stable|beta"
+
+[inputs.jobs]
+kind = "integer"
+default = "4"
+pattern = "[0-9]+"
+
+[workspace]
+root_dir = "."
+build_dir = "build"
+out_dir = "out"
+
+[image]
+kind = "starting-point"
+rootfs_path = "/tmp/rootfs"
+"#,
+    );
+    let path_str = path.to_str().expect("temp path utf-8");
+
+    let report = validate_spec(&resolve_config(path_str));
+    let codes = |location: &str| {
+        report
+            .diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.location.as_deref() == Some(location))
+            .map(|diagnostic| diagnostic.code)
+            .collect::<Vec<_>>()
+    };
+    assert!(codes("input:version").is_empty());
+    assert_eq!(codes("input:image_name"), ["input_pattern_mismatch"]);
+    assert_eq!(codes("input:channel"), ["input_pattern_invalid"]);
+    assert_eq!(codes("input:jobs"), ["input_pattern_unsupported"]);
+
+    let overridden = gaia_config::resolve_config_with_options(
+        path_str,
+        &gaia_config::ResolveOptions {
+            explicit_overrides: vec![("input.version".into(), "1.4".into())],
+            ..gaia_config::ResolveOptions::default()
+        },
+    );
+    let report = validate_spec(&overridden);
+    assert!(report.diagnostics.iter().any(|diagnostic| {
+        diagnostic.code == "input_pattern_mismatch"
+            && diagnostic.message
+                == r"input 'version' must match pattern '\d+\.\d+\.\d+', got '1.4'"
+    }));
+
+    let _ = fs::remove_file(path);
+}
@@ -1,9 +1,10 @@
 use gaia_artifact_providers::{
     ArtifactBackendState, ArtifactExecutionContract, ArtifactPlan, ArtifactProvider,
     ArtifactProviderError, ArtifactProviderErrorKind, ArtifactProviderOperation,
-    ArtifactProviderValidationIssue, ProcessCancelCheck, ProcessLogSink, artifact_output_path,
-    command_version_line, ensure_artifact_output_parent, materialize_artifact_marker_and_state,
-    render_artifact_backend_state, run_command_with_retries,
+    ArtifactProviderValidationIssue, PlannedCommand, ProcessCancelCheck, ProcessLogSink,
+    artifact_host_tools, artifact_output_path, command_version_line, ensure_artifact_output_parent,
+    materialize_artifact_marker_and_state, planned_command, render_artifact_backend_state,
+    run_command_with_retries,
 };
 use gaia_spec::{ArtifactDefinition, ArtifactSpec, ResolvedBuildSpec};
 use std::process::Command;
@@ -15,10 +16,18 @@ impl ArtifactProvider for GoProvider {
         "artifact.go"
     }
 
+    fn describe(&self) -> &'static str {
+        "Builds a Go package with go build."
+    }
+
     fn kind(&self) -> gaia_spec::ArtifactProviderKind {
         gaia_spec::ArtifactProviderKind::Go
     }
 
+    fn host_tools(&self, spec: &ResolvedBuildSpec) -> Vec<&'static str> {
+        artifact_host_tools(spec, self.kind(), &["go"])
+    }
+
     fn supports(&self, _spec: &ResolvedBuildSpec) -> bool {
         true
     }
@@ -56,22 +65,11 @@ impl ArtifactProvider for GoProvider {
         log_sink: Option<ProcessLogSink>,
         cancel_check: Option<ProcessCancelCheck>,
     ) -> Result<Vec<String>, ArtifactProviderError> {
-        let package = match &artifact.definition {
-            ArtifactDefinition::Go(go) => go.package.clone(),
-            _ => artifact.id.as_str().to_string(),
-        };
+        let package = go_package(artifact);
         let source_dir = contract.source_dir.as_deref().unwrap_or(".");
-        let output_path = artifact_output_path(contract, source_dir);
-        ensure_artifact_output_parent(&output_path)?;
+        ensure_artifact_output_parent(&artifact_output_path(contract, source_dir))?;
 
-        let mut command = Command::new("go");
-        command
-            .arg("build")
-            .arg("-o")
-            .arg(&output_path)
-            .arg(&package)
-            .current_dir(source_dir);
-        apply_go_target_env(&mut command, contract.artifact_target.as_deref())?;
+        let command = go_build_command(&package, contract)?;
         run_command(command, &package, contract, log_sink, cancel_check)?;
         let mut messages = Vec::new();
         write_marker(self.id(), artifact, contract, &package)?;
@@ -83,6 +81,43 @@ impl ArtifactProvider for GoProvider {
         ));
         Ok(messages)
     }
+
+    fn planned_artifact_commands(
+        &self,
+        artifact: &ArtifactSpec,
+        contract: &ArtifactExecutionContract,
+    ) -> Result<Vec<PlannedCommand>, ArtifactProviderError> {
+        let package = go_package(artifact);
+        let command = go_build_command(&package, contract)?;
+        Ok(vec![planned_command(
+            &command,
+            contract,
+            &format!("go build for package '{package}'"),
+        )?])
+    }
+}
+
+fn go_package(artifact: &ArtifactSpec) -> String {
+    match &artifact.definition {
+        ArtifactDefinition::Go(go) => go.package.clone(),
+        _ => artifact.id.as_str().to_string(),
+    }
+}
+
+fn go_build_command(
+    package: &str,
+    contract: &ArtifactExecutionContract,
+) -> Result<Command, ArtifactProviderError> {
+    let source_dir = contract.source_dir.as_deref().unwrap_or(".");
+    let mut command = Command::new("go");
+    command
+        .arg("build")
+        .arg("-o")
+        .arg(artifact_output_path(contract, source_dir))
+        .arg(package)
+        .current_dir(source_dir);
+    apply_go_target_env(&mut command, contract.artifact_target.as_deref())?;
+    Ok(command)
 }
 
 fn run_command(
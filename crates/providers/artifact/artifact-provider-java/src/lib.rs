@@ -1,11 +1,16 @@
 use gaia_artifact_providers::{
-    ArtifactBackendState, ArtifactExecutionContract, ArtifactPlan, ArtifactProvider,
-    ArtifactProviderError, ArtifactProviderErrorKind, ArtifactProviderOperation,
-    ArtifactProviderValidationIssue, ProcessCancelCheck, ProcessLogSink, artifact_output_path,
-    command_version_line, copy_artifact_file_to_output, materialize_artifact_marker_and_state,
-    render_artifact_backend_state, run_command_with_retries,
+    ArtifactBackendState, ArtifactExecutionBackend, ArtifactExecutionContract, ArtifactPlan,
+    ArtifactProvider, ArtifactProviderError, ArtifactProviderErrorKind, ArtifactProviderOperation,
+    ArtifactProviderValidationIssue, PlannedCommand, ProcessCancelCheck, ProcessLogSink,
+    artifact_host_tools, artifact_output_path, command_version_line, copy_artifact_file_to_output,
+    materialize_artifact_marker_and_state, planned_command, render_artifact_backend_state,
+    run_command_with_retries,
 };
-use gaia_spec::{ArtifactDefinition, ArtifactSpec, ResolvedBuildSpec};
+use gaia_spec::{
+    ArtifactDefinition, ArtifactSpec, JavaArtifactSpec, JavaBuildToolSpec, ResolvedBuildSpec,
+};
+use std::env;
+use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
@@ -16,10 +21,18 @@ impl ArtifactProvider for JavaProvider {
         "artifact.java"
     }
 
+    fn describe(&self) -> &'static str {
+        "Builds a Java target with Maven or Gradle."
+    }
+
     fn kind(&self) -> gaia_spec::ArtifactProviderKind {
         gaia_spec::ArtifactProviderKind::Java
     }
 
+    fn host_tools(&self, spec: &ResolvedBuildSpec) -> Vec<&'static str> {
+        artifact_host_tools(spec, self.kind(), &["java"])
+    }
+
     fn supports(&self, _spec: &ResolvedBuildSpec) -> bool {
         true
     }
@@ -69,13 +82,11 @@ impl ArtifactProvider for JavaProvider {
         cancel_check: Option<ProcessCancelCheck>,
     ) -> Result<Vec<String>, ArtifactProviderError> {
         reject_unsupported_artifact_target(artifact)?;
-        let build_target = match &artifact.definition {
-            ArtifactDefinition::Java(java) => java.build_target.clone(),
-            _ => artifact.id.as_str().to_string(),
-        };
+        let java = java_spec(artifact);
+        let build_target = java.build_target.clone();
         let source_dir = contract.source_dir.as_deref().unwrap_or(".");
         let (build_tool, mut messages) =
-            run_java_build(source_dir, contract, log_sink, cancel_check)?;
+            run_java_build(source_dir, &java, contract, log_sink, cancel_check)?;
         let built_path = resolve_java_built_path(source_dir, &build_target)?;
         let output_path = artifact_output_path(contract, source_dir);
         copy_artifact_file_to_output(&built_path, &output_path, "built java artifact")?;
@@ -88,6 +99,28 @@ impl ArtifactProvider for JavaProvider {
         ));
         Ok(messages)
     }
+
+    fn planned_artifact_commands(
+        &self,
+        artifact: &ArtifactSpec,
+        contract: &ArtifactExecutionContract,
+    ) -> Result<Vec<PlannedCommand>, ArtifactProviderError> {
+        reject_unsupported_artifact_target(artifact)?;
+        let source_dir = contract.source_dir.as_deref().unwrap_or(".");
+        let (_, label, command) = java_build_command(source_dir, &java_spec(artifact))?;
+        Ok(vec![planned_command(&command, contract, label)?])
+    }
+}
+
+fn java_spec(artifact: &ArtifactSpec) -> JavaArtifactSpec {
+    match &artifact.definition {
+        ArtifactDefinition::Java(java) => java.clone(),
+        _ => JavaArtifactSpec {
+            build_target: artifact.id.as_str().to_string(),
+            build_tool: None,
+            tasks: Vec::new(),
+        },
+    }
 }
 
 fn reject_unsupported_artifact_target(
@@ -110,65 +143,96 @@ fn reject_unsupported_artifact_target(
 
 fn run_java_build(
     source_dir: &str,
+    java: &JavaArtifactSpec,
     contract: &ArtifactExecutionContract,
     log_sink: Option<ProcessLogSink>,
     cancel_check: Option<ProcessCancelCheck>,
 ) -> Result<(String, Vec<String>), ArtifactProviderError> {
+    let (build_tool, label, command) = java_build_command(source_dir, java)?;
+    ensure_build_tool_available(&command, &contract.execution_backend)?;
+    Ok((
+        build_tool.to_string(),
+        run_command(command, label, contract, log_sink, cancel_check)?,
+    ))
+}
+
+fn java_build_command(
+    source_dir: &str,
+    java: &JavaArtifactSpec,
+) -> Result<(&'static str, &'static str, Command), ArtifactProviderError> {
     let source_dir = Path::new(source_dir);
-    if source_dir.join("pom.xml").is_file() {
-        let mut command = Command::new("mvn");
-        command
-            .arg("-q")
-            .arg("-DskipTests")
-            .arg("package")
-            .current_dir(source_dir);
-        return Ok((
-            "maven".to_string(),
-            run_command(
-                command,
-                "maven package",
-                contract,
-                log_sink,
-                cancel_check.clone(),
-            )?,
-        ));
-    }
+    let has_gradle_build =
+        source_dir.join("build.gradle").is_file() || source_dir.join("build.gradle.kts").is_file();
+    let build_tool = match java.build_tool {
+        Some(build_tool) => build_tool,
+        None if source_dir.join("pom.xml").is_file() => JavaBuildToolSpec::Maven,
+        None if source_dir.join("gradlew").is_file() || has_gradle_build => {
+            JavaBuildToolSpec::Gradle
+        }
+        None => {
+            return Err(ArtifactProviderError::new(
+                ArtifactProviderErrorKind::PolicyBlocked,
+                format!(
+                    "java source '{}' did not contain a supported build file (pom.xml, gradlew, build.gradle, build.gradle.kts)",
+                    source_dir.display()
+                ),
+            ));
+        }
+    };
 
-    if source_dir.join("gradlew").is_file() {
-        let mut command = Command::new(source_dir.join("gradlew"));
-        command.arg("build").arg("-q").current_dir(source_dir);
-        return Ok((
-            "gradle-wrapper".to_string(),
-            run_command(
-                command,
-                "gradle wrapper build",
-                contract,
-                log_sink,
-                cancel_check.clone(),
-            )?,
-        ));
+    match build_tool {
+        JavaBuildToolSpec::Maven => {
+            let mut command = Command::new("mvn");
+            command.arg("-q").arg("-DskipTests");
+            add_tasks(&mut command, &java.tasks, "package");
+            command.current_dir(source_dir);
+            Ok(("maven", "maven package", command))
+        }
+        // The wrapper pins the Gradle version the project was written against.
+        JavaBuildToolSpec::Gradle if source_dir.join("gradlew").is_file() => {
+            let mut command = Command::new(source_dir.join("gradlew"));
+            add_tasks(&mut command, &java.tasks, "build");
+            command.arg("-q").current_dir(source_dir);
+            Ok(("gradle-wrapper", "gradle wrapper build", command))
+        }
+        JavaBuildToolSpec::Gradle => {
+            let mut command = Command::new("gradle");
+            add_tasks(&mut command, &java.tasks, "build");
+            command.arg("-q").current_dir(source_dir);
+            Ok(("gradle", "gradle build", command))
+        }
     }
+}
 
-    if source_dir.join("build.gradle").is_file() || source_dir.join("build.gradle.kts").is_file() {
-        let mut command = Command::new("gradle");
-        command.arg("build").arg("-q").current_dir(source_dir);
-        return Ok((
-            "gradle".to_string(),
-            run_command(
-                command,
-                "gradle build",
-                contract,
-                log_sink,
-                cancel_check.clone(),
-            )?,
-        ));
+fn add_tasks(command: &mut Command, tasks: &[String], default_task: &str) {
+    if tasks.is_empty() {
+        command.arg(default_task);
+    } else {
+        command.args(tasks);
     }
+}
 
+// Checked up front so a missing Maven or Gradle reads as a setup problem rather than a
+// generic spawn failure. A Docker build runs the tool from the image, so the host PATH says
+// nothing about it.
+fn ensure_build_tool_available(
+    command: &Command,
+    backend: &ArtifactExecutionBackend,
+) -> Result<(), ArtifactProviderError> {
+    let program = Path::new(command.get_program());
+    if matches!(backend, ArtifactExecutionBackend::Docker(_)) || program.components().count() > 1 {
+        return Ok(());
+    }
+    let found = env::var_os("PATH")
+        .is_some_and(|path| env::split_paths(&path).any(|dir| dir.join(program).is_file()));
+    if found {
+        return Ok(());
+    }
     Err(ArtifactProviderError::new(
-        ArtifactProviderErrorKind::PolicyBlocked,
+        ArtifactProviderErrorKind::ToolStart,
         format!(
-            "java source '{}' did not contain a supported build file (pom.xml, gradlew, build.gradle, build.gradle.kts)",
-            source_dir.display()
+            "java build tool '{}' was not found on PATH",
+            program.display()
         ),
     ))
 }
@@ -191,15 +255,111 @@ fn resolve_java_built_path(
             .join(build_target),
     ];
 
-    candidates
-        .into_iter()
-        .find(|candidate| candidate.is_file())
-        .ok_or_else(|| ArtifactProviderError::new(
+    if !is_glob(build_target) {
+        return candidates
+            .into_iter()
+            .find(|candidate| candidate.is_file())
+            .ok_or_else(|| ArtifactProviderError::new(
                 ArtifactProviderErrorKind::OutputMissing,
                 format!(
-                "java build completed but built target '{}' was not found in expected locations",
-                build_target
-            )))
+                    "java build completed but built target '{}' was not found in expected locations",
+                    build_target
+                )));
+    }
+
+    // Patterns only match within the final path component; the first location with matches wins.
+    for candidate in &candidates {
+        let matches = glob_matches(candidate);
+        match matches.len() {
+            0 => continue,
+            1 => return Ok(matches.into_iter().next().expect("one match")),
+            _ => {
+                return Err(ArtifactProviderError::new(
+                    ArtifactProviderErrorKind::OutputMissing,
+                    format!(
+                        "java build_target pattern '{}' matched {} files, expected exactly one: {}",
+                        build_target,
+                        matches.len(),
+                        matches
+                            .iter()
+                            .map(|path| path.display().to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                ));
+            }
+        }
+    }
+    Err(ArtifactProviderError::new(
+        ArtifactProviderErrorKind::OutputMissing,
+        format!(
+            "java build completed but no file matched build_target pattern '{}' in {}",
+            build_target,
+            candidates
+                .iter()
+                .filter_map(|candidate| candidate.parent())
+                .map(|dir| dir.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    ))
+}
+
+fn is_glob(value: &str) -> bool {
+    value.contains(['*', '?'])
+}
+
+fn glob_matches(pattern: &Path) -> Vec<PathBuf> {
+    let (Some(dir), Some(name)) = (pattern.parent(), pattern.file_name()) else {
+        return Vec::new();
+    };
+    let name = name.to_string_lossy();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut matches = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            path.file_name()
+                .is_some_and(|file| wildcard_match(&name, &file.to_string_lossy()))
+        })
+        .collect::<Vec<_>>();
+    matches.sort();
+    matches
+}
+
+fn wildcard_match(pattern: &str, value: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let value = value.chars().collect::<Vec<_>>();
+    let (mut p, mut v) = (0, 0);
+    let mut backtrack = None;
+    while v < value.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, v));
+                p += 1;
+            }
+            Some('?') => {
+                p += 1;
+                v += 1;
+            }
+            Some(ch) if *ch == value[v] => {
+                p += 1;
+                v += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    v = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|ch| *ch == '*')
 }
 
 fn run_command(
@@ -267,6 +427,7 @@ fn artifact_state_contents(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use gaia_artifact_providers::ArtifactDockerExecution;
     use std::fs;
     use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -290,6 +451,8 @@ mod tests {
                     "java-missing-tool",
                     ArtifactDefinition::Java(gaia_spec::JavaArtifactSpec {
                         build_target: "app.jar".into(),
+                        build_tool: None,
+                        tasks: Vec::new(),
                     }),
                     None,
                     gaia_spec::ArtifactOutputSpec {
@@ -321,6 +484,8 @@ mod tests {
             "java-artifact",
             ArtifactDefinition::Java(gaia_spec::JavaArtifactSpec {
                 build_target: "build/libs/app.jar".into(),
+                build_tool: None,
+                tasks: Vec::new(),
             }),
             None,
             gaia_spec::ArtifactOutputSpec {
@@ -356,6 +521,8 @@ mod tests {
             "java-targeted",
             ArtifactDefinition::Java(gaia_spec::JavaArtifactSpec {
                 build_target: "build/libs/app.jar".into(),
+                build_tool: None,
+                tasks: Vec::new(),
             }),
             None,
             gaia_spec::ArtifactOutputSpec {
@@ -379,6 +546,8 @@ mod tests {
             "java-targeted",
             ArtifactDefinition::Java(gaia_spec::JavaArtifactSpec {
                 build_target: "build/libs/app.jar".into(),
+                build_tool: None,
+                tasks: Vec::new(),
             }),
             None,
             gaia_spec::ArtifactOutputSpec {
@@ -405,4 +574,72 @@ mod tests {
                 .contains("target-aware builds are not supported yet")
         );
     }
+
+    #[test]
+    fn java_build_command_runs_configured_gradle_tasks() {
+        let source_dir = temp_path("gaia-java-provider-gradle");
+        fs::create_dir_all(&source_dir).expect("source dir");
+        fs::write(source_dir.join("pom.xml"), "<project/>").expect("pom");
+        let java = gaia_spec::JavaArtifactSpec {
+            build_target: "*-all.jar".into(),
+            build_tool: Some(JavaBuildToolSpec::Gradle),
+            tasks: vec!["shadowJar".into()],
+        };
+
+        let (tool, _, command) =
+            java_build_command(&source_dir.display().to_string(), &java).expect("command");
+
+        assert_eq!(tool, "gradle");
+        assert_eq!(command.get_program(), "gradle");
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            vec!["shadowJar", "-q"]
+        );
+    }
+
+    #[test]
+    fn resolve_java_built_path_matches_a_single_glob_result() {
+        let source_dir = temp_path("gaia-java-provider-glob");
+        let libs = source_dir.join("build/libs");
+        fs::create_dir_all(&libs).expect("libs");
+        fs::write(libs.join("app-1.2.0.jar"), "thin").expect("jar");
+        fs::write(libs.join("app-1.2.0-all.jar"), "fat").expect("jar");
+        let source_dir = source_dir.display().to_string();
+
+        let built = resolve_java_built_path(&source_dir, "app-*-all.jar").expect("single match");
+        assert_eq!(built, libs.join("app-1.2.0-all.jar"));
+
+        let error = resolve_java_built_path(&source_dir, "app-*.jar").expect_err("ambiguous");
+        assert!(error.message.contains("matched 2 files"));
+
+        let error = resolve_java_built_path(&source_dir, "*-shaded.jar").expect_err("no match");
+        assert_eq!(error.kind, ArtifactProviderErrorKind::OutputMissing);
+        assert!(error.message.contains("no file matched"));
+    }
+
+    #[test]
+    fn ensure_build_tool_available_reports_missing_tool() {
+        let error = ensure_build_tool_available(
+            &Command::new("gaia-missing-mvn"),
+            &ArtifactExecutionBackend::Host,
+        )
+        .expect_err("missing tool should fail");
+
+        assert_eq!(error.kind, ArtifactProviderErrorKind::ToolStart);
+        assert!(
+            error
+                .message
+                .contains("java build tool 'gaia-missing-mvn' was not found on PATH")
+        );
+    }
+
+    #[test]
+    fn ensure_build_tool_available_skips_docker_builds() {
+        let backend = ArtifactExecutionBackend::Docker(ArtifactDockerExecution {
+            image: "maven:3-eclipse-temurin-21".to_string(),
+        });
+
+        ensure_build_tool_available(&Command::new("gaia-missing-mvn"), &backend)
+            .expect("the image provides the tool");
+    }
 }
@@ -1,9 +1,10 @@
 use gaia_artifact_providers::{
     ArtifactBackendState, ArtifactExecutionContract, ArtifactPlan, ArtifactProvider,
     ArtifactProviderError, ArtifactProviderErrorKind, ArtifactProviderOperation,
-    ArtifactProviderValidationIssue, ProcessCancelCheck, ProcessLogSink, artifact_output_path,
-    artifact_package_root, command_version_line, copy_artifact_file_to_output,
-    materialize_artifact_marker_and_state, render_artifact_backend_state, run_command_with_retries,
+    ArtifactProviderValidationIssue, PlannedCommand, ProcessCancelCheck, ProcessLogSink,
+    artifact_host_tools, artifact_output_path, artifact_package_root, command_version_line,
+    copy_artifact_file_to_output, materialize_artifact_marker_and_state, planned_command,
+    render_artifact_backend_state, run_command_with_retries,
 };
 use gaia_spec::{ArtifactDefinition, ArtifactSpec, ResolvedBuildSpec};
 use std::fs;
@@ -17,10 +18,18 @@ impl ArtifactProvider for NodeProvider {
         "artifact.node"
     }
 
+    fn describe(&self) -> &'static str {
+        "Packs a Node package directory with npm pack."
+    }
+
     fn kind(&self) -> gaia_spec::ArtifactProviderKind {
         gaia_spec::ArtifactProviderKind::Node
     }
 
+    fn host_tools(&self, spec: &ResolvedBuildSpec) -> Vec<&'static str> {
+        artifact_host_tools(spec, self.kind(), &["npm"])
+    }
+
     fn supports(&self, _spec: &ResolvedBuildSpec) -> bool {
         true
     }
@@ -70,26 +79,15 @@ impl ArtifactProvider for NodeProvider {
         cancel_check: Option<ProcessCancelCheck>,
     ) -> Result<Vec<String>, ArtifactProviderError> {
         reject_unsupported_artifact_target(artifact)?;
-        let package_dir = match &artifact.definition {
-            ArtifactDefinition::Node(node) => node.package_dir.clone(),
-            _ => artifact.id.as_str().to_string(),
-        };
+        let package_dir = node_package_dir(artifact);
         let source_dir = contract.source_dir.as_deref().unwrap_or(".");
-        let package_root = artifact_package_root(source_dir, &package_dir);
-        let pack_dir = package_root.join(".gaia-pack");
+        let (command, pack_dir) = npm_pack_command(source_dir, &package_dir);
         fs::create_dir_all(&pack_dir).map_err(|error| {
             format!(
                 "failed to create node pack dir '{}': {error}",
                 pack_dir.display()
             )
         })?;
-
-        let mut command = Command::new("npm");
-        command
-            .arg("pack")
-            .arg("--pack-destination")
-            .arg(&pack_dir)
-            .current_dir(&package_root);
         run_command(command, &package_dir, contract, log_sink, cancel_check)?;
         let mut messages = Vec::new();
 
@@ -105,6 +103,41 @@ impl ArtifactProvider for NodeProvider {
         ));
         Ok(messages)
     }
+
+    fn planned_artifact_commands(
+        &self,
+        artifact: &ArtifactSpec,
+        contract: &ArtifactExecutionContract,
+    ) -> Result<Vec<PlannedCommand>, ArtifactProviderError> {
+        reject_unsupported_artifact_target(artifact)?;
+        let package_dir = node_package_dir(artifact);
+        let source_dir = contract.source_dir.as_deref().unwrap_or(".");
+        let (command, _) = npm_pack_command(source_dir, &package_dir);
+        Ok(vec![planned_command(
+            &command,
+            contract,
+            &format!("npm pack for package dir '{package_dir}'"),
+        )?])
+    }
+}
+
+fn node_package_dir(artifact: &ArtifactSpec) -> String {
+    match &artifact.definition {
+        ArtifactDefinition::Node(node) => node.package_dir.clone(),
+        _ => artifact.id.as_str().to_string(),
+    }
+}
+
+fn npm_pack_command(source_dir: &str, package_dir: &str) -> (Command, PathBuf) {
+    let package_root = artifact_package_root(source_dir, package_dir);
+    let pack_dir = package_root.join(".gaia-pack");
+    let mut command = Command::new("npm");
+    command
+        .arg("pack")
+        .arg("--pack-destination")
+        .arg(&pack_dir)
+        .current_dir(&package_root);
+    (command, pack_dir)
 }
 
 fn reject_unsupported_artifact_target(
@@ -1,9 +1,10 @@
 use gaia_artifact_providers::{
     ArtifactBackendState, ArtifactExecutionContract, ArtifactPlan, ArtifactProvider,
     ArtifactProviderError, ArtifactProviderErrorKind, ArtifactProviderOperation,
-    ArtifactProviderValidationIssue, ProcessCancelCheck, ProcessLogSink, artifact_output_path,
-    artifact_package_root, command_version_line, copy_artifact_file_to_output,
-    materialize_artifact_marker_and_state, render_artifact_backend_state, run_command_with_retries,
+    ArtifactProviderValidationIssue, PlannedCommand, ProcessCancelCheck, ProcessLogSink,
+    artifact_host_tools, artifact_output_path, artifact_package_root, command_version_line,
+    copy_artifact_file_to_output, materialize_artifact_marker_and_state, planned_command,
+    render_artifact_backend_state, run_command_with_retries,
 };
 use gaia_spec::{ArtifactDefinition, ArtifactSpec, ResolvedBuildSpec};
 use std::fs;
@@ -17,10 +18,18 @@ impl ArtifactProvider for PythonProvider {
         "artifact.python"
     }
 
+    fn describe(&self) -> &'static str {
+        "Builds a wheel from a Python package directory with pip."
+    }
+
     fn kind(&self) -> gaia_spec::ArtifactProviderKind {
         gaia_spec::ArtifactProviderKind::Python
     }
 
+    fn host_tools(&self, spec: &ResolvedBuildSpec) -> Vec<&'static str> {
+        artifact_host_tools(spec, self.kind(), &["python3"])
+    }
+
     fn supports(&self, _spec: &ResolvedBuildSpec) -> bool {
         true
     }
@@ -70,30 +79,15 @@ impl ArtifactProvider for PythonProvider {
         cancel_check: Option<ProcessCancelCheck>,
     ) -> Result<Vec<String>, ArtifactProviderError> {
         reject_unsupported_artifact_target(artifact)?;
-        let package_dir = match &artifact.definition {
-            ArtifactDefinition::Python(python) => python.package_dir.clone(),
-            _ => artifact.id.as_str().to_string(),
-        };
+        let package_dir = python_package_dir(artifact);
         let source_dir = contract.source_dir.as_deref().unwrap_or(".");
-        let package_root = artifact_package_root(source_dir, &package_dir);
-        let wheelhouse = package_root.join(".gaia-wheelhouse");
+        let (command, wheelhouse) = pip_wheel_command(source_dir, &package_dir);
         fs::create_dir_all(&wheelhouse).map_err(|error| {
             format!(
                 "failed to create python wheelhouse '{}': {error}",
                 wheelhouse.display()
             )
         })?;
-
-        let mut command = Command::new("python3");
-        command
-            .arg("-m")
-            .arg("pip")
-            .arg("wheel")
-            .arg(".")
-            .arg("--no-deps")
-            .arg("-w")
-            .arg(&wheelhouse)
-            .current_dir(&package_root);
         run_command(command, &package_dir, contract, log_sink, cancel_check)?;
         let mut messages = Vec::new();
 
@@ -109,6 +103,45 @@ impl ArtifactProvider for PythonProvider {
         ));
         Ok(messages)
     }
+
+    fn planned_artifact_commands(
+        &self,
+        artifact: &ArtifactSpec,
+        contract: &ArtifactExecutionContract,
+    ) -> Result<Vec<PlannedCommand>, ArtifactProviderError> {
+        reject_unsupported_artifact_target(artifact)?;
+        let package_dir = python_package_dir(artifact);
+        let source_dir = contract.source_dir.as_deref().unwrap_or(".");
+        let (command, _) = pip_wheel_command(source_dir, &package_dir);
+        Ok(vec![planned_command(
+            &command,
+            contract,
+            &format!("python wheel build for package dir '{package_dir}'"),
+        )?])
+    }
+}
+
+fn python_package_dir(artifact: &ArtifactSpec) -> String {
+    match &artifact.definition {
+        ArtifactDefinition::Python(python) => python.package_dir.clone(),
+        _ => artifact.id.as_str().to_string(),
+    }
+}
+
+fn pip_wheel_command(source_dir: &str, package_dir: &str) -> (Command, PathBuf) {
+    let package_root = artifact_package_root(source_dir, package_dir);
+    let wheelhouse = package_root.join(".gaia-wheelhouse");
+    let mut command = Command::new("python3");
+    command
+        .arg("-m")
+        .arg("pip")
+        .arg("wheel")
+        .arg(".")
+        .arg("--no-deps")
+        .arg("-w")
+        .arg(&wheelhouse)
+        .current_dir(&package_root);
+    (command, wheelhouse)
 }
 
 fn reject_unsupported_artifact_target(
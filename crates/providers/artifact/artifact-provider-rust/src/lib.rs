@@ -1,13 +1,19 @@
+mod toolchain;
+
 use gaia_artifact_providers::{
     ArtifactBackendState, ArtifactExecutionContract, ArtifactPlan, ArtifactProvider,
     ArtifactProviderError, ArtifactProviderErrorKind, ArtifactProviderOperation,
-    ArtifactProviderValidationIssue, ProcessCancelCheck, ProcessLogSink, artifact_output_path,
-    command_version_line, copy_artifact_file_to_output, materialize_artifact_marker_and_state,
-    materialize_artifact_output, render_artifact_backend_state, run_command_with_retries,
+    ArtifactProviderValidationIssue, PlannedCommand, ProcessCancelCheck, ProcessLogSink,
+    artifact_host_tools, artifact_output_path, command_version_line, copy_artifact_file_to_output,
+    materialize_artifact_marker_and_state, materialize_artifact_output, planned_command,
+    render_artifact_backend_state, run_command_with_retries,
+};
+use gaia_spec::{
+    ArtifactDefinition, ArtifactSpec, BuildModeSpec, ResolvedBuildSpec, RustArtifactSpec,
 };
-use gaia_spec::{ArtifactDefinition, ArtifactSpec, BuildModeSpec, ResolvedBuildSpec};
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use toolchain::{check_cross_toolchain, cross_target_env};
 
 pub struct RustProvider;
 
@@ -16,10 +22,23 @@ impl ArtifactProvider for RustProvider {
         "artifact.rust"
     }
 
+    fn describe(&self) -> &'static str {
+        "Builds a Rust package with cargo."
+    }
+
     fn kind(&self) -> gaia_spec::ArtifactProviderKind {
         gaia_spec::ArtifactProviderKind::Rust
     }
 
+    fn host_tools(&self, spec: &ResolvedBuildSpec) -> Vec<&'static str> {
+        artifact_host_tools(spec, self.kind(), &["cargo"])
+    }
+
+    // Target checks fall back to the compiler's sysroot without rustup.
+    fn optional_host_tools(&self, spec: &ResolvedBuildSpec) -> Vec<&'static str> {
+        artifact_host_tools(spec, self.kind(), &["rustup"])
+    }
+
     fn supports(&self, _spec: &ResolvedBuildSpec) -> bool {
         true
     }
@@ -39,8 +58,10 @@ impl ArtifactProvider for RustProvider {
 
     fn validate_artifact(&self, artifact: &ArtifactSpec) -> Vec<ArtifactProviderValidationIssue> {
         let mut issues = Vec::new();
-        if let ArtifactDefinition::Rust(rust) = &artifact.definition
-            && let Some(target_name) = &rust.target_name
+        let ArtifactDefinition::Rust(rust) = &artifact.definition else {
+            return issues;
+        };
+        if let Some(target_name) = &rust.target_name
             && target_name.trim().is_empty()
         {
             issues.push(ArtifactProviderValidationIssue {
@@ -48,6 +69,15 @@ impl ArtifactProvider for RustProvider {
                 message: "rust target_name cannot be empty when set".into(),
             });
         }
+        if (rust.linker.is_some() || rust.sysroot.is_some()) && artifact.target.is_none() {
+            issues.push(ArtifactProviderValidationIssue {
+                code: "rust_cross_settings_require_target",
+                message: format!(
+                    "rust artifact '{}' sets linker or sysroot without a cross-compilation target",
+                    artifact.id.as_str()
+                ),
+            });
+        }
         issues
     }
 
@@ -58,22 +88,7 @@ impl ArtifactProvider for RustProvider {
         log_sink: Option<ProcessLogSink>,
         cancel_check: Option<ProcessCancelCheck>,
     ) -> Result<Vec<String>, ArtifactProviderError> {
-        let (package, target_name) = match &artifact.definition {
-            ArtifactDefinition::Rust(rust) => (
-                rust.package.clone(),
-                rust.target_name.clone().unwrap_or_else(|| {
-                    Path::new(&contract.output.path)
-                        .file_name()
-                        .and_then(|name| name.to_str())
-                        .unwrap_or(&rust.package)
-                        .to_string()
-                }),
-            ),
-            _ => (
-                artifact.id.as_str().to_string(),
-                artifact.id.as_str().to_string(),
-            ),
-        };
+        let (package, target_name) = rust_package_and_target(artifact, contract);
 
         let source_dir = contract.source_dir.as_deref().unwrap_or(".");
         let output_path = artifact_output_path(contract, source_dir);
@@ -81,10 +96,14 @@ impl ArtifactProvider for RustProvider {
         let (build_mode, mut messages) = if output_path.is_file() {
             ("existing-output", Vec::new())
         } else if contract.allow_nested_build {
+            if let Some(rust) = rust_definition(artifact) {
+                check_cross_toolchain(rust, contract, source_dir)?;
+            }
             (
                 "cargo",
                 build_with_cargo(
                     source_dir,
+                    rust_definition(artifact),
                     &package,
                     contract,
                     &target_name,
@@ -135,19 +154,63 @@ impl ArtifactProvider for RustProvider {
         ));
         Ok(messages)
     }
+
+    fn planned_artifact_commands(
+        &self,
+        artifact: &ArtifactSpec,
+        contract: &ArtifactExecutionContract,
+    ) -> Result<Vec<PlannedCommand>, ArtifactProviderError> {
+        let (package, _) = rust_package_and_target(artifact, contract);
+        let source_dir = contract.source_dir.as_deref().unwrap_or(".");
+        if artifact_output_path(contract, source_dir).is_file() || !contract.allow_nested_build {
+            return Ok(Vec::new());
+        }
+        let (command, _) =
+            cargo_build_command(source_dir, rust_definition(artifact), &package, contract);
+        Ok(vec![planned_command(
+            &command,
+            contract,
+            &format!("cargo build for package '{package}'"),
+        )?])
+    }
 }
 
-fn build_with_cargo(
+fn rust_definition(artifact: &ArtifactSpec) -> Option<&RustArtifactSpec> {
+    match &artifact.definition {
+        ArtifactDefinition::Rust(rust) => Some(rust),
+        _ => None,
+    }
+}
+
+fn rust_package_and_target(
+    artifact: &ArtifactSpec,
+    contract: &ArtifactExecutionContract,
+) -> (String, String) {
+    match &artifact.definition {
+        ArtifactDefinition::Rust(rust) => (
+            rust.package.clone(),
+            rust.target_name.clone().unwrap_or_else(|| {
+                Path::new(&contract.output.path)
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .unwrap_or(&rust.package)
+                    .to_string()
+            }),
+        ),
+        _ => (
+            artifact.id.as_str().to_string(),
+            artifact.id.as_str().to_string(),
+        ),
+    }
+}
+
+fn cargo_build_command(
     source_dir: &str,
+    rust: Option<&RustArtifactSpec>,
     package: &str,
     contract: &ArtifactExecutionContract,
-    target_name: &str,
-    log_sink: Option<ProcessLogSink>,
-    cancel_check: Option<ProcessCancelCheck>,
-) -> Result<Vec<String>, ArtifactProviderError> {
+) -> (Command, PathBuf) {
     let target_dir = PathBuf::from(source_dir).join(".gaia").join("cargo-target");
-    let cargo_target = contract.artifact_target.as_deref();
-
     let mut command = Command::new("cargo");
     command.arg("build").arg("-p").arg(package);
     if let Some(mode) = &contract.build_mode {
@@ -161,11 +224,29 @@ fn build_with_cargo(
             }
         }
     }
-    if let Some(target) = cargo_target {
+    if let Some(target) = contract.artifact_target.as_deref() {
         command.arg("--target").arg(target);
+        command.envs(
+            rust.map(|rust| cross_target_env(rust, target, source_dir))
+                .unwrap_or_default(),
+        );
     }
     command.arg("--target-dir").arg(&target_dir);
     command.current_dir(source_dir);
+    (command, target_dir)
+}
+
+fn build_with_cargo(
+    source_dir: &str,
+    rust: Option<&RustArtifactSpec>,
+    package: &str,
+    contract: &ArtifactExecutionContract,
+    target_name: &str,
+    log_sink: Option<ProcessLogSink>,
+    cancel_check: Option<ProcessCancelCheck>,
+) -> Result<Vec<String>, ArtifactProviderError> {
+    let cargo_target = contract.artifact_target.as_deref();
+    let (command, target_dir) = cargo_build_command(source_dir, rust, package, contract);
     run_command_with_retries(
         &command,
         contract,
@@ -282,6 +363,8 @@ mod tests {
                 package: "gaia".into(),
                 target_name: Some("gaia".into()),
                 variant: ArtifactVariantSpec::File,
+                linker: None,
+                sysroot: None,
             }),
             None,
             ArtifactOutputSpec {
@@ -307,6 +390,29 @@ mod tests {
         assert!(error.message.contains("failed to start cargo build"));
     }
 
+    #[test]
+    fn rust_provider_lists_rustup_as_optional_only_for_rust_builds() {
+        let mut spec = ResolvedBuildSpec::new("rust-host-tools");
+        assert!(RustProvider.optional_host_tools(&spec).is_empty());
+
+        spec.artifacts.push(ArtifactSpec::new(
+            "gaia-app",
+            ArtifactDefinition::Rust(RustArtifactSpec {
+                package: "gaia".into(),
+                target_name: None,
+                variant: ArtifactVariantSpec::File,
+                linker: None,
+                sysroot: None,
+            }),
+            None,
+            ArtifactOutputSpec {
+                path: "out/gaia".into(),
+            },
+        ));
+        assert_eq!(RustProvider.host_tools(&spec), vec!["cargo"]);
+        assert_eq!(RustProvider.optional_host_tools(&spec), vec!["rustup"]);
+    }
+
     #[test]
     fn rust_artifact_state_persists_backend_native_fields() {
         let output_path = temp_path("gaia-rust-provider-state");
@@ -317,6 +423,8 @@ mod tests {
                 package: "gaia".into(),
                 target_name: Some("gaia".into()),
                 variant: ArtifactVariantSpec::File,
+                linker: None,
+                sysroot: None,
             }),
             None,
             ArtifactOutputSpec {
@@ -349,4 +457,75 @@ mod tests {
         assert!(state.contains("compiler_tool=rustc"));
         assert!(state.contains("artifact_target=aarch64-unknown-linux-gnu"));
     }
+
+    #[test]
+    fn cross_toolchain_problems_fail_before_cargo_starts() {
+        let source_dir = temp_path("gaia-rust-provider-cross-source");
+        fs::create_dir_all(source_dir.join("sysroot/lib/rustlib")).expect("sysroot");
+        let rust = RustArtifactSpec {
+            package: "gaia".into(),
+            target_name: Some("gaia".into()),
+            variant: ArtifactVariantSpec::File,
+            linker: Some("gaia-missing-cross-gcc".into()),
+            sysroot: Some("sysroot".into()),
+        };
+        let mut artifact = ArtifactSpec::new(
+            "gaia-app",
+            ArtifactDefinition::Rust(rust.clone()),
+            None,
+            ArtifactOutputSpec {
+                path: source_dir.join("out/gaia").display().to_string(),
+            },
+        );
+        artifact.target = Some("aarch64-unknown-linux-gnu".into());
+        let contract = ArtifactExecutionContract::from_spec(
+            &artifact,
+            Some(source_dir.display().to_string()),
+            true,
+            ArtifactExecutionContract::default_command_policy(),
+            gaia_spec::OutputRetentionPolicySpec::default(),
+        );
+        let source = source_dir.to_str().expect("utf-8 source dir");
+
+        let missing_target = check_cross_toolchain(&rust, &contract, source)
+            .expect_err("target missing from sysroot");
+        assert!(missing_target.message.contains("missing from sysroot"));
+
+        fs::create_dir_all(source_dir.join("sysroot/lib/rustlib/aarch64-unknown-linux-gnu"))
+            .expect("target dir");
+        let missing_linker = RustProvider
+            .execute_artifact(&artifact, &contract, None, None)
+            .expect_err("missing linker");
+        assert!(
+            missing_linker
+                .message
+                .contains("linker 'gaia-missing-cross-gcc' was not found")
+        );
+
+        let missing_sysroot = RustArtifactSpec {
+            sysroot: Some("absent".into()),
+            ..rust.clone()
+        };
+        assert!(
+            check_cross_toolchain(&missing_sysroot, &contract, source)
+                .expect_err("missing sysroot")
+                .message
+                .contains("is not a directory")
+        );
+        assert_eq!(
+            cross_target_env(&rust, "aarch64-unknown-linux-gnu", source),
+            vec![
+                (
+                    "CARGO_TARGET_AARCH64_UNKNOWN_LINUX_GNU_LINKER".to_string(),
+                    "gaia-missing-cross-gcc".to_string()
+                ),
+                (
+                    "CARGO_TARGET_AARCH64_UNKNOWN_LINUX_GNU_RUSTFLAGS".to_string(),
+                    format!("--sysroot={source}/sysroot")
+                ),
+            ]
+        );
+
+        let _ = fs::remove_dir_all(source_dir);
+    }
 }
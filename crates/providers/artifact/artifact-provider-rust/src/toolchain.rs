@@ -0,0 +1,160 @@
+use gaia_artifact_providers::{
+    ArtifactExecutionBackend, ArtifactExecutionContract, ArtifactProviderError,
+    ArtifactProviderErrorKind, command_output_with_timeout,
+};
+use gaia_spec::RustArtifactSpec;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+const TOOLCHAIN_QUERY_TIMEOUT: Duration = Duration::from_secs(30);
+
+// Runs before cargo so a misconfigured cross toolchain fails with a direct message instead of a
+// linker error deep into the build. Docker builds are skipped: the host toolchain is not the one
+// that will run.
+pub(crate) fn check_cross_toolchain(
+    rust: &RustArtifactSpec,
+    contract: &ArtifactExecutionContract,
+    source_dir: &str,
+) -> Result<(), ArtifactProviderError> {
+    if !matches!(contract.execution_backend, ArtifactExecutionBackend::Host) {
+        return Ok(());
+    }
+    let sysroot = rust
+        .sysroot
+        .as_deref()
+        .map(|sysroot| Path::new(source_dir).join(sysroot));
+    if let Some(sysroot) = &sysroot
+        && !sysroot.is_dir()
+    {
+        return Err(toolchain_error(format!(
+            "rust sysroot '{}' is not a directory",
+            sysroot.display()
+        )));
+    }
+    if let Some(target) = contract.artifact_target.as_deref() {
+        check_target_installed(target, sysroot.as_deref(), contract, source_dir)?;
+    }
+    if let Some(linker) = rust.linker.as_deref()
+        && find_executable(linker, source_dir).is_none()
+    {
+        return Err(toolchain_error(format!(
+            "rust linker '{linker}' was not found on PATH; install it or point `linker` at the cross linker"
+        )));
+    }
+    Ok(())
+}
+
+// Cargo reads per-target linker and rustflags from these variables, so the settings only affect
+// the cross target and leave build scripts on the host toolchain. rustc runs from each package's
+// directory, so a relative sysroot is anchored to the source dir as the toolchain check reads it.
+pub(crate) fn cross_target_env(
+    rust: &RustArtifactSpec,
+    target: &str,
+    source_dir: &str,
+) -> Vec<(String, String)> {
+    let prefix = format!(
+        "CARGO_TARGET_{}",
+        target.to_ascii_uppercase().replace(['-', '.'], "_")
+    );
+    let mut vars = Vec::new();
+    if let Some(linker) = &rust.linker {
+        vars.push((format!("{prefix}_LINKER"), linker.clone()));
+    }
+    if let Some(sysroot) = &rust.sysroot {
+        vars.push((
+            format!("{prefix}_RUSTFLAGS"),
+            format!(
+                "--sysroot={}",
+                Path::new(source_dir).join(sysroot).display()
+            ),
+        ));
+    }
+    vars
+}
+
+fn check_target_installed(
+    target: &str,
+    sysroot: Option<&Path>,
+    contract: &ArtifactExecutionContract,
+    source_dir: &str,
+) -> Result<(), ArtifactProviderError> {
+    if let Some(sysroot) = sysroot {
+        let target_dir = sysroot.join("lib/rustlib").join(target);
+        if !target_dir.is_dir() {
+            return Err(toolchain_error(format!(
+                "rust target '{target}' is missing from sysroot '{}' (expected '{}')",
+                sysroot.display(),
+                target_dir.display()
+            )));
+        }
+        return Ok(());
+    }
+    if let Some(installed) = query_lines(
+        contract,
+        source_dir,
+        "rustup",
+        &["target", "list", "--installed"],
+    ) {
+        if !installed.iter().any(|line| line == target) {
+            return Err(toolchain_error(format!(
+                "rust target '{target}' is not installed; run `rustup target add {target}`"
+            )));
+        }
+        return Ok(());
+    }
+    // Without rustup, fall back to the active compiler's own sysroot.
+    let Some(rustc_sysroot) = query_lines(contract, source_dir, "rustc", &["--print", "sysroot"])
+        .and_then(|lines| lines.into_iter().next())
+    else {
+        return Ok(());
+    };
+    check_target_installed(
+        target,
+        Some(Path::new(&rustc_sysroot)),
+        contract,
+        source_dir,
+    )
+}
+
+// Run from the source dir so a `rust-toolchain.toml` there picks the toolchain, as it will for
+// cargo.
+fn query_lines(
+    contract: &ArtifactExecutionContract,
+    source_dir: &str,
+    program: &str,
+    args: &[&str],
+) -> Option<Vec<String>> {
+    let mut command = Command::new(program);
+    command.args(args).current_dir(source_dir);
+    contract.environment.apply(&mut command);
+    let output = command_output_with_timeout(
+        &mut command,
+        TOOLCHAIN_QUERY_TIMEOUT,
+        &format!("{program} toolchain query"),
+    )
+    .ok()?;
+    output.status.success().then(|| {
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect()
+    })
+}
+
+fn find_executable(program: &str, source_dir: &str) -> Option<PathBuf> {
+    if program.contains('/') {
+        let path = Path::new(source_dir).join(program);
+        return path.is_file().then_some(path);
+    }
+    env::split_paths(&env::var_os("PATH")?)
+        .map(|dir| dir.join(program))
+        .find(|path| path.is_file())
+}
+
+fn toolchain_error(message: String) -> ArtifactProviderError {
+    ArtifactProviderError::new(ArtifactProviderErrorKind::RuntimeState, message)
+}
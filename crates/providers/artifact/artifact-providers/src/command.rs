@@ -3,8 +3,8 @@ use crate::{
     ArtifactProviderError, ArtifactProviderErrorKind, ProcessCancelCheck, ProcessLogSink,
 };
 use gaia_process::{
-    DockerRunSpec, ProcessOutputRetention, ProcessRunErrorKind, docker_run_command,
-    run_command_with_timeout, run_command_with_timeout_and_retention,
+    DockerRunSpec, PlannedCommand, ProcessExit, ProcessOutputRetention, ProcessRunErrorKind,
+    docker_run_command, run_command_with_timeout, run_command_with_timeout_and_retention,
 };
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -70,11 +70,28 @@ pub fn command_for_execution(
     contract: &ArtifactExecutionContract,
 ) -> Result<Command, ArtifactProviderError> {
     match &contract.execution_backend {
-        ArtifactExecutionBackend::Host => Ok(gaia_process::clone_command(command)),
-        ArtifactExecutionBackend::Docker(docker) => docker_command(command, contract, docker),
+        ArtifactExecutionBackend::Host => {
+            let mut command = gaia_process::clone_command(command);
+            contract.environment.apply(&mut command);
+            Ok(command)
+        }
+        ArtifactExecutionBackend::Docker(docker) => {
+            let mut command = gaia_process::clone_command(command);
+            command.envs(&contract.environment.vars);
+            docker_command(&command, contract, docker)
+        }
     }
 }
 
+pub fn planned_command(
+    command: &Command,
+    contract: &ArtifactExecutionContract,
+    label: &str,
+) -> Result<PlannedCommand, ArtifactProviderError> {
+    command_for_execution(command, contract)
+        .map(|command| PlannedCommand::from_command(label, &command))
+}
+
 pub fn run_command_with_retries(
     command: &Command,
     contract: &ArtifactExecutionContract,
@@ -85,6 +102,7 @@ pub fn run_command_with_retries(
     let attempts = contract.retry_attempts.max(1);
     let timeout = Duration::from_secs(contract.timeout_seconds.max(1));
     let mut last_error = String::new();
+    let mut last_exit = None;
     for attempt in 1..=attempts {
         tracing::debug!(
             command_label = label,
@@ -120,6 +138,12 @@ pub fn run_command_with_retries(
             );
             return Ok(());
         }
+        last_exit = match contract.execution_backend {
+            ArtifactExecutionBackend::Host => ProcessExit::from_status(&output.output.status),
+            ArtifactExecutionBackend::Docker(_) => {
+                ProcessExit::from_container_status(&output.output.status)
+            }
+        };
         last_error = format!(
             "{label} failed on attempt {attempt}/{attempts}: {}",
             String::from_utf8_lossy(&output.output.stderr).trim()
@@ -168,10 +192,10 @@ pub fn run_command_with_retries(
         backend = execution_backend(contract),
         "artifact provider command exhausted retries"
     );
-    Err(ArtifactProviderError::new(
-        ArtifactProviderErrorKind::BackendCommand,
-        last_error,
-    ))
+    Err(
+        ArtifactProviderError::new(ArtifactProviderErrorKind::BackendCommand, last_error)
+            .with_exit(last_exit),
+    )
 }
 
 fn execution_backend(contract: &ArtifactExecutionContract) -> &'static str {
@@ -1,3 +1,4 @@
+use gaia_process::ProcessEnvironment;
 use gaia_spec::{
     ArtifactDefinition, ArtifactExecutionSpec, ArtifactProviderKind, ArtifactRef, ArtifactSpec,
     ArtifactVariantSpec, BuildModeSpec, DockerExecutionSpec, OutputRetentionPolicySpec,
@@ -27,6 +28,7 @@ pub struct ArtifactExecutionContract {
     pub retry_backoff_strategy: RetryBackoffStrategySpec,
     pub timeout_seconds: u64,
     pub output_retention: OutputRetentionPolicySpec,
+    pub environment: ProcessEnvironment,
     pub build_mode: Option<BuildModeSpec>,
     pub dependencies: Vec<ArtifactDependencyContract>,
     pub output: ArtifactOutputContract,
@@ -40,6 +42,7 @@ impl ArtifactExecutionContract {
             retry_backoff_strategy: RetryBackoffStrategySpec::Fixed,
             timeout_seconds: 300,
             local_jobs: 0,
+            clean_env: None,
         }
     }
 
@@ -77,6 +80,7 @@ impl ArtifactExecutionContract {
             retry_backoff_strategy: command_policy.retry_backoff_strategy,
             timeout_seconds: command_policy.timeout_seconds,
             output_retention,
+            environment: ProcessEnvironment::default(),
             build_mode: artifact.build_mode.clone(),
             dependencies: artifact
                 .dependencies
@@ -120,6 +124,12 @@ impl ArtifactExecutionContract {
         self.build_branch = spec.metadata.branch.clone();
         self.build_target = spec.metadata.target.clone();
         self.build_profile = spec.metadata.profile.clone();
+        let command_policy = spec.policy.providers.artifact_command_policy(self.provider);
+        self.environment = ProcessEnvironment {
+            clean: spec.policy.execution.clean_env_for(command_policy),
+            allowlist: spec.policy.execution.env_allowlist.clone(),
+            ..ProcessEnvironment::default()
+        };
     }
 
     fn validate_release_invariants(&self) -> Result<(), ArtifactProviderError> {
@@ -141,6 +151,39 @@ impl ArtifactExecutionContract {
     }
 }
 
+// Docker-backed artifacts build inside the container, so the host only needs docker for them.
+pub fn artifact_host_tools(
+    spec: &ResolvedBuildSpec,
+    kind: ArtifactProviderKind,
+    tools: &[&'static str],
+) -> Vec<&'static str> {
+    let mut needed = Vec::new();
+    for artifact in spec
+        .artifacts
+        .iter()
+        .filter(|artifact| artifact.provider_kind() == kind)
+    {
+        let contract = ArtifactExecutionContract::from_spec(
+            artifact,
+            None,
+            false,
+            ArtifactExecutionContract::default_command_policy(),
+            OutputRetentionPolicySpec::default(),
+        )
+        .with_build_context(spec);
+        let required = match contract.execution_backend {
+            ArtifactExecutionBackend::Docker(_) => &["docker"][..],
+            ArtifactExecutionBackend::Host => tools,
+        };
+        for tool in required {
+            if !needed.contains(tool) {
+                needed.push(*tool);
+            }
+        }
+    }
+    needed
+}
+
 fn execution_backend_for_spec(
     spec: &ResolvedBuildSpec,
     current: &ArtifactExecutionBackend,
@@ -7,17 +7,19 @@ mod tests;
 
 pub use command::{
     command_for_execution, command_output_with_timeout, command_output_with_timeout_and_sink,
-    run_command_with_retries,
+    planned_command, run_command_with_retries,
 };
 pub use contract::{
     ArtifactDependencyContract, ArtifactDockerExecution, ArtifactExecutionBackend,
-    ArtifactExecutionContract, ArtifactOutputContract, ArtifactOutputKind,
+    ArtifactExecutionContract, ArtifactOutputContract, ArtifactOutputKind, artifact_host_tools,
 };
 pub use digest::{
     ArtifactBackendState, command_version_line, dir_digest, file_sha256_or_placeholder, path_bytes,
     produced_filename, render_artifact_backend_state,
 };
-pub use gaia_process::{ProcessCancelCheck, ProcessLogLine, ProcessLogSink, sleep_with_cancel};
+pub use gaia_process::{
+    PlannedCommand, ProcessCancelCheck, ProcessLogLine, ProcessLogSink, sleep_with_cancel,
+};
 pub use outputs::{
     artifact_marker_contract, artifact_output_path, artifact_package_root, artifact_state_path,
     copy_artifact_file_to_output, ensure_artifact_output_parent, finalize_temp_output,
@@ -30,10 +32,22 @@ use std::time::Duration;
 
 pub trait ArtifactProvider: Send + Sync {
     fn id(&self) -> &'static str;
+    // One-line summary for `gaia providers`; defaults to the id.
+    fn describe(&self) -> &'static str {
+        self.id()
+    }
     fn kind(&self) -> ArtifactProviderKind;
     fn supports(&self, _spec: &ResolvedBuildSpec) -> bool {
         true
     }
+    // Host programs `gaia doctor` checks for when the build uses this provider.
+    fn host_tools(&self, _spec: &ResolvedBuildSpec) -> Vec<&'static str> {
+        Vec::new()
+    }
+    // Host programs some builds need; `gaia doctor` only warns when one is missing.
+    fn optional_host_tools(&self, _spec: &ResolvedBuildSpec) -> Vec<&'static str> {
+        Vec::new()
+    }
     fn plan_artifact(&self, artifact: &ArtifactSpec) -> ArtifactPlan {
         ArtifactPlan {
             operations: vec![ArtifactProviderOperation::Build],
@@ -65,6 +79,14 @@ pub trait ArtifactProvider: Send + Sync {
             ),
         ))
     }
+    // Commands `execute_artifact` would start, resolved but not run, for `gaia run --dry-run`.
+    fn planned_artifact_commands(
+        &self,
+        _artifact: &ArtifactSpec,
+        _contract: &ArtifactExecutionContract,
+    ) -> Result<Vec<PlannedCommand>, ArtifactProviderError> {
+        Ok(Vec::new())
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -100,6 +122,8 @@ pub enum ArtifactProviderErrorKind {
 pub struct ArtifactProviderError {
     pub kind: ArtifactProviderErrorKind,
     pub message: String,
+    // How a failed command ended, so a signal such as an OOM kill is not lost in the message.
+    pub exit: Option<gaia_process::ProcessExit>,
 }
 
 impl ArtifactProviderError {
@@ -107,9 +131,14 @@ impl ArtifactProviderError {
         Self {
             kind,
             message: message.into(),
+            exit: None,
         }
     }
 
+    pub fn with_exit(self, exit: Option<gaia_process::ProcessExit>) -> Self {
+        Self { exit, ..self }
+    }
+
     pub fn backend_command(message: impl Into<String>) -> Self {
         Self::new(ArtifactProviderErrorKind::BackendCommand, message)
     }
@@ -158,6 +187,11 @@ impl ArtifactProviderCatalog {
         self.providers.push(provider);
     }
 
+    // Registration order, which is also the order `find_for_kind` searches.
+    pub fn providers(&self) -> impl Iterator<Item = &dyn ArtifactProvider> {
+        self.providers.iter().map(Box::as_ref)
+    }
+
     pub fn find_for_kind(&self, kind: ArtifactProviderKind) -> Option<&dyn ArtifactProvider> {
         self.providers
             .iter()
@@ -39,6 +39,8 @@ fn default_artifact_execution_fails_instead_of_materializing_placeholder() {
             package: "dummy".into(),
             target_name: None,
             variant: ArtifactVariantSpec::File,
+            linker: None,
+            sysroot: None,
         }),
         None,
         ArtifactOutputSpec {
@@ -83,6 +85,7 @@ fn materialize_artifact_output_cleans_temp_file_when_rename_fails() {
         retry_backoff_strategy: RetryBackoffStrategySpec::Fixed,
         timeout_seconds: 300,
         output_retention: gaia_spec::OutputRetentionPolicySpec::default(),
+        environment: gaia_process::ProcessEnvironment::default(),
         build_mode: None,
         dependencies: Vec::new(),
         output: ArtifactOutputContract {
@@ -156,6 +159,7 @@ fn materialize_artifact_marker_and_state_writes_both_files() {
         retry_backoff_strategy: RetryBackoffStrategySpec::Fixed,
         timeout_seconds: 300,
         output_retention: gaia_spec::OutputRetentionPolicySpec::default(),
+        environment: gaia_process::ProcessEnvironment::default(),
         build_mode: None,
         dependencies: Vec::new(),
         output: ArtifactOutputContract {
@@ -227,6 +231,7 @@ fn command_for_execution_wraps_docker_backend() {
         retry_backoff_strategy: RetryBackoffStrategySpec::Fixed,
         timeout_seconds: 300,
         output_retention: gaia_spec::OutputRetentionPolicySpec::default(),
+        environment: gaia_process::ProcessEnvironment::default(),
         build_mode: None,
         dependencies: Vec::new(),
         output: ArtifactOutputContract {
@@ -304,6 +309,7 @@ fn run_command_with_retries_reuses_contract_retry_policy() {
         retry_backoff_strategy: RetryBackoffStrategySpec::Fixed,
         timeout_seconds: 5,
         output_retention: gaia_spec::OutputRetentionPolicySpec::default(),
+        environment: gaia_process::ProcessEnvironment::default(),
         build_mode: None,
         dependencies: Vec::new(),
         output: ArtifactOutputContract {
@@ -340,6 +346,8 @@ fn artifact_execution_backend_prefers_explicit_setting_over_global_default() {
             package: "demo".to_string(),
             target_name: None,
             variant: ArtifactVariantSpec::File,
+            linker: None,
+            sysroot: None,
         }),
         None,
         ArtifactOutputSpec {
@@ -389,6 +397,8 @@ fn artifact_contract_rejects_empty_inherited_docker_image() {
             package: "demo".to_string(),
             target_name: None,
             variant: ArtifactVariantSpec::File,
+            linker: None,
+            sysroot: None,
         }),
         None,
         ArtifactOutputSpec {
@@ -271,8 +271,11 @@ pub(crate) fn archive_directory(
             ))
         })?;
     }
+    // File capabilities live in the `security.capability` xattr, which tar drops by default.
     let mut command = Command::new("tar");
     command
+        .arg("--xattrs")
+        .arg("--xattrs-include=security.capability")
         .arg("-cf")
         .arg(archive_path)
         .arg("-C")
@@ -60,32 +60,59 @@ pub(crate) struct BuildrootRunRequest<'a> {
 pub(crate) fn run_buildroot(
     request: BuildrootRunRequest<'_>,
 ) -> Result<Vec<String>, ImageProviderError> {
-    let BuildrootRunRequest {
+    let mut messages = prepare_buildroot_output(&request)?;
+    messages.extend(configure_buildroot(&request)?);
+    messages.extend(make_buildroot(request)?);
+    Ok(messages)
+}
+
+pub(crate) fn prepare_buildroot_output(
+    request: &BuildrootRunRequest<'_>,
+) -> Result<Vec<String>, ImageProviderError> {
+    fs::create_dir_all(request.output_dir).map_err(|error| {
+        ImageProviderError::backend_command(format!(
+            "failed to create buildroot output dir '{}': {error}",
+            request.output_dir.display()
+        ))
+    })?;
+    let mut messages = Vec::new();
+    if let Some(ccache_dir) = buildroot_ccache_dir(request.spec, request.image) {
+        fs::create_dir_all(&ccache_dir).map_err(|error| {
+            ImageProviderError::backend_command(format!(
+                "failed to create buildroot ccache dir '{}': {error}",
+                ccache_dir.display()
+            ))
+        })?;
+        messages.push(format!(
+            "buildroot ccache enabled at '{}'",
+            ccache_dir.display()
+        ));
+    }
+    Ok(messages)
+}
+
+fn configure_buildroot(
+    request: &BuildrootRunRequest<'_>,
+) -> Result<Vec<String>, ImageProviderError> {
+    let &BuildrootRunRequest {
         spec,
         image,
         buildroot_dir,
         output_dir,
-        command: command_context,
+        command: ref command_context,
     } = request;
-    fs::create_dir_all(output_dir).map_err(|error| {
-        ImageProviderError::backend_command(format!(
-            "failed to create buildroot output dir '{}': {error}",
-            output_dir.display()
-        ))
-    })?;
     let mut messages = Vec::new();
 
-    let (defconfig, defconfig_path, config_fragments, config_overrides, external_tree) =
-        match &image.definition {
-            ImageDefinition::Buildroot(buildroot) => (
-                buildroot.defconfig.as_deref(),
-                buildroot.defconfig_path.as_deref(),
-                buildroot.config_fragments.as_slice(),
-                buildroot.config_overrides.as_slice(),
-                buildroot.external_tree.as_deref(),
-            ),
-            _ => (None, None, &[][..], &[][..], None),
-        };
+    let (defconfig, defconfig_path, config_fragments, external_tree) = match &image.definition {
+        ImageDefinition::Buildroot(buildroot) => (
+            buildroot.defconfig.as_deref(),
+            buildroot.defconfig_path.as_deref(),
+            buildroot.config_fragments.as_slice(),
+            buildroot.external_tree.as_deref(),
+        ),
+        _ => (None, None, &[][..], None),
+    };
+    let config_overrides = &buildroot_config_overrides(spec, image)[..];
 
     if let Some(defconfig_path) = defconfig_path {
         let resolved_defconfig_path = resolve_workspace_path(
@@ -96,18 +123,11 @@ pub(crate) fn run_buildroot(
             defconfig_path,
         )?;
         materialize_defconfig_support_files(&resolved_defconfig_path, output_dir)?;
-        let mut command = Command::new("make");
-        command
-            .arg(format!("O={}", output_dir.display()))
-            .arg("defconfig")
-            .arg(format!(
-                "BR2_DEFCONFIG={}",
-                resolved_defconfig_path.display()
-            ))
-            .current_dir(buildroot_dir);
-        if let Some(external_tree) = external_tree {
-            command.env("BR2_EXTERNAL", external_tree);
-        }
+        let mut command = buildroot_make_command(buildroot_dir, output_dir, external_tree);
+        command.arg("defconfig").arg(format!(
+            "BR2_DEFCONFIG={}",
+            resolved_defconfig_path.display()
+        ));
         messages.extend(run_command(
             command,
             "buildroot defconfig",
@@ -139,14 +159,8 @@ pub(crate) fn run_buildroot(
             )?);
         }
     } else if let Some(defconfig) = defconfig {
-        let mut command = Command::new("make");
-        command
-            .arg(format!("O={}", output_dir.display()))
-            .arg(defconfig)
-            .current_dir(buildroot_dir);
-        if let Some(external_tree) = external_tree {
-            command.env("BR2_EXTERNAL", external_tree);
-        }
+        let mut command = buildroot_make_command(buildroot_dir, output_dir, external_tree);
+        command.arg(defconfig);
         messages.extend(run_command(
             command,
             "buildroot defconfig",
@@ -180,27 +194,127 @@ pub(crate) fn run_buildroot(
     } else if !config_fragments.is_empty() || !config_overrides.is_empty() {
         return Err(ImageProviderError::new(
             ImageProviderErrorKind::PolicyBlocked,
-            "buildroot config_fragments/config_overrides require defconfig or defconfig_path",
+            "buildroot config_fragments/config_overrides/ccache require defconfig or defconfig_path",
         ));
     }
+    Ok(messages)
+}
 
-    let mut command = Command::new("make");
-    command
-        .arg(format!("O={}", output_dir.display()))
-        .current_dir(buildroot_dir);
+pub(crate) fn make_buildroot(
+    request: BuildrootRunRequest<'_>,
+) -> Result<Vec<String>, ImageProviderError> {
+    let BuildrootRunRequest {
+        spec,
+        image,
+        buildroot_dir,
+        output_dir,
+        command: command_context,
+    } = request;
+    let external_tree = match &image.definition {
+        ImageDefinition::Buildroot(buildroot) => buildroot.external_tree.as_deref(),
+        _ => None,
+    };
+    let mut command = buildroot_make_command(buildroot_dir, output_dir, external_tree);
     append_make_jobs(&mut command, command_context.policy.local_jobs);
-    if let Some(external_tree) = external_tree {
-        command.env("BR2_EXTERNAL", external_tree);
+    if let Some(ccache_dir) = buildroot_ccache_dir(spec, image) {
+        command.env("CCACHE_DIR", ccache_dir);
     }
-    messages.extend(run_command(
+    run_command(
         command,
         "buildroot make",
         command_context.execution,
         command_context.policy,
         command_context.log_sink,
         command_context.cancel_check,
-    )?);
-    Ok(messages)
+    )
+}
+
+pub(crate) fn buildroot_ccache_dir(spec: &ResolvedBuildSpec, image: &ImageSpec) -> Option<PathBuf> {
+    match &image.definition {
+        ImageDefinition::Buildroot(buildroot) => buildroot.ccache_dir(&spec.workspace),
+        _ => None,
+    }
+}
+
+// `ccache` becomes ordinary symbol overrides so it shares the olddefconfig pass with
+// `config_overrides`, and replaces any BR2_CCACHE entries listed there.
+pub(crate) fn buildroot_config_overrides(
+    spec: &ResolvedBuildSpec,
+    image: &ImageSpec,
+) -> Vec<(String, String)> {
+    let ImageDefinition::Buildroot(buildroot) = &image.definition else {
+        return Vec::new();
+    };
+    let mut overrides = buildroot.config_overrides.clone();
+    if let Some(ccache_dir) = buildroot.ccache_dir(&spec.workspace) {
+        overrides.retain(|(key, _)| key != "BR2_CCACHE" && key != "BR2_CCACHE_DIR");
+        overrides.push(("BR2_CCACHE".into(), "y".into()));
+        overrides.push((
+            "BR2_CCACHE_DIR".into(),
+            format!("\"{}\"", ccache_dir.display()),
+        ));
+    }
+    overrides
+}
+
+pub(crate) fn buildroot_make_command(
+    buildroot_dir: &Path,
+    output_dir: &Path,
+    external_tree: Option<&str>,
+) -> Command {
+    let mut command = Command::new("make");
+    command
+        .arg(format!("O={}", output_dir.display()))
+        .current_dir(buildroot_dir);
+    if let Some(external_tree) = external_tree {
+        command.env("BR2_EXTERNAL", external_tree);
+    }
+    command
+}
+
+// Mirrors the make invocations `run_buildroot` issues against a fresh output dir, without
+// materializing defconfig support files or merging config fragments.
+pub(crate) fn planned_buildroot_commands(
+    spec: &ResolvedBuildSpec,
+    image: &ImageSpec,
+    buildroot_dir: &Path,
+    output_dir: &Path,
+    local_jobs: u32,
+) -> Result<Vec<(&'static str, Command)>, ImageProviderError> {
+    let ImageDefinition::Buildroot(buildroot) = &image.definition else {
+        return Ok(Vec::new());
+    };
+    let external_tree = buildroot.external_tree.as_deref();
+    let mut commands = Vec::new();
+    if let Some(defconfig_path) = &buildroot.defconfig_path {
+        let resolved_defconfig_path = resolve_workspace_path(spec, defconfig_path)?;
+        let mut command = buildroot_make_command(buildroot_dir, output_dir, external_tree);
+        command.arg("defconfig").arg(format!(
+            "BR2_DEFCONFIG={}",
+            resolved_defconfig_path.display()
+        ));
+        commands.push(("buildroot defconfig", command));
+    } else if let Some(defconfig) = &buildroot.defconfig {
+        let mut command = buildroot_make_command(buildroot_dir, output_dir, external_tree);
+        command.arg(defconfig);
+        commands.push(("buildroot defconfig", command));
+    }
+    if !commands.is_empty() {
+        let olddefconfig_runs = usize::from(!buildroot.config_fragments.is_empty())
+            + usize::from(!buildroot_config_overrides(spec, image).is_empty());
+        for _ in 0..olddefconfig_runs {
+            let mut command = buildroot_make_command(buildroot_dir, output_dir, external_tree);
+            command.arg("olddefconfig");
+            commands.push(("buildroot olddefconfig", command));
+        }
+    }
+    let mut command = buildroot_make_command(buildroot_dir, output_dir, external_tree);
+    append_make_jobs(&mut command, local_jobs);
+    if let Some(ccache_dir) = buildroot_ccache_dir(spec, image) {
+        command.env("CCACHE_DIR", ccache_dir);
+    }
+    commands.push(("buildroot make", command));
+    Ok(commands)
 }
 
 pub(crate) struct BuildrootConfigOverrideRequest<'a> {
@@ -255,14 +369,8 @@ pub(crate) fn apply_buildroot_config_overrides(
         )
     })?;
 
-    let mut command = Command::new("make");
-    command
-        .arg(format!("O={}", output_dir.display()))
-        .arg("olddefconfig")
-        .current_dir(buildroot_dir);
-    if let Some(external_tree) = external_tree {
-        command.env("BR2_EXTERNAL", external_tree);
-    }
+    let mut command = buildroot_make_command(buildroot_dir, output_dir, external_tree);
+    command.arg("olddefconfig");
     run_command(
         command,
         "buildroot olddefconfig",
@@ -369,7 +477,8 @@ pub(crate) fn apply_buildroot_config_fragments(
         ));
     }
 
-    let mut merged = fs::read_to_string(&config_path).map_err(|error| {
+    let symbols = read_buildroot_config_fragments(spec, fragments)?;
+    let base = fs::read_to_string(&config_path).map_err(|error| {
         ImageProviderError::new(
             ImageProviderErrorKind::RuntimeState,
             format!(
@@ -378,26 +487,7 @@ pub(crate) fn apply_buildroot_config_fragments(
             ),
         )
     })?;
-    if !merged.ends_with('\n') {
-        merged.push('\n');
-    }
-    for fragment in fragments {
-        let resolved = resolve_workspace_path(spec, fragment)?;
-        let fragment_contents = fs::read_to_string(&resolved).map_err(|error| {
-            ImageProviderError::new(
-                ImageProviderErrorKind::RuntimeState,
-                format!(
-                    "failed to read buildroot config fragment '{}': {error}",
-                    resolved.display()
-                ),
-            )
-        })?;
-        merged.push('\n');
-        merged.push_str(&fragment_contents);
-        if !fragment_contents.ends_with('\n') {
-            merged.push('\n');
-        }
-    }
+    let merged = merge_buildroot_config_assignments(&base, &symbols);
     fs::write(&config_path, merged).map_err(|error| {
         ImageProviderError::new(
             ImageProviderErrorKind::RuntimeState,
@@ -408,14 +498,8 @@ pub(crate) fn apply_buildroot_config_fragments(
         )
     })?;
 
-    let mut command = Command::new("make");
-    command
-        .arg(format!("O={}", output_dir.display()))
-        .arg("olddefconfig")
-        .current_dir(buildroot_dir);
-    if let Some(external_tree) = external_tree {
-        command.env("BR2_EXTERNAL", external_tree);
-    }
+    let mut command = buildroot_make_command(buildroot_dir, output_dir, external_tree);
+    command.arg("olddefconfig");
     run_command(
         command,
         "buildroot olddefconfig",
@@ -426,6 +510,74 @@ pub(crate) fn apply_buildroot_config_fragments(
     )
 }
 
+// Reads fragments in order into one symbol set. A symbol keeps the position of its first
+// assignment and the value of its last, so later fragments override earlier ones.
+pub(crate) fn read_buildroot_config_fragments(
+    spec: &ResolvedBuildSpec,
+    fragments: &[String],
+) -> Result<Vec<(String, String)>, ImageProviderError> {
+    let mut symbols = Vec::<(String, String)>::new();
+    for fragment in fragments {
+        let resolved = resolve_workspace_path(spec, fragment)?;
+        let contents = fs::read_to_string(&resolved).map_err(|error| {
+            ImageProviderError::new(
+                ImageProviderErrorKind::RuntimeState,
+                format!(
+                    "failed to read buildroot config fragment '{}': {error}",
+                    resolved.display()
+                ),
+            )
+        })?;
+        for (key, value) in parse_buildroot_config_fragment(&resolved, &contents)? {
+            match symbols.iter_mut().find(|(existing, _)| *existing == key) {
+                Some((_, existing)) => *existing = value,
+                None => symbols.push((key, value)),
+            }
+        }
+    }
+    Ok(symbols)
+}
+
+// Accepts `SYMBOL=value`, `# SYMBOL is not set` (read as `SYMBOL=n`), comments, and blank lines.
+pub(crate) fn parse_buildroot_config_fragment(
+    path: &Path,
+    contents: &str,
+) -> Result<Vec<(String, String)>, ImageProviderError> {
+    let is_symbol = |key: &str| {
+        !key.is_empty()
+            && key
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+    };
+    let mut symbols = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if let Some(key) = line
+            .strip_prefix("# ")
+            .and_then(|line| line.strip_suffix(" is not set"))
+            .filter(|key| is_symbol(key))
+        {
+            symbols.push((key.to_string(), "n".to_string()));
+        } else if line.is_empty() || line.starts_with('#') {
+            continue;
+        } else if let Some((key, value)) = line.split_once('=')
+            && is_symbol(key)
+        {
+            symbols.push((key.to_string(), value.to_string()));
+        } else {
+            return Err(ImageProviderError::new(
+                ImageProviderErrorKind::RuntimeState,
+                format!(
+                    "buildroot config fragment '{}' line {} is not a SYMBOL=value assignment: '{line}'",
+                    path.display(),
+                    index + 1
+                ),
+            ));
+        }
+    }
+    Ok(symbols)
+}
+
 pub(crate) fn materialize_defconfig_support_files(
     defconfig_path: &Path,
     output_dir: &Path,
@@ -76,6 +76,7 @@ pub(crate) fn materialize_fallback_rootfs(
     spec: &ResolvedBuildSpec,
     image: &ImageSpec,
     rootfs_dir: &Path,
+    messages: &mut Vec<String>,
 ) -> Result<Vec<String>, ImageProviderError> {
     let execution = execution_context(spec);
     if rootfs_dir.exists() {
@@ -99,7 +100,7 @@ pub(crate) fn materialize_fallback_rootfs(
         )
     })?;
 
-    apply_image_feed_to_rootfs(spec, image, rootfs_dir)?;
+    messages.extend(apply_image_feed_to_rootfs(spec, image, rootfs_dir)?);
 
     let ImageDefinition::Buildroot(buildroot) = &image.definition else {
         return Ok(Vec::new());
@@ -138,7 +139,8 @@ pub(crate) fn apply_image_feed_to_rootfs(
     spec: &ResolvedBuildSpec,
     image: &ImageSpec,
     rootfs_dir: &Path,
-) -> Result<(), ImageProviderError> {
+) -> Result<Vec<String>, ImageProviderError> {
+    let mut messages = Vec::new();
     for install_id in &image.feed.install_entries {
         let install = spec
             .install
@@ -219,6 +221,7 @@ pub(crate) fn apply_image_feed_to_rootfs(
         let src = resolve_workspace_path(spec, &stage_file.src)?;
         let dest = rootfs_path(rootfs_dir, &stage_file.dest);
         copy_path(&src, &dest)?;
+        messages.extend(apply_stage_file_attributes(stage_file, &dest)?);
     }
 
     for env_set_id in &image.feed.stage_env_sets {
@@ -289,7 +292,51 @@ pub(crate) fn apply_image_feed_to_rootfs(
             .join(&service.name);
         copy_path(&src, &dest)?;
     }
-    Ok(())
+
+    for overlay_id in &image.feed.stage_overlays {
+        let overlay = spec
+            .stage
+            .overlays
+            .iter()
+            .find(|overlay| overlay.id == *overlay_id)
+            .ok_or_else(|| {
+                ImageProviderError::new(
+                    ImageProviderErrorKind::RuntimeState,
+                    format!(
+                        "image feed references unknown stage overlay '{}'",
+                        overlay_id.as_str()
+                    ),
+                )
+            })?;
+        let src = resolve_workspace_path(spec, &overlay.src)?;
+        apply_stage_overlay(
+            &src,
+            &rootfs_path(rootfs_dir, &overlay.dest),
+            spec.stage.fast_copy,
+        )?;
+    }
+
+    for symlink_id in &image.feed.stage_symlinks {
+        let symlink = spec
+            .stage
+            .symlinks
+            .iter()
+            .find(|symlink| symlink.id == *symlink_id)
+            .ok_or_else(|| {
+                ImageProviderError::new(
+                    ImageProviderErrorKind::RuntimeState,
+                    format!(
+                        "image feed references unknown stage symlink '{}'",
+                        symlink_id.as_str()
+                    ),
+                )
+            })?;
+        apply_stage_symlink(
+            symlink.target_path(),
+            &rootfs_path(rootfs_dir, &symlink.link),
+        )?;
+    }
+    Ok(messages)
 }
 
 pub(crate) fn verify_install_artifact_target(
@@ -444,6 +491,8 @@ pub(crate) fn image_feed_has_content(image: &ImageSpec) -> bool {
         || !image.feed.stage_files.is_empty()
         || !image.feed.stage_env_sets.is_empty()
         || !image.feed.stage_services.is_empty()
+        || !image.feed.stage_overlays.is_empty()
+        || !image.feed.stage_symlinks.is_empty()
 }
 
 pub(crate) fn image_feed_signature_path(output_dir: &Path) -> PathBuf {
@@ -538,9 +587,12 @@ pub(crate) fn build_image_feed_signature(
             })?;
         let src = resolve_workspace_path(spec, &stage_file.src)?;
         signature.push_str(&format!(
-            "{}|{}|{}\n",
+            "{}|{}|{:?}|{:?}|{:?}|{}\n",
             stage_file.id.as_str(),
             stage_file.dest,
+            stage_file.uid,
+            stage_file.gid,
+            stage_file.caps,
             dir_digest(&src)
         ));
     }
@@ -592,5 +644,54 @@ pub(crate) fn build_image_feed_signature(
         ));
     }
 
+    signature.push_str("stage-overlays:\n");
+    for overlay_id in &image.feed.stage_overlays {
+        let overlay = spec
+            .stage
+            .overlays
+            .iter()
+            .find(|overlay| overlay.id == *overlay_id)
+            .ok_or_else(|| {
+                ImageProviderError::new(
+                    ImageProviderErrorKind::RuntimeState,
+                    format!(
+                        "image feed references unknown stage overlay '{}'",
+                        overlay_id.as_str()
+                    ),
+                )
+            })?;
+        let src = resolve_workspace_path(spec, &overlay.src)?;
+        signature.push_str(&format!(
+            "{}|{}|{}\n",
+            overlay.id.as_str(),
+            overlay.dest,
+            dir_digest(&src)
+        ));
+    }
+
+    signature.push_str("stage-symlinks:\n");
+    for symlink_id in &image.feed.stage_symlinks {
+        let symlink = spec
+            .stage
+            .symlinks
+            .iter()
+            .find(|symlink| symlink.id == *symlink_id)
+            .ok_or_else(|| {
+                ImageProviderError::new(
+                    ImageProviderErrorKind::RuntimeState,
+                    format!(
+                        "image feed references unknown stage symlink '{}'",
+                        symlink_id.as_str()
+                    ),
+                )
+            })?;
+        signature.push_str(&format!(
+            "{}|{}|{}\n",
+            symlink.id.as_str(),
+            symlink.link,
+            symlink.target
+        ));
+    }
+
     Ok(signature)
 }
@@ -0,0 +1,111 @@
+use super::*;
+
+pub(crate) fn buildroot_incremental(image: &ImageSpec) -> bool {
+    match &image.definition {
+        ImageDefinition::Buildroot(buildroot) => buildroot.incremental,
+        _ => false,
+    }
+}
+
+// An incremental image keeps its output dir between runs. While the configure inputs match the
+// signature written by the last configure, only `make` runs and Buildroot's stamp files decide
+// what to rebuild. Buildroot does not rebuild packages for config changes, so changed inputs
+// start over from an empty output dir.
+pub(crate) fn run_buildroot_incremental(
+    request: BuildrootRunRequest<'_>,
+) -> Result<Vec<String>, ImageProviderError> {
+    let output_dir = request.output_dir;
+    let signature = build_buildroot_config_signature(request.spec, request.image)?;
+    if output_dir.join(".config").is_file()
+        && buildroot_config_signature_is_current(output_dir, &signature)
+    {
+        let mut messages = vec![format!(
+            "incremental buildroot build reusing configured output at '{}'",
+            output_dir.display()
+        )];
+        messages.extend(prepare_buildroot_output(&request)?);
+        messages.extend(make_buildroot(request)?);
+        return Ok(messages);
+    }
+
+    let mut messages = Vec::new();
+    if output_dir.exists() {
+        fs::remove_dir_all(output_dir).map_err(|error| {
+            ImageProviderError::new(
+                ImageProviderErrorKind::RuntimeState,
+                format!(
+                    "failed to clear incremental buildroot output '{}': {error}",
+                    output_dir.display()
+                ),
+            )
+        })?;
+        messages.push(format!(
+            "buildroot config changed; cleared incremental output at '{}'",
+            output_dir.display()
+        ));
+    }
+    messages.extend(run_buildroot(request)?);
+    write_buildroot_config_signature(output_dir, &signature)?;
+    Ok(messages)
+}
+
+pub(crate) fn buildroot_config_signature_path(output_dir: &Path) -> PathBuf {
+    output_dir.join(".gaia-buildroot-config-state.txt")
+}
+
+pub(crate) fn buildroot_config_signature_is_current(output_dir: &Path, signature: &str) -> bool {
+    fs::read_to_string(buildroot_config_signature_path(output_dir))
+        .is_ok_and(|current| current == signature)
+}
+
+pub(crate) fn write_buildroot_config_signature(
+    output_dir: &Path,
+    signature: &str,
+) -> Result<(), ImageProviderError> {
+    fs::write(buildroot_config_signature_path(output_dir), signature).map_err(|error| {
+        ImageProviderError::new(
+            ImageProviderErrorKind::RuntimeState,
+            format!(
+                "failed to write buildroot config state in '{}': {error}",
+                output_dir.display()
+            ),
+        )
+    })
+}
+
+// Covers the inputs of the defconfig, fragment and override passes.
+pub(crate) fn build_buildroot_config_signature(
+    spec: &ResolvedBuildSpec,
+    image: &ImageSpec,
+) -> Result<String, ImageProviderError> {
+    let mut signature = String::from("gaia-buildroot-config-v1\n");
+    let ImageDefinition::Buildroot(buildroot) = &image.definition else {
+        return Ok(signature);
+    };
+    if let Some(defconfig) = &buildroot.defconfig {
+        signature.push_str(&format!("defconfig={defconfig}\n"));
+    }
+    if let Some(defconfig_path) = &buildroot.defconfig_path {
+        let resolved = resolve_workspace_path(spec, defconfig_path)?;
+        signature.push_str(&format!(
+            "defconfig_path={}|{}\n",
+            resolved.display(),
+            dir_digest(&resolved)
+        ));
+    }
+    for fragment in &buildroot.config_fragments {
+        let resolved = resolve_workspace_path(spec, fragment)?;
+        signature.push_str(&format!(
+            "fragment={}|{}\n",
+            resolved.display(),
+            dir_digest(&resolved)
+        ));
+    }
+    for (key, value) in buildroot_config_overrides(spec, image) {
+        signature.push_str(&format!("override={key}={value}\n"));
+    }
+    if let Some(external_tree) = &buildroot.external_tree {
+        signature.push_str(&format!("external_tree={external_tree}\n"));
+    }
+    Ok(signature)
+}
@@ -1,13 +1,15 @@
 use gaia_image_providers::{
-    ImageExecutionPolicy, ImageExecutionResult, ImageOutputContract, ImagePlan, ImageProvider,
-    ImageProviderError, ImageProviderErrorKind, ImageProviderOperation,
-    ImageProviderValidationIssue, ProcessCancelCheck, ProcessLogSink, ProcessOutputRetention,
-    build_image_contract_state_details, build_state_details, dir_digest,
-    file_sha256_or_placeholder, materialize_image_output,
+    ImageExecutionPolicy, ImageExecutionResult, ImageOutputContract, ImagePackage, ImagePlan,
+    ImageProvider, ImageProviderError, ImageProviderErrorKind, ImageProviderOperation,
+    ImageProviderValidationIssue, PlannedCommand, ProcessCancelCheck, ProcessLogLine,
+    ProcessLogSink, ProcessOutputRetention, ProcessProgressSink, apply_stage_file_attributes,
+    apply_stage_overlay, apply_stage_symlink, build_image_contract_state_details,
+    build_state_details, dir_digest, file_sha256_or_placeholder, materialize_image_output,
 };
 use gaia_process::{
-    DockerRunSpec, ProcessRetryBackoffStrategy, ProcessRunErrorKind, docker_run_command,
-    label_process_log_sink, retry_backoff_duration as process_retry_backoff_duration,
+    DockerRunSpec, ProcessEnvironment, ProcessRetryBackoffStrategy, ProcessRunErrorKind,
+    docker_run_command, label_process_log_sink,
+    retry_backoff_duration as process_retry_backoff_duration,
     run_command_with_timeout_and_retention, sleep_with_cancel,
 };
 use gaia_spec::{
@@ -29,6 +31,7 @@ pub struct BuildrootImageProvider;
 struct ImageExecutionContext {
     workspace_root: PathBuf,
     docker_image: Option<String>,
+    environment: ProcessEnvironment,
 }
 
 impl ImageProvider for BuildrootImageProvider {
@@ -36,6 +39,10 @@ impl ImageProvider for BuildrootImageProvider {
         "image.buildroot"
     }
 
+    fn describe(&self) -> &'static str {
+        "Builds the rootfs and images with Buildroot."
+    }
+
     fn kind(&self) -> gaia_spec::ImageProviderKind {
         gaia_spec::ImageProviderKind::Buildroot
     }
@@ -44,6 +51,25 @@ impl ImageProvider for BuildrootImageProvider {
         true
     }
 
+    fn host_tools(&self, spec: &ResolvedBuildSpec) -> Vec<&'static str> {
+        if execution_context(spec).docker_image.is_some() {
+            return vec!["docker"];
+        }
+        // Buildroot's own mandatory host packages.
+        vec![
+            "make", "gcc", "g++", "patch", "perl", "rsync", "cpio", "unzip", "bc", "file", "wget",
+            "tar",
+        ]
+    }
+
+    // Buildroot's optional host packages, which many package recipes download or patch with.
+    fn optional_host_tools(&self, spec: &ResolvedBuildSpec) -> Vec<&'static str> {
+        if execution_context(spec).docker_image.is_some() {
+            return Vec::new();
+        }
+        vec!["git", "python3"]
+    }
+
     fn plan_image(&self, image: &ImageSpec) -> ImagePlan {
         let output = ImageOutputContract {
             collect_dir: image.output.collect_dir.clone(),
@@ -128,31 +154,36 @@ impl ImageProvider for BuildrootImageProvider {
         let mut messages = Vec::new();
         let mut reuse_details = Vec::new();
         let mut state_details = vec![("defconfig".to_string(), defconfig.clone())];
+        let rootfs_dir;
+        let mut packages = Vec::new();
         if let Some(path) = &defconfig_path {
             state_details.push(("defconfig_path".to_string(), path.clone()));
         }
         if let Some(buildroot_dir) = resolve_buildroot_dir(spec, image) {
             let output_dir = collect_dir.join("buildroot-output");
             let target_dir = output_dir.join("target");
-            if buildroot_expected_images_present(image, &output_dir) {
+            let run_request = BuildrootRunRequest {
+                spec,
+                image,
+                buildroot_dir: &buildroot_dir,
+                output_dir: &output_dir,
+                command: ImageCommandContext {
+                    execution: &execution,
+                    policy,
+                    log_sink: log_sink.clone(),
+                    cancel_check: cancel_check.clone(),
+                },
+            };
+            if buildroot_incremental(image) {
+                messages.extend(run_buildroot_incremental(run_request)?);
+            } else if buildroot_expected_images_present(image, &output_dir) {
                 messages.push(format!(
                     "reused completed buildroot output at '{}'",
                     output_dir.display()
                 ));
                 reuse_details.push("buildroot-output".to_string());
             } else {
-                messages.extend(run_buildroot(BuildrootRunRequest {
-                    spec,
-                    image,
-                    buildroot_dir: &buildroot_dir,
-                    output_dir: &output_dir,
-                    command: ImageCommandContext {
-                        execution: &execution,
-                        policy,
-                        log_sink: log_sink.clone(),
-                        cancel_check: cancel_check.clone(),
-                    },
-                })?);
+                messages.extend(run_buildroot(run_request)?);
             }
             if image_feed_has_content(image) {
                 let feed_signature = build_image_feed_signature(spec, image)?;
@@ -165,7 +196,11 @@ impl ImageProvider for BuildrootImageProvider {
                     ));
                     reuse_details.push("image-feed-overlay".to_string());
                 } else {
-                    apply_image_feed_to_rootfs(spec, image, &target_dir)?;
+                    messages.extend(apply_image_feed_to_rootfs(spec, image, &target_dir)?);
+                    write_stage_permission_table(
+                        &output_dir,
+                        &stage_permission_table(spec, image, &target_dir)?,
+                    )?;
                     messages.extend(refresh_buildroot_images_after_feed_overlay(
                         image,
                         &buildroot_dir,
@@ -210,6 +245,8 @@ impl ImageProvider for BuildrootImageProvider {
                 "matched_expected_images".to_string(),
                 matched_expected_images.join(","),
             ));
+            packages = buildroot_packages(&output_dir);
+            rootfs_dir = Some(target_dir);
             messages.push(format!(
                 "buildroot image built using backend '{}' into '{}'",
                 buildroot_dir.display(),
@@ -218,7 +255,7 @@ impl ImageProvider for BuildrootImageProvider {
         } else if buildroot_allow_fallback(image) {
             let fallback_rootfs_dir = collect_dir.join("rootfs");
             let matched_expected_images =
-                materialize_fallback_rootfs(spec, image, &fallback_rootfs_dir)?;
+                materialize_fallback_rootfs(spec, image, &fallback_rootfs_dir, &mut messages)?;
             if let Some(archive_path) = &archive_path {
                 messages.extend(archive_directory(
                     &fallback_rootfs_dir,
@@ -239,6 +276,7 @@ impl ImageProvider for BuildrootImageProvider {
                 "matched_expected_images".to_string(),
                 matched_expected_images.join(","),
             ));
+            rootfs_dir = Some(fallback_rootfs_dir);
             messages.push(format!(
                 "buildroot backend unavailable; assembled fallback rootfs for defconfig '{}'",
                 defconfig
@@ -264,6 +302,8 @@ impl ImageProvider for BuildrootImageProvider {
                 details.extend(build_image_contract_state_details(image));
                 details
             },
+            rootfs_dir,
+            packages,
         };
         materialize_image_output(&result)?;
         Ok(result)
@@ -312,7 +352,16 @@ impl ImageProvider for BuildrootImageProvider {
                         output_dir.display()
                     )]
                 } else {
-                    run_buildroot(BuildrootRunRequest {
+                    // A declared source runs the full make here, so prepare reports the progress.
+                    let log_sink = match request.progress_sink {
+                        Some(progress_sink) => Some(buildroot_progress_log_sink(
+                            &output_dir,
+                            request.log_sink,
+                            progress_sink,
+                        )),
+                        None => request.log_sink,
+                    };
+                    let run_request = BuildrootRunRequest {
                         spec: request.spec,
                         image: request.image,
                         buildroot_dir: &buildroot_dir,
@@ -320,10 +369,15 @@ impl ImageProvider for BuildrootImageProvider {
                         command: ImageCommandContext {
                             execution: &execution,
                             policy: request.policy,
-                            log_sink: request.log_sink,
+                            log_sink,
                             cancel_check: request.cancel_check,
                         },
-                    })?
+                    };
+                    if buildroot_incremental(request.image) {
+                        run_buildroot_incremental(run_request)?
+                    } else {
+                        run_buildroot(run_request)?
+                    }
                 };
                 let result = ImageExecutionResult {
                     provider_id: self.id().into(),
@@ -348,19 +402,74 @@ impl ImageProvider for BuildrootImageProvider {
                         details.extend(build_image_contract_state_details(request.image));
                         details
                     },
+                    rootfs_dir: None,
+                    packages: Vec::new(),
                 };
                 materialize_image_output(&result)?;
                 Ok(result)
             }
-            ImageProviderOperation::Build => self.execute_image(
-                request.spec,
-                request.image,
-                request.output,
-                request.policy,
-                request.log_sink,
-                request.cancel_check,
-            ),
+            ImageProviderOperation::Build => {
+                let log_sink = match request.progress_sink {
+                    Some(progress_sink) => {
+                        let output_dir = request
+                            .output
+                            .collect_dir
+                            .as_ref()
+                            .map(PathBuf::from)
+                            .unwrap_or_else(|| PathBuf::from("out/images/buildroot"))
+                            .join("buildroot-output");
+                        Some(buildroot_progress_log_sink(
+                            &output_dir,
+                            request.log_sink,
+                            progress_sink,
+                        ))
+                    }
+                    None => request.log_sink,
+                };
+                self.execute_image(
+                    request.spec,
+                    request.image,
+                    request.output,
+                    request.policy,
+                    log_sink,
+                    request.cancel_check,
+                )
+            }
+        }
+    }
+
+    fn planned_image_commands(
+        &self,
+        spec: &ResolvedBuildSpec,
+        image: &ImageSpec,
+        operation: ImageProviderOperation,
+        policy: &ImageExecutionPolicy,
+    ) -> Result<Vec<PlannedCommand>, ImageProviderError> {
+        // With a declared source the full make runs during prepare, and build only refreshes it.
+        if operation == ImageProviderOperation::Build && buildroot_source_id(image).is_some() {
+            return Ok(Vec::new());
         }
+        // Sources are not materialized in a dry run, so fall back to where the source would land.
+        let Some(buildroot_dir) = resolve_buildroot_dir(spec, image).or_else(|| {
+            buildroot_source_id(image).map(|source_id| buildroot_source_dir(spec, source_id))
+        }) else {
+            return Ok(Vec::new());
+        };
+        let output_dir = self
+            .plan_image(image)
+            .output
+            .collect_dir
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("out/images/buildroot"))
+            .join("buildroot-output");
+        let execution = execution_context(spec);
+        planned_buildroot_commands(spec, image, &buildroot_dir, &output_dir, policy.local_jobs)?
+            .into_iter()
+            .map(|(label, command)| {
+                command_for_execution(&command, &execution)
+                    .map(|command| PlannedCommand::from_command(label, &command))
+            })
+            .collect()
     }
 }
 
@@ -369,6 +478,10 @@ mod buildroot;
 mod command;
 mod feed;
 mod fs_util;
+mod incremental;
+mod packages;
+mod permissions;
+mod progress;
 mod squashfs;
 #[cfg(test)]
 mod tests;
@@ -378,4 +491,8 @@ pub(crate) use buildroot::*;
 pub(crate) use command::*;
 pub(crate) use feed::*;
 pub(crate) use fs_util::*;
+pub(crate) use incremental::*;
+pub(crate) use packages::*;
+pub(crate) use permissions::*;
+pub(crate) use progress::*;
 pub(crate) use squashfs::*;
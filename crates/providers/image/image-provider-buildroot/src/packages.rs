@@ -0,0 +1,66 @@
+use super::*;
+
+// Buildroot lists every file a target package installs in
+// `build/packages-file-list.txt` as `package,./path` lines. The list carries no
+// versions, so those come from the package build directories, which Buildroot
+// names `<package>-<version>` (or just `<package>` when unversioned). A
+// directory belongs to the longest package name it starts with, so
+// `linux-headers-6.1` is not read as version `headers-6.1` of `linux`.
+pub(crate) fn buildroot_packages(output_dir: &Path) -> Vec<ImagePackage> {
+    let build_dir = output_dir.join("build");
+    let Ok(file_list) = fs::read_to_string(build_dir.join("packages-file-list.txt")) else {
+        return Vec::new();
+    };
+    let mut package_files = BTreeMap::<String, BTreeSet<String>>::new();
+    for line in file_list.lines() {
+        let Some((package, path)) = line.split_once(',') else {
+            continue;
+        };
+        let path = path.trim_start_matches("./");
+        if package.is_empty() || path.is_empty() {
+            continue;
+        }
+        package_files
+            .entry(package.to_string())
+            .or_default()
+            .insert(path.to_string());
+    }
+
+    let mut versions = BTreeMap::<&str, String>::new();
+    for dir_name in package_build_dir_names(&build_dir) {
+        let owner = package_files
+            .keys()
+            .filter(|package| {
+                dir_name == package.as_str()
+                    || dir_name
+                        .strip_prefix(package.as_str())
+                        .is_some_and(|rest| rest.starts_with('-'))
+            })
+            .max_by_key(|package| package.len());
+        if let Some(package) = owner
+            && let Some(version) = dir_name.strip_prefix(&format!("{package}-"))
+        {
+            versions.insert(package, version.to_string());
+        }
+    }
+
+    package_files
+        .iter()
+        .map(|(name, files)| ImagePackage {
+            name: name.clone(),
+            version: versions.get(name.as_str()).cloned(),
+            files: files.iter().cloned().collect(),
+        })
+        .collect()
+}
+
+fn package_build_dir_names(build_dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(build_dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect()
+}
@@ -0,0 +1,182 @@
+use super::*;
+
+// Written next to `target/` after each feed. Buildroot's fakeroot step resets the whole target to
+// 0:0 before running `makedevs`, so ownership and capabilities set on `target/` itself never reach
+// the non-tar images; listing them in a device table is what makes `makedevs` reapply them.
+pub(crate) const STAGE_PERMISSION_TABLE: &str = "gaia_stage_permissions.txt";
+
+// One `makedevs` line per staged path of every fed stage file with `uid`, `gid`, or `caps`, keeping
+// the mode the path has in the target. An unset `uid` or `gid` stays 0, as Buildroot left it.
+// Symlinks are skipped since `makedevs` would follow them, and `caps` become an `|xattr` line under
+// the file they belong to.
+pub(crate) fn stage_permission_table(
+    spec: &ResolvedBuildSpec,
+    image: &ImageSpec,
+    rootfs_dir: &Path,
+) -> Result<String, ImageProviderError> {
+    let mut table = String::new();
+    for stage_file_id in &image.feed.stage_files {
+        let Some(stage_file) = spec
+            .stage
+            .files
+            .iter()
+            .find(|file| file.id == *stage_file_id)
+        else {
+            continue;
+        };
+        if stage_file.uid.is_none() && stage_file.gid.is_none() && stage_file.caps.is_none() {
+            continue;
+        }
+        let mut paths = Vec::new();
+        collect_table_paths(&rootfs_path(rootfs_dir, &stage_file.dest), &mut paths)?;
+        for (path, is_dir) in paths {
+            let relative = path.strip_prefix(rootfs_dir).unwrap_or(&path);
+            table.push_str(&format!(
+                "/{} {} {:o} {} {} - - - - -\n",
+                relative.display(),
+                if is_dir { 'd' } else { 'f' },
+                path_mode(&path)?,
+                stage_file.uid.unwrap_or(0),
+                stage_file.gid.unwrap_or(0)
+            ));
+            if let Some(caps) = &stage_file.caps
+                && !is_dir
+            {
+                table.push_str(&format!("|xattr {caps}\n"));
+            }
+        }
+    }
+    Ok(table)
+}
+
+// Replaces the table from the previous feed, or removes it when nothing needs one.
+pub(crate) fn write_stage_permission_table(
+    output_dir: &Path,
+    table: &str,
+) -> Result<(), ImageProviderError> {
+    let path = output_dir.join(STAGE_PERMISSION_TABLE);
+    let written = if table.is_empty() {
+        match fs::remove_file(&path) {
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            removed => removed,
+        }
+    } else {
+        fs::write(&path, table)
+    };
+    written.map_err(|error| {
+        ImageProviderError::new(
+            ImageProviderErrorKind::RuntimeState,
+            format!(
+                "failed to write stage permission table '{}': {error}",
+                path.display()
+            ),
+        )
+    })
+}
+
+pub(crate) fn stage_permission_table_path(output_dir: &Path) -> Option<PathBuf> {
+    Some(output_dir.join(STAGE_PERMISSION_TABLE)).filter(|path| path.is_file())
+}
+
+// `make` argument that adds the stage table to the tables the defconfig already lists, since a
+// command-line variable replaces the `.config` value rather than extending it.
+pub(crate) fn buildroot_device_table_arg(output_dir: &Path, table: &Path) -> String {
+    let configured = fs::read_to_string(output_dir.join(".config"))
+        .ok()
+        .and_then(|config| {
+            config.lines().find_map(|line| {
+                line.strip_prefix("BR2_ROOTFS_DEVICE_TABLE=")
+                    .map(|value| value.trim_matches('"').to_string())
+            })
+        })
+        .unwrap_or_default();
+    let tables = [configured, table.display().to_string()]
+        .into_iter()
+        .filter(|table| !table.trim().is_empty())
+        .collect::<Vec<_>>();
+    format!("BR2_ROOTFS_DEVICE_TABLE={}", tables.join(" "))
+}
+
+// The direct squashfs refresh runs Buildroot's own fakeroot script against a copy of its merged
+// device table, so the stage entries are appended to that copy instead.
+pub(crate) fn append_stage_permission_table(
+    devices_table: &Path,
+    table: &Path,
+) -> Result<(), ImageProviderError> {
+    let entries = fs::read_to_string(table).map_err(|error| {
+        ImageProviderError::new(
+            ImageProviderErrorKind::RuntimeState,
+            format!(
+                "failed to read stage permission table '{}': {error}",
+                table.display()
+            ),
+        )
+    })?;
+    let mut contents = fs::read_to_string(devices_table).unwrap_or_default();
+    if !contents.is_empty() && !contents.ends_with('\n') {
+        contents.push('\n');
+    }
+    contents.push_str(&entries);
+    fs::write(devices_table, contents).map_err(|error| {
+        ImageProviderError::new(
+            ImageProviderErrorKind::RuntimeState,
+            format!(
+                "failed to extend devices table '{}': {error}",
+                devices_table.display()
+            ),
+        )
+    })
+}
+
+fn collect_table_paths(
+    path: &Path,
+    paths: &mut Vec<(PathBuf, bool)>,
+) -> Result<(), ImageProviderError> {
+    let metadata = fs::symlink_metadata(path).map_err(|error| {
+        ImageProviderError::new(
+            ImageProviderErrorKind::RuntimeState,
+            format!("failed to stat staged path '{}': {error}", path.display()),
+        )
+    })?;
+    if metadata.file_type().is_symlink() {
+        return Ok(());
+    }
+    paths.push((path.to_path_buf(), metadata.is_dir()));
+    if !metadata.is_dir() {
+        return Ok(());
+    }
+    let mut children = fs::read_dir(path)
+        .and_then(|entries| {
+            entries
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(|error| {
+            ImageProviderError::new(
+                ImageProviderErrorKind::RuntimeState,
+                format!("failed to read staged dir '{}': {error}", path.display()),
+            )
+        })?;
+    children.sort();
+    for child in children {
+        collect_table_paths(&child, paths)?;
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn path_mode(path: &Path) -> Result<u32, ImageProviderError> {
+    fs::symlink_metadata(path)
+        .map(|metadata| metadata.permissions().mode() & 0o7777)
+        .map_err(|error| {
+            ImageProviderError::new(
+                ImageProviderErrorKind::RuntimeState,
+                format!("failed to stat staged path '{}': {error}", path.display()),
+            )
+        })
+}
+
+#[cfg(not(unix))]
+fn path_mode(path: &Path) -> Result<u32, ImageProviderError> {
+    Ok(if path.is_dir() { 0o755 } else { 0o644 })
+}
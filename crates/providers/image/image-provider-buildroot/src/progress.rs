@@ -0,0 +1,86 @@
+use super::*;
+use std::sync::{Arc, Mutex};
+
+// Buildroot does not report an overall package count while building, so the
+// estimate compares packages that reached their `Building` step against the
+// enabled `BR2_PACKAGE_*` symbols in the output `.config`. Sub-options count
+// as symbols too, so the estimate errs low and stays below 100 until make
+// exits.
+pub(crate) struct BuildrootProgress {
+    config_path: PathBuf,
+    total: Option<usize>,
+    built: BTreeSet<String>,
+    last_percent: Option<u8>,
+}
+
+impl BuildrootProgress {
+    pub(crate) fn new(output_dir: &Path) -> Self {
+        Self {
+            config_path: output_dir.join(".config"),
+            total: None,
+            built: BTreeSet::new(),
+            last_percent: None,
+        }
+    }
+
+    pub(crate) fn observe(&mut self, line: &str) -> Option<u8> {
+        let package = building_package(line)?;
+        if package.starts_with("host-") || !self.built.insert(package.to_string()) {
+            return None;
+        }
+        let config_path = &self.config_path;
+        let total = *self.total.get_or_insert_with(|| {
+            fs::read_to_string(config_path)
+                .map(|config| enabled_package_count(&config))
+                .unwrap_or(0)
+        });
+        if total == 0 {
+            return None;
+        }
+        let percent = ((self.built.len() * 100) / total).min(99) as u8;
+        if self.last_percent == Some(percent) {
+            return None;
+        }
+        self.last_percent = Some(percent);
+        Some(percent)
+    }
+}
+
+pub(crate) fn enabled_package_count(config: &str) -> usize {
+    config
+        .lines()
+        .filter_map(|line| line.trim().strip_suffix("=y"))
+        .filter(|symbol| symbol.starts_with("BR2_PACKAGE_") && !symbol.contains("_HOST_"))
+        .count()
+}
+
+fn building_package(line: &str) -> Option<&str> {
+    let start = line.find(">>> ")?;
+    let mut parts = line[start + 4..].split_whitespace();
+    let package = parts.next()?;
+    parts.next()?;
+    parts
+        .next()
+        .is_some_and(|step| step.starts_with("Building"))
+        .then_some(package)
+}
+
+pub(crate) fn buildroot_progress_log_sink(
+    output_dir: &Path,
+    log_sink: Option<ProcessLogSink>,
+    progress_sink: ProcessProgressSink,
+) -> ProcessLogSink {
+    let progress = Mutex::new(BuildrootProgress::new(output_dir));
+    Arc::new(move |line: ProcessLogLine| {
+        let percent = progress
+            .lock()
+            .ok()
+            .and_then(|mut progress| progress.observe(&line.line));
+        if let Some(percent) = percent {
+            progress_sink(percent);
+        }
+        if let Some(log_sink) = &log_sink {
+            log_sink(line);
+        }
+    })
+}
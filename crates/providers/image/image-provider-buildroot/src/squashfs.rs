@@ -38,6 +38,9 @@ pub(crate) fn refresh_buildroot_images_after_feed_overlay(
     if let Some(external_tree) = buildroot.external_tree.as_deref() {
         command.env("BR2_EXTERNAL", external_tree);
     }
+    if let Some(table) = stage_permission_table_path(output_dir) {
+        command.arg(buildroot_device_table_arg(output_dir, &table));
+    }
     run_command(
         command,
         "buildroot target-post-image refresh",
@@ -66,10 +69,13 @@ pub(crate) fn refresh_buildroot_squashfs_images_direct(
     let host_dir = output_dir.join("host");
     let fakeroot_bin = host_dir.join("bin/fakeroot");
 
+    let stage_table = stage_permission_table_path(output_dir);
+    // Without a merged device table to extend, stage ownership needs the `make` refresh instead.
     if !fakeroot_script.is_file()
         || !fakeroot_bin.is_file()
         || !source_target_dir.is_dir()
         || !host_dir.join("bin/mksquashfs").is_file()
+        || (stage_table.is_some() && !devices_table.is_file())
     {
         return Ok(None);
     }
@@ -96,6 +102,9 @@ pub(crate) fn refresh_buildroot_squashfs_images_direct(
         &working_devices_table,
         &working_target_dir,
     )?;
+    if let Some(table) = &stage_table {
+        append_stage_permission_table(&working_devices_table, table)?;
+    }
     materialize_fakeroot_script_for_target(
         &fakeroot_script,
         &working_fakeroot_script,
@@ -106,6 +106,7 @@ fn command_for_execution_mounts_parent_dir_for_output_files() {
     let execution = ImageExecutionContext {
         workspace_root: workspace_root.clone(),
         docker_image: Some("docker.io/library/debian:stable-slim".to_string()),
+        environment: ProcessEnvironment::default(),
     };
     let mut command = Command::new("tar");
     command
@@ -200,6 +201,61 @@ fn refresh_buildroot_images_after_feed_overlay_runs_target_post_image_for_non_ta
     assert!(messages.is_empty());
 }
 
+#[test]
+fn refresh_buildroot_images_after_feed_overlay_adds_stage_permission_table() {
+    let buildroot_dir = temp_path("gaia-buildroot-post-image-table-dir");
+    let output_dir = temp_path("gaia-buildroot-post-image-table-out");
+    fs::create_dir_all(&buildroot_dir).expect("buildroot dir");
+    fs::create_dir_all(&output_dir).expect("output dir");
+    fs::write(
+        buildroot_dir.join("Makefile"),
+        "target-post-image:\n\t@mkdir -p $(O)/images\n\t@printf '%s' '$(BR2_ROOTFS_DEVICE_TABLE)' > $(O)/images/tables.txt\n",
+    )
+    .expect("makefile");
+    fs::write(
+        output_dir.join(".config"),
+        "BR2_ROOTFS_DEVICE_TABLE=\"system/device_table.txt\"\n",
+    )
+    .expect("config");
+    write_stage_permission_table(&output_dir, "/usr/bin/web f 755 0 33 - - - - -\n")
+        .expect("stage table");
+    let image = ImageSpec {
+        definition: ImageDefinition::Buildroot(BuildrootImageSpec {
+            expected_images: vec![BuildrootExpectedImageSpec {
+                name: "rootfs.ext4".into(),
+                format: BuildrootExpectedImageFormatSpec::Ext4,
+                required: true,
+            }],
+            ..BuildrootImageSpec::default()
+        }),
+        feed: gaia_spec::ImageFeedSpec::default(),
+        output: ImageOutputSpec {
+            collect_dir: None,
+            archive_name: None,
+            emit_report: true,
+        },
+    };
+
+    refresh_buildroot_images_after_feed_overlay(
+        &image,
+        &buildroot_dir,
+        &output_dir,
+        &test_execution(),
+        &ImageExecutionPolicy::default(),
+        None,
+        None,
+    )
+    .expect("target-post-image refresh should succeed");
+
+    assert_eq!(
+        fs::read_to_string(output_dir.join("images/tables.txt")).expect("tables"),
+        format!(
+            "system/device_table.txt {}",
+            output_dir.join(STAGE_PERMISSION_TABLE).display()
+        )
+    );
+}
+
 #[test]
 fn materialize_defconfig_support_files_copies_sibling_assets_into_output_dir() {
     let assets_dir = temp_path("gaia-buildroot-defconfig-assets");
@@ -288,6 +344,62 @@ fn apply_buildroot_config_fragments_merges_fragments_and_runs_olddefconfig() {
     );
 }
 
+#[test]
+fn buildroot_config_fragments_merge_in_order_with_later_overrides() {
+    let workspace_root = temp_path("gaia-buildroot-fragment-order");
+    fs::create_dir_all(&workspace_root).expect("workspace dir");
+    fs::write(
+        workspace_root.join("base.cfg"),
+        "# board defaults\nBR2_PACKAGE_BUSYBOX=y\nBR2_TARGET_GENERIC_HOSTNAME=\"base\"\n",
+    )
+    .expect("base fragment");
+    fs::write(
+        workspace_root.join("product.cfg"),
+        "\n# BR2_PACKAGE_BUSYBOX is not set\nBR2_PACKAGE_DROPBEAR=y\n",
+    )
+    .expect("product fragment");
+    fs::write(
+        workspace_root.join("broken.cfg"),
+        "BR2_OK=y\nnot a symbol\n",
+    )
+    .expect("broken");
+    let mut spec = ResolvedBuildSpec::new("buildroot-fragment-order");
+    spec.workspace.root_dir = workspace_root.display().to_string();
+
+    let symbols =
+        read_buildroot_config_fragments(&spec, &["base.cfg".into(), "product.cfg".into()])
+            .expect("fragments should parse");
+    assert_eq!(
+        symbols,
+        vec![
+            ("BR2_PACKAGE_BUSYBOX".to_string(), "n".to_string()),
+            (
+                "BR2_TARGET_GENERIC_HOSTNAME".to_string(),
+                "\"base\"".to_string()
+            ),
+            ("BR2_PACKAGE_DROPBEAR".to_string(), "y".to_string()),
+        ]
+    );
+    assert_eq!(
+        merge_buildroot_config_assignments(
+            "BR2_PACKAGE_BUSYBOX=y\nBR2_ARCH=\"x86_64\"\n",
+            &symbols
+        ),
+        "BR2_ARCH=\"x86_64\"\nBR2_PACKAGE_BUSYBOX=n\nBR2_TARGET_GENERIC_HOSTNAME=\"base\"\nBR2_PACKAGE_DROPBEAR=y\n"
+    );
+
+    let error = read_buildroot_config_fragments(&spec, &["broken.cfg".into()])
+        .expect_err("malformed fragment should fail");
+    assert!(error.message.contains("line 2"), "{}", error.message);
+    let missing = read_buildroot_config_fragments(&spec, &["missing.cfg".into()])
+        .expect_err("missing fragment should fail");
+    assert!(
+        missing.message.contains("missing.cfg"),
+        "{}",
+        missing.message
+    );
+}
+
 #[test]
 fn apply_buildroot_config_overrides_merges_overrides_and_runs_olddefconfig() {
     let buildroot_dir = temp_path("gaia-buildroot-override-buildroot");
@@ -438,3 +550,190 @@ fn refresh_buildroot_images_after_feed_overlay_reports_target_post_image_failure
     assert_eq!(error.kind, ImageProviderErrorKind::BackendCommand);
     assert!(error.message.contains("broken post-image"));
 }
+
+#[test]
+fn buildroot_progress_counts_target_packages_against_enabled_symbols() {
+    let output_dir = temp_path("gaia-buildroot-progress");
+    fs::create_dir_all(&output_dir).expect("output dir");
+    fs::write(
+        output_dir.join(".config"),
+        "BR2_PACKAGE_BUSYBOX=y\nBR2_PACKAGE_ZLIB=y\n# BR2_PACKAGE_OPENSSL is not set\nBR2_PACKAGE_HOST_PATCHELF=y\nBR2_PACKAGE_DROPBEAR=y\nBR2_PACKAGE_HTOP=y\n",
+    )
+    .expect("write config");
+    let mut progress = BuildrootProgress::new(&output_dir);
+
+    assert_eq!(progress.observe(">>> host-patchelf 0.18.0 Building"), None);
+    assert_eq!(progress.observe(">>> busybox 1.36.1 Extracting"), None);
+    assert_eq!(
+        progress.observe("\u{1b}[7m>>> busybox 1.36.1 Building\u{1b}[27m"),
+        Some(25)
+    );
+    assert_eq!(progress.observe(">>> busybox 1.36.1 Building"), None);
+    assert_eq!(progress.observe(">>> zlib 1.3.1 Building"), Some(50));
+    assert_eq!(progress.observe(">>> dropbear 2024.85 Building"), Some(75));
+    assert_eq!(progress.observe(">>> htop 3.3.0 Building"), Some(99));
+
+    let _ = fs::remove_dir_all(output_dir);
+}
+
+#[test]
+fn planned_buildroot_commands_mirror_the_make_sequence() {
+    let spec = ResolvedBuildSpec::new("buildroot-test");
+    let image = ImageSpec {
+        definition: ImageDefinition::Buildroot(BuildrootImageSpec {
+            defconfig: Some("qemu_x86_64_defconfig".into()),
+            external_tree: Some("/opt/br-external".into()),
+            config_overrides: vec![("BR2_CCACHE".into(), "y".into())],
+            ..BuildrootImageSpec::default()
+        }),
+        feed: gaia_spec::ImageFeedSpec::default(),
+        output: ImageOutputSpec::default(),
+    };
+
+    let commands = planned_buildroot_commands(
+        &spec,
+        &image,
+        Path::new("/src/buildroot"),
+        Path::new("/out/buildroot-output"),
+        4,
+    )
+    .expect("planned commands")
+    .into_iter()
+    .map(|(label, command)| PlannedCommand::from_command(label, &command))
+    .collect::<Vec<_>>();
+
+    let labels = commands
+        .iter()
+        .map(|command| command.label.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        labels,
+        [
+            "buildroot defconfig",
+            "buildroot olddefconfig",
+            "buildroot make"
+        ]
+    );
+    assert_eq!(
+        commands[0].render(),
+        "cd /src/buildroot && BR2_EXTERNAL=/opt/br-external make O=/out/buildroot-output qemu_x86_64_defconfig"
+    );
+    assert_eq!(commands[2].args.last().map(String::as_str), Some("-j4"));
+}
+
+#[test]
+fn run_buildroot_enables_ccache_symbols_and_exports_cache_dir() {
+    let mut spec = ResolvedBuildSpec::new("buildroot-ccache-test");
+    spec.workspace.build_dir = temp_path("gaia-buildroot-ccache-build")
+        .display()
+        .to_string();
+    let buildroot_dir = temp_path("gaia-buildroot-ccache-dir");
+    let output_dir = temp_path("gaia-buildroot-ccache-out");
+    fs::create_dir_all(&buildroot_dir).expect("buildroot dir");
+    fs::write(
+        buildroot_dir.join("Makefile"),
+        "all:\n\t@echo \"$$CCACHE_DIR\" > $(O)/ccache-env\ncache_defconfig:\n\t@echo BR2_CCACHE_DIR=\\\"/old\\\" > $(O)/.config\nolddefconfig:\n\t@true\n",
+    )
+    .expect("makefile");
+    let image = ImageSpec {
+        definition: ImageDefinition::Buildroot(BuildrootImageSpec {
+            defconfig: Some("cache_defconfig".into()),
+            ccache: true,
+            ..BuildrootImageSpec::default()
+        }),
+        feed: gaia_spec::ImageFeedSpec::default(),
+        output: ImageOutputSpec::default(),
+    };
+
+    let execution = test_execution();
+    let policy = ImageExecutionPolicy::default();
+    run_buildroot(BuildrootRunRequest {
+        spec: &spec,
+        image: &image,
+        buildroot_dir: &buildroot_dir,
+        output_dir: &output_dir,
+        command: test_command_context(&execution, &policy),
+    })
+    .expect("buildroot run with ccache");
+
+    let ccache_dir = Path::new(&spec.workspace.build_dir).join("buildroot-ccache");
+    assert!(ccache_dir.is_dir());
+    let config = fs::read_to_string(output_dir.join(".config")).expect("merged config");
+    assert_eq!(
+        config,
+        format!(
+            "BR2_CCACHE=y\nBR2_CCACHE_DIR=\"{}\"\n",
+            ccache_dir.display()
+        )
+    );
+    assert_eq!(
+        fs::read_to_string(output_dir.join("ccache-env"))
+            .expect("make env")
+            .trim(),
+        ccache_dir.display().to_string()
+    );
+
+    let _ = fs::remove_dir_all(buildroot_dir);
+    let _ = fs::remove_dir_all(output_dir);
+    let _ = fs::remove_dir_all(&spec.workspace.build_dir);
+}
+
+#[test]
+fn run_buildroot_incremental_skips_configure_until_config_inputs_change() {
+    let spec = ResolvedBuildSpec::new("buildroot-incremental-test");
+    let buildroot_dir = temp_path("gaia-buildroot-incremental-dir");
+    let output_dir = temp_path("gaia-buildroot-incremental-out");
+    fs::create_dir_all(&buildroot_dir).expect("buildroot dir");
+    fs::write(
+        buildroot_dir.join("Makefile"),
+        "all:\n\t@echo make >> $(O)/steps\nfirst_defconfig:\n\t@echo first > $(O)/.config\n\t@echo defconfig >> $(O)/steps\nsecond_defconfig:\n\t@echo second > $(O)/.config\n\t@echo defconfig >> $(O)/steps\n",
+    )
+    .expect("makefile");
+    let image_with = |defconfig: &str| ImageSpec {
+        definition: ImageDefinition::Buildroot(BuildrootImageSpec {
+            defconfig: Some(defconfig.into()),
+            incremental: true,
+            ..BuildrootImageSpec::default()
+        }),
+        feed: gaia_spec::ImageFeedSpec::default(),
+        output: ImageOutputSpec::default(),
+    };
+    let execution = test_execution();
+    let policy = ImageExecutionPolicy::default();
+    let run = |image: &ImageSpec| {
+        run_buildroot_incremental(BuildrootRunRequest {
+            spec: &spec,
+            image,
+            buildroot_dir: &buildroot_dir,
+            output_dir: &output_dir,
+            command: test_command_context(&execution, &policy),
+        })
+        .expect("incremental buildroot run")
+    };
+    let steps = || fs::read_to_string(output_dir.join("steps")).expect("steps");
+
+    let first = image_with("first_defconfig");
+    run(&first);
+    assert_eq!(steps(), "defconfig\nmake\n");
+
+    let messages = run(&first);
+    assert_eq!(steps(), "defconfig\nmake\nmake\n");
+    assert!(
+        messages[0].starts_with("incremental buildroot build reusing configured output"),
+        "{messages:?}"
+    );
+
+    let messages = run(&image_with("second_defconfig"));
+    assert_eq!(steps(), "defconfig\nmake\n");
+    assert!(
+        messages[0].starts_with("buildroot config changed; cleared incremental output"),
+        "{messages:?}"
+    );
+    assert_eq!(
+        fs::read_to_string(output_dir.join(".config")).expect("config"),
+        "second\n"
+    );
+
+    let _ = fs::remove_dir_all(buildroot_dir);
+    let _ = fs::remove_dir_all(output_dir);
+}
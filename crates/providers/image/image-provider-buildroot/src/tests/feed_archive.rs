@@ -224,6 +224,8 @@ fn image_feed_has_content_is_true_when_stage_or_install_entries_exist() {
             stage_files: vec!["motd".into()],
             stage_env_sets: vec![],
             stage_services: vec![],
+            stage_overlays: vec![],
+            stage_symlinks: vec![],
         },
         output: ImageOutputSpec {
             collect_dir: None,
@@ -341,6 +343,8 @@ fn apply_image_feed_to_rootfs_writes_install_file_env_and_service_content() {
             package: "smoke-app".into(),
             target_name: None,
             variant: gaia_spec::ArtifactVariantSpec::File,
+            linker: None,
+            sysroot: None,
         }),
         None,
         gaia_spec::ArtifactOutputSpec {
@@ -356,12 +360,13 @@ fn apply_image_feed_to_rootfs_writes_install_file_env_and_service_content() {
         owner: Some("root".into()),
         group: Some("root".into()),
     });
-    spec.stage.files.push(gaia_spec::StageFileSpec {
-        id: "motd".into(),
-        src: "assets/motd".into(),
-        dest: "/etc/motd".into(),
-        origin: gaia_spec::StageContentOriginSpec::StaticAsset,
-    });
+    spec.stage
+        .files
+        .push(gaia_spec::StageFileSpec::static_asset(
+            "motd",
+            "assets/motd",
+            "/etc/motd",
+        ));
     spec.stage.env_sets.push(gaia_spec::StageEnvSetSpec {
         id: "runtime-env".into(),
         name: "runtime".into(),
@@ -403,6 +408,297 @@ fn apply_image_feed_to_rootfs_writes_install_file_env_and_service_content() {
     );
 }
 
+#[cfg(unix)]
+#[test]
+fn apply_image_feed_to_rootfs_copies_stage_overlay_tree() {
+    let workspace_root = temp_path("gaia-buildroot-overlay-workspace");
+    let rootfs_dir = temp_path("gaia-buildroot-overlay-rootfs");
+    let overlay_dir = workspace_root.join("overlay");
+    fs::create_dir_all(overlay_dir.join("bin")).expect("overlay bin dir");
+    fs::create_dir_all(overlay_dir.join("var/empty")).expect("overlay empty dir");
+    fs::write(overlay_dir.join("bin/hello"), "#!/bin/sh\necho hi\n").expect("overlay script");
+    fs::set_permissions(
+        overlay_dir.join("bin/hello"),
+        fs::Permissions::from_mode(0o750),
+    )
+    .expect("overlay script mode");
+    std::os::unix::fs::symlink("hello", overlay_dir.join("bin/hi")).expect("overlay symlink");
+
+    let mut spec = ResolvedBuildSpec::new("buildroot-overlay-test");
+    spec.workspace.root_dir = workspace_root.display().to_string();
+    spec.stage.overlays.push(gaia_spec::StageOverlaySpec::new(
+        "tools",
+        "overlay",
+        "/opt/tools",
+    ));
+
+    let mut image = ImageSpec::new(ImageDefinition::Buildroot(BuildrootImageSpec::default()));
+    image.feed.stage_overlays.push("tools".into());
+    assert!(image_feed_has_content(&image));
+
+    apply_image_feed_to_rootfs(&spec, &image, &rootfs_dir).expect("overlay feed");
+
+    let script = rootfs_dir.join("opt/tools/bin/hello");
+    assert_eq!(
+        fs::read_to_string(&script).expect("overlay script"),
+        "#!/bin/sh\necho hi\n"
+    );
+    assert_eq!(
+        fs::metadata(&script)
+            .expect("script metadata")
+            .permissions()
+            .mode()
+            & 0o777,
+        0o750
+    );
+    assert_eq!(
+        fs::read_link(rootfs_dir.join("opt/tools/bin/hi")).expect("overlay symlink"),
+        PathBuf::from("hello")
+    );
+    assert!(rootfs_dir.join("opt/tools/var/empty").is_dir());
+
+    let _ = fs::remove_dir_all(workspace_root);
+    let _ = fs::remove_dir_all(rootfs_dir);
+}
+
+#[test]
+fn fast_copy_overlay_matches_a_plain_copy_on_any_filesystem() {
+    let workspace_root = temp_path("gaia-buildroot-fast-copy-workspace");
+    let rootfs_dir = temp_path("gaia-buildroot-fast-copy-rootfs");
+    let overlay_dir = workspace_root.join("overlay");
+    fs::create_dir_all(overlay_dir.join("lib")).expect("overlay lib dir");
+    fs::write(overlay_dir.join("lib/first.so"), "first").expect("first file");
+    fs::write(overlay_dir.join("lib/second.so"), "second").expect("second file");
+    fs::set_permissions(
+        overlay_dir.join("lib/second.so"),
+        fs::Permissions::from_mode(0o755),
+    )
+    .expect("second file mode");
+    fs::create_dir_all(rootfs_dir.join("opt/lib")).expect("existing rootfs dir");
+    fs::write(rootfs_dir.join("opt/lib/first.so"), "stale").expect("stale file");
+
+    let mut spec = ResolvedBuildSpec::new("buildroot-fast-copy-test");
+    spec.workspace.root_dir = workspace_root.display().to_string();
+    spec.stage.fast_copy = true;
+    spec.stage
+        .overlays
+        .push(gaia_spec::StageOverlaySpec::new("libs", "overlay", "/opt"));
+    let mut image = ImageSpec::new(ImageDefinition::Buildroot(BuildrootImageSpec::default()));
+    image.feed.stage_overlays.push("libs".into());
+
+    // Whether the temp filesystem clones or not, the staged tree must match the overlay.
+    apply_image_feed_to_rootfs(&spec, &image, &rootfs_dir).expect("fast-copy overlay feed");
+
+    assert_eq!(
+        fs::read_to_string(rootfs_dir.join("opt/lib/first.so")).expect("first staged"),
+        "first"
+    );
+    let second = rootfs_dir.join("opt/lib/second.so");
+    assert_eq!(
+        fs::read_to_string(&second).expect("second staged"),
+        "second"
+    );
+    assert_eq!(
+        fs::metadata(&second)
+            .expect("second metadata")
+            .permissions()
+            .mode()
+            & 0o777,
+        0o755
+    );
+
+    let _ = fs::remove_dir_all(workspace_root);
+    let _ = fs::remove_dir_all(rootfs_dir);
+}
+
+#[cfg(unix)]
+#[test]
+fn stage_overlay_merges_through_relative_directory_symlinks() {
+    let workspace_root = temp_path("gaia-buildroot-merged-usr-workspace");
+    let rootfs_dir = temp_path("gaia-buildroot-merged-usr-rootfs");
+    let overlay_dir = workspace_root.join("overlay");
+    fs::create_dir_all(overlay_dir.join("lib/firmware")).expect("overlay firmware dir");
+    fs::write(overlay_dir.join("lib/firmware/wifi.bin"), "fw").expect("overlay firmware");
+    fs::create_dir_all(rootfs_dir.join("usr/lib")).expect("rootfs usr/lib");
+    std::os::unix::fs::symlink("usr/lib", rootfs_dir.join("lib")).expect("merged-usr link");
+
+    let mut spec = ResolvedBuildSpec::new("buildroot-merged-usr-test");
+    spec.workspace.root_dir = workspace_root.display().to_string();
+    spec.stage
+        .overlays
+        .push(gaia_spec::StageOverlaySpec::new("firmware", "overlay", "/"));
+    let mut image = ImageSpec::new(ImageDefinition::Buildroot(BuildrootImageSpec::default()));
+    image.feed.stage_overlays.push("firmware".into());
+
+    apply_image_feed_to_rootfs(&spec, &image, &rootfs_dir).expect("overlay feed");
+
+    assert_eq!(
+        fs::read_link(rootfs_dir.join("lib")).expect("merged-usr link kept"),
+        PathBuf::from("usr/lib")
+    );
+    assert_eq!(
+        fs::read_to_string(rootfs_dir.join("usr/lib/firmware/wifi.bin")).expect("firmware"),
+        "fw"
+    );
+
+    let _ = fs::remove_dir_all(workspace_root);
+    let _ = fs::remove_dir_all(rootfs_dir);
+}
+
+#[cfg(unix)]
+#[test]
+fn stage_overlay_fails_on_file_and_directory_clashes() {
+    let workspace_root = temp_path("gaia-buildroot-overlay-clash-workspace");
+    let rootfs_dir = temp_path("gaia-buildroot-overlay-clash-rootfs");
+    fs::create_dir_all(workspace_root.join("files/etc")).expect("file overlay dir");
+    fs::write(workspace_root.join("files/etc/app"), "config").expect("overlay file");
+    fs::create_dir_all(workspace_root.join("dirs/etc/motd")).expect("dir overlay dir");
+    fs::create_dir_all(rootfs_dir.join("etc/app")).expect("rootfs dir");
+    fs::write(rootfs_dir.join("etc/app/keep.conf"), "keep").expect("rootfs file in dir");
+    fs::write(rootfs_dir.join("etc/motd"), "hello").expect("rootfs file");
+
+    let mut spec = ResolvedBuildSpec::new("buildroot-overlay-clash-test");
+    spec.workspace.root_dir = workspace_root.display().to_string();
+    spec.stage
+        .overlays
+        .push(gaia_spec::StageOverlaySpec::new("files", "files", "/"));
+    spec.stage
+        .overlays
+        .push(gaia_spec::StageOverlaySpec::new("dirs", "dirs", "/"));
+
+    let mut image = ImageSpec::new(ImageDefinition::Buildroot(BuildrootImageSpec::default()));
+    image.feed.stage_overlays.push("files".into());
+    let error = apply_image_feed_to_rootfs(&spec, &image, &rootfs_dir)
+        .expect_err("file over a directory should fail");
+    assert!(
+        error.message.contains("would replace directory"),
+        "{}",
+        error.message
+    );
+    assert_eq!(
+        fs::read_to_string(rootfs_dir.join("etc/app/keep.conf")).expect("dir kept"),
+        "keep"
+    );
+
+    let mut image = ImageSpec::new(ImageDefinition::Buildroot(BuildrootImageSpec::default()));
+    image.feed.stage_overlays.push("dirs".into());
+    let error = apply_image_feed_to_rootfs(&spec, &image, &rootfs_dir)
+        .expect_err("directory over a file should fail");
+    assert!(
+        error.message.contains("would replace non-directory"),
+        "{}",
+        error.message
+    );
+    assert_eq!(
+        fs::read_to_string(rootfs_dir.join("etc/motd")).expect("file kept"),
+        "hello"
+    );
+
+    let _ = fs::remove_dir_all(workspace_root);
+    let _ = fs::remove_dir_all(rootfs_dir);
+}
+
+#[cfg(unix)]
+#[test]
+fn stage_permission_table_lists_stage_file_ownership_and_caps() {
+    let workspace_root = temp_path("gaia-buildroot-ownership-workspace");
+    let rootfs_dir = temp_path("gaia-buildroot-ownership-rootfs");
+    let output_dir = temp_path("gaia-buildroot-ownership-output");
+    fs::create_dir_all(workspace_root.join("assets/conf.d")).expect("assets dir");
+    fs::write(workspace_root.join("assets/conf.d/app.conf"), "port=80").expect("stage file");
+    fs::write(workspace_root.join("assets/web"), "#!/bin/sh\n").expect("stage binary");
+    std::os::unix::fs::symlink("app.conf", workspace_root.join("assets/conf.d/current"))
+        .expect("stage symlink");
+
+    let mut spec = ResolvedBuildSpec::new("buildroot-ownership-test");
+    spec.workspace.root_dir = workspace_root.display().to_string();
+    let mut conf = gaia_spec::StageFileSpec::static_asset("conf", "assets/conf.d", "/etc/conf.d");
+    conf.uid = Some(1234);
+    conf.gid = Some(5678);
+    let mut web = gaia_spec::StageFileSpec::static_asset("web", "assets/web", "/usr/bin/web");
+    web.gid = Some(33);
+    web.caps = Some("cap_net_bind_service=+ep".into());
+    let plain = gaia_spec::StageFileSpec::static_asset("plain", "assets/web", "/usr/bin/plain");
+    spec.stage.files.extend([conf, web, plain]);
+    let mut image = ImageSpec::new(ImageDefinition::Buildroot(BuildrootImageSpec::default()));
+    image.feed.stage_files = vec!["conf".into(), "web".into(), "plain".into()];
+
+    apply_image_feed_to_rootfs(&spec, &image, &rootfs_dir).expect("feed");
+    for (path, mode) in [
+        ("etc/conf.d", 0o750),
+        ("etc/conf.d/app.conf", 0o640),
+        ("usr/bin/web", 0o755),
+    ] {
+        fs::set_permissions(rootfs_dir.join(path), fs::Permissions::from_mode(mode))
+            .expect("staged mode");
+    }
+    let table = stage_permission_table(&spec, &image, &rootfs_dir).expect("permission table");
+
+    // Buildroot's fakeroot step resets the target to 0:0 and then applies these lines, so the
+    // image gets the ownership and caps whether or not Gaia could set them on the target itself.
+    assert_eq!(
+        table,
+        concat!(
+            "/etc/conf.d d 750 1234 5678 - - - - -\n",
+            "/etc/conf.d/app.conf f 640 1234 5678 - - - - -\n",
+            "/usr/bin/web f 755 0 33 - - - - -\n",
+            "|xattr cap_net_bind_service=+ep\n",
+        )
+    );
+
+    fs::create_dir_all(&output_dir).expect("output dir");
+    write_stage_permission_table(&output_dir, &table).expect("write table");
+    assert_eq!(
+        stage_permission_table_path(&output_dir),
+        Some(output_dir.join(STAGE_PERMISSION_TABLE))
+    );
+    write_stage_permission_table(&output_dir, "").expect("clear table");
+    assert_eq!(stage_permission_table_path(&output_dir), None);
+
+    let _ = fs::remove_dir_all(workspace_root);
+    let _ = fs::remove_dir_all(rootfs_dir);
+    let _ = fs::remove_dir_all(output_dir);
+}
+
+#[cfg(unix)]
+#[test]
+fn apply_image_feed_to_rootfs_creates_stage_symlinks_without_replacing_other_paths() {
+    let rootfs_dir = temp_path("gaia-buildroot-symlink-rootfs");
+    let mut spec = ResolvedBuildSpec::new("buildroot-symlink-test");
+    spec.stage.symlinks.push(gaia_spec::StageSymlinkSpec::new(
+        "busybox-sh",
+        "/bin/busybox",
+        "/usr/bin/sh",
+    ));
+
+    let mut image = ImageSpec::new(ImageDefinition::Buildroot(BuildrootImageSpec::default()));
+    image.feed.stage_symlinks.push("busybox-sh".into());
+    assert!(image_feed_has_content(&image));
+
+    apply_image_feed_to_rootfs(&spec, &image, &rootfs_dir).expect("symlink feed");
+    assert_eq!(
+        fs::read_link(rootfs_dir.join("usr/bin/sh")).expect("stage symlink"),
+        PathBuf::from("/bin/busybox")
+    );
+
+    // A rebuild feeds the same persistent target again; the link it left behind is kept.
+    apply_image_feed_to_rootfs(&spec, &image, &rootfs_dir).expect("repeated symlink feed");
+
+    fs::remove_file(rootfs_dir.join("usr/bin/sh")).expect("remove stage symlink");
+    std::os::unix::fs::symlink("/bin/bash", rootfs_dir.join("usr/bin/sh")).expect("other link");
+    let error = apply_image_feed_to_rootfs(&spec, &image, &rootfs_dir)
+        .expect_err("link with another target should be rejected");
+    assert!(error.message.contains("would replace an existing path"));
+
+    fs::remove_file(rootfs_dir.join("usr/bin/sh")).expect("remove other link");
+    fs::write(rootfs_dir.join("usr/bin/sh"), "#!/bin/sh\n").expect("regular file");
+    let error = apply_image_feed_to_rootfs(&spec, &image, &rootfs_dir)
+        .expect_err("regular file should be rejected");
+    assert!(error.message.contains("would replace an existing path"));
+
+    let _ = fs::remove_dir_all(rootfs_dir);
+}
+
 #[test]
 fn final_tar_image_contains_install_stage_env_and_service_content() {
     let workspace_root = temp_path("gaia-buildroot-final-image-workspace");
@@ -430,6 +726,8 @@ fn final_tar_image_contains_install_stage_env_and_service_content() {
             package: "smoke-app".into(),
             target_name: None,
             variant: gaia_spec::ArtifactVariantSpec::File,
+            linker: None,
+            sysroot: None,
         }),
         None,
         gaia_spec::ArtifactOutputSpec {
@@ -445,12 +743,13 @@ fn final_tar_image_contains_install_stage_env_and_service_content() {
         owner: Some("root".into()),
         group: Some("root".into()),
     });
-    spec.stage.files.push(gaia_spec::StageFileSpec {
-        id: "motd".into(),
-        src: "assets/motd".into(),
-        dest: "/etc/motd".into(),
-        origin: gaia_spec::StageContentOriginSpec::StaticAsset,
-    });
+    spec.stage
+        .files
+        .push(gaia_spec::StageFileSpec::static_asset(
+            "motd",
+            "assets/motd",
+            "/etc/motd",
+        ));
     spec.stage.env_sets.push(gaia_spec::StageEnvSetSpec {
         id: "runtime-env".into(),
         name: "runtime".into(),
@@ -560,6 +859,8 @@ fn apply_image_feed_to_rootfs_rejects_wrong_target_artifact_binary() {
             package: "smoke-app".into(),
             target_name: None,
             variant: gaia_spec::ArtifactVariantSpec::File,
+            linker: None,
+            sysroot: None,
         }),
         None,
         gaia_spec::ArtifactOutputSpec {
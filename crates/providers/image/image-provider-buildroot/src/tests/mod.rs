@@ -19,6 +19,7 @@ fn test_execution() -> ImageExecutionContext {
     ImageExecutionContext {
         workspace_root: std::env::temp_dir(),
         docker_image: None,
+        environment: ProcessEnvironment::default(),
     }
 }
 
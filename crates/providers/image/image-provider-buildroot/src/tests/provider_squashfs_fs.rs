@@ -10,6 +10,7 @@ fn command_for_execution_wraps_docker_backend() {
     let execution = ImageExecutionContext {
         workspace_root: workspace_root.clone(),
         docker_image: Some("docker.io/library/alpine:latest".to_string()),
+        environment: ProcessEnvironment::default(),
     };
     let mut command = Command::new("make");
     command
@@ -125,6 +126,7 @@ fn prepare_operation_reuses_existing_buildroot_output_tree() {
             },
             policy: &ImageExecutionPolicy::default(),
             log_sink: None,
+            progress_sink: None,
             cancel_check: None,
         })
         .expect("prepare should reuse existing target tree");
@@ -272,3 +274,41 @@ fn ensure_fakeroot_chown_paths_exist_creates_missing_directories() {
 
     assert!(staged_target_dir.join("var/empty").is_dir());
 }
+
+#[test]
+fn buildroot_packages_reads_file_list_and_versions_from_build_dirs() {
+    let output_dir = temp_path("gaia-buildroot-packages");
+    let build_dir = output_dir.join("build");
+    for dir in [
+        "busybox-1.36.1",
+        "linux-6.6.30",
+        "linux-headers-6.6.30",
+        "skeleton-init-common",
+        "host-gcc-final-13.3.0",
+    ] {
+        fs::create_dir_all(build_dir.join(dir)).expect("package build dir");
+    }
+    fs::write(
+        build_dir.join("packages-file-list.txt"),
+        "busybox,./bin/busybox\nbusybox,./bin/sh\nlinux-headers,./usr/include/linux/types.h\nskeleton-init-common,./etc/hostname\nbusybox,./bin/busybox\n",
+    )
+    .expect("file list");
+
+    let packages = buildroot_packages(&output_dir);
+
+    assert_eq!(
+        packages
+            .iter()
+            .map(|package| (package.name.as_str(), package.version.as_deref()))
+            .collect::<Vec<_>>(),
+        vec![
+            ("busybox", Some("1.36.1")),
+            ("linux-headers", Some("6.6.30")),
+            ("skeleton-init-common", None),
+        ]
+    );
+    assert_eq!(packages[0].files, vec!["bin/busybox", "bin/sh"]);
+    assert!(buildroot_packages(&temp_path("gaia-buildroot-no-packages")).is_empty());
+
+    let _ = fs::remove_dir_all(output_dir);
+}
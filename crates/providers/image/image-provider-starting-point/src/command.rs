@@ -24,7 +24,8 @@ pub(crate) fn command_status(
         Err(ImageProviderError::new(
             error_kind,
             format!("{label} failed: {}", stderr_or_stdout(&output)),
-        ))
+        )
+        .with_exit(gaia_process::ProcessExit::from_status(&output.status)))
     }
 }
 
@@ -55,7 +56,8 @@ pub(crate) fn cleanup_command_status(
         Err(ImageProviderError::new(
             error_kind,
             format!("{label} failed: {}", stderr_or_stdout(&output)),
-        ))
+        )
+        .with_exit(gaia_process::ProcessExit::from_status(&output.status)))
     }
 }
 
@@ -180,6 +182,7 @@ pub(crate) fn run_command(
     let attempts = policy.retry_attempts.max(1);
     let timeout = Duration::from_secs(policy.timeout_seconds.max(1));
     let mut last_error = String::new();
+    let mut last_exit = None;
     let label = format!("starting-point archive build '{}'", archive_path.display());
     for attempt in 1..=attempts {
         tracing::debug!(
@@ -211,6 +214,11 @@ pub(crate) fn run_command(
             );
             return Ok(Vec::new());
         }
+        last_exit = if execution.docker_image.is_some() {
+            gaia_process::ProcessExit::from_container_status(&output.status)
+        } else {
+            gaia_process::ProcessExit::from_status(&output.status)
+        };
         last_error = format!(
             "starting-point archive build failed for '{}' on attempt {attempt}/{attempts}: {}",
             archive_path.display(),
@@ -257,7 +265,7 @@ pub(crate) fn run_command(
         attempts,
         "image provider command exhausted retries"
     );
-    Err(ImageProviderError::backend_command(last_error))
+    Err(ImageProviderError::backend_command(last_error).with_exit(last_exit))
 }
 
 fn execution_backend(execution: &ImageExecutionContext) -> &'static str {
@@ -315,6 +323,10 @@ pub(crate) fn command_output_with_timeout(
 
 pub(crate) fn execution_context(spec: &ResolvedBuildSpec) -> ImageExecutionContext {
     let workspace_root = PathBuf::from(&spec.workspace.root_dir);
+    let command_policy = spec
+        .policy
+        .providers
+        .image_command_policy(gaia_spec::ImageProviderKind::StartingPoint);
     ImageExecutionContext {
         workspace_root: fs::canonicalize(&workspace_root).unwrap_or(workspace_root),
         docker_image: spec
@@ -323,6 +335,11 @@ pub(crate) fn execution_context(spec: &ResolvedBuildSpec) -> ImageExecutionConte
             .docker
             .as_ref()
             .map(|docker| docker.image.clone()),
+        environment: ProcessEnvironment {
+            clean: spec.policy.execution.clean_env_for(command_policy),
+            allowlist: spec.policy.execution.env_allowlist.clone(),
+            ..ProcessEnvironment::default()
+        },
     }
 }
 
@@ -331,7 +348,9 @@ pub(crate) fn command_for_execution(
     execution: &ImageExecutionContext,
 ) -> Result<Command, ImageProviderError> {
     let Some(image) = &execution.docker_image else {
-        return Ok(gaia_process::clone_command(command));
+        let mut command = gaia_process::clone_command(command);
+        execution.environment.apply(&mut command);
+        return Ok(command);
     };
     if image.trim().is_empty() {
         return Err(ImageProviderError::new(
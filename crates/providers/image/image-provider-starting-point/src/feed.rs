@@ -4,7 +4,8 @@ pub(crate) fn apply_image_feed_to_rootfs(
     spec: &ResolvedBuildSpec,
     image: &ImageSpec,
     rootfs_dir: &Path,
-) -> Result<(), ImageProviderError> {
+) -> Result<Vec<String>, ImageProviderError> {
+    let mut messages = Vec::new();
     for install_id in &image.feed.install_entries {
         let install = spec
             .install
@@ -83,6 +84,7 @@ pub(crate) fn apply_image_feed_to_rootfs(
         let src = resolve_workspace_path(spec, &stage_file.src)?;
         let dest = rootfs_path(rootfs_dir, &stage_file.dest);
         copy_into_rootfs(&src, &dest)?;
+        messages.extend(apply_stage_file_attributes(stage_file, &dest)?);
     }
 
     for env_set_id in &image.feed.stage_env_sets {
@@ -154,7 +156,51 @@ pub(crate) fn apply_image_feed_to_rootfs(
         copy_into_rootfs(&src, &dest)?;
     }
 
-    Ok(())
+    for overlay_id in &image.feed.stage_overlays {
+        let overlay = spec
+            .stage
+            .overlays
+            .iter()
+            .find(|overlay| overlay.id == *overlay_id)
+            .ok_or_else(|| {
+                ImageProviderError::new(
+                    ImageProviderErrorKind::RuntimeState,
+                    format!(
+                        "image feed references unknown stage overlay '{}'",
+                        overlay_id.as_str()
+                    ),
+                )
+            })?;
+        let src = resolve_workspace_path(spec, &overlay.src)?;
+        apply_stage_overlay(
+            &src,
+            &rootfs_path(rootfs_dir, &overlay.dest),
+            spec.stage.fast_copy,
+        )?;
+    }
+
+    for symlink_id in &image.feed.stage_symlinks {
+        let symlink = spec
+            .stage
+            .symlinks
+            .iter()
+            .find(|symlink| symlink.id == *symlink_id)
+            .ok_or_else(|| {
+                ImageProviderError::new(
+                    ImageProviderErrorKind::RuntimeState,
+                    format!(
+                        "image feed references unknown stage symlink '{}'",
+                        symlink_id.as_str()
+                    ),
+                )
+            })?;
+        apply_stage_symlink(
+            symlink.target_path(),
+            &rootfs_path(rootfs_dir, &symlink.link),
+        )?;
+    }
+
+    Ok(messages)
 }
 
 pub(crate) fn rootfs_path(rootfs_dir: &Path, image_path: &str) -> PathBuf {
@@ -2,13 +2,15 @@ use gaia_image_providers::{
     ImageExecutionPolicy, ImageExecutionResult, ImageOutputContract, ImagePlan, ImageProvider,
     ImageProviderError, ImageProviderErrorKind, ImageProviderOperation,
     ImageProviderValidationIssue, ProcessCancelCheck, ProcessLogSink, ProcessOutputRetention,
+    apply_stage_file_attributes, apply_stage_overlay, apply_stage_symlink,
     build_image_contract_state_details, build_state_details, dir_digest,
     file_sha256_or_placeholder, materialize_image_output,
 };
 use gaia_process::{
-    DockerRunSpec, ProcessRetryBackoffStrategy, ProcessRunErrorKind, docker_run_command,
-    label_process_log_sink, retry_backoff_duration as process_retry_backoff_duration,
-    run_command_with_timeout, run_command_with_timeout_and_retention, sleep_with_cancel,
+    DockerRunSpec, ProcessEnvironment, ProcessRetryBackoffStrategy, ProcessRunErrorKind,
+    docker_run_command, label_process_log_sink,
+    retry_backoff_duration as process_retry_backoff_duration, run_command_with_timeout,
+    run_command_with_timeout_and_retention, sleep_with_cancel,
 };
 use gaia_spec::{
     ImageDefinition, ImageSpec, ResolvedBuildSpec, RetryBackoffStrategySpec, SourceId,
@@ -30,6 +32,7 @@ pub struct StartingPointImageProvider;
 struct ImageExecutionContext {
     workspace_root: PathBuf,
     docker_image: Option<String>,
+    environment: ProcessEnvironment,
 }
 
 impl ImageProvider for StartingPointImageProvider {
@@ -37,6 +40,10 @@ impl ImageProvider for StartingPointImageProvider {
         "image.starting-point"
     }
 
+    fn describe(&self) -> &'static str {
+        "Applies the image feed to an existing rootfs or image."
+    }
+
     fn kind(&self) -> gaia_spec::ImageProviderKind {
         gaia_spec::ImageProviderKind::StartingPoint
     }
@@ -45,6 +52,22 @@ impl ImageProvider for StartingPointImageProvider {
         true
     }
 
+    fn host_tools(&self, spec: &ResolvedBuildSpec) -> Vec<&'static str> {
+        if execution_context(spec).docker_image.is_some() {
+            return vec!["docker"];
+        }
+        let mut tools = vec!["tar"];
+        if let ImageDefinition::StartingPoint(image) = &spec.image.definition {
+            if image.image_partition.is_some() {
+                tools.extend(["losetup", "partx", "mount", "umount"]);
+            }
+            if image.packages.enabled && image.packages.execute {
+                tools.push("chroot");
+            }
+        }
+        tools
+    }
+
     fn plan_image(&self, image: &ImageSpec) -> ImagePlan {
         let output = ImageOutputContract {
             collect_dir: image.output.collect_dir.clone(),
@@ -144,6 +167,7 @@ impl ImageProvider for StartingPointImageProvider {
             ("rootfs_source".to_string(), rootfs_source),
         ];
         let mut messages = Vec::new();
+        let mut rootfs_dir = None;
         validate_rootfs(&rootfs, validation_mode)?;
         if rootfs.exists() {
             if looks_like_raw_image(&rootfs) {
@@ -197,6 +221,7 @@ impl ImageProvider for StartingPointImageProvider {
                     )?);
                 }
                 state_details.push(("rootfs_digest".to_string(), dir_digest(&mutable_rootfs)));
+                rootfs_dir = Some(mutable_rootfs);
             }
         }
 
@@ -220,6 +245,8 @@ impl ImageProvider for StartingPointImageProvider {
                 details.extend(build_image_contract_state_details(image));
                 details
             },
+            rootfs_dir,
+            packages: Vec::new(),
         };
         materialize_image_output(&result)?;
         Ok(result)
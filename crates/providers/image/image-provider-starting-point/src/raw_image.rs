@@ -105,7 +105,7 @@ pub(crate) fn materialize_mutable_raw_image(
             log_sink.clone(),
             cancel_check.clone(),
         )?;
-        apply_image_feed_to_rootfs(spec, image, &mount_dir)?;
+        messages.extend(apply_image_feed_to_rootfs(spec, image, &mount_dir)?);
         if matches!(
             starting_point.output_mode,
             StartingPointOutputModeSpec::CopyRootfs | StartingPointOutputModeSpec::CopyAndArchive
@@ -126,6 +126,8 @@ pub(crate) fn image_feed_has_runtime_content(image: &ImageSpec) -> bool {
         || !image.feed.stage_files.is_empty()
         || !image.feed.stage_env_sets.is_empty()
         || !image.feed.stage_services.is_empty()
+        || !image.feed.stage_overlays.is_empty()
+        || !image.feed.stage_symlinks.is_empty()
 }
 
 pub(crate) struct RawImageRuntimeGuard<'a> {
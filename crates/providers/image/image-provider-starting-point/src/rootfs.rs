@@ -195,6 +195,6 @@ pub(crate) fn materialize_mutable_rootfs(
         cancel_check,
     )?;
     messages.extend(package_messages);
-    apply_image_feed_to_rootfs(spec, image, &dest)?;
+    messages.extend(apply_image_feed_to_rootfs(spec, image, &dest)?);
     Ok(dest)
 }
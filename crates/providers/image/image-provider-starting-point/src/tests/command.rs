@@ -5,6 +5,7 @@ fn run_command_reports_missing_tool() {
     let execution = ImageExecutionContext {
         workspace_root: std::env::temp_dir(),
         docker_image: None,
+        environment: ProcessEnvironment::default(),
     };
     let error = run_command(
         Command::new("gaia-missing-starting-point-tool"),
@@ -30,6 +31,7 @@ fn run_command_times_out_long_running_processes() {
     let execution = ImageExecutionContext {
         workspace_root: root.clone(),
         docker_image: None,
+        environment: ProcessEnvironment::default(),
     };
     let policy = ImageExecutionPolicy {
         timeout_seconds: 1,
@@ -65,6 +67,7 @@ fn command_for_execution_wraps_docker_backend() {
     let execution = ImageExecutionContext {
         workspace_root: workspace_root.clone(),
         docker_image: Some("docker.io/library/alpine:latest".to_string()),
+        environment: ProcessEnvironment::default(),
     };
     let mut command = Command::new("tar");
     command
@@ -95,6 +98,7 @@ fn command_for_execution_mounts_parent_dir_for_output_files() {
     let execution = ImageExecutionContext {
         workspace_root: workspace_root.clone(),
         docker_image: Some("docker.io/library/alpine:latest".to_string()),
+        environment: ProcessEnvironment::default(),
     };
     let mut command = Command::new("tar");
     command
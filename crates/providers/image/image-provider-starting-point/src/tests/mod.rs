@@ -2,8 +2,8 @@ use super::*;
 use gaia_spec::{
     ArtifactDefinition, ArtifactOutputSpec, ArtifactRef, ArtifactSpec, ArtifactVariantSpec,
     ImageDefinition, ImageOutputSpec, ImageSpec, InstallEntrySpec, RustArtifactSpec,
-    SourceDefinition, SourceRef, SourceSpec, StageContentOriginSpec, StageEnvSetSpec,
-    StageFileSpec, StageServiceSpec, StartingPointImageSpec,
+    SourceDefinition, SourceRef, SourceSpec, StageEnvSetSpec, StageFileSpec, StageServiceSpec,
+    StartingPointImageSpec,
 };
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
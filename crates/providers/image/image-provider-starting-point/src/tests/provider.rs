@@ -48,6 +48,8 @@ fn starting_point_tar_rootfs_accepts_image_feed_overlay() {
             stage_files: vec!["motd".into()],
             stage_env_sets: vec!["runtime-env".into()],
             stage_services: vec!["runtime-service".into()],
+            stage_overlays: Vec::new(),
+            stage_symlinks: Vec::new(),
         },
         output: ImageOutputSpec {
             collect_dir: Some(root.join("out/images").display().to_string()),
@@ -70,6 +72,8 @@ fn starting_point_tar_rootfs_accepts_image_feed_overlay() {
             package: "smoke-app".into(),
             target_name: None,
             variant: ArtifactVariantSpec::File,
+            linker: None,
+            sysroot: None,
         }),
         Some(SourceRef::new("base-rootfs")),
         ArtifactOutputSpec {
@@ -85,12 +89,11 @@ fn starting_point_tar_rootfs_accepts_image_feed_overlay() {
         owner: None,
         group: None,
     });
-    spec.stage.files.push(StageFileSpec {
-        id: "motd".into(),
-        src: motd.display().to_string(),
-        dest: "/etc/motd".into(),
-        origin: StageContentOriginSpec::StaticAsset,
-    });
+    spec.stage.files.push(StageFileSpec::static_asset(
+        "motd",
+        motd.display().to_string(),
+        "/etc/motd",
+    ));
     spec.stage.env_sets.push(StageEnvSetSpec {
         id: "runtime-env".into(),
         name: "runtime".into(),
@@ -164,6 +167,8 @@ fn starting_point_rejects_overlay_on_opaque_file_rootfs() {
             stage_files: Vec::new(),
             stage_env_sets: Vec::new(),
             stage_services: Vec::new(),
+            stage_overlays: Vec::new(),
+            stage_symlinks: Vec::new(),
         },
         output: ImageOutputSpec {
             collect_dir: Some(root.join("out/images").display().to_string()),
@@ -49,6 +49,8 @@ fn starting_point_raw_image_rejects_read_only_overlay_before_root_check() {
             stage_files: Vec::new(),
             stage_env_sets: Vec::new(),
             stage_services: Vec::new(),
+            stage_overlays: Vec::new(),
+            stage_symlinks: Vec::new(),
         },
         output: ImageOutputSpec {
             collect_dir: Some(root.join("out/images").display().to_string()),
@@ -62,6 +64,8 @@ fn starting_point_raw_image_rejects_read_only_overlay_before_root_check() {
             package: "smoke-app".into(),
             target_name: None,
             variant: ArtifactVariantSpec::File,
+            linker: None,
+            sysroot: None,
         }),
         None,
         ArtifactOutputSpec {
@@ -1,9 +1,18 @@
+mod overlay;
+mod ownership;
+
 pub use gaia_process::{
-    ProcessCancelCheck, ProcessLogLine, ProcessLogSink, ProcessOutputRetention,
+    PlannedCommand, ProcessCancelCheck, ProcessLogLine, ProcessLogSink, ProcessOutputRetention,
+    ProcessProgressSink,
 };
 use gaia_spec::{
     ImageDefinition, ImageProviderKind, ImageSpec, ResolvedBuildSpec, RetryBackoffStrategySpec,
 };
+pub use overlay::{
+    StageOverlayEntry, StageOverlayEntryKind, apply_stage_overlay, apply_stage_symlink,
+    stage_overlay_entries,
+};
+pub use ownership::apply_stage_file_attributes;
 use std::collections::hash_map::DefaultHasher;
 use std::fs;
 use std::hash::{Hash, Hasher};
@@ -13,10 +22,22 @@ use std::time::UNIX_EPOCH;
 
 pub trait ImageProvider: Send + Sync {
     fn id(&self) -> &'static str;
+    // One-line summary for `gaia providers`; defaults to the id.
+    fn describe(&self) -> &'static str {
+        self.id()
+    }
     fn kind(&self) -> ImageProviderKind;
     fn supports(&self, _spec: &ResolvedBuildSpec) -> bool {
         true
     }
+    // Host programs `gaia doctor` checks for when the build uses this provider.
+    fn host_tools(&self, _spec: &ResolvedBuildSpec) -> Vec<&'static str> {
+        Vec::new()
+    }
+    // Host programs some builds need; `gaia doctor` only warns when one is missing.
+    fn optional_host_tools(&self, _spec: &ResolvedBuildSpec) -> Vec<&'static str> {
+        Vec::new()
+    }
     fn plan_image(&self, _image: &ImageSpec) -> ImagePlan {
         ImagePlan {
             operations: vec![ImageProviderOperation::Build],
@@ -67,6 +88,18 @@ pub trait ImageProvider: Send + Sync {
             )),
         }
     }
+
+    // Commands `execute_image_operation` would start for a fresh output dir, resolved but not
+    // run, for `gaia run --dry-run`.
+    fn planned_image_commands(
+        &self,
+        _spec: &ResolvedBuildSpec,
+        _image: &ImageSpec,
+        _operation: ImageProviderOperation,
+        _policy: &ImageExecutionPolicy,
+    ) -> Result<Vec<PlannedCommand>, ImageProviderError> {
+        Ok(Vec::new())
+    }
 }
 
 pub struct ImageOperationExecution<'a> {
@@ -76,6 +109,7 @@ pub struct ImageOperationExecution<'a> {
     pub output: &'a ImageOutputContract,
     pub policy: &'a ImageExecutionPolicy,
     pub log_sink: Option<ProcessLogSink>,
+    pub progress_sink: Option<ProcessProgressSink>,
     pub cancel_check: Option<ProcessCancelCheck>,
 }
 
@@ -113,6 +147,8 @@ pub enum ImageProviderErrorKind {
 pub struct ImageProviderError {
     pub kind: ImageProviderErrorKind,
     pub message: String,
+    // How a failed command ended, so a signal such as an OOM kill is not lost in the message.
+    pub exit: Option<gaia_process::ProcessExit>,
 }
 
 impl ImageProviderError {
@@ -120,9 +156,14 @@ impl ImageProviderError {
         Self {
             kind,
             message: message.into(),
+            exit: None,
         }
     }
 
+    pub fn with_exit(self, exit: Option<gaia_process::ProcessExit>) -> Self {
+        Self { exit, ..self }
+    }
+
     pub fn backend_command(message: impl Into<String>) -> Self {
         Self::new(ImageProviderErrorKind::BackendCommand, message)
     }
@@ -184,6 +225,18 @@ pub struct ImageExecutionResult {
     pub reuse_details: Vec<String>,
     pub messages: Vec<String>,
     pub state_details: Vec<(String, String)>,
+    // The finished root filesystem tree, when the provider assembles one on disk.
+    pub rootfs_dir: Option<PathBuf>,
+    // Packages the provider knows were built into the image, for the SBOM report.
+    pub packages: Vec<ImagePackage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImagePackage {
+    pub name: String,
+    pub version: Option<String>,
+    // Paths relative to the rootfs that this package installed.
+    pub files: Vec<String>,
 }
 
 pub fn materialize_image_output(result: &ImageExecutionResult) -> Result<(), ImageProviderError> {
@@ -350,6 +403,14 @@ pub fn build_image_contract_state_details(image: &ImageSpec) -> Vec<(String, Str
             "feed_stage_services".to_string(),
             join_ids(image.feed.stage_services.iter().map(|id| id.as_str())),
         ),
+        (
+            "feed_stage_overlays".to_string(),
+            join_ids(image.feed.stage_overlays.iter().map(|id| id.as_str())),
+        ),
+        (
+            "feed_stage_symlinks".to_string(),
+            join_ids(image.feed.stage_symlinks.iter().map(|id| id.as_str())),
+        ),
     ];
 
     match &image.definition {
@@ -479,6 +540,11 @@ impl ImageProviderCatalog {
         self.providers.push(provider);
     }
 
+    // Registration order, which is also the order `find_for_kind` searches.
+    pub fn providers(&self) -> impl Iterator<Item = &dyn ImageProvider> {
+        self.providers.iter().map(Box::as_ref)
+    }
+
     pub fn find_for_kind(&self, kind: ImageProviderKind) -> Option<&dyn ImageProvider> {
         self.providers
             .iter()
@@ -555,6 +621,8 @@ mod tests {
             reuse_details: Vec::new(),
             messages: Vec::new(),
             state_details: Vec::new(),
+            rootfs_dir: None,
+            packages: Vec::new(),
         };
 
         let error = materialize_image_output(&result)
@@ -0,0 +1,354 @@
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use crate::{ImageProviderError, ImageProviderErrorKind};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StageOverlayEntryKind {
+    Directory,
+    File,
+    Symlink(PathBuf),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageOverlayEntry {
+    pub relative: PathBuf,
+    pub kind: StageOverlayEntryKind,
+}
+
+impl StageOverlayEntry {
+    // Directories merge into whatever already exists, so only files and links claim a destination.
+    pub fn claims_destination(&self) -> bool {
+        !matches!(self.kind, StageOverlayEntryKind::Directory)
+    }
+
+    pub fn image_path(&self, dest: &str) -> String {
+        format!(
+            "{}/{}",
+            dest.trim_end_matches('/'),
+            self.relative.to_string_lossy()
+        )
+    }
+}
+
+// Walks an overlay tree in sorted order without following symlinks. Relative symlinks that climb
+// above the overlay root are rejected; absolute targets are kept as rootfs-absolute paths.
+pub fn stage_overlay_entries(root: &Path) -> Result<Vec<StageOverlayEntry>, ImageProviderError> {
+    if !fs::symlink_metadata(root).is_ok_and(|metadata| metadata.is_dir()) {
+        return Err(overlay_error(format!(
+            "stage overlay source '{}' is not a directory",
+            root.display()
+        )));
+    }
+    let mut entries = Vec::new();
+    collect_entries(root, Path::new(""), &mut entries)?;
+    Ok(entries)
+}
+
+// Copies an overlay tree under `dest_dir`, preserving file and directory modes, recreating
+// symlinks verbatim, and creating empty directories. With `fast_copy`, files are cloned
+// copy-on-write until the filesystem first refuses a clone. Directories merge into existing
+// directories, including relative directory symlinks such as a merged-usr `/lib -> usr/lib`, while
+// files and symlinks replace only files and symlinks; any other clash fails.
+pub fn apply_stage_overlay(
+    root: &Path,
+    dest_dir: &Path,
+    fast_copy: bool,
+) -> Result<(), ImageProviderError> {
+    let entries = stage_overlay_entries(root)?;
+    let mut reflink = fast_copy;
+    create_dir(dest_dir)?;
+    for entry in &entries {
+        let src = root.join(&entry.relative);
+        let dest = dest_dir.join(&entry.relative);
+        let existing = existing_destination(&dest);
+        match &entry.kind {
+            StageOverlayEntryKind::Directory => match existing {
+                None => create_dir(&dest)?,
+                Some(ExistingDestination::Directory) => {}
+                Some(ExistingDestination::DirectorySymlink) => {}
+                Some(ExistingDestination::AbsoluteSymlink(target)) => {
+                    return Err(overlay_error(format!(
+                        "overlay dir '{}' would merge through absolute symlink '{}' -> '{}'",
+                        src.display(),
+                        dest.display(),
+                        target.display()
+                    )));
+                }
+                Some(ExistingDestination::Other) => {
+                    return Err(overlay_error(format!(
+                        "overlay dir '{}' would replace non-directory '{}'",
+                        src.display(),
+                        dest.display()
+                    )));
+                }
+            },
+            StageOverlayEntryKind::File | StageOverlayEntryKind::Symlink(_) => {
+                match existing {
+                    None => {}
+                    Some(ExistingDestination::AbsoluteSymlink(_) | ExistingDestination::Other) => {
+                        remove_existing(&dest)?
+                    }
+                    Some(
+                        ExistingDestination::Directory | ExistingDestination::DirectorySymlink,
+                    ) => {
+                        return Err(overlay_error(format!(
+                            "overlay entry '{}' would replace directory '{}'",
+                            src.display(),
+                            dest.display()
+                        )));
+                    }
+                }
+                match &entry.kind {
+                    StageOverlayEntryKind::Symlink(target) => create_symlink(target, &dest)?,
+                    _ => copy_overlay_file(&src, &dest, &mut reflink)?,
+                }
+            }
+        }
+    }
+    // Directory modes are applied last so read-only directories do not block their own contents.
+    #[cfg(unix)]
+    for entry in entries
+        .iter()
+        .rev()
+        .filter(|entry| entry.kind == StageOverlayEntryKind::Directory)
+    {
+        let src = root.join(&entry.relative);
+        let dest = dest_dir.join(&entry.relative);
+        let permissions = fs::metadata(&src)
+            .map_err(|error| {
+                overlay_error(format!(
+                    "failed to stat overlay dir '{}': {error}",
+                    src.display()
+                ))
+            })?
+            .permissions();
+        fs::set_permissions(&dest, permissions).map_err(|error| {
+            overlay_error(format!(
+                "failed to set overlay dir mode on '{}': {error}",
+                dest.display()
+            ))
+        })?;
+    }
+    Ok(())
+}
+
+// A refused clone is the capability probe: it turns reflinks off for the rest of the overlay and
+// the file falls back to a byte copy, so unsupported filesystems never fail the build.
+fn copy_overlay_file(
+    src: &Path,
+    dest: &Path,
+    reflink: &mut bool,
+) -> Result<(), ImageProviderError> {
+    if *reflink {
+        if reflink_file(src, dest).is_ok() {
+            return Ok(());
+        }
+        *reflink = false;
+        let _ = fs::remove_file(dest);
+    }
+    fs::copy(src, dest).map(|_| ()).map_err(|error| {
+        overlay_error(format!(
+            "failed to copy overlay file '{}' to '{}': {error}",
+            src.display(),
+            dest.display()
+        ))
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn reflink_file(src: &Path, dest: &Path) -> io::Result<()> {
+    use std::os::fd::AsRawFd;
+
+    let source = fs::File::open(src)?;
+    let permissions = source.metadata()?.permissions();
+    let target = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(dest)?;
+    // SAFETY: FICLONE takes the source descriptor as its argument and both files stay open for
+    // the duration of the call.
+    if unsafe { libc::ioctl(target.as_raw_fd(), libc::FICLONE, source.as_raw_fd()) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    fs::set_permissions(dest, permissions)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn reflink_file(_src: &Path, _dest: &Path) -> io::Result<()> {
+    Err(io::ErrorKind::Unsupported.into())
+}
+
+// Creates a single explicit symlink at `link`. Unlike overlay entries it never replaces an
+// existing path, so a collision with earlier staged content fails loudly instead. The same link
+// left by an earlier feed into a persistent rootfs (Buildroot's `output/target`) is kept as is.
+pub fn apply_stage_symlink(target: &Path, link: &Path) -> Result<(), ImageProviderError> {
+    if let Ok(metadata) = fs::symlink_metadata(link) {
+        if metadata.file_type().is_symlink()
+            && fs::read_link(link).is_ok_and(|existing| existing == target)
+        {
+            return Ok(());
+        }
+        return Err(overlay_error(format!(
+            "stage symlink '{}' -> '{}' would replace an existing path",
+            link.display(),
+            target.display()
+        )));
+    }
+    if let Some(parent) = link.parent() {
+        create_dir(parent)?;
+    }
+    create_symlink(target, link)
+}
+
+fn collect_entries(
+    root: &Path,
+    relative: &Path,
+    entries: &mut Vec<StageOverlayEntry>,
+) -> Result<(), ImageProviderError> {
+    let dir = root.join(relative);
+    let mut children = fs::read_dir(&dir)
+        .map_err(|error| {
+            overlay_error(format!(
+                "failed to read overlay dir '{}': {error}",
+                dir.display()
+            ))
+        })?
+        .map(|entry| entry.map(|entry| entry.file_name()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| {
+            overlay_error(format!(
+                "failed to read overlay entry under '{}': {error}",
+                dir.display()
+            ))
+        })?;
+    children.sort();
+    for name in children {
+        let child = relative.join(&name);
+        let path = root.join(&child);
+        let metadata = fs::symlink_metadata(&path).map_err(|error| {
+            overlay_error(format!(
+                "failed to stat overlay entry '{}': {error}",
+                path.display()
+            ))
+        })?;
+        if metadata.file_type().is_symlink() {
+            let target = fs::read_link(&path).map_err(|error| {
+                overlay_error(format!(
+                    "failed to read overlay symlink '{}': {error}",
+                    path.display()
+                ))
+            })?;
+            if symlink_escapes_root(&child, &target) {
+                return Err(overlay_error(format!(
+                    "overlay symlink '{}' -> '{}' escapes the overlay root '{}'",
+                    path.display(),
+                    target.display(),
+                    root.display()
+                )));
+            }
+            entries.push(StageOverlayEntry {
+                relative: child,
+                kind: StageOverlayEntryKind::Symlink(target),
+            });
+        } else if metadata.is_dir() {
+            entries.push(StageOverlayEntry {
+                relative: child.clone(),
+                kind: StageOverlayEntryKind::Directory,
+            });
+            collect_entries(root, &child, entries)?;
+        } else {
+            entries.push(StageOverlayEntry {
+                relative: child,
+                kind: StageOverlayEntryKind::File,
+            });
+        }
+    }
+    Ok(())
+}
+
+fn symlink_escapes_root(link: &Path, target: &Path) -> bool {
+    if target.is_absolute() {
+        return false;
+    }
+    let mut depth = link.components().count().saturating_sub(1);
+    for component in target.components() {
+        match component {
+            Component::ParentDir if depth == 0 => return true,
+            Component::ParentDir => depth -= 1,
+            Component::Normal(_) => depth += 1,
+            _ => {}
+        }
+    }
+    false
+}
+
+fn create_dir(path: &Path) -> Result<(), ImageProviderError> {
+    fs::create_dir_all(path).map_err(|error| {
+        overlay_error(format!(
+            "failed to create overlay dir '{}': {error}",
+            path.display()
+        ))
+    })
+}
+
+enum ExistingDestination {
+    Directory,
+    DirectorySymlink,
+    // Resolving an absolute target here would leave the rootfs for the host, so its type is
+    // unknown.
+    AbsoluteSymlink(PathBuf),
+    Other,
+}
+
+// A relative symlink counts as a directory when it resolves to one.
+fn existing_destination(path: &Path) -> Option<ExistingDestination> {
+    let metadata = fs::symlink_metadata(path).ok()?;
+    if metadata.file_type().is_symlink() {
+        let target = fs::read_link(path).ok()?;
+        return Some(if target.is_absolute() {
+            ExistingDestination::AbsoluteSymlink(target)
+        } else if fs::metadata(path).is_ok_and(|metadata| metadata.is_dir()) {
+            ExistingDestination::DirectorySymlink
+        } else {
+            ExistingDestination::Other
+        });
+    }
+    Some(if metadata.is_dir() {
+        ExistingDestination::Directory
+    } else {
+        ExistingDestination::Other
+    })
+}
+
+fn remove_existing(path: &Path) -> Result<(), ImageProviderError> {
+    fs::remove_file(path).map_err(|error| {
+        overlay_error(format!(
+            "failed to replace existing overlay destination '{}': {error}",
+            path.display()
+        ))
+    })
+}
+
+#[cfg(unix)]
+fn create_symlink(target: &Path, dest: &Path) -> Result<(), ImageProviderError> {
+    std::os::unix::fs::symlink(target, dest).map_err(|error| {
+        overlay_error(format!(
+            "failed to create overlay symlink '{}': {error}",
+            dest.display()
+        ))
+    })
+}
+
+#[cfg(not(unix))]
+fn create_symlink(_target: &Path, dest: &Path) -> Result<(), ImageProviderError> {
+    Err(overlay_error(format!(
+        "failed to create overlay symlink '{}': symlink copy is unsupported on this platform",
+        dest.display()
+    )))
+}
+
+fn overlay_error(message: String) -> ImageProviderError {
+    ImageProviderError::new(ImageProviderErrorKind::RuntimeState, message)
+}
@@ -0,0 +1,98 @@
+use std::path::Path;
+
+use gaia_spec::StageFileSpec;
+
+use crate::ImageProviderError;
+
+// Applies a stage file's `uid`, `gid`, and `caps` to its copy in the rootfs and returns a warning
+// for anything that had to be skipped. Both need root, so an unprivileged run skips them instead
+// of failing, and platforms without Unix ownership always skip them.
+pub fn apply_stage_file_attributes(
+    file: &StageFileSpec,
+    dest: &Path,
+) -> Result<Vec<String>, ImageProviderError> {
+    if file.uid.is_none() && file.gid.is_none() && file.caps.is_none() {
+        return Ok(Vec::new());
+    }
+    apply_attributes(file, dest)
+}
+
+#[cfg(unix)]
+fn apply_attributes(file: &StageFileSpec, dest: &Path) -> Result<Vec<String>, ImageProviderError> {
+    // SAFETY: geteuid has no preconditions and cannot fail.
+    if unsafe { libc::geteuid() } != 0 {
+        return Ok(vec![format!(
+            "WARN: skipped ownership and capabilities of stage file '{}': not running as root",
+            file.id.as_str()
+        )]);
+    }
+    // Ownership goes first because changing it clears any file capabilities.
+    if file.uid.is_some() || file.gid.is_some() {
+        chown_tree(dest, file.uid, file.gid)?;
+    }
+    if let Some(caps) = &file.caps {
+        set_capabilities(dest, caps)?;
+    }
+    Ok(Vec::new())
+}
+
+#[cfg(not(unix))]
+fn apply_attributes(file: &StageFileSpec, _dest: &Path) -> Result<Vec<String>, ImageProviderError> {
+    Ok(vec![format!(
+        "WARN: skipped ownership and capabilities of stage file '{}': only supported on Unix",
+        file.id.as_str()
+    )])
+}
+
+// A staged directory takes the ownership as a whole tree; symlinks are changed themselves rather
+// than followed, so nothing outside the rootfs is touched.
+#[cfg(unix)]
+fn chown_tree(path: &Path, uid: Option<u32>, gid: Option<u32>) -> Result<(), ImageProviderError> {
+    std::os::unix::fs::lchown(path, uid, gid).map_err(|error| {
+        attribute_error(format!(
+            "failed to change ownership of '{}': {error}",
+            path.display()
+        ))
+    })?;
+    let is_dir = std::fs::symlink_metadata(path).is_ok_and(|metadata| metadata.is_dir());
+    if !is_dir {
+        return Ok(());
+    }
+    let entries = std::fs::read_dir(path).map_err(|error| {
+        attribute_error(format!("failed to read '{}': {error}", path.display()))
+    })?;
+    for entry in entries {
+        let entry = entry.map_err(|error| {
+            attribute_error(format!("failed to read '{}': {error}", path.display()))
+        })?;
+        chown_tree(&entry.path(), uid, gid)?;
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn set_capabilities(path: &Path, caps: &str) -> Result<(), ImageProviderError> {
+    let output = std::process::Command::new("setcap")
+        .arg(caps)
+        .arg(path)
+        .output()
+        .map_err(|error| {
+            ImageProviderError::new(
+                crate::ImageProviderErrorKind::ToolStart,
+                format!("failed to start setcap: {error}"),
+            )
+        })?;
+    if output.status.success() {
+        return Ok(());
+    }
+    Err(attribute_error(format!(
+        "setcap '{caps}' failed on '{}': {}",
+        path.display(),
+        String::from_utf8_lossy(&output.stderr).trim()
+    )))
+}
+
+#[cfg(unix)]
+fn attribute_error(message: String) -> ImageProviderError {
+    ImageProviderError::new(crate::ImageProviderErrorKind::RuntimeState, message)
+}
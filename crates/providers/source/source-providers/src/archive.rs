@@ -5,10 +5,18 @@ impl SourceProvider for ArchiveSourceProvider {
         "source.archive"
     }
 
+    fn describe(&self) -> &'static str {
+        "Extracts a local tar archive into the source directory."
+    }
+
     fn kind(&self) -> SourceProviderKind {
         SourceProviderKind::Archive
     }
 
+    fn host_tools(&self, _spec: &ResolvedBuildSpec) -> Vec<&'static str> {
+        vec!["tar"]
+    }
+
     fn execute_source(
         &self,
         spec: &ResolvedBuildSpec,
@@ -24,7 +32,7 @@ impl SourceProvider for ArchiveSourceProvider {
         };
         let materialized_dir = materialized_dir(spec, source);
         prepare_materialized_dir(&materialized_dir)?;
-        let execution = execution_context(spec);
+        let execution = execution_context(spec, SourceProviderKind::Archive);
 
         extract_archive(
             archive,
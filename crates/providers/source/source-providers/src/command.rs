@@ -4,6 +4,7 @@ use super::*;
 pub(crate) struct SourceExecutionContext {
     pub(crate) workspace_root: PathBuf,
     pub(crate) docker: Option<SourceDockerExecution>,
+    pub(crate) environment: ProcessEnvironment,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -52,8 +53,12 @@ pub(crate) fn command_output_with_timeout(
     })
 }
 
-pub(crate) fn execution_context(spec: &ResolvedBuildSpec) -> SourceExecutionContext {
+pub(crate) fn execution_context(
+    spec: &ResolvedBuildSpec,
+    provider: SourceProviderKind,
+) -> SourceExecutionContext {
     let workspace_root = PathBuf::from(&spec.workspace.root_dir);
+    let command_policy = spec.policy.providers.source_command_policy(provider);
     SourceExecutionContext {
         workspace_root: fs::canonicalize(&workspace_root).unwrap_or(workspace_root),
         docker: spec
@@ -64,6 +69,11 @@ pub(crate) fn execution_context(spec: &ResolvedBuildSpec) -> SourceExecutionCont
             .map(|docker| SourceDockerExecution {
                 image: docker.image.clone(),
             }),
+        environment: ProcessEnvironment {
+            clean: spec.policy.execution.clean_env_for(command_policy),
+            allowlist: spec.policy.execution.env_allowlist.clone(),
+            ..ProcessEnvironment::default()
+        },
     }
 }
 
@@ -82,7 +92,9 @@ pub(crate) fn command_for_execution(
     execution: &SourceExecutionContext,
 ) -> Result<Command, SourceProviderError> {
     let Some(docker) = &execution.docker else {
-        return Ok(gaia_process::clone_command(command));
+        let mut command = gaia_process::clone_command(command);
+        execution.environment.apply(&mut command);
+        return Ok(command);
     };
     if docker.image.trim().is_empty() {
         return Err(SourceProviderError::new(
@@ -111,6 +123,7 @@ pub(crate) fn run_command_with_policy(
     let attempts = policy.attempts.max(1);
     let timeout = Duration::from_secs(policy.timeout_seconds.max(1));
     let mut last_error = String::new();
+    let mut last_exit = None;
     for attempt in 1..=attempts {
         tracing::debug!(
             command_label = description,
@@ -146,6 +159,11 @@ pub(crate) fn run_command_with_policy(
             );
             return Ok(());
         }
+        last_exit = if execution.docker.is_some() {
+            gaia_process::ProcessExit::from_container_status(&output.status)
+        } else {
+            gaia_process::ProcessExit::from_status(&output.status)
+        };
         last_error = format!(
             "{description} failed on attempt {attempt}/{attempts}: {}",
             String::from_utf8_lossy(&output.stderr).trim()
@@ -188,7 +206,7 @@ pub(crate) fn run_command_with_policy(
         attempts,
         "source provider command exhausted retries"
     );
-    Err(SourceProviderError::backend_command(last_error))
+    Err(SourceProviderError::backend_command(last_error).with_exit(last_exit))
 }
 
 fn execution_backend(execution: &SourceExecutionContext) -> &'static str {
@@ -35,10 +35,16 @@ pub(crate) fn sha256_or_placeholder(path: &Path) -> String {
         .to_string()
 }
 
+// Per-file sha256 runs spawn a process each, so large trees are hashed on a few worker threads.
+// Capped because beyond this the disk, not the hashing, is the bottleneck.
+const MAX_TREE_DIGEST_THREADS: usize = 8;
+
 pub(crate) fn tree_digest(path: &Path, ignored_names: &[&str]) -> String {
-    let mut hasher = DefaultHasher::new();
-    hash_tree(path, ignored_names, &mut hasher);
-    format!("{:016x}", hasher.finish())
+    let threads = thread::available_parallelism()
+        .map(usize::from)
+        .unwrap_or(1)
+        .clamp(1, MAX_TREE_DIGEST_THREADS);
+    tree_digest_with_threads(path, ignored_names, threads)
 }
 
 pub(crate) fn path_source_digest(path: &Path, identity_ignore: &[String]) -> String {
@@ -49,7 +55,59 @@ pub(crate) fn path_source_digest(path: &Path, identity_ignore: &[String]) -> Str
     tree_digest(path, &ignored)
 }
 
-pub(crate) fn hash_tree(path: &Path, ignored_names: &[&str], hasher: &mut DefaultHasher) {
+// The tree is walked first and the hasher fed afterwards in walk order, so the digest does not
+// depend on how many threads hashed the files.
+pub(crate) fn tree_digest_with_threads(
+    path: &Path,
+    ignored_names: &[&str],
+    threads: usize,
+) -> String {
+    let mut nodes = Vec::new();
+    walk_tree(path, ignored_names, &mut nodes);
+    let files = nodes
+        .iter()
+        .filter(|node| node.state.is_some_and(|state| state.is_file))
+        .map(|node| node.path.as_path())
+        .collect::<Vec<_>>();
+    let mut file_digests = sha256_files(&files, threads).into_iter();
+
+    let mut hasher = DefaultHasher::new();
+    for node in &nodes {
+        node.path.display().to_string().hash(&mut hasher);
+        let Some(state) = node.state else {
+            "missing".hash(&mut hasher);
+            continue;
+        };
+        state.is_dir.hash(&mut hasher);
+        state.is_file.hash(&mut hasher);
+        state.is_symlink.hash(&mut hasher);
+        state.len.hash(&mut hasher);
+        if state.is_file {
+            file_digests
+                .next()
+                .expect("one digest per walked file")
+                .hash(&mut hasher);
+        }
+    }
+    format!("{:016x}", hasher.finish())
+}
+
+struct TreeNode {
+    path: PathBuf,
+    // `None` when the path could not be read.
+    state: Option<TreeNodeState>,
+}
+
+#[derive(Clone, Copy)]
+struct TreeNodeState {
+    is_dir: bool,
+    is_file: bool,
+    is_symlink: bool,
+    len: u64,
+}
+
+// Depth-first with directory entries sorted, so the order matches a sorted recursive walk.
+fn walk_tree(path: &Path, ignored_names: &[&str], nodes: &mut Vec<TreeNode>) {
     let file_name = path
         .file_name()
         .and_then(|name| name.to_str())
@@ -57,18 +115,25 @@ pub(crate) fn hash_tree(path: &Path, ignored_names: &[&str], hasher: &mut Defaul
     if ignored_names.iter().any(|ignored| ignored == &file_name) {
         return;
     }
-    path.display().to_string().hash(hasher);
     let metadata = match fs::symlink_metadata(path) {
         Ok(metadata) => metadata,
         Err(_) => {
-            "missing".hash(hasher);
+            nodes.push(TreeNode {
+                path: path.to_path_buf(),
+                state: None,
+            });
             return;
         }
     };
-    metadata.is_dir().hash(hasher);
-    metadata.is_file().hash(hasher);
-    metadata.file_type().is_symlink().hash(hasher);
-    metadata.len().hash(hasher);
+    nodes.push(TreeNode {
+        path: path.to_path_buf(),
+        state: Some(TreeNodeState {
+            is_dir: metadata.is_dir(),
+            is_file: metadata.is_file(),
+            is_symlink: metadata.file_type().is_symlink(),
+            len: metadata.len(),
+        }),
+    });
     if metadata.is_dir() {
         let mut entries = match fs::read_dir(path) {
             Ok(entries) => entries
@@ -79,9 +144,35 @@ pub(crate) fn hash_tree(path: &Path, ignored_names: &[&str], hasher: &mut Defaul
         };
         entries.sort();
         for entry in entries {
-            hash_tree(&entry, ignored_names, hasher);
+            walk_tree(&entry, ignored_names, nodes);
         }
-    } else if metadata.is_file() {
-        sha256_or_placeholder(path).hash(hasher);
     }
 }
+
+// Digests come back in the order of `files`; each worker takes one contiguous chunk.
+fn sha256_files(files: &[&Path], threads: usize) -> Vec<String> {
+    if threads <= 1 || files.len() <= 1 {
+        return files
+            .iter()
+            .map(|path| sha256_or_placeholder(path))
+            .collect();
+    }
+    let chunk_size = files.len().div_ceil(threads);
+    thread::scope(|scope| {
+        let workers = files
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|path| sha256_or_placeholder(path))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("tree digest worker panicked"))
+            .collect()
+    })
+}
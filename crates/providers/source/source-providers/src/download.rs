@@ -5,10 +5,18 @@ impl SourceProvider for DownloadSourceProvider {
         "source.download"
     }
 
+    fn describe(&self) -> &'static str {
+        "Downloads a file with curl and checks its sha256 when one is set."
+    }
+
     fn kind(&self) -> SourceProviderKind {
         SourceProviderKind::Download
     }
 
+    fn host_tools(&self, _spec: &ResolvedBuildSpec) -> Vec<&'static str> {
+        vec!["curl", "sha256sum"]
+    }
+
     fn execute_source(
         &self,
         spec: &ResolvedBuildSpec,
@@ -24,7 +32,7 @@ impl SourceProvider for DownloadSourceProvider {
         };
         let materialized_dir = materialized_dir(spec, source);
         prepare_materialized_dir(&materialized_dir)?;
-        let execution = execution_context(spec);
+        let execution = execution_context(spec, SourceProviderKind::Download);
 
         let output_path = materialized_dir.join(&download.output_path);
         if let Some(parent) = output_path.parent() {
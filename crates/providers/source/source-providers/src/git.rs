@@ -5,10 +5,18 @@ impl SourceProvider for GitSourceProvider {
         "source.git"
     }
 
+    fn describe(&self) -> &'static str {
+        "Clones or fetches a git repository at the configured revision."
+    }
+
     fn kind(&self) -> SourceProviderKind {
         SourceProviderKind::Git
     }
 
+    fn host_tools(&self, _spec: &ResolvedBuildSpec) -> Vec<&'static str> {
+        vec!["git"]
+    }
+
     fn execute_source(
         &self,
         spec: &ResolvedBuildSpec,
@@ -24,7 +32,7 @@ impl SourceProvider for GitSourceProvider {
         };
         let materialized_dir = materialized_dir(spec, source);
         prepare_materialized_dir(&materialized_dir)?;
-        let execution = execution_context(spec);
+        let execution = execution_context(spec, SourceProviderKind::Git);
 
         let mut messages = Vec::new();
         let git_policy = SourceCommandPolicy {
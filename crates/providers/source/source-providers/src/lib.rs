@@ -1,6 +1,6 @@
 use gaia_process::{
-    DockerRunSpec, ProcessOutputRetention, ProcessRetryBackoffStrategy, ProcessRunErrorKind,
-    docker_run_command, label_process_log_sink,
+    DockerRunSpec, ProcessEnvironment, ProcessOutputRetention, ProcessRetryBackoffStrategy,
+    ProcessRunErrorKind, docker_run_command, label_process_log_sink,
     retry_backoff_duration as process_retry_backoff_duration,
     run_command_with_timeout_and_retention, sleep_with_cancel,
 };
@@ -19,14 +19,27 @@ use std::fs;
 use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Output};
+use std::thread;
 use std::time::Duration;
 
 pub trait SourceProvider: Send + Sync {
     fn id(&self) -> &'static str;
+    // One-line summary for `gaia providers`; defaults to the id.
+    fn describe(&self) -> &'static str {
+        self.id()
+    }
     fn kind(&self) -> SourceProviderKind;
     fn supports(&self, _spec: &ResolvedBuildSpec) -> bool {
         true
     }
+    // Host programs `gaia doctor` checks for when the build uses this provider.
+    fn host_tools(&self, _spec: &ResolvedBuildSpec) -> Vec<&'static str> {
+        Vec::new()
+    }
+    // Host programs some builds need; `gaia doctor` only warns when one is missing.
+    fn optional_host_tools(&self, _spec: &ResolvedBuildSpec) -> Vec<&'static str> {
+        Vec::new()
+    }
     fn plan_source(&self, _source: &SourceSpec) -> Vec<SourceProviderOperation> {
         vec![SourceProviderOperation::Materialize]
     }
@@ -78,6 +91,8 @@ pub enum SourceProviderErrorKind {
 pub struct SourceProviderError {
     pub kind: SourceProviderErrorKind,
     pub message: String,
+    // How a failed command ended, so a signal such as an OOM kill is not lost in the message.
+    pub exit: Option<gaia_process::ProcessExit>,
 }
 
 impl SourceProviderError {
@@ -85,9 +100,14 @@ impl SourceProviderError {
         Self {
             kind,
             message: message.into(),
+            exit: None,
         }
     }
 
+    pub fn with_exit(self, exit: Option<gaia_process::ProcessExit>) -> Self {
+        Self { exit, ..self }
+    }
+
     pub fn backend_command(message: impl Into<String>) -> Self {
         Self::new(SourceProviderErrorKind::BackendCommand, message)
     }
@@ -132,6 +152,11 @@ impl SourceProviderCatalog {
         catalog
     }
 
+    // Registration order, which is also the order `find_for_kind` searches.
+    pub fn providers(&self) -> impl Iterator<Item = &dyn SourceProvider> {
+        self.providers.iter().map(Box::as_ref)
+    }
+
     pub fn find_for_kind(&self, kind: SourceProviderKind) -> Option<&dyn SourceProvider> {
         self.providers
             .iter()
@@ -5,6 +5,10 @@ impl SourceProvider for PathSourceProvider {
         "source.path"
     }
 
+    fn describe(&self) -> &'static str {
+        "Uses an existing directory as the source."
+    }
+
     fn kind(&self) -> SourceProviderKind {
         SourceProviderKind::Path
     }
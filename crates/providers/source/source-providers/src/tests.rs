@@ -104,6 +104,7 @@ fn command_for_execution_mounts_parent_dir_for_output_files() {
         docker: Some(SourceDockerExecution {
             image: "docker.io/library/alpine:latest".to_string(),
         }),
+        environment: ProcessEnvironment::default(),
     };
     let mut command = Command::new("curl");
     command
@@ -323,6 +324,7 @@ fn run_command_reports_missing_tool() {
     let execution = SourceExecutionContext {
         workspace_root: std::env::temp_dir(),
         docker: None,
+        environment: ProcessEnvironment::default(),
     };
     let error = run_command_with_policy(
         Command::new("gaia-missing-source-tool"),
@@ -361,6 +363,7 @@ fn command_for_execution_wraps_docker_backend() {
         docker: Some(SourceDockerExecution {
             image: "docker.io/library/alpine:latest".to_string(),
         }),
+        environment: ProcessEnvironment::default(),
     };
     let mut command = Command::new("git");
     command
@@ -403,3 +406,132 @@ fn retry_backoff_duration_supports_fixed_and_exponential_strategies() {
         Duration::from_millis(40)
     );
 }
+
+fn write_digest_tree(root: &Path, dirs: usize, files_per_dir: usize) {
+    for dir in 0..dirs {
+        let dir_path = root.join(format!("dir-{dir:03}")).join("nested");
+        fs::create_dir_all(&dir_path).expect("digest tree dir");
+        for file in 0..files_per_dir {
+            fs::write(
+                dir_path.join(format!("file-{file:03}.txt")),
+                format!("contents {dir} {file}\n").repeat(file + 1),
+            )
+            .expect("digest tree file");
+        }
+    }
+}
+
+#[test]
+fn tree_digest_is_the_same_for_sequential_and_parallel_hashing() {
+    let root = temp_path("gaia-tree-digest-parallel");
+    write_digest_tree(&root, 6, 5);
+    fs::write(root.join("source.txt"), "ignored\n").expect("ignored file");
+    #[cfg(unix)]
+    std::os::unix::fs::symlink("dir-000", root.join("link")).expect("symlink");
+
+    let sequential = digest::tree_digest_with_threads(&root, &["source.txt"], 1);
+    for threads in [2, 3, 8, 64] {
+        assert_eq!(
+            digest::tree_digest_with_threads(&root, &["source.txt"], threads),
+            sequential,
+            "{threads} threads"
+        );
+    }
+    assert_eq!(digest::tree_digest(&root, &["source.txt"]), sequential);
+    assert_eq!(
+        sequential,
+        sequential_tree_digest_reference(&root, &["source.txt"])
+    );
+
+    fs::write(root.join("dir-003/nested/file-002.txt"), "changed\n").expect("edit file");
+    assert_ne!(
+        digest::tree_digest_with_threads(&root, &["source.txt"], 4),
+        sequential
+    );
+
+    let _ = fs::remove_dir_all(root);
+}
+
+// The recursive digest `tree_digest` used before files were hashed on worker threads, kept to
+// pin the walk and hashing order of the current one.
+fn sequential_tree_digest_reference(path: &Path, ignored_names: &[&str]) -> String {
+    fn hash_tree(path: &Path, ignored_names: &[&str], hasher: &mut DefaultHasher) {
+        let file_name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        if ignored_names.iter().any(|ignored| ignored == &file_name) {
+            return;
+        }
+        path.display().to_string().hash(hasher);
+        let metadata = match fs::symlink_metadata(path) {
+            Ok(metadata) => metadata,
+            Err(_) => {
+                "missing".hash(hasher);
+                return;
+            }
+        };
+        metadata.is_dir().hash(hasher);
+        metadata.is_file().hash(hasher);
+        metadata.file_type().is_symlink().hash(hasher);
+        metadata.len().hash(hasher);
+        if metadata.is_dir() {
+            let mut entries = match fs::read_dir(path) {
+                Ok(entries) => entries
+                    .filter_map(Result::ok)
+                    .map(|entry| entry.path())
+                    .collect::<Vec<_>>(),
+                Err(_) => return,
+            };
+            entries.sort();
+            for entry in entries {
+                hash_tree(&entry, ignored_names, hasher);
+            }
+        } else if metadata.is_file() {
+            digest::sha256_or_placeholder(path).hash(hasher);
+        }
+    }
+
+    let mut hasher = DefaultHasher::new();
+    hash_tree(path, ignored_names, &mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+// Directory sizes depend on the filesystem, so the golden value covers a single file at a fixed
+// path; it was computed with the digest from before files were hashed on worker threads.
+#[cfg(unix)]
+#[test]
+fn tree_digest_matches_the_golden_digest_of_a_file() {
+    const GOLDEN: &str = "0ecdfecfa740c4af";
+    let root = PathBuf::from("/tmp/gaia-tree-digest-golden");
+    fs::create_dir_all(&root).expect("golden dir");
+    let file = root.join("golden.txt");
+    fs::write(&file, "gaia golden digest\n").expect("golden file");
+
+    assert_eq!(sequential_tree_digest_reference(&file, &[]), GOLDEN);
+    assert_eq!(digest::tree_digest_with_threads(&file, &[], 1), GOLDEN);
+    assert_eq!(digest::tree_digest_with_threads(&file, &[], 4), GOLDEN);
+
+    let _ = fs::remove_dir_all(root);
+}
+
+// Timing comparison for large trees:
+// cargo test -p gaia-source-providers --lib tree_digest_benchmark -- --ignored --nocapture
+#[test]
+#[ignore = "benchmark"]
+fn tree_digest_benchmark() {
+    let root = temp_path("gaia-tree-digest-benchmark");
+    write_digest_tree(&root, 40, 25);
+
+    let started = std::time::Instant::now();
+    let sequential = digest::tree_digest_with_threads(&root, &[], 1);
+    let sequential_elapsed = started.elapsed();
+    let started = std::time::Instant::now();
+    let parallel = digest::tree_digest(&root, &[]);
+    let parallel_elapsed = started.elapsed();
+
+    assert_eq!(parallel, sequential);
+    println!("1000 files: sequential {sequential_elapsed:?}, parallel {parallel_elapsed:?}");
+
+    let _ = fs::remove_dir_all(root);
+}